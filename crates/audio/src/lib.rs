@@ -1,48 +1,353 @@
 //! Audio system using Kira for spatial sound.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use engine_core::Vec3;
 use kira::{
-    manager::{AudioManager, AudioManagerSettings, backend::DefaultBackend},
+    effect::{
+        filter::{FilterBuilder, FilterHandle},
+        reverb::{ReverbBuilder, ReverbHandle},
+    },
+    manager::{AudioManager, AudioManagerSettings, Capacities, backend::DefaultBackend},
     sound::static_sound::{StaticSoundData, StaticSoundHandle, StaticSoundSettings},
     spatial::{
         emitter::{EmitterHandle, EmitterSettings},
         listener::{ListenerHandle, ListenerSettings},
         scene::{SpatialSceneHandle, SpatialSceneSettings},
     },
+    track::{TrackBuilder, TrackHandle, TrackRoutes},
     tween::Tween,
+    Volume,
 };
+use rand::Rng;
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Stable identifier for a persistent spatial emitter owned by `AudioSystem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmitterId(u64);
+
+/// Identifier for an individually controllable playing sound (e.g. a loop started by
+/// `play_looping`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoundId(u64);
+
+/// Identifier for a reverb zone added with `add_reverb_zone`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReverbZoneId(u64);
+
+/// Mixer sub-track a sound plays on. Each bus has its own volume under the master track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioBus {
+    Music,
+    Sfx,
+    Voice,
+    Ambient,
+}
+
+impl AudioBus {
+    pub const ALL: [AudioBus; 4] = [AudioBus::Music, AudioBus::Sfx, AudioBus::Voice, AudioBus::Ambient];
+}
+
+/// How a space reverberates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverbPreset {
+    /// No reverb send at all.
+    Dry,
+    /// Open terrain: almost dry, with a faint long tail off distant ridges.
+    OpenField,
+    /// Narrow steel corridors (Roger Young interior): short, bright and enclosed.
+    SteelCorridor,
+    /// Large enclosed metal volume (hangar, drop bay): longer, still metallic.
+    Hangar,
+}
+
+impl ReverbPreset {
+    /// (send level 0–1, feedback, damping) for the shared reverb effect.
+    fn params(self) -> (f64, f64, f64) {
+        match self {
+            ReverbPreset::Dry => (0.0, 0.5, 0.5),
+            ReverbPreset::OpenField => (0.12, 0.93, 0.7),
+            ReverbPreset::SteelCorridor => (0.5, 0.78, 0.15),
+            ReverbPreset::Hangar => (0.4, 0.88, 0.3),
+        }
+    }
+}
+
+/// An axis-aligned box of space with its own reverb. Where zones overlap, the smallest one
+/// containing the listener wins, so a ship interior can sit inside a planet-wide zone.
+#[derive(Debug, Clone, Copy)]
+pub struct ReverbZone {
+    /// (min, max) corners.
+    pub bounds: (Vec3, Vec3),
+    pub preset: ReverbPreset,
+}
+
+impl ReverbZone {
+    /// Zone spanning two opposite corners (in any order).
+    pub fn new(a: Vec3, b: Vec3, preset: ReverbPreset) -> Self {
+        Self { bounds: (a.min(b), a.max(b)), preset }
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.bounds.0).all() && point.cmple(self.bounds.1).all()
+    }
+
+    fn volume(&self) -> f32 {
+        let size = self.bounds.1 - self.bounds.0;
+        size.x * size.y * size.z
+    }
+}
+
+/// Reverb change glide, so walking through a hatch doesn't switch the room abruptly.
+const REVERB_FADE_SECS: f32 = 0.6;
+
+/// A bus's mixer track plus the spatial scene whose listener feeds it. Kira routes spatial audio
+/// through the listener's track, so each bus needs its own scene for positional sounds. A second
+/// scene feeds a low-passed sub-track for positional one-shots heard through cover.
+struct Bus {
+    track: TrackHandle,
+    spatial_scene: SpatialSceneHandle,
+    listener: ListenerHandle,
+    muffled_scene: SpatialSceneHandle,
+    muffled_listener: ListenerHandle,
+    /// Held only to keep the muffled scene's filtered track alive.
+    _muffled_track: TrackHandle,
+    /// Linear 0–1 volume as set by the caller.
+    volume: f64,
+    /// Fraction of `volume` currently ducked away (0 = not ducked).
+    duck: f64,
+    /// When the current duck ends, and how long its recovery takes.
+    ducked_until: Option<Instant>,
+    duck_release: f32,
+}
+
+impl Bus {
+    /// Caller volume after ducking.
+    fn effective_volume(&self) -> f64 {
+        self.volume * (1.0 - self.duck)
+    }
+}
+
+/// Sidechain-style ducking: loud Sfx (gunfire, explosions) push Music and Ambient down for a
+/// moment so combat reads over the score.
+#[derive(Debug, Clone, Copy)]
+pub struct DuckingSettings {
+    /// Sfx sounds at or above this linear volume trigger a duck (after distance falloff for
+    /// positional sounds). Above 1.0 disables automatic ducking.
+    pub threshold: f64,
+    /// Fraction of the Music and Ambient bus volumes removed while ducked.
+    pub music_amount: f64,
+    pub ambient_amount: f64,
+    /// Seconds to reach the ducked level, to stay there after the last trigger, and to recover.
+    pub attack: f32,
+    pub hold: f32,
+    pub release: f32,
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.6,
+            music_amount: 0.5,
+            ambient_amount: 0.35,
+            attack: 0.05,
+            hold: 0.6,
+            release: 1.2,
+        }
+    }
+}
+
+/// Positional Sfx count as fully loud at the listener and silent at Kira's default emitter
+/// max distance, for the ducking threshold.
+const DUCK_FALLOFF_DISTANCE: f32 = 100.0;
+
+/// A persistent emitter. Kira applies effects per track, not per emitter, so each one gets its
+/// own spatial scene and listener feeding a sub-track (under its bus) that carries the occlusion
+/// low-pass and volume.
+struct Emitter {
+    handle: EmitterHandle,
+    bus: AudioBus,
+    position: Vec3,
+    /// Current occlusion, 0 (clear line of sight) to 1 (fully blocked).
+    occlusion: f32,
+    filter: FilterHandle,
+    track: TrackHandle,
+    listener: ListenerHandle,
+    /// Held only to keep the emitter's scene alive.
+    _scene: SpatialSceneHandle,
+}
+
+/// Line-of-sight test supplied by the game (physics raycast, terrain march, ship hull check), so
+/// audio never depends on the physics crate directly. Returns how blocked the straight path from
+/// `from` to `to` is: 0 = clear, 1 = fully occluded.
+pub trait OcclusionQuery {
+    fn occlusion(&self, from: Vec3, to: Vec3) -> f32;
+}
+
+impl<F: Fn(Vec3, Vec3) -> f32> OcclusionQuery for F {
+    fn occlusion(&self, from: Vec3, to: Vec3) -> f32 {
+        self(from, to)
+    }
+}
+
+/// Most persistent emitters alive at once; each uses its own spatial scene and sub-track.
+const MAX_PERSISTENT_EMITTERS: u16 = 64;
+
+/// Low-pass cutoff with a clear line of sight (effectively unfiltered) and when fully occluded.
+const OPEN_CUTOFF_HZ: f64 = 20_000.0;
+const OCCLUDED_CUTOFF_HZ: f64 = 600.0;
+/// Volume drop when fully occluded.
+const OCCLUDED_VOLUME_DB: f64 = -12.0;
+/// Occlusion changes glide over this long so a source crossing a ridge line doesn't pop.
+const OCCLUSION_FADE_SECS: f32 = 0.15;
+/// One-shots can't be re-filtered once playing, so they go through the bus's muffled scene
+/// from this much occlusion on.
+const ONE_SHOT_MUFFLE_THRESHOLD: f32 = 0.5;
+
+/// A fire-and-forget sound still playing, remembered so it can be cleaned up and capped.
+struct ActiveSound {
+    name: String,
+    handle: StaticSoundHandle,
+    /// Music is never culled to make room for new voices.
+    cullable: bool,
+}
+
+/// Master low-pass cutoff while the listener is underwater, and how long going in or out takes.
+const UNDERWATER_CUTOFF_HZ: f64 = 500.0;
+const UNDERWATER_FADE_SECS: f32 = 0.25;
+
+/// Fade used when culling the oldest voice to make room for a new one.
+const CULL_FADE_SECS: f32 = 0.02;
 
 /// Main audio system managing sounds and spatial audio.
 pub struct AudioSystem {
     manager: AudioManager,
-    spatial_scene: SpatialSceneHandle,
-    listener: ListenerHandle,
+    buses: HashMap<AudioBus, Bus>,
+    /// Linear 0–1 master volume.
+    master_volume: f64,
     sounds: HashMap<String, StaticSoundData>,
-    active_sounds: Vec<StaticSoundHandle>,
+    active_sounds: Vec<ActiveSound>,
+    /// Persistent emitters that follow moving sources (bugs, dropships).
+    emitters: HashMap<EmitterId, Emitter>,
+    next_emitter_id: u64,
+    /// Sounds the caller can address by `SoundId` (loops, engine hums, klaxons).
+    tracked_sounds: HashMap<SoundId, StaticSoundHandle>,
+    next_sound_id: u64,
+    /// When each sound name was last started through `play_with_variation`.
+    last_played: HashMap<String, Instant>,
+    /// Minimum time between two varied plays of the same sound.
+    min_repeat_interval: Duration,
+    /// Maximum simultaneous varied plays of the same sound.
+    max_voices_per_sound: usize,
+    /// Last listener position and orientation, for distance-based variation and occlusion rays.
+    listener_position: Vec3,
+    listener_orientation: glam::Quat,
+    /// Cap on simultaneous voices; the oldest one-shot is culled when a new sound would exceed it.
+    max_voices: usize,
+    /// Wet-only reverb track every non-music bus sends into.
+    reverb_track: TrackHandle,
+    reverb: ReverbHandle,
+    reverb_zones: Vec<(ReverbZoneId, ReverbZone)>,
+    next_reverb_zone_id: u64,
+    /// Zone forced by `set_listener_zone`, overriding the position lookup.
+    forced_reverb_zone: Option<ReverbZoneId>,
+    active_reverb: ReverbPreset,
+    ducking: DuckingSettings,
+    /// Low-pass on the main track; closes while the listener is underwater.
+    master_filter: FilterHandle,
+    underwater: bool,
 }
 
 impl AudioSystem {
     /// Create a new audio system.
     pub fn new() -> Result<Self> {
-        let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
-        
-        let mut spatial_scene = manager.add_spatial_scene(SpatialSceneSettings::default())?;
-        
-        let listener = spatial_scene.add_listener(
-            mint::Vector3 { x: 0.0, y: 0.0, z: 0.0 },
-            mint::Quaternion { v: mint::Vector3 { x: 0.0, y: 0.0, z: 0.0 }, s: 1.0 },
-            ListenerSettings::default(),
-        )?;
+        let bus_count = AudioBus::ALL.len() as u16;
+        let capacities = Capacities {
+            sub_track_capacity: bus_count * 2 + 1 + MAX_PERSISTENT_EMITTERS,
+            spatial_scene_capacity: bus_count * 2 + MAX_PERSISTENT_EMITTERS,
+            ..Default::default()
+        };
+        let mut main_track_builder = TrackBuilder::new();
+        let master_filter = main_track_builder.add_effect(FilterBuilder::new().cutoff(OPEN_CUTOFF_HZ));
+        let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings {
+            capacities,
+            main_track_builder,
+            ..Default::default()
+        })?;
+
+        let (dry_send, feedback, damping) = ReverbPreset::Dry.params();
+        let mut reverb_builder = TrackBuilder::new();
+        let reverb = reverb_builder.add_effect(ReverbBuilder::new().feedback(feedback).damping(damping).mix(1.0));
+        let reverb_track = manager.add_sub_track(reverb_builder)?;
+
+        let mut buses = HashMap::new();
+        for bus in AudioBus::ALL {
+            // Sub-tracks route to the main (master) track by default; all but music also send to
+            // the reverb track at the active zone's level.
+            let mut routes = TrackRoutes::new();
+            if bus != AudioBus::Music {
+                routes = routes.with_route(&reverb_track, Volume::Amplitude(dry_send));
+            }
+            let track = manager.add_sub_track(TrackBuilder::new().routes(routes))?;
+            let mut spatial_scene = manager.add_spatial_scene(SpatialSceneSettings::default())?;
+            let listener = spatial_scene.add_listener(
+                mint::Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+                mint::Quaternion { v: mint::Vector3 { x: 0.0, y: 0.0, z: 0.0 }, s: 1.0 },
+                ListenerSettings::new().track(&track),
+            )?;
+            let mut muffled_builder = TrackBuilder::new().routes(TrackRoutes::parent(&track));
+            muffled_builder.add_effect(FilterBuilder::new().cutoff(OCCLUDED_CUTOFF_HZ));
+            let muffled_track = manager.add_sub_track(muffled_builder)?;
+            let mut muffled_scene = manager.add_spatial_scene(SpatialSceneSettings::default())?;
+            let muffled_listener = muffled_scene.add_listener(
+                to_mint(Vec3::ZERO),
+                to_mint_quat(glam::Quat::IDENTITY),
+                ListenerSettings::new().track(&muffled_track),
+            )?;
+            buses.insert(
+                bus,
+                Bus {
+                    track,
+                    spatial_scene,
+                    listener,
+                    muffled_scene,
+                    muffled_listener,
+                    _muffled_track: muffled_track,
+                    volume: 1.0,
+                    duck: 0.0,
+                    ducked_until: None,
+                    duck_release: 0.0,
+                },
+            );
+        }
 
         Ok(Self {
             manager,
-            spatial_scene,
-            listener,
+            buses,
+            master_volume: 1.0,
             sounds: HashMap::new(),
             active_sounds: Vec::new(),
+            emitters: HashMap::new(),
+            next_emitter_id: 0,
+            tracked_sounds: HashMap::new(),
+            next_sound_id: 0,
+            last_played: HashMap::new(),
+            min_repeat_interval: Duration::from_millis(25),
+            max_voices_per_sound: 8,
+            listener_position: Vec3::ZERO,
+            listener_orientation: glam::Quat::IDENTITY,
+            max_voices: 64,
+            reverb_track,
+            reverb,
+            reverb_zones: Vec::new(),
+            next_reverb_zone_id: 0,
+            forced_reverb_zone: None,
+            active_reverb: ReverbPreset::Dry,
+            ducking: DuckingSettings::default(),
+            master_filter,
+            underwater: false,
         })
     }
 
@@ -61,53 +366,343 @@ impl AudioSystem {
         Ok(())
     }
 
-    /// Play a 2D sound (UI, music).
-    pub fn play(&mut self, name: &str) -> Result<()> {
-        if let Some(sound_data) = self.sounds.get(name) {
-            let handle = self.manager.play(sound_data.clone())?;
-            self.active_sounds.push(handle);
+    fn bus(&self, bus: AudioBus) -> &Bus {
+        &self.buses[&bus]
+    }
+
+    fn bus_mut(&mut self, bus: AudioBus) -> &mut Bus {
+        self.buses.get_mut(&bus).expect("every AudioBus is created in AudioSystem::new")
+    }
+
+    /// Play a 2D sound (UI, music) on a bus.
+    pub fn play(&mut self, name: &str, bus: AudioBus) -> Result<()> {
+        if let Some(sound_data) = self.sounds.get(name).cloned() {
+            self.make_room();
+            let settings = StaticSoundSettings::new().output_destination(&self.bus(bus).track);
+            let handle = self.manager.play(sound_data.with_settings(settings))?;
+            self.push_active(name, handle, bus != AudioBus::Music);
+            self.sidechain(bus, 1.0);
         }
         Ok(())
     }
 
-    /// Play a sound with volume control.
-    pub fn play_with_volume(&mut self, name: &str, volume: f64) -> Result<()> {
-        if let Some(sound_data) = self.sounds.get(name) {
-            let settings = StaticSoundSettings::new().volume(volume);
-            let modified = sound_data.clone().with_settings(settings);
+    /// Play a sound on a bus with volume control.
+    pub fn play_with_volume(&mut self, name: &str, volume: f64, bus: AudioBus) -> Result<()> {
+        if let Some(sound_data) = self.sounds.get(name).cloned() {
+            self.make_room();
+            let settings = StaticSoundSettings::new()
+                .volume(volume)
+                .output_destination(&self.bus(bus).track);
+            let modified = sound_data.with_settings(settings);
             let handle = self.manager.play(modified)?;
-            self.active_sounds.push(handle);
+            self.push_active(name, handle, bus != AudioBus::Music);
+            self.sidechain(bus, volume);
+        }
+        Ok(())
+    }
+
+    /// Play a sound with a random playback rate and volume drawn from the given ranges, so
+    /// repeated SFX (gunshots, footsteps) don't sound identical. Plays of the same name closer
+    /// together than the repeat interval, or beyond the per-sound voice cap, are skipped.
+    pub fn play_with_variation(
+        &mut self,
+        name: &str,
+        pitch_range: RangeInclusive<f64>,
+        volume_range: RangeInclusive<f64>,
+        bus: AudioBus,
+    ) -> Result<()> {
+        if !self.can_play_varied(name) {
+            return Ok(());
+        }
+        if let Some(sound_data) = self.sounds.get(name).cloned() {
+            self.make_room();
+            let mut rng = rand::thread_rng();
+            let volume = sample_range(&mut rng, &volume_range);
+            let settings = StaticSoundSettings::new()
+                .playback_rate(sample_range(&mut rng, &pitch_range))
+                .volume(volume)
+                .output_destination(&self.bus(bus).track);
+            let handle = self.manager.play(sound_data.with_settings(settings))?;
+            self.push_active(name, handle, bus != AudioBus::Music);
+            self.last_played.insert(name.to_string(), Instant::now());
+            self.sidechain(bus, volume);
+        }
+        Ok(())
+    }
+
+    /// Spatial `play_with_variation`: distant sources also play slightly lower and duller, which
+    /// reads as distance far better than volume falloff alone. `occlusion` (0 = clear, 1 = fully
+    /// blocked, e.g. from the game's `OcclusionQuery`) muffles the sound for its whole length.
+    pub fn play_at_position_with_variation(
+        &mut self,
+        name: &str,
+        position: Vec3,
+        pitch_range: RangeInclusive<f64>,
+        volume_range: RangeInclusive<f64>,
+        bus: AudioBus,
+        occlusion: f32,
+    ) -> Result<()> {
+        if !self.can_play_varied(name) {
+            return Ok(());
         }
+        let Some(sound_data) = self.sounds.get(name).cloned() else {
+            return Ok(());
+        };
+        // Up to 10% lower pitch at 150m+
+        let distance = position.distance(self.listener_position) as f64;
+        let distance_factor = 1.0 - 0.1 * (distance / 150.0).min(1.0);
+        let mut rng = rand::thread_rng();
+        let rate = sample_range(&mut rng, &pitch_range) * distance_factor;
+        let volume = sample_range(&mut rng, &volume_range) * one_shot_occlusion(occlusion).1;
+        self.make_room();
+        let emitter = self.add_one_shot_emitter(bus, position, occlusion)?;
+        let settings = StaticSoundSettings::new()
+            .playback_rate(rate)
+            .volume(volume)
+            .output_destination(&emitter);
+        let handle = self.manager.play(sound_data.with_settings(settings))?;
+        self.push_active(name, handle, bus != AudioBus::Music);
+        self.last_played.insert(name.to_string(), Instant::now());
+        self.sidechain(bus, volume * self.distance_gain(position));
         Ok(())
     }
 
-    /// Create a spatial emitter at a position.
-    pub fn create_emitter(&mut self, position: Vec3) -> Result<EmitterHandle> {
-        let emitter = self.spatial_scene.add_emitter(
+    /// Set the minimum time between varied plays of the same sound.
+    pub fn set_min_repeat_interval(&mut self, interval: Duration) {
+        self.min_repeat_interval = interval;
+    }
+
+    /// Set how many varied plays of the same sound may overlap.
+    pub fn set_max_voices_per_sound(&mut self, max: usize) {
+        self.max_voices_per_sound = max.max(1);
+    }
+
+    fn can_play_varied(&self, name: &str) -> bool {
+        if self
+            .last_played
+            .get(name)
+            .is_some_and(|t| t.elapsed() < self.min_repeat_interval)
+        {
+            return false;
+        }
+        let playing = self
+            .active_sounds
+            .iter()
+            .filter(|s| s.name == name && s.handle.state() != kira::sound::PlaybackState::Stopped)
+            .count();
+        playing < self.max_voices_per_sound
+    }
+
+    fn push_active(&mut self, name: &str, handle: StaticSoundHandle, cullable: bool) {
+        self.active_sounds.push(ActiveSound { name: name.to_string(), handle, cullable });
+    }
+
+    /// Drop finished sounds, then if one more voice would exceed `max_voices`, fade out the oldest
+    /// cullable one-shot. Loops and music are never culled.
+    fn make_room(&mut self) {
+        self.cleanup();
+        while self.active_sounds.len() + self.tracked_sounds.len() >= self.max_voices {
+            let Some(oldest) = self.active_sounds.iter().position(|s| s.cullable) else {
+                break;
+            };
+            let mut sound = self.active_sounds.remove(oldest);
+            sound.handle.stop(fade_tween(CULL_FADE_SECS));
+        }
+    }
+
+    /// Set the simultaneous voice cap.
+    pub fn set_max_voices(&mut self, max_voices: usize) {
+        self.max_voices = max_voices.max(1);
+    }
+
+    /// Number of voices currently playing (one-shots plus tracked loops), for debugging.
+    pub fn active_voice_count(&self) -> usize {
+        let playing = |h: &StaticSoundHandle| h.state() != kira::sound::PlaybackState::Stopped;
+        self.active_sounds.iter().filter(|s| playing(&s.handle)).count()
+            + self.tracked_sounds.values().filter(|h| playing(h)).count()
+    }
+
+    /// Start a sound on a bus that loops until stopped with `stop_sound`. Errors if the sound
+    /// isn't loaded.
+    pub fn play_looping(&mut self, name: &str, bus: AudioBus) -> Result<SoundId> {
+        let sound_data = self
+            .sounds
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown sound '{}'", name))?
+            .clone();
+        self.make_room();
+        let settings = StaticSoundSettings::new()
+            .loop_region(..)
+            .output_destination(&self.bus(bus).track);
+        let handle = self.manager.play(sound_data.with_settings(settings))?;
+        Ok(self.track_sound(handle))
+    }
+
+    fn track_sound(&mut self, handle: StaticSoundHandle) -> SoundId {
+        let id = SoundId(self.next_sound_id);
+        self.next_sound_id += 1;
+        self.tracked_sounds.insert(id, handle);
+        id
+    }
+
+    /// Stop a tracked sound, fading out over `fade` seconds.
+    pub fn stop_sound(&mut self, id: SoundId, fade: f32) {
+        if let Some(mut handle) = self.tracked_sounds.remove(&id) {
+            handle.stop(fade_tween(fade));
+        }
+    }
+
+    /// Set a tracked sound's volume (linear amplitude).
+    pub fn set_sound_volume(&mut self, id: SoundId, volume: f64) {
+        if let Some(handle) = self.tracked_sounds.get_mut(&id) {
+            handle.set_volume(volume, Tween::default());
+        }
+    }
+
+    /// Whether a tracked sound is still playing (false once stopped or finished).
+    pub fn is_sound_playing(&self, id: SoundId) -> bool {
+        self.tracked_sounds
+            .get(&id)
+            .is_some_and(|h| h.state() != kira::sound::PlaybackState::Stopped)
+    }
+
+    /// Create a spatial emitter at a position. Sounds played through it mix into `bus`.
+    pub fn create_emitter(&mut self, position: Vec3, bus: AudioBus) -> Result<EmitterHandle> {
+        let emitter = self.bus_mut(bus).spatial_scene.add_emitter(
             mint::Vector3 { x: position.x, y: position.y, z: position.z },
             EmitterSettings::default(),
         )?;
         Ok(emitter)
     }
 
-    /// Play a sound at a 3D position.
-    pub fn play_at_position(&mut self, name: &str, position: Vec3) -> Result<()> {
+    /// Play a one-shot sound at a fixed 3D position on a bus, muffled by `occlusion`
+    /// (0 = clear line of sight, 1 = fully blocked).
+    pub fn play_at_position(&mut self, name: &str, position: Vec3, bus: AudioBus, occlusion: f32) -> Result<()> {
         // Clone the sound data first to avoid borrow conflict
         let sound_data = self.sounds.get(name).cloned();
         if let Some(sound_data) = sound_data {
-            let emitter = self.create_emitter(position)?;
+            self.make_room();
+            let emitter = self.add_one_shot_emitter(bus, position, occlusion)?;
+            let gain = one_shot_occlusion(occlusion).1;
             let settings = StaticSoundSettings::new()
+                .volume(gain)
                 .output_destination(&emitter);
             let modified = sound_data.with_settings(settings);
             let handle = self.manager.play(modified)?;
-            self.active_sounds.push(handle);
-            // Note: emitter will be dropped, but sound continues playing
-            // For persistent emitters, store them elsewhere
+            self.push_active(name, handle, bus != AudioBus::Music);
+            self.sidechain(bus, gain * self.distance_gain(position));
         }
         Ok(())
     }
 
-    /// Update listener position and orientation (call each frame).
+    /// Emitter for a positional one-shot: in the bus's plain scene, or its low-passed one when
+    /// the source is mostly hidden. The emitter outlives its handle until the sound finishes, so
+    /// callers can drop it right after starting the sound.
+    fn add_one_shot_emitter(&mut self, bus: AudioBus, position: Vec3, occlusion: f32) -> Result<EmitterHandle> {
+        let (muffled, _) = one_shot_occlusion(occlusion);
+        let bus = self.bus_mut(bus);
+        let scene = if muffled { &mut bus.muffled_scene } else { &mut bus.spatial_scene };
+        Ok(scene.add_emitter(to_mint(position), EmitterSettings::new().persist_until_sounds_finish(true))?)
+    }
+
+    /// Create an emitter that lives until `remove_emitter` is called. Use it for sources that move
+    /// (update with `move_emitter` each frame) or that play several sounds over time. Persistent
+    /// emitters follow `set_occlusion` / `update_occlusion` while they play; one-shots take
+    /// their occlusion when started.
+    pub fn create_persistent_emitter(&mut self, position: Vec3, bus: AudioBus) -> Result<EmitterId> {
+        let mut builder = TrackBuilder::new().routes(TrackRoutes::parent(&self.bus(bus).track));
+        let filter = builder.add_effect(FilterBuilder::new().cutoff(OPEN_CUTOFF_HZ));
+        let track = self.manager.add_sub_track(builder)?;
+        let mut scene = self
+            .manager
+            .add_spatial_scene(SpatialSceneSettings::new().emitter_capacity(1).listener_capacity(1))?;
+        let listener = scene.add_listener(
+            to_mint(self.listener_position),
+            to_mint_quat(self.listener_orientation),
+            ListenerSettings::new().track(&track),
+        )?;
+        let handle = scene.add_emitter(to_mint(position), EmitterSettings::default())?;
+        let emitter = Emitter { handle, bus, position, occlusion: 0.0, filter, track, listener, _scene: scene };
+        let id = EmitterId(self.next_emitter_id);
+        self.next_emitter_id += 1;
+        self.emitters.insert(id, emitter);
+        Ok(id)
+    }
+
+    /// Play a sound from a persistent emitter. Unknown emitters or sounds are ignored.
+    pub fn play_at_emitter(&mut self, name: &str, emitter_id: EmitterId) -> Result<()> {
+        let Some(sound_data) = self.sounds.get(name).cloned() else {
+            return Ok(());
+        };
+        if !self.emitters.contains_key(&emitter_id) {
+            return Ok(());
+        }
+        self.make_room();
+        let emitter = &self.emitters[&emitter_id];
+        let (bus, gain) = (emitter.bus, self.distance_gain(emitter.position));
+        let settings = StaticSoundSettings::new().output_destination(&emitter.handle);
+        let handle = self.manager.play(sound_data.with_settings(settings))?;
+        self.push_active(name, handle, true);
+        self.sidechain(bus, gain);
+        Ok(())
+    }
+
+    /// Move a persistent emitter (call each frame for moving sources).
+    pub fn move_emitter(&mut self, emitter_id: EmitterId, position: Vec3) {
+        if let Some(emitter) = self.emitters.get_mut(&emitter_id) {
+            emitter.position = position;
+            emitter.handle.set_position(to_mint(position), Tween::default());
+        }
+    }
+
+    /// Muffle a persistent emitter: 0 = clear line of sight, 1 = fully blocked (low-passed and
+    /// about 12 dB quieter). Changes glide briefly to avoid pops. Unknown emitters are ignored.
+    pub fn set_occlusion(&mut self, emitter_id: EmitterId, amount: f32) {
+        let Some(emitter) = self.emitters.get_mut(&emitter_id) else {
+            return;
+        };
+        let amount = amount.clamp(0.0, 1.0);
+        if (amount - emitter.occlusion).abs() < 0.01 {
+            return;
+        }
+        emitter.occlusion = amount;
+        let (cutoff, volume) = occlusion_filter(amount);
+        let tween = fade_tween(OCCLUSION_FADE_SECS);
+        emitter.filter.set_cutoff(cutoff, tween);
+        emitter.track.set_volume(volume, tween);
+    }
+
+    /// Current occlusion of a persistent emitter (0 for unknown emitters).
+    pub fn occlusion(&self, emitter_id: EmitterId) -> f32 {
+        self.emitters.get(&emitter_id).map_or(0.0, |e| e.occlusion)
+    }
+
+    /// Re-test every persistent emitter against `query` from the listener and apply the result.
+    /// Call after `update_listener` and `move_emitter`, every frame or every few frames.
+    pub fn update_occlusion(&mut self, query: &impl OcclusionQuery) {
+        let listener = self.listener_position;
+        let amounts: Vec<(EmitterId, f32)> = self
+            .emitters
+            .iter()
+            .map(|(id, e)| (*id, query.occlusion(listener, e.position)))
+            .collect();
+        for (id, amount) in amounts {
+            self.set_occlusion(id, amount);
+        }
+    }
+
+    /// Remove a persistent emitter (e.g. when its entity dies). Sounds routed through it stop.
+    pub fn remove_emitter(&mut self, emitter_id: EmitterId) {
+        self.emitters.remove(&emitter_id);
+    }
+
+    /// Whether a persistent emitter is still registered.
+    pub fn has_emitter(&self, emitter_id: EmitterId) -> bool {
+        self.emitters.contains_key(&emitter_id)
+    }
+
+    /// Update listener position and orientation (call each frame). Also picks the reverb zone
+    /// and releases expired ducks.
     pub fn update_listener(&mut self, position: Vec3, forward: Vec3, up: Vec3) {
         // Compute orientation quaternion from forward and up vectors
         let right = forward.cross(up).normalize();
@@ -116,38 +711,302 @@ impl AudioSystem {
         // Build rotation matrix and convert to quaternion
         let rotation = glam::Mat3::from_cols(right, corrected_up, -forward);
         let quat = glam::Quat::from_mat3(&rotation);
+        self.listener_position = position;
+        self.listener_orientation = quat;
 
-        self.listener.set_position(
-            mint::Vector3 { x: position.x, y: position.y, z: position.z },
-            Tween::default(),
-        );
-        self.listener.set_orientation(
-            mint::Quaternion { 
-                v: mint::Vector3 { x: quat.x, y: quat.y, z: quat.z }, 
-                s: quat.w 
-            },
-            Tween::default(),
-        );
+        for bus in self.buses.values_mut() {
+            bus.listener.set_position(
+                mint::Vector3 { x: position.x, y: position.y, z: position.z },
+                Tween::default(),
+            );
+            bus.listener.set_orientation(
+                mint::Quaternion {
+                    v: mint::Vector3 { x: quat.x, y: quat.y, z: quat.z },
+                    s: quat.w
+                },
+                Tween::default(),
+            );
+            bus.muffled_listener.set_position(to_mint(position), Tween::default());
+            bus.muffled_listener.set_orientation(to_mint_quat(quat), Tween::default());
+        }
+        for emitter in self.emitters.values_mut() {
+            emitter.listener.set_position(to_mint(position), Tween::default());
+            emitter.listener.set_orientation(to_mint_quat(quat), Tween::default());
+        }
+        self.update_reverb();
+        self.update_ducking();
+    }
+
+    /// Add a reverb zone; it takes effect the next time the listener moves into it.
+    pub fn add_reverb_zone(&mut self, zone: ReverbZone) -> ReverbZoneId {
+        let id = ReverbZoneId(self.next_reverb_zone_id);
+        self.next_reverb_zone_id += 1;
+        self.reverb_zones.push((id, zone));
+        id
+    }
+
+    pub fn remove_reverb_zone(&mut self, id: ReverbZoneId) {
+        self.reverb_zones.retain(|(zone_id, _)| *zone_id != id);
+        if self.forced_reverb_zone == Some(id) {
+            self.forced_reverb_zone = None;
+        }
+        self.update_reverb();
+    }
+
+    /// Drop every zone (e.g. when leaving a planet); the mix goes dry.
+    pub fn clear_reverb_zones(&mut self) {
+        self.reverb_zones.clear();
+        self.forced_reverb_zone = None;
+        self.update_reverb();
+    }
+
+    /// Force the listener into a zone regardless of position (e.g. a cutscene inside the ship),
+    /// or `None` to go back to picking the zone from the listener position.
+    pub fn set_listener_zone(&mut self, id: Option<ReverbZoneId>) {
+        self.forced_reverb_zone = id;
+        self.update_reverb();
+    }
+
+    /// Reverb preset currently applied to the mix.
+    pub fn active_reverb(&self) -> ReverbPreset {
+        self.active_reverb
+    }
+
+    /// Pick the zone for the listener and glide the reverb and bus sends to its preset.
+    fn update_reverb(&mut self) {
+        let zone = self
+            .forced_reverb_zone
+            .or_else(|| zone_at(&self.reverb_zones, self.listener_position));
+        let preset = zone
+            .and_then(|id| self.reverb_zones.iter().find(|(zone_id, _)| *zone_id == id))
+            .map_or(ReverbPreset::Dry, |(_, zone)| zone.preset);
+        if preset == self.active_reverb {
+            return;
+        }
+        self.active_reverb = preset;
+        let (send, feedback, damping) = preset.params();
+        let tween = fade_tween(REVERB_FADE_SECS);
+        self.reverb.set_feedback(feedback, tween);
+        self.reverb.set_damping(damping, tween);
+        for (bus, state) in self.buses.iter_mut() {
+            if *bus != AudioBus::Music {
+                state
+                    .track
+                    .set_route(&self.reverb_track, Volume::Amplitude(send), tween)
+                    .expect("non-music buses are built with a reverb send");
+            }
+        }
+    }
+
+    /// Muffle everything (master low-pass) while the listener is underwater.
+    pub fn set_underwater(&mut self, underwater: bool) {
+        if underwater == self.underwater {
+            return;
+        }
+        self.underwater = underwater;
+        let cutoff = if underwater { UNDERWATER_CUTOFF_HZ } else { OPEN_CUTOFF_HZ };
+        self.master_filter.set_cutoff(cutoff, fade_tween(UNDERWATER_FADE_SECS));
+    }
+
+    pub fn is_underwater(&self) -> bool {
+        self.underwater
     }
 
     /// Clean up finished sounds.
     pub fn cleanup(&mut self) {
-        self.active_sounds.retain(|handle| handle.state() != kira::sound::PlaybackState::Stopped);
+        self.active_sounds.retain(|s| s.handle.state() != kira::sound::PlaybackState::Stopped);
+        self.tracked_sounds.retain(|_, handle| handle.state() != kira::sound::PlaybackState::Stopped);
     }
 
     /// Stop all sounds.
     pub fn stop_all(&mut self) {
-        for handle in &mut self.active_sounds {
-            let _ = handle.stop(Tween::default());
+        for sound in &mut self.active_sounds {
+            sound.handle.stop(Tween::default());
         }
         self.active_sounds.clear();
+        for handle in self.tracked_sounds.values_mut() {
+            handle.stop(Tween::default());
+        }
+        self.tracked_sounds.clear();
     }
 
-    /// Set master volume (0.0 to 1.0).
+    /// Set master volume (0.0 to 1.0). Parent of every bus.
     pub fn set_master_volume(&mut self, volume: f64) {
-        let _ = self.manager.main_track().set_volume(volume, Tween::default());
+        self.master_volume = volume.clamp(0.0, 1.0);
+        self.manager.main_track().set_volume(linear_to_volume(self.master_volume), Tween::default());
+    }
+
+    /// Master volume (0.0 to 1.0).
+    pub fn master_volume(&self) -> f64 {
+        self.master_volume
+    }
+
+    /// Set a bus volume (0.0 to 1.0), e.g. lower music without touching gunfire.
+    pub fn set_bus_volume(&mut self, bus: AudioBus, volume: f64) {
+        let bus = self.bus_mut(bus);
+        bus.volume = volume.clamp(0.0, 1.0);
+        bus.track.set_volume(linear_to_volume(bus.effective_volume()), Tween::default());
+    }
+
+    /// Bus volume (0.0 to 1.0).
+    pub fn bus_volume(&self, bus: AudioBus) -> f64 {
+        self.bus(bus).volume
+    }
+
+    /// Duck a bus by `amount` (0–1 of its volume) over `attack` seconds. It stays down for the
+    /// ducking hold time after the latest trigger, then recovers over `release` seconds. A
+    /// smaller duck while a deeper one is active only extends the hold.
+    pub fn duck_bus(&mut self, target: AudioBus, amount: f64, attack: f32, release: f32) {
+        let hold = Duration::from_secs_f32(self.ducking.hold.max(0.0));
+        let bus = self.bus_mut(target);
+        bus.ducked_until = Some(Instant::now() + hold);
+        bus.duck_release = release;
+        let amount = amount.clamp(0.0, 1.0);
+        if amount > bus.duck {
+            bus.duck = amount;
+            bus.track.set_volume(linear_to_volume(bus.effective_volume()), fade_tween(attack));
+        }
+    }
+
+    /// Tune automatic ducking (threshold, depth per bus, attack/hold/release).
+    pub fn set_ducking(&mut self, settings: DuckingSettings) {
+        self.ducking = settings;
+    }
+
+    pub fn ducking(&self) -> DuckingSettings {
+        self.ducking
+    }
+
+    /// Linear 0–1 gain for a positional sound's loudness at the listener (ducking only).
+    fn distance_gain(&self, position: Vec3) -> f64 {
+        (1.0 - position.distance(self.listener_position) / DUCK_FALLOFF_DISTANCE).clamp(0.0, 1.0) as f64
+    }
+
+    /// Duck Music and Ambient when an Sfx sound at least as loud as the threshold starts.
+    fn sidechain(&mut self, bus: AudioBus, loudness: f64) {
+        if bus != AudioBus::Sfx || loudness < self.ducking.threshold {
+            return;
+        }
+        let d = self.ducking;
+        self.duck_bus(AudioBus::Music, d.music_amount, d.attack, d.release);
+        self.duck_bus(AudioBus::Ambient, d.ambient_amount, d.attack, d.release);
+    }
+
+    /// Release ducks whose hold has run out (called from `update_listener`).
+    fn update_ducking(&mut self) {
+        let now = Instant::now();
+        for bus in self.buses.values_mut() {
+            if bus.ducked_until.is_some_and(|t| t <= now) {
+                bus.ducked_until = None;
+                bus.duck = 0.0;
+                bus.track.set_volume(linear_to_volume(bus.volume), fade_tween(bus.duck_release));
+            }
+        }
+    }
+}
+
+/// Convert a linear 0–1 slider value to Kira decibels (0 → silent, 1 → 0 dB).
+fn linear_to_volume(volume: f64) -> Volume {
+    if volume <= 0.0 {
+        Volume::Decibels(Volume::MIN_DECIBELS)
+    } else {
+        Volume::Decibels((20.0 * volume.log10()).max(Volume::MIN_DECIBELS))
+    }
+}
+
+/// Low-pass cutoff (Hz) and volume for an occlusion amount. The cutoff moves on a log scale so
+/// partial cover already takes the edge off before the volume drops much.
+fn occlusion_filter(amount: f32) -> (f64, Volume) {
+    let amount = amount.clamp(0.0, 1.0) as f64;
+    let cutoff = OPEN_CUTOFF_HZ * (OCCLUDED_CUTOFF_HZ / OPEN_CUTOFF_HZ).powf(amount);
+    (cutoff, Volume::Decibels(OCCLUDED_VOLUME_DB * amount))
+}
+
+/// Whether a one-shot with this occlusion goes through the muffled (low-passed) scene, and its
+/// linear gain. The gain follows the same curve as a persistent emitter's volume.
+fn one_shot_occlusion(amount: f32) -> (bool, f64) {
+    let amount = amount.clamp(0.0, 1.0);
+    let gain = 10f64.powf(OCCLUDED_VOLUME_DB * amount as f64 / 20.0);
+    (amount >= ONE_SHOT_MUFFLE_THRESHOLD, gain)
+}
+
+/// The smallest zone containing `point`.
+fn zone_at(zones: &[(ReverbZoneId, ReverbZone)], point: Vec3) -> Option<ReverbZoneId> {
+    zones
+        .iter()
+        .filter(|(_, zone)| zone.contains(point))
+        .min_by(|(_, a), (_, b)| a.volume().total_cmp(&b.volume()))
+        .map(|(id, _)| *id)
+}
+
+fn to_mint(v: Vec3) -> mint::Vector3<f32> {
+    mint::Vector3 { x: v.x, y: v.y, z: v.z }
+}
+
+fn to_mint_quat(q: glam::Quat) -> mint::Quaternion<f32> {
+    mint::Quaternion { v: mint::Vector3 { x: q.x, y: q.y, z: q.z }, s: q.w }
+}
+
+/// Uniform sample from an inclusive range; a degenerate range returns its start.
+fn sample_range(rng: &mut impl Rng, range: &RangeInclusive<f64>) -> f64 {
+    if range.start() < range.end() {
+        rng.gen_range(range.clone())
+    } else {
+        *range.start()
+    }
+}
+
+/// Linear fade of `seconds` (instant if zero or negative).
+fn fade_tween(seconds: f32) -> Tween {
+    Tween {
+        duration: Duration::from_secs_f32(seconds.max(0.0)),
+        ..Default::default()
     }
 }
 
 // Re-export for convenience
 pub use kira;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn occlusion_lowers_cutoff_and_volume_monotonically() {
+        let (open_cutoff, open_volume) = occlusion_filter(0.0);
+        assert_eq!(open_cutoff, OPEN_CUTOFF_HZ);
+        assert_eq!(open_volume, Volume::Decibels(0.0));
+
+        let (half_cutoff, _) = occlusion_filter(0.5);
+        let (full_cutoff, full_volume) = occlusion_filter(1.0);
+        assert!(open_cutoff > half_cutoff && half_cutoff > full_cutoff);
+        assert!((full_cutoff - OCCLUDED_CUTOFF_HZ).abs() < 1e-6);
+        assert_eq!(full_volume, Volume::Decibels(OCCLUDED_VOLUME_DB));
+        // Out-of-range amounts clamp
+        assert_eq!(occlusion_filter(3.0).0, full_cutoff);
+    }
+
+    #[test]
+    fn occluded_one_shots_are_muffled_and_quieter() {
+        assert_eq!(one_shot_occlusion(0.0), (false, 1.0));
+        let (partly_muffled, partly) = one_shot_occlusion(0.3);
+        let (hidden_muffled, hidden) = one_shot_occlusion(1.0);
+        assert!(!partly_muffled && hidden_muffled);
+        assert!(1.0 > partly && partly > hidden);
+        // Full occlusion drops the same 12 dB as a persistent emitter
+        assert!((20.0 * hidden.log10() - OCCLUDED_VOLUME_DB).abs() < 1e-9);
+        assert_eq!(one_shot_occlusion(-1.0), one_shot_occlusion(0.0));
+    }
+
+    #[test]
+    fn smallest_containing_reverb_zone_wins() {
+        let planet = ReverbZone::new(Vec3::splat(-1000.0), Vec3::splat(1000.0), ReverbPreset::OpenField);
+        // Corners given in reverse order still make a valid box
+        let ship = ReverbZone::new(Vec3::new(20.0, 10.0, 5.0), Vec3::new(-20.0, -2.0, -5.0), ReverbPreset::SteelCorridor);
+        let zones = [(ReverbZoneId(0), planet), (ReverbZoneId(1), ship)];
+
+        assert_eq!(zone_at(&zones, Vec3::new(0.0, 1.0, 0.0)), Some(ReverbZoneId(1)));
+        assert_eq!(zone_at(&zones, Vec3::new(500.0, 0.0, 0.0)), Some(ReverbZoneId(0)));
+        assert_eq!(zone_at(&zones, Vec3::new(5000.0, 0.0, 0.0)), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! Parent/child transform hierarchy.
+//!
+//! An entity with a `Parent` has its `Transform` interpreted relative to the parent.
+//! `update_global_transforms` resolves the chain into a world-space `GlobalTransform`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use glam::{Mat4, Vec3};
+use hecs::{Entity, World};
+
+use crate::Transform;
+
+/// Deepest parent chain followed before it is treated as malformed.
+pub const MAX_HIERARCHY_DEPTH: usize = 64;
+
+/// Set once a malformed chain has been reported, so a persistent cycle doesn't flood the log.
+static MALFORMED_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Makes this entity's `Transform` local to another entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(pub Entity);
+
+/// World-space matrix for an entity, composed from its parent chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTransform(pub Mat4);
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self(Mat4::IDENTITY)
+    }
+}
+
+impl GlobalTransform {
+    /// World-space position.
+    pub fn position(&self) -> Vec3 {
+        self.0.w_axis.truncate()
+    }
+
+    /// Transform a point from this entity's local space into world space.
+    pub fn transform_point(&self, local: Vec3) -> Vec3 {
+        self.0.transform_point3(local)
+    }
+}
+
+/// Compute the world matrix of `entity` by composing its parents' transforms.
+///
+/// A missing `Transform` counts as identity. A dangling parent ends the chain. If the chain
+/// loops back on itself or exceeds `MAX_HIERARCHY_DEPTH`, the walk stops there and the
+/// partial result is returned, so a malformed hierarchy can't hang the frame. The first such
+/// chain is logged; later ones are truncated silently.
+pub fn world_matrix(world: &World, entity: Entity) -> Mat4 {
+    let mut matrix = Mat4::IDENTITY;
+    let mut visited: Vec<Entity> = Vec::new();
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if visited.contains(&e) || visited.len() >= MAX_HIERARCHY_DEPTH {
+            if !MALFORMED_WARNED.swap(true, Ordering::Relaxed) {
+                log::warn!("Transform hierarchy cycle or too deep at {:?}; truncating", e);
+            }
+            break;
+        }
+        visited.push(e);
+        let local = world
+            .get::<&Transform>(e)
+            .map(|t| t.to_matrix())
+            .unwrap_or(Mat4::IDENTITY);
+        matrix = local * matrix;
+        current = world.get::<&Parent>(e).ok().map(|p| p.0);
+    }
+    matrix
+}
+
+/// Write `GlobalTransform` for every entity that has a `Parent`. Run once per frame
+/// after local transforms have been updated and before rendering.
+pub fn update_global_transforms(world: &mut World) {
+    let children: Vec<Entity> = world
+        .query::<&Parent>()
+        .iter()
+        .map(|(e, _)| e)
+        .collect();
+    for child in children {
+        let global = GlobalTransform(world_matrix(world, child));
+        // Overwrites any existing GlobalTransform on the child.
+        world.insert_one(child, global).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_composes_parent_transform() {
+        let mut world = World::new();
+        let parent = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let child = world.spawn((Transform::from_position(Vec3::new(0.0, 2.0, 0.0)), Parent(parent)));
+        update_global_transforms(&mut world);
+        let global = *world.get::<&GlobalTransform>(child).unwrap();
+        assert!(global.position().abs_diff_eq(Vec3::new(10.0, 2.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn cycle_terminates() {
+        let mut world = World::new();
+        let a = world.spawn((Transform::from_position(Vec3::X),));
+        let b = world.spawn((Transform::from_position(Vec3::Y), Parent(a)));
+        world.insert_one(a, Parent(b)).unwrap();
+        let m = world_matrix(&world, a);
+        assert!(m.w_axis.truncate().abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-5));
+    }
+}
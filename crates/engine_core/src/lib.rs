@@ -2,14 +2,20 @@
 //!
 //! This crate provides the foundational types used across all engine systems:
 //! - Transform and spatial components
+//! - Parent/child transform hierarchy
 //! - Time management
+//! - Seeded RNG for reproducible gameplay
 //! - Common component types for ECS
 
 pub mod components;
+pub mod hierarchy;
+pub mod rng;
 pub mod time;
 pub mod transform;
 
 pub use components::*;
+pub use hierarchy::*;
+pub use rng::*;
 pub use time::*;
 pub use transform::*;
 
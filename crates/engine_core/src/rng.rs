@@ -0,0 +1,104 @@
+//! Seeded random number generation for reproducible gameplay.
+
+use rand::distributions::uniform::{SampleRange, SampleUniform};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Deterministic RNG seeded from the universe seed. Systems that affect gameplay
+/// (war state, spawning, gore) take a `&mut GameRng` instead of `thread_rng()` so a
+/// run can be replayed from its seed. Implements `RngCore`, so all `rand::Rng` methods work too.
+#[derive(Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    /// Create a generator from a seed. The same seed always yields the same sequence.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed this generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Independent generator for a sub-system, derived from this one's seed and `stream`.
+    /// Does not advance `self`, so adding a new stream doesn't shift existing sequences.
+    pub fn fork(&self, stream: u64) -> Self {
+        Self::new(self.seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// Uniform sample from a range, e.g. `rng.range(0.0..1.0)` or `rng.range(1..=6)`.
+    pub fn range<T, R>(&mut self, range: R) -> T
+    where
+        T: SampleUniform,
+        R: SampleRange<T>,
+    {
+        self.rng.gen_range(range)
+    }
+
+    /// Uniform f32 in [0, 1).
+    pub fn unit(&mut self) -> f32 {
+        self.rng.gen::<f32>()
+    }
+
+    /// True with probability `p` (clamped to 0..1).
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.rng.gen::<f32>() < p.clamp(0.0, 1.0)
+    }
+
+    /// Random element of a slice, or `None` if empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.rng.gen_range(0..items.len())])
+        }
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = GameRng::new(42);
+        let mut b = GameRng::new(42);
+        for _ in 0..16 {
+            assert_eq!(a.range(0..1000), b.range(0..1000));
+        }
+    }
+
+    #[test]
+    fn pick_and_chance_edges() {
+        let mut rng = GameRng::new(7);
+        assert!(rng.pick::<u8>(&[]).is_none());
+        assert_eq!(rng.pick(&[3]), Some(&3));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+}
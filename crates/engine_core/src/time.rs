@@ -19,6 +19,42 @@ pub struct Time {
     fixed_timestep: Duration,
     /// Accumulated time for fixed updates.
     accumulator: Duration,
+    /// Multiplier applied to game delta (0.25 = slow-mo, 2.0 = fast-forward).
+    time_scale: f32,
+    /// When paused, game delta is zero and fixed updates stop accumulating.
+    paused: bool,
+    /// Active hitstop, if any (multiplies on top of `time_scale`).
+    dilation: Option<Dilation>,
+}
+
+/// Brief time dilation: game time held at `scale` for `hold`, then eased back to full speed over
+/// `ramp`. Runs on real time so it ends on schedule however slow the game clock is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dilation {
+    scale: f32,
+    hold: Duration,
+    ramp: Duration,
+    age: Duration,
+}
+
+impl Dilation {
+    /// Time multiplier at the current age; 1.0 once the ramp is over.
+    fn factor(&self) -> f32 {
+        if self.age < self.hold {
+            return self.scale;
+        }
+        let ramp = self.ramp.as_secs_f32();
+        if ramp <= 0.0 {
+            return 1.0;
+        }
+        let t = ((self.age - self.hold).as_secs_f32() / ramp).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.scale + (1.0 - self.scale) * eased
+    }
+
+    fn finished(&self) -> bool {
+        self.age >= self.hold + self.ramp
+    }
 }
 
 impl Default for Time {
@@ -39,6 +75,9 @@ impl Time {
             frame_count: 0,
             fixed_timestep: Duration::from_secs_f64(1.0 / 60.0),
             accumulator: Duration::ZERO,
+            time_scale: 1.0,
+            paused: false,
+            dilation: None,
         }
     }
 
@@ -49,19 +88,86 @@ impl Time {
         self.last_frame = now;
         self.elapsed = now - self.start_time;
         self.frame_count += 1;
-        self.accumulator += self.delta;
+        if let Some(dilation) = &mut self.dilation {
+            if dilation.finished() {
+                self.dilation = None;
+            } else if !self.paused {
+                dilation.age += self.delta;
+            }
+        }
+        self.accumulator += self.scaled_delta();
+    }
+
+    /// Game delta: real delta scaled by `time_scale` and any hitstop, or zero while paused.
+    fn scaled_delta(&self) -> Duration {
+        if self.paused {
+            Duration::ZERO
+        } else {
+            self.delta.mul_f32(self.effective_time_scale())
+        }
     }
 
-    /// Get the delta time in seconds.
+    /// Get the game delta time in seconds (scaled, zero while paused).
     pub fn delta_seconds(&self) -> f32 {
-        self.delta.as_secs_f32()
+        self.scaled_delta().as_secs_f32()
     }
 
-    /// Get the delta time as a Duration.
+    /// Get the game delta time as a Duration (scaled, zero while paused).
     pub fn delta(&self) -> Duration {
+        self.scaled_delta()
+    }
+
+    /// Get the real frame delta in seconds, ignoring pause and time scale (for UI/menus).
+    pub fn unscaled_delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Get the real frame delta as a Duration, ignoring pause and time scale.
+    pub fn unscaled_delta(&self) -> Duration {
         self.delta
     }
 
+    /// Stop game time. Unscaled delta and elapsed time keep running.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume game time after `pause`.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Whether game time is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set the game time scale (clamped to be non-negative).
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    /// Get the game time scale.
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Hitstop: drop game time to `scale` (e.g. 0.05) for `hold`, then ease back to normal over
+    /// `ramp`. Both run on real time. Replaces any hitstop already in progress.
+    pub fn dilate(&mut self, scale: f32, hold: Duration, ramp: Duration) {
+        self.dilation = Some(Dilation { scale: scale.clamp(0.0, 1.0), hold, ramp, age: Duration::ZERO });
+    }
+
+    /// Whether a hitstop is slowing game time.
+    pub fn is_dilated(&self) -> bool {
+        self.dilation.is_some()
+    }
+
+    /// Multiplier actually applied to game delta: `time_scale` times any hitstop in progress.
+    pub fn effective_time_scale(&self) -> f32 {
+        self.time_scale * self.dilation.as_ref().map_or(1.0, Dilation::factor)
+    }
+
     /// Get total elapsed time in seconds.
     pub fn elapsed_seconds(&self) -> f32 {
         self.elapsed.as_secs_f32()
@@ -92,6 +198,12 @@ impl Time {
         }
     }
 
+    /// Fraction of a fixed step left in the accumulator (0..1). Use it to blend between the
+    /// previous and current physics state when rendering.
+    pub fn interpolation_alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f32() / self.fixed_timestep.as_secs_f32()).clamp(0.0, 1.0)
+    }
+
     /// Get the current FPS (averaged over last frame).
     pub fn fps(&self) -> f32 {
         if self.delta.as_secs_f32() > 0.0 {
@@ -106,3 +218,25 @@ impl Time {
         self.fixed_timestep = Duration::from_secs_f64(1.0 / hz);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hitstop_holds_then_eases_back_to_full_speed() {
+        let mut time = Time::new();
+        time.set_time_scale(0.5);
+        time.dilate(0.05, Duration::from_millis(80), Duration::from_millis(100));
+        assert!((time.effective_time_scale() - 0.025).abs() < 1e-6);
+
+        let mut dilation = time.dilation.unwrap();
+        dilation.age = Duration::from_millis(79);
+        assert_eq!(dilation.factor(), 0.05);
+        dilation.age = Duration::from_millis(130);
+        assert!((dilation.factor() - 0.525).abs() < 1e-3, "halfway through the ramp");
+        dilation.age = Duration::from_millis(180);
+        assert_eq!(dilation.factor(), 1.0);
+        assert!(dilation.finished());
+    }
+}
@@ -39,6 +39,15 @@ impl Transform {
         }
     }
 
+    /// Blend between two transforms: lerp position/scale, slerp rotation.
+    pub fn lerp(a: &Transform, b: &Transform, t: f32) -> Self {
+        Self {
+            position: a.position.lerp(b.position, t),
+            rotation: a.rotation.slerp(b.rotation, t),
+            scale: a.scale.lerp(b.scale, t),
+        }
+    }
+
     /// Create the model matrix for this transform.
     pub fn to_matrix(&self) -> Mat4 {
         Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.position)
@@ -83,6 +92,26 @@ impl Transform {
     }
 }
 
+/// Transform as of the previous fixed (physics) step. Entities with this component are
+/// rendered between the previous and current step instead of snapping at the physics rate.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PreviousTransform(pub Transform);
+
+impl PreviousTransform {
+    /// Transform to render given the current one and `Time::interpolation_alpha`.
+    pub fn interpolate(&self, current: &Transform, alpha: f32) -> Transform {
+        Transform::lerp(&self.0, current, alpha)
+    }
+}
+
+/// Snapshot `Transform` into `PreviousTransform` for every entity that has both.
+/// Call before a physics step.
+pub fn store_previous_transforms(world: &mut hecs::World) {
+    for (_, (transform, previous)) in world.query_mut::<(&Transform, &mut PreviousTransform)>() {
+        previous.0 = *transform;
+    }
+}
+
 /// Raw transform data for GPU upload (instance data).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
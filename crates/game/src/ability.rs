@@ -0,0 +1,231 @@
+//! Class abilities (Q): what each class's ability does, and what it leaves behind in the world.
+//!
+//! The ability a class gets (and its cooldown) is class data in `classes.ron`; `AbilityState`
+//! tracks the effects while they last: an active dash, deployed shields and scan marks.
+
+use glam::Vec3;
+use hecs::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Dash: horizontal speed (m/s) for the dash duration (s).
+const DASH_SPEED: f32 = 22.0;
+const DASH_DURATION: f32 = 0.25;
+/// Shield dome: radius (m) and lifetime (s).
+const DOME_RADIUS: f32 = 5.0;
+const DOME_DURATION: f32 = 12.0;
+/// Barricade: half width and height of the wall (m), distance in front of the player, lifetime (s).
+const BARRICADE_HALF_WIDTH: f32 = 3.0;
+const BARRICADE_HEIGHT: f32 = 2.2;
+const BARRICADE_DISTANCE: f32 = 2.5;
+const BARRICADE_DURATION: f32 = 20.0;
+/// Scan ping: reach (m), how many bugs it can mark, and how long marks last (s).
+pub const SCAN_RADIUS: f32 = 80.0;
+pub const SCAN_MAX_MARKS: usize = 16;
+const SCAN_DURATION: f32 = 8.0;
+/// Ammo station: magazines added to every weapon's reserve.
+pub const AMMO_STATION_MAGAZINES: u32 = 2;
+
+/// A class's Q ability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClassAbility {
+    /// Short burst of speed in the move direction (forward when standing still)
+    Dash,
+    /// Deploy a shield wall in front of the player
+    DeployBarricade,
+    /// Resupply reserve ammo for every weapon
+    AmmoStation,
+    /// Ping nearby bugs so they show on screen for a few seconds
+    ScanPulse,
+    /// Deploy a shield dome around the player
+    ShieldDome,
+}
+
+impl ClassAbility {
+    pub fn name(self) -> &'static str {
+        match self {
+            ClassAbility::Dash => "Dash",
+            ClassAbility::DeployBarricade => "Barricade",
+            ClassAbility::AmmoStation => "Ammo Station",
+            ClassAbility::ScanPulse => "Scan Ping",
+            ClassAbility::ShieldDome => "Shield Dome",
+        }
+    }
+}
+
+/// Shape of a deployed shield.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShieldShape {
+    /// Hemisphere of `radius` around the shield center.
+    Dome { radius: f32 },
+    /// Upright wall through the shield center, `half_width` to each side along `along`
+    /// (horizontal unit vector).
+    Wall { half_width: f32, height: f32, along: Vec3 },
+}
+
+/// A deployed shield. Bug and Skinny attacks whose line to the player crosses it are blocked.
+#[derive(Debug, Clone, Copy)]
+pub struct DeployedShield {
+    pub center: Vec3,
+    pub shape: ShieldShape,
+    /// Seconds left before it collapses.
+    pub remaining: f32,
+    pub duration: f32,
+}
+
+impl DeployedShield {
+    /// True when the straight line from `attacker` to `target` passes through the shield.
+    pub fn blocks(&self, attacker: Vec3, target: Vec3) -> bool {
+        match self.shape {
+            // Exactly one end inside the dome: the line crosses its surface
+            ShieldShape::Dome { radius } => {
+                let inside = |p: Vec3| p.distance_squared(self.center) <= radius * radius;
+                inside(attacker) != inside(target)
+            }
+            // Top-down segment intersection with the wall's footprint
+            ShieldShape::Wall { half_width, along, .. } => {
+                let a = self.center - along * half_width;
+                let b = self.center + along * half_width;
+                segments_cross_xz(attacker, target, a, b)
+            }
+        }
+    }
+
+    /// 0–1 of its lifetime left (for fading the visual out).
+    pub fn strength(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.remaining / self.duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A bug picked up by a scan ping.
+#[derive(Debug, Clone, Copy)]
+pub struct ScanMark {
+    pub entity: Entity,
+    pub remaining: f32,
+}
+
+/// Effects of class abilities that outlive the key press.
+#[derive(Debug, Default)]
+pub struct AbilityState {
+    /// Horizontal dash velocity and seconds left.
+    dash: Option<(Vec3, f32)>,
+    pub shields: Vec<DeployedShield>,
+    pub scan_marks: Vec<ScanMark>,
+}
+
+impl AbilityState {
+    /// Start a dash along `direction` (flattened to horizontal).
+    pub fn start_dash(&mut self, direction: Vec3) {
+        let dir = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+        if dir != Vec3::ZERO {
+            self.dash = Some((dir * DASH_SPEED, DASH_DURATION));
+        }
+    }
+
+    /// Horizontal velocity the dash forces this frame, if one is active.
+    pub fn dash_velocity(&self) -> Option<Vec3> {
+        self.dash.map(|(velocity, _)| velocity)
+    }
+
+    /// Deploy a shield dome centered on `position`.
+    pub fn deploy_dome(&mut self, position: Vec3) {
+        self.shields.push(DeployedShield {
+            center: position,
+            shape: ShieldShape::Dome { radius: DOME_RADIUS },
+            remaining: DOME_DURATION,
+            duration: DOME_DURATION,
+        });
+    }
+
+    /// Deploy a shield wall across `facing` a little in front of `position`.
+    pub fn deploy_barricade(&mut self, position: Vec3, facing: Vec3) {
+        let forward = Vec3::new(facing.x, 0.0, facing.z).normalize_or(Vec3::NEG_Z);
+        self.shields.push(DeployedShield {
+            center: position + forward * BARRICADE_DISTANCE,
+            shape: ShieldShape::Wall {
+                half_width: BARRICADE_HALF_WIDTH,
+                height: BARRICADE_HEIGHT,
+                along: Vec3::new(-forward.z, 0.0, forward.x),
+            },
+            remaining: BARRICADE_DURATION,
+            duration: BARRICADE_DURATION,
+        });
+    }
+
+    /// Replace the scan marks with `entities`.
+    pub fn mark(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.scan_marks = entities
+            .into_iter()
+            .take(SCAN_MAX_MARKS)
+            .map(|entity| ScanMark { entity, remaining: SCAN_DURATION })
+            .collect();
+    }
+
+    /// True when any deployed shield stands between `attacker` and `target`.
+    pub fn blocks(&self, attacker: Vec3, target: Vec3) -> bool {
+        self.shields.iter().any(|s| s.blocks(attacker, target))
+    }
+
+    /// Count down the dash, shields and marks, dropping the expired ones.
+    pub fn update(&mut self, dt: f32) {
+        if let Some((_, remaining)) = &mut self.dash {
+            *remaining -= dt;
+            if *remaining <= 0.0 {
+                self.dash = None;
+            }
+        }
+        for shield in &mut self.shields {
+            shield.remaining -= dt;
+        }
+        self.shields.retain(|s| s.remaining > 0.0);
+        for mark in &mut self.scan_marks {
+            mark.remaining -= dt;
+        }
+        self.scan_marks.retain(|m| m.remaining > 0.0);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Whether segments p1–p2 and q1–q2 intersect, looking straight down (XZ plane).
+fn segments_cross_xz(p1: Vec3, p2: Vec3, q1: Vec3, q2: Vec3) -> bool {
+    let cross = |o: Vec3, a: Vec3, b: Vec3| (a.x - o.x) * (b.z - o.z) - (a.z - o.z) * (b.x - o.x);
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    (d1 > 0.0) != (d2 > 0.0) && (d3 > 0.0) != (d4 > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dome_blocks_only_attacks_crossing_its_surface() {
+        let mut abilities = AbilityState::default();
+        abilities.deploy_dome(Vec3::ZERO);
+        let inside = Vec3::new(1.0, 0.0, 1.0);
+        assert!(abilities.blocks(Vec3::new(12.0, 0.0, 0.0), inside));
+        assert!(!abilities.blocks(Vec3::new(-1.0, 0.0, 0.0), inside));
+        assert!(!abilities.blocks(Vec3::new(12.0, 0.0, 0.0), Vec3::new(20.0, 0.0, 0.0)));
+
+        abilities.update(DOME_DURATION + 0.1);
+        assert!(abilities.shields.is_empty());
+    }
+
+    #[test]
+    fn barricade_blocks_from_the_front_but_not_around_the_side() {
+        let mut abilities = AbilityState::default();
+        let player = Vec3::ZERO;
+        abilities.deploy_barricade(player, Vec3::NEG_Z);
+        assert!(abilities.blocks(Vec3::new(0.5, 0.0, -10.0), player));
+        assert!(!abilities.blocks(Vec3::new(20.0, 0.0, -10.0), player));
+        assert!(!abilities.blocks(Vec3::new(0.0, 0.0, 10.0), player));
+    }
+}
@@ -8,6 +8,14 @@ use rand::Rng;
 pub const SHELL_FIRE_DELAY: f32 = 0.45;
 /// Number of shells per barrage.
 pub const SHELLS_PER_BARRAGE: usize = 6;
+/// Downward acceleration of shells (orbital guns = high velocity, steep arcs).
+pub const ARTILLERY_GRAVITY: f32 = 90.0;
+/// Bugs within this distance of an impact are killed.
+pub const ARTILLERY_KILL_RADIUS: f32 = 28.0;
+/// Random scatter of each shell around the designator (meters, full width).
+const SHELL_SCATTER: f32 = 25.0;
+/// Shells detonate this far above the ground.
+const DETONATE_HEIGHT: f32 = 0.5;
 
 /// An artillery shell fired from orbit, arcing down to the target.
 pub struct ArtilleryShell {
@@ -22,17 +30,16 @@ pub struct ArtilleryShell {
 }
 
 impl ArtilleryShell {
-    /// Create a shell fired from a corvette/destroyer toward the target.
-    pub fn new(from_pos: Vec3, target: Vec3) -> Self {
+    /// Create a shell fired from a corvette/destroyer that reaches the target after `flight_time` seconds.
+    pub fn new(from_pos: Vec3, target: Vec3, flight_time: f32) -> Self {
         let to_target = target - from_pos;
         let horiz = Vec3::new(to_target.x, 0.0, to_target.z);
         let horiz_dist = horiz.length().max(1.0);
         let horiz_dir = horiz / horiz_dist;
 
         // Arc trajectory: orbital guns launch with high velocity — punchy impact
-        let flight_time = 0.5 + rand::thread_rng().gen::<f32>() * 0.2; // ~0.5–0.7s
-        let gravity = 90.0;
-        let dy = to_target.y - from_pos.y;
+        let gravity = ARTILLERY_GRAVITY;
+        let dy = to_target.y;
 
         // Solve: y = vy*t - 0.5*g*t^2 => vy = (dy + 0.5*g*t^2) / t
         let vy = (dy + 0.5 * gravity * flight_time * flight_time) / flight_time;
@@ -48,6 +55,34 @@ impl ArtilleryShell {
             detonated: false,
         }
     }
+
+    /// Where and in how many seconds this shell will detonate, following its ballistic path
+    /// until it meets the ground given by `surface_height(x, z)`.
+    pub fn predicted_impact(&self, surface_height: impl Fn(f32, f32) -> f32) -> (Vec3, f32) {
+        const STEP: f32 = 1.0 / 120.0;
+        const MAX_TIME: f32 = 5.0;
+        let gravity = Vec3::new(0.0, -ARTILLERY_GRAVITY, 0.0);
+        let at = |t: f32| self.position + self.velocity * t + gravity * (0.5 * t * t);
+        let below = |p: Vec3| p.y <= surface_height(p.x, p.z) + DETONATE_HEIGHT;
+        let mut t = 0.0;
+        while !below(at(t)) {
+            if t >= MAX_TIME {
+                return (at(t), t);
+            }
+            t += STEP;
+        }
+        // Shells cover meters per step: bisect the last step for the crossing
+        let (mut lo, mut hi) = ((t - STEP).max(0.0), t);
+        for _ in 0..10 {
+            let mid = 0.5 * (lo + hi);
+            if below(at(mid)) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        (at(hi), hi)
+    }
 }
 
 /// Brief muzzle flash at a ship when it fires. Rendered for ~0.2s.
@@ -140,6 +175,7 @@ impl GroundedArtilleryShell {
 }
 
 /// An active barrage: fires 6 shells one after another with delay between each.
+/// Each shell's scatter and flight time are rolled up front so impacts can be shown before firing.
 pub struct ArtilleryBarrage {
     /// Base target position (from red smoke).
     pub target: Vec3,
@@ -149,4 +185,110 @@ pub struct ArtilleryBarrage {
     pub fire_timer: f32,
     /// Index for alternating corvette/destroyer (0..6).
     pub fire_index: usize,
+    /// Per-shell offset from the target (XZ scatter).
+    scatter: [Vec3; SHELLS_PER_BARRAGE],
+    /// Per-shell flight time (seconds).
+    flight_times: [f32; SHELLS_PER_BARRAGE],
+}
+
+impl ArtilleryBarrage {
+    /// A barrage on the designator at `target`; the first shell fires immediately.
+    pub fn new(target: Vec3) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            target,
+            shells_remaining: SHELLS_PER_BARRAGE,
+            fire_timer: 0.0,
+            fire_index: 0,
+            scatter: std::array::from_fn(|_| {
+                Vec3::new(
+                    (rng.gen::<f32>() - 0.5) * SHELL_SCATTER,
+                    0.0,
+                    (rng.gen::<f32>() - 0.5) * SHELL_SCATTER,
+                )
+            }),
+            flight_times: std::array::from_fn(|_| 0.5 + rng.gen::<f32>() * 0.2), // ~0.5–0.7s
+        }
+    }
+
+    /// Aim point and flight time of shell `index` (offset from the current target).
+    pub fn shell_aim(&self, index: usize) -> (Vec3, f32) {
+        let i = index % SHELLS_PER_BARRAGE;
+        (self.target + self.scatter[i], self.flight_times[i])
+    }
+
+    /// Move the aim point (the designator smoke moved); shells not yet fired follow it.
+    pub fn retarget(&mut self, target: Vec3) {
+        self.target = target;
+    }
+
+    /// Impact point and seconds until impact for each shell not yet fired.
+    pub fn pending_impacts(&self) -> impl Iterator<Item = (Vec3, f32)> + '_ {
+        (0..self.shells_remaining).map(move |k| {
+            let (aim, flight_time) = self.shell_aim(self.fire_index + k);
+            (aim, self.fire_timer.max(0.0) + k as f32 * SHELL_FIRE_DELAY + flight_time)
+        })
+    }
+}
+
+/// A predicted artillery impact: where it lands and how long until it does.
+#[derive(Debug, Clone, Copy)]
+pub struct ImpactMarker {
+    pub position: Vec3,
+    pub time_to_impact: f32,
+}
+
+/// Predicted impacts of every shell in flight plus the unfired shells of the active barrage,
+/// so the landing points can be marked on the ground with a countdown.
+pub fn predicted_impacts(
+    shells: &[ArtilleryShell],
+    barrage: Option<&ArtilleryBarrage>,
+    surface_height: impl Fn(f32, f32) -> f32,
+) -> Vec<ImpactMarker> {
+    let mut markers: Vec<ImpactMarker> = shells
+        .iter()
+        .filter(|s| !s.detonated)
+        .map(|s| {
+            let (position, time_to_impact) = s.predicted_impact(&surface_height);
+            ImpactMarker { position, time_to_impact }
+        })
+        .collect();
+    if let Some(barrage) = barrage {
+        markers.extend(barrage.pending_impacts().map(|(aim, t)| ImpactMarker {
+            position: Vec3::new(aim.x, surface_height(aim.x, aim.z), aim.z),
+            time_to_impact: t,
+        }));
+    }
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predicted_impact_matches_the_aim_point() {
+        let flat = |_: f32, _: f32| 10.0;
+        let target = Vec3::new(40.0, 10.0 + DETONATE_HEIGHT, -25.0);
+        let shell = ArtilleryShell::new(Vec3::new(-200.0, 300.0, 150.0), target, 0.6);
+        let (impact, t) = shell.predicted_impact(flat);
+        assert!((t - 0.6).abs() < 0.02, "flight time {t}");
+        assert!(Vec3::new(impact.x - target.x, 0.0, impact.z - target.z).length() < 0.5);
+    }
+
+    #[test]
+    fn pending_impacts_follow_a_retargeted_designator() {
+        let mut barrage = ArtilleryBarrage::new(Vec3::ZERO);
+        let before: Vec<(Vec3, f32)> = barrage.pending_impacts().collect();
+        assert_eq!(before.len(), SHELLS_PER_BARRAGE);
+        // Later shells land later
+        assert!(before.windows(2).all(|w| w[1].1 > w[0].1));
+
+        let shift = Vec3::new(15.0, -2.0, 5.0);
+        barrage.retarget(shift);
+        for ((a, ta), (b, tb)) in before.iter().zip(barrage.pending_impacts()) {
+            assert!((b - (*a + shift)).length() < 1e-4);
+            assert_eq!(*ta, tb);
+        }
+    }
 }
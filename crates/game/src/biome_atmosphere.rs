@@ -2,7 +2,15 @@
 //! Per-biome cinematic particles: fog banks, embers, spores, crystals, etc.
 
 use glam::Vec3;
-use procgen::BiomeType;
+use procgen::{BiomeType, Planet, StarType};
+use renderer::{AtmosphereParams, FogParams};
+
+use crate::state::Weather;
+
+/// Fog thins with height above the ground (per meter): most haze sits in the lowest ~100m.
+const FOG_HEIGHT_FALLOFF: f32 = 0.012;
+/// How much the fog color takes on the sky horizon color (time of day) vs. the biome tint.
+const FOG_SKY_BLEND: f32 = 0.55;
 
 /// Volumetric particle types for biome atmosphere.
 #[derive(Clone, Copy, PartialEq)]
@@ -57,6 +65,8 @@ pub struct BiomeAtmoConfig {
     pub fog_tint: [f32; 3],
     /// Extra fog density multiplier
     pub fog_density_mult: f32,
+    /// Ground haze extinction per meter in clear weather (renderer fog; higher = hazier)
+    pub fog_density: f32,
     /// Ambient light tint
     pub ambient_tint: [f32; 3],
 }
@@ -74,6 +84,7 @@ impl BiomeAtmoConfig {
                 max_particles: 85,
                 fog_tint: [0.85, 0.75, 0.55],
                 fog_density_mult: 1.2,
+                fog_density: 0.0030,
                 ambient_tint: [1.0, 0.9, 0.7],
             },
             BiomeType::Badlands => Self {
@@ -86,6 +97,7 @@ impl BiomeAtmoConfig {
                 max_particles: 70,
                 fog_tint: [0.7, 0.5, 0.4],
                 fog_density_mult: 1.3,
+                fog_density: 0.0030,
                 ambient_tint: [0.9, 0.7, 0.6],
             },
             BiomeType::HiveWorld => Self {
@@ -100,6 +112,7 @@ impl BiomeAtmoConfig {
                 max_particles: 140,
                 fog_tint: [0.4, 0.5, 0.3],
                 fog_density_mult: 1.8,
+                fog_density: 0.0045,
                 ambient_tint: [0.7, 0.8, 0.5],
             },
             BiomeType::Volcanic => Self {
@@ -113,6 +126,7 @@ impl BiomeAtmoConfig {
                 max_particles: 175,
                 fog_tint: [0.6, 0.3, 0.15],
                 fog_density_mult: 1.5,
+                fog_density: 0.0040,
                 ambient_tint: [1.0, 0.6, 0.3],
             },
             BiomeType::Frozen => Self {
@@ -126,6 +140,7 @@ impl BiomeAtmoConfig {
                 max_particles: 105,
                 fog_tint: [0.7, 0.8, 0.95],
                 fog_density_mult: 1.4,
+                fog_density: 0.0020,
                 ambient_tint: [0.8, 0.85, 1.0],
             },
             BiomeType::Toxic => Self {
@@ -140,6 +155,7 @@ impl BiomeAtmoConfig {
                 max_particles: 155,
                 fog_tint: [0.4, 0.55, 0.2],
                 fog_density_mult: 2.0,
+                fog_density: 0.0055,
                 ambient_tint: [0.6, 0.8, 0.3],
             },
             BiomeType::Mountain => Self {
@@ -152,6 +168,7 @@ impl BiomeAtmoConfig {
                 max_particles: 55,
                 fog_tint: [0.6, 0.65, 0.7],
                 fog_density_mult: 1.1,
+                fog_density: 0.0016,
                 ambient_tint: [0.85, 0.85, 0.9],
             },
             BiomeType::Swamp => Self {
@@ -166,6 +183,7 @@ impl BiomeAtmoConfig {
                 max_particles: 140,
                 fog_tint: [0.35, 0.45, 0.3],
                 fog_density_mult: 2.2,
+                fog_density: 0.0060,
                 ambient_tint: [0.6, 0.7, 0.5],
             },
             BiomeType::Crystalline => Self {
@@ -179,6 +197,7 @@ impl BiomeAtmoConfig {
                 max_particles: 125,
                 fog_tint: [0.6, 0.5, 0.8],
                 fog_density_mult: 0.8,
+                fog_density: 0.0014,
                 ambient_tint: [0.8, 0.7, 1.0],
             },
             BiomeType::Ashlands => Self {
@@ -192,6 +211,7 @@ impl BiomeAtmoConfig {
                 max_particles: 155,
                 fog_tint: [0.5, 0.48, 0.45],
                 fog_density_mult: 2.0,
+                fog_density: 0.0050,
                 ambient_tint: [0.7, 0.65, 0.6],
             },
             BiomeType::Jungle => Self {
@@ -206,6 +226,7 @@ impl BiomeAtmoConfig {
                 max_particles: 140,
                 fog_tint: [0.3, 0.5, 0.25],
                 fog_density_mult: 1.6,
+                fog_density: 0.0045,
                 ambient_tint: [0.6, 0.85, 0.4],
             },
             BiomeType::Wasteland => Self {
@@ -219,6 +240,7 @@ impl BiomeAtmoConfig {
                 max_particles: 100,
                 fog_tint: [0.6, 0.55, 0.4],
                 fog_density_mult: 1.5,
+                fog_density: 0.0050,
                 ambient_tint: [0.8, 0.7, 0.5],
             },
             BiomeType::Tundra => Self {
@@ -232,6 +254,7 @@ impl BiomeAtmoConfig {
                 max_particles: 90,
                 fog_tint: [0.68, 0.75, 0.82],
                 fog_density_mult: 1.3,
+                fog_density: 0.0010,
                 ambient_tint: [0.82, 0.86, 0.92],
             },
             BiomeType::SaltFlat => Self {
@@ -244,6 +267,7 @@ impl BiomeAtmoConfig {
                 max_particles: 70,
                 fog_tint: [0.9, 0.88, 0.85],
                 fog_density_mult: 0.9,
+                fog_density: 0.0012,
                 ambient_tint: [1.0, 0.98, 0.95],
             },
             BiomeType::Storm => Self {
@@ -257,6 +281,7 @@ impl BiomeAtmoConfig {
                 max_particles: 180,
                 fog_tint: [0.35, 0.38, 0.42],
                 fog_density_mult: 2.4,
+                fog_density: 0.0060,
                 ambient_tint: [0.5, 0.52, 0.55],
             },
             BiomeType::Fungal => Self {
@@ -270,6 +295,7 @@ impl BiomeAtmoConfig {
                 max_particles: 150,
                 fog_tint: [0.45, 0.35, 0.5],
                 fog_density_mult: 1.7,
+                fog_density: 0.0045,
                 ambient_tint: [0.65, 0.55, 0.75],
             },
             BiomeType::Scorched => Self {
@@ -282,6 +308,7 @@ impl BiomeAtmoConfig {
                 max_particles: 165,
                 fog_tint: [0.4, 0.28, 0.2],
                 fog_density_mult: 1.9,
+                fog_density: 0.0045,
                 ambient_tint: [0.7, 0.45, 0.3],
             },
             BiomeType::Ruins => Self {
@@ -295,12 +322,83 @@ impl BiomeAtmoConfig {
                 max_particles: 85,
                 fog_tint: [0.5, 0.48, 0.46],
                 fog_density_mult: 1.4,
+                fog_density: 0.0030,
                 ambient_tint: [0.75, 0.72, 0.68],
             },
         }
     }
 }
 
+/// Relative Rayleigh scattering of red, green and blue light (∝ 1/λ⁴ at 680, 550 and 440 nm).
+const RAYLEIGH: [f32; 3] = [0.18, 0.41, 1.0];
+
+/// Light a star of this type shines on its planets. Softer than the star disk colors so skies
+/// stay readable; a red dwarf still starves the air of blue.
+fn starlight(star: StarType) -> [f32; 3] {
+    match star {
+        StarType::RedDwarf => [1.0, 0.55, 0.32],
+        StarType::YellowMain => [1.0, 0.96, 0.88],
+        StarType::BlueGiant => [0.72, 0.84, 1.0],
+        StarType::WhiteDwarf => [0.92, 0.95, 1.0],
+        StarType::BinaryStar => [1.0, 0.86, 0.66],
+    }
+}
+
+/// Dust, ash, spores or spray a biome hangs in its air: (color, amount 0..1). Aerosols scatter all
+/// wavelengths about equally, so they wash the Rayleigh blue out toward their own color.
+fn aerosol(biome: BiomeType) -> ([f32; 3], f32) {
+    match biome {
+        BiomeType::Volcanic | BiomeType::Ashlands | BiomeType::Scorched => ([0.62, 0.38, 0.2], 0.55),
+        BiomeType::Desert | BiomeType::Badlands | BiomeType::Wasteland => ([0.78, 0.62, 0.42], 0.35),
+        BiomeType::Toxic | BiomeType::Swamp => ([0.48, 0.62, 0.22], 0.45),
+        BiomeType::HiveWorld | BiomeType::Fungal => ([0.6, 0.5, 0.35], 0.35),
+        BiomeType::Storm => ([0.5, 0.53, 0.58], 0.45),
+        BiomeType::SaltFlat | BiomeType::Crystalline => ([0.9, 0.9, 0.92], 0.2),
+        BiomeType::Jungle => ([0.7, 0.8, 0.75], 0.2),
+        BiomeType::Frozen | BiomeType::Tundra | BiomeType::Mountain | BiomeType::Ruins => ([0.8, 0.82, 0.85], 0.12),
+    }
+}
+
+/// Scale `c` so its brightest channel is `peak` (keeps the hue, fixes the brightness).
+fn with_peak(c: [f32; 3], peak: f32) -> [f32; 3] {
+    let max = c[0].max(c[1]).max(c[2]).max(1e-4);
+    [c[0] / max * peak, c[1] / max * peak, c[2] / max * peak]
+}
+
+fn mix3(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
+
+/// Sky palette for `planet` under a `star`: Rayleigh blue, washed toward the primary biome's
+/// aerosols (ash, dust, spores) and lit by the star's light. Sunsets are the starlight left after a
+/// long path through that air. Airless worlds get a dim, near-black sky.
+pub fn atmosphere_params(planet: &Planet, star: StarType) -> AtmosphereParams {
+    let light = starlight(star);
+    let (haze, amount) = aerosol(planet.primary_biome);
+
+    let scatter: [f32; 3] = std::array::from_fn(|i| (RAYLEIGH[i] * (1.0 - amount) + haze[i] * amount) * light[i]);
+    let mut zenith = with_peak(scatter, 0.85);
+    if planet.has_atmosphere {
+        // A touch of the planet's own generated tint so neighbouring worlds still differ
+        zenith = mix3(zenith, planet.atmosphere_color_rgb(), 0.2);
+    }
+    // Near the horizon the sky whitens toward the starlight (more air, multiple scattering)
+    let horizon = mix3(zenith, with_peak(light, 0.9), 0.45 + amount * 0.2);
+
+    // What survives a low, long path: blue scatters out first, aerosols absorb their complement
+    let path = 2.2 + amount * 1.5;
+    let extinction: [f32; 3] = std::array::from_fn(|i| RAYLEIGH[i] * (1.0 - amount) + (1.0 - haze[i]) * amount);
+    let sunset = with_peak(std::array::from_fn(|i| light[i] * (-extinction[i] * path).exp()), 0.98);
+    let sun_color = with_peak(std::array::from_fn(|i| light[i] * (-extinction[i] * 0.3).exp()), 1.0);
+
+    let params = AtmosphereParams { zenith, horizon, sunset, sun_color };
+    if planet.has_atmosphere {
+        params
+    } else {
+        AtmosphereParams { zenith: with_peak(zenith, 0.2), horizon: with_peak(horizon, 0.3), sunset: with_peak(sunset, 0.4), ..params }
+    }
+}
+
 /// Biome volumetric atmosphere manager.
 pub struct BiomeAtmosphere {
     pub particles: Vec<AtmoParticle>,
@@ -326,6 +424,24 @@ impl BiomeAtmosphere {
         self.biome = biome;
     }
 
+    /// Renderer fog for this biome under `weather`: clear skies keep the biome's base haze, overcast
+    /// and rain thicken it, and heavy snow (blizzards) cuts visibility hardest. `sun_elevation` is
+    /// the sun direction's y; `ground_height` is the terrain height under the camera.
+    pub fn fog_params(&self, weather: &Weather, sun_elevation: f32, ground_height: f32) -> FogParams {
+        let overcast = 0.6 + 0.4 * weather.fog_density / 0.0003;
+        let precipitation = 1.0 + weather.rain_intensity.clamp(0.0, 1.0) + 4.0 * weather.snow_amount();
+        let daylight = ((sun_elevation + 0.1) / 0.4).clamp(0.0, 1.0);
+        let light = 0.06 + 0.94 * daylight * daylight * (3.0 - 2.0 * daylight);
+        let tint = self.config.fog_tint;
+        FogParams {
+            color: [tint[0] * light, tint[1] * light, tint[2] * light],
+            density: self.config.fog_density * overcast * precipitation,
+            height_falloff: FOG_HEIGHT_FALLOFF,
+            base_height: ground_height,
+            sky_blend: FOG_SKY_BLEND,
+        }
+    }
+
     pub fn update(&mut self, dt: f32, cam_pos: Vec3, time: f32) {
         // Spawn new particles
         self.spawn_accum += self.config.spawn_rate * dt;
@@ -553,3 +669,58 @@ impl BiomeAtmosphere {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::WeatherState;
+
+    fn settled(state: WeatherState) -> Weather {
+        let mut w = Weather::new();
+        w.hold_timer = 1000.0;
+        w.set_target(state);
+        for _ in 0..2000 {
+            w.update(0.1);
+        }
+        w
+    }
+
+    #[test]
+    fn fog_is_hazier_in_wasteland_than_tundra_and_thickest_in_a_blizzard() {
+        let clear = settled(WeatherState::Clear);
+        let tundra = BiomeAtmosphere::new(BiomeType::Tundra);
+        let wasteland = BiomeAtmosphere::new(BiomeType::Wasteland);
+        let crisp = tundra.fog_params(&clear, 0.8, 0.0);
+        assert!(wasteland.fog_params(&clear, 0.8, 0.0).density > crisp.density * 3.0);
+
+        let blizzard = tundra.fog_params(&settled(WeatherState::Snow), 0.8, 0.0);
+        assert!(blizzard.density > crisp.density * 4.0);
+        assert!(tundra.fog_params(&clear, -0.5, 0.0).color[0] < crisp.color[0] * 0.2, "fog darkens at night");
+    }
+
+    fn planet_with(biome: BiomeType) -> Planet {
+        let mut planet = Planet::generate(7);
+        planet.primary_biome = biome;
+        planet.has_atmosphere = true;
+        planet.atmosphere_color = Vec3::new(0.5, 0.6, 0.8);
+        planet
+    }
+
+    #[test]
+    fn sky_follows_the_star_and_the_biome() {
+        let volcanic_red = atmosphere_params(&planet_with(BiomeType::Volcanic), StarType::RedDwarf);
+        let [r, g, b] = volcanic_red.zenith;
+        assert!(r > g && g > b, "sickly orange sky under a red star: {:?}", volcanic_red.zenith);
+
+        let clear_yellow = atmosphere_params(&planet_with(BiomeType::Tundra), StarType::YellowMain);
+        assert!(clear_yellow.zenith[2] > clear_yellow.zenith[0] * 2.0, "clear air is blue");
+        let clear_blue = atmosphere_params(&planet_with(BiomeType::Tundra), StarType::BlueGiant);
+        let blueness = |p: &AtmosphereParams| p.zenith[2] / p.zenith[0];
+        assert!(blueness(&clear_blue) > blueness(&clear_yellow));
+
+        // Sunsets are always redder than the midday horizon
+        for params in [volcanic_red, clear_yellow, clear_blue] {
+            assert!(params.sunset[0] / params.sunset[2] > params.horizon[0] / params.horizon[2]);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Hive Queen: the boss guarding a Hive Destruction site, fought in phases once the hives burn.
+//!
+//! She starts sealed in armor: gunfire glances off for a fraction of its damage and her abdomen
+//! doesn't count as a weakpoint. Every so often she rears up to call a brood of adds out of the
+//! ground, and her carapace stays open for a few seconds after. Below a health threshold she
+//! enrages: the carapace never closes again, broods come faster and she closes in faster.
+
+/// Damage multiplier on gunfire while her carapace is sealed.
+pub const QUEEN_ARMOR_MULT: f32 = 0.15;
+/// Seconds between brood calls while armored, and adds per brood.
+const BROOD_INTERVAL: f32 = 14.0;
+const BROOD_SIZE: usize = 6;
+/// Seconds she stays reared up, calling, before she moves again.
+pub const BROOD_CALL_SECS: f32 = 2.5;
+/// Seconds the carapace stays open after a brood call.
+const EXPOSED_SECS: f32 = 7.0;
+/// Health fraction at which she enrages.
+pub const RAGE_THRESHOLD: f32 = 0.35;
+const RAGE_BROOD_INTERVAL: f32 = 8.0;
+const RAGE_BROOD_SIZE: usize = 4;
+/// Move speed and attack damage multipliers once enraged.
+pub const RAGE_SPEED_MULT: f32 = 1.6;
+pub const RAGE_DAMAGE_MULT: f32 = 1.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueenPhase {
+    /// Carapace sealed: weakpoint hidden, gunfire mostly glances off.
+    Armored,
+    /// Carapace open after a brood call: the abdomen is a weakpoint.
+    Exposed,
+    /// Below the rage threshold: always open, broods more often, faster.
+    Enraged,
+}
+
+/// Something the game has to act on this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueenEvent {
+    /// Call `count` adds out of the ground around her.
+    Brood { count: usize },
+    /// The carapace closed again.
+    Sealed,
+    /// She crossed the rage threshold.
+    Enraged,
+}
+
+/// Boss state on the Queen's bug entity. Movement and her slam come from the horde AI's Tanker
+/// behavior; this drives the fight's phases on top of it.
+#[derive(Debug, Clone, Copy)]
+pub struct QueenBoss {
+    pub phase: QueenPhase,
+    /// Seconds left in the Exposed phase.
+    phase_timer: f32,
+    /// Seconds until the next brood call.
+    brood_timer: f32,
+}
+
+impl QueenBoss {
+    pub fn new() -> Self {
+        Self {
+            phase: QueenPhase::Armored,
+            phase_timer: 0.0,
+            // First brood comes early so the trooper sees how to open her up
+            brood_timer: BROOD_INTERVAL * 0.5,
+        }
+    }
+
+    /// Advance the fight given her current health (0–1).
+    pub fn update(&mut self, health_fraction: f32, dt: f32) -> Option<QueenEvent> {
+        if self.phase != QueenPhase::Enraged && health_fraction <= RAGE_THRESHOLD {
+            self.phase = QueenPhase::Enraged;
+            self.brood_timer = RAGE_BROOD_INTERVAL * 0.5;
+            return Some(QueenEvent::Enraged);
+        }
+        match self.phase {
+            QueenPhase::Armored => {
+                self.brood_timer -= dt;
+                if self.brood_timer <= 0.0 {
+                    self.brood_timer = BROOD_INTERVAL;
+                    self.phase = QueenPhase::Exposed;
+                    self.phase_timer = EXPOSED_SECS;
+                    return Some(QueenEvent::Brood { count: BROOD_SIZE });
+                }
+            }
+            QueenPhase::Exposed => {
+                self.phase_timer -= dt;
+                if self.phase_timer <= 0.0 {
+                    self.phase = QueenPhase::Armored;
+                    return Some(QueenEvent::Sealed);
+                }
+            }
+            QueenPhase::Enraged => {
+                self.brood_timer -= dt;
+                if self.brood_timer <= 0.0 {
+                    self.brood_timer = RAGE_BROOD_INTERVAL;
+                    return Some(QueenEvent::Brood { count: RAGE_BROOD_SIZE });
+                }
+            }
+        }
+        None
+    }
+
+    pub fn is_armored(&self) -> bool {
+        self.phase == QueenPhase::Armored
+    }
+
+    /// Filter a shot's region multiplier and weakpoint flag through her carapace.
+    pub fn filter_hit(&self, region_mult: f32, is_weakpoint: bool) -> (f32, bool) {
+        if self.is_armored() {
+            (region_mult * QUEEN_ARMOR_MULT, false)
+        } else {
+            (region_mult, is_weakpoint)
+        }
+    }
+
+    /// Phase tag for the boss bar.
+    pub fn label(&self) -> &'static str {
+        match self.phase {
+            QueenPhase::Armored => "ARMORED",
+            QueenPhase::Exposed => "EXPOSED",
+            QueenPhase::Enraged => "ENRAGED",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brood_call_opens_the_carapace_until_it_seals_again() {
+        let mut queen = QueenBoss::new();
+        assert_eq!(queen.filter_hit(3.0, true), (3.0 * QUEEN_ARMOR_MULT, false));
+
+        let mut events = Vec::new();
+        for _ in 0..100 {
+            events.extend(queen.update(1.0, 0.1));
+        }
+        assert_eq!(events, vec![QueenEvent::Brood { count: BROOD_SIZE }]);
+        assert_eq!(queen.phase, QueenPhase::Exposed);
+        assert_eq!(queen.filter_hit(3.0, true), (3.0, true));
+
+        for _ in 0..(EXPOSED_SECS * 10.0) as usize + 1 {
+            events.extend(queen.update(1.0, 0.1));
+        }
+        assert_eq!(events.last(), Some(&QueenEvent::Sealed));
+        assert!(queen.is_armored());
+    }
+
+    #[test]
+    fn rage_keeps_her_open_and_broods_faster() {
+        let mut queen = QueenBoss::new();
+        assert_eq!(queen.update(RAGE_THRESHOLD - 0.01, 0.1), Some(QueenEvent::Enraged));
+        assert!(!queen.is_armored());
+
+        let mut broods = 0;
+        for _ in 0..(RAGE_BROOD_INTERVAL * 10.0) as usize * 2 {
+            if let Some(QueenEvent::Brood { count }) = queen.update(0.2, 0.1) {
+                assert_eq!(count, RAGE_BROOD_SIZE);
+                broods += 1;
+            }
+            assert_eq!(queen.phase, QueenPhase::Enraged);
+        }
+        assert!(broods >= 2);
+    }
+}
@@ -2,6 +2,8 @@
 
 use engine_core::{AIComponent, AIState, Health, Transform, Velocity, Vec3};
 
+use crate::bug_entity::HitRegion;
+
 /// Biome-specific bug variant (one per biome). Affects stats, color, and on-death behavior.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BugVariant {
@@ -130,6 +132,8 @@ pub struct Bug {
     pub move_speed: f32,
     /// Biome-specific variant (affects stats, color, death effect).
     pub variant: Option<BugVariant>,
+    /// Where the bug is in its type's signature move (charge, leap, slam, spit).
+    pub maneuver: Maneuver,
 }
 
 impl Bug {
@@ -144,6 +148,7 @@ impl Bug {
             BugType::Spitter => (10.0, 4.0),
             BugType::Tanker => (30.0, 3.0),
             BugType::Hopper => (10.0, 8.0),
+            BugType::Queen => (45.0, 2.5),
         };
 
         let mult_damage = variant.map(|v| v.damage_mult()).unwrap_or(1.0);
@@ -154,6 +159,18 @@ impl Bug {
             attack_damage: attack_damage * mult_damage,
             move_speed: move_speed * mult_speed,
             variant,
+            maneuver: Maneuver::Ready { cooldown: bug_type.behavior().cooldown() * 0.5 },
+        }
+    }
+
+    /// Height (m) above its terrain stance: a leaping Hopper follows a parabola.
+    pub fn lift(&self) -> f32 {
+        match self.maneuver {
+            Maneuver::Active { elapsed, .. } if self.bug_type.behavior() == BugBehavior::Hopper => {
+                let t = (elapsed / LEAP_DURATION).clamp(0.0, 1.0);
+                4.0 * LEAP_HEIGHT * t * (1.0 - t)
+            }
+            _ => 0.0,
         }
     }
 
@@ -165,6 +182,147 @@ impl Bug {
     }
 }
 
+/// Charger wind-up (rearing) before the dash, seconds.
+pub const CHARGE_WINDUP: f32 = 0.6;
+/// Longest a charge lasts before the Charger gives up, seconds.
+pub const CHARGE_DURATION: f32 = 1.1;
+/// Dash speed as a multiple of the Charger's run speed.
+pub const CHARGE_SPEED_MULT: f32 = 1.9;
+/// A dashing Charger within this distance (m) of its target bowls it over.
+pub const CHARGE_HIT_RADIUS: f32 = 1.8;
+/// Hopper crouch before the leap, seconds.
+pub const LEAP_WINDUP: f32 = 0.35;
+/// Hopper time in the air, seconds.
+pub const LEAP_DURATION: f32 = 0.8;
+/// Apex of the Hopper's arc above its stance, m.
+pub const LEAP_HEIGHT: f32 = 3.5;
+/// A Hopper landing within this distance (m) of its target pounces on it.
+pub const POUNCE_RADIUS: f32 = 2.2;
+/// Tanker forelegs-up wind-up before the slam, seconds.
+pub const SLAM_WINDUP: f32 = 1.0;
+/// Radius (m) of the Tanker's ground slam; damage falls off to the edge.
+pub const SLAM_RADIUS: f32 = 6.0;
+/// Spitter rear-back before the glob leaves, seconds.
+pub const SPIT_WINDUP: f32 = 0.4;
+/// Horizontal speed (m/s) of a lobbed acid glob; sets the arc's flight time.
+pub const SPIT_SPEED: f32 = 16.0;
+/// Gravity (m/s²) acting on acid globs.
+pub const ACID_GRAVITY: f32 = 14.0;
+/// An acid glob bursting within this distance (m) of the trooper splashes them.
+pub const ACID_SPLASH_RADIUS: f32 = 1.4;
+
+/// Per-type signature behavior, dispatched by the horde AI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BugBehavior {
+    /// Warriors: flow-field chase and bite.
+    Melee,
+    /// Spitters: kite at range and lob acid.
+    Spitter,
+    /// Chargers: rear up, then dash in a straight line and bowl the target over.
+    Charger,
+    /// Hoppers: crouch, then leap onto the target.
+    Hopper,
+    /// Tankers: slow and tough, ground-slam when the target gets close.
+    Tanker,
+}
+
+impl BugBehavior {
+    /// Distance band (m) to the target in which the signature move triggers.
+    pub fn trigger_range(&self) -> (f32, f32) {
+        match self {
+            BugBehavior::Melee => (0.0, 0.0),
+            BugBehavior::Spitter => (6.0, 30.0),
+            BugBehavior::Charger => (6.0, 22.0),
+            BugBehavior::Hopper => (4.0, 14.0),
+            BugBehavior::Tanker => (0.0, 4.5),
+        }
+    }
+
+    /// Seconds before the move can be used again.
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            BugBehavior::Melee => 0.0,
+            BugBehavior::Spitter => 2.6,
+            BugBehavior::Charger => 3.5,
+            BugBehavior::Hopper => 2.5,
+            BugBehavior::Tanker => 4.0,
+        }
+    }
+
+    /// Telegraph time before the move lands, seconds.
+    pub fn windup(&self) -> f32 {
+        match self {
+            BugBehavior::Melee => 0.0,
+            BugBehavior::Spitter => SPIT_WINDUP,
+            BugBehavior::Charger => CHARGE_WINDUP,
+            BugBehavior::Hopper => LEAP_WINDUP,
+            BugBehavior::Tanker => SLAM_WINDUP,
+        }
+    }
+
+    /// Vulnerable pause after the move, seconds (a Charger that missed shows its soft rear).
+    pub fn recovery(&self) -> f32 {
+        match self {
+            BugBehavior::Melee | BugBehavior::Spitter => 0.0,
+            BugBehavior::Charger => 1.2,
+            BugBehavior::Hopper => 0.5,
+            BugBehavior::Tanker => 1.5,
+        }
+    }
+
+    /// Spitters hold this distance band (m): closer and they back off, farther and they close in.
+    pub fn kite_range(&self) -> Option<(f32, f32)> {
+        match self {
+            BugBehavior::Spitter => Some((10.0, 22.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Progress through a bug's signature move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Maneuver {
+    /// Moving normally; the move is available once `cooldown` runs out.
+    Ready { cooldown: f32 },
+    /// Telegraphing toward `direction` (XZ, unit).
+    WindUp { timer: f32, direction: Vec3 },
+    /// Committed: dashing or airborne along `direction` at `speed` m/s.
+    Active { elapsed: f32, direction: Vec3, speed: f32 },
+    /// Catching its breath after the move.
+    Recover { timer: f32 },
+}
+
+/// Something a bug's move did this frame, for the game to apply to the trooper and the world.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BugStrike {
+    /// A Spitter lobbed an acid glob.
+    Spit { from: Vec3, velocity: Vec3, damage: f32 },
+    /// A dashing Charger at `from` hit the target, shoving it along `direction`.
+    Charge { from: Vec3, direction: Vec3, damage: f32 },
+    /// A Hopper landed on the target.
+    Pounce { from: Vec3, damage: f32 },
+    /// A Tanker slammed the ground at `center`.
+    Slam { center: Vec3, damage: f32 },
+}
+
+/// An acid glob in flight (ballistic); bursts on the trooper or the ground.
+#[derive(Debug, Clone)]
+pub struct AcidGlob {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub damage: f32,
+}
+
+/// Launch velocity for a glob from `from` to land on `to` after `flight_time` seconds under `gravity`.
+pub fn lob_velocity(from: Vec3, to: Vec3, flight_time: f32, gravity: f32) -> Vec3 {
+    let delta = to - from;
+    Vec3::new(
+        delta.x / flight_time,
+        (delta.y + 0.5 * gravity * flight_time * flight_time) / flight_time,
+        delta.z / flight_time,
+    )
+}
+
 /// Types of bugs with different behaviors.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BugType {
@@ -178,9 +336,23 @@ pub enum BugType {
     Tanker,
     /// Flying/jumping bug.
     Hopper,
+    /// Hive Queen boss (see `boss`): spawned as the Hive Destruction finale, never by the horde.
+    Queen,
 }
 
 impl BugType {
+    /// Signature behavior the horde AI runs for this type.
+    pub fn behavior(&self) -> BugBehavior {
+        match self {
+            BugType::Warrior => BugBehavior::Melee,
+            BugType::Charger => BugBehavior::Charger,
+            BugType::Spitter => BugBehavior::Spitter,
+            BugType::Tanker => BugBehavior::Tanker,
+            BugType::Hopper => BugBehavior::Hopper,
+            BugType::Queen => BugBehavior::Tanker,
+        }
+    }
+
     /// Get the color for this bug type (chitinous STE-style: dark carapace, readable silhouettes).
     /// Renderer applies health factor (0.5–1.0) and variant tint.
     pub fn color(&self) -> [f32; 4] {
@@ -190,6 +362,7 @@ impl BugType {
             BugType::Spitter => [0.28, 0.38, 0.22, 1.0],   // Dark body, greenish sac tint
             BugType::Tanker => [0.38, 0.34, 0.30, 1.0],    // Grey-brown, heavy plates
             BugType::Hopper => [0.32, 0.26, 0.22, 1.0],    // Similar to Warrior, angular
+            BugType::Queen => [0.36, 0.22, 0.34, 1.0],     // Bruised purple, swollen brood sacs
         }
     }
 
@@ -201,6 +374,7 @@ impl BugType {
             BugType::Spitter => Vec3::splat(0.9),
             BugType::Tanker => Vec3::splat(2.0),
             BugType::Hopper => Vec3::new(0.7, 0.6, 0.7),
+            BugType::Queen => Vec3::new(3.5, 3.0, 4.5),
         }
     }
 
@@ -212,10 +386,50 @@ impl BugType {
             BugType::Spitter => 40.0,
             BugType::Tanker => 200.0,
             BugType::Hopper => 25.0,
+            BugType::Queen => 3000.0,
+        }
+    }
+
+    /// Weak spots for this bug type: where aiming pays off.
+    pub fn weakpoints(&self) -> &'static [Weakpoint] {
+        const HEAD: Weakpoint = Weakpoint { region: HitRegion::Head, damage_mult: 2.0, glows: false };
+        match self {
+            BugType::Warrior | BugType::Hopper => &[HEAD],
+            // Armored head; the soft rear is the target once it has charged past
+            BugType::Charger => &[Weakpoint { region: HitRegion::Abdomen, damage_mult: 2.0, glows: false }],
+            // Glowing acid sac
+            BugType::Spitter => &[HEAD, Weakpoint { region: HitRegion::Abdomen, damage_mult: 2.5, glows: true }],
+            BugType::Tanker => &[Weakpoint { region: HitRegion::Abdomen, damage_mult: 2.5, glows: true }],
+            // Brood sac under the carapace; only counts while she's open (`QueenBoss::filter_hit`)
+            BugType::Queen => &[Weakpoint { region: HitRegion::Abdomen, damage_mult: 3.0, glows: true }],
+        }
+    }
+
+    pub fn weakpoint(&self, region: HitRegion) -> Option<&'static Weakpoint> {
+        self.weakpoints().iter().find(|w| w.region == region)
+    }
+
+    /// Damage multiplier for a hit on `region`: the weakpoint's if it is one. A head that isn't a
+    /// weakpoint is armored and takes thorax damage.
+    pub fn region_damage_mult(&self, region: HitRegion) -> f32 {
+        match self.weakpoint(region) {
+            Some(weakpoint) => weakpoint.damage_mult,
+            None if region == HitRegion::Head => HitRegion::Thorax.damage_mult(),
+            None => region.damage_mult(),
         }
     }
 }
 
+/// A body region that takes extra damage and gives the weakpoint hit response (hit-marker,
+/// crunch, bigger gore burst).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weakpoint {
+    pub region: HitRegion,
+    pub damage_mult: f32,
+    /// Drawn with an emissive glow so it reads as a target.
+    pub glows: bool,
+}
+
 /// Bundle of components for spawning a bug.
 pub struct BugBundle {
     pub transform: Transform,
@@ -258,3 +472,30 @@ impl BugBundle {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lobbed_acid_lands_on_its_target() {
+        let from = Vec3::new(0.0, 1.0, 0.0);
+        let to = Vec3::new(12.0, 2.5, -5.0);
+        let flight_time = 1.2;
+        let v = lob_velocity(from, to, flight_time, ACID_GRAVITY);
+        let landed = from + v * flight_time - Vec3::Y * 0.5 * ACID_GRAVITY * flight_time * flight_time;
+        assert!(landed.distance(to) < 1e-3);
+        assert!(v.y > 0.0, "lobbed upward, not fired flat");
+    }
+
+    #[test]
+    fn each_bug_type_has_its_own_behavior() {
+        let behaviors = [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper]
+            .map(|t| t.behavior());
+        for (i, a) in behaviors.iter().enumerate() {
+            assert!(behaviors[i + 1..].iter().all(|b| b != a));
+        }
+        assert!(BugBehavior::Spitter.kite_range().is_some());
+        assert!(BugBehavior::Charger.trigger_range().0 > 0.0, "chargers need a run-up");
+    }
+}
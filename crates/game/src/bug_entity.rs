@@ -1,13 +1,146 @@
 //! Complete bug entity with physics, ragdoll, and rendering integration
 
-use engine_core::{Health, Transform, Velocity, Vec3};
+use engine_core::{GameRng, Health, PreviousTransform, Transform, Velocity, Vec3};
 use glam::Quat;
 use hecs::{Entity, World};
 use physics::{PhysicsWorld, ColliderHandle, RigidBodyHandle};
 use rand::prelude::*;
+use renderer::{DEFORM_HALF_SIZE, DEFORM_TEXTURE_SIZE};
 
 use crate::bug::{Bug, BugType};
 
+/// Fastest a killing blow can launch a ragdoll (m/s).
+const MAX_RAGDOLL_LAUNCH_SPEED: f32 = 14.0;
+/// Upward velocity added on death so the body leaves the ground (m/s).
+const RAGDOLL_LIFT: f32 = 2.5;
+/// Longest a single volley can keep a bug staggered (s).
+const MAX_STAGGER: f32 = 0.6;
+/// Stagger seconds per point of (damage x knockback / size).
+const STAGGER_PER_IMPULSE: f32 = 0.004;
+/// Recoil distance (m) per point of (damage x knockback / size), and its cap.
+const RECOIL_PER_IMPULSE: f32 = 0.004;
+const MAX_RECOIL: f32 = 0.5;
+/// Stagger time at which a bug is slowed the most, and how much that is (fraction of speed lost).
+const FULL_STAGGER: f32 = 0.3;
+const STAGGER_SLOW: f32 = 0.85;
+
+// Authored bug mesh layout (unit space, Y-up, bug faces +Z; see authored_bug_meshes).
+const MESH_SPINE_Y: f32 = 0.2;
+const MESH_BELLY_Y: f32 = 0.0;
+const MESH_BODY_HALF_WIDTH: f32 = 0.28;
+const MESH_NOSE_Z: f32 = 0.9;
+const MESH_HEAD_Z: f32 = 0.55;
+const MESH_ABDOMEN_Z: f32 = -0.15;
+const MESH_TAIL_Z: f32 = -0.55;
+const MESH_FRONT_LEGS_Z: f32 = 0.25;
+const MESH_REAR_LEGS_Z: f32 = -0.05;
+
+/// Damage to one leg (fraction of max health) that shoots it off.
+const LEG_SEVER_FRACTION: f32 = 0.2;
+/// Damage to the abdomen (fraction of max health) that blows it off.
+const ABDOMEN_SEVER_FRACTION: f32 = 0.35;
+/// Speed lost per missing leg, and the crawl that two or more missing legs reduce a bug to.
+const SPEED_LOSS_PER_LEG: f32 = 0.12;
+const CRAWL_LEGS_LOST: u32 = 2;
+const CRAWL_SPEED_SCALE: f32 = 0.3;
+/// Fraction of max health a bug with a severed abdomen bleeds per second.
+const ABDOMEN_BLEED_RATE: f32 = 0.04;
+/// Seconds between ichor drips from a severed abdomen.
+const LEAK_INTERVAL: f32 = 0.35;
+/// Seconds a burrowing bug takes to claw its way out once it breaks the surface.
+pub const EMERGE_DURATION: f32 = 0.9;
+/// How deep (in body heights) a burrowed bug waits below its stance.
+const BURROW_DEPTH: f32 = 1.6;
+
+/// Where a shot landed on a bug, from the authored mesh proportions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitRegion {
+    Head,
+    Thorax,
+    Abdomen,
+    /// Leg 0..6 in `limb_damage` order: pairs rear to front, even = mesh -X side.
+    Leg(usize),
+}
+
+impl HitRegion {
+    /// Classify a shot from `origin` along `direction` that hit the bug at `bug`. Works in the
+    /// mesh's unit space: finds where the shot passes the spine, so it doesn't depend on where
+    /// the (much simpler) capsule collider was touched.
+    pub fn from_shot(bug: &Transform, origin: Vec3, direction: Vec3) -> Self {
+        let inv = bug.rotation.inverse();
+        let scale = bug.scale.max(Vec3::splat(0.01));
+        let o = inv * (origin - bug.position) / scale;
+        let d = (inv * direction / scale).normalize_or_zero();
+
+        // Closest approach to the spine (a line along Z); shots along it enter at nose or tail.
+        let across = d.x * d.x + d.y * d.y;
+        let t = if across < 0.01 {
+            let entry_z = if d.z < 0.0 { MESH_NOSE_Z } else { MESH_TAIL_Z };
+            (entry_z - o.z) / d.z
+        } else {
+            -(o.x * d.x + (o.y - MESH_SPINE_Y) * d.y) / across
+        };
+        let p = o + d * t.max(0.0);
+        let z = p.z.clamp(MESH_TAIL_Z, MESH_NOSE_Z);
+
+        if p.y < MESH_BELLY_Y || p.x.abs() > MESH_BODY_HALF_WIDTH {
+            // Low shots from the side take the near leg
+            let neg_x = if p.x.abs() > MESH_BODY_HALF_WIDTH { p.x < 0.0 } else { o.x < 0.0 };
+            let pair = if z > MESH_FRONT_LEGS_Z {
+                2
+            } else if z < MESH_REAR_LEGS_Z {
+                0
+            } else {
+                1
+            };
+            HitRegion::Leg(pair * 2 + usize::from(!neg_x))
+        } else if z > MESH_HEAD_Z {
+            HitRegion::Head
+        } else if z < MESH_ABDOMEN_Z {
+            HitRegion::Abdomen
+        } else {
+            HitRegion::Thorax
+        }
+    }
+
+    /// Center of the region in the authored mesh's unit space (legs have none).
+    pub fn mesh_center(self) -> Option<Vec3> {
+        match self {
+            HitRegion::Head => Some(Vec3::new(0.0, MESH_SPINE_Y, (MESH_HEAD_Z + MESH_NOSE_Z) * 0.5)),
+            HitRegion::Thorax => Some(Vec3::new(0.0, MESH_SPINE_Y, (MESH_ABDOMEN_Z + MESH_HEAD_Z) * 0.5)),
+            HitRegion::Abdomen => Some(Vec3::new(0.0, MESH_SPINE_Y, (MESH_TAIL_Z + MESH_ABDOMEN_Z) * 0.5)),
+            HitRegion::Leg(_) => None,
+        }
+    }
+
+    /// Damage multiplier: headshots are doubled, the abdomen and legs aren't vital.
+    pub fn damage_mult(self) -> f32 {
+        match self {
+            HitRegion::Head => 2.0,
+            HitRegion::Thorax => 1.0,
+            HitRegion::Abdomen => 0.75,
+            HitRegion::Leg(_) => 0.5,
+        }
+    }
+}
+
+/// A body part shot off a living (or dying) bug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severance {
+    Leg(usize),
+    Abdomen,
+}
+
+impl Severance {
+    /// Size of the severed-part debris relative to the bug's scale.
+    pub fn debris_scale(self) -> Vec3 {
+        match self {
+            Severance::Leg(_) => Vec3::new(0.08, 0.08, 0.45),
+            Severance::Abdomen => Vec3::new(0.32, 0.28, 0.4),
+        }
+    }
+}
+
 /// Physics-enabled bug with ragdoll support
 #[derive(Debug, Clone)]
 pub struct PhysicsBug {
@@ -21,12 +154,35 @@ pub struct PhysicsBug {
     pub ragdoll_time: f32,
     /// Death animation phase
     pub death_phase: DeathPhase,
-    /// Hit impact velocity (for ragdoll launch)
+    /// Hit impact velocity from the last hit; on death it becomes the ragdoll launch velocity
     pub impact_velocity: Vec3,
-    /// Limb damage for procedural death
+    /// Damage taken per leg; a leg is shot off past `LEG_SEVER_FRACTION` of max health
     pub limb_damage: [f32; 6], // legs
+    /// Bitmask of legs shot off (bit i = `limb_damage[i]`)
+    pub severed_legs: u8,
+    /// Damage taken to the abdomen
+    pub abdomen_damage: f32,
+    /// Abdomen shot off: the bug leaks ichor and slowly bleeds out
+    pub abdomen_severed: bool,
+    /// Seconds until the next ichor drip from a severed abdomen
+    pub leak_timer: f32,
     /// Gore spawned
     pub gore_spawned: bool,
+    /// Seconds left of hit-reaction stagger; slows the bug while > 0
+    pub stagger_timer: f32,
+    /// Burrow ambush: underground, clawing out, or surfaced (the only phase that moves and bites)
+    pub emerge: EmergePhase,
+}
+
+/// Burrowing ambush state. A bug spawned underground waits, breaks the surface, then climbs out.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EmergePhase {
+    #[default]
+    Surfaced,
+    /// Underground; the ground trembles until it breaks the surface in `timer` seconds
+    Burrowed { timer: f32 },
+    /// Climbing out of its hole, `progress` 0 → 1
+    Emerging { progress: f32 },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -54,7 +210,13 @@ impl Default for PhysicsBug {
             death_phase: DeathPhase::Alive,
             impact_velocity: Vec3::ZERO,
             limb_damage: [0.0; 6],
+            severed_legs: 0,
+            abdomen_damage: 0.0,
+            abdomen_severed: false,
+            leak_timer: 0.0,
             gore_spawned: false,
+            stagger_timer: 0.0,
+            emerge: EmergePhase::Surfaced,
         }
     }
 }
@@ -64,12 +226,131 @@ impl PhysicsBug {
         Self::default()
     }
 
-    /// Activate ragdoll with impact force — Euphoria-style violent launch
-    pub fn activate_ragdoll(&mut self, impact_dir: Vec3, force: f32) {
+    /// Activate ragdoll with the velocity it is launched at — Euphoria-style violent launch
+    pub fn activate_ragdoll(&mut self, launch_velocity: Vec3) {
         self.is_ragdoll = true;
         self.death_phase = DeathPhase::Launched;
         self.ragdoll_time = 0.0;
-        self.impact_velocity = impact_dir * force * 0.6;
+        self.impact_velocity = launch_velocity;
+    }
+
+    /// Launch velocity for the killing blow: the stored hit impulse, capped so a rocket doesn't
+    /// send a warrior into orbit, plus a small upward pop so it leaves the ground.
+    pub fn ragdoll_launch_velocity(impact_velocity: Vec3) -> Vec3 {
+        impact_velocity.clamp_length_max(MAX_RAGDOLL_LAUNCH_SPEED) + Vec3::Y * RAGDOLL_LIFT
+    }
+
+    /// Flinch from a non-lethal hit travelling along `direction`. Heavier hits (damage x weapon
+    /// knockback) stagger longer; big bugs shrug more off. Returns the positional recoil to apply.
+    pub fn apply_hit_stagger(&mut self, direction: Vec3, damage: f32, knockback: f32, size: f32) -> Vec3 {
+        let impulse = damage * knockback / size.max(0.5);
+        if impulse <= 0.0 {
+            return Vec3::ZERO;
+        }
+        self.stagger_timer = (self.stagger_timer + impulse * STAGGER_PER_IMPULSE).min(MAX_STAGGER);
+        let push = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+        push * (impulse * RECOIL_PER_IMPULSE).min(MAX_RECOIL)
+    }
+
+    /// Movement speed multiplier while staggered (1.0 when not staggered).
+    pub fn stagger_speed_scale(&self) -> f32 {
+        1.0 - STAGGER_SLOW * (self.stagger_timer / FULL_STAGGER).min(1.0)
+    }
+
+    /// Record a hit on `region`. Returns the part that came off, if this hit severed one.
+    pub fn register_limb_hit(&mut self, region: HitRegion, damage: f32, max_health: f32) -> Option<Severance> {
+        match region {
+            HitRegion::Leg(i) if i < self.limb_damage.len() && self.severed_legs & (1 << i) == 0 => {
+                self.limb_damage[i] += damage;
+                if self.limb_damage[i] >= max_health * LEG_SEVER_FRACTION {
+                    self.severed_legs |= 1 << i;
+                    return Some(Severance::Leg(i));
+                }
+                None
+            }
+            HitRegion::Abdomen if !self.abdomen_severed => {
+                self.abdomen_damage += damage;
+                if self.abdomen_damage >= max_health * ABDOMEN_SEVER_FRACTION {
+                    self.abdomen_severed = true;
+                    return Some(Severance::Abdomen);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Number of legs shot off.
+    pub fn legs_lost(&self) -> u32 {
+        self.severed_legs.count_ones()
+    }
+
+    /// Two or more legs gone: the bug drags itself along the ground.
+    pub fn is_crawling(&self) -> bool {
+        self.legs_lost() >= CRAWL_LEGS_LOST
+    }
+
+    /// Movement speed multiplier from hit stagger and lost legs.
+    pub fn speed_scale(&self) -> f32 {
+        let limbs = if self.is_crawling() {
+            CRAWL_SPEED_SCALE
+        } else {
+            1.0 - SPEED_LOSS_PER_LEG * self.legs_lost() as f32
+        };
+        self.stagger_speed_scale() * limbs
+    }
+
+    /// Health lost to a severed abdomen over `dt` (0 when intact).
+    pub fn bleed_damage(&self, max_health: f32, dt: f32) -> f32 {
+        if self.abdomen_severed { max_health * ABDOMEN_BLEED_RATE * dt } else { 0.0 }
+    }
+
+    /// Tick the ichor leak; true when a drip should fall this frame.
+    pub fn tick_leak(&mut self, dt: f32) -> bool {
+        if !self.abdomen_severed {
+            return false;
+        }
+        self.leak_timer -= dt;
+        if self.leak_timer <= 0.0 {
+            self.leak_timer = LEAK_INTERVAL;
+            return true;
+        }
+        false
+    }
+
+    /// Out of the ground: moves, attacks and is steered by the horde AI.
+    pub fn is_surfaced(&self) -> bool {
+        self.emerge == EmergePhase::Surfaced
+    }
+
+    /// Advance the burrow ambush. True on the frame the bug breaks the surface (burst effect).
+    pub fn update_emerge(&mut self, dt: f32) -> bool {
+        match self.emerge {
+            EmergePhase::Burrowed { timer } if timer - dt <= 0.0 => {
+                self.emerge = EmergePhase::Emerging { progress: 0.0 };
+                return true;
+            }
+            EmergePhase::Burrowed { timer } => self.emerge = EmergePhase::Burrowed { timer: timer - dt },
+            EmergePhase::Emerging { progress } => {
+                let progress = progress + dt / EMERGE_DURATION;
+                self.emerge = if progress >= 1.0 {
+                    EmergePhase::Surfaced
+                } else {
+                    EmergePhase::Emerging { progress }
+                };
+            }
+            EmergePhase::Surfaced => {}
+        }
+        false
+    }
+
+    /// Distance below its normal stance for a bug `height` tall: fully buried, then rising (eased).
+    pub fn emerge_sink(&self, height: f32) -> f32 {
+        match self.emerge {
+            EmergePhase::Surfaced => 0.0,
+            EmergePhase::Burrowed { .. } => height * BURROW_DEPTH,
+            EmergePhase::Emerging { progress } => height * BURROW_DEPTH * (1.0 - progress).powi(2),
+        }
     }
 
     /// Update death animation phases
@@ -191,6 +472,25 @@ pub struct MuzzleFlash {
     pub intensity: f32,
 }
 
+/// Brief light thrown by a blast; fed to the renderer as a point light while it fades.
+#[derive(Debug, Clone)]
+pub struct FlashLight {
+    pub position: Vec3,
+    pub color: [f32; 3],
+    pub radius: f32,
+    pub intensity: f32,
+    pub age: f32,
+    pub duration: f32,
+}
+
+impl FlashLight {
+    /// Brightness now: full at the blast, dying off quadratically to nothing at `duration`.
+    pub fn current_intensity(&self) -> f32 {
+        let left = (1.0 - self.age / self.duration).max(0.0);
+        self.intensity * left * left
+    }
+}
+
 /// Single explosion particle (Tac Fighter strikes: fire/smoke billboard)
 #[derive(Debug, Clone)]
 pub struct ExplosionParticle {
@@ -204,6 +504,29 @@ pub struct ExplosionParticle {
     pub kind: u8,
 }
 
+/// Seconds a blood decal stains the ground; it fades out over the last third.
+const BLOOD_DECAL_LIFETIME: f32 = 90.0;
+/// Stain intensity where decals overlap is capped here (1.0 = fully soaked).
+const BLOOD_DECAL_MAX_STAIN: f32 = 1.0;
+
+/// Blood/ichor stain on the ground where a bug was hit or died. Stamped into the terrain's
+/// blood map each frame (same world-space tile as the deformation buffer).
+#[derive(Debug, Clone)]
+pub struct BloodDecal {
+    pub position: Vec3,
+    /// Radius in meters.
+    pub size: f32,
+    pub age: f32,
+}
+
+impl BloodDecal {
+    /// Stain intensity: full while fresh, fading to 0 over the last third of its life.
+    pub fn strength(&self) -> f32 {
+        let fade_start = BLOOD_DECAL_LIFETIME * (2.0 / 3.0);
+        1.0 - ((self.age - fade_start) / (BLOOD_DECAL_LIFETIME - fade_start)).clamp(0.0, 1.0)
+    }
+}
+
 /// Ground track (footprint / trail) in snow or sand — Dune / Helldivers 2 style.
 #[derive(Debug, Clone)]
 pub struct GroundTrack {
@@ -237,7 +560,7 @@ pub fn spawn_complete_bug(
 
     // Create physics body
     let body_handle = physics.add_kinematic_body(position);
-    let collider_handle = physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+    let collider_handle = physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
 
     let physics_bug = PhysicsBug {
         body_handle: Some(body_handle),
@@ -245,7 +568,7 @@ pub fn spawn_complete_bug(
         ..Default::default()
     };
 
-    world.spawn((
+    let entity = world.spawn((
         Transform {
             position,
             rotation: Quat::IDENTITY,
@@ -256,30 +579,35 @@ pub fn spawn_complete_bug(
         bug,
         physics_bug,
         engine_core::AIComponent::new(85.0, 2.5, 1.0),  // Extermination: large aggro
-    ))
+    ));
+    physics.set_collider_entity(collider_handle, entity);
+    entity
 }
 
 /// System to update bug physics and death animations
-pub fn update_bug_physics(world: &mut World, physics: &mut PhysicsWorld, dt: f32) {
+pub fn update_bug_physics(world: &mut World, physics: &mut PhysicsWorld, rng: &mut GameRng, dt: f32) {
     // Collect bugs that need ragdoll activation
     let mut to_ragdoll: Vec<(Entity, Vec3)> = Vec::new();
 
-    for (entity, (health, physics_bug, transform)) in
-        world.query_mut::<(&Health, &mut PhysicsBug, &Transform)>()
-    {
+    for (entity, (health, physics_bug)) in world.query_mut::<(&Health, &mut PhysicsBug)>() {
+        // Hit-reaction stagger recovers over time; the horde AI applies the slowdown through
+        // `speed_scale` when it sets the bug's velocity
+        if physics_bug.stagger_timer > 0.0 && !health.is_dead() {
+            physics_bug.stagger_timer = (physics_bug.stagger_timer - dt).max(0.0);
+        }
+
         if health.is_dead() && !physics_bug.is_ragdoll {
-            // Random death direction if no impact recorded
-            let impact_dir = if physics_bug.impact_velocity.length() > 0.1 {
-                physics_bug.impact_velocity.normalize()
+            // Killing shot's impact velocity; random knock-over if no impact recorded
+            let impact = if physics_bug.impact_velocity.length() > 0.1 {
+                physics_bug.impact_velocity
             } else {
-                let mut rng = rand::thread_rng();
                 Vec3::new(
-                    rng.gen_range(-1.0..1.0),
+                    rng.range(-1.0..1.0),
                     0.5,
-                    rng.gen_range(-1.0..1.0),
-                ).normalize()
+                    rng.range(-1.0..1.0),
+                ).normalize() * 4.0
             };
-            to_ragdoll.push((entity, impact_dir));
+            to_ragdoll.push((entity, PhysicsBug::ragdoll_launch_velocity(impact)));
         }
 
         // Update death animation
@@ -287,37 +615,36 @@ pub fn update_bug_physics(world: &mut World, physics: &mut PhysicsWorld, dt: f32
     }
 
     // Activate ragdolls
-    for (entity, impact_dir) in to_ragdoll {
+    for (entity, launch_velocity) in to_ragdoll {
         if let Ok(mut physics_bug) = world.get::<&mut PhysicsBug>(entity) {
-            physics_bug.activate_ragdoll(impact_dir, 8.0);
+            physics_bug.activate_ragdoll(launch_velocity);
 
             // Convert to dynamic body for ragdoll — Euphoria-style violent death
             if let Some(handle) = physics_bug.body_handle {
-                if let Some(body) = physics.rigid_body_set.get_mut(handle) {
-                    use physics::rapier3d::prelude::RigidBodyType;
-                    use physics::rapier3d::na::Vector3;
-                    body.set_body_type(RigidBodyType::Dynamic, true);
-                    // Euphoria-style: explosive death impulse — body flies back violently
-                    let impulse = impact_dir * 18.0 + Vec3::Y * 6.0;
-                    body.apply_impulse(
-                        Vector3::new(impulse.x, impulse.y, impulse.z),
-                        true,
-                    );
+                if physics.convert_to_dynamic(handle) {
+                    // Killing shot knocks the body back along the hit direction
+                    physics.apply_velocity_impulse(handle, launch_velocity);
                     // Chaotic tumble — limbs flying, dismemberment feel
-                    body.apply_torque_impulse(
-                        Vector3::new(
-                            rand::random::<f32>() * 12.0 - 6.0,
-                            rand::random::<f32>() * 12.0 - 6.0,
-                            rand::random::<f32>() * 12.0 - 6.0,
+                    physics.apply_torque_impulse(
+                        handle,
+                        Vec3::new(
+                            rng.range(-6.0..6.0),
+                            rng.range(-6.0..6.0),
+                            rng.range(-6.0..6.0),
                         ),
-                        true,
                     );
                 }
             }
         }
+        // Ragdolls are physics-driven from here on: render them interpolated between steps.
+        // Living bugs are moved by the horde AI every frame, so they are drawn as-is.
+        let current = world.get::<&Transform>(entity).map(|t| *t).unwrap_or_default();
+        world.insert_one(entity, PreviousTransform(current)).ok();
     }
+}
 
-    // Sync physics transforms
+/// Copy ragdoll rigid body poses into their `Transform`. Call after stepping physics.
+pub fn sync_ragdoll_transforms(world: &mut World, physics: &PhysicsWorld) {
     for (_entity, (transform, physics_bug)) in
         world.query_mut::<(&mut Transform, &PhysicsBug)>()
     {
@@ -341,12 +668,17 @@ pub struct EffectsManager {
     pub bullet_impacts: Vec<BulletImpact>,
     pub muzzle_flashes: Vec<MuzzleFlash>,
     pub explosion_particles: Vec<ExplosionParticle>,
+    /// Lights from explosions, fading out
+    pub flash_lights: Vec<FlashLight>,
     /// Footprints and trails in snow/sand (Dune / Helldivers 2 style)
     pub ground_tracks: Vec<GroundTrack>,
+    /// Blood stains projected onto the terrain (kill zones)
+    pub blood_decals: Vec<BloodDecal>,
     pub max_gore: usize,
     pub max_impacts: usize,
     pub max_explosion_particles: usize,
     pub max_ground_tracks: usize,
+    pub max_blood_decals: usize,
 }
 
 impl EffectsManager {
@@ -356,11 +688,56 @@ impl EffectsManager {
             bullet_impacts: Vec::new(),
             muzzle_flashes: Vec::new(),
             explosion_particles: Vec::new(),
+            flash_lights: Vec::new(),
             ground_tracks: Vec::new(),
+            blood_decals: Vec::new(),
             max_gore: 400,
             max_impacts: 100,
             max_explosion_particles: 800,
             max_ground_tracks: 450,
+            max_blood_decals: 300,
+        }
+    }
+
+    /// Stain the ground with blood in a `size` m radius around `position` (XZ; height ignored).
+    pub fn spawn_blood_decal(&mut self, position: Vec3, size: f32) {
+        self.blood_decals.push(BloodDecal { position, size, age: 0.0 });
+        while self.blood_decals.len() > self.max_blood_decals {
+            self.blood_decals.remove(0);
+        }
+    }
+
+    /// Rasterize the blood decals into `buffer` (DEFORM_TEXTURE_SIZE² stain map centered on
+    /// world (origin_x, origin_z)). Overlapping splats accumulate into darker pools.
+    pub fn stamp_blood_decals(&self, buffer: &mut [f32], origin_x: f32, origin_z: f32) {
+        buffer.fill(0.0);
+        let size = DEFORM_TEXTURE_SIZE as i32;
+        let texels_per_unit = DEFORM_TEXTURE_SIZE as f32 / (2.0 * DEFORM_HALF_SIZE);
+        let min_x = origin_x - DEFORM_HALF_SIZE;
+        let min_z = origin_z - DEFORM_HALF_SIZE;
+        for decal in &self.blood_decals {
+            let strength = decal.strength();
+            if strength <= 0.0 || decal.size <= 0.0 {
+                continue;
+            }
+            let (dx, dz, r) = (decal.position.x, decal.position.z, decal.size);
+            let min_i = (((dx - r - min_x) * texels_per_unit).floor() as i32).max(0);
+            let max_i = (((dx + r - min_x) * texels_per_unit).ceil() as i32).min(size - 1);
+            let min_j = (((dz - r - min_z) * texels_per_unit).floor() as i32).max(0);
+            let max_j = (((dz + r - min_z) * texels_per_unit).ceil() as i32).min(size - 1);
+            for j in min_j..=max_j {
+                for i in min_i..=max_i {
+                    let wx = min_x + (i as f32 + 0.5) / texels_per_unit;
+                    let wz = min_z + (j as f32 + 0.5) / texels_per_unit;
+                    let dist = ((wx - dx).powi(2) + (wz - dz).powi(2)).sqrt();
+                    if dist < r {
+                        let t = dist / r;
+                        let falloff = 1.0 - t * t * (3.0 - 2.0 * t);
+                        let idx = (i + j * size) as usize;
+                        buffer[idx] = (buffer[idx] + strength * falloff).min(BLOOD_DECAL_MAX_STAIN);
+                    }
+                }
+            }
         }
     }
 
@@ -383,9 +760,8 @@ impl EffectsManager {
         }
     }
 
-    pub fn spawn_gore(&mut self, position: Vec3, _normal: Vec3, size: f32) {
+    pub fn spawn_gore(&mut self, rng: &mut GameRng, position: Vec3, _normal: Vec3, size: f32) {
         // Euphoria-style: explosion of bug guts — dense splatters, sprays, pools
-        let mut rng = rand::thread_rng();
 
         let ground_pos = Vec3::new(position.x, 0.02, position.z);
 
@@ -445,6 +821,24 @@ impl EffectsManager {
         }
     }
 
+    /// Single ichor drip under a wounded bug (severed abdomen leak).
+    pub fn spawn_gore_drip(&mut self, rng: &mut GameRng, position: Vec3, size: f32) {
+        self.gore_splatters.push(GoreSplatter {
+            position: Vec3::new(
+                position.x + rng.range(-0.3..0.3) * size,
+                0.02,
+                position.z + rng.range(-0.3..0.3) * size,
+            ),
+            normal: Vec3::Y,
+            size: size * rng.range(0.15..0.35),
+            age: 0.0,
+            splatter_type: GoreType::Drip,
+        });
+        while self.gore_splatters.len() > self.max_gore {
+            self.gore_splatters.remove(0);
+        }
+    }
+
     pub fn spawn_bullet_impact(&mut self, position: Vec3, normal: Vec3, is_blood: bool) {
         self.bullet_impacts.push(BulletImpact {
             position,
@@ -467,8 +861,56 @@ impl EffectsManager {
         });
     }
 
+    /// Flamethrower stream: a few short-lived fire billboards thrown along `dir` each frame.
+    pub fn spawn_flame_stream(&mut self, nozzle: Vec3, dir: Vec3, range: f32) {
+        let mut rng = rand::thread_rng();
+        let available = self.max_explosion_particles.saturating_sub(self.explosion_particles.len());
+        for _ in 0..4.min(available) {
+            let jitter = Vec3::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 0.25;
+            let speed = range * (1.6 + rng.gen::<f32>() * 0.6);
+            let max_life = 0.35 + rng.gen::<f32>() * 0.25;
+            self.explosion_particles.push(ExplosionParticle {
+                position: nozzle,
+                velocity: (dir + jitter).normalize_or_zero() * speed,
+                life: max_life,
+                max_life,
+                size: 0.15 + rng.gen::<f32>() * 0.2,
+                phase: rng.gen::<f32>() * std::f32::consts::TAU,
+                kind: if rng.gen::<f32>() < 0.5 { 0 } else { 1 },
+            });
+        }
+    }
+
+    /// Flames licking up from burning ground.
+    pub fn spawn_ground_flame(&mut self, center: Vec3, radius: f32) {
+        if self.explosion_particles.len() >= self.max_explosion_particles {
+            return;
+        }
+        let mut rng = rand::thread_rng();
+        let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+        let dist = rng.gen::<f32>().sqrt() * radius;
+        let max_life = 0.5 + rng.gen::<f32>() * 0.4;
+        self.explosion_particles.push(ExplosionParticle {
+            position: center + Vec3::new(angle.cos() * dist, 0.1, angle.sin() * dist),
+            velocity: Vec3::new(0.0, 6.0 + rng.gen::<f32>() * 4.0, 0.0),
+            life: max_life,
+            max_life,
+            size: 0.2 + rng.gen::<f32>() * 0.3,
+            phase: rng.gen::<f32>() * std::f32::consts::TAU,
+            kind: if rng.gen::<f32>() < 0.7 { 1 } else { 2 },
+        });
+    }
+
     /// Spawn Tac Fighter impact explosion: fire/smoke billboard particles (flat billboard look).
     pub fn spawn_tac_explosion(&mut self, center: Vec3) {
+        self.flash_lights.push(FlashLight {
+            position: center + Vec3::Y * 1.5,
+            color: [1.0, 0.6, 0.25],
+            radius: 28.0,
+            intensity: 14.0,
+            age: 0.0,
+            duration: 0.6,
+        });
         let mut rng = rand::thread_rng();
         let available = self.max_explosion_particles.saturating_sub(self.explosion_particles.len());
         let count = 120.min(available);
@@ -516,6 +958,12 @@ impl EffectsManager {
         }
         self.ground_tracks.retain(|t| t.age < 120.0);
 
+        // Update blood decals (stains fade, then disappear)
+        for decal in &mut self.blood_decals {
+            decal.age += dt;
+        }
+        self.blood_decals.retain(|d| d.age < BLOOD_DECAL_LIFETIME);
+
         // Update impacts
         for impact in &mut self.bullet_impacts {
             impact.age += dt;
@@ -529,6 +977,11 @@ impl EffectsManager {
         }
         self.muzzle_flashes.retain(|f| f.age < 0.1);
 
+        for light in &mut self.flash_lights {
+            light.age += dt;
+        }
+        self.flash_lights.retain(|l| l.age < l.duration);
+
         // Update explosion particles (Tac Fighter impacts)
         for p in &mut self.explosion_particles {
             p.life -= dt;
@@ -542,3 +995,125 @@ impl EffectsManager {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warrior_at_origin() -> Transform {
+        Transform { position: Vec3::ZERO, rotation: Quat::IDENTITY, scale: Vec3::ONE }
+    }
+
+    #[test]
+    fn burrowed_bugs_rise_out_of_the_ground_then_fight() {
+        let mut bug = PhysicsBug { emerge: EmergePhase::Burrowed { timer: 0.5 }, ..Default::default() };
+        let buried = bug.emerge_sink(1.0);
+        assert!(buried > 1.0, "starts below ground");
+        assert!(!bug.update_emerge(0.3));
+        assert!(bug.update_emerge(0.3), "breaks the surface once");
+        assert!(!bug.is_surfaced());
+
+        let mut last = buried;
+        let mut frames = 0;
+        while !bug.is_surfaced() {
+            assert!(!bug.update_emerge(0.1));
+            let sink = bug.emerge_sink(1.0);
+            assert!(sink <= last, "only rises");
+            last = sink;
+            frames += 1;
+        }
+        assert!(frames as f32 <= EMERGE_DURATION / 0.1 + 1.0, "climbs out in about EMERGE_DURATION");
+        assert_eq!(bug.emerge_sink(1.0), 0.0);
+    }
+
+    #[test]
+    fn hit_regions_follow_the_mesh_layout() {
+        let bug = warrior_at_origin();
+        // Head-on at head height, and from behind
+        assert_eq!(HitRegion::from_shot(&bug, Vec3::new(0.0, 0.2, 20.0), -Vec3::Z), HitRegion::Head);
+        assert_eq!(HitRegion::from_shot(&bug, Vec3::new(0.0, 0.2, -20.0), Vec3::Z), HitRegion::Abdomen);
+        // From the left side: through the middle, then low near the front
+        assert_eq!(HitRegion::from_shot(&bug, Vec3::new(-20.0, 0.2, 0.1), Vec3::X), HitRegion::Thorax);
+        assert_eq!(HitRegion::from_shot(&bug, Vec3::new(-20.0, -0.2, 0.35), Vec3::X), HitRegion::Leg(4));
+        // Turned to face +X, a low shot along +X comes in from behind and takes a rear leg
+        let turned = Transform { rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2), ..bug };
+        assert_eq!(HitRegion::from_shot(&turned, Vec3::new(-20.0, -0.2, -0.1), Vec3::X), HitRegion::Leg(1));
+    }
+
+    #[test]
+    fn blood_decals_stain_their_footprint_and_fade() {
+        let mut effects = EffectsManager::new();
+        let mut buffer = vec![0.0; (DEFORM_TEXTURE_SIZE * DEFORM_TEXTURE_SIZE) as usize];
+        let texel = |x: f32, z: f32| {
+            let per_unit = DEFORM_TEXTURE_SIZE as f32 / (2.0 * DEFORM_HALF_SIZE);
+            let i = ((x + DEFORM_HALF_SIZE) * per_unit) as usize;
+            let j = ((z + DEFORM_HALF_SIZE) * per_unit) as usize;
+            i + j * DEFORM_TEXTURE_SIZE as usize
+        };
+
+        effects.spawn_blood_decal(Vec3::new(10.0, 3.0, -5.0), 2.0);
+        effects.spawn_blood_decal(Vec3::new(10.0, 3.0, -5.0), 2.0);
+        effects.stamp_blood_decals(&mut buffer, 0.0, 0.0);
+        assert!(buffer[texel(10.0, -5.0)] > 0.9, "overlapping splats pool up");
+        assert_eq!(buffer[texel(20.0, -5.0)], 0.0);
+
+        effects.update(BLOOD_DECAL_LIFETIME * 0.9);
+        effects.stamp_blood_decals(&mut buffer, 0.0, 0.0);
+        assert!(buffer[texel(10.0, -5.0)] < 0.7, "old stains fade");
+        effects.update(BLOOD_DECAL_LIFETIME);
+        assert!(effects.blood_decals.is_empty());
+    }
+
+    #[test]
+    fn two_severed_legs_make_a_bug_crawl() {
+        let mut bug = PhysicsBug::default();
+        assert_eq!(bug.register_limb_hit(HitRegion::Leg(1), 5.0, 50.0), None);
+        assert_eq!(bug.register_limb_hit(HitRegion::Leg(1), 5.0, 50.0), Some(Severance::Leg(1)));
+        assert_eq!(bug.register_limb_hit(HitRegion::Leg(1), 50.0, 50.0), None, "already gone");
+        assert!(!bug.is_crawling());
+        assert!((bug.speed_scale() - (1.0 - SPEED_LOSS_PER_LEG)).abs() < 1e-6);
+
+        assert_eq!(bug.register_limb_hit(HitRegion::Leg(4), 20.0, 50.0), Some(Severance::Leg(4)));
+        assert!(bug.is_crawling());
+        assert_eq!(bug.speed_scale(), CRAWL_SPEED_SCALE);
+
+        assert_eq!(bug.register_limb_hit(HitRegion::Abdomen, 20.0, 50.0), Some(Severance::Abdomen));
+        assert!(bug.bleed_damage(50.0, 1.0) > 0.0);
+        assert!(bug.tick_leak(0.01));
+    }
+
+    #[test]
+    fn stagger_slows_a_bug_once_per_hit() {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let mut bug = PhysicsBug::default();
+        bug.apply_hit_stagger(Vec3::X, 40.0, 1.0, 1.0);
+        let scale = bug.speed_scale();
+        assert!(scale < 1.0);
+        // The horde AI sets the staggered speed; the physics step must not slow it again
+        let entity = world.spawn((
+            warrior_at_origin(),
+            Health::new(50.0),
+            bug,
+            Velocity { linear: Vec3::X * 4.0 * scale, ..Default::default() },
+        ));
+        update_bug_physics(&mut world, &mut physics, &mut GameRng::new(1), 0.1);
+        let velocity = world.get::<&Velocity>(entity).unwrap().linear;
+        assert!((velocity.x - 4.0 * scale).abs() < 1e-5, "stagger applied twice: {velocity:?}");
+        assert!(world.get::<&PhysicsBug>(entity).unwrap().speed_scale() > scale, "stagger recovers");
+    }
+
+    #[test]
+    fn weakpoints_differ_per_bug_type() {
+        use crate::bug::BugType;
+        assert_eq!(BugType::Warrior.region_damage_mult(HitRegion::Head), 2.0);
+        // Charger head is armored; its rear is the weak spot
+        assert_eq!(BugType::Charger.region_damage_mult(HitRegion::Head), HitRegion::Thorax.damage_mult());
+        assert!(BugType::Charger.weakpoint(HitRegion::Abdomen).is_some());
+        let sac = BugType::Spitter.weakpoint(HitRegion::Abdomen).expect("spitter acid sac");
+        assert!(sac.glows && sac.damage_mult > HitRegion::Abdomen.damage_mult());
+        // Every weakpoint has somewhere on the mesh to draw its glow
+        for bug_type in [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper] {
+            assert!(bug_type.weakpoints().iter().all(|w| w.region.mesh_center().is_some()));
+        }
+    }
+}
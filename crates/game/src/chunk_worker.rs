@@ -0,0 +1,301 @@
+//! Background terrain chunk generation.
+//!
+//! Voxel generation, mesh building and heightmap extraction run on worker threads.
+//! The main thread only uploads finished buffers to the GPU and creates the physics collider.
+
+use procgen::{BlockEdits, PlanetBiomes, TerrainConfig, VoxelChunk};
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+/// Most jobs waiting for a worker. Past this the farthest waiting jobs are dropped; the
+/// manager asks for them again if they are still wanted once the queue drains.
+pub const MAX_QUEUED_CHUNKS: usize = 48;
+
+/// One chunk to generate.
+struct ChunkJob {
+    generation: u64,
+    key: (i32, i32),
+    config: TerrainConfig,
+    biomes: Arc<PlanetBiomes>,
+    edits: Option<Arc<BlockEdits>>,
+}
+
+/// Jobs waiting for a worker, keyed by chunk. Workers always take the one nearest `focus`
+/// (the player's chunk), so a player on the move never waits behind chunks they left behind.
+struct JobQueue<T> {
+    jobs: Vec<((i32, i32), T)>,
+    focus: (i32, i32),
+    /// Set when the workers are dropped; idle threads exit.
+    closed: bool,
+}
+
+impl<T> JobQueue<T> {
+    fn new() -> Self {
+        Self { jobs: Vec::new(), focus: (0, 0), closed: false }
+    }
+
+    /// Squared chunk distance from the focus.
+    fn distance(focus: (i32, i32), (cx, cz): (i32, i32)) -> i32 {
+        (cx - focus.0).pow(2) + (cz - focus.1).pow(2)
+    }
+
+    /// Add a job, then drop the farthest ones beyond `capacity`. Returns the dropped keys.
+    fn push(&mut self, key: (i32, i32), job: T, capacity: usize) -> Vec<(i32, i32)> {
+        self.jobs.push((key, job));
+        if self.jobs.len() <= capacity {
+            return Vec::new();
+        }
+        let focus = self.focus;
+        self.jobs.sort_unstable_by_key(|&(key, _)| Self::distance(focus, key));
+        self.jobs.drain(capacity..).map(|(key, _)| key).collect()
+    }
+
+    /// Take the waiting job nearest the focus.
+    fn pop_nearest(&mut self) -> Option<T> {
+        let nearest = (0..self.jobs.len()).min_by_key(|&i| Self::distance(self.focus, self.jobs[i].0))?;
+        Some(self.jobs.swap_remove(nearest).1)
+    }
+}
+
+/// Queue shared between the manager and the worker threads.
+type SharedQueue = Arc<(Mutex<JobQueue<ChunkJob>>, Condvar)>;
+
+/// CPU-side result of generating a chunk, ready for GPU upload.
+pub struct ChunkBuild {
+    /// `ChunkWorkers::generation` when the job was queued; stale results are dropped.
+    pub generation: u64,
+    pub key: (i32, i32),
+    pub voxel: VoxelChunk,
+    pub vertices: Vec<renderer::Vertex>,
+    pub indices: Vec<u32>,
+    /// Transparent water surface, if the chunk has any water.
+    pub water: Option<(Vec<renderer::Vertex>, Vec<u32>)>,
+    /// Top-surface heights for the physics heightfield.
+    pub heightmap: Vec<f32>,
+}
+
+impl ChunkBuild {
+    /// Generate a chunk on the calling thread, replaying `edits` from an earlier visit.
+    pub fn generate(
+        key: (i32, i32),
+        config: &TerrainConfig,
+        biomes: &PlanetBiomes,
+        edits: Option<&BlockEdits>,
+        generation: u64,
+    ) -> Self {
+        let mut voxel = VoxelChunk::generate(config, Some(biomes));
+        if let Some(edits) = edits {
+            voxel.apply_edits(edits);
+        }
+        let to_vertex = |v: &procgen::TerrainVertex| renderer::Vertex {
+            position: v.position,
+            normal: v.normal,
+            tex_coords: v.uv,
+            color: v.color,
+            tangent: [0.0; 4],
+        };
+
+        // Culled cube faces; water excluded for the transparent pass
+        let (terrain_vertices, indices) = voxel.to_mesh();
+        let vertices = terrain_vertices.iter().map(to_vertex).collect();
+
+        let (water_vertices, water_indices) = voxel.to_water_mesh();
+        let water = if water_vertices.is_empty() {
+            None
+        } else {
+            Some((water_vertices.iter().map(to_vertex).collect(), water_indices))
+        };
+
+        let heightmap = voxel.to_heightmap();
+        Self {
+            generation,
+            key,
+            voxel,
+            vertices,
+            indices,
+            water,
+            heightmap,
+        }
+    }
+}
+
+/// Small thread pool that turns `ChunkJob`s into `ChunkBuild`s.
+pub struct ChunkWorkers {
+    queue: SharedQueue,
+    result_rx: Receiver<ChunkBuild>,
+    /// Chunks queued or being generated, so they aren't requested twice.
+    in_flight: HashSet<(i32, i32)>,
+    /// Bumped on planet change so results for the old planet are discarded.
+    generation: u64,
+}
+
+impl ChunkWorkers {
+    pub fn new() -> Self {
+        let queue: SharedQueue = Arc::new((Mutex::new(JobQueue::new()), Condvar::new()));
+        let (result_tx, result_rx) = mpsc::channel();
+        // Leave a couple of cores for the main and render threads
+        let threads = thread::available_parallelism()
+            .map_or(2, |n| n.get().saturating_sub(2))
+            .clamp(1, 4);
+        for i in 0..threads {
+            let queue = Arc::clone(&queue);
+            let result_tx = result_tx.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("chunk-gen-{}", i))
+                .spawn(move || loop {
+                    // Exits when the manager (and with it the queue) is dropped
+                    let (lock, ready) = &*queue;
+                    let Ok(mut waiting) = lock.lock() else { return };
+                    let job = loop {
+                        if waiting.closed {
+                            return;
+                        }
+                        if let Some(job) = waiting.pop_nearest() {
+                            break job;
+                        }
+                        let Ok(woken) = ready.wait(waiting) else { return };
+                        waiting = woken;
+                    };
+                    drop(waiting);
+                    let build =
+                        ChunkBuild::generate(job.key, &job.config, &job.biomes, job.edits.as_deref(), job.generation);
+                    if result_tx.send(build).is_err() {
+                        return;
+                    }
+                });
+            if let Err(e) = spawned {
+                log::warn!("Failed to spawn chunk worker thread: {}", e);
+            }
+        }
+        Self {
+            queue,
+            result_rx,
+            in_flight: HashSet::new(),
+            generation: 0,
+        }
+    }
+
+    /// Current generation; pass to `ChunkBuild::generate` for synchronous builds.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Generate nearest `center` first from now on (the player's chunk).
+    pub fn set_focus(&mut self, center: (i32, i32)) {
+        if let Ok(mut waiting) = self.queue.0.lock() {
+            waiting.focus = center;
+        }
+    }
+
+    /// Queue a chunk for background generation. No-op if it is already in flight.
+    /// Returns false if the queue is full of nearer chunks and this one was dropped.
+    pub fn request(
+        &mut self,
+        key: (i32, i32),
+        config: TerrainConfig,
+        biomes: Arc<PlanetBiomes>,
+        edits: Option<Arc<BlockEdits>>,
+    ) -> bool {
+        if !self.in_flight.insert(key) {
+            return true;
+        }
+        let job = ChunkJob {
+            generation: self.generation,
+            key,
+            config,
+            biomes,
+            edits,
+        };
+        let (lock, ready) = &*self.queue;
+        let Ok(mut waiting) = lock.lock() else {
+            self.in_flight.remove(&key);
+            return false;
+        };
+        let dropped = waiting.push(key, job, MAX_QUEUED_CHUNKS);
+        drop(waiting);
+        ready.notify_one();
+        for dropped_key in &dropped {
+            self.in_flight.remove(dropped_key);
+        }
+        !dropped.contains(&key)
+    }
+
+    pub fn is_in_flight(&self, key: (i32, i32)) -> bool {
+        self.in_flight.contains(&key)
+    }
+
+    /// Finished chunks for the current generation. Results from before `invalidate` are dropped.
+    pub fn drain_finished(&mut self) -> Vec<ChunkBuild> {
+        let mut finished = Vec::new();
+        while let Ok(build) = self.result_rx.try_recv() {
+            if build.generation != self.generation {
+                continue;
+            }
+            self.in_flight.remove(&build.key);
+            finished.push(build);
+        }
+        finished
+    }
+
+    /// Forget all in-flight work (e.g. on planet change). Waiting jobs are dropped; jobs a
+    /// worker already started still finish, but their results are ignored.
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+        self.in_flight.clear();
+        if let Ok(mut waiting) = self.queue.0.lock() {
+            waiting.jobs.clear();
+        }
+    }
+}
+
+impl Drop for ChunkWorkers {
+    fn drop(&mut self) {
+        let (lock, ready) = &*self.queue;
+        if let Ok(mut waiting) = lock.lock() {
+            waiting.closed = true;
+        }
+        ready.notify_all();
+    }
+}
+
+impl Default for ChunkWorkers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workers_take_the_chunk_nearest_the_player_first() {
+        let mut queue = JobQueue::new();
+        for key in [(5, 0), (1, 1), (-3, 2), (0, 0)] {
+            queue.push(key, key, MAX_QUEUED_CHUNKS);
+        }
+        assert_eq!(queue.pop_nearest(), Some((0, 0)));
+        assert_eq!(queue.pop_nearest(), Some((1, 1)));
+
+        // The player moves: what was far is now nearest
+        queue.focus = (6, 0);
+        assert_eq!(queue.pop_nearest(), Some((5, 0)));
+        assert_eq!(queue.pop_nearest(), Some((-3, 2)));
+        assert_eq!(queue.pop_nearest(), None);
+    }
+
+    #[test]
+    fn a_full_queue_drops_its_farthest_chunks() {
+        let mut queue = JobQueue::new();
+        for x in 0..4 {
+            assert!(queue.push((x, 0), (), 4).is_empty());
+        }
+        // A nearer chunk evicts the farthest waiting one; a farther one is turned away itself
+        queue.focus = (-1, 0);
+        assert_eq!(queue.push((-2, 0), (), 4), vec![(3, 0)]);
+        assert_eq!(queue.push((9, 9), (), 4), vec![(9, 9)]);
+        assert_eq!(queue.jobs.len(), 4);
+    }
+}
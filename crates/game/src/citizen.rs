@@ -1,13 +1,34 @@
 //! Earth settlement citizens: Starship Troopers aesthetic — civilians walking to/from places,
-//! schedule-driven AI, time-of-day and weather cycles.
+//! schedule-driven AI, time-of-day and weather cycles. Off Earth, colonists caught near bugs
+//! panic: they run for the LZ or shelter, or freeze and cower when a bug is on top of them.
 
 use engine_core::{Transform, Velocity};
 use glam::{Quat, Vec3};
 use hecs::{Entity, World};
 use rand::Rng;
 
+use crate::objective::Rescuee;
 use crate::state::{Weather, WeatherState};
 
+/// A bug inside this radius sends a citizen running (m).
+pub const PANIC_RADIUS: f32 = 18.0;
+/// A bug this close is too close to outrun: the citizen freezes and cowers (m).
+const COWER_RADIUS: f32 = 3.5;
+/// Seconds with no bug in range before a panicked citizen settles down.
+const CALM_DOWN_SECS: f32 = 5.0;
+/// Flat-out run speed while fleeing (m/s).
+const FLEE_SPEED: f32 = 4.5;
+/// Close enough to a refuge to stop running (m).
+const REFUGE_REACHED: f32 = 4.0;
+
+/// How a citizen is reacting to bugs: going about their routine, running, or frozen in fear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitizenState {
+    Wander,
+    Flee,
+    Cower,
+}
+
 /// Schedule phase for citizen AI (driven by time of day and weather).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CitizenSchedule {
@@ -103,6 +124,9 @@ pub struct Citizen {
     pub dwell_until: f32,
     /// Next re-pick waypoint after this time (staggered so crowd doesn't move in sync).
     pub next_wander_at: f32,
+    pub state: CitizenState,
+    /// Seconds since a panicked citizen last had a bug in range.
+    pub calm_timer: f32,
 }
 
 impl Citizen {
@@ -131,8 +155,97 @@ impl Citizen {
             schedule_offset: (rng.gen::<f32>() - 0.5) * 0.16, // -0.08..0.08
             dwell_until: 0.0,
             next_wander_at: phase_timer + 18.0 + rng.gen::<f32>() * 25.0,
+            state: CitizenState::Wander,
+            calm_timer: 0.0,
         }
     }
+
+    /// React to the distance (m) of the nearest bug. Returns true the moment a calm citizen
+    /// panics, which is when they scream.
+    pub fn update_panic(&mut self, nearest_bug: Option<f32>, dt: f32) -> bool {
+        let was_calm = self.state == CitizenState::Wander;
+        self.state = match nearest_bug {
+            Some(dist) if dist < COWER_RADIUS => CitizenState::Cower,
+            Some(dist) if dist < PANIC_RADIUS => CitizenState::Flee,
+            _ if was_calm => CitizenState::Wander,
+            _ if self.calm_timer + dt >= CALM_DOWN_SECS => CitizenState::Wander,
+            _ => CitizenState::Flee,
+        };
+        if nearest_bug.is_some_and(|dist| dist < PANIC_RADIUS) {
+            self.calm_timer = 0.0;
+        } else if self.state == CitizenState::Flee {
+            self.calm_timer += dt;
+        } else {
+            self.calm_timer = 0.0;
+        }
+        was_calm && self.state != CitizenState::Wander
+    }
+}
+
+/// Direction a fleeing citizen runs: away from the bugs in range (closer ones push harder), bent
+/// toward the nearest refuge (LZ, base) unless getting there means running at the bugs.
+pub fn flee_direction(pos: Vec3, bugs: &[Vec3], refuges: &[Vec3]) -> Vec3 {
+    let flat = |v: Vec3| Vec3::new(v.x, 0.0, v.z);
+    let mut away = Vec3::ZERO;
+    for &bug in bugs {
+        let from_bug = flat(pos - bug);
+        let dist_sq = from_bug.length_squared();
+        if dist_sq > 1e-6 && dist_sq < PANIC_RADIUS * PANIC_RADIUS {
+            away += from_bug / dist_sq;
+        }
+    }
+    let away = away.normalize_or_zero();
+    let refuge = refuges
+        .iter()
+        .map(|&r| flat(r - pos))
+        .filter(|to| to.length() > REFUGE_REACHED)
+        .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+    match refuge.map(Vec3::normalize) {
+        Some(to_refuge) if to_refuge.dot(away) > -0.2 => (to_refuge + away).normalize_or_zero(),
+        _ => away,
+    }
+}
+
+/// Panic for citizens near bugs: run toward a refuge, or cower when a bug is on top of them.
+/// Rescue colonists already following the trooper stay with them. Returns where citizens just
+/// panicked, for screams.
+pub fn update_citizen_panic(
+    world: &mut World,
+    bugs: &[Vec3],
+    refuges: &[Vec3],
+    dt: f32,
+    sample_terrain_y: impl Fn(f32, f32) -> f32,
+) -> Vec<Vec3> {
+    let mut screams = Vec::new();
+    for (_, (transform, velocity, citizen, rescuee)) in
+        world.query_mut::<(&mut Transform, &mut Velocity, &mut Citizen, Option<&Rescuee>)>()
+    {
+        if rescuee.is_some_and(|r| r.following) {
+            citizen.state = CitizenState::Wander;
+            continue;
+        }
+        let pos = transform.position;
+        let nearest_bug = bugs
+            .iter()
+            .map(|b| Vec3::new(b.x - pos.x, 0.0, b.z - pos.z).length())
+            .min_by(f32::total_cmp);
+        if citizen.update_panic(nearest_bug, dt) {
+            screams.push(pos);
+        }
+        if citizen.state != CitizenState::Flee {
+            velocity.linear = Vec3::ZERO;
+            continue;
+        }
+        let dir = flee_direction(pos, bugs, refuges);
+        velocity.linear = dir * FLEE_SPEED;
+        if dir == Vec3::ZERO {
+            continue;
+        }
+        transform.rotation = Quat::from_rotation_y(f32::atan2(-dir.x, -dir.z));
+        transform.position += velocity.linear * dt;
+        transform.position.y = sample_terrain_y(transform.position.x, transform.position.z) + 0.5;
+    }
+    screams
 }
 
 /// Walk speed multiplier by schedule — commute faster, recreation slower (simulated world feel).
@@ -227,6 +340,10 @@ pub fn update_citizens(
     for (_, (transform, velocity, citizen)) in
         world.query_mut::<(&mut Transform, &mut Velocity, &mut Citizen)>()
     {
+        // Panicked citizens are moved by `update_citizen_panic` until they calm down
+        if citizen.state != CitizenState::Wander {
+            continue;
+        }
         // Per-citizen effective time so not everyone switches schedule at once (simulated world).
         let effective_time = (time_of_day + citizen.schedule_offset).rem_euclid(1.0);
         let target_schedule = if bad_weather {
@@ -294,3 +411,37 @@ pub fn update_citizens(
         transform.position.y = ground_y + 0.5;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citizen() -> Citizen {
+        Citizen::new("Carmen".to_string(), 0, &mut rand::thread_rng(), 0, 0)
+    }
+
+    #[test]
+    fn bugs_send_citizens_running_then_cowering_then_they_calm_down() {
+        let mut c = citizen();
+        assert!(!c.update_panic(None, 0.1));
+        assert!(c.update_panic(Some(PANIC_RADIUS - 1.0), 0.1), "first panic should scream");
+        assert_eq!(c.state, CitizenState::Flee);
+        assert!(!c.update_panic(Some(COWER_RADIUS - 1.0), 0.1), "already panicking, no second scream");
+        assert_eq!(c.state, CitizenState::Cower);
+
+        c.update_panic(None, CALM_DOWN_SECS * 0.5);
+        assert_eq!(c.state, CitizenState::Flee, "keeps running for a while after losing the bugs");
+        c.update_panic(None, CALM_DOWN_SECS);
+        assert_eq!(c.state, CitizenState::Wander);
+    }
+
+    #[test]
+    fn citizens_flee_toward_a_refuge_but_never_through_the_bugs() {
+        let bug = [Vec3::new(0.0, 0.0, 5.0)];
+        let to_side = flee_direction(Vec3::ZERO, &bug, &[Vec3::new(30.0, 0.0, 0.0)]);
+        assert!(to_side.z < 0.0 && to_side.x > 0.0, "should run away and toward the LZ: {to_side:?}");
+
+        let past_bugs = flee_direction(Vec3::ZERO, &bug, &[Vec3::new(0.0, 0.0, 30.0)]);
+        assert!((past_bugs - Vec3::NEG_Z).length() < 1e-5, "refuge behind the bugs is ignored: {past_bugs:?}");
+    }
+}
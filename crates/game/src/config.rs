@@ -21,6 +21,33 @@ pub struct GameConfig {
     /// Mouse sensitivity multiplier (1.0 = default).
     #[serde(default = "default_sensitivity")]
     pub sensitivity: f32,
+    /// Invert vertical look.
+    #[serde(default)]
+    pub invert_y: bool,
+    /// Base field of view in degrees (60–110); aiming and sprinting change it from here.
+    #[serde(default = "default_fov")]
+    pub fov: f32,
+    /// Terrain view distance in chunks (2–16) at high altitude; on the ground it is 2 less.
+    #[serde(default = "default_view_distance")]
+    pub view_distance: i32,
+    /// Most terrain chunks kept in memory; the farthest are unloaded beyond this. Also caps the
+    /// view distance.
+    #[serde(default = "default_max_loaded_chunks")]
+    pub max_loaded_chunks: usize,
+    /// Bug corpses kept before the oldest/farthest fade out (lower for better performance).
+    #[serde(default = "default_max_corpses")]
+    pub max_corpses: usize,
+    /// Floating damage numbers shown at once; the oldest are dropped beyond this.
+    #[serde(default = "default_max_damage_numbers")]
+    pub max_damage_numbers: usize,
+    /// Call stratagems by holding their key and entering a directional code (arrows/WASD)
+    /// instead of a single key press.
+    #[serde(default)]
+    pub stratagem_codes: bool,
+    /// Fly the approach craft and piloted Tac Fighters with free 6DOF rotation (mouse pitch/yaw,
+    /// A/D roll) instead of arcade yaw/pitch.
+    #[serde(default)]
+    pub flight_six_dof: bool,
 }
 
 fn default_window_width() -> u32 {
@@ -35,6 +62,21 @@ fn default_true() -> bool {
 fn default_sensitivity() -> f32 {
     1.0
 }
+fn default_fov() -> f32 {
+    70.0
+}
+fn default_view_distance() -> i32 {
+    5
+}
+fn default_max_loaded_chunks() -> usize {
+    256
+}
+fn default_max_corpses() -> usize {
+    crate::destruction::DEFAULT_CORPSE_BUDGET
+}
+fn default_max_damage_numbers() -> usize {
+    crate::fps::DEFAULT_MAX_DAMAGE_NUMBERS
+}
 
 impl Default for GameConfig {
     fn default() -> Self {
@@ -44,6 +86,14 @@ impl Default for GameConfig {
             vsync: default_true(),
             fullscreen: false,
             sensitivity: default_sensitivity(),
+            invert_y: false,
+            fov: default_fov(),
+            view_distance: default_view_distance(),
+            max_loaded_chunks: default_max_loaded_chunks(),
+            max_corpses: default_max_corpses(),
+            max_damage_numbers: default_max_damage_numbers(),
+            stratagem_codes: false,
+            flight_six_dof: false,
         }
     }
 }
@@ -75,3 +125,8 @@ impl GameConfig {
 fn config_path() -> std::path::PathBuf {
     std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("config.ron")
 }
+
+/// Path of the user's custom key bindings (`keybindings.ron`, next to `config.ron`).
+pub fn keybindings_path() -> std::path::PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("keybindings.ron")
+}
@@ -16,8 +16,13 @@ pub struct Destructible {
     pub debris_count: u32,
     /// Size of debris pieces.
     pub debris_size: f32,
+    /// Only blasts of at least `SEALING_BLAST_MIN` hurt it (bug holes); small arms plink off.
+    pub requires_explosive: bool,
 }
 
+/// Least damage a single blast must deal to an explosive-only destructible to count.
+pub const SEALING_BLAST_MIN: f32 = 40.0;
+
 impl Destructible {
     pub fn new(health: f32, debris_count: u32, debris_size: f32) -> Self {
         Self {
@@ -25,11 +30,30 @@ impl Destructible {
             max_health: health,
             debris_count,
             debris_size,
+            requires_explosive: false,
         }
     }
 
-    /// Apply damage and return true if destroyed.
+    /// Shrug off everything but a real blast.
+    pub fn explosive_only(mut self) -> Self {
+        self.requires_explosive = true;
+        self
+    }
+
+    /// Apply non-explosive damage (gunfire, bites) and return true if destroyed.
     pub fn damage(&mut self, amount: f32) -> bool {
+        if self.requires_explosive {
+            return false;
+        }
+        self.health = (self.health - amount).max(0.0);
+        self.health <= 0.0
+    }
+
+    /// Apply blast damage and return true if destroyed.
+    pub fn damage_explosive(&mut self, amount: f32) -> bool {
+        if self.requires_explosive && amount < SEALING_BLAST_MIN {
+            return false;
+        }
         self.health = (self.health - amount).max(0.0);
         self.health <= 0.0
     }
@@ -40,6 +64,53 @@ impl Destructible {
     }
 }
 
+/// Destructible cover at a defense base. Its static collider stops shots and marks the flow field
+/// so bugs path around it, until it's shot away or chewed through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cover {
+    /// Stack of supply crates: light, quick to lose.
+    Crate,
+    /// Steel barricade: long and low.
+    Barricade,
+    /// Sandbag wall: soaks the most punishment.
+    Sandbags,
+}
+
+impl Cover {
+    pub fn scale(self) -> Vec3 {
+        match self {
+            Cover::Crate => Vec3::new(1.4, 1.4, 1.4),
+            Cover::Barricade => Vec3::new(3.5, 1.6, 0.5),
+            Cover::Sandbags => Vec3::new(3.0, 1.1, 1.0),
+        }
+    }
+
+    pub fn health(self) -> f32 {
+        match self {
+            Cover::Crate => 150.0,
+            Cover::Barricade => 300.0,
+            Cover::Sandbags => 450.0,
+        }
+    }
+
+    pub fn color(self) -> [f32; 4] {
+        match self {
+            Cover::Crate => [0.42, 0.36, 0.22, 1.0],
+            Cover::Barricade => [0.30, 0.32, 0.34, 1.0],
+            Cover::Sandbags => [0.52, 0.46, 0.34, 1.0],
+        }
+    }
+
+    /// Destructible for this cover: health, debris count and debris size.
+    pub fn destructible(self) -> Destructible {
+        match self {
+            Cover::Crate => Destructible::new(self.health(), 8, 0.25),
+            Cover::Barricade => Destructible::new(self.health(), 10, 0.3),
+            Cover::Sandbags => Destructible::new(self.health(), 14, 0.3),
+        }
+    }
+}
+
 /// Marker for destructible rock props on terrain.
 #[derive(Debug, Clone, Copy)]
 pub struct Rock;
@@ -76,6 +147,58 @@ impl BugHole {
     }
 }
 
+/// A bug hole brought down by a blast. The game caves it in (`HoleCollapse`) and credits the seal.
+#[derive(Debug, Clone, Copy)]
+pub struct SealedHole {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+/// What a blast brought down: bug holes to cave in, and the chain reactions of destroyed
+/// destructibles (with where they went off) for the game to set off in turn.
+#[derive(Debug, Default)]
+pub struct BlastOutcome {
+    pub sealed: Vec<SealedHole>,
+    pub chain_reactions: Vec<(Vec3, ChainReaction)>,
+}
+
+/// Seconds a sealed hole takes to cave in, and how many loads of dirt fill it.
+const HOLE_COLLAPSE_SECS: f32 = 1.2;
+const HOLE_COLLAPSE_STAGES: u32 = 4;
+
+/// A sealed bug hole caving in: dirt pours in from the bottom up until the mound closes it.
+#[derive(Debug, Clone, Copy)]
+pub struct HoleCollapse {
+    pub position: Vec3,
+    pub radius: f32,
+    elapsed: f32,
+    stages_filled: u32,
+}
+
+impl HoleCollapse {
+    pub fn new(hole: SealedHole) -> Self {
+        Self { position: hole.position, radius: hole.radius, elapsed: 0.0, stages_filled: 0 }
+    }
+
+    /// Advance the cave-in; returns the dirt fills (center, radius) due this frame, lowest first.
+    pub fn update(&mut self, dt: f32) -> Vec<(Vec3, f32)> {
+        self.elapsed += dt;
+        let due = ((self.elapsed / HOLE_COLLAPSE_SECS * HOLE_COLLAPSE_STAGES as f32) as u32 + 1).min(HOLE_COLLAPSE_STAGES);
+        let mut fills = Vec::new();
+        while self.stages_filled < due {
+            self.stages_filled += 1;
+            let rise = self.stages_filled as f32 / HOLE_COLLAPSE_STAGES as f32;
+            let center = self.position - Vec3::Y * self.radius * (1.0 - rise);
+            fills.push((center, self.radius * (0.6 + 0.4 * rise)));
+        }
+        fills
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.stages_filled >= HOLE_COLLAPSE_STAGES
+    }
+}
+
 /// Organic hive structure (decorative + destructible).
 #[derive(Debug, Clone, Copy)]
 pub struct HiveStructure;
@@ -341,12 +464,26 @@ pub const MESH_GROUP_BEVELED_CUBE: u8 = 8;
 /// Hive cave / tunnel entrance: arched surface hole (Minecraft-style).
 pub const MESH_GROUP_HIVE_CAVE_ENTRANCE: u8 = 9;
 
+/// Debris rests where it lands this long (s), then sinks away over `DEBRIS_FADE_SECS`.
+pub const DEBRIS_SETTLE_SECS: f32 = 6.0;
+pub const DEBRIS_FADE_SECS: f32 = 1.5;
+/// Below this speed (m/s) a chunk touching the ground comes to rest.
+const DEBRIS_REST_SPEED: f32 = 1.0;
+
 /// Debris particle component.
 #[derive(Debug, Clone, Copy)]
 pub struct Debris {
     pub angular_velocity: Vec3,
 }
 
+impl Debris {
+    /// Render scale for a chunk with `lifetime` left: full size while it settles, shrinking into
+    /// the ground over its last `DEBRIS_FADE_SECS`.
+    pub fn fade(lifetime: &Lifetime) -> f32 {
+        (lifetime.remaining / DEBRIS_FADE_SECS).clamp(0.0, 1.0)
+    }
+}
+
 /// Flying bug guts / dismembered chunks — Euphoria-style gore explosion.
 #[derive(Debug, Clone, Copy)]
 pub struct BugGoreChunk {
@@ -354,10 +491,20 @@ pub struct BugGoreChunk {
     pub angular_velocity: Vec3,
 }
 
+/// Default number of bug corpses kept before the oldest/farthest start fading out.
+pub const DEFAULT_CORPSE_BUDGET: usize = 400;
+/// Seconds an evicted corpse takes to fade out and despawn.
+pub const CORPSE_FADE_TIME: f32 = 3.0;
+/// Corpses this close to the player and this fresh are kept for corpse-climbing.
+const CORPSE_KEEP_RADIUS_SQ: f32 = 40.0 * 40.0;
+const CORPSE_KEEP_AGE: f32 = 90.0;
+
 /// Manages destruction effects and debris.
 pub struct DestructionSystem {
     /// Maximum debris particles in the world.
     max_debris: usize,
+    /// Maximum bug corpses before the excess fades out.
+    corpse_budget: usize,
     /// Debris lifetime in seconds.
     debris_lifetime: f32,
     /// Random number generator.
@@ -374,11 +521,49 @@ impl DestructionSystem {
     pub fn new() -> Self {
         Self {
             max_debris: 500,
-            debris_lifetime: 5.0,
+            corpse_budget: DEFAULT_CORPSE_BUDGET,
+            debris_lifetime: DEBRIS_SETTLE_SECS + DEBRIS_FADE_SECS,
             rng: StdRng::from_entropy(),
         }
     }
 
+    pub fn set_corpse_budget(&mut self, budget: usize) {
+        self.corpse_budget = budget;
+    }
+
+    /// Keep at most `corpse_budget` corpses: start the excess fading out (a `Lifetime` that the
+    /// update loop expires), farthest from the player and oldest first. Fresh corpses near the
+    /// player go last so the piles being climbed survive. Corpses carry no colliders (climbing
+    /// reads their transforms), so despawning the entity later is all the cleanup needed.
+    /// Returns how many corpses started fading.
+    pub fn enforce_corpse_budget(&self, world: &mut World, player_pos: Vec3) -> usize {
+        // (entity, protected, distance², age) for corpses not already fading
+        let mut corpses: Vec<(hecs::Entity, bool, f32, f32)> = world
+            .query::<(&Transform, &BugCorpse)>()
+            .without::<&Lifetime>()
+            .iter()
+            .map(|(entity, (transform, corpse))| {
+                let dist_sq = transform.position.distance_squared(player_pos);
+                let protected = dist_sq < CORPSE_KEEP_RADIUS_SQ && corpse.decay_timer < CORPSE_KEEP_AGE;
+                (entity, protected, dist_sq, corpse.decay_timer)
+            })
+            .collect();
+        let excess = corpses.len().saturating_sub(self.corpse_budget);
+        if excess == 0 {
+            return 0;
+        }
+
+        corpses.sort_by(|a, b| {
+            a.1.cmp(&b.1)
+                .then(b.2.total_cmp(&a.2))
+                .then(b.3.total_cmp(&a.3))
+        });
+        for &(entity, ..) in &corpses[..excess] {
+            world.insert_one(entity, Lifetime::new(CORPSE_FADE_TIME)).ok();
+        }
+        excess
+    }
+
     /// Spawn flying bug guts / dismembered chunks — Euphoria-style explosion of gore.
     pub fn spawn_bug_gore_debris(
         &mut self,
@@ -443,6 +628,41 @@ impl DestructionSystem {
         }
     }
 
+    /// Spawn a shot-off leg or abdomen (`limb_scale` already sized to the bug) flung along the shot.
+    pub fn spawn_severed_limb(
+        &mut self,
+        world: &mut World,
+        position: Vec3,
+        impact_dir: Vec3,
+        bug_color: [f32; 4],
+        limb_scale: Vec3,
+    ) {
+        let spread = Vec3::new(
+            self.rng.gen_range(-0.4..0.4),
+            self.rng.gen_range(0.5..1.0),
+            self.rng.gen_range(-0.4..0.4),
+        );
+        let velocity = (impact_dir.normalize_or_zero() + spread).normalize_or_zero() * self.rng.gen_range(5.0..10.0);
+        let angular = Vec3::new(
+            self.rng.gen_range(-10.0..10.0),
+            self.rng.gen_range(-10.0..10.0),
+            self.rng.gen_range(-10.0..10.0),
+        );
+        // Exoskeleton, darker than the living bug
+        let color = [bug_color[0] * 0.6, bug_color[1] * 0.6, bug_color[2] * 0.6, 1.0];
+
+        world.spawn((
+            Transform {
+                position,
+                rotation: glam::Quat::from_rotation_arc(Vec3::Z, velocity.normalize_or_zero()),
+                scale: limb_scale,
+            },
+            Velocity::with_angular(velocity, angular),
+            BugGoreChunk { color, angular_velocity: angular },
+            Lifetime::new(8.0),
+        ));
+    }
+
     /// Spawn debris particles when something is destroyed.
     pub fn spawn_debris(
         &mut self,
@@ -504,7 +724,7 @@ impl DestructionSystem {
         let gravity = Vec3::new(0.0, -20.0, 0.0);
 
         for (_, (transform, velocity, debris)) in
-            world.query_mut::<(&mut Transform, &mut Velocity, &Debris)>()
+            world.query_mut::<(&mut Transform, &mut Velocity, &mut Debris)>()
         {
             let (ground_y, water_level) = surface_fn(transform.position.x, transform.position.z);
             if let Some(wl) = water_level {
@@ -524,13 +744,19 @@ impl DestructionSystem {
             let rotation_delta = glam::Quat::from_scaled_axis(debris.angular_velocity * dt);
             transform.rotation = rotation_delta * transform.rotation;
 
-            // Ground/water surface collision
-            let surface = ground_y.max(transform.scale.x);
+            // Ground/water surface collision: rest the chunk on the surface rather than in it
+            let surface = ground_y + transform.scale.x * 0.5;
             if transform.position.y < surface {
                 transform.position.y = surface;
                 velocity.linear.y = -velocity.linear.y * 0.3; // Bounce
                 velocity.linear.x *= 0.8; // Friction
                 velocity.linear.z *= 0.8;
+                debris.angular_velocity *= 0.7;
+                // Settle instead of jittering and spinning in place
+                if water_level.is_none() && velocity.linear.length() < DEBRIS_REST_SPEED {
+                    velocity.linear = Vec3::ZERO;
+                    debris.angular_velocity = Vec3::ZERO;
+                }
             }
 
             // Damping
@@ -576,7 +802,8 @@ impl DestructionSystem {
         }
     }
 
-    /// Apply explosion damage to destructibles.
+    /// Apply explosion damage to destructibles. Returns the bug holes it brought down and the
+    /// chain reactions of everything it destroyed.
     pub fn apply_explosion(
         &mut self,
         world: &mut World,
@@ -584,7 +811,7 @@ impl DestructionSystem {
         center: Vec3,
         radius: f32,
         damage: f32,
-    ) {
+    ) -> BlastOutcome {
         // Collect destructibles in range
         let in_range: Vec<(hecs::Entity, Vec3, u32, f32)> = world
             .query::<(&Transform, &Destructible)>()
@@ -601,16 +828,20 @@ impl DestructionSystem {
 
         // Apply damage and collect destroyed entities
         let mut to_spawn_debris: Vec<(Vec3, u32, f32)> = Vec::new();
-        
+        let mut outcome = BlastOutcome::default();
+
         for (entity, pos, debris_count, debris_size) in in_range {
             let dist = pos.distance(center);
             let falloff = 1.0 - (dist / radius);
             let actual_damage = damage * falloff;
 
             if let Ok(mut destructible) = world.get::<&mut Destructible>(entity) {
-                if destructible.damage(actual_damage) {
+                if destructible.damage_explosive(actual_damage) {
                     // Mark for debris spawn after borrow ends
                     to_spawn_debris.push((pos, debris_count, debris_size));
+                    if let Ok(chain) = world.get::<&ChainReaction>(entity) {
+                        outcome.chain_reactions.push((pos, (*chain).clone()));
+                    }
                 }
             }
         }
@@ -629,8 +860,17 @@ impl DestructionSystem {
             .collect();
 
         for entity in destroyed {
+            if let Ok(phys) = world.get::<&DestructiblePhysics>(entity) {
+                physics.remove_body(phys.body_handle);
+            }
+            if let Ok(mut query) = world.query_one::<(&Transform, &BugHole)>(entity) {
+                if let Some((transform, _)) = query.get() {
+                    outcome.sealed.push(SealedHole { position: transform.position, radius: transform.scale.x });
+                }
+            }
             world.despawn(entity).ok();
         }
+        outcome
     }
 
     /// Create a chunk of terrain that can be destroyed.
@@ -650,3 +890,119 @@ impl DestructionSystem {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn_corpse(world: &mut World, position: Vec3, age: f32) -> hecs::Entity {
+        let mut corpse = BugCorpse::new([1.0; 4], 0, Vec3::ONE);
+        corpse.decay_timer = age;
+        world.spawn((Transform { position, ..Default::default() }, corpse))
+    }
+
+    #[test]
+    fn bug_holes_only_fall_to_big_enough_blasts_and_cave_in_from_the_bottom() {
+        let mut world = World::new();
+        let mut destruction = DestructionSystem::new();
+        let mut physics = PhysicsWorld::new();
+        let hole = world.spawn((
+            Transform { position: Vec3::new(0.0, 2.0, 0.0), scale: Vec3::splat(2.0), ..Default::default() },
+            Destructible::new(100.0, 4, 0.3).explosive_only(),
+            BugHole::new(5.0, 4),
+        ));
+
+        // Small arms and a distant pop do nothing
+        assert!(!world.get::<&mut Destructible>(hole).unwrap().damage(500.0));
+        assert!(destruction.apply_explosion(&mut world, &mut physics, Vec3::new(9.0, 2.0, 0.0), 10.0, 200.0).sealed.is_empty());
+        assert_eq!(world.get::<&Destructible>(hole).unwrap().health, 100.0);
+
+        let sealed = destruction.apply_explosion(&mut world, &mut physics, Vec3::new(1.0, 2.0, 0.0), 10.0, 200.0).sealed;
+        assert_eq!(sealed.len(), 1);
+        assert!(!world.contains(hole));
+
+        let mut collapse = HoleCollapse::new(sealed[0]);
+        let mut fills = Vec::new();
+        while !collapse.is_done() {
+            fills.extend(collapse.update(0.1));
+        }
+        assert_eq!(fills.len(), HOLE_COLLAPSE_STAGES as usize);
+        assert!(fills.windows(2).all(|w| w[0].0.y < w[1].0.y));
+        assert_eq!(fills.last().unwrap().0, sealed[0].position);
+    }
+
+    #[test]
+    fn blasting_an_explosive_only_mound_sets_off_its_chain_reaction() {
+        let mut world = World::new();
+        let mut destruction = DestructionSystem::new();
+        let mut physics = PhysicsWorld::new();
+        let chain = ChainReaction { radius: 6.5, damage: 58.0, effect: ChainEffect::Collapse };
+        let mound = world.spawn((
+            Transform { position: Vec3::new(0.0, 1.0, 0.0), ..Default::default() },
+            Destructible::new(100.0, 4, 0.3).explosive_only(),
+            chain.clone(),
+        ));
+
+        // Scratched but standing: nothing goes off
+        let outcome = destruction.apply_explosion(&mut world, &mut physics, Vec3::ZERO, 10.0, 60.0);
+        assert!(outcome.chain_reactions.is_empty());
+        assert!(world.contains(mound));
+
+        let outcome = destruction.apply_explosion(&mut world, &mut physics, Vec3::ZERO, 10.0, 200.0);
+        assert!(!world.contains(mound));
+        assert_eq!(outcome.chain_reactions.len(), 1);
+        let (position, reaction) = &outcome.chain_reactions[0];
+        assert_eq!(*position, Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(reaction.radius, chain.radius);
+        assert_eq!(reaction.effect, ChainEffect::Collapse);
+    }
+
+    #[test]
+    fn debris_lands_settles_and_fades() {
+        let mut world = World::new();
+        let mut destruction = DestructionSystem::new();
+        let mut physics = PhysicsWorld::new();
+        destruction.spawn_debris(&mut world, Vec3::new(0.0, 5.0, 0.0), 6, 0.4, &mut physics);
+        for _ in 0..300 {
+            destruction.update_debris(&mut world, 1.0 / 60.0, |_, _| (0.0, None));
+        }
+        for (_, (transform, velocity, debris, lifetime)) in
+            world.query::<(&Transform, &Velocity, &Debris, &Lifetime)>().iter()
+        {
+            assert!((transform.position.y - transform.scale.x * 0.5).abs() < 0.05);
+            assert_eq!(velocity.linear, Vec3::ZERO);
+            assert_eq!(debris.angular_velocity, Vec3::ZERO);
+            // Still around and at full size after landing
+            assert_eq!(Debris::fade(lifetime), 1.0);
+        }
+        assert_eq!(world.query::<&Debris>().iter().count(), 6);
+        assert_eq!(Debris::fade(&Lifetime::new(DEBRIS_FADE_SECS * 0.5)), 0.5);
+    }
+
+    #[test]
+    fn corpse_budget_fades_far_and_old_corpses_first() {
+        let mut world = World::new();
+        let mut destruction = DestructionSystem::new();
+        destruction.set_corpse_budget(2);
+
+        let fresh_near = spawn_corpse(&mut world, Vec3::new(5.0, 0.0, 0.0), 1.0);
+        let old_near = spawn_corpse(&mut world, Vec3::new(8.0, 0.0, 0.0), 500.0);
+        let far = spawn_corpse(&mut world, Vec3::new(200.0, 0.0, 0.0), 10.0);
+        let farther = spawn_corpse(&mut world, Vec3::new(300.0, 0.0, 0.0), 5.0);
+
+        assert_eq!(destruction.enforce_corpse_budget(&mut world, Vec3::ZERO), 2);
+        assert!(world.get::<&Lifetime>(farther).is_ok());
+        assert!(world.get::<&Lifetime>(far).is_ok());
+        assert!(world.get::<&Lifetime>(old_near).is_err());
+        assert!(world.get::<&Lifetime>(fresh_near).is_err());
+
+        // Fading corpses don't count against the budget, so nothing more is evicted
+        assert_eq!(destruction.enforce_corpse_budget(&mut world, Vec3::ZERO), 0);
+
+        // A new kill pushes it over again: the old corpse goes before the fresh nearby one
+        spawn_corpse(&mut world, Vec3::new(3.0, 0.0, 0.0), 0.0);
+        assert_eq!(destruction.enforce_corpse_budget(&mut world, Vec3::ZERO), 1);
+        assert!(world.get::<&Lifetime>(old_near).is_ok());
+        assert!(world.get::<&Lifetime>(fresh_near).is_err());
+    }
+}
@@ -1,169 +1,315 @@
 //! Dialogue system for Earth settlement citizens — Starship Troopers flavor (Federation, service, propaganda).
+//!
+//! Each dialogue is a small graph: a choice jumps to any node or ends the talk, and can set a
+//! flag in the speaker's `DialogueMemory` entry. Choices can require (or be hidden by) a flag,
+//! and speakers who remember you open on their return greeting instead of the first node.
+
+use std::collections::{HashMap, HashSet};
 
 use hecs::Entity;
 
-/// One dialogue node: line of text and optional choices (label, next node index or None to close).
+/// Flag set on a speaker the first time you talk to them.
+pub const FLAG_MET: &str = "met";
+
+/// One player choice: label, where it leads, and the flags it reads and writes.
+#[derive(Debug, Clone)]
+pub struct DialogueChoice {
+    pub label: String,
+    /// Next node index, or None to close the dialogue.
+    pub next: Option<usize>,
+    /// Flag remembered for this speaker when picked.
+    pub sets: Option<&'static str>,
+    /// Only offered once this flag is set for the speaker.
+    pub requires: Option<&'static str>,
+    /// Hidden once this flag is set for the speaker.
+    pub unless: Option<&'static str>,
+}
+
+impl DialogueChoice {
+    pub fn goto(label: &str, next: usize) -> Self {
+        Self { label: label.to_string(), next: Some(next), sets: None, requires: None, unless: None }
+    }
+
+    pub fn end(label: &str) -> Self {
+        Self { label: label.to_string(), next: None, sets: None, requires: None, unless: None }
+    }
+
+    pub fn sets(mut self, flag: &'static str) -> Self {
+        self.sets = Some(flag);
+        self
+    }
+
+    pub fn requires(mut self, flag: &'static str) -> Self {
+        self.requires = Some(flag);
+        self
+    }
+
+    pub fn unless(mut self, flag: &'static str) -> Self {
+        self.unless = Some(flag);
+        self
+    }
+
+    fn offered(&self, flags: Option<&HashSet<&'static str>>) -> bool {
+        let has = |flag: &'static str| flags.is_some_and(|f| f.contains(flag));
+        self.requires.is_none_or(has) && !self.unless.is_some_and(has)
+    }
+}
+
+/// One dialogue node: line of text and its choices.
 #[derive(Debug, Clone)]
 pub struct DialogueNode {
     pub text: String,
-    pub choices: Vec<(String, Option<usize>)>,
+    pub choices: Vec<DialogueChoice>,
+}
+
+fn node(text: &str, choices: Vec<DialogueChoice>) -> DialogueNode {
+    DialogueNode { text: text.to_string(), choices }
+}
+
+/// A dialogue graph: nodes plus the node a speaker who remembers you opens with.
+#[derive(Debug, Clone)]
+pub struct DialogueGraph {
+    pub nodes: Vec<DialogueNode>,
+    /// Entry node on later talks (after `FLAG_MET`); None = always start at node 0.
+    pub return_node: Option<usize>,
+}
+
+/// Per-speaker conversation flags, so NPCs remember prior talks (keyed by speaker name).
+#[derive(Debug, Clone, Default)]
+pub struct DialogueMemory {
+    flags: HashMap<String, HashSet<&'static str>>,
+}
+
+impl DialogueMemory {
+    pub fn has(&self, speaker: &str, flag: &str) -> bool {
+        self.flags.get(speaker).is_some_and(|f| f.contains(flag))
+    }
+
+    pub fn set(&mut self, speaker: &str, flag: &'static str) {
+        self.flags.entry(speaker.to_string()).or_default().insert(flag);
+    }
+
+    fn flags(&self, speaker: &str) -> Option<&HashSet<&'static str>> {
+        self.flags.get(speaker)
+    }
 }
 
-/// Predefined dialogue trees per dialogue_id (0..5). Advanced dialogue: topics and responses.
-pub fn dialogue_content(dialogue_id: usize) -> Vec<DialogueNode> {
-    let nodes: Vec<DialogueNode> = match dialogue_id {
-        0 => vec![
-            DialogueNode {
-                text: "Citizen! Doing your part today? The Federation needs every hand.".to_string(),
-                choices: vec![
-                    ("What's the situation?".to_string(), Some(1)),
-                    ("I'm with the MI. Hold the line.".to_string(), Some(2)),
-                    ("Stay safe. Goodbye.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Bugs pushed back from the perimeter last week. We're rebuilding. Would do it again for Earth.".to_string(),
-                choices: vec![("I'm with the MI. Hold the line.".to_string(), Some(2)), ("Stay safe.".to_string(), None)],
-            },
-            DialogueNode {
-                text: "Thank you, trooper. We see the drop pods. Good hunting.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        1 => vec![
-            DialogueNode {
-                text: "You're one of the Mobile Infantry? We heard the Roger Young was in orbit.".to_string(),
-                choices: vec![
-                    ("That's right. Defending the homeworld.".to_string(), Some(1)),
-                    ("How's the colony holding up?".to_string(), Some(2)),
-                    ("Carry on, citizen.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Earth is worth it. We're all doing our part.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-            DialogueNode {
-                text: "We keep the power and water running. You keep the bugs off our doorstep.".to_string(),
-                choices: vec![("We will. Goodbye.".to_string(), None)],
-            },
-        ],
-        2 => vec![
-            DialogueNode {
-                text: "Service guarantees citizenship. You're living proof.".to_string(),
-                choices: vec![
-                    ("What do you do here?".to_string(), Some(1)),
-                    ("Would you like to know more?".to_string(), Some(2)),
-                    ("Goodbye.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Logistics. Food, ammo, repairs. The colony runs so you can fight.".to_string(),
-                choices: vec![("Thank you. Goodbye.".to_string(), None)],
-            },
-            DialogueNode {
-                text: "I'm from Buenos Aires, and I say kill 'em all!".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        3 => vec![
-            DialogueNode {
-                text: "Rough weather. Stay dry, trooper.".to_string(),
-                choices: vec![
-                    ("How often does it storm here?".to_string(), Some(1)),
-                    ("You too. Goodbye.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "More than we'd like. We get under cover. You get the bugs.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        4 => vec![
-            DialogueNode {
-                text: "The only good bug is a dead bug. We're counting on you.".to_string(),
-                choices: vec![
-                    ("We'll hold the line.".to_string(), Some(1)),
-                    ("Goodbye.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Earth is worth fighting for. We remember.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
+/// Predefined dialogue graphs per dialogue_id (0..4 citizens, 5..9 Roger Young crew).
+pub fn dialogue_content(dialogue_id: usize) -> DialogueGraph {
+    use DialogueChoice as C;
+    let (nodes, return_node) = match dialogue_id {
+        0 => (
+            vec![
+                node(
+                    "Citizen! Doing your part today? The Federation needs every hand.",
+                    vec![
+                        C::goto("What's the situation?", 1).sets("asked_situation"),
+                        C::goto("I'm with the MI. Hold the line.", 2),
+                        C::end("Stay safe. Goodbye."),
+                    ],
+                ),
+                node(
+                    "Bugs pushed back from the perimeter last week. We're rebuilding. Would do it again for Earth.",
+                    vec![C::goto("I'm with the MI. Hold the line.", 2), C::end("Stay safe.")],
+                ),
+                node("Thank you, trooper. We see the drop pods. Good hunting.", vec![C::end("Goodbye.")]),
+                node(
+                    "Back again, trooper? Still standing, same as us.",
+                    vec![
+                        C::goto("How's the rebuilding going?", 4).requires("asked_situation"),
+                        C::goto("What's the situation?", 1).sets("asked_situation").unless("asked_situation"),
+                        C::end("Just checking in. Goodbye."),
+                    ],
+                ),
+                node(
+                    "East wall's back up. Thanks for asking — nobody in the MI ever does.",
+                    vec![C::end("Keep at it. Goodbye.")],
+                ),
+            ],
+            Some(3),
+        ),
+        1 => (
+            vec![
+                node(
+                    "You're one of the Mobile Infantry? We heard the Roger Young was in orbit.",
+                    vec![
+                        C::goto("That's right. Defending the homeworld.", 1),
+                        C::goto("How's the colony holding up?", 2).sets("asked_colony"),
+                        C::end("Carry on, citizen."),
+                    ],
+                ),
+                node("Earth is worth it. We're all doing our part.", vec![C::end("Goodbye.")]),
+                node(
+                    "We keep the power and water running. You keep the bugs off our doorstep.",
+                    vec![C::end("We will. Goodbye.").sets("promised_defense")],
+                ),
+                node(
+                    "The trooper from the Roger Young! You kept your word — no bugs on our doorstep.",
+                    vec![C::end("That's the job. Goodbye.")],
+                ),
+                node(
+                    "Roger Young's still up there, then. Good.",
+                    vec![
+                        C::goto("Told you we'd keep them off your doorstep.", 3).requires("promised_defense"),
+                        C::goto("How's the colony holding up?", 2).sets("asked_colony").unless("asked_colony"),
+                        C::end("Carry on, citizen."),
+                    ],
+                ),
+            ],
+            Some(4),
+        ),
+        2 => (
+            vec![
+                node(
+                    "Service guarantees citizenship. You're living proof.",
+                    vec![
+                        C::goto("What do you do here?", 1),
+                        C::goto("Would you like to know more?", 2),
+                        C::end("Goodbye."),
+                    ],
+                ),
+                node(
+                    "Logistics. Food, ammo, repairs. The colony runs so you can fight.",
+                    vec![C::end("Thank you. Goodbye.").sets("thanked")],
+                ),
+                node("I'm from Buenos Aires, and I say kill 'em all!", vec![C::end("Goodbye.")]),
+                node(
+                    "Good to see you in one piece. Ammo crates went out on schedule.",
+                    vec![
+                        C::end("The crates made the difference. Thanks.").requires("thanked"),
+                        C::goto("Would you like to know more?", 2),
+                        C::end("Goodbye."),
+                    ],
+                ),
+            ],
+            Some(3),
+        ),
+        3 => (
+            vec![
+                node(
+                    "Rough weather. Stay dry, trooper.",
+                    vec![C::goto("How often does it storm here?", 1), C::end("You too. Goodbye.")],
+                ),
+                node("More than we'd like. We get under cover. You get the bugs.", vec![C::end("Goodbye.")]),
+            ],
+            None,
+        ),
+        4 => (
+            vec![
+                node(
+                    "The only good bug is a dead bug. We're counting on you.",
+                    vec![C::goto("We'll hold the line.", 1).sets("held_line"), C::end("Goodbye.")],
+                ),
+                node("Earth is worth fighting for. We remember.", vec![C::end("Goodbye.")]),
+                node(
+                    "You said you'd hold the line. Still holding?",
+                    vec![
+                        C::goto("Every day.", 1).requires("held_line"),
+                        C::end("Goodbye."),
+                    ],
+                ),
+            ],
+            Some(2),
+        ),
         // 5–9: Roger Young crew (Fleet, FleetOfficer, MobileInfantry, Marauder, Johnny Rico)
-        5 => vec![
-            DialogueNode {
-                text: "Ship's running smooth. War table's that way if you're dropping.".to_string(),
-                choices: vec![
-                    ("What's our status?".to_string(), Some(1)),
-                    ("Carry on.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "All systems nominal. Pick a planet, get your mission, and head to the bay.".to_string(),
-                choices: vec![("Thanks.".to_string(), None)],
-            },
-        ],
-        6 => vec![
-            DialogueNode {
-                text: "We hold the line so you can drop. Don't make our job harder.".to_string(),
-                choices: vec![
-                    ("What's the word from Fleet?".to_string(), Some(1)),
-                    ("Understood. Good hunting.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Same as always: kill bugs, hold ground, extract when you're done.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        7 => vec![
-            DialogueNode {
-                text: "Ready to drop, trooper? War table's where you pick the mission.".to_string(),
-                choices: vec![
-                    ("Would you like to know more?".to_string(), Some(1)),
-                    ("See you on the surface.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "I'm from Buenos Aires, and I say kill 'em all!".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        8 => vec![
-            DialogueNode {
-                text: "Marauder squad's on standby. You need fire support, we're there.".to_string(),
-                choices: vec![
-                    ("What's the loadout?".to_string(), Some(1)),
-                    ("Good to know. Thanks.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Heavy armor, heavy guns. We punch holes; you fill 'em.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        9 => vec![
-            DialogueNode {
-                text: "Rico. Pick your planet and mission at the war table. Drop bay's aft.".to_string(),
-                choices: vec![
-                    ("What's our priority?".to_string(), Some(1)),
-                    ("We'll get it done.".to_string(), None),
-                ],
-            },
-            DialogueNode {
-                text: "Same as always: protect the Federation. Now move out.".to_string(),
-                choices: vec![("Goodbye.".to_string(), None)],
-            },
-        ],
-        _ => vec![DialogueNode {
-            text: "Citizen. Good day.".to_string(),
-            choices: vec![("Goodbye.".to_string(), None)],
-        }],
+        5 => (
+            vec![
+                node(
+                    "Ship's running smooth. War table's that way if you're dropping.",
+                    vec![C::goto("What's our status?", 1), C::end("Carry on.")],
+                ),
+                node(
+                    "All systems nominal. Pick a planet, get your mission, and head to the bay.",
+                    vec![C::end("Thanks.")],
+                ),
+            ],
+            None,
+        ),
+        6 => (
+            vec![
+                node(
+                    "We hold the line so you can drop. Don't make our job harder.",
+                    vec![C::goto("What's the word from Fleet?", 1), C::end("Understood. Good hunting.")],
+                ),
+                node(
+                    "Same as always: kill bugs, hold ground, extract when you're done.",
+                    vec![C::end("Goodbye.")],
+                ),
+            ],
+            None,
+        ),
+        7 => (
+            vec![
+                node(
+                    "Ready to drop, trooper? War table's where you pick the mission.",
+                    vec![
+                        C::goto("Would you like to know more?", 1).sets("heard_motto"),
+                        C::end("See you on the surface."),
+                    ],
+                ),
+                node("I'm from Buenos Aires, and I say kill 'em all!", vec![C::end("Goodbye.")]),
+                node(
+                    "Back for more? Boots laced, rifle clean?",
+                    vec![
+                        C::goto("Say it again.", 1).requires("heard_motto"),
+                        C::end("See you on the surface."),
+                    ],
+                ),
+            ],
+            Some(2),
+        ),
+        8 => (
+            vec![
+                node(
+                    "Marauder squad's on standby. You need fire support, we're there.",
+                    vec![
+                        C::goto("What's the loadout?", 1).sets("asked_loadout"),
+                        C::end("Good to know. Thanks."),
+                    ],
+                ),
+                node("Heavy armor, heavy guns. We punch holes; you fill 'em.", vec![C::end("Goodbye.")]),
+                node(
+                    "Still standing by, trooper.",
+                    vec![
+                        C::goto("Remind me of the loadout.", 1).requires("asked_loadout"),
+                        C::goto("What's the loadout?", 1).sets("asked_loadout").unless("asked_loadout"),
+                        C::end("Good to know. Thanks."),
+                    ],
+                ),
+            ],
+            Some(2),
+        ),
+        9 => (
+            vec![
+                node(
+                    "Rico. Pick your planet and mission at the war table. Drop bay's aft.",
+                    vec![
+                        C::goto("What's our priority?", 1).sets("briefed"),
+                        C::end("We'll get it done."),
+                    ],
+                ),
+                node(
+                    "Same as always: protect the Federation. Now move out.",
+                    vec![C::end("Goodbye.")],
+                ),
+                node(
+                    "You again. Briefing hasn't changed, trooper.",
+                    vec![
+                        C::goto("Anything else I should know?", 3).requires("briefed"),
+                        C::goto("What's our priority?", 1).sets("briefed").unless("briefed"),
+                        C::end("Moving out."),
+                    ],
+                ),
+                node(
+                    "Everybody fights, nobody quits. You don't do your job, I'll shoot you myself.",
+                    vec![C::end("Understood, sir.")],
+                ),
+            ],
+            Some(2),
+        ),
+        _ => (vec![node("Citizen. Good day.", vec![C::end("Goodbye.")])], None),
     };
-    nodes
+    DialogueGraph { nodes, return_node }
 }
 
 /// Current dialogue UI state.
@@ -187,65 +333,123 @@ impl Default for DialogueState {
 }
 
 impl DialogueState {
+    /// Start talking to a speaker: opens on their return greeting if they remember you.
+    pub fn open(
+        speaker_entity: Option<Entity>,
+        speaker_name: String,
+        dialogue_id: usize,
+        memory: &mut DialogueMemory,
+    ) -> Self {
+        let graph = dialogue_content(dialogue_id);
+        let node_index = graph
+            .return_node
+            .filter(|_| memory.has(&speaker_name, FLAG_MET))
+            .unwrap_or(0);
+        memory.set(&speaker_name, FLAG_MET);
+        DialogueState::Open {
+            speaker_entity,
+            speaker_name,
+            dialogue_id,
+            node_index,
+            showing_choices: true,
+        }
+    }
+
     pub fn is_open(&self) -> bool {
         matches!(self, DialogueState::Open { .. })
     }
 
-    /// Get current line and choices for overlay.
-    pub fn current_line_and_choices(&self) -> Option<(String, Vec<(String, Option<usize>)>)> {
+    /// Current line and the choices offered to the player (flag-gated choices filtered out).
+    pub fn current_line_and_choices(&self, memory: &DialogueMemory) -> Option<(String, Vec<String>)> {
+        let (node, flags) = self.current_node(memory)?;
+        let labels = node
+            .choices
+            .iter()
+            .filter(|c| c.offered(flags))
+            .map(|c| c.label.clone())
+            .collect();
+        Some((node.text, labels))
+    }
+
+    fn current_node<'m>(&self, memory: &'m DialogueMemory) -> Option<(DialogueNode, Option<&'m HashSet<&'static str>>)> {
         match self {
-            DialogueState::Open {
-                dialogue_id,
-                node_index,
-                ..
-            } => {
-                let nodes = dialogue_content(*dialogue_id);
-                let node = nodes.get(*node_index)?;
-                Some((
-                    node.text.clone(),
-                    node.choices.clone(),
-                ))
+            DialogueState::Open { speaker_name, dialogue_id, node_index, .. } => {
+                let node = dialogue_content(*dialogue_id).nodes.get(*node_index)?.clone();
+                Some((node, memory.flags(speaker_name)))
             }
-            _ => None,
+            DialogueState::Closed => None,
         }
     }
 
-    /// Select choice by index (0-based). Returns true if dialogue closed.
-    pub fn select_choice(&mut self, choice_index: usize) -> bool {
-        let (dialogue_id, node_index, speaker_entity, speaker_name) = match self {
-            DialogueState::Open {
-                speaker_entity,
-                speaker_name,
-                dialogue_id,
-                node_index,
-                ..
-            } => (*dialogue_id, *node_index, *speaker_entity, speaker_name.clone()),
-            _ => return true,
-        };
-        let nodes = dialogue_content(dialogue_id);
-        let node = match nodes.get(node_index) {
-            Some(n) => n,
-            None => return true,
-        };
-        let (_, next) = match node.choices.get(choice_index) {
-            Some(c) => c,
-            None => return true,
+    /// Select an offered choice by index (0-based): remembers its flag for the speaker, then
+    /// jumps to its node or closes. Returns true if dialogue closed.
+    pub fn select_choice(&mut self, choice_index: usize, memory: &mut DialogueMemory) -> bool {
+        let Some((node, flags)) = self.current_node(memory) else { return true };
+        let Some(choice) = node.choices.into_iter().filter(|c| c.offered(flags)).nth(choice_index) else {
+            return true;
         };
-        match next {
+        let DialogueState::Open { speaker_name, node_index, .. } = self else { return true };
+        if let Some(flag) = choice.sets {
+            memory.set(speaker_name, flag);
+        }
+        match choice.next {
+            Some(next) => {
+                *node_index = next;
+                false
+            }
             None => {
                 *self = DialogueState::Closed;
                 true
             }
-            Some(next_idx) => {
-                *self = DialogueState::Open {
-                    speaker_entity,
-                    speaker_name,
-                    dialogue_id,
-                    node_index: *next_idx,
-                    showing_choices: true,
-                };
-                false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_choice_leads_to_a_real_node() {
+        for id in 0..=10 {
+            let graph = dialogue_content(id);
+            let len = graph.nodes.len();
+            assert!(graph.return_node.is_none_or(|n| n < len), "dialogue {id} return node");
+            for node in &graph.nodes {
+                for choice in &node.choices {
+                    assert!(choice.next.is_none_or(|n| n < len), "dialogue {id}: {}", choice.label);
+                }
             }
         }
     }
+
+    #[test]
+    fn choices_set_flags_that_change_the_next_talk() {
+        let mut memory = DialogueMemory::default();
+        let mut talk = DialogueState::open(None, "Johnny Rico".into(), 9, &mut memory);
+        let (_, choices) = talk.current_line_and_choices(&memory).unwrap();
+        assert_eq!(choices, ["What's our priority?", "We'll get it done."]);
+
+        // Asking for the priority remembers the briefing and jumps to its node
+        assert!(!talk.select_choice(0, &mut memory));
+        assert!(memory.has("Johnny Rico", "briefed"));
+        let (line, _) = talk.current_line_and_choices(&memory).unwrap();
+        assert!(line.starts_with("Same as always"));
+        assert!(talk.select_choice(0, &mut memory));
+        assert!(!talk.is_open());
+
+        // Second talk opens on the return greeting with the flag-gated follow-up
+        let mut talk = DialogueState::open(None, "Johnny Rico".into(), 9, &mut memory);
+        let (line, choices) = talk.current_line_and_choices(&memory).unwrap();
+        assert!(line.starts_with("You again"));
+        assert_eq!(choices, ["Anything else I should know?", "Moving out."]);
+        assert!(!talk.select_choice(0, &mut memory));
+        let (line, _) = talk.current_line_and_choices(&memory).unwrap();
+        assert!(line.starts_with("Everybody fights"));
+
+        // Another speaker with the same script has their own memory
+        let talk = DialogueState::open(None, "Sgt. Zim".into(), 9, &mut memory);
+        let (line, _) = talk.current_line_and_choices(&memory).unwrap();
+        assert!(line.starts_with("Rico."));
+    }
 }
@@ -0,0 +1,117 @@
+//! Difficulty presets chosen at the war table.
+//!
+//! Each preset is a set of multipliers on top of planet danger: bug spawn pressure,
+//! bug health, damage the player takes, stamina recovery, and stratagem rearm times.
+
+/// Player-selected difficulty, saved with the galactic war.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Helldive,
+}
+
+impl Difficulty {
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Helldive => "Helldive",
+        }
+    }
+
+    /// Next preset, wrapping back to Easy (war table cycles through them).
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Helldive,
+            Difficulty::Helldive => Difficulty::Easy,
+        }
+    }
+
+    /// Scales bug spawn rate and the starting horde size.
+    pub fn spawn_rate_mult(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+            Difficulty::Helldive => 2.0,
+        }
+    }
+
+    /// Scales bug health at spawn.
+    pub fn bug_health_mult(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+            Difficulty::Helldive => 1.7,
+        }
+    }
+
+    /// Scales all damage the player takes.
+    pub fn damage_taken_mult(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
+            Difficulty::Helldive => 1.6,
+        }
+    }
+
+    /// Scales how fast stamina recovers after sprinting.
+    pub fn stamina_recovery_mult(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.25,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.9,
+            Difficulty::Helldive => 0.8,
+        }
+    }
+
+    /// Scales stratagem cooldowns (supply drop, reinforcements, artillery, tac fighters).
+    pub fn stratagem_cooldown_mult(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.2,
+            Difficulty::Helldive => 1.5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: [Difficulty; 4] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Helldive];
+
+    #[test]
+    fn each_tier_is_harder_than_the_last() {
+        for pair in ORDER.windows(2) {
+            let (easier, harder) = (pair[0], pair[1]);
+            assert!(harder.spawn_rate_mult() > easier.spawn_rate_mult());
+            assert!(harder.bug_health_mult() > easier.bug_health_mult());
+            assert!(harder.damage_taken_mult() > easier.damage_taken_mult());
+            assert!(harder.stamina_recovery_mult() < easier.stamina_recovery_mult());
+            assert!(harder.stratagem_cooldown_mult() > easier.stratagem_cooldown_mult());
+        }
+        // Normal is the unscaled baseline
+        let normal = Difficulty::default();
+        assert_eq!(normal, Difficulty::Normal);
+        assert_eq!(normal.spawn_rate_mult(), 1.0);
+        assert_eq!(normal.damage_taken_mult(), 1.0);
+    }
+
+    #[test]
+    fn cycling_visits_every_tier_and_wraps() {
+        for pair in ORDER.windows(2) {
+            assert_eq!(pair[0].next(), pair[1]);
+        }
+        assert_eq!(Difficulty::Helldive.next(), Difficulty::Easy);
+    }
+}
@@ -1,89 +1,181 @@
-//! Ambient particle effects: dust motes, rain, and tracer projectiles.
+//! Ambient particle effects: pooled rain, snow and dust motes, and tracer projectiles.
 
 use glam::Vec3;
+use renderer::ParticleInstance;
+use std::collections::VecDeque;
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum DustShape {
-    Billboard,
-    Sphere,
+/// Which emitter a pooled particle belongs to; selects how it spawns and how it is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// Thin falling streaks around the camera.
+    Rain,
+    /// Soft drifting flakes (Snow weather).
+    Snow,
+    /// Ambient dust motes floating in the air.
+    Dust,
 }
 
-/// Ambient dust particles floating in the air for atmosphere.
-pub struct AmbientDust {
-    pub particles: Vec<DustParticle>,
-    pub spawn_timer: f32,
+impl ParticleKind {
+    pub const ALL: [ParticleKind; 3] = [ParticleKind::Rain, ParticleKind::Snow, ParticleKind::Dust];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Pool size: storage is allocated once; emitting while full drops the oldest particle.
+    fn capacity(self) -> usize {
+        match self {
+            ParticleKind::Rain => 400,
+            ParticleKind::Snow => 350,
+            ParticleKind::Dust => 375,
+        }
+    }
+
+    /// A new particle somewhere around `center`; `speed` is the emitter's fall speed (rain/snow).
+    fn spawn(self, center: Vec3, speed: f32) -> Particle {
+        let r = || rand::random::<f32>();
+        match self {
+            ParticleKind::Rain => Particle {
+                position: center + Vec3::new((r() - 0.5) * 40.0, r() * 20.0, (r() - 0.5) * 40.0),
+                velocity: Vec3::new(0.5, -speed, 0.2),
+                life: 2.0,
+                size: 0.02,
+            },
+            ParticleKind::Snow => Particle {
+                position: center + Vec3::new((r() - 0.5) * 35.0, r() * 18.0, (r() - 0.5) * 35.0),
+                velocity: Vec3::new((r() - 0.5) * 1.5, -speed, (r() - 0.5) * 1.5),
+                life: 4.0,
+                size: 0.04 + r() * 0.05,
+            },
+            ParticleKind::Dust => Particle {
+                position: center + Vec3::new((r() - 0.5) * 30.0, (r() - 0.3) * 10.0, (r() - 0.5) * 30.0),
+                velocity: Vec3::new((r() - 0.5) * 0.5, (r() - 0.5) * 0.2, (r() - 0.5) * 0.5),
+                life: 4.0 + r() * 4.0,
+                // Mostly larger motes with some fine specks
+                size: if r() < 0.7 { 0.03 + r() * 0.06 } else { 0.015 + r() * 0.03 },
+            },
+        }
+    }
+
+    /// Billboard for a live particle, or None once it has faded out.
+    fn instance(self, p: &Particle) -> Option<ParticleInstance> {
+        let (width, height, color) = match self {
+            ParticleKind::Rain => (p.size, 0.12, [0.65, 0.75, 0.95, p.life.min(1.0)]),
+            ParticleKind::Snow => (p.size, p.size, [0.95, 0.97, 1.0, (p.life / 2.0).min(1.0) * 0.9]),
+            ParticleKind::Dust => {
+                let shimmer = 1.0 + (p.life * 3.0).sin() * 0.15;
+                let tint = (p.position.x * 7.3 + p.position.z * 11.1).sin() * 0.05;
+                let alpha = (p.life / 4.0).min(1.0) * 0.8;
+                (p.size * shimmer, p.size * shimmer, [0.82 + tint, 0.76 + tint, 0.65, alpha])
+            }
+        };
+        let min_alpha = match self {
+            ParticleKind::Snow => 0.15,
+            _ => 0.2,
+        };
+        if color[3] < min_alpha {
+            return None;
+        }
+        let instance = ParticleInstance::new(p.position.to_array(), [width, height], color);
+        // Rain streaks stay vertical; flakes and motes face the camera
+        Some(if self == ParticleKind::Rain { instance.upright() } else { instance })
+    }
 }
 
-pub struct DustParticle {
+/// One pooled particle. All kinds share the same motion; `ParticleKind` decides the look.
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
     pub position: Vec3,
     pub velocity: Vec3,
     pub life: f32,
     pub size: f32,
-    pub shape: DustShape,
-    /// Slow spin for billboard particles (radians)
-    pub spin: f32,
 }
 
-impl AmbientDust {
+/// Pooled particles of one kind plus its current emission settings.
+struct Emitter {
+    pool: VecDeque<Particle>,
+    capacity: usize,
+    /// Particles per second; fractional spawns carry over between frames.
+    rate: f32,
+    speed: f32,
+    /// Stop emitting while this many are alive (below `capacity` for density-driven effects).
+    max_alive: usize,
+    carry: f32,
+}
+
+/// All ambient particles in fixed pools with one update and one instance-collection path.
+/// Set each kind's emission from gameplay (weather, location), then `update` once per frame.
+pub struct ParticleSystem {
+    emitters: Vec<Emitter>,
+}
+
+impl ParticleSystem {
     pub fn new() -> Self {
-        Self { particles: Vec::new(), spawn_timer: 0.0 }
+        let emitters = ParticleKind::ALL
+            .iter()
+            .map(|kind| Emitter {
+                pool: VecDeque::with_capacity(kind.capacity()),
+                capacity: kind.capacity(),
+                rate: 0.0,
+                speed: 0.0,
+                max_alive: kind.capacity(),
+                carry: 0.0,
+            })
+            .collect();
+        Self { emitters }
     }
 
-    /// density_mult: 1.0 = normal; higher when cloudy/rain for more visible floating particles
-    pub fn update(&mut self, dt: f32, cam_pos: Vec3, density_mult: f32) {
-        self.spawn_timer += dt;
-        let max_particles = (150.0 * density_mult.min(2.5)) as usize;
-        let spawn_interval = 0.05 / density_mult.max(0.5);
-        if self.spawn_timer > spawn_interval && self.particles.len() < max_particles {
-            self.spawn_timer = 0.0;
-            let px = cam_pos.x + (rand::random::<f32>() - 0.5) * 30.0;
-            let py = cam_pos.y + (rand::random::<f32>() - 0.3) * 10.0;
-            let pz = cam_pos.z + (rand::random::<f32>() - 0.5) * 30.0;
-            // 70% billboard, 30% sphere for variety
-            let shape = if rand::random::<f32>() < 0.7 {
-                DustShape::Billboard
-            } else {
-                DustShape::Sphere
-            };
-            let base_size = if shape == DustShape::Billboard {
-                0.03 + rand::random::<f32>() * 0.06 // billboard quads slightly larger
-            } else {
-                0.015 + rand::random::<f32>() * 0.03 // spheres smaller & denser
-            };
-            self.particles.push(DustParticle {
-                position: Vec3::new(px, py, pz),
-                velocity: Vec3::new(
-                    (rand::random::<f32>() - 0.5) * 0.5,
-                    (rand::random::<f32>() - 0.5) * 0.2,
-                    (rand::random::<f32>() - 0.5) * 0.5,
-                ),
-                life: 4.0 + rand::random::<f32>() * 4.0,
-                size: base_size,
-                shape,
-                spin: rand::random::<f32>() * std::f32::consts::TAU,
-            });
-        }
-        for p in &mut self.particles {
-            p.position += p.velocity * dt;
-            p.life -= dt;
-            p.spin += dt * 0.5; // gentle rotation
+    /// Emit `rate` particles per second of `kind` (0 stops emitting; live particles still finish).
+    /// `speed` is the fall speed for rain and snow. At most `max_alive` are emitted into.
+    pub fn set_emission(&mut self, kind: ParticleKind, rate: f32, speed: f32, max_alive: usize) {
+        let emitter = &mut self.emitters[kind.index()];
+        emitter.rate = rate.max(0.0);
+        emitter.speed = speed;
+        emitter.max_alive = max_alive.min(emitter.capacity);
+    }
+
+    /// Spawn around `center` (the camera), move, age and expire every particle.
+    pub fn update(&mut self, dt: f32, center: Vec3) {
+        for (kind, emitter) in ParticleKind::ALL.into_iter().zip(&mut self.emitters) {
+            emitter.carry += emitter.rate * dt;
+            while emitter.carry >= 1.0 {
+                emitter.carry -= 1.0;
+                if emitter.pool.len() >= emitter.max_alive && emitter.max_alive < emitter.capacity {
+                    continue;
+                }
+                if emitter.pool.len() == emitter.capacity {
+                    emitter.pool.pop_front();
+                }
+                emitter.pool.push_back(kind.spawn(center, emitter.speed));
+            }
+            if emitter.rate == 0.0 {
+                emitter.carry = 0.0;
+            }
+            for p in emitter.pool.iter_mut() {
+                p.position += p.velocity * dt;
+                p.life -= dt;
+            }
+            emitter.pool.retain(|p| p.life > 0.0);
         }
-        self.particles.retain(|p| p.life > 0.0);
     }
-}
 
-pub struct RainDrop {
-    pub position: Vec3,
-    pub velocity: Vec3,
-    pub life: f32,
-}
+    /// Drop every live particle (e.g. leaving a planet).
+    pub fn clear(&mut self) {
+        for emitter in &mut self.emitters {
+            emitter.pool.clear();
+            emitter.carry = 0.0;
+        }
+    }
 
-/// Snow particle: slower fall, slight drift, for Snow weather.
-pub struct SnowParticle {
-    pub position: Vec3,
-    pub velocity: Vec3,
-    pub life: f32,
-    pub size: f32,
+    /// Billboards for every visible particle of the kinds `include` accepts.
+    pub fn collect_instances(&self, include: impl Fn(ParticleKind) -> bool) -> Vec<ParticleInstance> {
+        ParticleKind::ALL
+            .into_iter()
+            .zip(&self.emitters)
+            .filter(|(kind, _)| include(*kind))
+            .flat_map(|(kind, emitter)| emitter.pool.iter().filter_map(move |p| kind.instance(p)))
+            .collect()
+    }
 }
 
 /// Visual-only bullet tracer for first-person feedback
@@ -92,3 +184,31 @@ pub struct TracerProjectile {
     pub velocity: Vec3,
     pub lifetime: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alive(system: &ParticleSystem, kind: ParticleKind) -> usize {
+        system.emitters[kind.index()].pool.len()
+    }
+
+    #[test]
+    fn pools_stay_bounded_and_respect_max_alive() {
+        let mut system = ParticleSystem::new();
+        // Far more rain than the pool holds: the oldest drops are recycled.
+        system.set_emission(ParticleKind::Rain, 10_000.0, 20.0, usize::MAX);
+        // Dust is density-limited below its pool size.
+        system.set_emission(ParticleKind::Dust, 10_000.0, 0.0, 50);
+        for _ in 0..10 {
+            system.update(0.1, Vec3::ZERO);
+        }
+        assert_eq!(alive(&system, ParticleKind::Rain), ParticleKind::Rain.capacity());
+        assert_eq!(alive(&system, ParticleKind::Dust), 50);
+        assert_eq!(alive(&system, ParticleKind::Snow), 0);
+        assert!(system.collect_instances(|k| k == ParticleKind::Dust).len() <= 50);
+
+        system.clear();
+        assert!(ParticleKind::ALL.iter().all(|&k| alive(&system, k) == 0));
+    }
+}
@@ -9,7 +9,7 @@ use winit::window::CursorGrabMode;
 
 use crate::bug::{Bug, BugType};
 use crate::bug_entity::PhysicsBug;
-use crate::state::{GamePhase, WarpSequence};
+use crate::state::GamePhase;
 
 impl crate::GameState {
     /// Handle a window event. Returns true if the app should exit.
@@ -29,10 +29,13 @@ impl crate::GameState {
                     self.input.process_keyboard(key, event.state);
 
                     if key == KeyCode::Escape && event.state.is_pressed() {
-                        if self.phase == GamePhase::Paused {
+                        if self.phase == GamePhase::Playing && self.galaxy_map_open {
+                            self.galaxy_map_open = false;
+                        } else if self.phase == GamePhase::Paused {
                             if self.pause_menu_selected == 0 {
                                 if let Some(prev) = self.previous_phase.take() {
                                     self.phase = prev;
+                                    self.time.resume();
                                     let _ = self.renderer.window.set_cursor_grab(CursorGrabMode::Locked)
                                         .or_else(|_| self.renderer.window.set_cursor_grab(CursorGrabMode::Confined));
                                     self.renderer.window.set_cursor_visible(false);
@@ -42,6 +45,7 @@ impl crate::GameState {
                         } else if self.phase == GamePhase::Playing || self.phase == GamePhase::InShip {
                             self.previous_phase = Some(self.phase);
                             self.phase = GamePhase::Paused;
+                            self.time.pause();
                             self.pause_menu_selected = 0;
                             let _ = self.renderer.window.set_cursor_grab(CursorGrabMode::None);
                             self.renderer.window.set_cursor_visible(true);
@@ -65,6 +69,7 @@ impl crate::GameState {
                                 if self.pause_menu_selected == 0 {
                                     if let Some(prev) = self.previous_phase.take() {
                                         self.phase = prev;
+                                        self.time.resume();
                                         let _ = self.renderer.window.set_cursor_grab(CursorGrabMode::Locked)
                                             .or_else(|_| self.renderer.window.set_cursor_grab(CursorGrabMode::Confined));
                                         self.renderer.window.set_cursor_visible(false);
@@ -88,12 +93,12 @@ impl crate::GameState {
                             let bug = Bug::new_with_variant(bug_type, variant);
                             let scale = bug_type.scale();
                             let body_handle = self.physics.add_kinematic_body(pos);
-                            let collider_handle = self.physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+                            let collider_handle = self.physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
 
-                            self.world.spawn((
+                            let entity = self.world.spawn((
                                 Transform { position: pos, rotation: Quat::IDENTITY, scale },
                                 Velocity::default(),
-                                Health::new(bug.effective_health()),
+                                Health::new(bug.effective_health() * self.difficulty.bug_health_mult()),
                                 bug,
                                 PhysicsBug {
                                     body_handle: Some(body_handle),
@@ -102,6 +107,7 @@ impl crate::GameState {
                                 },
                                 engine_core::AIComponent::new(85.0, 2.5, 1.0),
                             ));
+                            self.physics.set_collider_entity(collider_handle, entity);
                         }
                         #[cfg(debug_assertions)]
                         self.game_messages.info("Spawned 10 debug bugs!");
@@ -159,40 +165,12 @@ impl crate::GameState {
                         self.regenerate_planet();
                     }
 
-                    if key == KeyCode::KeyM && event.state.is_pressed()
-                        && self.phase != GamePhase::DropSequence && self.phase != GamePhase::InShip && self.phase != GamePhase::ApproachPlanet
-                    {
-                        self.galaxy_map_open = !self.galaxy_map_open;
+                    // Map navigation itself runs in `update_warp_map` (held keys, mouse, scroll)
+                    if key == KeyCode::KeyM && event.state.is_pressed() && self.phase == GamePhase::Playing {
                         if self.galaxy_map_open {
-                            self.galaxy_map_selected = self.current_system_idx;
-                        }
-                    }
-
-                    if self.galaxy_map_open && event.state.is_pressed() {
-                        let num_systems = self.universe.systems.len();
-                        match key {
-                            KeyCode::ArrowRight | KeyCode::ArrowDown => {
-                                self.galaxy_map_selected = (self.galaxy_map_selected + 1) % num_systems;
-                            }
-                            KeyCode::ArrowLeft | KeyCode::ArrowUp => {
-                                self.galaxy_map_selected = if self.galaxy_map_selected == 0 {
-                                    num_systems - 1
-                                } else {
-                                    self.galaxy_map_selected - 1
-                                };
-                            }
-                            KeyCode::Enter | KeyCode::NumpadEnter => {
-                                if self.current_planet_idx.is_some() {
-                                    self.game_messages.warning("Must be in orbit to initiate warp drive!".to_string());
-                                } else if self.galaxy_map_selected != self.current_system_idx {
-                                    let target = self.galaxy_map_selected;
-                                    let target_name = self.universe.systems[target].name.clone();
-                                    self.game_messages.warning(format!("Initiating warp to {}...", target_name));
-                                    self.warp_sequence = Some(WarpSequence::new(target));
-                                    self.galaxy_map_open = false;
-                                }
-                            }
-                            _ => {}
+                            self.galaxy_map_open = false;
+                        } else {
+                            self.open_galaxy_map();
                         }
                     }
                 }
@@ -4,11 +4,12 @@
 //! DR-8 Skyhook: a small Fleet shuttle/retrieval boat used to transport personnel
 //! and rescue Mobile Infantry. It descends from orbit, lands, opens the rear ramp,
 //! then climbs back to the Roger Young with the player aboard.
+//! The wait is a last stand: bug pressure surges while the boat is inbound and on the pad.
 //! Ref: <https://starshiptroopers.fandom.com/wiki/DR-8_Skyhook>
 
 use glam::Vec3;
 use rand::Rng;
-use rapier3d::prelude::RigidBodyHandle;
+use rapier3d::prelude::{ColliderHandle, RigidBodyHandle};
 
 // ── Extraction phases ───────────────────────────────────────────────────
 
@@ -55,6 +56,8 @@ pub struct ExtractionDropship {
     // ── Boarding ──
     pub player_aboard: bool,
     pub boarding_progress: f32,
+    /// The trooper died during the surge: no squad pickup on this extraction.
+    pub trooper_fell: bool,
 
     // ── Comms message triggers ──
     pub msg_15s_sent: bool,
@@ -78,6 +81,8 @@ pub struct ExtractionDropship {
 
     // ── Physics collision ──
     pub hull_body: Option<RigidBodyHandle>,
+    /// Trigger volume around the LZ; the player standing in it starts boarding.
+    pub lz_trigger: Option<ColliderHandle>,
 
     // ── Boarding camera ──
     pub boarding_start_pos: Option<Vec3>,
@@ -104,6 +109,9 @@ const ASCENT_DURATION: f32 = 28.0;
 
 pub const BOARDING_RADIUS: f32 = 8.0;
 
+/// Spawn pressure multiplier at touchdown; ramps up from 1x while the boat is inbound.
+const SURGE_PEAK: f32 = 3.0;
+
 const APPROACH_ALTITUDE: f32 = 250.0;
 const HOVER_ALTITUDE: f32 = 4.0;
 const LZ_FORWARD_OFFSET: f32 = 30.0;
@@ -155,6 +163,7 @@ impl ExtractionDropship {
             lz_ground_y: ground_y_at_lz,
             player_aboard: false,
             boarding_progress: 0.0,
+            trooper_fell: false,
             msg_15s_sent: false,
             msg_10s_sent: false,
             msg_touchdown_sent: false,
@@ -170,6 +179,7 @@ impl ExtractionDropship {
             gunner_left_target: None,
             gunner_right_target: None,
             hull_body: None,
+            lz_trigger: None,
             boarding_start_pos: None,
             ascent_start_pos: None,
             roger_young_pos: ry_pos,
@@ -178,7 +188,8 @@ impl ExtractionDropship {
     }
 
     /// Tick the extraction sequence.  Returns comms messages.
-    pub fn update(&mut self, dt: f32, player_pos: Vec3) -> Vec<ExtractionMessage> {
+    /// `board` is whether the player, inside the `lz_trigger` volume, asked to board this frame.
+    pub fn update(&mut self, dt: f32, board: bool) -> Vec<ExtractionMessage> {
         self.total_timer += dt;
         self.phase_timer += dt;
         let mut messages: Vec<ExtractionMessage> = Vec::new();
@@ -261,12 +272,7 @@ impl ExtractionDropship {
                 self.engine_intensity = 0.6 + (self.total_timer * 3.0).sin() * 0.15;
                 self.ramp_open = 1.0;
 
-                let dist_to_player = Vec3::new(
-                    player_pos.x - self.lz_position.x, 0.0,
-                    player_pos.z - self.lz_position.z,
-                ).length();
-
-                if dist_to_player <= BOARDING_RADIUS {
+                if board {
                     self.phase = ExtractionPhase::Boarding;
                     self.phase_timer = 0.0;
                     messages.push(ExtractionMessage::Success(
@@ -475,6 +481,20 @@ impl ExtractionDropship {
         }
     }
 
+    /// Bug spawn pressure multiplier for the last-stand surge: climbs from 1x at the call to
+    /// `SURGE_PEAK` at touchdown, holds while the boat waits, and drops once the ramp closes.
+    pub fn surge_multiplier(&self) -> f32 {
+        let countdown = CALLED_DURATION + INBOUND_DURATION + LANDING_DURATION;
+        match self.phase {
+            ExtractionPhase::Called | ExtractionPhase::Inbound | ExtractionPhase::Landing => {
+                let t = (1.0 - self.eta_to_touchdown() / countdown).clamp(0.0, 1.0);
+                1.0 + (SURGE_PEAK - 1.0) * t
+            }
+            ExtractionPhase::Waiting | ExtractionPhase::Boarding => SURGE_PEAK,
+            ExtractionPhase::Departing | ExtractionPhase::Ascent => 1.0,
+        }
+    }
+
     pub fn time_until_dustoff(&self) -> f32 {
         if self.phase == ExtractionPhase::Waiting {
             (WAITING_DURATION - self.phase_timer).max(0.0)
@@ -564,6 +584,11 @@ impl ExtractionDropship {
         )
     }
 
+    /// The LZ trigger exists from touchdown until boarding starts.
+    pub fn needs_lz_trigger(&self) -> bool {
+        matches!(self.phase, ExtractionPhase::Landing | ExtractionPhase::Waiting)
+    }
+
     // ── Boarding camera interpolation ────────────────────────────────────
 
     pub fn boarding_interior_pos(&self) -> Vec3 {
@@ -691,3 +716,37 @@ pub enum ExtractionMessage {
     Warning(String),
     Success(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surge_builds_to_touchdown_and_boarding_needs_a_request() {
+        let mut boat = ExtractionDropship::new(Vec3::ZERO, Vec3::NEG_Z, 0.0, Vec3::new(0.0, 300.0, 200.0));
+        assert!((boat.surge_multiplier() - 1.0).abs() < 1e-5);
+
+        let dt = 0.5;
+        let mut last = boat.surge_multiplier();
+        while boat.phase != ExtractionPhase::Waiting {
+            boat.update(dt, false);
+            let surge = boat.surge_multiplier();
+            assert!(surge >= last);
+            last = surge;
+        }
+        assert_eq!(boat.surge_multiplier(), SURGE_PEAK);
+
+        // Standing around does not board; asking to does
+        boat.update(dt, false);
+        assert_eq!(boat.phase, ExtractionPhase::Waiting);
+        boat.update(dt, true);
+        assert_eq!(boat.phase, ExtractionPhase::Boarding);
+        assert_eq!(boat.surge_multiplier(), SURGE_PEAK);
+
+        while boat.phase == ExtractionPhase::Boarding {
+            boat.update(dt, false);
+        }
+        assert!(boat.player_aboard);
+        assert_eq!(boat.surge_multiplier(), 1.0);
+    }
+}
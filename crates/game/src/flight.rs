@@ -0,0 +1,202 @@
+//! Shared flight model for player-flown craft (approach craft, piloted Tac Fighter).
+//!
+//! One controller integrates thrust, drag and rotation; a `FlightTuning` preset gives each craft
+//! its feel. Arcade mode steers like an FPS camera (world-up yaw, clamped pitch, no roll);
+//! 6DOF mode rotates freely about the craft's own axes.
+
+use glam::{Quat, Vec2, Vec3};
+use renderer::Camera;
+
+/// Pitch limit in arcade mode (matches the FPS camera clamp).
+const ARCADE_MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// How rotation input is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlightMode {
+    /// Yaw about world up, clamped pitch, no roll. Velocity can be pulled toward the nose (`grip`).
+    Arcade,
+    /// Free rotation about the craft's own pitch/yaw/roll axes; velocity is purely Newtonian.
+    SixDof,
+}
+
+/// Per-craft flight feel.
+#[derive(Debug, Clone, Copy)]
+pub struct FlightTuning {
+    pub mode: FlightMode,
+    /// Acceleration at full throttle (m/s²).
+    pub thrust: f32,
+    /// Fraction of velocity lost per second (0 = none).
+    pub drag: f32,
+    /// Turn rates at full stick (rad/s). Mouse look bypasses these.
+    pub pitch_rate: f32,
+    pub yaw_rate: f32,
+    pub roll_rate: f32,
+    /// Arcade only: how quickly velocity swings toward the nose (per second, 0 = pure inertia).
+    pub grip: f32,
+    /// Speed cap (m/s).
+    pub max_speed: f32,
+}
+
+impl FlightTuning {
+    /// Approach craft: gentle thrust along the view, 2%/s drag, no grip — drifts like the original approach flight.
+    pub const APPROACH_CRAFT: FlightTuning = FlightTuning {
+        mode: FlightMode::Arcade,
+        thrust: 35.0,
+        drag: 0.02,
+        pitch_rate: 1.2,
+        yaw_rate: 1.2,
+        roll_rate: 1.5,
+        grip: 0.0,
+        max_speed: f32::INFINITY,
+    };
+
+    /// Tac Fighter: fast, banks hard, and holds its line through turns for strafing runs.
+    pub const TAC_FIGHTER: FlightTuning = FlightTuning {
+        mode: FlightMode::Arcade,
+        thrust: 60.0,
+        drag: 0.12,
+        pitch_rate: 1.4,
+        yaw_rate: 1.0,
+        roll_rate: 2.5,
+        grip: 2.5,
+        max_speed: 160.0,
+    };
+
+    /// Same preset flown in the given mode.
+    pub fn with_mode(self, mode: FlightMode) -> Self {
+        Self { mode, ..self }
+    }
+}
+
+/// Pilot input for one frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlightControls {
+    /// -1 (reverse) .. 1 (full ahead).
+    pub throttle: f32,
+    /// Stick axes, -1 .. 1: pitch up, yaw right, roll right.
+    pub pitch: f32,
+    pub yaw: f32,
+    pub roll: f32,
+    /// Mouse look this frame in radians (x = right, y = down), applied directly.
+    pub look: Vec2,
+}
+
+/// Position, velocity and orientation of a player-flown craft.
+#[derive(Debug, Clone)]
+pub struct FlyingVehicleController {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub tuning: FlightTuning,
+    orientation: Quat,
+    /// Arcade-mode yaw/pitch (kept separately so pitch can be clamped).
+    yaw: f32,
+    pitch: f32,
+}
+
+impl FlyingVehicleController {
+    pub fn new(position: Vec3, velocity: Vec3, forward: Vec3, tuning: FlightTuning) -> Self {
+        let f = forward.try_normalize().unwrap_or(Vec3::NEG_Z);
+        let yaw = (-f.x).atan2(-f.z);
+        let pitch = f.y.clamp(-1.0, 1.0).asin().clamp(-ARCADE_MAX_PITCH, ARCADE_MAX_PITCH);
+        Self {
+            position,
+            velocity,
+            tuning,
+            orientation: Quat::from_rotation_y(yaw) * Quat::from_rotation_x(pitch),
+            yaw,
+            pitch,
+        }
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        self.orientation * Vec3::NEG_Z
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.velocity.length()
+    }
+
+    /// Apply one frame of controls: rotate, thrust, drag, grip, then integrate position.
+    pub fn update(&mut self, controls: &FlightControls, dt: f32) {
+        let t = self.tuning;
+        let pitch = controls.pitch * t.pitch_rate * dt - controls.look.y;
+        let yaw = controls.yaw * t.yaw_rate * dt + controls.look.x;
+        match t.mode {
+            FlightMode::Arcade => {
+                self.yaw -= yaw;
+                self.pitch = (self.pitch + pitch).clamp(-ARCADE_MAX_PITCH, ARCADE_MAX_PITCH);
+                self.orientation = Quat::from_rotation_y(self.yaw) * Quat::from_rotation_x(self.pitch);
+            }
+            FlightMode::SixDof => {
+                let roll = controls.roll * t.roll_rate * dt;
+                self.orientation = (self.orientation
+                    * Quat::from_rotation_y(-yaw)
+                    * Quat::from_rotation_x(pitch)
+                    * Quat::from_rotation_z(-roll))
+                .normalize();
+            }
+        }
+
+        let forward = self.forward();
+        self.velocity += forward * t.thrust * controls.throttle.clamp(-1.0, 1.0) * dt;
+        if t.mode == FlightMode::Arcade && t.grip > 0.0 {
+            let along = forward * self.velocity.length();
+            self.velocity = self.velocity.lerp(along, 1.0 - (-t.grip * dt).exp());
+        }
+        self.velocity *= (1.0 - t.drag).max(0.0).powf(dt);
+        if self.velocity.length() > t.max_speed {
+            self.velocity = self.velocity.normalize() * t.max_speed;
+        }
+        self.position += self.velocity * dt;
+    }
+
+    /// Put the camera at `offset` (craft-local: x right, y up, z back) looking along the nose.
+    pub fn sync_camera(&self, camera: &mut Camera, offset: Vec3) {
+        camera.transform.position = self.position + self.orientation * offset;
+        match self.tuning.mode {
+            FlightMode::Arcade => camera.set_yaw_pitch(self.yaw, self.pitch),
+            FlightMode::SixDof => camera.transform.rotation = self.orientation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approach_preset_keeps_the_original_drift_and_drag() {
+        let mut craft = FlyingVehicleController::new(Vec3::ZERO, Vec3::ZERO, Vec3::NEG_Z, FlightTuning::APPROACH_CRAFT);
+        let ahead = FlightControls { throttle: 1.0, ..Default::default() };
+        craft.update(&ahead, 0.5);
+        assert!((craft.velocity - Vec3::NEG_Z * 35.0 * 0.5 * 0.98f32.powf(0.5)).length() < 1e-3);
+
+        // Turning without grip leaves the velocity where it was.
+        let before = craft.velocity;
+        craft.update(&FlightControls { look: Vec2::new(1.0, 0.0), ..Default::default() }, 1.0);
+        assert!(craft.forward().dot(Vec3::NEG_Z) < 0.6);
+        assert!((craft.velocity - before * 0.98).length() < 1e-3);
+    }
+
+    #[test]
+    fn arcade_grip_swings_velocity_to_the_nose_and_six_dof_does_not() {
+        let turn = FlightControls { yaw: 1.0, ..Default::default() };
+        let mut arcade = FlyingVehicleController::new(Vec3::ZERO, Vec3::NEG_Z * 100.0, Vec3::NEG_Z, FlightTuning::TAC_FIGHTER);
+        let mut six_dof = FlyingVehicleController::new(
+            Vec3::ZERO,
+            Vec3::NEG_Z * 100.0,
+            Vec3::NEG_Z,
+            FlightTuning::TAC_FIGHTER.with_mode(FlightMode::SixDof),
+        );
+        for _ in 0..60 {
+            arcade.update(&turn, 1.0 / 60.0);
+            six_dof.update(&turn, 1.0 / 60.0);
+        }
+        assert!(arcade.forward().x > 0.5, "yaw right turns toward +X");
+        for _ in 0..60 {
+            arcade.update(&FlightControls::default(), 1.0 / 60.0);
+        }
+        assert!(arcade.velocity.normalize().dot(arcade.forward()) > 0.99);
+        assert!(six_dof.velocity.normalize().dot(Vec3::NEG_Z) > 0.99);
+    }
+}
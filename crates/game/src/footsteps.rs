@@ -0,0 +1,224 @@
+//! Footstep sounds keyed to the ground underfoot and the player's gait.
+//!
+//! A foot lands twice per head-bob cycle (each dip of the bob), so steps stay in time with the
+//! camera. Samples are optional: `Footsteps::load` registers whichever
+//! `assets/sounds/footsteps/<surface>_<n>.ogg` files exist; surfaces without samples are silent.
+
+use audio::{AudioBus, AudioSystem};
+use procgen::BlockId;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// Where footstep samples are looked up.
+const SAMPLE_DIR: &str = "assets/sounds/footsteps";
+/// Variants tried per surface (`<surface>_0.ogg` .. `<surface>_3.ogg`).
+const MAX_VARIANTS: usize = 4;
+/// Snow deeper than this (m) covers whatever block is under it.
+const SNOW_STEP_DEPTH: f32 = 0.05;
+
+/// What the player is walking on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FootSurface {
+    Dirt,
+    Grass,
+    Sand,
+    Rock,
+    Snow,
+    Water,
+    /// Ship decks and hull plating.
+    Metal,
+}
+
+impl FootSurface {
+    pub const ALL: [FootSurface; 7] = [
+        FootSurface::Dirt,
+        FootSurface::Grass,
+        FootSurface::Sand,
+        FootSurface::Rock,
+        FootSurface::Snow,
+        FootSurface::Water,
+        FootSurface::Metal,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            FootSurface::Dirt => "dirt",
+            FootSurface::Grass => "grass",
+            FootSurface::Sand => "sand",
+            FootSurface::Rock => "rock",
+            FootSurface::Snow => "snow",
+            FootSurface::Water => "water",
+            FootSurface::Metal => "metal",
+        }
+    }
+
+    /// Surface underfoot on a planet: standing water and settled snow win over the block below.
+    pub fn classify(block: Option<BlockId>, snow_depth: f32, in_water: bool) -> Self {
+        if in_water {
+            return FootSurface::Water;
+        }
+        if snow_depth > SNOW_STEP_DEPTH {
+            return FootSurface::Snow;
+        }
+        match block {
+            Some(BlockId::Grass) => FootSurface::Grass,
+            Some(BlockId::Sand) => FootSurface::Sand,
+            Some(BlockId::Stone | BlockId::Bedrock) => FootSurface::Rock,
+            Some(BlockId::Snow) => FootSurface::Snow,
+            Some(BlockId::Water) => FootSurface::Water,
+            Some(BlockId::Dirt | BlockId::Air) | None => FootSurface::Dirt,
+        }
+    }
+
+    fn sound_name(self, variant: usize) -> String {
+        format!("footstep_{}_{}", self.name(), variant)
+    }
+}
+
+/// How the player is moving; sets step cadence and loudness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gait {
+    Walk,
+    Sprint,
+    /// Crouched or prone: same cadence as walking, much quieter.
+    Crouch,
+}
+
+impl Gait {
+    /// Head-bob frequency (rad/s) for this gait; the step cycle runs at the same rate.
+    fn bob_freq(self) -> f32 {
+        match self {
+            Gait::Sprint => 12.0,
+            Gait::Walk | Gait::Crouch => 8.0,
+        }
+    }
+
+    /// Kept below the Sfx ducking threshold so the player's own steps never duck the music.
+    fn volume(self) -> RangeInclusive<f64> {
+        match self {
+            Gait::Walk => 0.32..=0.42,
+            Gait::Sprint => 0.45..=0.58,
+            Gait::Crouch => 0.15..=0.22,
+        }
+    }
+
+    fn pitch(self) -> RangeInclusive<f64> {
+        match self {
+            Gait::Walk => 0.92..=1.08,
+            Gait::Sprint => 0.98..=1.15,
+            Gait::Crouch => 0.88..=1.0,
+        }
+    }
+}
+
+/// Step cycle plus the samples available for each surface.
+#[derive(Debug, Default)]
+pub struct Footsteps {
+    /// Head-bob phase (rad); a foot lands each time it crosses a multiple of PI.
+    phase: f32,
+    /// Loaded variants per surface, indexed like `FootSurface::ALL`.
+    variants: [usize; FootSurface::ALL.len()],
+    next_variant: usize,
+}
+
+impl Footsteps {
+    /// Load every footstep sample found under `assets/sounds/footsteps`.
+    pub fn load(audio: Option<&mut AudioSystem>) -> Self {
+        let mut footsteps = Self { phase: FRAC_PI_2, ..Default::default() };
+        let Some(audio) = audio else {
+            return footsteps;
+        };
+        for (i, surface) in FootSurface::ALL.into_iter().enumerate() {
+            for variant in 0..MAX_VARIANTS {
+                let path = Path::new(SAMPLE_DIR).join(format!("{}_{}.ogg", surface.name(), variant));
+                if !path.exists() {
+                    break;
+                }
+                match audio.load_sound(&surface.sound_name(variant), &path) {
+                    Ok(()) => footsteps.variants[i] += 1,
+                    Err(e) => {
+                        log::warn!("Footstep sample {} failed to load: {}", path.display(), e);
+                        break;
+                    }
+                }
+            }
+        }
+        footsteps
+    }
+
+    /// Advance the step cycle. Returns true on the frame a foot lands. Standing still or being
+    /// airborne parks the cycle half a step in, so the first step after starting comes quickly.
+    pub fn update(&mut self, dt: f32, gait: Gait, moving: bool) -> bool {
+        if !moving {
+            self.phase = FRAC_PI_2;
+            return false;
+        }
+        let before = (self.phase / PI).floor();
+        self.phase = (self.phase + gait.bob_freq() * dt) % (2.0 * PI);
+        let after = (self.phase / PI).floor();
+        before != after
+    }
+
+    /// Head-bob offset in [-1, 1] for the current step phase: lowest as each foot lands, highest
+    /// mid-stride, so the camera and the footstep sounds share one cycle.
+    pub fn bob(&self) -> f32 {
+        2.0 * self.phase.sin().abs() - 1.0
+    }
+
+    /// Play one step on `surface` with per-step pitch and volume variation.
+    pub fn play(&mut self, audio: &mut AudioSystem, surface: FootSurface, gait: Gait) {
+        let index = FootSurface::ALL.iter().position(|s| *s == surface).unwrap_or(0);
+        let count = self.variants[index];
+        if count == 0 {
+            return;
+        }
+        self.next_variant = (self.next_variant + 1) % count;
+        let name = surface.sound_name(self.next_variant);
+        if let Err(e) = audio.play_with_variation(&name, gait.pitch(), gait.volume(), AudioBus::Sfx) {
+            log::warn!("Footstep playback failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_land_twice_per_bob_cycle_and_faster_when_sprinting() {
+        let count_steps = |gait: Gait| {
+            let mut footsteps = Footsteps::default();
+            (0..600).filter(|_| footsteps.update(1.0 / 60.0, gait, true)).count()
+        };
+        // 10 s at 8 rad/s = 80 rad = ~25 half-cycles
+        assert_eq!(count_steps(Gait::Walk), (80.0 / PI) as usize);
+        assert!(count_steps(Gait::Sprint) > count_steps(Gait::Walk));
+
+        let mut footsteps = Footsteps::default();
+        assert!((0..60).all(|_| !footsteps.update(1.0 / 60.0, Gait::Walk, false)));
+    }
+
+    #[test]
+    fn feet_land_at_the_bottom_of_the_bob() {
+        let mut footsteps = Footsteps::default();
+        footsteps.update(1.0 / 60.0, Gait::Walk, false);
+        assert_eq!(footsteps.bob(), 1.0, "parked mid-stride");
+        let mut steps = 0;
+        for _ in 0..600 {
+            if footsteps.update(1.0 / 60.0, Gait::Walk, true) {
+                steps += 1;
+                assert!(footsteps.bob() < -0.7, "step landed at bob {}", footsteps.bob());
+            }
+        }
+        assert!(steps > 0);
+    }
+
+    #[test]
+    fn water_and_snow_cover_the_block_underfoot() {
+        assert_eq!(FootSurface::classify(Some(BlockId::Grass), 0.0, false), FootSurface::Grass);
+        assert_eq!(FootSurface::classify(Some(BlockId::Grass), 0.2, false), FootSurface::Snow);
+        assert_eq!(FootSurface::classify(Some(BlockId::Stone), 0.2, true), FootSurface::Water);
+        assert_eq!(FootSurface::classify(None, 0.0, false), FootSurface::Dirt);
+    }
+}
@@ -4,16 +4,23 @@
 use engine_core::{Health, Transform, Vec3};
 use glam::Quat;
 use hecs::{Entity, World};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
 
+use crate::ability::{AbilityState, ClassAbility};
 use crate::bug::{Bug, BugType};
+use crate::bug_entity::PhysicsBug;
+use crate::difficulty::Difficulty;
+use crate::grenade::MAX_FRAG_GRENADES;
+use crate::objective::{Objective, ObjectiveContext};
 use crate::skinny::Skinny;
-use crate::weapons::{Weapon, WeaponType};
+use crate::weapons::{Weapon, WeaponPickup, WeaponType};
 
 /// Player class types (similar to STE)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlayerClass {
-    /// Hunter - Assault class with jetpack burst
+    /// Hunter - Assault class with a dash and extra stamina
     Hunter,
     /// Bastion - Heavy weapons and fortification
     Bastion,
@@ -26,8 +33,66 @@ pub enum PlayerClass {
 }
 
 impl PlayerClass {
+    pub const ALL: [PlayerClass; 5] = [
+        PlayerClass::Hunter,
+        PlayerClass::Bastion,
+        PlayerClass::Operator,
+        PlayerClass::Ranger,
+        PlayerClass::Guardian,
+    ];
+
+    /// Loadout for this class, from `classes.ron` if it was loaded.
     pub fn loadout(&self) -> ClassLoadout {
+        class_loadout(*self).clone()
+    }
+
+    pub fn name(&self) -> &'static str {
         match self {
+            PlayerClass::Hunter => "Hunter",
+            PlayerClass::Bastion => "Bastion",
+            PlayerClass::Operator => "Operator",
+            PlayerClass::Ranger => "Ranger",
+            PlayerClass::Guardian => "Guardian",
+        }
+    }
+
+    /// Next class, wrapping back to Hunter (war table cycles through them).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// What a class starts with and how it plays, as stored in `classes.ron`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassLoadout {
+    pub primary: WeaponType,
+    pub secondary: WeaponType,
+    pub tertiary: WeaponType,  // Slot 3: MachineGun for all classes
+    pub max_health: f32,
+    pub move_speed: f32,
+    pub sprint_multiplier: f32,
+    /// Stamina tuning; fields left out keep the defaults.
+    #[serde(default)]
+    pub stamina: Stamina,
+    /// Passive: reload speed multiplier (1.3 = reloads 30% faster).
+    #[serde(default = "default_reload_speed")]
+    pub reload_speed: f32,
+    /// Passive: armor at spawn and respawn.
+    #[serde(default)]
+    pub starting_armor: f32,
+    pub ability: ClassAbility,
+    pub ability_cooldown: f32,
+}
+
+fn default_reload_speed() -> f32 {
+    1.0
+}
+
+impl ClassLoadout {
+    /// Compiled-in loadout, used when `classes.ron` doesn't cover a class.
+    pub fn builtin(class: PlayerClass) -> Self {
+        match class {
             PlayerClass::Hunter => ClassLoadout {
                 primary: WeaponType::Rifle,
                 secondary: WeaponType::Shotgun,
@@ -35,7 +100,10 @@ impl PlayerClass {
                 max_health: 100.0,
                 move_speed: 8.0,
                 sprint_multiplier: 1.6,
-                ability: ClassAbility::JetpackBurst,
+                stamina: Stamina { max: 120.0, ..Stamina::default() },
+                reload_speed: 1.0,
+                starting_armor: 0.0,
+                ability: ClassAbility::Dash,
                 ability_cooldown: 15.0,
             },
             PlayerClass::Bastion => ClassLoadout {
@@ -45,6 +113,9 @@ impl PlayerClass {
                 max_health: 150.0,
                 move_speed: 6.0,
                 sprint_multiplier: 1.3,
+                stamina: Stamina { drain_rate: 24.0, recovery_rate: 12.0, ..Stamina::default() },
+                reload_speed: 1.0,
+                starting_armor: 50.0,
                 ability: ClassAbility::DeployBarricade,
                 ability_cooldown: 30.0,
             },
@@ -55,6 +126,9 @@ impl PlayerClass {
                 max_health: 100.0,
                 move_speed: 7.0,
                 sprint_multiplier: 1.5,
+                stamina: Stamina::default(),
+                reload_speed: 1.3,
+                starting_armor: 0.0,
                 ability: ClassAbility::AmmoStation,
                 ability_cooldown: 45.0,
             },
@@ -65,6 +139,9 @@ impl PlayerClass {
                 max_health: 80.0,
                 move_speed: 7.5,
                 sprint_multiplier: 1.5,
+                stamina: Stamina { recovery_rate: 18.0, ..Stamina::default() },
+                reload_speed: 1.0,
+                starting_armor: 0.0,
                 ability: ClassAbility::ScanPulse,
                 ability_cooldown: 20.0,
             },
@@ -75,47 +152,140 @@ impl PlayerClass {
                 max_health: 125.0,
                 move_speed: 6.5,
                 sprint_multiplier: 1.4,
+                stamina: Stamina::default(),
+                reload_speed: 1.0,
+                starting_armor: 25.0,
                 ability: ClassAbility::ShieldDome,
                 ability_cooldown: 40.0,
             },
         }
     }
+}
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            PlayerClass::Hunter => "Hunter",
-            PlayerClass::Bastion => "Bastion",
-            PlayerClass::Operator => "Operator",
-            PlayerClass::Ranger => "Ranger",
-            PlayerClass::Guardian => "Guardian",
-        }
+static CLASS_LOADOUTS: OnceLock<HashMap<PlayerClass, ClassLoadout>> = OnceLock::new();
+
+fn builtin_loadouts() -> HashMap<PlayerClass, ClassLoadout> {
+    PlayerClass::ALL.iter().map(|&c| (c, ClassLoadout::builtin(c))).collect()
+}
+
+/// Parse `classes.ron` text; classes the file leaves out keep their compiled defaults.
+fn parse_class_loadouts(s: &str) -> Result<HashMap<PlayerClass, ClassLoadout>, ron::error::SpannedError> {
+    let overrides: HashMap<PlayerClass, ClassLoadout> = ron::from_str(s)?;
+    let mut loadouts = builtin_loadouts();
+    loadouts.extend(overrides);
+    Ok(loadouts)
+}
+
+/// Load class loadouts from `classes.ron` in the current directory. Call once at startup, before
+/// the player is created; a missing or invalid file leaves the compiled defaults in place.
+pub fn load_class_loadouts() {
+    let path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("classes.ron");
+    let loadouts = match std::fs::read_to_string(&path) {
+        Ok(data) => parse_class_loadouts(&data).unwrap_or_else(|e| {
+            log::warn!("Invalid class data at {:?}: {}, using defaults", path, e);
+            builtin_loadouts()
+        }),
+        Err(_) => builtin_loadouts(),
+    };
+    if CLASS_LOADOUTS.set(loadouts).is_err() {
+        log::warn!("Class data was already loaded; ignoring {:?}", path);
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct ClassLoadout {
-    pub primary: WeaponType,
-    pub secondary: WeaponType,
-    pub tertiary: WeaponType,  // Slot 3: MachineGun for all classes
-    pub max_health: f32,
-    pub move_speed: f32,
-    pub sprint_multiplier: f32,
-    pub ability: ClassAbility,
-    pub ability_cooldown: f32,
+/// Loadout for `class`, from `classes.ron` if it was loaded.
+pub fn class_loadout(class: PlayerClass) -> &'static ClassLoadout {
+    &CLASS_LOADOUTS.get_or_init(builtin_loadouts)[&class]
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ClassAbility {
-    /// Jump boost in any direction
-    JetpackBurst,
-    /// Deploy a defensive wall
-    DeployBarricade,
-    /// Deploy ammo resupply
-    AmmoStation,
-    /// Reveal nearby bugs
-    ScanPulse,
-    /// Protective dome shield
-    ShieldDome,
+/// Sprint stamina. Sprinting drains it; it recovers after a short pause. Running it down to the
+/// exhaustion threshold leaves the player winded: sprint stays locked until stamina has recovered
+/// past `recover_threshold`, so it can't flicker on and off at empty.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stamina {
+    #[serde(skip)]
+    pub current: f32,
+    pub max: f32,
+    /// Drain per second while sprinting.
+    pub drain_rate: f32,
+    /// Recovery per second once `recovery_delay` has passed.
+    pub recovery_rate: f32,
+    /// Seconds after the last drain before recovery starts.
+    pub recovery_delay: f32,
+    /// Fraction of `max` at or below which the player becomes winded.
+    pub exhaustion_threshold: f32,
+    /// Fraction of `max` a winded player must recover to before sprinting again.
+    pub recover_threshold: f32,
+    #[serde(skip)]
+    pub winded: bool,
+    /// Seconds since stamina was last drained or spent.
+    #[serde(skip)]
+    since_drain: f32,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Self {
+            current: 100.0,
+            max: 100.0,
+            drain_rate: 20.0,
+            recovery_rate: 15.0,
+            recovery_delay: 0.6,
+            exhaustion_threshold: 0.02,
+            recover_threshold: 0.35,
+            winded: false,
+            since_drain: 0.0,
+        }
+    }
+}
+
+impl Stamina {
+    /// Full stamina for this tuning (`current` follows `max`).
+    pub fn full(self) -> Self {
+        Self { current: self.max, winded: false, since_drain: self.recovery_delay, ..self }
+    }
+
+    pub fn can_sprint(&self) -> bool {
+        !self.winded && self.current > 0.0
+    }
+
+    /// Spend a lump of stamina (combat roll). Returns false and spends nothing if there isn't enough.
+    pub fn spend(&mut self, amount: f32) -> bool {
+        if self.current < amount {
+            return false;
+        }
+        self.current -= amount;
+        self.since_drain = 0.0;
+        self.check_exhausted();
+        true
+    }
+
+    /// Drain while `sprinting`, otherwise recover after the delay.
+    pub fn update(&mut self, dt: f32, sprinting: bool) {
+        if sprinting {
+            self.current = (self.current - self.drain_rate * dt).max(0.0);
+            self.since_drain = 0.0;
+            self.check_exhausted();
+        } else {
+            self.since_drain += dt;
+            if self.since_drain >= self.recovery_delay {
+                self.current = (self.current + self.recovery_rate * dt).min(self.max);
+            }
+        }
+        if self.winded && self.current >= self.max * self.recover_threshold {
+            self.winded = false;
+        }
+    }
+
+    pub fn percent(&self) -> f32 {
+        self.current / self.max
+    }
+
+    fn check_exhausted(&mut self) {
+        if self.current <= self.max * self.exhaustion_threshold {
+            self.winded = true;
+        }
+    }
 }
 
 /// FPS Player state
@@ -144,9 +314,10 @@ pub struct FPSPlayer {
     pub is_sprinting: bool,
     pub is_crouching: bool,
     pub is_prone: bool,
+    /// Fully submerged (eye below the water surface): swims instead of walking.
+    pub is_swimming: bool,
     pub is_grounded: bool,
-    pub stamina: f32,
-    pub max_stamina: f32,
+    pub stamina: Stamina,
 
     // Combat (slot 0=primary, 1=secondary, 2=machine gun, 3=entrenching shovel)
     pub weapons: [Weapon; 3],
@@ -155,6 +326,10 @@ pub struct FPSPlayer {
     pub aim_progress: f32, // 0 = hip, 1 = ADS
     pub last_damage_time: f32,
     pub damage_direction: Option<Vec3>,
+    /// Frag grenades left (see `grenade::MAX_FRAG_GRENADES`).
+    pub grenades: u32,
+    /// Difficulty multiplier on all incoming damage.
+    pub damage_taken_mult: f32,
 
     // Ability
     pub ability: ClassAbility,
@@ -175,10 +350,87 @@ pub struct FPSPlayer {
 
 #[derive(Debug, Clone)]
 pub enum InteractionTarget {
-    AmmoCrate(Vec3),
+    /// Index into the game's supply crates.
+    SupplyCrate(usize),
+    /// Entity with a `WeaponPickup` lying on the ground.
+    WeaponPickup(Entity),
     HealthStation(Vec3),
     Objective(String, Vec3),
     Revivable(Entity),
+    /// NPC the player is looking at; `entity` is None for ship crew (not ECS entities).
+    Speaker {
+        name: String,
+        dialogue_id: usize,
+        entity: Option<Entity>,
+        position: Vec3,
+    },
+}
+
+/// Cosine of the half-angle of the look cone used to pick an interaction target (~25°).
+pub const LOOK_CONE_COS: f32 = 0.9;
+
+/// Pick the candidate the camera is looking at: within `max_range` and inside the look cone,
+/// preferring the one closest to the crosshair. Candidates are given by their floor position;
+/// the aim point is the nearest point on their body (0.3–1.8 m above the floor) to the view ray,
+/// so looking at a face or a chest both count.
+pub fn pick_look_target<T>(
+    cam_pos: Vec3,
+    cam_forward: Vec3,
+    max_range: f32,
+    candidates: impl IntoIterator<Item = (T, Vec3)>,
+) -> Option<T> {
+    let forward = cam_forward.normalize_or_zero();
+    let mut best: Option<(T, f32)> = None;
+    for (candidate, base) in candidates {
+        let flat = Vec3::new(base.x - cam_pos.x, 0.0, base.z - cam_pos.z);
+        let flat_fwd = Vec3::new(forward.x, 0.0, forward.z).length().max(1e-3);
+        let ray_y = cam_pos.y + forward.y * flat.length() / flat_fwd;
+        let aim = Vec3::new(base.x, ray_y.clamp(base.y + 0.3, base.y + 1.8), base.z);
+        let to_aim = aim - cam_pos;
+        let dist = to_aim.length();
+        if dist > max_range || dist < 1e-3 {
+            continue;
+        }
+        let dot = to_aim.dot(forward) / dist;
+        if dot >= LOOK_CONE_COS && best.as_ref().is_none_or(|(_, d)| dot > *d) {
+            best = Some((candidate, dot));
+        }
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
+/// Melee strike (V): reach from the eye, cone half-angle cosine (~60°), damage per bug, stagger
+/// knockback, seconds between strikes and how many bugs one swing can catch.
+pub const MELEE_RANGE: f32 = 2.4;
+pub const MELEE_CONE_COS: f32 = 0.5;
+pub const MELEE_DAMAGE: f32 = 60.0;
+pub const MELEE_KNOCKBACK: f32 = 5.0;
+pub const MELEE_COOLDOWN: f32 = 0.8;
+pub const MELEE_MAX_TARGETS: usize = 3;
+
+/// Bugs caught by a melee swing: within `MELEE_RANGE` of the eye (measured to the near side of
+/// each bug, given as center and radius) and inside the strike cone, closest first, at most
+/// `MELEE_MAX_TARGETS`.
+pub fn melee_targets<T>(
+    eye: Vec3,
+    forward: Vec3,
+    candidates: impl IntoIterator<Item = (T, Vec3, f32)>,
+) -> Vec<(T, Vec3)> {
+    let forward = forward.normalize_or_zero();
+    let mut hits: Vec<(T, Vec3, f32)> = candidates
+        .into_iter()
+        .filter_map(|(candidate, center, radius)| {
+            let to_center = center - eye;
+            let dist = to_center.length();
+            let reach = (dist - radius).max(0.0);
+            // Point blank counts whichever way the trooper is facing
+            let in_cone = dist <= radius || to_center.dot(forward) >= MELEE_CONE_COS * dist;
+            (reach <= MELEE_RANGE && in_cone).then_some((candidate, center, reach))
+        })
+        .collect();
+    hits.sort_by(|a, b| a.2.total_cmp(&b.2));
+    hits.truncate(MELEE_MAX_TARGETS);
+    hits.into_iter().map(|(candidate, center, _)| (candidate, center)).collect()
 }
 
 impl FPSPlayer {
@@ -190,7 +442,7 @@ impl FPSPlayer {
             callsign,
             health: loadout.max_health,
             max_health: loadout.max_health,
-            armor: 0.0,
+            armor: loadout.starting_armor,
             max_armor: 50.0,
             is_alive: true,
             respawn_timer: 0.0,
@@ -205,20 +457,22 @@ impl FPSPlayer {
             is_sprinting: false,
             is_crouching: false,
             is_prone: false,
+            is_swimming: false,
             is_grounded: true,
-            stamina: 100.0,
-            max_stamina: 100.0,
-
-            weapons: [
-                Weapon::new(loadout.primary),
-                Weapon::new(loadout.secondary),
-                Weapon::new(loadout.tertiary),
-            ],
+            stamina: loadout.stamina.full(),
+
+            weapons: [loadout.primary, loadout.secondary, loadout.tertiary].map(|weapon_type| {
+                let mut weapon = Weapon::new(weapon_type);
+                weapon.reload_speed = loadout.reload_speed;
+                weapon
+            }),
             current_weapon_slot: 0,
             is_aiming: false,
             aim_progress: 0.0,
             last_damage_time: -10.0,
             damage_direction: None,
+            grenades: MAX_FRAG_GRENADES,
+            damage_taken_mult: 1.0,
 
             ability: loadout.ability,
             ability_cooldown: loadout.ability_cooldown,
@@ -235,6 +489,48 @@ impl FPSPlayer {
         }
     }
 
+    /// Switch class (war table): weapons, health, stamina and ability come from the new class's
+    /// loadout. Callsign, position, difficulty scaling and mission stats carry over.
+    pub fn set_class(&mut self, class: PlayerClass) {
+        let previous = std::mem::replace(self, Self::new(class, String::new(), self.position));
+        self.callsign = previous.callsign;
+        self.damage_taken_mult = previous.damage_taken_mult;
+        // Keep the difficulty's stamina recovery factor, applied to the new class's base rate
+        let recovery_mult = previous.stamina.recovery_rate / previous.class.loadout().stamina.recovery_rate;
+        self.stamina.recovery_rate *= recovery_mult;
+        self.kills = previous.kills;
+        self.deaths = previous.deaths;
+        self.damage_dealt = previous.damage_dealt;
+        self.damage_taken = previous.damage_taken;
+    }
+
+    /// Swap the heavy weapon (slot 3) for `weapon_type`, fully loaded, keeping the class reload speed.
+    pub fn equip_heavy(&mut self, weapon_type: WeaponType) {
+        let slot = &mut self.weapons[Self::HEAVY_SLOT];
+        if slot.weapon_type != weapon_type {
+            let reload_speed = slot.reload_speed;
+            *slot = Weapon::new(weapon_type);
+            slot.reload_speed = reload_speed;
+        }
+    }
+
+    /// Take a weapon off the ground into the held slot (the primary while the shovel is out) and
+    /// switch to it. Returns what was in that slot, to be dropped in its place. A weapon the trooper
+    /// already carries is stripped for its ammo instead, and nothing is dropped.
+    pub fn pick_up_weapon(&mut self, pickup: WeaponPickup) -> Option<WeaponPickup> {
+        if let Some(carried) = self.weapons.iter_mut().find(|w| w.weapon_type == pickup.weapon_type) {
+            carried.reserve_ammo += pickup.ammo;
+            return None;
+        }
+        let slot = if self.is_shovel_equipped() { 0 } else { self.current_weapon_slot };
+        let reload_speed = self.weapons[slot].reload_speed;
+        let dropped = std::mem::replace(&mut self.weapons[slot], pickup.into_weapon(reload_speed));
+        self.set_weapon_slot(slot);
+        Some(WeaponPickup::from_weapon(&dropped))
+    }
+
+    /// Slot index for the heavy weapon (key 3; the loadout's `tertiary`).
+    pub const HEAVY_SLOT: usize = 2;
     /// Slot index for the entrenching shovel (key 4).
     pub const SHOVEL_SLOT: usize = 3;
     /// Total equipment slots (3 weapons + shovel).
@@ -271,6 +567,8 @@ impl FPSPlayer {
             return;
         }
 
+        let amount = amount * self.damage_taken_mult;
+
         // Armor absorbs first
         let armor_absorbed = amount.min(self.armor);
         self.armor -= armor_absorbed;
@@ -297,16 +595,18 @@ impl FPSPlayer {
     pub fn respawn(&mut self, position: Vec3) {
         self.is_alive = true;
         self.health = self.max_health;
-        self.armor = 0.0;
+        self.armor = self.class.loadout().starting_armor;
         self.position = position;
         self.velocity = Vec3::ZERO;
         self.respawn_timer = 0.0;
+        self.stamina = self.stamina.full();
 
         // Refill ammo
         for weapon in &mut self.weapons {
             weapon.current_ammo = weapon.magazine_size;
             weapon.reserve_ammo = weapon.magazine_size * 4;
         }
+        self.grenades = MAX_FRAG_GRENADES;
     }
 
     pub fn heal(&mut self, amount: f32) {
@@ -352,14 +652,9 @@ impl FPSPlayer {
         }
 
         // Update stamina
-        if self.is_sprinting && self.is_grounded {
-            self.stamina -= 20.0 * dt;
-            if self.stamina <= 0.0 {
-                self.stamina = 0.0;
-                self.is_sprinting = false;
-            }
-        } else {
-            self.stamina = (self.stamina + 15.0 * dt).min(self.max_stamina);
+        self.stamina.update(dt, self.is_sprinting && self.is_grounded);
+        if !self.stamina.can_sprint() {
+            self.is_sprinting = false;
         }
 
         // Update ADS — deliberate transition (Helldivers 2 / SST Extermination feel)
@@ -393,7 +688,13 @@ impl FPSPlayer {
     }
 
     pub fn stamina_percent(&self) -> f32 {
-        self.stamina / self.max_stamina
+        self.stamina.percent()
+    }
+
+    /// Apply difficulty scaling: incoming damage and stamina recovery (from the class base rate).
+    pub fn apply_difficulty(&mut self, difficulty: Difficulty) {
+        self.damage_taken_mult = difficulty.damage_taken_mult();
+        self.stamina.recovery_rate = self.class.loadout().stamina.recovery_rate * difficulty.stamina_recovery_mult();
     }
 
     pub fn ability_ready_percent(&self) -> f32 {
@@ -418,12 +719,70 @@ pub struct HitResult {
     pub bug_type: Option<BugType>,
 }
 
+/// Damage numbers shown at once before the oldest are dropped.
+pub const DEFAULT_MAX_DAMAGE_NUMBERS: usize = 64;
+/// Only the latest hit marker is drawn; a few are kept so rapid hits don't evict a kill marker early.
+const MAX_HIT_MARKERS: usize = 8;
+
+/// Fixed-capacity ring buffer for short-lived combat feedback. Storage is allocated once up front;
+/// pushing while full drops the oldest entry, so sustained fire never grows or reallocates it.
+/// Derefs to the underlying `VecDeque` (oldest first) for read access.
+#[derive(Debug, Clone)]
+pub struct CombatPool<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> CombatPool<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add an entry, dropping the oldest if the pool is full.
+    pub fn push(&mut self, item: T) {
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    /// Change the cap; shrinking drops the oldest entries.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.items.len() > self.capacity {
+            self.items.pop_front();
+        }
+        self.items.reserve(self.capacity - self.items.len());
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items.iter_mut()
+    }
+
+    /// Remove expired entries in place (no reallocation).
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.items.retain(f);
+    }
+}
+
+impl<T> std::ops::Deref for CombatPool<T> {
+    type Target = VecDeque<T>;
+
+    fn deref(&self) -> &VecDeque<T> {
+        &self.items
+    }
+}
+
 /// Combat system for FPS gameplay
 pub struct CombatSystem {
-    /// Damage numbers to display
-    pub damage_numbers: Vec<DamageNumber>,
+    /// Damage numbers to display (capped; oldest dropped first)
+    pub damage_numbers: CombatPool<DamageNumber>,
     /// Hit markers
-    pub hit_markers: Vec<HitMarker>,
+    pub hit_markers: CombatPool<HitMarker>,
     /// Kill feed entries
     pub kill_feed: Vec<KillFeedEntry>,
 }
@@ -440,7 +799,8 @@ pub struct DamageNumber {
 #[derive(Debug, Clone)]
 pub struct HitMarker {
     pub is_kill: bool,
-    pub is_headshot: bool,
+    /// Head of a skinny or a bug type's weak spot.
+    pub is_weakpoint: bool,
     pub lifetime: f32,
 }
 
@@ -456,12 +816,17 @@ pub struct KillFeedEntry {
 impl CombatSystem {
     pub fn new() -> Self {
         Self {
-            damage_numbers: Vec::new(),
-            hit_markers: Vec::new(),
+            damage_numbers: CombatPool::with_capacity(DEFAULT_MAX_DAMAGE_NUMBERS),
+            hit_markers: CombatPool::with_capacity(MAX_HIT_MARKERS),
             kill_feed: Vec::new(),
         }
     }
 
+    /// Cap how many damage numbers are displayed at once (oldest are dropped first).
+    pub fn set_max_damage_numbers(&mut self, max: usize) {
+        self.damage_numbers.set_capacity(max);
+    }
+
     /// Process a weapon hit against bugs
     pub fn process_hit(
         &mut self,
@@ -512,7 +877,7 @@ impl CombatSystem {
                 // Add hit marker
                 self.hit_markers.push(HitMarker {
                     is_kill: was_kill,
-                    is_headshot,
+                    is_weakpoint: is_headshot,
                     lifetime: 0.3,
                 });
 
@@ -576,7 +941,7 @@ impl CombatSystem {
                 });
                 self.hit_markers.push(HitMarker {
                     is_kill: was_kill,
-                    is_headshot,
+                    is_weakpoint: is_headshot,
                     lifetime: 0.3,
                 });
                 if was_kill {
@@ -608,7 +973,7 @@ impl CombatSystem {
     /// Update combat system (damage numbers, etc.)
     pub fn update(&mut self, dt: f32) {
         // Update damage numbers
-        for dn in &mut self.damage_numbers {
+        for dn in self.damage_numbers.iter_mut() {
             dn.lifetime -= dt;
             dn.position += dn.velocity * dt;
             dn.velocity.y -= 5.0 * dt; // Gravity
@@ -616,7 +981,7 @@ impl CombatSystem {
         self.damage_numbers.retain(|dn| dn.lifetime > 0.0);
 
         // Update hit markers
-        for hm in &mut self.hit_markers {
+        for hm in self.hit_markers.iter_mut() {
             hm.lifetime -= dt;
         }
         self.hit_markers.retain(|hm| hm.lifetime > 0.0);
@@ -633,7 +998,7 @@ impl CombatSystem {
     }
 
     pub fn latest_hit_marker(&self) -> Option<&HitMarker> {
-        self.hit_markers.last()
+        self.hit_markers.back()
     }
 }
 
@@ -643,7 +1008,7 @@ impl Default for CombatSystem {
     }
 }
 
-/// Bug or Skinny attack data
+/// Bug attack data
 #[derive(Debug, Clone)]
 pub struct BugAttack {
     pub bug_entity: Entity,
@@ -654,7 +1019,7 @@ pub struct BugAttack {
     pub last_attack_time: f32,
 }
 
-/// System to handle bugs attacking the player
+/// System to handle bugs attacking the player (Skinnies shoot instead; see `GameState::update_skinny_fire`)
 pub struct BugCombatSystem {
     attacks: HashMap<Entity, BugAttack>,
 }
@@ -666,8 +1031,8 @@ impl BugCombatSystem {
         }
     }
 
-    /// Update bug attacks against player
-    pub fn update(&mut self, world: &World, player: &mut FPSPlayer, dt: f32) {
+    /// Update bug attacks against player. Attacks through a deployed shield hit the shield instead.
+    pub fn update(&mut self, world: &World, player: &mut FPSPlayer, abilities: &AbilityState, dt: f32) {
         if !player.is_alive {
             return;
         }
@@ -678,14 +1043,19 @@ impl BugCombatSystem {
         }
 
         // Check for bugs in attack range
-        for (entity, (transform, bug)) in world.query::<(&Transform, &Bug)>().iter() {
+        for (entity, (transform, bug, physics_bug)) in world.query::<(&Transform, &Bug, Option<&PhysicsBug>)>().iter() {
+            // Burrowers can't bite until they are out of the ground
+            if physics_bug.is_some_and(|p| !p.is_surfaced()) {
+                continue;
+            }
             let distance = transform.position.distance(player.position);
             let attack_range = match bug.bug_type {
                 BugType::Warrior => 2.5,
                 BugType::Charger => 3.0,
                 BugType::Tanker => 4.0,
                 BugType::Hopper => 2.0,
-                BugType::Spitter => 25.0, // Ranged
+                BugType::Queen => 6.0, // Reach from the middle of her bulk
+                BugType::Spitter => 2.0, // Cornered bite; its acid lob is a BugStrike from the horde AI
             };
 
             let attack = self.attacks.entry(entity).or_insert_with(|| BugAttack {
@@ -698,42 +1068,20 @@ impl BugCombatSystem {
                     BugType::Charger => 0.8,
                     BugType::Tanker => 2.0,
                     BugType::Hopper => 1.2,
-                    BugType::Spitter => 3.0,
+                    BugType::Queen => 1.6,
+                    BugType::Spitter => 1.5,
                 },
                 last_attack_time: 0.0,
             });
 
             if distance <= attack_range && attack.last_attack_time >= attack.attack_cooldown {
-                let damage_direction = Some((transform.position - player.position).normalize());
-                player.take_damage(attack.attack_damage, damage_direction);
                 attack.last_attack_time = 0.0;
-                log::debug!("{:?} attacked player for {} damage!", bug.bug_type, attack.attack_damage);
-            }
-        }
-
-        // Skinnies (Heinlein): same chase/attack logic, different ranges
-        for (entity, (transform, skinny)) in world.query::<(&Transform, &Skinny)>().iter() {
-            let distance = transform.position.distance(player.position);
-            let (attack_range, cooldown) = match skinny.skinny_type {
-                crate::skinny::SkinnyType::Grunt => (2.5, 1.0),
-                crate::skinny::SkinnyType::Sniper => (15.0, 2.5),
-                crate::skinny::SkinnyType::Officer => (3.0, 1.2),
-            };
-
-            let attack = self.attacks.entry(entity).or_insert_with(|| BugAttack {
-                bug_entity: entity,
-                bug_type: None,
-                attack_range,
-                attack_damage: skinny.attack_damage,
-                attack_cooldown: cooldown,
-                last_attack_time: 0.0,
-            });
-
-            if distance <= attack_range && attack.last_attack_time >= attack.attack_cooldown {
+                if abilities.blocks(transform.position, player.position) {
+                    continue;
+                }
                 let damage_direction = Some((transform.position - player.position).normalize());
                 player.take_damage(attack.attack_damage, damage_direction);
-                attack.last_attack_time = 0.0;
-                log::debug!("Skinny attacked player for {} damage!", attack.attack_damage);
+                log::debug!("{:?} attacked player for {} damage!", bug.bug_type, attack.attack_damage);
             }
         }
 
@@ -759,12 +1107,15 @@ pub struct MissionState {
     pub time_elapsed: f32,
     /// Peak simultaneous bugs the trooper has faced.
     pub peak_bugs_alive: u32,
-    /// Whether the trooper has fallen (all lives exhausted).
+    /// Kills landed on a weakpoint.
+    pub weakpoint_kills: u32,
+    /// Bug holes collapsed with explosives.
+    pub holes_sealed: u32,
+    /// Set when an objective can no longer be completed (citizens lost, point overrun).
     pub is_failed: bool,
-    /// Optional: kill this many bugs to complete (Bug Hunt).
-    pub kill_target: Option<u32>,
-    /// Optional: survive this many seconds to complete (Hold the Line).
-    pub time_target_secs: Option<f32>,
+    /// Objectives in order; the first incomplete one is active. Objectives tied to things in the
+    /// world are added at landing (`GameState::spawn_mission_objectives`).
+    pub objectives: Vec<Objective>,
     /// Set when objective is met; trooper can extract for full success.
     pub objective_complete: bool,
 }
@@ -777,10 +1128,12 @@ pub enum MissionType {
     BugHunt,
     /// Hold the Line: survive for time then extract.
     HoldTheLine,
-    /// Defend a location (future).
+    /// Defense: capture a point, then hold it while bugs wear it down.
     Defense,
-    /// Destroy bug hive (future).
+    /// Hive Destruction: find and destroy the marked hives.
     HiveDestruction,
+    /// Rescue: escort stranded citizens back to the LZ.
+    Rescue,
     /// Earth visit — safe zone, resupply & R&R, return to orbit when ready.
     EarthVisit,
 }
@@ -793,148 +1146,105 @@ impl MissionType {
             MissionType::HoldTheLine => "Hold the Line",
             MissionType::Defense => "Defense",
             MissionType::HiveDestruction => "Hive Destruction",
+            MissionType::Rescue => "Rescue",
             MissionType::EarthVisit => "Visit Earth",
         }
     }
 }
 
 impl MissionState {
-    /// Create a new mission (default: Extermination, no target).
-    pub fn new_horde() -> Self {
+    fn with_objectives(mission_type: MissionType, objectives: Vec<Objective>) -> Self {
         Self {
-            mission_type: MissionType::Extermination,
+            mission_type,
             bugs_killed: 0,
             bugs_remaining: 0,
             time_elapsed: 0.0,
             peak_bugs_alive: 0,
+            weakpoint_kills: 0,
+            holes_sealed: 0,
             is_failed: false,
-            kill_target: None,
-            time_target_secs: None,
+            objectives,
             objective_complete: false,
         }
     }
 
+    /// Create a new mission (default: Extermination, no target).
+    pub fn new_horde() -> Self {
+        Self::with_objectives(MissionType::Extermination, Vec::new())
+    }
+
     /// Create Bug Hunt: kill this many bugs then extract.
     pub fn new_bug_hunt(kill_target: u32) -> Self {
-        Self {
-            mission_type: MissionType::BugHunt,
-            bugs_killed: 0,
-            bugs_remaining: 0,
-            time_elapsed: 0.0,
-            peak_bugs_alive: 0,
-            is_failed: false,
-            kill_target: Some(kill_target),
-            time_target_secs: None,
-            objective_complete: false,
-        }
+        Self::with_objectives(MissionType::BugHunt, vec![Objective::kill_bugs(kill_target)])
     }
 
     /// Create Hold the Line: survive this many seconds then extract.
     pub fn new_hold_the_line(secs: f32) -> Self {
-        Self {
-            mission_type: MissionType::HoldTheLine,
-            bugs_killed: 0,
-            bugs_remaining: 0,
-            time_elapsed: 0.0,
-            peak_bugs_alive: 0,
-            is_failed: false,
-            kill_target: None,
-            time_target_secs: Some(secs),
-            objective_complete: false,
-        }
+        Self::with_objectives(MissionType::HoldTheLine, vec![Objective::survive(secs)])
     }
 
-    /// Create Defense: hold the position for this many seconds (HD2 style).
-    pub fn new_defense(secs: f32) -> Self {
-        Self {
-            mission_type: MissionType::Defense,
-            bugs_killed: 0,
-            bugs_remaining: 0,
-            time_elapsed: 0.0,
-            peak_bugs_alive: 0,
-            is_failed: false,
-            kill_target: None,
-            time_target_secs: Some(secs),
-            objective_complete: false,
-        }
+    /// Create Defense: the capture and hold objectives are added at landing, once the point is known.
+    pub fn new_defense() -> Self {
+        Self::with_objectives(MissionType::Defense, Vec::new())
     }
 
-    /// Create Hive Destruction: kill this many bugs (destroy hive presence).
-    pub fn new_hive_destruction(kill_target: u32) -> Self {
-        Self {
-            mission_type: MissionType::HiveDestruction,
-            bugs_killed: 0,
-            bugs_remaining: 0,
-            time_elapsed: 0.0,
-            peak_bugs_alive: 0,
-            is_failed: false,
-            kill_target: Some(kill_target),
-            time_target_secs: None,
-            objective_complete: false,
-        }
+    /// Create Hive Destruction: the hives to destroy are spawned and added at landing.
+    pub fn new_hive_destruction() -> Self {
+        Self::with_objectives(MissionType::HiveDestruction, Vec::new())
+    }
+
+    /// Create Rescue: the citizens and LZ are spawned and added at landing.
+    pub fn new_rescue() -> Self {
+        Self::with_objectives(MissionType::Rescue, Vec::new())
     }
 
     /// Earth visit: safe zone, no combat — resupply and return to orbit when ready.
     pub fn new_earth_visit() -> Self {
         Self {
-            mission_type: MissionType::EarthVisit,
-            bugs_killed: 0,
-            bugs_remaining: 0,
-            time_elapsed: 0.0,
-            peak_bugs_alive: 0,
-            is_failed: false,
-            kill_target: None,
-            time_target_secs: None,
             objective_complete: true, // extract anytime
+            ..Self::with_objectives(MissionType::EarthVisit, Vec::new())
         }
     }
 
-    pub fn update(&mut self, dt: f32, _player_alive: bool) {
+    /// The objective being worked on (first incomplete one).
+    pub fn active_objective(&self) -> Option<&Objective> {
+        self.objectives.iter().find(|o| !o.is_complete())
+    }
+
+    pub fn update(&mut self, world: &mut World, dt: f32, player_pos: Vec3, sample_terrain_y: impl Fn(f32, f32) -> f32) {
         self.time_elapsed += dt;
 
         if self.bugs_remaining > self.peak_bugs_alive {
             self.peak_bugs_alive = self.bugs_remaining;
         }
 
-        // Check objectives
-        if !self.objective_complete {
-            match self.mission_type {
-                MissionType::BugHunt | MissionType::HiveDestruction => {
-                    if let Some(t) = self.kill_target {
-                        if self.bugs_killed >= t {
-                            self.objective_complete = true;
-                        }
-                    }
-                }
-                MissionType::HoldTheLine | MissionType::Defense => {
-                    if let Some(t) = self.time_target_secs {
-                        if self.time_elapsed >= t {
-                            self.objective_complete = true;
-                        }
-                    }
-                }
-                _ => {}
-            }
+        // Check objectives: only the active one advances
+        if self.objective_complete || self.is_failed {
+            return;
+        }
+        let ctx = ObjectiveContext { dt, player_pos, bugs_killed: self.bugs_killed };
+        if let Some(active) = self.objectives.iter_mut().find(|o| !o.is_complete()) {
+            active.update(world, &ctx, sample_terrain_y);
+            self.is_failed = active.is_failed();
+        }
+        if !self.objectives.is_empty() && self.objectives.iter().all(Objective::is_complete) {
+            self.objective_complete = true;
         }
     }
 
-    /// Short objective string for HUD (e.g. "Kill 25 bugs" or "Survive 5:00").
+    /// Short objective string for HUD (e.g. "Kill bugs 12/25" or "Survive 04:12").
     pub fn objective_text(&self) -> Option<String> {
         match self.mission_type {
-            MissionType::BugHunt => self.kill_target.map(|t| format!("Kill {} bugs", t)),
-            MissionType::HiveDestruction => self.kill_target.map(|t| format!("Destroy hive: {} kills", t)),
-            MissionType::HoldTheLine => self.time_target_secs.map(|s| {
-                let m = (s / 60.0) as u32;
-                let sec = (s % 60.0) as u32;
-                format!("Survive {:02}:{:02}", m, sec)
-            }),
-            MissionType::Defense => self.time_target_secs.map(|s| {
-                let m = (s / 60.0) as u32;
-                let sec = (s % 60.0) as u32;
-                format!("Hold position {:02}:{:02}", m, sec)
-            }),
             MissionType::EarthVisit => Some("Visit — return to orbit when ready".to_string()),
-            _ => None,
+            _ if self.objective_complete => self.objectives.last().map(|o| o.text()),
+            _ => self.active_objective().map(|o| {
+                let step = self.objectives.iter().take_while(|o| o.is_complete()).count() + 1;
+                if self.objectives.len() > 1 {
+                    format!("[{}/{}] {}", step, self.objectives.len(), o.text())
+                } else {
+                    o.text()
+                }
+            }),
         }
     }
 
@@ -945,3 +1255,172 @@ impl MissionState {
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn damage_number(damage: f32, lifetime: f32) -> DamageNumber {
+        DamageNumber {
+            position: Vec3::ZERO,
+            damage,
+            is_critical: false,
+            lifetime,
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    #[test]
+    fn damage_numbers_are_capped_dropping_the_oldest() {
+        let mut combat = CombatSystem::new();
+        combat.set_max_damage_numbers(4);
+        for i in 0..10 {
+            combat.damage_numbers.push(damage_number(i as f32, 1.0));
+        }
+        let shown: Vec<f32> = combat.damage_numbers.iter().map(|dn| dn.damage).collect();
+        assert_eq!(shown, [6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(combat.damage_numbers.back().map(|dn| dn.damage), Some(9.0));
+
+        combat.set_max_damage_numbers(2);
+        let shown: Vec<f32> = combat.damage_numbers.iter().map(|dn| dn.damage).collect();
+        assert_eq!(shown, [8.0, 9.0]);
+    }
+
+    #[test]
+    fn expired_entries_are_removed_without_growing_the_pool() {
+        let mut combat = CombatSystem::new();
+        let allocated = combat.damage_numbers.capacity();
+        for _ in 0..DEFAULT_MAX_DAMAGE_NUMBERS * 4 {
+            combat.damage_numbers.push(damage_number(0.0, 0.1));
+        }
+        assert_eq!(combat.damage_numbers.len(), DEFAULT_MAX_DAMAGE_NUMBERS);
+        assert_eq!(combat.damage_numbers.capacity(), allocated);
+
+        combat.damage_numbers.push(damage_number(1.0, 0.1));
+        combat.damage_numbers.push(damage_number(2.0, 2.0));
+        combat.hit_markers.push(HitMarker { is_kill: true, is_weakpoint: false, lifetime: 0.3 });
+        combat.update(0.5);
+        assert_eq!(combat.damage_numbers.len(), 1);
+        assert_eq!(combat.damage_numbers.back().map(|dn| dn.damage), Some(2.0));
+        assert!(!combat.has_active_hit_marker());
+        assert_eq!(combat.damage_numbers.capacity(), allocated);
+    }
+
+    #[test]
+    fn look_target_prefers_the_npc_under_the_crosshair_over_the_nearest() {
+        let cam = Vec3::new(0.0, 1.7, 0.0);
+        let forward = Vec3::new(0.0, 0.0, -1.0);
+        let npcs = [
+            ("near_but_beside", Vec3::new(1.2, 0.0, -1.0)),
+            ("ahead", Vec3::new(0.2, 0.0, -2.8)),
+            ("behind", Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        assert_eq!(pick_look_target(cam, forward, 3.0, npcs), Some("ahead"));
+
+        // Looking down at someone's feet still targets them; out of range does not.
+        let down = Vec3::new(0.0, -0.5, -1.0);
+        assert_eq!(pick_look_target(cam, down, 3.0, [("short", Vec3::new(0.0, 0.0, -2.0))]), Some("short"));
+        assert_eq!(pick_look_target(cam, forward, 3.0, [("far", Vec3::new(0.0, 0.0, -6.0))]), None::<&str>);
+    }
+
+    #[test]
+    fn running_dry_locks_sprint_until_recovered_past_the_threshold() {
+        let mut stamina = Stamina::default().full();
+        let mut seconds = 0.0;
+        while stamina.can_sprint() {
+            stamina.update(0.1, true);
+            seconds += 0.1;
+        }
+        assert!(stamina.winded);
+        assert!((4.8..=5.0).contains(&seconds)); // 100 at 20/s, winded just before empty
+
+        // Nothing comes back during the recovery delay
+        let winded_at = stamina.current;
+        stamina.update(0.5, false);
+        assert_eq!(stamina.current, winded_at);
+
+        // Still locked just under the recovery threshold, free again once past it
+        while stamina.current < 30.0 {
+            stamina.update(0.1, false);
+        }
+        assert!(!stamina.can_sprint());
+        while stamina.current < 36.0 {
+            stamina.update(0.1, false);
+        }
+        assert!(stamina.can_sprint());
+    }
+
+    #[test]
+    fn shipped_class_data_matches_builtin_and_partial_files_fall_back() {
+        let shipped = parse_class_loadouts(include_str!("../../../classes.ron")).expect("classes.ron parses");
+        for class in PlayerClass::ALL {
+            let (a, b) = (&shipped[&class], ClassLoadout::builtin(class));
+            assert_eq!((a.primary, a.secondary, a.ability), (b.primary, b.secondary, b.ability));
+            assert_eq!((a.max_health, a.reload_speed, a.starting_armor), (b.max_health, b.reload_speed, b.starting_armor));
+            assert_eq!((a.stamina.max, a.stamina.recovery_rate), (b.stamina.max, b.stamina.recovery_rate));
+        }
+
+        let partial = parse_class_loadouts(
+            "{ Ranger: (primary: Rifle, secondary: Sniper, tertiary: MachineGun, max_health: 90.0, move_speed: 7.0, \
+             sprint_multiplier: 1.5, ability: Dash, ability_cooldown: 10.0) }",
+        )
+        .unwrap();
+        assert_eq!(partial[&PlayerClass::Ranger].ability, ClassAbility::Dash);
+        assert_eq!(partial[&PlayerClass::Ranger].reload_speed, 1.0);
+        assert_eq!(partial[&PlayerClass::Bastion].starting_armor, ClassLoadout::builtin(PlayerClass::Bastion).starting_armor);
+    }
+
+    #[test]
+    fn switching_class_keeps_difficulty_scaling() {
+        let mut player = FPSPlayer::new(PlayerClass::Hunter, "Rico".to_string(), Vec3::ZERO);
+        player.apply_difficulty(Difficulty::Helldive);
+        player.set_class(PlayerClass::Bastion);
+        let base = PlayerClass::Bastion.loadout().stamina.recovery_rate;
+        assert!((player.stamina.recovery_rate - base * Difficulty::Helldive.stamina_recovery_mult()).abs() < 1e-4);
+        assert_eq!(player.damage_taken_mult, Difficulty::Helldive.damage_taken_mult());
+    }
+
+    #[test]
+    fn picking_up_a_weapon_swaps_the_held_one_out() {
+        let mut player = FPSPlayer::new(PlayerClass::Hunter, "Rico".to_string(), Vec3::ZERO);
+        let held = player.weapons[0].weapon_type;
+        let new_type = WeaponType::ALL.into_iter().find(|t| player.weapons.iter().all(|w| w.weapon_type != *t)).unwrap();
+        player.weapons[0].current_ammo = 5;
+        player.weapons[0].reserve_ammo = 20;
+        player.weapons[0].reload_speed = 1.25;
+        player.set_weapon_slot(FPSPlayer::SHOVEL_SLOT);
+
+        let dropped = player.pick_up_weapon(WeaponPickup { weapon_type: new_type, ammo: 1000 }).unwrap();
+        assert_eq!((dropped.weapon_type, dropped.ammo), (held, 25));
+        assert_eq!(player.current_weapon_slot, 0, "shovel out: swaps into the primary");
+        let taken = player.current_weapon();
+        assert_eq!(taken.weapon_type, new_type);
+        assert_eq!(taken.current_ammo, taken.magazine_size);
+        assert_eq!(taken.current_ammo + taken.reserve_ammo, 1000);
+        assert_eq!(taken.reload_speed, 1.25);
+
+        // A weapon already carried only gives up its ammo
+        let reserve = player.weapons[1].reserve_ammo;
+        let same = WeaponPickup { weapon_type: player.weapons[1].weapon_type, ammo: 30 };
+        assert!(player.pick_up_weapon(same).is_none());
+        assert_eq!(player.weapons[1].reserve_ammo, reserve + 30);
+    }
+
+    #[test]
+    fn melee_catches_the_closest_bugs_in_front() {
+        let eye = Vec3::new(0.0, 1.8, 0.0);
+        let bugs = [
+            ("far", Vec3::new(0.0, 1.0, -6.0), 1.0),
+            ("behind", Vec3::new(0.0, 1.0, 1.5), 0.8),
+            ("close", Vec3::new(0.3, 1.0, -1.5), 0.8),
+            ("big", Vec3::new(-0.5, 1.0, -3.5), 1.5),
+            ("wide", Vec3::new(2.0, 1.0, -0.5), 0.5),
+            ("mid", Vec3::new(0.0, 1.2, -2.5), 0.6),
+        ];
+        let hit: Vec<&str> = melee_targets(eye, Vec3::NEG_Z, bugs).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(hit, ["close", "mid", "big"]);
+
+        let hugging = [("hugging", eye + Vec3::Z * 0.5, 1.0)];
+        assert_eq!(melee_targets(eye, Vec3::NEG_Z, hugging).len(), 1, "a bug on top of the trooper is always in reach");
+    }
+}
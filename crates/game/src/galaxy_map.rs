@@ -0,0 +1,308 @@
+//! 3D galaxy map: an orbit camera over `Universe::systems`, reticle picking at screen center,
+//! and the filtered/sorted system list shown beside it.
+//!
+//! Map space is galaxy space (the core at the origin, the disc in XZ), so star positions are
+//! used as-is. The map camera never touches the player's camera.
+
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use input::InputState;
+use procgen::{StarType, Universe};
+use renderer::Camera;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Closest and farthest the map camera orbits from its focus.
+pub const MAP_MIN_DISTANCE: f32 = 40.0;
+pub const MAP_MAX_DISTANCE: f32 = 2600.0;
+/// Orbit distance when the map opens (a neighbourhood of a few dozen systems).
+const MAP_DEFAULT_DISTANCE: f32 = 320.0;
+/// Pitch range: from just above the disc to almost straight down (radians, negative = looking down).
+const MAP_PITCH_MIN: f32 = -1.5;
+const MAP_PITCH_MAX: f32 = -0.08;
+/// Orbit speed for arrow keys (rad/s) and for mouse motion (rad per pixel).
+const ORBIT_KEY_SPEED: f32 = 1.6;
+const ORBIT_MOUSE_SPEED: f32 = 0.004;
+/// Zoom factor per scroll notch or per second of holding +/-.
+const ZOOM_STEP: f32 = 1.2;
+/// Pan speed as a fraction of orbit distance per second, so panning feels the same at any zoom.
+const PAN_SPEED: f32 = 0.9;
+/// Reticle pick radius as a fraction of screen height.
+pub const RETICLE_RADIUS: f32 = 0.06;
+/// Drawn star radius as a fraction of its distance from the map camera (constant apparent size).
+pub const STAR_ANGULAR_SIZE: f32 = 0.005;
+
+/// Color a star type is drawn with on the map (RGBA).
+pub fn star_color(star_type: StarType) -> [f32; 4] {
+    match star_type {
+        StarType::RedDwarf => [1.0, 0.3, 0.2, 0.9],
+        StarType::YellowMain => [1.0, 0.95, 0.5, 0.9],
+        StarType::BlueGiant => [0.5, 0.6, 1.0, 0.9],
+        StarType::WhiteDwarf => [0.9, 0.9, 1.0, 0.8],
+        StarType::BinaryStar => [1.0, 0.8, 0.4, 0.9],
+    }
+}
+
+/// Which systems the map shows and lets the reticle pick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapFilter {
+    All,
+    /// Systems the fleet has fought in (war intel available).
+    Charted,
+    /// Systems nobody has dropped into yet.
+    Uncharted,
+}
+
+impl MapFilter {
+    pub fn next(self) -> Self {
+        match self {
+            MapFilter::All => MapFilter::Charted,
+            MapFilter::Charted => MapFilter::Uncharted,
+            MapFilter::Uncharted => MapFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MapFilter::All => "ALL",
+            MapFilter::Charted => "CHARTED",
+            MapFilter::Uncharted => "UNCHARTED",
+        }
+    }
+}
+
+/// Order of the system list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapSort {
+    /// Nearest to the current system first.
+    Distance,
+    Name,
+}
+
+impl MapSort {
+    pub fn next(self) -> Self {
+        match self {
+            MapSort::Distance => MapSort::Name,
+            MapSort::Name => MapSort::Distance,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MapSort::Distance => "DISTANCE",
+            MapSort::Name => "NAME",
+        }
+    }
+}
+
+/// Map camera and list settings while the galaxy map is open.
+#[derive(Debug, Clone)]
+pub struct GalaxyMapView {
+    /// Point the camera orbits; the reticle sits on it.
+    pub focus: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub filter: MapFilter,
+    pub sort: MapSort,
+    /// Cursor position last frame of a left-button drag (free cursor only).
+    drag_from: Option<Vec2>,
+}
+
+impl Default for GalaxyMapView {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            yaw: 0.0,
+            pitch: -0.6,
+            distance: MAP_DEFAULT_DISTANCE,
+            filter: MapFilter::All,
+            sort: MapSort::Distance,
+            drag_from: None,
+        }
+    }
+}
+
+/// Project a map-space point to screen pixels, or `None` if it is behind the camera.
+pub fn project(view_proj: Mat4, point: Vec3, sw: f32, sh: f32) -> Option<Vec2> {
+    let clip = view_proj * Vec4::new(point.x, point.y, point.z, 1.0);
+    if clip.w <= 0.01 || clip.z / clip.w > 1.0 {
+        return None;
+    }
+    Some(Vec2::new((clip.x / clip.w + 1.0) * 0.5 * sw, (1.0 - clip.y / clip.w) * 0.5 * sh))
+}
+
+impl GalaxyMapView {
+    /// Move the focus (and reticle) onto a star, keeping the current angle and zoom.
+    pub fn focus_on(&mut self, universe: &Universe, idx: usize) {
+        if let Some(entry) = universe.systems.get(idx) {
+            self.focus = entry.position.as_vec3();
+        }
+        self.drag_from = None;
+    }
+
+    /// Orbit (arrows, mouse), zoom (scroll, +/-) and pan across the disc (WASD).
+    /// Mouse orbit uses raw motion when the cursor is locked and a left-drag when it is free.
+    pub fn handle_input(&mut self, input: &InputState, dt: f32) {
+        let mut orbit = Vec2::ZERO;
+        if input.is_key_held(KeyCode::ArrowLeft) { orbit.x -= ORBIT_KEY_SPEED * dt; }
+        if input.is_key_held(KeyCode::ArrowRight) { orbit.x += ORBIT_KEY_SPEED * dt; }
+        if input.is_key_held(KeyCode::ArrowUp) { orbit.y -= ORBIT_KEY_SPEED * dt; }
+        if input.is_key_held(KeyCode::ArrowDown) { orbit.y += ORBIT_KEY_SPEED * dt; }
+        if input.is_cursor_locked() {
+            orbit += input.raw_mouse_delta() * ORBIT_MOUSE_SPEED;
+            self.drag_from = None;
+        } else if input.is_mouse_held(MouseButton::Left) {
+            let cursor = input.mouse_position();
+            if let Some(from) = self.drag_from {
+                orbit += (cursor - from) * ORBIT_MOUSE_SPEED;
+            }
+            self.drag_from = Some(cursor);
+        } else {
+            self.drag_from = None;
+        }
+        self.orbit(orbit.x, orbit.y);
+
+        if input.is_scroll_up() { self.zoom(1.0 / ZOOM_STEP); }
+        if input.is_scroll_down() { self.zoom(ZOOM_STEP); }
+        if input.is_key_held(KeyCode::Equal) { self.zoom(ZOOM_STEP.powf(-3.0 * dt)); }
+        if input.is_key_held(KeyCode::Minus) { self.zoom(ZOOM_STEP.powf(3.0 * dt)); }
+
+        let mut pan = Vec2::ZERO;
+        if input.is_key_held(KeyCode::KeyD) { pan.x += 1.0; }
+        if input.is_key_held(KeyCode::KeyA) { pan.x -= 1.0; }
+        if input.is_key_held(KeyCode::KeyW) { pan.y += 1.0; }
+        if input.is_key_held(KeyCode::KeyS) { pan.y -= 1.0; }
+        if pan != Vec2::ZERO {
+            self.pan(pan.normalize() * PAN_SPEED * self.distance * dt);
+        }
+
+        if input.is_key_pressed(KeyCode::KeyF) { self.filter = self.filter.next(); }
+        if input.is_key_pressed(KeyCode::Tab) { self.sort = self.sort.next(); }
+    }
+
+    /// Turn the camera around the focus (positive pitch delta tilts toward top-down).
+    pub fn orbit(&mut self, d_yaw: f32, d_pitch: f32) {
+        self.yaw -= d_yaw;
+        self.pitch = (self.pitch - d_pitch).clamp(MAP_PITCH_MIN, MAP_PITCH_MAX);
+    }
+
+    /// Scale the orbit distance (< 1 zooms in).
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance = (self.distance * factor).clamp(MAP_MIN_DISTANCE, MAP_MAX_DISTANCE);
+    }
+
+    /// Slide the focus across the galactic plane: x = screen right, y = screen forward.
+    pub fn pan(&mut self, offset: Vec2) {
+        let (sin, cos) = self.yaw.sin_cos();
+        let forward = Vec3::new(-sin, 0.0, -cos);
+        let right = Vec3::new(cos, 0.0, -sin);
+        self.focus += right * offset.x + forward * offset.y;
+    }
+
+    /// Camera position in map space.
+    pub fn eye(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let forward = Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch);
+        self.focus - forward * self.distance
+    }
+
+    /// The map camera, looking at the focus with clip planes sized to the whole galaxy.
+    pub fn camera(&self, aspect: f32) -> Camera {
+        let mut camera = Camera::new(self.eye());
+        camera.set_yaw_pitch(self.yaw, self.pitch);
+        camera.aspect = aspect;
+        camera.near = self.distance * 0.01;
+        camera.far = self.distance + MAP_MAX_DISTANCE * 1.5;
+        camera
+    }
+
+    /// Whether the filter lets system `idx` through. The current system is always shown.
+    pub fn shows(&self, universe: &Universe, idx: usize, current: usize) -> bool {
+        let Some(entry) = universe.systems.get(idx) else { return false };
+        idx == current
+            || match self.filter {
+                MapFilter::All => true,
+                MapFilter::Charted => entry.visited,
+                MapFilter::Uncharted => !entry.visited,
+            }
+    }
+
+    /// Systems passing the filter, in list order.
+    pub fn listing(&self, universe: &Universe, current: usize) -> Vec<usize> {
+        let origin = universe.systems.get(current).map_or(glam::DVec3::ZERO, |e| e.position);
+        let mut list: Vec<usize> = (0..universe.systems.len()).filter(|&i| self.shows(universe, i, current)).collect();
+        match self.sort {
+            MapSort::Distance => list.sort_by(|&a, &b| {
+                let da = universe.systems[a].position.distance_squared(origin);
+                let db = universe.systems[b].position.distance_squared(origin);
+                da.total_cmp(&db)
+            }),
+            MapSort::Name => list.sort_by(|&a, &b| universe.systems[a].name.cmp(&universe.systems[b].name)),
+        }
+        list
+    }
+
+    /// The shown system whose star lands closest to the reticle (screen center), if any is inside it.
+    pub fn system_under_reticle(&self, universe: &Universe, current: usize, aspect: f32) -> Option<usize> {
+        let view_proj = self.camera(aspect).view_projection_matrix();
+        // Screen measured in screen heights so the reticle radius is resolution independent
+        let center = Vec2::new(aspect * 0.5, 0.5);
+        (0..universe.systems.len())
+            .filter(|&i| self.shows(universe, i, current))
+            .filter_map(|i| {
+                let screen = project(view_proj, universe.systems[i].position.as_vec3(), aspect, 1.0)?;
+                let off = screen.distance(center);
+                (off <= RETICLE_RADIUS).then_some((i, off))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reticle_picks_the_focused_star_and_respects_the_filter() {
+        let mut universe = Universe::generate(7, 60);
+        let mut view = GalaxyMapView::default();
+        let target = 12;
+        view.focus_on(&universe, target);
+        assert_eq!(view.system_under_reticle(&universe, 0, 16.0 / 9.0), Some(target));
+
+        // Orbiting keeps the focused star under the reticle
+        view.orbit(1.1, 0.4);
+        view.zoom(0.5);
+        assert_eq!(view.system_under_reticle(&universe, 0, 16.0 / 9.0), Some(target));
+
+        // Filtered-out systems can't be picked, but marking it charted brings it back
+        view.filter = MapFilter::Charted;
+        assert_ne!(view.system_under_reticle(&universe, 0, 16.0 / 9.0), Some(target));
+        universe.systems[target].visited = true;
+        assert_eq!(view.system_under_reticle(&universe, 0, 16.0 / 9.0), Some(target));
+    }
+
+    #[test]
+    fn listing_sorts_by_distance_from_the_current_system_or_by_name() {
+        let universe = Universe::generate(3, 40);
+        let mut view = GalaxyMapView::default();
+        let current = 5;
+        let by_distance = view.listing(&universe, current);
+        assert_eq!(by_distance.len(), universe.systems.len());
+        assert_eq!(by_distance[0], current);
+        let origin = universe.systems[current].position;
+        assert!(by_distance.windows(2).all(|w| {
+            universe.systems[w[0]].position.distance(origin) <= universe.systems[w[1]].position.distance(origin)
+        }));
+
+        view.sort = MapSort::Name;
+        let by_name = view.listing(&universe, current);
+        assert!(by_name.windows(2).all(|w| universe.systems[w[0]].name <= universe.systems[w[1]].name));
+
+        // Nothing charted yet: only the current system survives the charted filter
+        view.filter = MapFilter::Charted;
+        assert_eq!(view.listing(&universe, current), vec![current]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Frag grenades: hold G to pull the pin and cook, release to throw along the aim arc.
+//!
+//! In flight the grenade is a real rigid body, so it bounces off terrain, cover and bugs and rolls
+//! to a stop. The fuse starts on the pin pull, not the throw: cooking shortens the time bugs get
+//! to scatter, and holding it too long goes off in hand.
+
+use glam::Vec3;
+use physics::RigidBodyHandle;
+
+/// Seconds from pin pull to detonation.
+pub const FRAG_FUSE_SECS: f32 = 3.5;
+/// Grenades carried into a drop; resupply tops back up to this.
+pub const MAX_FRAG_GRENADES: u32 = 4;
+/// Blast radius and damage at the center (falls off to half at the edge).
+pub const FRAG_RADIUS: f32 = 7.0;
+pub const FRAG_DAMAGE: f32 = 180.0;
+/// Collision radius of the grenade body.
+pub const GRENADE_RADIUS: f32 = 0.09;
+/// Release speed, and how much the throw is lofted above the crosshair.
+const THROW_SPEED: f32 = 20.0;
+const THROW_LOFT: f32 = 0.3;
+/// Preview integration step and cap on the number of arc points.
+const PREVIEW_STEP: f32 = 0.05;
+const PREVIEW_MAX_POINTS: usize = 64;
+
+/// A thrown frag grenade. `position` mirrors the rigid body for rendering.
+pub struct FragGrenade {
+    pub body: RigidBodyHandle,
+    pub position: Vec3,
+    /// Seconds until it goes off.
+    pub fuse: f32,
+}
+
+impl FragGrenade {
+    /// Burn the fuse. Returns true once it detonates.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.fuse -= dt;
+        self.fuse <= 0.0
+    }
+}
+
+/// Fuse left after cooking for `cook_secs`.
+pub fn cooked_fuse(cook_secs: f32) -> f32 {
+    (FRAG_FUSE_SECS - cook_secs).max(0.0)
+}
+
+/// Release velocity for a throw along `aim`, carrying the thrower's own momentum.
+pub fn throw_velocity(aim: Vec3, carrier_velocity: Vec3) -> Vec3 {
+    let dir = (aim.normalize_or_zero() + Vec3::Y * THROW_LOFT).normalize_or_zero();
+    dir * THROW_SPEED + carrier_velocity
+}
+
+/// Points along the ballistic arc from `origin`, ending where it meets the ground (from `ground`,
+/// a height sampler) or where the fuse would run out mid-air. Bounces aren't predicted.
+pub fn trajectory_preview(
+    origin: Vec3,
+    velocity: Vec3,
+    gravity: f32,
+    fuse: f32,
+    ground: impl Fn(f32, f32) -> f32,
+) -> Vec<Vec3> {
+    let mut points = vec![origin];
+    let (mut pos, mut vel, mut t) = (origin, velocity, 0.0);
+    while points.len() < PREVIEW_MAX_POINTS && t < fuse {
+        vel.y += gravity * PREVIEW_STEP;
+        pos += vel * PREVIEW_STEP;
+        t += PREVIEW_STEP;
+        let ground_y = ground(pos.x, pos.z) + GRENADE_RADIUS;
+        if pos.y <= ground_y {
+            points.push(Vec3::new(pos.x, ground_y, pos.z));
+            break;
+        }
+        points.push(pos);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooking_shortens_the_fuse_down_to_zero() {
+        assert_eq!(cooked_fuse(0.0), FRAG_FUSE_SECS);
+        assert!((cooked_fuse(1.5) - (FRAG_FUSE_SECS - 1.5)).abs() < 1e-5);
+        assert_eq!(cooked_fuse(FRAG_FUSE_SECS + 1.0), 0.0);
+    }
+
+    #[test]
+    fn preview_arcs_up_and_lands_on_the_ground() {
+        let vel = throw_velocity(Vec3::NEG_Z, Vec3::ZERO);
+        assert!(vel.y > 0.0, "throw is not lofted: {vel:?}");
+
+        let points = trajectory_preview(Vec3::new(0.0, 1.6, 0.0), vel, -9.81, FRAG_FUSE_SECS, |_, _| 0.0);
+        let apex = points.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        assert!(apex > 1.6);
+        let landing = *points.last().unwrap();
+        assert!((landing.y - GRENADE_RADIUS).abs() < 1e-5, "arc did not end on the ground: {landing:?}");
+        assert!(landing.z < -10.0, "throw fell short: {landing:?}");
+    }
+
+    #[test]
+    fn preview_stops_where_a_cooked_fuse_runs_out() {
+        let vel = throw_velocity(Vec3::NEG_Z, Vec3::ZERO);
+        let full = trajectory_preview(Vec3::Y, vel, -9.81, FRAG_FUSE_SECS, |_, _| 0.0);
+        let cooked = trajectory_preview(Vec3::Y, vel, -9.81, 0.5, |_, _| 0.0);
+        assert!(cooked.len() < full.len());
+        assert!(cooked.last().unwrap().y > GRENADE_RADIUS, "cooked arc should end mid-air");
+    }
+}
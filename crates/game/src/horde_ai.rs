@@ -1,16 +1,32 @@
 //! Horde AI system using flow fields.
 
-use engine_core::{AIComponent, AIState, Transform, Velocity, Vec3};
-use hecs::World;
+use std::collections::HashSet;
+
+use engine_core::{AIComponent, AIState, Health, Transform, Velocity, Vec3};
+use glam::{Mat3, Vec2};
+use hecs::{Entity, World};
 use procgen::FlowField;
 
-use crate::bug::Bug;
-use crate::skinny::Skinny;
+use crate::bug::{
+    lob_velocity, Bug, BugBehavior, BugStrike, Maneuver, ACID_GRAVITY, CHARGE_DURATION, CHARGE_HIT_RADIUS,
+    CHARGE_SPEED_MULT, LEAP_DURATION, POUNCE_RADIUS, SPIT_SPEED,
+};
+use crate::bug_entity::PhysicsBug;
+use crate::destruction::{Destructible, DestructiblePhysics};
+use crate::skinny::{RangedIntent, Skinny};
 
 /// Smoothing factor for velocity (higher = more responsive, lower = more natural/fluid)
 const VELOCITY_SMOOTHING: f32 = 0.25;
 /// Blend flow field with direct pursuit (0 = pure flow, 1 = pure direct)
 const DIRECT_PURSUIT_BLEND: f32 = 0.35;
+/// Destructibles with a smaller footprint half-extent (m) don't block the flow field; bugs shoulder past pebbles.
+const MIN_OBSTACLE_HALF_EXTENT: f32 = 0.4;
+/// Padding around obstacle footprints (m) so bugs path clear of the wall rather than scraping it.
+const OBSTACLE_MARGIN: f32 = 0.5;
+/// Skinnies backpedal at this fraction of their run speed.
+const SKINNY_RETREAT_SPEED: f32 = 0.8;
+/// Ids for static obstacles (buildings) set the top bit so they never collide with entity ids.
+const STATIC_OBSTACLE_ID_BIT: u64 = 1 << 63;
 
 /// Manages AI behavior for the bug horde.
 pub struct HordeAI {
@@ -18,6 +34,10 @@ pub struct HordeAI {
     target_position: Vec3,
     update_interval: f32,
     time_since_update: f32,
+    /// Destructible entities currently stamped into the flow field (synced from the world).
+    entity_obstacles: HashSet<u64>,
+    /// Static obstacles (e.g. Earth buildings) registered directly; not touched by the world sync.
+    static_obstacles: Vec<u64>,
 }
 
 impl HordeAI {
@@ -27,6 +47,8 @@ impl HordeAI {
             target_position: Vec3::ZERO,
             update_interval: 0.35, // Extermination: more responsive horde movement
             time_since_update: 0.0,
+            entity_obstacles: HashSet::new(),
+            static_obstacles: Vec::new(),
         }
     }
 
@@ -35,19 +57,34 @@ impl HordeAI {
         self.target_position = target;
     }
 
-    /// Update all bugs in the horde.
-    pub fn update(&mut self, world: &mut World, dt: f32) {
+    /// Update all bugs in the horde. Returns the signature-move hits (acid, charges, pounces,
+    /// slams) for the game to apply to the trooper.
+    pub fn update(&mut self, world: &mut World, dt: f32) -> Vec<BugStrike> {
         // Periodically update the flow field
         self.time_since_update += dt;
         if self.time_since_update >= self.update_interval {
             self.time_since_update = 0.0;
+            self.sync_obstacles(world);
             self.flow_field.set_goal(self.target_position);
         }
 
+        let mut strikes = Vec::new();
+
         // Update each bug
-        for (_, (transform, velocity, bug, ai)) in
-            world.query_mut::<(&mut Transform, &mut Velocity, &Bug, &mut AIComponent)>()
-        {
+        for (_, (transform, velocity, bug, ai, physics_bug, health)) in world.query_mut::<(
+            &mut Transform,
+            &mut Velocity,
+            &mut Bug,
+            &mut AIComponent,
+            Option<&PhysicsBug>,
+            Option<&Health>,
+        )>() {
+            // Still underground or clawing out: nothing to steer yet
+            if physics_bug.is_some_and(|p| !p.is_surfaced()) {
+                velocity.linear = Vec3::ZERO;
+                continue;
+            }
+
             // Update AI state based on distance to target
             let to_target = self.target_position - transform.position;
             let distance = to_target.length();
@@ -75,6 +112,18 @@ impl HordeAI {
                 AIState::Fleeing | AIState::Dead => {}
             }
 
+            // Signature move (charge, leap, slam, spit) takes over movement while it runs
+            if !health.is_some_and(Health::is_dead) {
+                let engaged = matches!(ai.state, AIState::Chasing | AIState::Attacking);
+                let speed_scale = physics_bug.map_or(1.0, PhysicsBug::speed_scale);
+                let (owns_movement, strike) =
+                    run_behavior(bug, transform, velocity, self.target_position, engaged, speed_scale, dt);
+                strikes.extend(strike);
+                if owns_movement {
+                    continue;
+                }
+            }
+
             // Movement based on state
             match ai.state {
                 AIState::Chasing => {
@@ -89,17 +138,26 @@ impl HordeAI {
                     };
 
                     // Blend flow field with direct pursuit — reduces zig-zag at cell boundaries
-                    let move_dir = (flow_3d * (1.0 - DIRECT_PURSUIT_BLEND) + direct_xz * DIRECT_PURSUIT_BLEND)
+                    let pursue = (flow_3d * (1.0 - DIRECT_PURSUIT_BLEND) + direct_xz * DIRECT_PURSUIT_BLEND)
                         .normalize_or_zero();
 
-                    let target_vel = move_dir * bug.move_speed;
+                    // Spitters kite: back off when rushed, stand and spit inside their band
+                    let (move_dir, pace) = match bug.bug_type.behavior().kite_range() {
+                        Some((near, _)) if distance < near => (-direct_xz, 0.8),
+                        Some((_, far)) if distance < far => (direct_xz, 0.0),
+                        _ => (pursue, 1.0),
+                    };
+
+                    // Staggered (recent hit) or legless bugs move at reduced speed
+                    let speed = bug.move_speed * pace * physics_bug.map_or(1.0, PhysicsBug::speed_scale);
+                    let target_vel = move_dir * speed;
 
                     // Smooth velocity for natural, fluid movement (no instant direction snaps)
                     let current_speed = velocity.linear.length();
                     velocity.linear = if current_speed > 0.01 {
                         let smoothed = velocity.linear * (1.0 - VELOCITY_SMOOTHING)
                             + target_vel * VELOCITY_SMOOTHING;
-                        smoothed.normalize_or_zero() * bug.move_speed
+                        smoothed.normalize_or_zero() * speed
                     } else {
                         target_vel
                     };
@@ -107,9 +165,9 @@ impl HordeAI {
                     // Update position
                     transform.position += velocity.linear * dt;
 
-                    // Face movement direction
-                    if velocity.linear.length_squared() > 0.01 {
-                        let forward = velocity.linear.normalize();
+                    // Face movement direction (a backpedalling or holding Spitter keeps facing the target)
+                    let forward = if pace < 1.0 { direct_xz } else { velocity.linear.normalize_or_zero() };
+                    if forward.length_squared() > 0.01 {
                         transform.rotation = glam::Quat::from_rotation_arc(Vec3::Z, forward);
                     }
                 }
@@ -142,13 +200,14 @@ impl HordeAI {
             // Y position is managed by the terrain snap in update_gameplay
         }
 
-        // Skinnies (Heinlein): same flow-field chase/attack
-        for (_, (transform, velocity, skinny, ai)) in
-            world.query_mut::<(&mut Transform, &mut Velocity, &Skinny, &mut AIComponent)>()
+        // Skinnies (Heinlein): ranged infantry — hold a band around the target, flank for a shot
+        for (_, (transform, velocity, skinny, ai, physics_bug)) in
+            world.query_mut::<(&mut Transform, &mut Velocity, &Skinny, &mut AIComponent, Option<&PhysicsBug>)>()
         {
             let to_target = self.target_position - transform.position;
             let distance = to_target.length();
 
+            // Attacking = in range with a clear shot; the fire step in GameState pulls the trigger
             match ai.state {
                 AIState::Idle => {
                     if distance < ai.aggro_range {
@@ -156,14 +215,14 @@ impl HordeAI {
                     }
                 }
                 AIState::Chasing => {
-                    if distance < ai.attack_range {
+                    if distance < ai.attack_range && skinny.has_line_of_sight {
                         ai.state = AIState::Attacking;
                     } else if distance > ai.aggro_range * 1.5 {
                         ai.state = AIState::Idle;
                     }
                 }
                 AIState::Attacking => {
-                    if distance > ai.attack_range * 1.5 {
+                    if distance > ai.attack_range * 1.2 || !skinny.has_line_of_sight {
                         ai.state = AIState::Chasing;
                     }
                     ai.update_cooldown(dt);
@@ -171,89 +230,240 @@ impl HordeAI {
                 AIState::Fleeing | AIState::Dead => {}
             }
 
-            match ai.state {
-                AIState::Chasing => {
-                    let flow_dir = self.flow_field.sample_smooth(transform.position);
-                    let direct_xz = Vec3::new(to_target.x, 0.0, to_target.z).normalize_or_zero();
+            let direct_xz = Vec3::new(to_target.x, 0.0, to_target.z).normalize_or_zero();
+            let speed = skinny.move_speed * physics_bug.map_or(1.0, PhysicsBug::speed_scale);
+            let target_vel = match ai.state {
+                AIState::Chasing | AIState::Attacking => match skinny.ranged_intent(distance) {
+                    RangedIntent::Approach => {
+                        let flow_dir = self.flow_field.sample_smooth(transform.position);
+                        let flow_3d = if flow_dir.length_squared() > 0.01 {
+                            Vec3::new(flow_dir.x, 0.0, flow_dir.y)
+                        } else {
+                            direct_xz
+                        };
+                        (flow_3d * (1.0 - DIRECT_PURSUIT_BLEND) + direct_xz * DIRECT_PURSUIT_BLEND)
+                            .normalize_or_zero() * speed
+                    }
+                    // Sidestep around whatever blocks the shot, drifting slightly inward
+                    RangedIntent::Flank => {
+                        let side = Vec3::new(-direct_xz.z, 0.0, direct_xz.x) * skinny.strafe_sign;
+                        (side + direct_xz * 0.25).normalize_or_zero() * speed
+                    }
+                    RangedIntent::Retreat => -direct_xz * speed * SKINNY_RETREAT_SPEED,
+                    RangedIntent::Hold => Vec3::ZERO,
+                },
+                AIState::Fleeing => -direct_xz * skinny.move_speed * 1.5,
+                AIState::Idle => velocity.linear * 0.9,
+                AIState::Dead => Vec3::ZERO,
+            };
+
+            velocity.linear = velocity.linear * (1.0 - VELOCITY_SMOOTHING) + target_vel * VELOCITY_SMOOTHING;
+            if !matches!(ai.state, AIState::Idle | AIState::Dead) {
+                transform.position += velocity.linear * dt;
+            }
 
-                    let flow_3d = if flow_dir.length_squared() > 0.01 {
-                        Vec3::new(flow_dir.x, 0.0, flow_dir.y)
-                    } else {
-                        direct_xz
-                    };
+            // Always face the target once engaged (backpedalling and strafing keep the gun on it)
+            let facing = if matches!(ai.state, AIState::Chasing | AIState::Attacking) {
+                direct_xz
+            } else {
+                Vec3::new(velocity.linear.x, 0.0, velocity.linear.z).normalize_or_zero()
+            };
+            if facing.length_squared() > 0.01 {
+                transform.rotation = glam::Quat::from_rotation_arc(Vec3::Z, facing);
+            }
+        }
 
-                    let move_dir = (flow_3d * (1.0 - DIRECT_PURSUIT_BLEND) + direct_xz * DIRECT_PURSUIT_BLEND)
-                        .normalize_or_zero();
+        strikes
+    }
 
-                    let target_vel = move_dir * skinny.move_speed;
+    /// Add a static obstacle footprint (XZ half-extents around `center`), e.g. a building.
+    pub fn add_static_obstacle(&mut self, center: Vec3, half_extents: Vec2) {
+        let id = STATIC_OBSTACLE_ID_BIT | self.static_obstacles.len() as u64;
+        let center = Vec2::new(center.x, center.z);
+        let half = half_extents + Vec2::splat(OBSTACLE_MARGIN);
+        self.flow_field.add_obstacle(id, center - half, center + half);
+        self.static_obstacles.push(id);
+    }
 
-                    let current_speed = velocity.linear.length();
-                    velocity.linear = if current_speed > 0.01 {
-                        let smoothed = velocity.linear * (1.0 - VELOCITY_SMOOTHING)
-                            + target_vel * VELOCITY_SMOOTHING;
-                        smoothed.normalize_or_zero() * skinny.move_speed
-                    } else {
-                        target_vel
-                    };
+    /// Remove all static obstacles (e.g. leaving Earth) and reroute.
+    pub fn clear_static_obstacles(&mut self) {
+        for id in self.static_obstacles.drain(..) {
+            self.flow_field.remove_obstacle(id);
+        }
+        self.flow_field.recompute();
+    }
 
-                    transform.position += velocity.linear * dt;
-                    if velocity.linear.length_squared() > 0.01 {
-                        let forward = velocity.linear.normalize();
-                        transform.rotation = glam::Quat::from_rotation_arc(Vec3::Z, forward);
-                    }
-                }
-                AIState::Attacking => {
-                    velocity.linear = Vec3::ZERO;
-                    let look_dir = to_target.normalize_or_zero();
-                    if look_dir.length_squared() > 0.01 {
-                        transform.rotation = glam::Quat::from_rotation_arc(
-                            Vec3::Z,
-                            Vec3::new(look_dir.x, 0.0, look_dir.z).normalize_or_zero(),
-                        );
-                    }
+    /// A destructible obstacle was destroyed: unblock its cells and reroute now rather than
+    /// waiting for the next flow field refresh.
+    pub fn remove_entity_obstacle(&mut self, entity: Entity) {
+        let id = entity.to_bits().get();
+        if self.entity_obstacles.remove(&id) && self.flow_field.remove_obstacle(id) {
+            self.flow_field.recompute();
+        }
+    }
+
+    /// Clear obstacles (for when destruction clears debris).
+    pub fn clear_obstacles(&mut self) {
+        self.flow_field.clear();
+        self.entity_obstacles.clear();
+        self.static_obstacles.clear();
+    }
+
+    /// Match the flow field's entity obstacles to the large destructibles currently in the world
+    /// (new spawns get stamped, despawned ones get unblocked).
+    fn sync_obstacles(&mut self, world: &World) {
+        let mut current: Vec<(u64, Vec2, Vec2)> = Vec::new();
+        for (entity, (transform, _destructible)) in world
+            .query::<(&Transform, &Destructible)>()
+            .with::<&DestructiblePhysics>()
+            .iter()
+        {
+            // World-space XZ bounds of the (possibly rotated) collider box
+            let rot = Mat3::from_quat(transform.rotation);
+            let half = transform.scale * 0.5;
+            let extent = rot.x_axis.abs() * half.x + rot.y_axis.abs() * half.y + rot.z_axis.abs() * half.z;
+            if extent.x.max(extent.z) < MIN_OBSTACLE_HALF_EXTENT {
+                continue;
+            }
+            let center = Vec2::new(transform.position.x, transform.position.z);
+            let half_xz = Vec2::new(extent.x, extent.z) + Vec2::splat(OBSTACLE_MARGIN);
+            current.push((entity.to_bits().get(), center - half_xz, center + half_xz));
+        }
+
+        let live: HashSet<u64> = current.iter().map(|(id, _, _)| *id).collect();
+        let gone: Vec<u64> = self.entity_obstacles.difference(&live).copied().collect();
+        for id in gone {
+            self.flow_field.remove_obstacle(id);
+            self.entity_obstacles.remove(&id);
+        }
+        for (id, min, max) in current {
+            if self.entity_obstacles.insert(id) {
+                self.flow_field.add_obstacle(id, min, max);
+            }
+        }
+    }
+}
+
+/// Step a bug's signature move toward `target`. Returns whether the move owns the bug's movement
+/// this frame (telegraphing, dashing, airborne or recovering) and any hit it landed.
+fn run_behavior(
+    bug: &mut Bug,
+    transform: &mut Transform,
+    velocity: &mut Velocity,
+    target: Vec3,
+    engaged: bool,
+    speed_scale: f32,
+    dt: f32,
+) -> (bool, Option<BugStrike>) {
+    let behavior = bug.bug_type.behavior();
+    if behavior == BugBehavior::Melee {
+        return (false, None);
+    }
+    let flat = Vec3::new(target.x - transform.position.x, 0.0, target.z - transform.position.z);
+    let distance = flat.length();
+    let toward = flat.normalize_or_zero();
+    let face = |transform: &mut Transform, dir: Vec3| {
+        if dir.length_squared() > 0.01 {
+            transform.rotation = glam::Quat::from_rotation_arc(Vec3::Z, dir);
+        }
+    };
+
+    match bug.maneuver {
+        Maneuver::Ready { cooldown } => {
+            let cooldown = cooldown - dt;
+            let (near, far) = behavior.trigger_range();
+            bug.maneuver = if engaged && cooldown <= 0.0 && (near..=far).contains(&distance) {
+                Maneuver::WindUp { timer: behavior.windup(), direction: toward }
+            } else {
+                Maneuver::Ready { cooldown }
+            };
+            (false, None)
+        }
+        // Telegraph: stand still and keep tracking the target
+        Maneuver::WindUp { timer, .. } => {
+            velocity.linear = Vec3::ZERO;
+            face(transform, toward);
+            let timer = timer - dt;
+            if timer > 0.0 {
+                bug.maneuver = Maneuver::WindUp { timer, direction: toward };
+                return (true, None);
+            }
+            match behavior {
+                BugBehavior::Spitter => {
+                    bug.maneuver = Maneuver::Ready { cooldown: behavior.cooldown() };
+                    let from = transform.position + Vec3::Y * transform.scale.y * 0.6;
+                    let flight_time = (distance / SPIT_SPEED).clamp(0.5, 1.8);
+                    let aim = target - Vec3::Y * 0.4;
+                    let velocity = lob_velocity(from, aim, flight_time, ACID_GRAVITY);
+                    (true, Some(BugStrike::Spit { from, velocity, damage: bug.attack_damage }))
                 }
-                AIState::Idle => {
-                    velocity.linear *= 0.9;
+                BugBehavior::Charger => {
+                    let speed = bug.move_speed * CHARGE_SPEED_MULT * speed_scale;
+                    bug.maneuver = Maneuver::Active { elapsed: 0.0, direction: toward, speed };
+                    (true, None)
                 }
-                AIState::Fleeing => {
-                    let flee_dir = -to_target.normalize_or_zero();
-                    velocity.linear = Vec3::new(flee_dir.x, 0.0, flee_dir.z) * skinny.move_speed * 1.5;
-                    transform.position += velocity.linear * dt;
+                // Leap to where the target stands now; a trooper who moves off the spot dodges it
+                BugBehavior::Hopper => {
+                    let speed = distance / LEAP_DURATION;
+                    bug.maneuver = Maneuver::Active { elapsed: 0.0, direction: toward, speed };
+                    (true, None)
                 }
-                AIState::Dead => {
-                    velocity.linear = Vec3::ZERO;
+                BugBehavior::Tanker => {
+                    bug.maneuver = Maneuver::Recover { timer: behavior.recovery() };
+                    let strike = BugStrike::Slam { center: transform.position, damage: bug.attack_damage * 1.5 };
+                    (true, Some(strike))
                 }
+                BugBehavior::Melee => (false, None),
             }
         }
-    }
-
-    /// Add obstacle to flow field (for destruction/terrain).
-    pub fn add_obstacle(&mut self, position: Vec3, radius: f32) {
-        let grid_pos = self.flow_field.world_to_grid(position);
-        let grid_radius = (radius / self.flow_field.cell_size).ceil() as i32;
-
-        for dy in -grid_radius..=grid_radius {
-            for dx in -grid_radius..=grid_radius {
-                let x = grid_pos.x + dx;
-                let y = grid_pos.y + dy;
-                if x >= 0 && y >= 0 {
-                    self.flow_field.set_blocked(x as usize, y as usize);
+        Maneuver::Active { elapsed, direction, speed } => {
+            let elapsed = elapsed + dt;
+            velocity.linear = direction * speed;
+            transform.position += velocity.linear * dt;
+            face(transform, direction);
+            let distance = Vec2::new(target.x - transform.position.x, target.z - transform.position.z).length();
+            let recover = Maneuver::Recover { timer: behavior.recovery() };
+            match behavior {
+                BugBehavior::Charger if distance < CHARGE_HIT_RADIUS => {
+                    bug.maneuver = recover;
+                    (true, Some(BugStrike::Charge { from: transform.position, direction, damage: bug.attack_damage * 1.4 }))
+                }
+                BugBehavior::Charger if elapsed < CHARGE_DURATION => {
+                    bug.maneuver = Maneuver::Active { elapsed, direction, speed };
+                    (true, None)
+                }
+                BugBehavior::Hopper if elapsed < LEAP_DURATION => {
+                    bug.maneuver = Maneuver::Active { elapsed, direction, speed };
+                    (true, None)
+                }
+                BugBehavior::Hopper if distance < POUNCE_RADIUS => {
+                    bug.maneuver = recover;
+                    (true, Some(BugStrike::Pounce { from: transform.position, damage: bug.attack_damage * 1.5 }))
+                }
+                _ => {
+                    bug.maneuver = recover;
+                    (true, None)
                 }
             }
         }
-    }
-
-    /// Clear obstacles (for when destruction clears debris).
-    pub fn clear_obstacles(&mut self) {
-        self.flow_field.clear();
+        // Skid to a stop; a Charger that missed overshoots with its rear exposed
+        Maneuver::Recover { timer } => {
+            velocity.linear *= 0.9;
+            transform.position += velocity.linear * dt;
+            let timer = timer - dt;
+            bug.maneuver = if timer > 0.0 {
+                Maneuver::Recover { timer }
+            } else {
+                Maneuver::Ready { cooldown: behavior.cooldown() }
+            };
+            (true, None)
+        }
     }
 }
 
 /// Separation behavior to prevent bugs from overlapping.
 /// Uses a spatial grid for O(n) average performance instead of O(n²).
 pub fn apply_separation(world: &mut World, separation_radius: f32, separation_force: f32) {
-    use std::collections::HashMap;
-    use crate::bug_entity::PhysicsBug;
 
     // Collect living bug positions (skip ragdolling/dead bugs)
     let positions: Vec<(hecs::Entity, Vec3)> = world
@@ -266,12 +476,30 @@ pub fn apply_separation(world: &mut World, separation_radius: f32, separation_fo
 
     if positions.len() < 2 { return; }
 
+    let points: Vec<Vec3> = positions.iter().map(|(_, p)| *p).collect();
+    let forces = separation_forces(&points, separation_radius, separation_force);
+
+    // Apply forces to velocities
+    for (idx, (entity, _)) in positions.iter().enumerate() {
+        if forces[idx].length_squared() > 0.01 {
+            if let Ok(mut velocity) = world.get::<&mut Velocity>(*entity) {
+                velocity.linear += forces[idx];
+            }
+        }
+    }
+}
+
+/// XZ separation push for each point away from its neighbours within `separation_radius`.
+/// Shared by the horde and the squad; returns one force per input point (zero if uncrowded).
+pub fn separation_forces(positions: &[Vec3], separation_radius: f32, separation_force: f32) -> Vec<Vec3> {
+    use std::collections::HashMap;
+
     // Build spatial grid (cell size = separation_radius)
     let cell_size = separation_radius;
     let inv_cell = 1.0 / cell_size;
     let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
 
-    for (idx, (_, pos)) in positions.iter().enumerate() {
+    for (idx, pos) in positions.iter().enumerate() {
         let cx = (pos.x * inv_cell).floor() as i32;
         let cz = (pos.z * inv_cell).floor() as i32;
         grid.entry((cx, cz)).or_default().push(idx);
@@ -282,7 +510,7 @@ pub fn apply_separation(world: &mut World, separation_radius: f32, separation_fo
     // Compute separation forces using grid neighbors
     let mut forces: Vec<Vec3> = vec![Vec3::ZERO; positions.len()];
 
-    for (idx, (_, pos)) in positions.iter().enumerate() {
+    for (idx, pos) in positions.iter().enumerate() {
         let cx = (pos.x * inv_cell).floor() as i32;
         let cz = (pos.z * inv_cell).floor() as i32;
 
@@ -295,7 +523,7 @@ pub fn apply_separation(world: &mut World, separation_radius: f32, separation_fo
                 if let Some(cell) = grid.get(&(cx + dx, cz + dz)) {
                     for &other_idx in cell {
                         if other_idx == idx { continue; }
-                        let diff = *pos - positions[other_idx].1;
+                        let diff = *pos - positions[other_idx];
                         let dist_sq = diff.x * diff.x + diff.z * diff.z; // XZ plane only
                         if dist_sq < sep_sq && dist_sq > 0.001 {
                             let dist = dist_sq.sqrt();
@@ -314,12 +542,62 @@ pub fn apply_separation(world: &mut World, separation_radius: f32, separation_fo
         }
     }
 
-    // Apply forces to velocities
-    for (idx, (entity, _)) in positions.iter().enumerate() {
-        if forces[idx].length_squared() > 0.01 {
-            if let Ok(mut velocity) = world.get::<&mut Velocity>(*entity) {
-                velocity.linear += forces[idx];
+    forces
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bug::{BugType, LEAP_HEIGHT};
+
+    /// Step `bug` toward a stationary target at `target` until it lands a hit (or gives up).
+    fn run_until_strike(bug_type: BugType, start: Vec3, target: Vec3) -> (Bug, Option<BugStrike>) {
+        let mut bug = Bug::new(bug_type);
+        bug.maneuver = Maneuver::Ready { cooldown: 0.0 };
+        let mut transform = Transform { position: start, ..Default::default() };
+        let mut velocity = Velocity::default();
+        for _ in 0..300 {
+            let (_, strike) = run_behavior(&mut bug, &mut transform, &mut velocity, target, true, 1.0, 1.0 / 60.0);
+            if strike.is_some() {
+                return (bug, strike);
             }
         }
+        (bug, None)
+    }
+
+    #[test]
+    fn charger_winds_up_then_dashes_into_the_target() {
+        let (bug, strike) = run_until_strike(BugType::Charger, Vec3::ZERO, Vec3::new(0.0, 0.0, 15.0));
+        match strike {
+            Some(BugStrike::Charge { direction, .. }) => assert!(direction.z > 0.99),
+            other => panic!("expected a charge hit, got {other:?}"),
+        }
+        assert!(matches!(bug.maneuver, Maneuver::Recover { .. }));
+    }
+
+    #[test]
+    fn hopper_pounces_tanker_slams_spitter_lobs() {
+        let (_, strike) = run_until_strike(BugType::Hopper, Vec3::ZERO, Vec3::new(8.0, 0.0, 0.0));
+        assert!(matches!(strike, Some(BugStrike::Pounce { .. })), "{strike:?}");
+
+        let (_, strike) = run_until_strike(BugType::Tanker, Vec3::ZERO, Vec3::new(0.0, 0.0, 3.0));
+        assert!(matches!(strike, Some(BugStrike::Slam { .. })), "{strike:?}");
+
+        let (_, strike) = run_until_strike(BugType::Spitter, Vec3::ZERO, Vec3::new(0.0, 0.0, 18.0));
+        assert!(matches!(strike, Some(BugStrike::Spit { velocity, .. }) if velocity.y > 0.0), "{strike:?}");
+
+        // Warriors have no signature move and never hand movement over
+        let (_, strike) = run_until_strike(BugType::Warrior, Vec3::ZERO, Vec3::new(0.0, 0.0, 1.0));
+        assert!(strike.is_none());
+    }
+
+    #[test]
+    fn a_leaping_hopper_arcs_above_its_stance() {
+        let mut bug = Bug::new(BugType::Hopper);
+        bug.maneuver = Maneuver::Active { elapsed: LEAP_DURATION * 0.5, direction: Vec3::X, speed: 10.0 };
+        assert!((bug.lift() - LEAP_HEIGHT).abs() < 1e-4);
+        bug.maneuver = Maneuver::Ready { cooldown: 0.0 };
+        assert_eq!(bug.lift(), 0.0);
     }
 }
@@ -1,10 +1,19 @@
 //! HUD (Heads-Up Display) system for FPS gameplay
 //! Renders health, ammo, crosshair, damage indicators, etc.
 
+use crate::boss::{QueenBoss, QueenPhase};
+use crate::bug::Bug;
+use crate::destruction::BugHole;
 use crate::fps::{CombatSystem, FPSPlayer, MissionState};
+use crate::skinny::Skinny;
 use crate::spawner::ThreatLevel;
+use crate::squad::SquadMate;
+use crate::stratagem::{Cooldown, Stratagem};
 use crate::weapons::WeaponType;
-use glam::Vec3;
+use engine_core::{Health, Transform};
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use hecs::World;
+use std::collections::HashMap;
 
 /// HUD configuration
 #[derive(Debug, Clone)]
@@ -21,6 +30,8 @@ pub struct HUDConfig {
     pub crosshair_color: [f32; 4],
     pub crosshair_size: f32,
     pub hud_scale: f32,
+    /// World distance (meters) shown from the minimap center to its rim.
+    pub minimap_radius: f32,
 }
 
 impl Default for HUDConfig {
@@ -38,6 +49,7 @@ impl Default for HUDConfig {
             crosshair_color: [1.0, 1.0, 1.0, 0.8],
             crosshair_size: 1.0,
             hud_scale: 1.0,
+            minimap_radius: 80.0,
         }
     }
 }
@@ -110,7 +122,7 @@ pub struct HUDData {
 #[derive(Debug, Clone)]
 pub struct HitMarkerData {
     pub is_kill: bool,
-    pub is_headshot: bool,
+    pub is_weakpoint: bool,
     pub alpha: f32,
 }
 
@@ -132,18 +144,299 @@ pub struct KillFeedData {
     pub alpha: f32,
 }
 
+/// What a minimap blip marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimapBlipKind {
+    /// Live bug or Skinny.
+    Enemy,
+    Squad,
+    /// Extraction LZ (pinned to the rim when out of range).
+    Extraction,
+    /// Bug hole (pinned to the rim when out of range).
+    BugHole,
+}
+
+/// A blip on the player-centered minimap.
+#[derive(Debug, Clone, Copy)]
+pub struct MinimapBlip {
+    pub kind: MinimapBlipKind,
+    /// Position in the unit disc: x right, y down, player facing is up (-y).
+    pub x: f32,
+    pub y: f32,
+    /// Fades with distance from the player.
+    pub alpha: f32,
+}
+
+/// Place `target` on a unit-disc minimap centered on `player_pos` and rotated so `forward` points up.
+/// Returns `None` beyond `radius` unless `pin_to_rim`, which clamps it onto the edge instead.
+pub fn minimap_project(
+    player_pos: Vec3,
+    forward: Vec3,
+    target: Vec3,
+    radius: f32,
+    pin_to_rim: bool,
+) -> Option<(f32, f32, f32)> {
+    let fwd = glam::Vec2::new(forward.x, forward.z).try_normalize().unwrap_or(glam::Vec2::NEG_Y);
+    let right = glam::Vec2::new(-fwd.y, fwd.x);
+    let rel = glam::Vec2::new(target.x - player_pos.x, target.z - player_pos.z) / radius.max(1.0);
+    let mut p = glam::Vec2::new(rel.dot(right), -rel.dot(fwd));
+    let dist = p.length();
+    if dist > 1.0 {
+        if !pin_to_rim {
+            return None;
+        }
+        p /= dist;
+    }
+    let alpha = 1.0 - dist.min(1.0) * 0.65;
+    Some((p.x, p.y, alpha))
+}
+
+/// Kind of world-space objective marker, shown on the compass and on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaypointIcon {
+    /// Extraction landing zone.
+    Lz,
+    /// The extraction dropship itself while inbound.
+    Dropship,
+    /// Mission objective chosen at the war table (e.g. the base to defend).
+    Objective,
+    /// Bug hive to destroy (Hive Destruction).
+    Hive,
+    /// Bug picked up by a scan ping.
+    Scanned,
+    /// Citizen waiting to be escorted to the LZ (Rescue).
+    Citizen,
+    /// The Hive Queen (Hive Destruction finale).
+    Queen,
+    /// Squad mate down and bleeding out.
+    Downed,
+}
+
+impl WaypointIcon {
+    pub fn label(self) -> &'static str {
+        match self {
+            WaypointIcon::Lz => "LZ",
+            WaypointIcon::Dropship => "DROPSHIP",
+            WaypointIcon::Objective => "OBJ",
+            WaypointIcon::Hive => "HIVE",
+            WaypointIcon::Scanned => "BUG",
+            WaypointIcon::Citizen => "CIV",
+            WaypointIcon::Queen => "QUEEN",
+            WaypointIcon::Downed => "DOWN",
+        }
+    }
+
+    pub fn color(self) -> [f32; 4] {
+        match self {
+            WaypointIcon::Lz => [0.3, 1.0, 0.4, 1.0],
+            WaypointIcon::Dropship => [0.5, 0.8, 1.0, 1.0],
+            WaypointIcon::Objective => [1.0, 0.85, 0.2, 1.0],
+            WaypointIcon::Hive => [1.0, 0.45, 0.1, 1.0],
+            WaypointIcon::Scanned => [1.0, 0.25, 0.25, 0.9],
+            WaypointIcon::Citizen => [0.4, 0.9, 1.0, 1.0],
+            WaypointIcon::Queen => [0.9, 0.3, 1.0, 1.0],
+            WaypointIcon::Downed => [1.0, 0.35, 0.25, 1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Waypoint {
+    pub position: Vec3,
+    pub icon: WaypointIcon,
+}
+
+/// A waypoint projected for this frame.
+#[derive(Debug, Clone, Copy)]
+pub struct WaypointMarker {
+    pub icon: WaypointIcon,
+    /// Screen position in pixels; clamped inside the screen edges when the waypoint is off-screen.
+    pub screen_x: f32,
+    pub screen_y: f32,
+    /// False when clamped to an edge (off-screen or behind the camera).
+    pub on_screen: bool,
+    /// Horizontal distance from the camera in meters.
+    pub distance: f32,
+    /// Heading to the waypoint in radians (see `compass_heading`).
+    pub bearing: f32,
+}
+
+/// Compass heading of a horizontal direction in radians: 0 = north (-Z), PI/2 = east (+X).
+pub fn compass_heading(dir: Vec3) -> f32 {
+    dir.x.atan2(-dir.z).rem_euclid(std::f32::consts::TAU)
+}
+
+/// Where `bearing` falls on a compass strip centered on `heading` that spans +/- `half_span` radians:
+/// -1 = left end, 1 = right end. `None` when outside the strip.
+pub fn compass_offset(heading: f32, bearing: f32, half_span: f32) -> Option<f32> {
+    use std::f32::consts::{PI, TAU};
+    let delta = (bearing - heading + PI).rem_euclid(TAU) - PI;
+    (delta.abs() <= half_span).then(|| delta / half_span)
+}
+
+/// Project a world point to screen pixels. Points off-screen or behind the camera are pushed to
+/// the screen edge (inset by `margin` pixels) in the direction they lie; the flag is false then.
+pub fn project_to_screen_edge(view_proj: Mat4, world_pos: Vec3, sw: f32, sh: f32, margin: f32) -> (Vec2, bool) {
+    let clip = view_proj * Vec4::new(world_pos.x, world_pos.y, world_pos.z, 1.0);
+    let behind = clip.w <= 0.01;
+    let mut ndc = Vec2::new(clip.x, clip.y) / clip.w.abs().max(0.01);
+    let limit = Vec2::new(1.0 - 2.0 * margin / sw, 1.0 - 2.0 * margin / sh).max(Vec2::ZERO);
+    let over = (ndc.x.abs() / limit.x).max(ndc.y.abs() / limit.y);
+    let on_screen = !behind && over <= 1.0;
+    if !on_screen {
+        if behind && ndc.length_squared() < 1e-6 {
+            ndc = Vec2::NEG_Y; // dead astern: show at the bottom edge
+        }
+        let over = (ndc.x.abs() / limit.x).max(ndc.y.abs() / limit.y);
+        ndc /= over;
+    }
+    (Vec2::new((ndc.x + 1.0) * 0.5 * sw, (1.0 - ndc.y) * 0.5 * sh), on_screen)
+}
+
+/// One stratagem in the HUD tray.
+#[derive(Debug, Clone, Copy)]
+pub struct TraySlot {
+    pub stratagem: Stratagem,
+    /// Share of the cooldown still to go (1 = just used, 0 = recharged); drawn as the slot fill.
+    pub cooldown_fraction: f32,
+    /// Seconds until recharged.
+    pub remaining: f32,
+    /// Off cooldown and callable right now: highlight the slot.
+    pub ready: bool,
+}
+
+/// Boss health bar across the top of the screen while a boss is alive.
+#[derive(Debug, Clone, Copy)]
+pub struct BossBar {
+    pub name: &'static str,
+    /// Fight phase tag (ARMORED / EXPOSED / ENRAGED).
+    pub phase: &'static str,
+    pub health_fraction: f32,
+    pub color: [f32; 4],
+}
+
 /// HUD system that generates display data
 pub struct HUDSystem {
     pub config: HUDConfig,
+    /// Objective markers for this frame (rebuilt by the game each update).
+    waypoints: Vec<Waypoint>,
 }
 
 impl HUDSystem {
     pub fn new() -> Self {
         Self {
             config: HUDConfig::default(),
+            waypoints: Vec::new(),
         }
     }
 
+    pub fn clear_waypoints(&mut self) {
+        self.waypoints.clear();
+    }
+
+    /// Stratagem tray, one slot per stratagem in `Stratagem::ALL` order. `available` says whether a
+    /// stratagem could be called apart from its cooldown (e.g. fleet has room).
+    pub fn stratagem_tray(
+        &self,
+        cooldowns: &HashMap<Stratagem, Cooldown>,
+        available: impl Fn(Stratagem) -> bool,
+    ) -> Vec<TraySlot> {
+        Stratagem::ALL
+            .into_iter()
+            .map(|stratagem| {
+                let cooldown = cooldowns.get(&stratagem).copied().unwrap_or_default();
+                TraySlot {
+                    stratagem,
+                    cooldown_fraction: cooldown.cooldown_fraction(),
+                    remaining: cooldown.remaining(),
+                    ready: cooldown.is_ready() && available(stratagem),
+                }
+            })
+            .collect()
+    }
+
+    /// Health bar for the living boss, if there is one.
+    pub fn boss_bar(&self, world: &World) -> Option<BossBar> {
+        world
+            .query::<(&Health, &QueenBoss)>()
+            .iter()
+            .find(|(_, (health, _))| !health.is_dead())
+            .map(|(_, (health, queen))| BossBar {
+                name: "HIVE QUEEN",
+                phase: queen.label(),
+                health_fraction: (health.current / health.max).clamp(0.0, 1.0),
+                color: match queen.phase {
+                    QueenPhase::Armored => [0.55, 0.5, 0.6, 1.0],
+                    QueenPhase::Exposed => [0.7, 1.0, 0.2, 1.0],
+                    QueenPhase::Enraged => [1.0, 0.2, 0.15, 1.0],
+                },
+            })
+    }
+
+    /// Mark a world position on the compass and screen.
+    pub fn add_waypoint(&mut self, world_pos: Vec3, icon: WaypointIcon) {
+        self.waypoints.push(Waypoint { position: world_pos, icon });
+    }
+
+    /// Project the current waypoints with the camera's view-projection.
+    pub fn project_waypoints(&self, view_proj: Mat4, cam_pos: Vec3, sw: f32, sh: f32) -> Vec<WaypointMarker> {
+        self.waypoints
+            .iter()
+            .map(|wp| {
+                let (screen, on_screen) = project_to_screen_edge(view_proj, wp.position, sw, sh, 40.0);
+                let to = wp.position - cam_pos;
+                WaypointMarker {
+                    icon: wp.icon,
+                    screen_x: screen.x,
+                    screen_y: screen.y,
+                    on_screen,
+                    distance: Vec2::new(to.x, to.z).length(),
+                    bearing: compass_heading(to),
+                }
+            })
+            .collect()
+    }
+
+    /// Minimap blips around the player: live enemies and squadmates within `minimap_radius`, plus
+    /// bug holes and the extraction LZ (kept on the rim when farther away).
+    pub fn minimap_blips(
+        &self,
+        world: &World,
+        player_pos: Vec3,
+        forward: Vec3,
+        extraction_lz: Option<Vec3>,
+    ) -> Vec<MinimapBlip> {
+        let radius = self.config.minimap_radius;
+        let mut blips = Vec::new();
+        let mut add = |kind, pos: Vec3, pin_to_rim| {
+            if let Some((x, y, alpha)) = minimap_project(player_pos, forward, pos, radius, pin_to_rim) {
+                blips.push(MinimapBlip { kind, x, y, alpha });
+            }
+        };
+        for (_, (transform, _)) in world.query::<(&Transform, &BugHole)>().iter() {
+            add(MinimapBlipKind::BugHole, transform.position, true);
+        }
+        for (_, (transform, _, health)) in world.query::<(&Transform, &Bug, &Health)>().iter() {
+            if !health.is_dead() {
+                add(MinimapBlipKind::Enemy, transform.position, false);
+            }
+        }
+        for (_, (transform, _, health)) in world.query::<(&Transform, &Skinny, &Health)>().iter() {
+            if !health.is_dead() {
+                add(MinimapBlipKind::Enemy, transform.position, false);
+            }
+        }
+        for (_, (transform, _, health)) in world.query::<(&Transform, &SquadMate, &Health)>().iter() {
+            if !health.is_dead() {
+                add(MinimapBlipKind::Squad, transform.position, false);
+            }
+        }
+        if let Some(lz) = extraction_lz {
+            add(MinimapBlipKind::Extraction, lz, true);
+        }
+        blips
+    }
+
     /// Generate HUD data from game state
     pub fn generate_hud_data(
         &self,
@@ -205,7 +498,7 @@ impl HUDSystem {
         // Hit marker
         let hit_marker = combat.latest_hit_marker().map(|hm| HitMarkerData {
             is_kill: hm.is_kill,
-            is_headshot: hm.is_headshot,
+            is_weakpoint: hm.is_weakpoint,
             alpha: hm.lifetime / 0.3,
         });
 
@@ -240,8 +533,8 @@ impl HUDSystem {
             max_health: player.max_health,
             armor: player.armor,
             max_armor: player.max_armor,
-            stamina: player.stamina,
-            max_stamina: player.max_stamina,
+            stamina: player.stamina.current,
+            max_stamina: player.stamina.max,
 
             weapon_name,
             weapon_icon,
@@ -250,7 +543,7 @@ impl HUDSystem {
             is_reloading,
             reload_progress,
 
-            ability_name: format!("{:?}", player.ability),
+            ability_name: player.ability.name().to_string(),
             ability_ready: player.can_use_ability(),
             ability_cooldown_percent: player.ability_ready_percent(),
 
@@ -316,9 +609,9 @@ impl HUDSystem {
             // Hit marker
             if let Some(hm) = &data.hit_marker {
                 let marker = if hm.is_kill {
-                    if hm.is_headshot { ">>> HEADSHOT KILL <<<" } else { ">>> KILL <<<" }
-                } else if hm.is_headshot {
-                    ">> HEADSHOT <<"
+                    if hm.is_weakpoint { ">>> WEAKPOINT KILL <<<" } else { ">>> KILL <<<" }
+                } else if hm.is_weakpoint {
+                    ">> WEAKPOINT <<"
                 } else {
                     "× HIT ×"
                 };
@@ -484,3 +777,102 @@ impl CrosshairRenderData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimap_rotates_with_facing_and_fades_with_distance() {
+        let player = Vec3::new(10.0, 0.0, 10.0);
+        // Facing -Z: a target straight ahead is at the top of the disc, one to the +X side is right.
+        let ahead = minimap_project(player, Vec3::NEG_Z, player + Vec3::new(0.0, 5.0, -40.0), 80.0, false).unwrap();
+        assert!(ahead.0.abs() < 1e-5 && (ahead.1 + 0.5).abs() < 1e-5);
+        let right = minimap_project(player, Vec3::NEG_Z, player + Vec3::new(40.0, 0.0, 0.0), 80.0, false).unwrap();
+        assert!((right.0 - 0.5).abs() < 1e-5 && right.1.abs() < 1e-5);
+
+        // Turning to face +X brings that target to the top.
+        let turned = minimap_project(player, Vec3::X, player + Vec3::new(40.0, 0.0, 0.0), 80.0, false).unwrap();
+        assert!(turned.0.abs() < 1e-5 && (turned.1 + 0.5).abs() < 1e-5);
+
+        let near = minimap_project(player, Vec3::NEG_Z, player + Vec3::new(0.0, 0.0, 8.0), 80.0, false).unwrap();
+        assert!(near.2 > ahead.2);
+    }
+
+    #[test]
+    fn compass_headings_and_strip_offsets() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        assert!(compass_heading(Vec3::NEG_Z).abs() < 1e-5);
+        assert!((compass_heading(Vec3::X) - FRAC_PI_2).abs() < 1e-5);
+        assert!((compass_heading(Vec3::Z) - PI).abs() < 1e-5);
+        // Facing north, east is at the right end of a +/-90 degree strip; wraps across north.
+        assert!((compass_offset(0.0, FRAC_PI_2, FRAC_PI_2).unwrap() - 1.0).abs() < 1e-5);
+        assert!(compass_offset(0.1, 2.0 * PI - 0.1, FRAC_PI_2).unwrap() < 0.0);
+        assert!(compass_offset(0.0, PI, FRAC_PI_2).is_none());
+    }
+
+    #[test]
+    fn waypoints_behind_or_off_screen_clamp_to_edges() {
+        let proj = Mat4::perspective_rh(1.2, 16.0 / 9.0, 0.1, 1000.0);
+        // Camera at origin looking down -Z
+        let view_proj = proj * Mat4::look_to_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let (sw, sh) = (1600.0, 900.0);
+        let (p, on) = project_to_screen_edge(view_proj, Vec3::new(0.0, 0.0, -50.0), sw, sh, 40.0);
+        assert!(on && (p.x - 800.0).abs() < 1e-3 && (p.y - 450.0).abs() < 1e-3);
+
+        let (p, on) = project_to_screen_edge(view_proj, Vec3::new(500.0, 0.0, -10.0), sw, sh, 40.0);
+        assert!(!on && (p.x - (sw - 40.0)).abs() < 1e-3);
+
+        // Behind and to the left stays on the left edge
+        let (p, on) = project_to_screen_edge(view_proj, Vec3::new(-20.0, 0.0, 30.0), sw, sh, 40.0);
+        assert!(!on && (p.x - 40.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn boss_bar_tracks_the_living_queen() {
+        let hud = HUDSystem::new();
+        let mut world = World::new();
+        assert!(hud.boss_bar(&world).is_none());
+
+        let mut health = Health::new(1000.0);
+        health.take_damage(250.0);
+        let queen = world.spawn((health, QueenBoss::new()));
+        let bar = hud.boss_bar(&world).unwrap();
+        assert!((bar.health_fraction - 0.75).abs() < 1e-5);
+        assert_eq!(bar.phase, "ARMORED");
+
+        world.get::<&mut Health>(queen).unwrap().take_damage(1000.0);
+        assert!(hud.boss_bar(&world).is_none());
+    }
+
+    #[test]
+    fn stratagem_tray_fills_with_cooldown_and_lights_when_ready() {
+        let mut cooldowns = crate::stratagem::initial_cooldowns();
+        cooldowns.get_mut(&Stratagem::SupplyDrop).unwrap().start(60.0);
+        cooldowns.get_mut(&Stratagem::SupplyDrop).unwrap().tick(15.0);
+        let hud = HUDSystem::new();
+        let tray = hud.stratagem_tray(&cooldowns, |s| s != Stratagem::Artillery);
+        assert_eq!(tray.len(), Stratagem::ALL.len());
+
+        let slot = |s: Stratagem| *tray.iter().find(|t| t.stratagem == s).unwrap();
+        let supply = slot(Stratagem::SupplyDrop);
+        assert!(!supply.ready && (supply.cooldown_fraction - 0.75).abs() < 1e-5 && supply.remaining == 45.0);
+        assert!(slot(Stratagem::Reinforce).ready && slot(Stratagem::Reinforce).cooldown_fraction == 0.0);
+        // Off cooldown but unavailable is not highlighted
+        assert!(!slot(Stratagem::Artillery).ready);
+        // CAS fleet starts rearming
+        assert!(!slot(Stratagem::OrbitalStrike).ready && slot(Stratagem::OrbitalStrike).cooldown_fraction == 1.0);
+
+        // Ticking past zero reports the recharge once
+        let cooldown = cooldowns.get_mut(&Stratagem::SupplyDrop).unwrap();
+        assert!(cooldown.tick(50.0) && !cooldown.tick(1.0) && cooldown.is_ready());
+    }
+
+    #[test]
+    fn out_of_range_blips_drop_or_pin_to_rim() {
+        let far = Vec3::new(0.0, 0.0, 500.0);
+        assert!(minimap_project(Vec3::ZERO, Vec3::NEG_Z, far, 80.0, false).is_none());
+        let (x, y, _) = minimap_project(Vec3::ZERO, Vec3::NEG_Z, far, 80.0, true).unwrap();
+        assert!(x.abs() < 1e-5 && (y - 1.0).abs() < 1e-5);
+    }
+}
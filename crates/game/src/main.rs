@@ -1,17 +1,22 @@
 //! OpenSST - Open Starship Troopers: Extermination-inspired FPS with Euphoria-style physics
 
+mod ability;
 mod biome_atmosphere;
 mod biome_features;
+mod boss;
 mod bug;
+mod chunk_worker;
 mod config;
+mod difficulty;
 mod render;
+mod save;
 mod state;
 mod update;
 
 pub use state::{DropPhase, GameMessage, GameMessages, GamePhase, SupplyCrate};
 use state::{
     ApproachFlightState, DebugSettings, DropPodSequence, InteractPrompt, KillStreakTracker,
-    ScreenShake, SquadDropSequence, WarpSequence, Weather,
+    ScreenShake, SquadDropSequence, WarpSequence, Weather, WeatherState,
     DEPLOY_KEY, INTERACT_KEY,
 };
 mod authored_bug_meshes;
@@ -21,13 +26,20 @@ mod bug_entity;
 mod destruction;
 mod effects;
 mod fleet;
+mod footsteps;
+mod flight;
 mod extraction;
 mod fps;
+mod galaxy_map;
+mod grenade;
 mod horde_ai;
 mod hud;
+mod objective;
 mod player;
 mod smoke;
+mod snow;
 mod spawner;
+mod stratagem;
 mod squad;
 mod artillery;
 mod citizen;
@@ -39,17 +51,17 @@ mod viewmodel;
 mod weapons;
 
 use anyhow::Result;
-use engine_core::{Health, Lifetime, Time, Transform, Velocity};
+use audio::{AudioBus, AudioSystem, ReverbPreset, ReverbZone};
+use engine_core::{DamageType, GameRng, Health, Time, Transform, Velocity};
 use rand::{Rng, SeedableRng};
 use glam::{DVec3, Quat, Vec3};
 use hecs::{Entity, World};
-use input::InputState;
-use physics::PhysicsWorld;
-use procgen::{BiomeType, FlowField, Planet, PlanetBiomes, PlanetClassification, StarSystem, Universe, TerrainConfig, VoxelChunk};
+use input::{Action, ActionMap, GamepadManager, InputState};
+use physics::{CollisionGroup, KinematicCharacterController, PhysicsWorld};
+use procgen::{BiomeType, BlockEdits, ChunkCorner, ChunkEdge, FlowField, Planet, PlanetBiomes, PlanetClassification, StarSystem, TextureData, TextureGenerator, Universe, TerrainConfig, VoxelChunk};
 use rapier3d::prelude::ColliderHandle;
-use renderer::{Camera, CelestialBodyInstance, InstanceData, Mesh, OverlayTextBuilder, Renderer, DEFORM_HALF_SIZE, DEFORM_TEXTURE_SIZE};
+use renderer::{Camera, CelestialBodyInstance, Cloth, DecalKind, LodLevel, LodMesh, Material, Mesh, Renderer, TransparentQueue, DEFORM_TEXTURE_SIZE, TERRAIN_MATERIAL_SIZE};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 use std::sync::Arc;
 use winit::{
     application::ApplicationHandler,
@@ -60,34 +72,42 @@ use winit::{
 };
 
 use biome_atmosphere::{AtmoParticleKind, BiomeAtmosphere};
-use bug::{Bug, BugBundle, BugType, VariantDeathEffect};
-use skinny::{Skinny, SkinnyType};
-use bug_entity::{DeathPhase, EffectsManager, GoreType, PhysicsBug, TrackKind, update_bug_physics};
+use bug::{AcidGlob, Bug, BugBundle, BugStrike, BugType, Maneuver, VariantDeathEffect};
+use skinny::{Skinny, SkinnyBolt, SkinnyType};
+use chunk_worker::{ChunkBuild, ChunkWorkers};
+use difficulty::Difficulty;
+use bug_entity::{DeathPhase, EffectsManager, EmergePhase, GoreType, HitRegion, PhysicsBug, TrackKind, update_bug_physics};
 use destruction::{
     AbandonedOutpost, BiomeDestructible, BiomeLandmark, BonePile, BugCorpse, BugHole, BurnCrater,
-    CachedRenderData, ChainEffect, ChainReaction, CrashedShip, Debris, Destructible, DestructiblePhysics,
-    DestructionSystem, EggCluster, EnvironmentProp, EnvironmentalHazard, HazardPool, HazardType,
+    CachedRenderData, ChainEffect, ChainReaction, Cover, CrashedShip, Debris, Destructible, DestructiblePhysics,
+    DestructionSystem, EggCluster, EnvironmentProp, EnvironmentalHazard, HazardPool, HazardType, HoleCollapse, SealedHole,
     HiveStructure, HiveNest, HiveTunnelEntrance, LandmarkType, Rock, SporeTower,
     ENV_MESH_GROUP_COUNT, MESH_GROUP_ROCK, MESH_GROUP_BUG_HOLE, MESH_GROUP_HIVE_MOUND,
-    MESH_GROUP_EGG_CLUSTER, MESH_GROUP_PROP_SPHERE, MESH_GROUP_CUBE,
+    MESH_GROUP_EGG_CLUSTER, MESH_GROUP_PROP_SPHERE, MESH_GROUP_CUBE, MESH_GROUP_BEVELED_CUBE,
     MESH_GROUP_LANDMARK, MESH_GROUP_HAZARD, MESH_GROUP_HIVE_CAVE_ENTRANCE,
 };
 use biome_features::get_biome_feature_table;
-use effects::{AmbientDust, DustShape, RainDrop, SnowParticle, TracerProjectile};
+use effects::{ParticleKind, ParticleSystem, TracerProjectile};
 use extraction::{ExtractionDropship, ExtractionMessage, ExtractionPhase, roger_young_parts};
+use flight::{FlightControls, FlightMode};
+use footsteps::{FootSurface, Footsteps, Gait};
 use horde_ai::apply_separation;
-use fps::{BugCombatSystem, CombatSystem, FPSPlayer, MissionState, PlayerClass};
+use ability::ClassAbility;
+use galaxy_map::GalaxyMapView;
+use fps::{melee_targets, pick_look_target, BugCombatSystem, CombatSystem, FPSPlayer, InteractionTarget, MissionState, PlayerClass};
 use horde_ai::HordeAI;
 use hud::HUDSystem;
+use grenade::{cooked_fuse, throw_velocity, trajectory_preview, FragGrenade, FRAG_DAMAGE, FRAG_RADIUS, GRENADE_RADIUS};
 use smoke::{SmokeCloud, SmokeGrenade, SmokeParticle};
 use spawner::BugSpawner;
 use citizen::{despawn_citizens, spawn_earth_citizens, update_citizens, Citizen};
-use squad::{despawn_squad, spawn_squad, update_squad_combat, update_squad_movement, SquadMate, SquadMateKind};
-use dialogue::DialogueState;
-use artillery::{ArtilleryBarrage, ArtilleryMuzzleFlash, ArtilleryShell, ArtilleryTrailParticle, GroundedArtilleryShell};
-use tac_fighter::{TacBomb, TacFighter, TacFighterPhase};
-use viewmodel::{GroundedShellCasing, ShellCasing, ShellCasingType, ViewmodelAnimState};
-use weapons::{WeaponSystem, WeaponType};
+use squad::{despawn_squad, spawn_squad, update_squad_combat, update_squad_movement, FormationKind, SquadMate, SquadMateKind, SquadMateStatus};
+use dialogue::{DialogueMemory, DialogueState};
+use artillery::{ArtilleryBarrage, ArtilleryMuzzleFlash, ArtilleryShell, ArtilleryTrailParticle, GroundedArtilleryShell, ImpactMarker};
+use stratagem::{Cooldown, Stratagem};
+use tac_fighter::{TacBomb, TacFighter, TacFighterPhase, MAX_TAC_FIGHTERS};
+use viewmodel::{GroundedShellCasing, ShellCasing, ShellCasingType, ViewmodelAnimState, ViewmodelRig};
+use weapons::{cone_falloff, FireZone, Projectile, ProjectileImpact, WeaponPickup, WeaponSystem, WeaponType};
 
 /// Main game state with full Euphoria-style physics integration
 pub struct GameState {
@@ -95,12 +115,17 @@ pub struct GameState {
     world: World,
     time: Time,
     input: InputState,
+    gamepads: GamepadManager,
     physics: PhysicsWorld,
 
     // Renderer
     renderer: Renderer,
     camera: Camera,
 
+    /// None when no audio output device is available; the game then runs silent.
+    audio: Option<AudioSystem>,
+    footsteps: Footsteps,
+
     // Authored STE-style bug meshes
     bug_meshes: AuthoredBugMeshes,
     environment_meshes: EnvironmentMeshes,
@@ -109,12 +134,15 @@ pub struct GameState {
     tracer_mesh: Mesh,      // Proper bullet-shaped diamond mesh
     flash_mesh: Mesh,       // Multi-pointed star for muzzle flashes
     billboard_mesh: Mesh,   // Camera-facing quad for billboard particles
+    /// Plated-metal normal map for ship steel and powered suits.
+    plate_material: Material,
 
     /// Heightfield for terrain deformation (footprints in snow/sand). 256x256 f32s, world follows player.
     deformation_buffer: Vec<f32>,
-    /// Snow accumulation (weather-driven). 256x256 f32s, same layout as deformation; center of 128m tile.
-    snow_accumulation_buffer: Vec<f32>,
-    snow_accumulation_origin: (f32, f32),
+    /// Snow cover (weather-driven, follows the camera). Drives terrain snow, footing and wading speed.
+    snow: snow::SnowField,
+    /// Blood decal stain map (DEFORM_TEXTURE_SIZE²), restamped around the camera each frame.
+    blood_decal_buffer: Vec<f32>,
 
     // Effects
     effects: EffectsManager,
@@ -123,7 +151,10 @@ pub struct GameState {
     player: FPSPlayer,
     combat: CombatSystem,
     bug_combat: BugCombatSystem,
+    /// Dash, deployed shields and scan marks from the class ability.
+    abilities: ability::AbilityState,
     hud: HUDSystem,
+    stratagem_input: stratagem::StratagemInput,
     mission: MissionState,
 
     // Game systems
@@ -137,6 +168,8 @@ pub struct GameState {
 
     // Universe navigation
     universe_seed: u64,
+    /// Gameplay RNG seeded from `universe_seed` (spawns, gore) so runs are reproducible.
+    rng: GameRng,
     universe: Universe,
     current_system: StarSystem,
     current_system_idx: usize,
@@ -148,7 +181,10 @@ pub struct GameState {
 
     // Galaxy map
     galaxy_map_open: bool,
+    /// System under the map reticle (warp / travel target).
     galaxy_map_selected: usize,
+    /// Map camera, filter and sort for the 3D galaxy map.
+    galaxy_map: GalaxyMapView,
     warp_sequence: Option<WarpSequence>,
     /// Galaxy position when warp started (for FTL interpolation so Roger Young "moves" to target system).
     warp_start_galaxy_position: Option<DVec3>,
@@ -159,6 +195,8 @@ pub struct GameState {
     drop_pod: Option<DropPodSequence>,
     /// Squad drop pods coming from orbit after the player lands (player can look up and see them).
     squad_drop_pods: Option<SquadDropSequence>,
+    /// Formation the squad holds around the player.
+    squad_formation: FormationKind,
     ship_state: Option<ShipState>,
     /// Planet we're deploying to (set when starting approach, used when starting drop).
     deploy_planet_idx: Option<usize>,
@@ -166,6 +204,8 @@ pub struct GameState {
     approach_timer: f32,
     /// Approach flight: flyable craft position/velocity (Star Citizen piloting).
     approach_flight_state: Option<ApproachFlightState>,
+    /// Rotation model for player-flown craft (config `flight_six_dof`).
+    flight_mode: FlightMode,
     // Galactic War Table
     war_state: GalacticWarState,
 
@@ -180,14 +220,16 @@ pub struct GameState {
     /// Building cuboid colliders on Earth (removed when leaving Earth).
     earth_building_colliders: Vec<ColliderHandle>,
     dialogue_state: DialogueState,
+    /// What each NPC remembers of past conversations (per speaker name).
+    dialogue_memory: DialogueMemory,
     /// Current interact prompt (war table, drop bay, talk to NPC). Set each frame; overlay draws same style for all.
     pub(crate) interaction_prompt: Option<InteractPrompt>,
 
     // Sky and weather (dynamic)
     time_of_day: f32,       // 0 = dawn, 0.25 = noon, 0.5 = dusk, 0.75 = night
     weather: Weather,
-    rain_drops: Vec<RainDrop>,
-    snow_particles: Vec<SnowParticle>,
+    /// Pooled rain, snow and dust motes around the camera.
+    particles: ParticleSystem,
 
     // Destructible environment
     destruction: DestructionSystem,
@@ -217,6 +259,14 @@ pub struct GameState {
 
     // Visible tracer projectiles (visual only; damage is hitscan)
     tracer_projectiles: Vec<TracerProjectile>,
+    /// Skinny rounds in flight (these do damage).
+    skinny_bolts: Vec<SkinnyBolt>,
+    /// Spitter acid globs in flight.
+    acid_globs: Vec<AcidGlob>,
+    /// Sealed bug holes still caving in.
+    hole_collapses: Vec<HoleCollapse>,
+    /// Told the trooper this landing that bullets won't close a bug hole.
+    hole_hint_given: bool,
 
     // Developer debug settings
     debug: DebugSettings,
@@ -224,6 +274,12 @@ pub struct GameState {
     // FPS player controller state
     player_velocity: Vec3,
     player_grounded: bool,
+    /// Collision, slope limit and step-up against rocks, props and terrain colliders.
+    player_controller: KinematicCharacterController,
+    /// Sensor following the player so trigger volumes can detect them.
+    player_sensor: ColliderHandle,
+    /// Trigger volumes the player is currently inside.
+    player_triggers: HashSet<ColliderHandle>,
     /// Movement speed multiplier from environmental hazards (quicksand, blizzard, etc.). 1.0 = normal.
     hazard_slow_multiplier: f32,
 
@@ -236,26 +292,34 @@ pub struct GameState {
 
     // Cinematic effects
     screen_shake: ScreenShake,
-    camera_recoil: f32,               // Current recoil pitch offset (decays back to 0)
-    crouch_hold_timer: f32,           // Hold Ctrl to go prone (Helldivers 2 style)
+    /// Real seconds until the next hitstop may fire.
+    hitstop_cooldown: f32,
+    recoil: weapons::Recoil,          // Per-weapon recoil pattern progress and unrecovered kick
+    crouch_toggled: bool,             // Tap Ctrl toggles crouch; hold goes prone (Helldivers 2 style)
+    combat_roll_timer: f32,           // Seconds left in the current combat roll (0 = not rolling)
+    combat_roll_cooldown: f32,
+    melee_cooldown: f32,              // Seconds until the next melee strike (V)
+    combat_roll_dir: Vec3,
     kill_streaks: KillStreakTracker,
-    ambient_dust: AmbientDust,
     biome_atmosphere: BiomeAtmosphere, // Per-biome volumetric particles
 
     // Viewmodel animation
     viewmodel_anim: ViewmodelAnimState,
+    viewmodel_rig: Option<ViewmodelRig>, // Camera -> gun -> muzzle hierarchy, spawned on first use
     shell_casings: Vec<ShellCasing>,
     grounded_shell_casings: Vec<GroundedShellCasing>,
 
     // Smoke grenades
     smoke_grenades: Vec<SmokeGrenade>,   // In-flight grenades
     smoke_clouds: Vec<SmokeCloud>,       // Active smoke clouds
-    smoke_grenade_cooldown: f32,         // Cooldown timer
+
+    // Frag grenades
+    frag_grenades: Vec<FragGrenade>,     // Thrown, fuse burning
+    grenade_cook: Option<f32>,           // Seconds the pin has been out of the one in hand
 
     // Tac Fighter fleet — multiple fighters can be on station (Starship Troopers style)
     tac_fighters: Vec<TacFighter>,
     tac_bombs: Vec<TacBomb>,
-    tac_fighter_cooldown: f32,           // Time until next fighter can be requested
     tac_fighter_available: bool,         // Whether CAS is available
 
     // Orbital artillery — red smoke designates; 6 shells fired one after another; rearm like tac fighters
@@ -264,25 +328,32 @@ pub struct GameState {
     artillery_trail_particles: Vec<ArtilleryTrailParticle>,
     grounded_artillery_shells: Vec<GroundedArtilleryShell>,
     artillery_barrage: Option<ArtilleryBarrage>,
-    artillery_cooldown: f32,
+    artillery_impacts: Vec<ImpactMarker>, // Predicted landing points of shells in flight and pending
 
     // Stratagems (Helldivers 2 style): B = Orbital Strike, N = Supply Drop, R = Reinforce
     supply_crates: Vec<SupplyCrate>,
-    supply_drop_cooldown: f32,
     supply_drop_smoke: Vec<SmokeCloud>,   // Smoke at each supply drop LZ (same style as LZ green)
-    reinforce_cooldown: f32,
     reinforce_smoke: Option<SmokeCloud>,   // Smoke when Reinforce is called
     orbital_strike_smoke: Option<SmokeCloud>, // Red smoke when B is pressed (like tac designator)
+    /// Rearm timers for every stratagem (smoke, CAS, artillery, supply, reinforce, extraction).
+    stratagem_cooldowns: HashMap<Stratagem, Cooldown>,
 
     // Extraction dropship
     extraction: Option<ExtractionDropship>,
-    extraction_cooldown: f32,            // Cooldown between extraction calls
     extraction_squadmates_aboard: Vec<Entity>, // NO TROOPER LEFT BEHIND — squadmates picked up with player
     extraction_collider: Option<ColliderHandle>, // Hull collider for player/bug collision
     lz_smoke: Option<SmokeCloud>,        // Green smoke marker at LZ
 
     /// Mission type for next drop (set at war table; used when drop launches).
     next_mission_type: fps::MissionType,
+    /// Difficulty preset (set at war table; saved with the galactic war).
+    difficulty: Difficulty,
+    /// Supply-line effects for the current drop (set in `prepare_planet_for_drop`).
+    supply: SupplyModifiers,
+    /// Major Order rewards earned so far (saved; survive warping to other systems).
+    granted_rewards: Vec<OrderReward>,
+    /// Heavy weapon slot choice (war table [X]): MachineGun or a requisitioned weapon.
+    heavy_weapon: WeaponType,
 
     /// Base defense mode (UCF planet + Hold the Line / Defense): center and inner radius.
     /// Bugs spawn outside this perimeter; player and squad spawn on walls.
@@ -307,221 +378,9 @@ struct ShipState {
     /// Position of the drop pod bay trigger.
     drop_bay_pos: Vec3,
     /// UCF flag (port wall).
-    ucf_flag: ClothFlag,
+    ucf_flag: Cloth,
     /// Mobile Infantry flag (starboard wall).
-    mi_flag: ClothFlag,
-}
-
-/// Cloth-simulated flag using Verlet integration with distance constraints.
-/// The flag is a grid of particles; the top row is pinned to a pole.
-struct ClothFlag {
-    /// Particle positions (row-major: cols across, rows down).
-    positions: Vec<Vec3>,
-    /// Previous positions (for Verlet integration).
-    prev_positions: Vec<Vec3>,
-    /// Which particles are pinned (top row attached to pole).
-    pinned: Vec<bool>,
-    /// Grid dimensions.
-    cols: usize,
-    rows: usize,
-    /// Rest distance between adjacent particles.
-    rest_dist: f32,
-    /// World-space origin of top-left pin.
-    origin: Vec3,
-    /// Direction the flag hangs away from the wall (unit vector).
-    hang_dir: Vec3,
-    /// Direction along the pole (unit vector, "right" for the flag).
-    pole_dir: Vec3,
-    /// Base color grid: each cell [row][col] has an RGBA color.
-    colors: Vec<[f32; 4]>,
-    /// Accumulated time for wind variation.
-    wind_time: f32,
-}
-
-impl ClothFlag {
-    /// Create a new flag.
-    /// `origin`: world position of top-left attachment point.
-    /// `pole_dir`: unit vector along the pole (direction of increasing column).
-    /// `hang_dir`: unit vector the flag hangs toward (away from wall, perpendicular to pole).
-    /// `width`, `height`: physical dimensions in meters.
-    /// `cols`, `rows`: grid resolution.
-    /// `colors`: flat vec of [r,g,b,a] per cell (cols * rows).
-    fn new(
-        origin: Vec3,
-        pole_dir: Vec3,
-        hang_dir: Vec3,
-        width: f32,
-        height: f32,
-        cols: usize,
-        rows: usize,
-        colors: Vec<[f32; 4]>,
-    ) -> Self {
-        let rest_dist_x = width / (cols - 1).max(1) as f32;
-        let rest_dist_y = height / (rows - 1).max(1) as f32;
-        let rest_dist = rest_dist_x.min(rest_dist_y);
-        let down = Vec3::new(0.0, -1.0, 0.0);
-
-        let mut positions = Vec::with_capacity(cols * rows);
-        let mut pinned = Vec::with_capacity(cols * rows);
-
-        for r in 0..rows {
-            for c in 0..cols {
-                let x_off = c as f32 * rest_dist_x;
-                let y_off = r as f32 * rest_dist_y;
-                let pos = origin + pole_dir * x_off + down * y_off;
-                positions.push(pos);
-                // Pin the top row
-                pinned.push(r == 0);
-            }
-        }
-
-        let prev_positions = positions.clone();
-
-        Self {
-            positions,
-            prev_positions,
-            pinned,
-            cols,
-            rows,
-            rest_dist: rest_dist_x, // horizontal rest distance
-            origin,
-            hang_dir,
-            pole_dir,
-            colors,
-            wind_time: 0.0,
-        }
-    }
-
-    /// Step the cloth simulation.
-    fn update(&mut self, dt: f32) {
-        let dt = dt.min(0.033); // cap to prevent explosion
-        self.wind_time += dt;
-
-        let gravity = Vec3::new(0.0, -4.0, 0.0);
-        let damping = 0.98;
-
-        // Wind: oscillating gusts in the hang direction with turbulence
-        let wind_base = 3.0 + (self.wind_time * 1.2).sin() * 2.0;
-        let wind_gust = (self.wind_time * 3.7).sin() * (self.wind_time * 0.8).cos() * 1.5;
-        let wind_force = self.hang_dir * (wind_base + wind_gust);
-        // Cross-wind turbulence
-        let cross = self.pole_dir * (self.wind_time * 2.3).sin() * 0.8;
-
-        // Verlet integration
-        for i in 0..self.positions.len() {
-            if self.pinned[i] { continue; }
-
-            let pos = self.positions[i];
-            let prev = self.prev_positions[i];
-            let vel = (pos - prev) * damping;
-
-            // Per-particle wind variation based on grid position
-            let r = i / self.cols;
-            let c = i % self.cols;
-            let wave = ((c as f32 * 0.5 + self.wind_time * 4.0).sin() * 0.3
-                + (r as f32 * 0.7 + self.wind_time * 2.5).cos() * 0.2)
-                * self.hang_dir;
-
-            let accel = gravity + wind_force + cross + wave;
-            let new_pos = pos + vel + accel * dt * dt;
-
-            self.prev_positions[i] = pos;
-            self.positions[i] = new_pos;
-        }
-
-        // Distance constraints (iterate multiple times for stiffness)
-        let rest_x = self.rest_dist;
-        let rest_y = (self.positions.len() > self.cols)
-            .then(|| {
-                let origin_y_span = self.positions[0].y - self.positions[self.cols].y;
-                origin_y_span.abs().max(rest_x)
-            })
-            .unwrap_or(rest_x);
-
-        for _iter in 0..5 {
-            // Horizontal constraints
-            for r in 0..self.rows {
-                for c in 0..(self.cols - 1) {
-                    let i = r * self.cols + c;
-                    let j = i + 1;
-                    self.apply_constraint(i, j, rest_x);
-                }
-            }
-            // Vertical constraints
-            for r in 0..(self.rows - 1) {
-                for c in 0..self.cols {
-                    let i = r * self.cols + c;
-                    let j = i + self.cols;
-                    self.apply_constraint(i, j, rest_y);
-                }
-            }
-        }
-    }
-
-    fn apply_constraint(&mut self, i: usize, j: usize, rest: f32) {
-        let delta = self.positions[j] - self.positions[i];
-        let dist = delta.length();
-        if dist < 0.0001 { return; }
-        let correction = delta * (1.0 - rest / dist) * 0.5;
-
-        if !self.pinned[i] { self.positions[i] += correction; }
-        if !self.pinned[j] { self.positions[j] -= correction; }
-    }
-
-    /// Generate renderable quad instances for this flag.
-    /// Returns a list of (matrix, color) for each cloth cell.
-    fn render_instances(&self) -> Vec<(glam::Mat4, [f32; 4])> {
-        let mut instances = Vec::new();
-
-        for r in 0..(self.rows - 1) {
-            for c in 0..(self.cols - 1) {
-                let tl = self.positions[r * self.cols + c];
-                let tr = self.positions[r * self.cols + c + 1];
-                let bl = self.positions[(r + 1) * self.cols + c];
-                let br = self.positions[(r + 1) * self.cols + c + 1];
-
-                // Quad center
-                let center = (tl + tr + bl + br) * 0.25;
-                // Approximate quad normal
-                let edge_h = (tr - tl + br - bl) * 0.5;
-                let edge_v = (bl - tl + br - tr) * 0.5;
-                let normal = edge_h.cross(edge_v);
-                let normal_len = normal.length();
-                if normal_len < 0.0001 { continue; }
-
-                // Scale from edge lengths
-                let sx = ((tr - tl).length() + (br - bl).length()) * 0.5;
-                let sy = ((bl - tl).length() + (br - tr).length()) * 0.5;
-
-                // Build rotation from edges
-                let right = edge_h.normalize_or_zero();
-                let up_approx = edge_v.normalize_or_zero();
-                let fwd = right.cross(up_approx).normalize_or_zero();
-                let corrected_up = fwd.cross(right).normalize_or_zero();
-
-                let rot_mat = glam::Mat3::from_cols(right, corrected_up, fwd);
-                let rotation = Quat::from_mat3(&rot_mat);
-
-                let matrix = glam::Mat4::from_scale_rotation_translation(
-                    Vec3::new(sx, 0.01, sy), // flat quad: X-width, Z-height, Y-thin
-                    rotation,
-                    center,
-                );
-
-                // Cell color from the pattern
-                let color_idx = r * (self.cols - 1) + c;
-                let color = if color_idx < self.colors.len() {
-                    self.colors[color_idx]
-                } else {
-                    [0.5, 0.5, 0.5, 1.0]
-                };
-
-                instances.push((matrix, color));
-            }
-        }
-
-        instances
-    }
+    mi_flag: Cloth,
 }
 
 /// Generate the color pattern for the United Citizen Federation flag (franchise).
@@ -983,6 +842,9 @@ struct PlanetWarStatus {
     /// Per-planet weather (each planet has its own conditions for variety).
     #[serde(default)]
     weather: Weather,
+    /// Place in the system's supply network (recomputed by `GalacticWarState::update_supply`).
+    #[serde(default)]
+    supply: SupplyStatus,
 }
 
 fn default_time_of_day() -> f32 {
@@ -990,8 +852,7 @@ fn default_time_of_day() -> f32 {
 }
 
 impl PlanetWarStatus {
-    fn new() -> Self {
-        let mut rng = rand::thread_rng();
+    fn new(rng: &mut GameRng) -> Self {
         Self {
             liberation: 0.0,
             active_operation: false,
@@ -999,8 +860,60 @@ impl PlanetWarStatus {
             successful_extractions: 0,
             liberated: false,
             defense_urgency: 0.0,
-            time_of_day: rng.gen::<f32>(),
-            weather: Weather::random(),
+            time_of_day: rng.unit(),
+            weather: Weather::random(rng),
+            supply: SupplyStatus::default(),
+        }
+    }
+
+    /// Held by the Federation: the planet feeds supply lines to its neighbours.
+    fn is_held(&self) -> bool {
+        self.liberated || self.liberation >= SUPPLY_HOLD_LIBERATION
+    }
+}
+
+/// Liberation at which a planet counts as held and starts supplying its neighbours.
+const SUPPLY_HOLD_LIBERATION: f32 = 0.5;
+/// Defense urgency lost per second on a linked planet, before the supply multiplier.
+const DEFENSE_URGENCY_DECAY: f32 = 0.002;
+
+/// How a planet sits in the system's supply network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum SupplyStatus {
+    /// No uncontested route to a held planet: bugs swarm harder and the defense window closes sooner.
+    #[default]
+    CutOff,
+    /// Reachable from a held planet over uncontested lines.
+    Linked,
+    /// Held, or directly linked to two or more held planets: the fleet rearms faster.
+    Reinforced,
+}
+
+/// What a planet's supply status does to a drop there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SupplyModifiers {
+    /// Multiplier on the planet's bug spawn rate.
+    spawn_rate_mult: f32,
+    /// Multiplier on how fast a counter-attack's defense urgency decays.
+    urgency_decay_mult: f32,
+    /// Multiplier on stratagem rearm times.
+    stratagem_cooldown_mult: f32,
+}
+
+impl SupplyStatus {
+    fn modifiers(self) -> SupplyModifiers {
+        match self {
+            SupplyStatus::CutOff => SupplyModifiers { spawn_rate_mult: 1.3, urgency_decay_mult: 2.0, stratagem_cooldown_mult: 1.0 },
+            SupplyStatus::Linked => SupplyModifiers { spawn_rate_mult: 1.0, urgency_decay_mult: 1.0, stratagem_cooldown_mult: 1.0 },
+            SupplyStatus::Reinforced => SupplyModifiers { spawn_rate_mult: 1.0, urgency_decay_mult: 1.0, stratagem_cooldown_mult: 0.75 },
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SupplyStatus::CutOff => "CUT OFF",
+            SupplyStatus::Linked => "LINKED",
+            SupplyStatus::Reinforced => "REINFORCED",
         }
     }
 }
@@ -1020,6 +933,38 @@ struct MajorOrder {
     completed: bool,
     /// Reward text.
     reward: String,
+    /// What completing the order grants (missing in saves from before rewards were granted).
+    #[serde(default)]
+    grants: Option<OrderReward>,
+}
+
+/// Permanent payoff for completing a Major Order, kept across systems and saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum OrderReward {
+    /// Unlocks a requisition weapon (`WeaponDef::requisition`) for the heavy slot.
+    WeaponRequisition(WeaponType),
+    /// Cuts a stratagem's rearm time for good.
+    StratagemClearance(Stratagem),
+}
+
+/// Rearm time multiplier for a stratagem with `OrderReward::StratagemClearance`.
+const STRATAGEM_CLEARANCE_MULT: f32 = 0.7;
+
+impl OrderReward {
+    /// Fleet Command announcement when the reward is granted.
+    fn announcement(self) -> String {
+        match self {
+            OrderReward::WeaponRequisition(weapon) => format!(
+                "REQUISITION APPROVED: {:?} unlocked — select it as your heavy weapon at the war table [X].",
+                weapon
+            ),
+            OrderReward::StratagemClearance(stratagem) => format!(
+                "CLEARANCE GRANTED: {} rearms {:.0}% faster.",
+                stratagem.name(),
+                (1.0 - STRATAGEM_CLEARANCE_MULT) * 100.0
+            ),
+        }
+    }
 }
 
 /// Supply line connecting two planets.
@@ -1051,17 +996,23 @@ struct GalacticWarState {
 }
 
 impl GalacticWarState {
-    fn new(num_planets: usize) -> Self {
+    /// Fresh war state for a system. Seed `rng` from the system seed so the same system
+    /// always starts with the same front lines. The first planet is the fleet's staging world,
+    /// held from the start so supply has somewhere to flow from.
+    fn new(num_planets: usize, rng: &mut GameRng) -> Self {
         let mut planets = Vec::with_capacity(num_planets);
-        let mut rng = rand::thread_rng();
         for _ in 0..num_planets {
-            let mut status = PlanetWarStatus::new();
+            let mut status = PlanetWarStatus::new(rng);
             // Some planets start partially liberated
             status.liberation = rng.gen::<f32>() * 0.3;
             // Random defense urgency
             status.defense_urgency = if rng.gen::<f32>() > 0.7 { rng.gen::<f32>() * 0.5 } else { 0.0 };
             planets.push(status);
         }
+        if let Some(staging) = planets.first_mut() {
+            staging.liberation = staging.liberation.max(SUPPLY_HOLD_LIBERATION + 0.1);
+            staging.defense_urgency = 0.0;
+        }
 
         // Generate supply lines (connect sequential planets + some cross-links)
         let mut supply_lines = Vec::new();
@@ -1087,6 +1038,7 @@ impl GalacticWarState {
                 progress: 0.0,
                 completed: false,
                 reward: "Medal of Valor + Weapon Requisition".to_string(),
+                grants: Some(OrderReward::WeaponRequisition(WeaponType::Flamethrower)),
             });
         }
         if num_planets > 2 {
@@ -1094,13 +1046,14 @@ impl GalacticWarState {
                 title: "DEFEND SUPPLY LINES".to_string(),
                 description: "Bug counter-offensive threatening critical supply routes. Hold the line!".to_string(),
                 target_planets: vec![],
-                progress: 0.3,
+                progress: 0.0,
                 completed: false,
                 reward: "Orbital Strike Clearance".to_string(),
+                grants: Some(OrderReward::StratagemClearance(Stratagem::OrbitalStrike)),
             });
         }
 
-        Self {
+        let mut state = Self {
             planets,
             major_orders,
             supply_lines,
@@ -1108,35 +1061,97 @@ impl GalacticWarState {
             system_kills: 0,
             holo_rotation: 0.0,
             ticker_offset: 0.0,
+        };
+        state.update_supply();
+        state
+    }
+
+    /// Recompute every planet's supply status: supply floods out from held planets along
+    /// uncontested lines, and a line between two held planets is secured for good.
+    fn update_supply(&mut self) {
+        let held: Vec<bool> = self.planets.iter().map(PlanetWarStatus::is_held).collect();
+        let is_held = |i: usize| held.get(i).copied().unwrap_or(false);
+        for line in &mut self.supply_lines {
+            if is_held(line.from) && is_held(line.to) {
+                line.contested = false;
+            }
+        }
+
+        let open_lines: Vec<(usize, usize)> = self.supply_lines.iter()
+            .filter(|line| !line.contested)
+            .map(|line| (line.from, line.to))
+            .collect();
+        let mut linked = held.clone();
+        let mut frontier: Vec<usize> = (0..held.len()).filter(|&i| held[i]).collect();
+        while let Some(i) = frontier.pop() {
+            for &(a, b) in &open_lines {
+                let next = if a == i { b } else if b == i { a } else { continue };
+                if next < linked.len() && !linked[next] {
+                    linked[next] = true;
+                    frontier.push(next);
+                }
+            }
+        }
+
+        for (i, status) in self.planets.iter_mut().enumerate() {
+            let held_neighbours = open_lines.iter()
+                .filter(|&&(a, b)| (a == i && is_held(b)) || (b == i && is_held(a)))
+                .count();
+            status.supply = if held[i] || held_neighbours >= 2 {
+                SupplyStatus::Reinforced
+            } else if linked[i] {
+                SupplyStatus::Linked
+            } else {
+                SupplyStatus::CutOff
+            };
         }
     }
 
-    /// Update war state each frame (called from ship phase).
-    fn update(&mut self, dt: f32) {
+    /// Supply modifiers for a drop onto `planet_idx` (neutral for an unknown planet).
+    fn supply_modifiers(&self, planet_idx: usize) -> SupplyModifiers {
+        self.planets.get(planet_idx).map_or(SupplyStatus::Linked, |p| p.supply).modifiers()
+    }
+
+    /// Update war state each frame (called from ship phase). Returns the rewards of orders
+    /// completed this frame.
+    fn update(&mut self, dt: f32) -> Vec<OrderReward> {
         self.holo_rotation += dt * 0.2;
         self.ticker_offset += dt * 40.0; // scrolling ticker speed
+        self.update_supply();
 
-        // Bug counter-attacks slowly erode liberation on contested planets
+        // Bug counter-attacks slowly erode liberation on contested planets; the urgency dies
+        // down over time, faster where supply is cut
         for status in &mut self.planets {
             if status.defense_urgency > 0.0 && !status.liberated {
-                status.liberation = (status.liberation - status.defense_urgency * 0.001 * dt).max(0.0);
+                let erosion = status.defense_urgency * 0.001;
+                status.liberation = (status.liberation - erosion * dt).max(0.0);
+                let decay = DEFENSE_URGENCY_DECAY * status.supply.modifiers().urgency_decay_mult;
+                status.defense_urgency = (status.defense_urgency - decay * dt).max(0.0);
             }
         }
 
-        // Update major order progress from planet liberations
+        // Major order progress: liberation of the target planets, or (with no targets) the share
+        // of supply lines secured by holding both ends
+        let secured = self.supply_lines.iter().filter(|line| !line.contested).count();
+        let supply_progress = secured as f32 / self.supply_lines.len().max(1) as f32;
+        let mut granted = Vec::new();
         for order in &mut self.major_orders {
             if order.completed { continue; }
-            if !order.target_planets.is_empty() {
+            order.progress = if order.target_planets.is_empty() {
+                supply_progress
+            } else {
                 let total: f32 = order.target_planets.iter()
                     .filter_map(|&i| self.planets.get(i))
                     .map(|p| p.liberation)
                     .sum();
-                order.progress = total / order.target_planets.len().max(1) as f32;
-                if order.progress >= 1.0 {
-                    order.completed = true;
-                }
+                total / order.target_planets.len() as f32
+            };
+            if order.progress >= 1.0 {
+                order.completed = true;
+                granted.extend(order.grants);
             }
         }
+        granted
     }
 
     /// Record kills from a mission (call after extraction or gameplay).
@@ -1166,46 +1181,85 @@ impl GalacticWarState {
     }
 }
 
-/// Persisted galactic war + universe (save file).
-#[derive(serde::Serialize, serde::Deserialize)]
-struct SaveData {
-    universe_seed: u64,
-    current_system_idx: usize,
-    war_state: GalacticWarState,
-}
-
-fn galactic_war_save_path() -> PathBuf {
-    std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")).join("opensst_save.ron")
-}
-
-fn save_galactic_war(universe_seed: u64, current_system_idx: usize, war_state: &GalacticWarState) {
-    let data = SaveData {
-        universe_seed,
-        current_system_idx,
-        war_state: war_state.clone(),
-    };
-    let path = galactic_war_save_path();
-    if let Ok(s) = ron::ser::to_string_pretty(&data, ron::ser::PrettyConfig::default()) {
-        if let Err(e) = std::fs::write(&path, s) {
-            log::warn!("Failed to save galactic war: {}", e);
-        }
+/// Burrow ambushes erupt in this ring (m) around the trooper — close enough to surprise.
+const BURROW_SPAWN_MIN_DIST: f32 = 6.0;
+const BURROW_SPAWN_MAX_DIST: f32 = 14.0;
+/// Base cover: bugs within this reach (m) of it chew it at this damage per second each, up to a
+/// few bugs at once.
+const COVER_CHEW_REACH: f32 = 1.5;
+const COVER_CHEW_DPS: f32 = 12.0;
+const COVER_CHEW_MAX_BUGS: usize = 4;
+/// Hive Queen: how far from the trooper she surfaces, and how far from her the brood digs up (m).
+const QUEEN_SPAWN_DISTANCE: std::ops::Range<f32> = 30.0..45.0;
+const QUEEN_BROOD_DISTANCE: std::ops::Range<f32> = 6.0..12.0;
+
+/// Bug LODs: clustering cell (unit mesh space) and switch distance. Big hordes are mostly far away.
+const BUG_LODS: [LodLevel; 2] = [
+    LodLevel { cell_size: 0.06, distance: 45.0 },
+    LodLevel { cell_size: 0.14, distance: 110.0 },
+];
+
+/// Weakpoint hit crunch: optional sample, silent when missing.
+const WEAKPOINT_CRUNCH_SOUND: &str = "weakpoint_crunch";
+const WEAKPOINT_CRUNCH_PATH: &str = "assets/sounds/weakpoint_crunch.ogg";
+/// Scream of a citizen who just panicked: optional sample, silent when missing.
+const CITIZEN_SCREAM_SOUND: &str = "citizen_scream";
+const CITIZEN_SCREAM_PATH: &str = "assets/sounds/citizen_scream.ogg";
+/// Extra seconds a weakpoint kill adds to the kill streak window.
+const WEAKPOINT_STREAK_BONUS_SECS: f32 = 1.0;
+/// Sound occlusion behind rocks, walls and other environment colliders (terrain blocks fully).
+const PROP_SOUND_OCCLUSION: f32 = 0.6;
+
+/// How blocked the straight path from the listener to a sound source is, for muffling it:
+/// 1 behind a ridge of terrain, `PROP_SOUND_OCCLUSION` behind props and buildings, 0 in the open.
+fn sound_occlusion(physics: &PhysicsWorld, listener: Vec3, source: Vec3) -> f32 {
+    let to_source = source - listener;
+    let distance = to_source.length();
+    if distance < 0.5 {
+        return 0.0;
+    }
+    let groups = CollisionGroup::query(&[CollisionGroup::Terrain, CollisionGroup::Environment]);
+    // Stop just short of the source so the ground or wall it sits on doesn't count
+    match physics.raycast_filtered(listener, to_source / distance, distance - 0.5, groups) {
+        Some(hit) if physics.collider_in_group(hit.collider, CollisionGroup::Terrain) => 1.0,
+        Some(_) => PROP_SOUND_OCCLUSION,
+        None => 0.0,
     }
 }
 
-fn load_galactic_war() -> Option<(u64, usize, GalacticWarState)> {
-    let path = galactic_war_save_path();
-    let s = std::fs::read_to_string(&path).ok()?;
-    let data: SaveData = ron::from_str(&s).ok()?;
-    Some((data.universe_seed, data.current_system_idx, data.war_state))
-}
+/// Hitstop on big kills (Tanker down, chain reaction): game time drops to this fraction...
+const HITSTOP_SCALE: f32 = 0.05;
+/// ...for this long (real time)...
+const HITSTOP_HOLD: std::time::Duration = std::time::Duration::from_millis(80);
+/// ...then eases back to full speed over this.
+const HITSTOP_RAMP: std::time::Duration = std::time::Duration::from_millis(120);
+/// Real seconds after a hitstop before another can fire, so rapid kills don't become a slideshow.
+const HITSTOP_COOLDOWN_SECS: f32 = 1.5;
+
+/// Bullet hole decal size (m across).
+const BULLET_HOLE_SIZE: f32 = 0.14;
+/// Scorch decal size per meter of explosion radius.
+const SCORCH_SIZE_PER_RADIUS: f32 = 0.6;
+
+/// Seed for the terrain material textures (same look on every planet; block colors tint them).
+const TERRAIN_MATERIAL_SEED: u64 = 0x7E77_A1B5;
+/// Plate normal map (hull steel, powered armor): texture size and plates per tile.
+const PLATE_NORMAL_SIZE: u32 = 256;
+const PLATE_NORMAL_PANELS: u32 = 4;
+
+/// Rock LODs for scattered rocks and rock landmarks.
+const ROCK_LODS: [LodLevel; 2] = [
+    LodLevel { cell_size: 0.15, distance: 80.0 },
+    LodLevel { cell_size: 0.35, distance: 180.0 },
+];
 
 /// Authored STE-style bug meshes (replaces procedural BugMeshGenerator).
 struct AuthoredBugMeshes {
-    warrior: Mesh,
-    charger: Mesh,
-    spitter: Mesh,
-    tanker: Mesh,
-    hopper: Mesh,
+    warrior: LodMesh,
+    charger: LodMesh,
+    spitter: LodMesh,
+    tanker: LodMesh,
+    hopper: LodMesh,
 }
 
 impl AuthoredBugMeshes {
@@ -1219,17 +1273,19 @@ impl AuthoredBugMeshes {
         }
     }
 
-    fn upload(device: &wgpu::Device, (vertices, indices): (Vec<renderer::Vertex>, Vec<u32>)) -> Mesh {
-        Mesh::from_data(device, &vertices, &indices)
+    fn upload(device: &wgpu::Device, (vertices, indices): (Vec<renderer::Vertex>, Vec<u32>)) -> LodMesh {
+        LodMesh::from_data(device, &vertices, &indices, &BUG_LODS)
     }
 
-    fn get(&self, bug_type: BugType) -> &Mesh {
+    fn get(&self, bug_type: BugType) -> &LodMesh {
         match bug_type {
             BugType::Warrior => &self.warrior,
             BugType::Charger => &self.charger,
             BugType::Spitter => &self.spitter,
             BugType::Tanker => &self.tanker,
             BugType::Hopper => &self.hopper,
+            // The Queen is a scaled-up Tanker silhouette
+            BugType::Queen => &self.tanker,
         }
     }
 }
@@ -1238,9 +1294,9 @@ impl AuthoredBugMeshes {
 struct EnvironmentMeshes {
     ground: Mesh,
     cube: Mesh,
-    rock: Mesh,
-    rock_chunk: Mesh,
-    rock_boulder: Mesh,
+    rock: LodMesh,
+    rock_chunk: LodMesh,
+    rock_boulder: LodMesh,
     /// First-person viewmodel (rifle)
     gun: Mesh,
     /// Bug hole rim (flattened sphere crater)
@@ -1253,8 +1309,6 @@ struct EnvironmentMeshes {
     crystal: Mesh,
     /// Generic prop sphere (for varied decorations)
     prop_sphere: Mesh,
-    /// Unit quad in XZ (normal Y) for Minecraft-style billboard particles (rain, snow, dust)
-    billboard_quad: Mesh,
     /// Beveled unit cube (UCF buildings — chamfered edges)
     beveled_cube: Mesh,
     /// Heinlein Skinnies (tall, gaunt humanoid mesh)
@@ -1274,11 +1328,11 @@ impl EnvironmentMeshes {
         let (v, idx) = authored_env_meshes::build_egg_cluster();
         let egg_cluster = Mesh::from_data(device, &v, &idx);
         let (v, idx) = authored_env_meshes::build_rock();
-        let rock = Mesh::from_data(device, &v, &idx);
+        let rock = LodMesh::from_data(device, &v, &idx, &ROCK_LODS);
         let (v, idx) = authored_env_meshes::build_rock_chunk();
-        let rock_chunk = Mesh::from_data(device, &v, &idx);
+        let rock_chunk = LodMesh::from_data(device, &v, &idx, &ROCK_LODS);
         let (v, idx) = authored_env_meshes::build_rock_boulder();
-        let rock_boulder = Mesh::from_data(device, &v, &idx);
+        let rock_boulder = LodMesh::from_data(device, &v, &idx, &ROCK_LODS);
         // Solid unit cube: corners connect (used for Earth buildings, UCF landmarks, etc.)
         let cube = Mesh::cube(device);
         let (v, idx) = authored_env_meshes::build_beveled_cube();
@@ -1298,7 +1352,6 @@ impl EnvironmentMeshes {
             egg_cluster,
             crystal: Mesh::sphere(device, 1.0, 6, 4),       // Crystal spike (stretched via transform)
             prop_sphere: Mesh::sphere(device, 1.0, 8, 6),   // Generic decoration
-            billboard_quad: Mesh::plane(device, 1.0),      // Camera-facing particle quads (Minecraft style)
             beveled_cube,
             skinny_mesh,
             hive_cave_entrance,
@@ -1314,23 +1367,44 @@ struct TerrainChunkData {
     mesh: Mesh,
     water_mesh: Option<Mesh>,
     collider_handle: ColliderHandle,
+    /// Boundary column heights as of the last edge sync (indexed like `ChunkEdge::ALL`).
+    /// Used to tell which edge columns a deformation actually changed.
+    edge_heights: [Vec<f32>; 4],
 }
 
+/// Default, minimum and maximum for the view distance setting (chunk radius at high altitude).
+const DEFAULT_VIEW_DISTANCE: i32 = 5;
+const MIN_VIEW_DISTANCE: i32 = 2;
+const MAX_VIEW_DISTANCE: i32 = 16;
+/// Default and minimum loaded-chunk budget. The minimum still holds a 3x3 around the player.
+const DEFAULT_CHUNK_BUDGET: usize = 256;
+const MIN_CHUNK_BUDGET: usize = 9;
+
 /// Manages an infinite grid of terrain chunks around the player.
 struct ChunkManager {
     chunks: HashMap<(i32, i32), TerrainChunkData>,
     chunk_size: f32,
     chunk_resolution: u32,
+    /// Chunk radius loaded this frame (from altitude, capped by `max_view_distance`).
     view_distance: i32,
+    /// Graphics setting: chunk radius loaded at the highest altitudes. Lower altitudes load less.
+    max_view_distance: i32,
+    /// Most chunks kept loaded; beyond this the farthest from the player are unloaded first.
+    chunk_budget: usize,
     planet_seed: u64,
     height_scale: f32,
     frequency: f64,
-    /// Multi-biome sampler for per-vertex biome colors and height variation.
-    planet_biomes: PlanetBiomes,
+    /// Multi-biome sampler for per-vertex biome colors and height variation (shared with workers).
+    planet_biomes: Arc<PlanetBiomes>,
     /// If true, terrain is smooth (no voxel quantization) and gentler — e.g. terraformed Earth.
     use_smooth_terrain: bool,
     /// Chunks that need mesh+collider rebuild; drained each frame (throttled) to avoid artillery lag.
     pending_chunk_rebuilds: Vec<(i32, i32)>,
+    /// Block edits (craters, foxholes, mounds) of unloaded chunks, replayed when they load again.
+    /// Kept until the planet is left.
+    block_edits: HashMap<(i32, i32), Arc<BlockEdits>>,
+    /// Background threads generating new chunks; the main thread only uploads results.
+    workers: ChunkWorkers,
 }
 
 impl ChunkManager {
@@ -1346,18 +1420,34 @@ impl ChunkManager {
             chunk_size: 96.0,   // larger chunks = more terrain per chunk, more destruction area
             chunk_resolution: 128, // finer heightmap for more deformation detail
             view_distance: 5,  // big infinite voxel world: load more chunks (Minecraft-style draw)
+            max_view_distance: DEFAULT_VIEW_DISTANCE,
+            chunk_budget: DEFAULT_CHUNK_BUDGET,
             planet_seed,
             height_scale,
             frequency,
-            planet_biomes,
+            planet_biomes: Arc::new(planet_biomes),
             use_smooth_terrain,
             pending_chunk_rebuilds: Vec::new(),
+            block_edits: HashMap::new(),
+            workers: ChunkWorkers::new(),
         }
     }
 
+    /// Set the chunk radius loaded at high altitude (clamped to `MIN_VIEW_DISTANCE..=MAX_VIEW_DISTANCE`).
+    fn set_view_distance(&mut self, chunks: i32) {
+        self.max_view_distance = chunks.clamp(MIN_VIEW_DISTANCE, MAX_VIEW_DISTANCE);
+    }
+
+    /// Cap on loaded chunks. The view distance shrinks if its square of chunks wouldn't fit.
+    fn set_chunk_budget(&mut self, chunks: usize) {
+        self.chunk_budget = chunks.max(MIN_CHUNK_BUDGET);
+    }
+
     /// Remove all chunks and their physics colliders.
     fn clear_all(&mut self, physics: &mut PhysicsWorld) {
         self.pending_chunk_rebuilds.clear();
+        self.block_edits.clear();
+        self.workers.invalidate();
         for (_, chunk) in self.chunks.drain() {
             physics.remove_collider(chunk.collider_handle);
         }
@@ -1378,7 +1468,7 @@ impl ChunkManager {
         self.planet_seed = planet_seed;
         self.height_scale = height_scale;
         self.frequency = frequency;
-        self.planet_biomes = planet_biomes;
+        self.planet_biomes = Arc::new(planet_biomes);
         self.use_smooth_terrain = use_smooth_terrain;
         self.chunk_resolution = if use_smooth_terrain { 160 } else { 128 };
     }
@@ -1415,24 +1505,34 @@ impl ChunkManager {
             for cx in min_cx..=max_cx {
                 if !self.chunks.contains_key(&(cx, cz)) {
                     let chunk = self.generate_chunk(cx, cz, device, physics);
-                    self.chunks.insert((cx, cz), chunk);
+                    self.insert_chunk((cx, cz), chunk);
                 }
             }
         }
     }
 
     /// Load/unload chunks around player. Dynamically adjusts view distance by altitude.
-    /// Batches chunk loading to max 2 per frame to avoid hitches.
+    /// Missing chunks are generated on worker threads; finished ones are uploaded here.
     fn update(&mut self, player_pos: Vec3, device: &wgpu::Device, physics: &mut PhysicsWorld) {
-        // Dynamic view distance: increase at higher altitudes for better orbital view
-        let altitude = player_pos.y.max(0.0);
-        self.view_distance = if altitude > 600.0 { 5 }
-            else if altitude > 300.0 { 4 }
-            else if altitude > 100.0 { 3 }
-            else { 3 };
+        // Dynamic view distance: increase at higher altitudes for better orbital view, up to the
+        // setting, and never more than the chunk budget can hold
+        self.view_distance = Self::budgeted_view_distance(player_pos.y, self.max_view_distance, self.chunk_budget);
 
         let (pcx, pcz) = Self::player_chunk(player_pos, self.chunk_size);
         let vd = self.view_distance;
+        let unload_dist = vd + 2;
+
+        // Upload chunks the workers have finished (skip ones the player already left behind)
+        for build in self.workers.drain_finished() {
+            let (cx, cz) = build.key;
+            if (cx - pcx).abs() > unload_dist || (cz - pcz).abs() > unload_dist
+                || self.chunks.contains_key(&build.key)
+            {
+                continue;
+            }
+            let chunk = self.upload_chunk(build, device, physics);
+            self.insert_chunk((cx, cz), chunk);
+        }
 
         // Quick check: count how many chunks *should* exist vs *do* exist in range
         // If all loaded already, skip the expensive sort + alloc
@@ -1456,22 +1556,23 @@ impl ChunkManager {
                 }
             }
             // Sort closest first so we prioritize nearby chunks
-            desired.sort_unstable_by_key(|&(cx, cz)| (cx - pcx).abs() + (cz - pcz).abs());
-
-            // Load missing chunks (batched: max 2 per frame to spread GPU/CPU load)
-            let mut loaded = 0;
-            for &(cx, cz) in &desired {
-                if loaded >= 2 { break; }
-                if !self.chunks.contains_key(&(cx, cz)) {
-                    let chunk = self.generate_chunk(cx, cz, device, physics);
-                    self.chunks.insert((cx, cz), chunk);
-                    loaded += 1;
+            desired.sort_unstable_by_key(|&(cx, cz)| (cx - pcx).pow(2) + (cz - pcz).pow(2));
+
+            // Queue missing chunks for background generation (closest first). Once the queue
+            // turns one away it is full of nearer chunks, and so is every later request.
+            self.workers.set_focus((pcx, pcz));
+            for &key in &desired {
+                if !self.chunks.contains_key(&key) && !self.workers.is_in_flight(key) {
+                    let config = self.chunk_config(key.0, key.1);
+                    let edits = self.block_edits.get(&key).cloned();
+                    if !self.workers.request(key, config, Arc::clone(&self.planet_biomes), edits) {
+                        break;
+                    }
                 }
             }
         }
 
         // Unload distant chunks (beyond view_distance + 2)
-        let unload_dist = vd + 2;
         let to_remove: Vec<(i32, i32)> = self
             .chunks
             .keys()
@@ -1481,20 +1582,60 @@ impl ChunkManager {
             .cloned()
             .collect();
         for key in to_remove {
-            if let Some(chunk) = self.chunks.remove(&key) {
-                physics.remove_collider(chunk.collider_handle);
+            self.unload_chunk(key, physics);
+        }
+
+        // Over budget (the margin ring between view and unload distance can hold a lot):
+        // drop the farthest chunks
+        for key in Self::over_budget(self.chunks.keys().copied(), (pcx, pcz), self.chunk_budget) {
+            self.unload_chunk(key, physics);
+        }
+    }
+
+    /// Chunk radius to load at `altitude`: up to `max_view_distance` high up, less near the
+    /// ground, and never a square bigger than `chunk_budget` chunks.
+    fn budgeted_view_distance(altitude: f32, max_view_distance: i32, chunk_budget: usize) -> i32 {
+        let altitude = altitude.max(0.0);
+        let altitude_drop = if altitude > 600.0 { 0 } else if altitude > 300.0 { 1 } else { 2 };
+        let budget_radius = ((chunk_budget as f32).sqrt() as i32 - 1) / 2;
+        (max_view_distance - altitude_drop).min(budget_radius).max(1)
+    }
+
+    /// Chunks to unload so at most `chunk_budget` remain, farthest from the player's chunk first.
+    fn over_budget(
+        loaded: impl Iterator<Item = (i32, i32)>,
+        (pcx, pcz): (i32, i32),
+        chunk_budget: usize,
+    ) -> Vec<(i32, i32)> {
+        let mut by_distance: Vec<(i32, i32)> = loaded.collect();
+        if by_distance.len() <= chunk_budget {
+            return Vec::new();
+        }
+        by_distance.sort_unstable_by_key(|&(cx, cz)| std::cmp::Reverse((cx - pcx).pow(2) + (cz - pcz).pow(2)));
+        let excess = by_distance.len() - chunk_budget;
+        by_distance.truncate(excess);
+        by_distance
+    }
+
+    /// Add a loaded chunk. It now carries its own edits, so the stored copy is dropped.
+    fn insert_chunk(&mut self, key: (i32, i32), chunk: TerrainChunkData) {
+        self.block_edits.remove(&key);
+        self.chunks.insert(key, chunk);
+    }
+
+    /// Drop a chunk and its collider, keeping its block edits for when it loads again.
+    fn unload_chunk(&mut self, key: (i32, i32), physics: &mut PhysicsWorld) {
+        if let Some(mut chunk) = self.chunks.remove(&key) {
+            physics.remove_collider(chunk.collider_handle);
+            if !chunk.voxel.edits.is_empty() {
+                self.block_edits.insert(key, Arc::new(std::mem::take(&mut chunk.voxel.edits)));
             }
         }
     }
 
-    fn generate_chunk(
-        &self,
-        cx: i32,
-        cz: i32,
-        device: &wgpu::Device,
-        physics: &mut PhysicsWorld,
-    ) -> TerrainChunkData {
-        let config = TerrainConfig {
+    /// Terrain generation parameters for chunk (cx, cz).
+    fn chunk_config(&self, cx: i32, cz: i32) -> TerrainConfig {
+        TerrainConfig {
             size: self.chunk_size,
             resolution: self.chunk_resolution,
             height_scale: self.height_scale,
@@ -1503,47 +1644,44 @@ impl ChunkManager {
             offset_z: cz as f32 * self.chunk_size,
             seed: self.planet_seed,
             ..Default::default()
-        };
-        let voxel = VoxelChunk::generate(&config, Some(&self.planet_biomes));
+        }
+    }
 
-        // Build GPU mesh from voxel (culled cube faces; water excluded for transparent pass)
-        let (terrain_vertices, terrain_indices) = voxel.to_mesh();
-        let vertices: Vec<renderer::Vertex> = terrain_vertices
-            .iter()
-            .map(|v| renderer::Vertex {
-                position: v.position,
-                normal: v.normal,
-                tex_coords: v.uv,
-                color: v.color,
-            })
-            .collect();
-        let mesh = Mesh::from_data(device, &vertices, &terrain_indices);
+    /// Generate a chunk synchronously on the main thread (used when terrain must exist now).
+    fn generate_chunk(
+        &self,
+        cx: i32,
+        cz: i32,
+        device: &wgpu::Device,
+        physics: &mut PhysicsWorld,
+    ) -> TerrainChunkData {
+        let config = self.chunk_config(cx, cz);
+        let edits = self.block_edits.get(&(cx, cz)).map(|e| e.as_ref());
+        let build = ChunkBuild::generate((cx, cz), &config, &self.planet_biomes, edits, self.workers.generation());
+        self.upload_chunk(build, device, physics)
+    }
 
-        // Transparent water mesh (Minecraft-style)
-        let (water_vertices, water_indices) = voxel.to_water_mesh();
-        let water_mesh = if water_vertices.is_empty() {
-            None
-        } else {
-            let wv: Vec<renderer::Vertex> = water_vertices
-                .iter()
-                .map(|v| renderer::Vertex {
-                    position: v.position,
-                    normal: v.normal,
-                    tex_coords: v.uv,
-                    color: v.color,
-                })
-                .collect();
-            Some(Mesh::from_data(device, &wv, &water_indices))
-        };
+    /// Upload a generated chunk's meshes and create its physics heightfield.
+    fn upload_chunk(
+        &self,
+        build: ChunkBuild,
+        device: &wgpu::Device,
+        physics: &mut PhysicsWorld,
+    ) -> TerrainChunkData {
+        let mesh = Mesh::new(device, &build.vertices, &build.indices);
+        let water_mesh = build
+            .water
+            .as_ref()
+            .map(|(vertices, indices)| Mesh::new(device, vertices, indices));
 
         // Add physics heightfield from voxel top surface (translation = chunk min corner, not center)
-        let heightmap = voxel.to_heightmap();
+        let voxel = build.voxel;
         let nrows = voxel.nz + 1;
         let ncols = voxel.nx + 1;
         let offset_min_x = voxel.offset_x - self.chunk_size * 0.5;
         let offset_min_z = voxel.offset_z - self.chunk_size * 0.5;
         let collider_handle = physics.add_terrain_heightfield_at(
-            &heightmap,
+            &build.heightmap,
             nrows,
             ncols,
             self.chunk_size,
@@ -1552,11 +1690,13 @@ impl ChunkManager {
             offset_min_z,
         );
 
+        let edge_heights = ChunkEdge::ALL.map(|edge| voxel.edge_heights(edge));
         TerrainChunkData {
             voxel,
             mesh,
             water_mesh,
             collider_handle,
+            edge_heights,
         }
     }
 
@@ -1583,13 +1723,14 @@ impl ChunkManager {
     /// True when the surface at (x,z) is water (not just "below water level").
     /// Crater floors and dry terrain below sea level are not treated as water.
     pub fn is_in_water(&self, x: f32, z: f32) -> bool {
+        self.surface_block_at(x, z) == Some(procgen::BlockId::Water)
+    }
+
+    /// Top block at (x,z), or None where no chunk is loaded.
+    pub fn surface_block_at(&self, x: f32, z: f32) -> Option<procgen::BlockId> {
         let cx = Self::world_to_chunk(x, self.chunk_size);
         let cz = Self::world_to_chunk(z, self.chunk_size);
-        if let Some(chunk) = self.chunks.get(&(cx, cz)) {
-            chunk.voxel.surface_block_at(x, z) == Some(procgen::BlockId::Water)
-        } else {
-            false
-        }
+        self.chunks.get(&(cx, cz)).and_then(|chunk| chunk.voxel.surface_block_at(x, z))
     }
 
     /// Effective walkable height (terrain or water surface). Use for spawn and object collision.
@@ -1621,9 +1762,47 @@ impl ChunkManager {
         chunk_keys.to_vec()
     }
 
-    /// Sync height at shared edges between modified chunks. No-op for voxel; returns keys to rebuild.
+    /// Copy modified boundary column heights into the neighbouring chunk's matching edge column
+    /// (and corner columns into the diagonal chunk), so meshes and heightfield colliders agree at
+    /// shared borders. Returns keys to rebuild (the modified chunks plus any neighbours that were changed).
     fn sync_chunk_edge_heights(&mut self, modified_keys: &[(i32, i32)]) -> Vec<(i32, i32)> {
-        modified_keys.to_vec()
+        let mut to_rebuild: Vec<(i32, i32)> = modified_keys.to_vec();
+        for &key in modified_keys {
+            for (e, edge) in ChunkEdge::ALL.into_iter().enumerate() {
+                let Some(chunk) = self.chunks.get(&key) else { break };
+                let changed = chunk.voxel.changed_edge_heights(edge, &chunk.edge_heights[e]);
+                if changed.is_empty() {
+                    continue;
+                }
+                let (dx, dz) = edge.neighbor_offset();
+                let neighbor_key = (key.0 + dx, key.1 + dz);
+                if let Some(neighbor) = self.chunks.get_mut(&neighbor_key) {
+                    if neighbor.voxel.sync_edge_from(edge, &changed) && !to_rebuild.contains(&neighbor_key) {
+                        to_rebuild.push(neighbor_key);
+                    }
+                }
+            }
+            for corner in ChunkCorner::ALL {
+                let Some(chunk) = self.chunks.get(&key) else { break };
+                let Some(height) = chunk.voxel.changed_corner_height(corner, &chunk.edge_heights) else {
+                    continue;
+                };
+                let (dx, dz) = corner.neighbor_offset();
+                let neighbor_key = (key.0 + dx, key.1 + dz);
+                if let Some(neighbor) = self.chunks.get_mut(&neighbor_key) {
+                    if neighbor.voxel.sync_corner_from(corner, height) && !to_rebuild.contains(&neighbor_key) {
+                        to_rebuild.push(neighbor_key);
+                    }
+                }
+            }
+        }
+        // Snapshot new edges so the next deformation only propagates its own changes
+        for key in &to_rebuild {
+            if let Some(chunk) = self.chunks.get_mut(key) {
+                chunk.edge_heights = ChunkEdge::ALL.map(|edge| chunk.voxel.edge_heights(edge));
+            }
+        }
+        to_rebuild
     }
 
     /// Flatten terrain inside a circle to a single height (e.g. city core). Returns chunk keys modified.
@@ -1771,9 +1950,10 @@ impl ChunkManager {
                     normal: v.normal,
                     tex_coords: v.uv,
                     color: v.color,
+                    tangent: [0.0; 4],
                 })
                 .collect();
-            chunk.mesh = Mesh::from_data(device, &vertices, &terrain_indices);
+            chunk.mesh = Mesh::new(device, &vertices, &terrain_indices);
             let (water_vertices, water_indices) = chunk.voxel.to_water_mesh();
             chunk.water_mesh = if water_vertices.is_empty() {
                 None
@@ -1785,9 +1965,10 @@ impl ChunkManager {
                         normal: v.normal,
                         tex_coords: v.uv,
                         color: v.color,
+                        tangent: [0.0; 4],
                     })
                     .collect();
-                Some(Mesh::from_data(device, &wv, &water_indices))
+                Some(Mesh::new(device, &wv, &water_indices))
             };
             physics.remove_collider(chunk.collider_handle);
             let heightmap = chunk.voxel.to_heightmap();
@@ -1976,55 +2157,43 @@ impl ChunkManager {
         }
     }
 
-    /// Check if a collider handle belongs to a terrain chunk.
-    fn is_terrain_collider(&self, handle: ColliderHandle) -> bool {
-        self.chunks.values().any(|chunk| chunk.collider_handle == handle)
-    }
-
-    /// Render visible chunks with frustum culling. Call after update_terrain uniform.
-    fn render_visible(
-        &self,
+    /// Render chunks whose bounds intersect the camera frustum. Call after update_terrain uniform.
+    /// `planet_radius` matches the shader curvature drop so distant chunks aren't culled early.
+    /// Water meshes go into `transparent` to be drawn sorted after all opaque geometry.
+    fn render_visible<'a>(
+        &'a self,
         renderer: &Renderer,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
         camera: &Camera,
+        planet_radius: f32,
+        transparent: &mut TransparentQueue<'a>,
     ) {
+        let frustum = camera.frustum();
         let cam_pos = camera.position();
-        let cam_fwd = camera.forward();
-        // Horizontal-only forward for ground-level culling
-        let cam_fwd_h = Vec3::new(cam_fwd.x, 0.0, cam_fwd.z).normalize_or_zero();
-        let near_dist_sq = (self.chunk_size * 1.5) * (self.chunk_size * 1.5);
-
-        for (&(cx, cz), chunk) in &self.chunks {
-            let chunk_center = Vec3::new(
-                cx as f32 * self.chunk_size,
-                0.0,
-                cz as f32 * self.chunk_size,
-            );
-            let to_chunk = Vec3::new(
-                chunk_center.x - cam_pos.x,
-                0.0,
-                chunk_center.z - cam_pos.z,
-            );
-            let dist_sq = to_chunk.length_squared();
 
-            // Always render the nearest chunks (player is standing on them)
-            if dist_sq > near_dist_sq {
-                // Frustum cull: skip chunks that are behind the camera
-                let dot = to_chunk.normalize_or_zero().dot(cam_fwd_h);
-                if dot < -0.3 {
-                    continue;
-                }
+        for chunk in self.chunks.values() {
+            let (mut min, max) = chunk.voxel.bounds();
+            let center = (min + max) * 0.5;
+            if planet_radius > 0.0 {
+                // Shader bends terrain down by d^2 / 2R; extend the box by the worst case (far corner).
+                let dx = (cam_pos.x - min.x).abs().max((cam_pos.x - max.x).abs());
+                let dz = (cam_pos.z - min.z).abs().max((cam_pos.z - max.z).abs());
+                min.y -= (dx * dx + dz * dz) / (2.0 * planet_radius);
+            }
+            if !frustum.intersects_aabb(min, max) {
+                continue;
             }
 
             renderer.render_terrain(encoder, view, &chunk.mesh);
             if let Some(ref water_mesh) = chunk.water_mesh {
-                renderer.render_water(encoder, view, water_mesh);
+                transparent.push_water(water_mesh, center);
             }
         }
     }
 
-    /// Render visible terrain chunks into the shadow map. Same culling as render_visible.
+    /// Render terrain chunks into the shadow map. Only culls chunks behind the camera, since
+    /// off-screen chunks can still cast shadows into view.
     fn render_visible_shadow(
         &self,
         renderer: &Renderer,
@@ -2064,13 +2233,46 @@ impl ChunkManager {
 impl GameState {
     async fn new(window: Arc<Window>) -> Result<Self> {
         // Initialize renderer
-        let renderer = Renderer::new(window.clone()).await?;
+        let mut renderer = Renderer::new(window.clone()).await?;
+
+        // Terrain material detail (rock, dirt, sand, snow, grass), tinted per block by the shader
+        let material_layers: Vec<Vec<u8>> = TextureGenerator::new(TERRAIN_MATERIAL_SEED)
+            .generate_terrain_materials(TERRAIN_MATERIAL_SIZE)
+            .iter()
+            .map(TextureData::to_bytes)
+            .collect();
+        renderer.upload_terrain_materials(&material_layers);
+        let plates = TextureGenerator::new(TERRAIN_MATERIAL_SEED).generate_plate_normals(PLATE_NORMAL_SIZE, PLATE_NORMAL_PANELS);
+        let plate_material = renderer.create_material("Plate Normals", PLATE_NORMAL_SIZE, &plates.to_bytes());
 
         // Create camera
         let mut camera = Camera::new(Vec3::new(0.0, 2.0, 10.0));
         let (width, height) = renderer.dimensions();
         camera.set_aspect(width, height);
 
+        let mut audio = match AudioSystem::new() {
+            Ok(audio) => Some(audio),
+            Err(e) => {
+                log::warn!("Audio disabled: {}", e);
+                None
+            }
+        };
+        let footsteps = Footsteps::load(audio.as_mut());
+        if let Some(audio) = audio.as_mut() {
+            let crunch = std::path::Path::new(WEAKPOINT_CRUNCH_PATH);
+            if crunch.exists() {
+                if let Err(e) = audio.load_sound(WEAKPOINT_CRUNCH_SOUND, crunch) {
+                    log::warn!("Weakpoint crunch sample failed to load: {}", e);
+                }
+            }
+            let scream = std::path::Path::new(CITIZEN_SCREAM_PATH);
+            if scream.exists() {
+                if let Err(e) = audio.load_sound(CITIZEN_SCREAM_SOUND, scream) {
+                    log::warn!("Citizen scream sample failed to load: {}", e);
+                }
+            }
+        }
+
         // Create procedural meshes
         let bug_meshes = AuthoredBugMeshes::new(renderer.device());
         let environment_meshes = EnvironmentMeshes::new(renderer.device());
@@ -2095,6 +2297,12 @@ impl GameState {
 
         // Initialize physics
         let mut physics = PhysicsWorld::new();
+        let (player_membership, player_filter) = CollisionGroup::player_presence();
+        let player_sensor = physics.add_sensor_with_groups(
+            Vec3::ZERO,
+            rapier3d::prelude::SharedShape::capsule_y(0.55, 0.35),
+            rapier3d::prelude::InteractionGroups::new(player_membership, player_filter),
+        );
 
         // Generate universe and initial star system (or load persisted galactic war)
         let universe_seed: u64 = 42;
@@ -2102,11 +2310,27 @@ impl GameState {
         let mut current_system_idx = 0;
         let mut current_system = universe.generate_system(current_system_idx);
         let num_system_planets = current_system.bodies.len();
-        let mut war_state_initial = GalacticWarState::new(num_system_planets);
+        let mut war_state_initial = GalacticWarState::new(num_system_planets, &mut GameRng::new(current_system.seed));
 
         let mut effective_seed = universe_seed;
         let mut has_save = false;
-        if let Some((saved_seed, saved_sys_idx, saved_war)) = load_galactic_war() {
+        let saved = match save::load() {
+            Ok(saved) => saved,
+            Err(e) => {
+                log::warn!("Galactic war save rejected: {}", e);
+                if let Some(backup) = save::back_up_rejected() {
+                    log::warn!("Kept a copy of the rejected save at {}", backup.display());
+                }
+                None
+            }
+        };
+        let mut saved_difficulty = Difficulty::default();
+        let mut saved_rewards = Vec::new();
+        let mut saved_heavy_weapon = WeaponType::MachineGun;
+        if let Some(save::SaveData { universe_seed: saved_seed, current_system_idx: saved_sys_idx, war_state: saved_war, difficulty, granted_rewards, heavy_weapon, .. }) = saved {
+            saved_difficulty = difficulty;
+            saved_rewards = granted_rewards;
+            saved_heavy_weapon = heavy_weapon;
             universe = Universe::generate(saved_seed, 100);
             current_system = universe.generate_system(saved_sys_idx);
             current_system_idx = saved_sys_idx;
@@ -2148,11 +2372,13 @@ impl GameState {
         // Initialize FPS player on terrain (Hunter class by default); use walkable height to avoid spawning underwater
         let spawn_y = chunk_manager.walkable_height(0.0, 0.0) + 1.8;
         let spawn_pos = Vec3::new(0.0, spawn_y, 0.0);
-        let player = FPSPlayer::new(
+        let mut player = FPSPlayer::new(
             PlayerClass::Hunter,
             "Trooper".to_string(),
             spawn_pos,
         );
+        player.apply_difficulty(saved_difficulty);
+        player.equip_heavy(saved_heavy_weapon);
 
         // Start camera at player position (on terrain)
         camera.transform.position = spawn_pos;
@@ -2162,7 +2388,8 @@ impl GameState {
         let horde_ai = HordeAI::new(flow_field);
 
         // Bug spawner (planet danger sets bug count and mix; spawn rate from planet.bug_spawn_rate())
-        let mut spawner = BugSpawner::new(planet.bug_spawn_rate(), planet.danger_level);
+        let rng = GameRng::new(effective_seed);
+        let mut spawner = BugSpawner::new(planet.bug_spawn_rate(), planet.danger_level, saved_difficulty, rng.fork(planet.seed));
         let biome_table = get_biome_feature_table(planet.primary_biome);
         spawner.set_biome_variant(biome_table.bug_variant, biome_table.variant_chance);
 
@@ -2174,10 +2401,17 @@ impl GameState {
         let mut game: Result<Self> = Ok(Self {
             world,
             time: Time::new(),
-            input: InputState::new(),
+            input: {
+                let mut input = InputState::new();
+                input.set_action_map(ActionMap::load(&config::keybindings_path()));
+                input
+            },
+            gamepads: GamepadManager::new(),
             physics,
             renderer,
             camera,
+            audio,
+            footsteps,
             bug_meshes,
             environment_meshes,
             gore_mesh,
@@ -2185,14 +2419,17 @@ impl GameState {
             tracer_mesh,
             flash_mesh,
             billboard_mesh,
+            plate_material,
             deformation_buffer: vec![0.0; (DEFORM_TEXTURE_SIZE * DEFORM_TEXTURE_SIZE) as usize],
-            snow_accumulation_buffer: vec![0.0; (DEFORM_TEXTURE_SIZE * DEFORM_TEXTURE_SIZE) as usize],
-            snow_accumulation_origin: (0.0, 0.0),
+            snow: snow::SnowField::new(),
+            blood_decal_buffer: vec![0.0; (DEFORM_TEXTURE_SIZE * DEFORM_TEXTURE_SIZE) as usize],
             effects: EffectsManager::new(),
             player,
             combat: CombatSystem::new(),
             bug_combat: BugCombatSystem::new(),
+            abilities: ability::AbilityState::default(),
             hud: HUDSystem::new(),
+            stratagem_input: stratagem::StratagemInput::default(),
             mission,
             horde_ai,
             spawner,
@@ -2200,6 +2437,7 @@ impl GameState {
             chunk_manager,
             planet,
             universe_seed: effective_seed,
+            rng,
             universe,
             current_system,
             current_system_idx,
@@ -2209,15 +2447,18 @@ impl GameState {
             universe_time_sec: 0.0,
             galaxy_map_open: false,
             galaxy_map_selected: 0,
+            galaxy_map: GalaxyMapView::default(),
             warp_sequence: None,
             warp_start_galaxy_position: None,
             warp_return_to_ship: false,
             drop_pod: None,
             squad_drop_pods: None,
+            squad_formation: FormationKind::default(),
             ship_state: None,
             deploy_planet_idx: None,
             approach_timer: 0.0,
             approach_flight_state: None,
+            flight_mode: FlightMode::Arcade,
             war_state: war_state_initial,
             settlement_center: None,
             earth_waypoints: None,
@@ -2225,11 +2466,11 @@ impl GameState {
             earth_road_colliders: Vec::new(),
             earth_building_colliders: Vec::new(),
             dialogue_state: DialogueState::default(),
+            dialogue_memory: DialogueMemory::default(),
             interaction_prompt: None,
             time_of_day: 0.25,  // start at noon
             weather: Weather::new(),
-            rain_drops: Vec::new(),
-            snow_particles: Vec::new(),
+            particles: ParticleSystem::new(),
             destruction: DestructionSystem::new(),
             game_messages: GameMessages::new(),
             phase: GamePhase::MainMenu,
@@ -2243,32 +2484,48 @@ impl GameState {
             total_gore_spawned: 0,
             physics_bodies_active: 0,
             tracer_projectiles: Vec::new(),
+            skinny_bolts: Vec::new(),
+            acid_globs: Vec::new(),
+            hole_collapses: Vec::new(),
+            hole_hint_given: false,
             debug: DebugSettings::new(),
             player_velocity: Vec3::ZERO,
             player_grounded: false,
+            player_controller: KinematicCharacterController {
+                // Enough to hug the ground running downhill at sprint speed
+                snap_distance: 0.5,
+                ..KinematicCharacterController::new(50f32.to_radians(), 0.4)
+            },
+            player_sensor,
+            player_triggers: HashSet::new(),
             hazard_slow_multiplier: 1.0,
             last_player_track_pos: None,
             ground_track_bug_timer: 0.0,
             squad_track_last: HashMap::new(),
             shovel_dig_cooldown: 0.0,
             screen_shake: ScreenShake::new(),
-            camera_recoil: 0.0,
-            crouch_hold_timer: 0.0,
+            hitstop_cooldown: 0.0,
+            recoil: weapons::Recoil::default(),
+            crouch_toggled: false,
+            combat_roll_timer: 0.0,
+            combat_roll_cooldown: 0.0,
+            melee_cooldown: 0.0,
+            combat_roll_dir: Vec3::ZERO,
             kill_streaks: KillStreakTracker::new(),
-            ambient_dust: AmbientDust::new(),
             biome_atmosphere: BiomeAtmosphere::new(initial_biome),
 
             viewmodel_anim: ViewmodelAnimState::new(),
+            viewmodel_rig: None,
             shell_casings: Vec::new(),
             grounded_shell_casings: Vec::new(),
 
             smoke_grenades: Vec::new(),
             smoke_clouds: Vec::new(),
-            smoke_grenade_cooldown: 0.0,
+            frag_grenades: Vec::new(),
+            grenade_cook: None,
 
             tac_fighters: Vec::new(),
             tac_bombs: Vec::new(),
-            tac_fighter_cooldown: 45.0, // First tac fighter after 45 seconds
             tac_fighter_available: true,
 
             artillery_shells: Vec::new(),
@@ -2276,21 +2533,23 @@ impl GameState {
             artillery_trail_particles: Vec::new(),
             grounded_artillery_shells: Vec::new(),
             artillery_barrage: None,
-            artillery_cooldown: 0.0,
+            artillery_impacts: Vec::new(),
 
             supply_crates: Vec::new(),
-            supply_drop_cooldown: 0.0,
             supply_drop_smoke: Vec::new(),
-            reinforce_cooldown: 0.0,
             reinforce_smoke: None,
             orbital_strike_smoke: None,
+            stratagem_cooldowns: stratagem::initial_cooldowns(),
 
     extraction: None,
-    extraction_cooldown: 0.0,
     extraction_squadmates_aboard: Vec::new(),
     extraction_collider: None,
     lz_smoke: None,
     next_mission_type: fps::MissionType::Extermination,
+    difficulty: saved_difficulty,
+    supply: SupplyStatus::Linked.modifiers(),
+    granted_rewards: saved_rewards,
+    heavy_weapon: saved_heavy_weapon,
     defense_base: None,
 });
 
@@ -2313,15 +2572,26 @@ impl GameState {
 
     fn update(&mut self) {
         self.time.update();
-        let raw_dt = self.time.delta_seconds();
+        let raw_dt = self.time.unscaled_delta_seconds();
         // Cap delta to avoid huge steps from hitches (keeps motion consistent).
-        let capped = (raw_dt * self.debug.time_scale).min(0.05);
+        let capped = self.time.delta_seconds().min(0.05);
         // Smooth delta so brief frame spikes don't cause one jerky frame. Use 0.4 (was 0.2) so
         // the game responds faster to frame time changes — overly aggressive smoothing can make
-        // the game feel laggy even at high FPS.
+        // the game feel laggy even at high FPS. Skip while paused so resume doesn't ramp up from zero.
         const SMOOTH: f32 = 0.4;
-        self.smoothed_dt = self.smoothed_dt * (1.0 - SMOOTH) + capped * SMOOTH;
-        let dt = self.smoothed_dt;
+        if self.time.is_dilated() {
+            // Hitstop has its own curve; smoothing would smear the freeze over several frames
+            self.smoothed_dt = capped;
+        } else if !self.time.is_paused() {
+            self.smoothed_dt = self.smoothed_dt * (1.0 - SMOOTH) + capped * SMOOTH;
+        }
+        self.hitstop_cooldown = (self.hitstop_cooldown - raw_dt).max(0.0);
+        let dt = if self.time.is_paused() { 0.0 } else { self.smoothed_dt };
+        // Menus and messages keep animating on real time while game time is paused.
+        let ui_dt = raw_dt.min(0.05);
+
+        // Pump controller events before any phase reads input
+        self.gamepads.poll(&mut self.input);
 
         // Process debug actions (execute one-shot requests)
         self.process_debug_actions();
@@ -2342,20 +2612,38 @@ impl GameState {
         }
 
         self.interaction_prompt = None;
+        self.resolve_interaction_target();
 
         match self.phase {
             GamePhase::MainMenu => self.update_main_menu(dt),
             GamePhase::InShip => self.update_ship(dt),
             GamePhase::ApproachPlanet => self.update_approach(dt),
             GamePhase::DropSequence => self.update_drop_sequence(dt),
+            // The map takes over mouse and WASD; the world keeps running underneath with no player input
+            GamePhase::Playing if self.galaxy_map_open => {
+                self.update_warp_map(ui_dt);
+                let input = std::mem::take(&mut self.input);
+                self.update_gameplay(dt);
+                self.input = input;
+            }
             GamePhase::Playing => self.update_gameplay(dt),
-            GamePhase::Paused => self.update_paused(dt),
+            GamePhase::Paused => self.update_paused(ui_dt),
             GamePhase::Victory | GamePhase::Defeat => {
                 self.update_camera_only(dt);
             }
             _ => {}
         }
 
+        // Audio listener rides the camera in every phase
+        let underwater = self.camera_underwater();
+        let physics = &self.physics;
+        if let Some(audio) = &mut self.audio {
+            audio.update_listener(self.camera.position(), self.camera.forward(), Vec3::Y);
+            audio.set_underwater(underwater);
+            audio.update_occlusion(&|from, to| sound_occlusion(physics, from, to));
+        }
+        self.renderer.set_underwater(if underwater { 1.0 } else { 0.0 });
+
         // Sync camera to renderer for phases that update it in their update (DropSequence does its own).
         if self.phase == GamePhase::DropSequence {
             self.renderer.update_camera(&self.camera, self.planet_radius_for_curvature());
@@ -2372,21 +2660,26 @@ impl GameState {
                     else if self.input.is_key_pressed(KeyCode::Digit4) { 3 }
                     else { 4 };
                 if idx < 4 {
-                    if let Some((_, choices)) = self.dialogue_state.current_line_and_choices() {
+                    if let Some((_, choices)) = self.dialogue_state.current_line_and_choices(&self.dialogue_memory) {
                         if idx < choices.len() {
-                            self.dialogue_state.select_choice(idx);
+                            self.dialogue_state.select_choice(idx, &mut self.dialogue_memory);
                         }
                     }
                 }
             }
         }
 
-        // Clear input for next frame
-        self.input.begin_frame();
+        // Clear input for next frame; the input clock runs on unscaled time so buffer windows
+        // don't stretch under debug time scale.
+        self.input.begin_frame(raw_dt);
     }
 
     /// Process one-shot debug actions (kill all bugs, teleport, etc.).
     fn process_debug_actions(&mut self) {
+        if let Some(scale) = self.debug.time_scale_requested.take() {
+            self.time.set_time_scale(scale);
+        }
+
         if self.debug.kill_all_bugs_requested {
             self.debug.kill_all_bugs_requested = false;
             let mut killed = 0u32;
@@ -2410,6 +2703,20 @@ impl GameState {
             }
         }
 
+        if self.debug.cycle_weather_requested {
+            self.debug.cycle_weather_requested = false;
+            let next = match self.weather.target {
+                WeatherState::Clear => WeatherState::Cloudy,
+                WeatherState::Cloudy => WeatherState::Rain,
+                WeatherState::Rain => WeatherState::Storm,
+                WeatherState::Storm => WeatherState::Snow,
+                WeatherState::Snow => WeatherState::Clear,
+            };
+            self.weather.set_target(next);
+            #[cfg(debug_assertions)]
+            self.game_messages.info(format!("[DEBUG] Weather -> {:?}", next));
+        }
+
         // God mode: heal player every frame
         if self.debug.god_mode && self.player.health < self.player.max_health {
             self.player.health = self.player.max_health;
@@ -2425,41 +2732,105 @@ impl GameState {
         }
     }
 
-    /// Update main menu: Continue/Play, Universe Map, Quit. Universe Map opens galaxy; Enter = travel and board.
-    fn update_main_menu(&mut self, dt: f32) {
-        if self.main_menu_galaxy_open {
-            // Galaxy map from main menu: M = close, arrows = select system, Enter = travel to system and board Roger Young
-            let num_systems = self.universe.systems.len();
-            if self.input.is_key_pressed(KeyCode::KeyM) || self.input.is_key_pressed(KeyCode::Escape) {
-                self.main_menu_galaxy_open = false;
-                self.galaxy_map_open = false;
-            } else if num_systems > 0 {
-                if self.input.is_key_pressed(KeyCode::ArrowUp) || self.input.is_key_pressed(KeyCode::KeyW) {
-                    self.galaxy_map_selected = if self.galaxy_map_selected == 0 { num_systems - 1 } else { self.galaxy_map_selected - 1 };
-                }
-                if self.input.is_key_pressed(KeyCode::ArrowDown) || self.input.is_key_pressed(KeyCode::KeyS) {
-                    self.galaxy_map_selected = (self.galaxy_map_selected + 1) % num_systems;
-                }
-                if self.input.is_key_pressed(KeyCode::Enter) || self.input.is_key_pressed(KeyCode::Space) {
-                    // Travel to selected system and board ship (Star Citizen style: pick destination then board)
-                    self.current_system_idx = self.galaxy_map_selected;
-                    self.current_system = self.universe.generate_system(self.galaxy_map_selected);
-                    let num_planets = self.current_system.bodies.len();
-                    self.war_state = GalacticWarState::new(num_planets);
-                    self.current_planet_idx = Some(0);
-                    self.planet = self.current_system.bodies[0].planet.clone();
-                    self.main_menu_galaxy_open = false;
-                    self.galaxy_map_open = false;
-                    self.begin_ship_phase(0);
-                    let _ = self.renderer.window.set_cursor_grab(CursorGrabMode::Locked)
-                        .or_else(|_| self.renderer.window.set_cursor_grab(CursorGrabMode::Confined));
-                    self.renderer.window.set_cursor_visible(false);
-                    self.input.set_cursor_locked(true);
-                self.game_messages.info(format!("FEDERATION DESTROYER \"ROGER YOUNG\" - {} SYSTEM", self.current_system.name));
+    /// Resolve what's under the crosshair once per frame (ship crew in the ship; supply crates,
+    /// weapons on the ground, then citizens on Earth, planetside). Prompts, dialogue and the highlighted nametag all read
+    /// `player.interaction_target`.
+    fn resolve_interaction_target(&mut self) {
+        const TALK_RANGE: f32 = 3.0;
+        const PICKUP_RANGE: f32 = 3.0;
+        let cam_pos = self.camera.position();
+        let cam_fwd = self.camera.forward();
+        self.player.interaction_target = match self.phase {
+            GamePhase::InShip => pick_look_target(
+                cam_pos,
+                cam_fwd,
+                TALK_RANGE,
+                roger_young_interior_npcs().into_iter().map(|npc| {
+                    let base = npc.position;
+                    (npc, base)
+                }),
+            )
+            .map(|npc| InteractionTarget::Speaker {
+                name: npc.name.to_string(),
+                dialogue_id: npc.dialogue_id,
+                entity: None,
+                position: npc.position,
+            }),
+            GamePhase::Playing if self.current_planet_idx.is_some() && self.settlement_center.is_some() => {
+                // Citizen transforms sit 0.5 m above the ground.
+                let candidates: Vec<(Entity, Vec3)> = self
+                    .world
+                    .query::<(&Transform, &Citizen)>()
+                    .iter()
+                    .map(|(entity, (transform, _))| (entity, transform.position - Vec3::Y * 0.5))
+                    .collect();
+                pick_look_target(cam_pos, cam_fwd, TALK_RANGE, candidates).and_then(|entity| {
+                    let transform = self.world.get::<&Transform>(entity).ok()?;
+                    let citizen = self.world.get::<&Citizen>(entity).ok()?;
+                    Some(InteractionTarget::Speaker {
+                        name: citizen.display_name.clone(),
+                        dialogue_id: citizen.dialogue_id,
+                        entity: Some(entity),
+                        position: transform.position - Vec3::Y * 0.5,
+                    })
+                })
+            }
+            _ => None,
+        };
+
+        // A downed trooper, supply crate or weapon under the crosshair wins over a citizen standing behind it
+        if self.phase == GamePhase::Playing && self.current_planet_idx.is_some() && self.player.is_alive {
+            // Squad mate transforms sit 0.5 m above the ground.
+            let downed: Vec<(Entity, Vec3)> = self
+                .world
+                .query::<(&Transform, &SquadMate)>()
+                .iter()
+                .filter(|(_, (_, mate))| mate.is_downed())
+                .map(|(entity, (transform, _))| (entity, transform.position - Vec3::Y * 0.5))
+                .collect();
+            let crates = self.supply_crates.iter().enumerate().filter(|(_, c)| c.charges > 0);
+            let weapons: Vec<(Entity, Vec3)> = self
+                .world
+                .query::<(&Transform, &WeaponPickup)>()
+                .iter()
+                .map(|(entity, (transform, _))| (entity, transform.position))
+                .collect();
+            if let Some(entity) = pick_look_target(cam_pos, cam_fwd, PICKUP_RANGE, downed) {
+                self.player.interaction_target = Some(InteractionTarget::Revivable(entity));
+            } else if let Some(index) = pick_look_target(cam_pos, cam_fwd, PICKUP_RANGE, crates.map(|(i, c)| (i, c.position))) {
+                self.player.interaction_target = Some(InteractionTarget::SupplyCrate(index));
+            } else if let Some(entity) = pick_look_target(cam_pos, cam_fwd, PICKUP_RANGE, weapons) {
+                self.player.interaction_target = Some(InteractionTarget::WeaponPickup(entity));
+            }
+        }
+    }
+
+    /// Update main menu: Continue/Play, Universe Map, Quit. Universe Map opens galaxy; Enter = travel and board.
+    fn update_main_menu(&mut self, dt: f32) {
+        if self.main_menu_galaxy_open {
+            // Galaxy map from main menu: M = close, Enter = travel to the system under the reticle and board Roger Young
+            if self.input.is_key_pressed(KeyCode::KeyM) || self.input.is_key_pressed(KeyCode::Escape) {
+                self.main_menu_galaxy_open = false;
+                self.galaxy_map_open = false;
+            } else if !self.universe.systems.is_empty() && (self.update_galaxy_map(dt) || self.input.is_key_pressed(KeyCode::Space)) {
+                // Travel to selected system and board ship (Star Citizen style: pick destination then board)
+                self.current_system_idx = self.galaxy_map_selected;
+                self.current_system = self.universe.generate_system(self.galaxy_map_selected);
+                let num_planets = self.current_system.bodies.len();
+                self.war_state = GalacticWarState::new(num_planets, &mut GameRng::new(self.current_system.seed));
+                self.current_planet_idx = Some(0);
+                self.planet = self.current_system.bodies[0].planet.clone();
+                self.main_menu_galaxy_open = false;
+                self.galaxy_map_open = false;
+                self.begin_ship_phase(0);
+                let _ = self.renderer.window.set_cursor_grab(CursorGrabMode::Locked)
+                    .or_else(|_| self.renderer.window.set_cursor_grab(CursorGrabMode::Confined));
+                self.renderer.window.set_cursor_visible(false);
+                self.input.set_cursor_locked(true);
+                self.game_messages.info(format!("FEDERATION DESTROYER \"ROGER YOUNG\" - {} SYSTEM", self.current_system.name));
                 self.game_messages.info(format!("Star: {} ({:?}) | {} planets", self.current_system.star.name, self.current_system.star.star_type, num_planets));
                 self.game_messages.info("Approach the WAR TABLE [E] — pick planet and mission. Drop bay is aft.");
                 self.game_messages.warning("Press [SPACE] to deploy drop pod!");
-                }
             }
             self.game_messages.update(dt);
             return;
@@ -2503,8 +2874,7 @@ impl GameState {
             } else if self.main_menu_selected == 1 {
                 // Universe Map — open galaxy (Star Citizen style: choose system then board)
                 self.main_menu_galaxy_open = true;
-                self.galaxy_map_open = true;
-                self.galaxy_map_selected = self.current_system_idx;
+                self.open_galaxy_map();
             } else {
                 // Quit
                 self.running = false;
@@ -2519,6 +2889,59 @@ impl GameState {
         self.game_messages.update(dt);
     }
 
+    /// Whether the galaxy map owns the screen (the pause menu and other phases draw over a closed map).
+    fn galaxy_map_visible(&self) -> bool {
+        self.galaxy_map_open && matches!(self.phase, GamePhase::MainMenu | GamePhase::Playing)
+    }
+
+    /// Open the galaxy map with the reticle on the current system.
+    fn open_galaxy_map(&mut self) {
+        self.galaxy_map_open = true;
+        self.galaxy_map_selected = self.current_system_idx;
+        self.galaxy_map.focus_on(&self.universe, self.current_system_idx);
+    }
+
+    /// Galaxy map navigation shared by the main menu and the in-flight map: moves the map camera,
+    /// keeps the reticle selection current, N / C jump the reticle to the next listed / current system.
+    /// Returns true when Enter confirms the selected system.
+    fn update_galaxy_map(&mut self, dt: f32) -> bool {
+        self.galaxy_map.handle_input(&self.input, dt);
+        let current = self.current_system_idx;
+        if self.input.is_key_pressed(KeyCode::KeyN) {
+            let list = self.galaxy_map.listing(&self.universe, current);
+            let next = list.iter().position(|&i| i == self.galaxy_map_selected).map_or(0, |p| (p + 1) % list.len());
+            if let Some(&idx) = list.get(next) {
+                self.galaxy_map.focus_on(&self.universe, idx);
+                self.galaxy_map_selected = idx;
+            }
+        }
+        if self.input.is_key_pressed(KeyCode::KeyC) {
+            self.galaxy_map.focus_on(&self.universe, current);
+            self.galaxy_map_selected = current;
+        }
+        if let Some(idx) = self.galaxy_map.system_under_reticle(&self.universe, current, self.camera.aspect) {
+            self.galaxy_map_selected = idx;
+        }
+        let confirm = self.input.is_key_pressed(KeyCode::Enter) || self.input.is_key_pressed(KeyCode::NumpadEnter);
+        confirm && self.galaxy_map.shows(&self.universe, self.galaxy_map_selected, current)
+    }
+
+    /// In-flight galaxy map (M): Enter warps to the selected system, but only from orbit.
+    fn update_warp_map(&mut self, dt: f32) {
+        if !self.update_galaxy_map(dt) {
+            return;
+        }
+        if self.current_planet_idx.is_some() {
+            self.game_messages.warning("Must be in orbit to initiate warp drive!".to_string());
+        } else if self.galaxy_map_selected != self.current_system_idx {
+            let target = self.galaxy_map_selected;
+            let target_name = self.universe.systems[target].name.clone();
+            self.game_messages.warning(format!("Initiating warp to {}...", target_name));
+            self.warp_sequence = Some(WarpSequence::new(target));
+            self.galaxy_map_open = false;
+        }
+    }
+
     /// Update when paused: only menu input and message decay.
     fn update_paused(&mut self, dt: f32) {
         self.game_messages.update(dt);
@@ -2533,6 +2956,7 @@ impl GameState {
         self.galaxy_map_open = false;
         self.pause_menu_selected = 0;
         self.previous_phase = None;
+        self.time.resume();
         self.ship_state = None;
         self.drop_pod = None;
         self.extraction = None;
@@ -2550,8 +2974,8 @@ impl GameState {
             let _ = self.world.despawn(entity);
         }
         self.effects = EffectsManager::new();
-        self.rain_drops.clear();
-        self.snow_particles.clear();
+        self.renderer.clear_decals();
+        self.particles.clear();
         self.artillery_shells.clear();
         self.artillery_muzzle_flashes.clear();
         self.artillery_trail_particles.clear();
@@ -2563,6 +2987,7 @@ impl GameState {
             self.physics.remove_body(s.body_handle);
         }
         self.artillery_barrage = None;
+        self.artillery_impacts.clear();
         self.extraction_squadmates_aboard.clear();
         self.last_player_track_pos = None;
         self.ground_track_bug_timer = 0.0;
@@ -2611,12 +3036,14 @@ impl GameState {
         if let Some(ref mut ship) = self.ship_state {
             ship.timer += dt;
             // Update cloth flag physics
-            ship.ucf_flag.update(dt);
-            ship.mi_flag.update(dt);
+            let (wind, gust) = self.weather.wind();
+            ship.ucf_flag.update(dt, wind, gust);
+            ship.mi_flag.update(dt, wind, gust);
         }
 
         // Update war table state
-        self.war_state.update(dt);
+        let granted = self.war_state.update(dt);
+        self.grant_rewards(granted);
 
         // Read ship state info before movement
         let war_table_active = self.ship_state.as_ref().map_or(false, |s| s.war_table_active);
@@ -2626,10 +3053,10 @@ impl GameState {
         // ── FPS movement inside the ship: artificial 1G (earth-like gravity) ──
         // Floor clamp and horizontal movement simulate gravity; no zero-G in interior.
         if !war_table_active {
-            // Mouse look (uses camera's built-in yaw/pitch system)
-            let mouse_delta = self.input.mouse_delta();
+            // Mouse / right-stick look (uses camera's built-in yaw/pitch system)
+            let look_delta = self.input.look_delta(dt);
             if self.input.is_cursor_locked() {
-                self.camera.process_mouse(mouse_delta.x, mouse_delta.y);
+                self.camera.process_mouse(look_delta.x, look_delta.y);
             }
 
             // WASD movement (clamped to ship interior bounds)
@@ -2639,16 +3066,17 @@ impl GameState {
             let move_dir_forward = Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero();
             let move_dir_right = Vec3::new(right.x, 0.0, right.z).normalize_or_zero();
 
-            let mut move_vec = Vec3::ZERO;
-            if self.input.is_key_held(KeyCode::KeyW) { move_vec += move_dir_forward; }
-            if self.input.is_key_held(KeyCode::KeyS) { move_vec -= move_dir_forward; }
-            if self.input.is_key_held(KeyCode::KeyD) { move_vec += move_dir_right; }
-            if self.input.is_key_held(KeyCode::KeyA) { move_vec -= move_dir_right; }
+            let movement = self.input.get_movement_input();
+            let mut move_vec = move_dir_forward * movement.y + move_dir_right * movement.x;
 
-            if move_vec.length_squared() > 0.01 {
+            let moving = move_vec.length_squared() > 0.01;
+            if moving {
                 move_vec = move_vec.normalize() * speed * dt;
                 self.camera.transform.position += move_vec;
             }
+            if self.footsteps.update(dt, Gait::Walk, moving) {
+                self.play_footstep(FootSurface::Metal, Gait::Walk);
+            }
 
             // Clamp to ship interior bounds
             // CIC main room: X[-9,9], Z[-14,14]
@@ -2735,7 +3163,8 @@ impl GameState {
                 let planet = &self.current_system.bodies[self.war_state.selected_planet].planet;
                 self.planet = planet.clone();
             }
-            // Mission type: 1 = Extermination, 2 = Bug Hunt, 3 = Hold the Line (Helldivers 2 style)
+            // Mission type: 1 = Extermination, 2 = Bug Hunt, 3 = Hold the Line, 4 = Defense,
+            // 5 = Hive Destruction, 6 = Rescue (Helldivers 2 style)
             if self.input.is_key_pressed(KeyCode::Digit1) {
                 self.next_mission_type = fps::MissionType::Extermination;
                 if let Some(ref mut ship) = self.ship_state {
@@ -2762,14 +3191,41 @@ impl GameState {
                 if let Some(ref mut ship) = self.ship_state {
                     ship.selected_mission_type = fps::MissionType::Defense;
                 }
-                self.game_messages.info("Mission: DEFENSE — Hold position 4:00, then extract.".to_string());
+                self.game_messages.info("Mission: DEFENSE — Capture the point, hold it 4:00, then extract.".to_string());
             }
             if self.input.is_key_pressed(KeyCode::Digit5) {
                 self.next_mission_type = fps::MissionType::HiveDestruction;
                 if let Some(ref mut ship) = self.ship_state {
                     ship.selected_mission_type = fps::MissionType::HiveDestruction;
                 }
-                self.game_messages.info("Mission: HIVE DESTRUCTION — 40 kills, then extract.".to_string());
+                self.game_messages.info("Mission: HIVE DESTRUCTION — Destroy the marked hives, then extract.".to_string());
+            }
+            if self.input.is_key_pressed(KeyCode::Digit6) {
+                self.next_mission_type = fps::MissionType::Rescue;
+                if let Some(ref mut ship) = self.ship_state {
+                    ship.selected_mission_type = fps::MissionType::Rescue;
+                }
+                self.game_messages.info("Mission: RESCUE — Escort stranded citizens to the LZ, then extract.".to_string());
+            }
+            if self.input.is_key_pressed(KeyCode::Tab) {
+                self.difficulty = self.difficulty.next();
+                self.game_messages.info(format!("Difficulty: {}", self.difficulty.name().to_uppercase()));
+            }
+            if self.input.is_key_pressed(KeyCode::KeyX) {
+                let choices = self.heavy_weapon_choices();
+                let next = choices.iter().position(|&w| w == self.heavy_weapon).map_or(0, |i| (i + 1) % choices.len());
+                self.heavy_weapon = choices[next];
+                self.player.equip_heavy(self.heavy_weapon);
+                self.game_messages.info(format!("Heavy weapon: {:?}", self.heavy_weapon));
+            }
+            if self.input.is_key_pressed(KeyCode::KeyC) {
+                self.player.set_class(self.player.class.next());
+                self.player.equip_heavy(self.heavy_weapon);
+                self.game_messages.info(format!(
+                    "Class: {} — [Q] {}",
+                    self.player.class.name().to_uppercase(),
+                    self.player.ability.name()
+                ));
             }
         }
 
@@ -2792,32 +3248,13 @@ impl GameState {
                     key: DEPLOY_KEY,
                     action: format!("DEPLOY TO {}", self.planet.name),
                 });
-            } else {
-                const TALK_RANGE_SQ: f32 = 3.0 * 3.0;
-                let cam_pos = self.camera.position();
-                let mut nearest: Option<(f32, &'static str, usize)> = None;
-                for npc in roger_young_interior_npcs() {
-                    let dist_sq = npc.position.distance_squared(cam_pos);
-                    if dist_sq < TALK_RANGE_SQ {
-                        if nearest.as_ref().map(|(d, _, _)| *d > dist_sq).unwrap_or(true) {
-                            nearest = Some((dist_sq, npc.name, npc.dialogue_id));
-                        }
-                    }
-                }
-                if let Some((_, name, dialogue_id)) = nearest {
-                    self.interaction_prompt = Some(InteractPrompt {
-                        key: INTERACT_KEY,
-                        action: format!("Talk to {}", name),
-                    });
-                    if self.input.is_key_pressed(KeyCode::KeyE) && !self.dialogue_state.is_open() {
-                        self.dialogue_state = DialogueState::Open {
-                            speaker_entity: None,
-                            speaker_name: name.to_string(),
-                            dialogue_id,
-                            node_index: 0,
-                            showing_choices: true,
-                        };
-                    }
+            } else if let Some(InteractionTarget::Speaker { name, dialogue_id, .. }) = &self.player.interaction_target {
+                self.interaction_prompt = Some(InteractPrompt {
+                    key: INTERACT_KEY,
+                    action: format!("Talk to {}", name),
+                });
+                if self.input.is_key_pressed(KeyCode::KeyE) && !self.dialogue_state.is_open() {
+                    self.dialogue_state = DialogueState::open(None, name.clone(), *dialogue_id, &mut self.dialogue_memory);
                 }
             }
         }
@@ -2833,9 +3270,11 @@ impl GameState {
                     // Roger Young stays in orbit; dropship takes MI trooper to Earth for resupply & visit
                     self.transition_to_earth_visit(planet_idx);
                 } else {
+                    // Fly the approach craft out of the bay; update_approach launches the drop pod
                     self.deploy_planet_idx = Some(planet_idx);
                     self.approach_flight_state = None;
-                    self.transition_approach_to_drop();
+                    self.phase = GamePhase::ApproachPlanet;
+                    self.game_messages.info("Approach craft away — W/S throttle, SPACE to launch the drop pod".to_string());
                 }
             }
         }
@@ -2848,23 +3287,11 @@ impl GameState {
 
     /// Update approach phase: flyable craft (Star Citizen–style piloting) or legacy timer.
     fn update_approach(&mut self, dt: f32) {
+        let controls = self.flight_controls(dt);
         if let Some(ref mut flight) = self.approach_flight_state {
-            // Flyable approach: mouse = look, W/S = throttle
-            let mouse_delta = self.input.mouse_delta();
-            self.camera.process_mouse(mouse_delta.x, mouse_delta.y);
-
-            let fwd = self.camera.forward();
-            const THRUST: f32 = 35.0;
-            if self.input.is_key_held(KeyCode::KeyW) {
-                flight.velocity += fwd * THRUST * dt;
-            }
-            if self.input.is_key_held(KeyCode::KeyS) {
-                flight.velocity -= fwd * THRUST * dt;
-            }
-            flight.velocity *= 0.98f32.powf(dt * 60.0 / 60.0); // gentle drag
-            flight.position += flight.velocity * dt;
-
-            self.camera.transform.position = flight.position;
+            // Flyable approach: mouse = look, W/S = throttle (A/D roll in 6DOF)
+            flight.craft.update(&controls, dt);
+            flight.craft.sync_camera(&mut self.camera, Vec3::ZERO);
             self.approach_timer += dt;
 
             const MIN_APPROACH_TIME: f32 = 4.0;
@@ -2878,11 +3305,13 @@ impl GameState {
                 self.transition_approach_to_drop();
             }
         } else {
-            // Legacy timer-only approach (shouldn't run if we always use flight)
-            self.approach_timer += dt;
-            if self.approach_timer >= 5.0 || self.input.is_key_pressed(KeyCode::Space) {
-                self.transition_approach_to_drop();
-            }
+            // Entering the approach: launch the craft from the current view
+            self.approach_flight_state = Some(ApproachFlightState::new(
+                self.camera.position(),
+                self.camera.forward(),
+                self.flight_mode,
+            ));
+            self.approach_timer = 0.0;
         }
         self.renderer.update_camera(&self.camera, self.planet_radius_for_curvature());
         self.game_messages.update(dt);
@@ -2892,10 +3321,8 @@ impl GameState {
     fn transition_to_earth_visit(&mut self, planet_idx: usize) {
         self.prepare_planet_for_drop(planet_idx);
         self.mission = fps::MissionState::new_earth_visit();
-        self.ambient_dust.particles.clear();
         self.biome_atmosphere.particles.clear();
-        self.rain_drops.clear();
-        self.snow_particles.clear();
+        self.particles.clear();
         self.complete_earth_visit();
     }
 
@@ -3008,13 +3435,15 @@ impl GameState {
             let half_extents = Vec3::new(sx * 0.5, sy * 0.5, sz * 0.5);
             let handle = self.physics.add_static_cuboid(center, 0.0, half_extents);
             self.earth_building_colliders.push(handle);
+            self.horde_ai.add_static_obstacle(center, glam::Vec2::new(half_extents.x, half_extents.z));
         }
 
         self.game_messages.success("DROPSHIP TOUCHED DOWN. Welcome home, trooper.".to_string());
         self.game_messages.info("Roger Young remains in Earth orbit. UCF safe zone — no bugs on the homeworld.".to_string());
-        self.game_messages.info("Resupply and visit. Cities, towns, farms — this is what we're fighting for. [V] when ready to return.".to_string());
-        self.game_messages.info("WASD = move | Shift = sprint | E = talk to citizens | M = galaxy map | V = return to ship".to_string());
+        self.game_messages.info("Resupply and visit. Cities, towns, farms — this is what we're fighting for. [Z] when ready to return.".to_string());
+        self.game_messages.info("WASD = move | Shift = sprint | E = talk to citizens | M = galaxy map | Z = return to ship".to_string());
 
+        self.set_reverb_zones(true);
         self.phase = GamePhase::Playing;
     }
 
@@ -3031,10 +3460,13 @@ impl GameState {
         self.game_messages.info("\"Come on you apes, you wanna live forever?!\"".to_string());
 
         self.prepare_planet_for_drop(planet_idx);
-        self.ambient_dust.particles.clear();
         self.biome_atmosphere.particles.clear();
-        self.rain_drops.clear();
-        self.snow_particles.clear();
+        self.particles.clear();
+        match self.war_state.planets.get(planet_idx).map(|p| p.supply) {
+            Some(SupplyStatus::CutOff) => self.game_messages.warning("FLEET COM: Supply lines cut — expect a heavier swarm.".to_string()),
+            Some(SupplyStatus::Reinforced) => self.game_messages.success("FLEET COM: Supply lines secure — stratagems rearm faster.".to_string()),
+            _ => {}
+        }
 
         self.drop_pod = Some(DropPodSequence::new(planet_idx));
         self.phase = GamePhase::DropSequence;
@@ -3091,6 +3523,10 @@ impl GameState {
             // Apply camera shake on top
             self.camera.transform.position += pod.shake_offset;
 
+            // Widen the view with descent speed
+            let kick = update::DROP_FOV_KICK * pod.velocity_factor;
+            self.camera.set_fov(self.camera.base_fov_degrees + kick, update::FOV_RATE, dt);
+
             // Helldivers 2–style: follow the drop pod and stream terrain under it for the entire descent.
             // Chunk center = pod position on the ground plane so terrain loads along the pod’s path.
             let stream_center = Vec3::new(
@@ -3176,16 +3612,20 @@ impl GameState {
             self.spawn_defense_base();
         }
 
-        // Reset game systems
-        self.spawner = spawner::BugSpawner::new(planet.bug_spawn_rate(), planet.danger_level);
+        // Reset game systems (supply lines decide how hard the bugs push and how fast the fleet rearms)
+        self.supply = self.war_state.supply_modifiers(planet_idx);
+        self.spawner = spawner::BugSpawner::new(planet.bug_spawn_rate() * self.supply.spawn_rate_mult, planet.danger_level, self.difficulty, self.rng.fork(planet.seed));
+        self.player.apply_difficulty(self.difficulty);
+        self.abilities.clear();
         let biome_table = get_biome_feature_table(planet.primary_biome);
         self.spawner.set_biome_variant(biome_table.bug_variant, biome_table.variant_chance);
         self.mission = match self.next_mission_type {
             fps::MissionType::Extermination => fps::MissionState::new_horde(),
             fps::MissionType::BugHunt => fps::MissionState::new_bug_hunt(25),
             fps::MissionType::HoldTheLine => fps::MissionState::new_hold_the_line(300.0),
-            fps::MissionType::Defense => fps::MissionState::new_defense(240.0),
-            fps::MissionType::HiveDestruction => fps::MissionState::new_hive_destruction(40),
+            fps::MissionType::Defense => fps::MissionState::new_defense(),
+            fps::MissionType::HiveDestruction => fps::MissionState::new_hive_destruction(),
+            fps::MissionType::Rescue => fps::MissionState::new_rescue(),
             _ => fps::MissionState::new_horde(),
         };
         // Time of day from real-time cycle (star + planet rotation); weather from saved conditions
@@ -3196,7 +3636,7 @@ impl GameState {
 
         // Reset biome atmosphere for the new planet's biome
         self.biome_atmosphere.reset(planet.primary_biome);
-        self.ambient_dust = AmbientDust::new();
+        self.particles.clear();
 
         self.planet = planet;
     }
@@ -3249,6 +3689,7 @@ impl GameState {
             self.player.position = self.camera.transform.position;
 
             self.squad_drop_pods = Some(SquadDropSequence::new(landing, terrain_y));
+            self.spawn_mission_objectives(landing);
 
             if is_base_defense {
                 self.game_messages.success("BASE DEFENSE! Hold the walls, trooper!".to_string());
@@ -3275,14 +3716,16 @@ impl GameState {
                     for h in self.earth_building_colliders.drain(..) {
                         self.physics.remove_collider(h);
                     }
+                    self.horde_ai.clear_static_obstacles();
                 }
                 self.game_messages.info("Look up — squad drop pods inbound from the Roger Young in orbit!".to_string());
                 self.game_messages.info(format!("IMPACT SITE: crater radius 16m | {:.0}m deep", 6.0));
             }
             self.game_messages.info("WASD = move | Shift = sprint | Space/Ctrl = up/down | M = galaxy map | R = next planet".to_string());
-            self.game_messages.info("G = smoke | T = Tac Fighter | V = extraction | X = entrenchment shovel".to_string());
+            self.game_messages.info("G = frag (hold to cook) | H = smoke | T = Tac Fighter | Z = extraction | V = melee | X = entrenchment shovel".to_string());
         }
 
+        self.set_reverb_zones(true);
         self.phase = GamePhase::Playing;
     }
 
@@ -3290,13 +3733,28 @@ impl GameState {
         update::gameplay(self, dt);
     }
 
+    /// Pin the first-person weapon rig to the camera and pose it from the viewmodel animation,
+    /// respawning the rig if the world was cleared since it was last used.
+    fn pose_viewmodel_rig(&mut self) -> ViewmodelRig {
+        let rig = match self.viewmodel_rig {
+            Some(rig) if rig.is_spawned(&self.world) => rig,
+            _ => ViewmodelRig::spawn(&mut self.world),
+        };
+        self.viewmodel_rig = Some(rig);
+        let weapon = (!self.player.is_shovel_equipped()).then(|| self.player.current_weapon().weapon_type);
+        let (gun_pos, gun_rot, muzzle_offset) = self.viewmodel_anim.held_pose(weapon, self.player.aim_progress);
+        rig.attach_to_camera(&mut self.world, self.camera.view_matrix().inverse());
+        rig.pose(&mut self.world, gun_pos, gun_rot, muzzle_offset);
+        rig
+    }
+
     fn spawn_physics_bugs(&mut self, dt: f32) {
         // Earth is a UCF safe zone — no bugs on the homeworld.
         if self.planet.name == "Earth" {
             return;
         }
         // Continuous horde spawning — no waves, no pauses, just bugs.
-        let spawn_positions: Vec<((BugType, Option<bug::BugVariant>), Vec3)> = {
+        let spawn_positions: Vec<((BugType, Option<bug::BugVariant>), Vec3, EmergePhase)> = {
             let mut positions = Vec::new();
 
             // Accumulate spawn pressure based on current spawn rate
@@ -3318,7 +3776,7 @@ impl GameState {
             while self.spawner.spawn_timer >= 1.0 && approx_alive + positions.len() < self.spawner.max_bugs {
                 self.spawner.spawn_timer -= 1.0;
 
-                let angle = rand::random::<f32>() * std::f32::consts::TAU;
+                let angle = self.rng.range(0.0..std::f32::consts::TAU);
                 // Base defense: spawn bugs OUTSIDE the perimeter so they come to you
                 let min_dist = if let Some((_, radius)) = self.defense_base {
                     (radius + 10.0).max(self.spawner.min_spawn_distance)
@@ -3326,7 +3784,7 @@ impl GameState {
                     self.spawner.min_spawn_distance
                 };
                 let dist = min_dist
-                    + rand::random::<f32>() * (self.spawner.max_spawn_distance - min_dist);
+                    + self.rng.unit() * (self.spawner.max_spawn_distance - min_dist);
 
                 let spawn_x = self.player.position.x + angle.cos() * dist;
                 let spawn_z = self.player.position.z + angle.sin() * dist;
@@ -3337,7 +3795,29 @@ impl GameState {
                 // Same formula as terrain snap in update.rs: feet on surface
                 let half_height = scale.y * 0.6 + 0.15;
                 let pos = Vec3::new(spawn_x, terrain_y + half_height, spawn_z);
-                positions.push((type_and_variant, pos));
+                positions.push((type_and_variant, pos, EmergePhase::Surfaced));
+            }
+
+            // Burrow ambush: a group digs up in a close ring around the trooper, in sight or not,
+            // erupting one after another
+            let ambush = self.spawner.tick_ambush(dt);
+            if ambush > 0 {
+                self.game_messages.warning("Seismic contact! Bugs burrowing below!");
+            }
+            for i in 0..ambush {
+                if approx_alive + positions.len() >= self.spawner.max_bugs {
+                    break;
+                }
+                let angle = self.rng.range(0.0..std::f32::consts::TAU);
+                let dist = self.rng.range(BURROW_SPAWN_MIN_DIST..BURROW_SPAWN_MAX_DIST);
+                let spawn_x = self.player.position.x + angle.cos() * dist;
+                let spawn_z = self.player.position.z + angle.sin() * dist;
+                let terrain_y = self.chunk_manager.sample_height_or(spawn_x, spawn_z, fallback_y);
+                let type_and_variant = self.spawner.random_bug_type();
+                let half_height = type_and_variant.0.scale().y * 0.6 + 0.15;
+                let pos = Vec3::new(spawn_x, terrain_y + half_height, spawn_z);
+                let timer = 1.0 + i as f32 * 0.4 + self.rng.range(0.0..0.3);
+                positions.push((type_and_variant, pos, EmergePhase::Burrowed { timer }));
             }
 
             // Cap accumulated timer so we don't get a burst after lag spikes
@@ -3347,32 +3827,168 @@ impl GameState {
         };
 
         // Spawn bugs with physics bodies
-        for ((bug_type, variant), position) in spawn_positions {
-            let bug = Bug::new_with_variant(bug_type, variant);
-            let scale = bug_type.scale();
-
-            // Create physics body for the bug
-            let body_handle = self.physics.add_kinematic_body(position);
-            let collider_handle = self.physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
-
-            let physics_bug = PhysicsBug {
-                body_handle: Some(body_handle),
-                collider_handle: Some(collider_handle),
-                ..Default::default()
-            };
+        for ((bug_type, variant), position, emerge) in spawn_positions {
+            self.spawn_bug(Bug::new_with_variant(bug_type, variant), position, emerge);
+        }
+    }
 
-            self.world.spawn((
-                Transform {
-                    position,
-                    rotation: Quat::IDENTITY,
-                    scale,
-                },
-                Velocity::default(),
-                Health::new(bug.effective_health()),
-                bug,
-                physics_bug,
-                engine_core::AIComponent::new(85.0, 2.5, 1.0),  // Extermination: large aggro = constant pressure
-            ));
+    /// Spawn a bug with its physics body and collider.
+    fn spawn_bug(&mut self, bug: Bug, position: Vec3, emerge: EmergePhase) -> hecs::Entity {
+        let scale = bug.bug_type.scale();
+
+        // Create physics body for the bug
+        let body_handle = self.physics.add_kinematic_body(position);
+        let collider_handle = self.physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+
+        let physics_bug = PhysicsBug {
+            body_handle: Some(body_handle),
+            collider_handle: Some(collider_handle),
+            emerge,
+            ..Default::default()
+        };
+
+        let entity = self.world.spawn((
+            Transform {
+                position,
+                rotation: Quat::IDENTITY,
+                scale,
+            },
+            Velocity::default(),
+            Health::new(bug.effective_health() * self.difficulty.bug_health_mult()),
+            bug,
+            physics_bug,
+            engine_core::AIComponent::new(85.0, 2.5, 1.0),  // Extermination: large aggro = constant pressure
+        ));
+        self.physics.set_collider_entity(collider_handle, entity);
+        entity
+    }
+
+    /// Hive Destruction finale: once the hives are down the Queen digs up near the trooper. Runs
+    /// her fight phases, calling broods out of the ground around her and enraging when low.
+    fn update_hive_queen(&mut self, dt: f32) {
+        if self.current_planet_idx.is_none() {
+            return;
+        }
+        let summon = self
+            .mission
+            .objectives
+            .iter()
+            .find(|o| !o.is_complete())
+            .is_some_and(|o| matches!(o, objective::Objective::SlayQueen { queen: None, .. }));
+        if summon {
+            let angle = self.rng.range(0.0..std::f32::consts::TAU);
+            let dist = self.rng.range(QUEEN_SPAWN_DISTANCE);
+            let x = self.player.position.x + angle.cos() * dist;
+            let z = self.player.position.z + angle.sin() * dist;
+            let half_height = BugType::Queen.scale().y * 0.6 + 0.15;
+            let position = Vec3::new(x, self.chunk_manager.sample_height(x, z) + half_height, z);
+            let queen = self.spawn_bug(Bug::new(BugType::Queen), position, EmergePhase::Burrowed { timer: 3.0 });
+            self.world.insert(queen, (boss::QueenBoss::new(), engine_core::AIComponent::new(250.0, 6.0, 1.6))).ok();
+            if let Some(objective::Objective::SlayQueen { queen: slot, .. }) =
+                self.mission.objectives.iter_mut().find(|o| !o.is_complete())
+            {
+                *slot = Some(queen);
+            }
+            self.game_messages.warning("The Hive Queen is surfacing! Wait for her to open up, then hit the brood sac!");
+            self.screen_shake.add_trauma(0.5);
+        }
+
+        let mut events = Vec::new();
+        for (entity, (transform, bug, health, physics_bug, queen)) in
+            self.world.query_mut::<(&Transform, &mut Bug, &Health, &PhysicsBug, &mut boss::QueenBoss)>()
+        {
+            if health.is_dead() || !physics_bug.is_surfaced() {
+                continue;
+            }
+            let Some(event) = queen.update(health.current / health.max, dt) else { continue };
+            match event {
+                // Rear up to call the brood
+                boss::QueenEvent::Brood { .. } => bug.maneuver = Maneuver::Recover { timer: boss::BROOD_CALL_SECS },
+                boss::QueenEvent::Enraged => {
+                    bug.move_speed *= boss::RAGE_SPEED_MULT;
+                    bug.attack_damage *= boss::RAGE_DAMAGE_MULT;
+                }
+                boss::QueenEvent::Sealed => {}
+            }
+            events.push((entity, transform.position, event, queen.phase));
+        }
+
+        for (_, position, event, phase) in events {
+            match event {
+                boss::QueenEvent::Brood { count } => {
+                    for i in 0..count {
+                        let angle = self.rng.range(0.0..std::f32::consts::TAU);
+                        let dist = self.rng.range(QUEEN_BROOD_DISTANCE);
+                        let x = position.x + angle.cos() * dist;
+                        let z = position.z + angle.sin() * dist;
+                        let bug_type = if self.rng.chance(0.3) { BugType::Charger } else { BugType::Warrior };
+                        let half_height = bug_type.scale().y * 0.6 + 0.15;
+                        let pos = Vec3::new(x, self.chunk_manager.sample_height(x, z) + half_height, z);
+                        let timer = 0.8 + i as f32 * 0.3;
+                        self.spawn_bug(Bug::new(bug_type), pos, EmergePhase::Burrowed { timer });
+                    }
+                    if phase == boss::QueenPhase::Exposed {
+                        self.game_messages.warning("She's calling her brood! Carapace open, hit the brood sac!");
+                    } else {
+                        self.game_messages.warning("More brood coming up around the Queen!");
+                    }
+                    self.screen_shake.add_trauma(0.3);
+                }
+                boss::QueenEvent::Sealed => self.game_messages.info("The Queen's carapace has sealed."),
+                boss::QueenEvent::Enraged => {
+                    self.game_messages.warning("The Queen is enraged! Keep moving, trooper!");
+                    self.screen_shake.add_trauma(0.6);
+                }
+            }
+        }
+    }
+
+    /// Burrowed bugs: the ground trembles over them, then they burst out in a dirt spray and
+    /// leave a mound, rearing up toward the trooper as they climb out.
+    fn update_burrowing_bugs(&mut self, dt: f32) {
+        let player_pos = self.player.position;
+        let mut tremors: Vec<Vec3> = Vec::new();
+        let mut bursts: Vec<(Vec3, f32)> = Vec::new();
+        for (_, (transform, physics_bug)) in self.world.query_mut::<(&mut Transform, &mut PhysicsBug)>() {
+            if physics_bug.is_surfaced() || physics_bug.is_ragdoll {
+                continue;
+            }
+            let was_burrowed = matches!(physics_bug.emerge, EmergePhase::Burrowed { .. });
+            if physics_bug.update_emerge(dt) {
+                bursts.push((transform.position, transform.scale.x));
+            } else if was_burrowed && self.rng.chance(dt * 6.0) {
+                tremors.push(transform.position);
+            }
+            // Climb out nose-first, facing the trooper
+            if let EmergePhase::Emerging { progress } = physics_bug.emerge {
+                let to_player = Vec3::new(player_pos.x - transform.position.x, 0.0, player_pos.z - transform.position.z);
+                let yaw = Quat::from_rotation_arc(Vec3::Z, to_player.normalize_or(Vec3::Z));
+                transform.rotation = yaw * Quat::from_rotation_x(-0.7 * (1.0 - progress));
+            }
+        }
+
+        for pos in tremors {
+            let surface = self.chunk_manager.walkable_height(pos.x, pos.z);
+            let jitter = Vec3::new(self.rng.range(-0.8..0.8), 0.0, self.rng.range(-0.8..0.8));
+            self.effects.spawn_bullet_impact(Vec3::new(pos.x, surface, pos.z) + jitter, Vec3::Y, false);
+        }
+        for (pos, size) in bursts {
+            let surface = Vec3::new(pos.x, self.chunk_manager.walkable_height(pos.x, pos.z), pos.z);
+            for i in 0..8 {
+                let angle = i as f32 * std::f32::consts::TAU / 8.0;
+                let offset = Vec3::new(angle.cos(), 0.3, angle.sin()) * size * 1.2;
+                self.effects.spawn_bullet_impact(surface + offset, (offset + Vec3::Y).normalize(), false);
+            }
+            self.effects.spawn_muzzle_flash(surface + Vec3::Y * 0.5, Vec3::Y);
+            self.chunk_manager.deform_mound_at(
+                surface + Vec3::new(size * 1.3, -0.2, 0.0),
+                0.8 * size.max(0.8),
+                0.5,
+                self.renderer.device(),
+                &mut self.physics,
+            );
+            let proximity = 1.0 - (surface.distance(player_pos) / BURROW_SPAWN_MAX_DIST).min(1.0);
+            self.screen_shake.add_trauma(0.1 + 0.25 * proximity);
         }
     }
 
@@ -3416,8 +4032,8 @@ impl GameState {
                 bug_hole.active_bugs += 1;
 
                 // Spawn position: near the hole with some random offset
-                let offset_angle = rand::random::<f32>() * std::f32::consts::TAU;
-                let offset_dist = 1.0 + rand::random::<f32>() * 3.0;
+                let offset_angle = self.rng.range(0.0..std::f32::consts::TAU);
+                let offset_dist = self.rng.range(1.0..4.0);
                 let spawn_pos = Vec3::new(
                     transform.position.x + offset_angle.cos() * offset_dist,
                     transform.position.y + 0.5,
@@ -3446,7 +4062,7 @@ impl GameState {
                 fallback_y,
             );
 
-            let spawn_skinny = spawn_skinny_chance > 0.0 && rand::random::<f32>() < spawn_skinny_chance;
+            let spawn_skinny = self.rng.chance(spawn_skinny_chance);
             if spawn_skinny {
                 let skinny_type = self.random_skinny_type();
                 let skinny = Skinny::new(skinny_type);
@@ -3454,20 +4070,22 @@ impl GameState {
                 let half_height = scale.y * 0.6 + 0.15;
                 spawn_pos.y = terrain_y + half_height;
                 let body_handle = self.physics.add_kinematic_body(spawn_pos);
-                let collider_handle = self.physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+                let collider_handle = self.physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
                 let physics_bug = PhysicsBug {
                     body_handle: Some(body_handle),
                     collider_handle: Some(collider_handle),
                     ..Default::default()
                 };
-                self.world.spawn((
+                let entity = self.world.spawn((
                     Transform { position: spawn_pos, rotation: Quat::IDENTITY, scale },
                     Velocity::default(),
                     engine_core::Health::new(skinny.effective_health()),
                     skinny,
                     physics_bug,
-                    engine_core::AIComponent::new(75.0, 2.5, 1.0),  // Skinnies: aggressive
+                    // Skinnies engage from their weapon's reach rather than melee range
+                    engine_core::AIComponent::new(75.0, skinny_type.fire_range(), skinny_type.fire_cooldown()),
                 ));
+                self.physics.set_collider_entity(collider_handle, entity);
             } else {
                 let (bug_type, variant) = self.random_bug_type();
                 let bug = Bug::new_with_variant(bug_type, variant);
@@ -3475,26 +4093,27 @@ impl GameState {
                 let half_height = scale.y * 0.6 + 0.15;
                 spawn_pos.y = terrain_y + half_height;
                 let body_handle = self.physics.add_kinematic_body(spawn_pos);
-                let collider_handle = self.physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+                let collider_handle = self.physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
                 let physics_bug = PhysicsBug {
                     body_handle: Some(body_handle),
                     collider_handle: Some(collider_handle),
                     ..Default::default()
                 };
-                self.world.spawn((
+                let entity = self.world.spawn((
                     Transform { position: spawn_pos, rotation: Quat::IDENTITY, scale },
                     Velocity::default(),
-                    engine_core::Health::new(bug.effective_health()),
+                    engine_core::Health::new(bug.effective_health() * self.difficulty.bug_health_mult()),
                     bug,
                     physics_bug,
                     engine_core::AIComponent::new(85.0, 2.5, 1.0),  // Extermination: large aggro = constant pressure
                 ));
+                self.physics.set_collider_entity(collider_handle, entity);
             }
         }
     }
 
     fn random_skinny_type(&mut self) -> SkinnyType {
-        let r = rand::random::<f32>();
+        let r = self.rng.unit();
         if r < 0.6 { SkinnyType::Grunt }
         else if r < 0.85 { SkinnyType::Sniper }
         else { SkinnyType::Officer }
@@ -3561,6 +4180,199 @@ impl GameState {
         self.spawner.random_bug_type()
     }
 
+    /// Apply the horde's signature moves to the trooper (charges bowl them over, slams knock
+    /// them back), then fly acid globs until they burst on the trooper or the ground.
+    fn update_bug_strikes(&mut self, strikes: Vec<BugStrike>, dt: f32) {
+        const CHARGE_KNOCKBACK: f32 = 14.0;
+        const SLAM_KNOCKBACK: f32 = 9.0;
+        let god_mode = self.debug.god_mode;
+        let player_pos = self.player.position;
+        let feet = player_pos - Vec3::Y * 1.6;
+
+        for strike in strikes {
+            match strike {
+                BugStrike::Spit { from, velocity, damage } => {
+                    self.acid_globs.push(AcidGlob { position: from, velocity, damage });
+                }
+                BugStrike::Charge { from, direction, damage } => {
+                    if self.abilities.blocks(from, player_pos) {
+                        continue;
+                    }
+                    if !god_mode {
+                        self.player.take_damage(damage, Some(-direction));
+                    }
+                    self.player_velocity += direction * CHARGE_KNOCKBACK + Vec3::Y * 4.5;
+                    self.player_grounded = false;
+                    self.screen_shake.add_trauma(0.45);
+                }
+                BugStrike::Pounce { from, damage } => {
+                    if self.abilities.blocks(from, player_pos) {
+                        continue;
+                    }
+                    if !god_mode {
+                        self.player.take_damage(damage, Some((from - player_pos).normalize_or_zero()));
+                    }
+                    self.screen_shake.add_trauma(0.25);
+                }
+                BugStrike::Slam { center, damage } => {
+                    for i in 0..6 {
+                        let angle = i as f32 * std::f32::consts::TAU / 6.0;
+                        let offset = Vec3::new(angle.cos() * 2.0, -0.8, angle.sin() * 2.0);
+                        self.effects.spawn_bullet_impact(center + offset, Vec3::Y, false);
+                    }
+                    let flat = Vec3::new(player_pos.x - center.x, 0.0, player_pos.z - center.z);
+                    let falloff = 1.0 - flat.length() / bug::SLAM_RADIUS;
+                    if falloff <= 0.0 || (feet.y - center.y).abs() > 3.0 {
+                        self.screen_shake.add_trauma(0.1);
+                        continue;
+                    }
+                    if !god_mode {
+                        self.player.take_damage(damage * falloff, Some(-flat.normalize_or_zero()));
+                    }
+                    self.player_velocity += flat.normalize_or_zero() * SLAM_KNOCKBACK * falloff + Vec3::Y * 3.0 * falloff;
+                    self.player_grounded = false;
+                    self.screen_shake.add_trauma(0.3 + 0.4 * falloff);
+                }
+            }
+        }
+
+        // Acid: ballistic globs that splash whatever they burst next to
+        let chest = player_pos - Vec3::Y * 0.4;
+        let mut globs = std::mem::take(&mut self.acid_globs);
+        globs.retain_mut(|glob| {
+            let from = glob.position;
+            glob.velocity.y -= bug::ACID_GRAVITY * dt;
+            glob.position += glob.velocity * dt;
+            let near = skinny::closest_point_on_segment(from, glob.position, chest);
+            let ground = self.chunk_manager.sample_height_or(glob.position.x, glob.position.z, feet.y);
+            let burst = if near.distance(chest) <= bug::ACID_SPLASH_RADIUS {
+                near
+            } else if glob.position.y <= ground {
+                Vec3::new(glob.position.x, ground, glob.position.z)
+            } else {
+                return true;
+            };
+            self.effects.spawn_bullet_impact(burst, Vec3::Y, true);
+            let splashed = skinny::closest_point_on_segment(feet, chest, burst).distance(burst) <= bug::ACID_SPLASH_RADIUS;
+            if splashed && !god_mode && !self.abilities.blocks(from, chest) {
+                self.player.take_damage(glob.damage, Some((from - chest).normalize_or_zero()));
+            }
+            false
+        });
+        self.acid_globs = globs;
+    }
+
+    /// Skinny gunfire: staggered line-of-sight checks, led shots from engaged Skinnies, and
+    /// bolts in flight that hit the trooper, a deployed shield, or whatever cover is in the way.
+    fn update_skinny_fire(&mut self, dt: f32) {
+        let chest = self.player.position - Vec3::Y * 0.4;
+        let player_velocity = self.player.velocity;
+        let cover = CollisionGroup::query(&[CollisionGroup::Environment]);
+
+        let mut shots: Vec<(Vec3, Vec3, SkinnyType, f32)> = Vec::new();
+        for (_, (transform, skinny, ai, health, physics_bug)) in self
+            .world
+            .query_mut::<(&Transform, &mut Skinny, &mut engine_core::AIComponent, &Health, &PhysicsBug)>()
+        {
+            if health.is_dead() || physics_bug.is_ragdoll {
+                continue;
+            }
+            let muzzle = transform.position + Vec3::Y * transform.scale.y * 0.9;
+            skinny.sight_timer -= dt;
+            if skinny.sight_timer <= 0.0 {
+                skinny.sight_timer = skinny::SIGHT_CHECK_INTERVAL * self.rng.range(0.75..1.25);
+                let to_chest = chest - muzzle;
+                let distance = to_chest.length();
+                let visible = distance <= skinny.skinny_type.fire_range()
+                    && self.physics.raycast_filtered(muzzle, to_chest / distance, distance, cover).is_none();
+                skinny.set_line_of_sight(visible);
+            }
+            if ai.state == engine_core::AIState::Attacking && skinny.has_line_of_sight && ai.can_attack() {
+                ai.trigger_attack();
+                shots.push((muzzle, transform.rotation * Vec3::Z, skinny.skinny_type, skinny.attack_damage));
+            }
+        }
+
+        for (muzzle, facing, skinny_type, damage) in shots {
+            let lead = skinny::lead_direction(muzzle, chest, player_velocity, skinny_type.bolt_speed());
+            let spread = skinny_type.aim_spread();
+            let jitter = Vec3::new(
+                self.rng.range(-spread..spread),
+                self.rng.range(-spread..spread),
+                self.rng.range(-spread..spread),
+            );
+            let dir = (lead + jitter).normalize_or_zero();
+            let muzzle = muzzle + facing * 0.4;
+            self.effects.spawn_muzzle_flash(muzzle, dir);
+            self.skinny_bolts.push(SkinnyBolt {
+                position: muzzle,
+                velocity: dir * skinny_type.bolt_speed(),
+                damage,
+                lifetime: skinny::BOLT_LIFETIME,
+            });
+        }
+
+        let god_mode = self.debug.god_mode;
+        let mut bolts = std::mem::take(&mut self.skinny_bolts);
+        bolts.retain_mut(|bolt| {
+            let from = bolt.position;
+            let step = bolt.velocity * dt;
+            let to = from + step;
+            bolt.position = to;
+            bolt.lifetime -= dt;
+
+            // Stopped by a shield the trooper is behind
+            if self.abilities.blocks(from, chest) && !self.abilities.blocks(to, chest) {
+                self.effects.spawn_bullet_impact(to, -step.normalize_or_zero(), false);
+                return false;
+            }
+            let wall = self.physics.raycast_filtered(from, step.normalize_or_zero(), step.length(), cover);
+            let near = skinny::closest_point_on_segment(from, to, chest);
+            let wall_first = wall.as_ref().is_some_and(|h| h.distance < from.distance(near));
+            if !wall_first && near.distance(chest) <= skinny::BOLT_HIT_RADIUS {
+                if !god_mode {
+                    self.player.take_damage(bolt.damage, Some(-bolt.velocity.normalize_or_zero()));
+                }
+                return false;
+            }
+            if let Some(hit) = wall {
+                self.effects.spawn_bullet_impact(hit.point, hit.normal, false);
+                return false;
+            }
+            bolt.lifetime > 0.0
+        });
+        self.skinny_bolts = bolts;
+    }
+
+    /// Bugs with a shot-off abdomen leak ichor and slowly bleed out.
+    fn process_wounded_bugs(&mut self, dt: f32) {
+        let mut drips: Vec<(Vec3, f32)> = Vec::new();
+        let mut bled_out: Vec<BugType> = Vec::new();
+
+        for (_, (transform, physics_bug, health, bug)) in
+            self.world.query_mut::<(&Transform, &mut PhysicsBug, &mut Health, &Bug)>()
+        {
+            if health.is_dead() || !physics_bug.abdomen_severed {
+                continue;
+            }
+            health.take_damage(physics_bug.bleed_damage(health.max, dt));
+            if health.is_dead() {
+                bled_out.push(bug.bug_type);
+            }
+            if physics_bug.tick_leak(dt) {
+                drips.push((transform.position, transform.scale.x));
+            }
+        }
+
+        for (pos, size) in drips {
+            self.effects.spawn_gore_drip(&mut self.rng, pos, size);
+        }
+        // Bleeding out still counts as the player's kill
+        for bug_type in bled_out {
+            self.register_player_kill(format!("{:?}", bug_type), false);
+        }
+    }
+
     fn process_dying_bugs(&mut self) {
         let mut gore_spawns: Vec<(Vec3, Vec3, f32)> = Vec::new();
         let mut gore_debris_spawns: Vec<(Vec3, Vec3, f32, [f32; 4])> = Vec::new();
@@ -3612,7 +4424,8 @@ impl GameState {
         }
 
         for (pos, dir, size) in gore_spawns {
-            self.effects.spawn_gore(pos, dir, size);
+            self.effects.spawn_gore(&mut self.rng, pos, dir, size);
+            self.effects.spawn_blood_decal(pos + Vec3::new(dir.x, 0.0, dir.z) * size * 0.5, size * 1.6);
         }
 
         for (pos, dir, size, color) in gore_debris_spawns {
@@ -3647,15 +4460,16 @@ impl GameState {
                         let half_height = scale.y * 0.6 + 0.15;
                         let spawn_pos = Vec3::new(spawn_pos.x, terrain_y + half_height, spawn_pos.z);
                         let body_handle = self.physics.add_kinematic_body(spawn_pos);
-                        let collider_handle = self.physics.add_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
-                        self.world.spawn((
+                        let collider_handle = self.physics.add_enemy_capsule_collider(body_handle, scale.y * 0.5, scale.x * 0.5);
+                        let entity = self.world.spawn((
                             Transform { position: spawn_pos, rotation: Quat::IDENTITY, scale },
                             Velocity::default(),
-                            Health::new(bug.effective_health()),
+                            Health::new(bug.effective_health() * self.difficulty.bug_health_mult()),
                             bug,
                             PhysicsBug { body_handle: Some(body_handle), collider_handle: Some(collider_handle), ..Default::default() },
                             engine_core::AIComponent::new(85.0, 2.5, 1.0),  // Extermination: large aggro = constant pressure
                         ));
+                        self.physics.set_collider_entity(collider_handle, entity);
                     }
                 }
                 VariantDeathEffect::Explosion => {
@@ -3667,7 +4481,7 @@ impl GameState {
                         let amount = DAMAGE * (1.0 - dist / RADIUS * 0.5);
                         let dir = (player_pos - pos).normalize_or_zero();
                         self.player.take_damage(amount, Some(dir));
-                        self.screen_shake.add_trauma(0.2);
+                        self.screen_shake.add_directional_trauma(dir, 0.2);
                     }
                     for (_, (t, health)) in self.world.query_mut::<(&Transform, &mut Health)>() {
                         if health.is_dead() { continue; }
@@ -3750,6 +4564,19 @@ impl GameState {
         count
     }
 
+    /// Camera below the water surface on a planet (underwater fog, tint and muffled audio).
+    fn camera_underwater(&self) -> bool {
+        let on_surface = matches!(
+            self.phase,
+            GamePhase::Playing | GamePhase::Paused | GamePhase::Victory | GamePhase::Defeat
+        );
+        let eye = self.camera.position();
+        on_surface
+            && self.current_planet_idx.is_some()
+            && self.chunk_manager.water_level().is_some_and(|wl| eye.y < wl)
+            && self.chunk_manager.is_in_water(eye.x, eye.z)
+    }
+
     /// Planet radius for shader curvature (d^2/2R). 0 when not on a planet surface.
     fn planet_radius_for_curvature(&self) -> f32 {
         if self.current_planet_idx.is_none() {
@@ -3763,10 +4590,10 @@ impl GameState {
         }
     }
 
-    fn update_camera_only(&mut self, _dt: f32) {
-        let mouse_delta = self.input.mouse_delta();
+    fn update_camera_only(&mut self, dt: f32) {
+        let look_delta = self.input.look_delta(dt);
         if self.input.is_cursor_locked() {
-            self.camera.process_mouse(mouse_delta.x, mouse_delta.y);
+            self.camera.process_mouse(look_delta.x, look_delta.y);
             self.player.yaw = self.camera.yaw();
             self.player.pitch = self.camera.pitch();
         }
@@ -3777,14 +4604,14 @@ impl GameState {
     fn handle_player_input(&mut self, dt: f32) {
         // If the player is locked inside the extraction boat, skip all input
         let in_boat = self.extraction.as_ref().map_or(false, |e: &ExtractionDropship| e.player_camera_locked());
-        if in_boat {
+        if in_boat || self.is_piloting() {
             return;
         }
 
-        // Mouse look (always active when cursor is locked)
-        let mouse_delta = self.input.mouse_delta();
+        // Mouse / right-stick look (always active when cursor is locked)
+        let look_delta = self.input.look_delta(dt);
         if self.input.is_cursor_locked() {
-            self.camera.process_mouse(mouse_delta.x, mouse_delta.y);
+            self.camera.process_mouse(look_delta.x, look_delta.y);
             self.player.yaw = self.camera.yaw();
             self.player.pitch = self.camera.pitch();
             self.player.look_direction = self.camera.forward();
@@ -3804,13 +4631,33 @@ impl GameState {
         }
     }
 
+    /// Pilot input for flown craft: W/S throttle, mouse/right-stick look, A/D yaw (arcade) or roll (6DOF).
+    fn flight_controls(&self, dt: f32) -> FlightControls {
+        let movement = self.input.get_movement_input();
+        let look = if self.input.is_cursor_locked() {
+            self.input.look_delta(dt) * self.camera.sensitivity
+        } else {
+            glam::Vec2::ZERO
+        };
+        let (yaw, roll) = match self.flight_mode {
+            FlightMode::Arcade => (movement.x, 0.0),
+            FlightMode::SixDof => (0.0, movement.x),
+        };
+        FlightControls { throttle: movement.y, pitch: 0.0, yaw, roll, look }
+    }
+
+    /// True while the player is flying a Tac Fighter (the trooper stands still on the ground).
+    fn is_piloting(&self) -> bool {
+        self.tac_fighters.iter().any(TacFighter::is_piloted)
+    }
+
     /// Zero-G space flight simulation: thrust (WASD + Space/Ctrl) accelerates the player; velocity persists with no gravity.
     fn handle_zero_g_movement(&mut self, dt: f32) {
         const THRUST: f32 = 28.0;
         const MAX_SPEED: f32 = 120.0;
 
         let movement = self.input.get_movement_input();
-        let move_y = if self.input.is_key_held(KeyCode::Space) {
+        let move_y = if self.input.is_action_held(Action::Jump) {
             1.0
         } else if self.input.is_crouching() {
             -1.0
@@ -3842,7 +4689,7 @@ impl GameState {
     /// Noclip free-fly camera movement (debug mode on planet).
     fn handle_noclip_movement(&mut self, dt: f32) {
         let movement = self.input.get_movement_input();
-        let move_y = if self.input.is_key_held(KeyCode::Space) {
+        let move_y = if self.input.is_action_held(Action::Jump) {
             1.0
         } else if self.input.is_crouching() {
             -1.0
@@ -3873,31 +4720,56 @@ impl GameState {
 
     /// FPS ground-based movement with gravity, terrain collision, jumping, and head bob.
     fn handle_fps_movement(&mut self, dt: f32) {
-        let movement = self.input.get_movement_input();
+        // While a stratagem code is being entered, WASD taps are code input, not movement
+        let movement = if self.stratagem_input.is_capturing() {
+            glam::Vec2::ZERO
+        } else {
+            self.input.get_movement_input()
+        };
 
-        // Crouch / prone (Helldivers 2 style): hold Ctrl = crouch; hold 0.5s = prone
-        let crouch_input = self.input.is_crouching();
-        if crouch_input {
-            self.crouch_hold_timer += dt;
-            if self.crouch_hold_timer >= 0.5 {
-                self.player.is_prone = true;
-                self.player.is_crouching = false;
-            } else {
-                self.player.is_prone = false;
-                self.player.is_crouching = true;
-            }
+        // Crouch / prone (Helldivers 2 style): tap Ctrl = toggle crouch; hold 0.5s = prone until
+        // released. While the key is down but not yet a hold we show crouch so it feels instant.
+        const PRONE_HOLD_SECS: f32 = 0.5;
+        if self.input.is_action_tapped(Action::Crouch, PRONE_HOLD_SECS) {
+            self.crouch_toggled = !self.crouch_toggled;
+        } else if self.input.is_action_released(Action::Crouch) {
+            // Released after going prone: stand back up
+            self.crouch_toggled = false;
+        }
+        if self.input.is_action_held_longer_than(Action::Crouch, PRONE_HOLD_SECS) {
+            self.player.is_prone = true;
+            self.player.is_crouching = false;
         } else {
-            self.crouch_hold_timer = 0.0;
             self.player.is_prone = false;
+            self.player.is_crouching = self.crouch_toggled || self.input.is_crouching();
+        }
+
+        // Water too deep to stand in: Ctrl dives instead of crouching. With the eye below the
+        // surface the player is swimming: no gravity or jump, Space/Ctrl swim up and down.
+        const SWIM_MIN_DEPTH: f32 = 1.6;
+        const SWIM_VERTICAL_SPEED: f32 = 3.0;
+        const TREAD_EYE_CLEARANCE: f32 = 0.35;
+        let water_level = self.chunk_manager.water_level().unwrap_or(f32::NEG_INFINITY);
+        let eye = self.camera.transform.position;
+        let in_deep_water = self.chunk_manager.is_in_water(eye.x, eye.z)
+            && self.chunk_manager.sample_height(eye.x, eye.z) < water_level - SWIM_MIN_DEPTH;
+        let is_swimming = in_deep_water && eye.y < water_level;
+        if in_deep_water {
+            self.crouch_toggled = false;
             self.player.is_crouching = false;
+            self.player.is_prone = false;
         }
+        if is_swimming {
+            self.player_grounded = false;
+        }
+        self.player.is_swimming = is_swimming;
         let is_crouching = self.player.is_crouching;
         let is_prone = self.player.is_prone;
 
         // Movement speed from class loadout
         let move_speed = self.player.move_speed;
         let sprint_mult = self.player.sprint_multiplier;
-        let is_sprinting = self.input.is_sprinting() && self.player.stamina > 0.0 && !is_prone;
+        let is_sprinting = self.input.is_sprinting() && self.player.stamina.can_sprint() && !is_prone && !is_swimming;
         let is_ads = self.player.is_aiming;
         let mut base_speed = if is_sprinting {
             move_speed * sprint_mult
@@ -3912,7 +4784,13 @@ impl GameState {
         if is_ads {
             base_speed *= 0.5; // Significant slowdown for precision aiming
         }
-        let speed = base_speed * self.hazard_slow_multiplier;
+        if is_swimming {
+            base_speed *= 0.6;
+        }
+        // Wading through snow slows proportionally to its depth at the player's feet
+        let feet = self.camera.transform.position;
+        let snow_scale = snow::snow_speed_scale(self.sample_snow_depth(feet.x, feet.z));
+        let speed = base_speed * self.hazard_slow_multiplier * snow_scale;
 
         // Horizontal movement: project camera forward/right onto horizontal plane
         let forward = self.camera.forward();
@@ -3925,10 +4803,45 @@ impl GameState {
             move_dir = move_dir.normalize();
         }
 
+        // Combat roll: double-tap a move direction while grounded (costs stamina)
+        const ROLL_DOUBLE_TAP_WINDOW: f32 = 0.25;
+        const ROLL_DURATION: f32 = 0.35;
+        const ROLL_SPEED: f32 = 12.0;
+        const ROLL_STAMINA: f32 = 25.0;
+        self.combat_roll_cooldown = (self.combat_roll_cooldown - dt).max(0.0);
+        if self.combat_roll_timer <= 0.0
+            && self.combat_roll_cooldown <= 0.0
+            && self.player_grounded
+            && !is_prone
+            && self.player.stamina.current >= ROLL_STAMINA
+        {
+            let roll_input = [
+                (Action::MoveForward, forward_flat),
+                (Action::MoveBack, -forward_flat),
+                (Action::MoveLeft, -right_flat),
+                (Action::MoveRight, right_flat),
+            ]
+            .into_iter()
+            .find(|(action, _)| self.input.is_action_double_tapped(*action, ROLL_DOUBLE_TAP_WINDOW));
+            if let Some((_, dir)) = roll_input {
+                self.combat_roll_timer = ROLL_DURATION;
+                self.combat_roll_cooldown = 0.8;
+                self.combat_roll_dir = dir;
+                self.player.stamina.spend(ROLL_STAMINA);
+                self.crouch_toggled = false;
+            }
+        }
+
         // Apply horizontal velocity with acceleration/deceleration
         let target_horizontal = move_dir * speed;
         let accel = if self.player_grounded { 40.0 } else { 10.0 }; // less air control
-        let decel = if self.player_grounded { 30.0 } else { 2.0 };
+        let decel = if self.player_grounded {
+            30.0
+        } else if is_swimming {
+            6.0 // water drag
+        } else {
+            2.0
+        };
 
         // Smoothly interpolate horizontal velocity
         let current_h = Vec3::new(self.player_velocity.x, 0.0, self.player_velocity.z);
@@ -3960,22 +4873,82 @@ impl GameState {
             }
         }
 
-        // Jump (cannot jump when prone or crouching — must stand first)
-        if self.player_grounded && self.input.is_jump_pressed() && !is_prone && !is_crouching {
+        // Combat roll overrides horizontal velocity for its duration
+        if self.combat_roll_timer > 0.0 {
+            self.combat_roll_timer -= dt;
+            self.player_velocity.x = self.combat_roll_dir.x * ROLL_SPEED;
+            self.player_velocity.z = self.combat_roll_dir.z * ROLL_SPEED;
+        }
+        // So does a class dash
+        if let Some(dash) = self.abilities.dash_velocity() {
+            self.player_velocity.x = dash.x;
+            self.player_velocity.z = dash.z;
+        }
+
+        // Jump (cannot jump when prone or crouching — must stand first). Uses the input buffer so
+        // a press shortly before landing still jumps on touchdown.
+        if self.player_grounded && self.input.is_jump_buffered() && !is_prone && !is_crouching && !is_swimming {
             self.player_velocity.y = 9.0; // jump impulse
             self.player_grounded = false;
+            self.input.consume_jump_buffer();
+        }
+
+        // Dive from the surface of deep water
+        let diving = in_deep_water && !is_swimming && self.input.is_action_held(Action::Crouch);
+        if diving {
+            self.player_velocity.y = -SWIM_VERTICAL_SPEED;
+            self.player_grounded = false;
         }
 
-        // Gravity
-        if !self.player_grounded {
+        // Gravity, or when swimming: Space/Ctrl swim up/down, otherwise drift slowly up
+        if is_swimming {
+            let target_vy = if self.input.is_action_held(Action::Jump) {
+                SWIM_VERTICAL_SPEED
+            } else if self.input.is_action_held(Action::Crouch) {
+                -SWIM_VERTICAL_SPEED
+            } else {
+                0.4
+            };
+            self.player_velocity.y += (target_vy - self.player_velocity.y) * (4.0 * dt).min(1.0);
+        } else if self.player_grounded {
+            // Keep pressing into the ground so the controller holds contact and refuses slopes
+            // that are too steep to climb
+            self.player_velocity.y = -1.0;
+        } else {
             self.player_velocity.y -= 25.0 * dt; // gravity
             // Terminal velocity
             self.player_velocity.y = self.player_velocity.y.max(-50.0);
         }
 
+        let eye_height = if is_prone { 0.4 } else if is_crouching { 1.2 } else { 1.8 };
+
         // Apply velocity to position
         let mut new_pos = self.camera.transform.position + self.player_velocity * dt;
 
+        // The character controller moves the player against terrain, rocks, egg clusters and
+        // other environment colliders: slides along them, steps onto low ones, refuses slopes
+        // that are too steep and snaps down to stay on the ground. Surfaces without colliders
+        // (corpse piles, snow, roads, treading water) are layered on by the sampling below.
+        let controller_grounded = {
+            const BODY_RADIUS: f32 = 0.35;
+            let eye = self.camera.transform.position;
+            let radius = BODY_RADIUS.min(eye_height * 0.5);
+            let half_height = (eye_height * 0.5 - radius).max(0.0);
+            let center = eye - Vec3::Y * (eye_height * 0.5);
+            let body = rapier3d::prelude::Capsule::new_y(half_height, radius);
+            let desired = new_pos - eye;
+            let movement = self.player_controller.move_shape(&self.physics, &body, center, desired, dt);
+            new_pos = eye + movement.translation;
+            // Blocked (not climbing a slope or step): drop the lost speed so it doesn't build up
+            // against a wall
+            let climbed = movement.translation.y > desired.y + 0.01;
+            if dt > 0.0 && !climbed {
+                self.player_velocity.x = movement.translation.x / dt;
+                self.player_velocity.z = movement.translation.z / dt;
+            }
+            movement.grounded
+        };
+
         // On Earth: push out of building footprints so player cannot walk through UCF buildings.
         // Do not push when the player is on a road — lets them walk on roads without being shoved.
         if self.planet.name == "Earth" {
@@ -3994,11 +4967,9 @@ impl GameState {
         // Terrain collision: sample ground height at new position
         let terrain_y = self.chunk_manager.sample_height(new_pos.x, new_pos.z);
         let is_in_water = self.chunk_manager.is_in_water(new_pos.x, new_pos.z);
-        let water_level = self.chunk_manager.water_level().unwrap_or(f32::NEG_INFINITY);
-        let eye_height = if is_prone { 0.4 } else if is_crouching { 1.2 } else { 1.8 };
 
         // Water physics: buoyancy, gentle wading slowdown (not immersion-breaking)
-        if is_in_water {
+        if is_in_water && !is_swimming {
             let submersion = (water_level - new_pos.y + eye_height).clamp(0.0, eye_height + 0.5);
             let buoyancy = submersion / (eye_height + 0.5); // 0 = at surface, 1 = fully submerged
             let buoyancy_force = buoyancy * 18.0;
@@ -4014,9 +4985,10 @@ impl GameState {
 
         // Corpse pile climbing: check nearby corpses to raise effective ground height
         // This gives the Starship Troopers: Extermination feel of walking over bug piles
-        // In water: ground is water surface (we float)
-        let mut ground_y = if is_in_water {
-            water_level
+        // In water: wade on the bottom, or tread water with the head just above the surface
+        // (swimming and diving use the bottom)
+        let mut ground_y = if is_in_water && !is_swimming && !diving {
+            terrain_y.max(water_level - eye_height + TREAD_EYE_CLEARANCE)
         } else {
             terrain_y
         };
@@ -4059,41 +5031,42 @@ impl GameState {
         }
 
         let feet_y = new_pos.y - eye_height;
-
         if feet_y <= ground_y {
-            // Below ground/corpse: snap up to surface
+            // Below a sampled surface (corpse pile, snow, road, unloaded chunk): lift onto it
             new_pos.y = ground_y + eye_height;
             self.player_velocity.y = 0.0;
             self.player_grounded = true;
-        } else if self.player_grounded {
-            // Currently grounded: stick to terrain/corpse surface on slopes
-            let gap = feet_y - ground_y;
-            if gap < 1.5 {
-                // Within stick range: smooth step up/down over corpse piles
-                new_pos.y = ground_y + eye_height;
-                self.player_velocity.y = 0.0;
-            } else {
-                // Walked off a ledge or cliff: become airborne
-                self.player_grounded = false;
-            }
+        } else if controller_grounded && self.player_velocity.y <= 0.0 {
+            // Standing on a collider; the controller's ground snap keeps us on it
+            self.player_velocity.y = 0.0;
+            self.player_grounded = true;
         } else {
-            // Airborne: check if we've landed on terrain or corpse pile
-            if feet_y - ground_y < 0.05 && self.player_velocity.y <= 0.0 {
-                new_pos.y = ground_y + eye_height;
-                self.player_velocity.y = 0.0;
-                self.player_grounded = true;
-            }
+            // Walked off a ledge, jumped, or still falling
+            self.player_grounded = false;
         }
 
-        // Head bob when walking on ground
-        if self.player_grounded {
-            let h_speed = Vec3::new(self.player_velocity.x, 0.0, self.player_velocity.z).length();
-            if h_speed > 1.0 {
-                let bob_freq = if is_sprinting { 12.0 } else { 8.0 };
-                let bob_amount = if is_sprinting { 0.06 } else { 0.03 };
-                let bob = (self.time.elapsed_seconds() * bob_freq).sin() * bob_amount * (h_speed / speed).min(1.0);
-                new_pos.y += bob;
-            }
+        // Head bob when walking on ground, with footsteps landing on each dip
+        let gait = if is_sprinting {
+            Gait::Sprint
+        } else if is_crouching || is_prone {
+            Gait::Crouch
+        } else {
+            Gait::Walk
+        };
+        let h_speed = Vec3::new(self.player_velocity.x, 0.0, self.player_velocity.z).length();
+        let walking = self.player_grounded && !is_swimming && h_speed > 1.0;
+        if self.footsteps.update(dt, gait, walking) {
+            let surface = FootSurface::classify(
+                self.chunk_manager.surface_block_at(new_pos.x, new_pos.z),
+                self.sample_snow_depth(new_pos.x, new_pos.z),
+                self.chunk_manager.is_in_water(new_pos.x, new_pos.z),
+            );
+            self.play_footstep(surface, gait);
+        }
+        if walking {
+            let bob_amount = if is_sprinting { 0.06 } else { 0.03 };
+            let bob = self.footsteps.bob() * bob_amount * (h_speed / speed).min(1.0);
+            new_pos.y += bob;
         }
 
         // Update camera and player position
@@ -4104,16 +5077,7 @@ impl GameState {
         self.player.is_sprinting = is_sprinting;
         self.player.is_crouching = is_crouching;
         self.player.is_prone = is_prone;
-
-        // Update player stamina (sprinting drains it)
-        if is_sprinting && self.player_grounded {
-            self.player.stamina -= 20.0 * dt;
-            if self.player.stamina <= 0.0 {
-                self.player.stamina = 0.0;
-            }
-        } else {
-            self.player.stamina = (self.player.stamina + 15.0 * dt).min(self.player.max_stamina);
-        }
+        // Stamina drains/recovers from these flags in `FPSPlayer::update`
     }
 
     /// Minecraft Steve scale: block size matches voxel (1m).
@@ -4125,10 +5089,12 @@ impl GameState {
         let direction = self.camera.forward();
         let max_range = 6.0;
 
-        let hits = self.physics.raycast_all(origin, direction, max_range);
-        let hit = hits
-            .into_iter()
-            .find(|h| self.chunk_manager.is_terrain_collider(h.collider) && h.distance <= max_range);
+        let hit = self.physics.raycast_filtered(
+            origin,
+            direction,
+            max_range,
+            CollisionGroup::query(&[CollisionGroup::Terrain]),
+        );
 
         if let Some(hit) = hit {
             // Snap to block center (same grid as voxel)
@@ -4181,10 +5147,12 @@ impl GameState {
         let direction = self.camera.forward();
         let max_range = 6.0;
 
-        let hits = self.physics.raycast_all(origin, direction, max_range);
-        let hit = hits
-            .into_iter()
-            .find(|h| self.chunk_manager.is_terrain_collider(h.collider) && h.distance <= max_range);
+        let hit = self.physics.raycast_filtered(
+            origin,
+            direction,
+            max_range,
+            CollisionGroup::query(&[CollisionGroup::Terrain]),
+        );
 
         if let Some(hit) = hit {
             // Place one block in the adjacent voxel (out from the hit face)
@@ -4221,6 +5189,36 @@ impl GameState {
         )
     }
 
+    /// Melee strike (V): catches the closest few bugs in a short forward cone for burst damage and
+    /// a hard stagger. Works mid-reload, on an empty magazine and with the shovel out.
+    fn handle_melee(&mut self, dt: f32) {
+        self.melee_cooldown = (self.melee_cooldown - dt).max(0.0);
+        if !self.input.is_melee_pressed() || self.melee_cooldown > 0.0 {
+            return;
+        }
+        self.melee_cooldown = fps::MELEE_COOLDOWN;
+        self.viewmodel_anim.trigger_melee();
+
+        let eye = self.camera.position();
+        let candidates: Vec<(Entity, Vec3, f32)> = self
+            .world
+            .query::<(&Transform, &Health, Option<&Bug>, Option<&Skinny>)>()
+            .iter()
+            .filter(|(_, (_, health, bug, skinny))| !health.is_dead() && (bug.is_some() || skinny.is_some()))
+            .map(|(entity, (transform, ..))| (entity, transform.position, transform.scale.x))
+            .collect();
+        let targets = melee_targets(eye, self.camera.forward(), candidates);
+        for &(entity, center) in &targets {
+            let dir = (center - eye).normalize_or_zero();
+            let radius = self.world.get::<&Transform>(entity).map_or(0.5, |t| t.scale.x);
+            let hit_point = center - dir * radius * 0.5;
+            self.check_bug_hits(eye, dir, hit_point, fps::MELEE_DAMAGE, fps::MELEE_KNOCKBACK, Some(entity));
+        }
+        if !targets.is_empty() {
+            self.screen_shake.add_trauma(0.12);
+        }
+    }
+
     fn handle_weapon_fire(&mut self) {
         if !self.player.is_alive {
             return;
@@ -4242,16 +5240,22 @@ impl GameState {
             return;
         }
 
-        if !self.input.is_fire_held() {
+        // Held fire, or a recent click (buffered so taps during fire cooldown or between frames
+        // aren't dropped).
+        if !self.input.is_fire_held() && !self.input.is_fire_buffered() {
+            return;
+        }
+
+        if self.player.current_weapon().flame_cone > 0.0 {
+            self.fire_flame_weapon(self.smoothed_dt);
             return;
         }
 
         {
             let weapon = self.player.current_weapon();
             if !weapon.can_fire() {
-                if weapon.current_ammo == 0 && weapon.reserve_ammo > 0 && !weapon.is_reloading {
+                if weapon.current_ammo == 0 && weapon.can_reload() {
                     self.player.current_weapon_mut().start_reload();
-                    self.viewmodel_anim.trigger_switch();
                 }
                 return;
             }
@@ -4270,17 +5274,19 @@ impl GameState {
         let shake_mult = if bipod_active { 0.4 } else { 1.0 };
 
         self.player.current_weapon_mut().fire();
+        self.input.consume_fire_buffer();
 
-        // --- Cinematic: weapon recoil kick ---
-        let recoil_amount = (if damage > 40.0 { 0.04 } else if damage > 20.0 { 0.025 } else { 0.015 }) * recoil_mult;
-        self.camera_recoil += recoil_amount;
+        // --- Weapon recoil: next kick in this weapon's pattern ---
+        let (kick_pitch, kick_yaw) =
+            self.recoil.kick(weapons::weapon_def(self.player.current_weapon().weapon_type), recoil_mult);
+        self.camera.set_yaw_pitch(self.camera.yaw() - kick_yaw, self.camera.pitch() + kick_pitch);
 
-        // --- Cinematic: screen shake from firing ---
+        // --- Cinematic: screen shake from firing (recoil twists the view rather than shoving it) ---
         let shake_amount = (if damage > 40.0 { 0.15 } else if damage > 20.0 { 0.08 } else { 0.04 }) * shake_mult;
-        self.screen_shake.add_trauma(shake_amount);
+        self.screen_shake.add_rotational_trauma(shake_amount);
 
-        // Spawn muzzle flash
-        let muzzle_pos = self.camera.position() + self.camera.forward() * 0.5;
+        // Spawn muzzle flash at the viewmodel's muzzle
+        let muzzle_pos = self.pose_viewmodel_rig().muzzle_position(&self.world);
         self.effects.spawn_muzzle_flash(muzzle_pos, self.camera.forward());
 
         // --- Viewmodel recoil animation ---
@@ -4298,14 +5304,8 @@ impl GameState {
                 WeaponType::Rocket => ShellCasingType::Rocket,
                 WeaponType::Flamethrower => ShellCasingType::Flamethrower,
             };
-            let (size, vel_scale, count) = match wt {
-                WeaponType::Rifle => (0.015, 1.0, 1),
-                WeaponType::Shotgun => (0.022, 1.2, projectile_count as usize),  // 8 hulls per shot
-                WeaponType::Sniper => (0.020, 1.3, 1),
-                WeaponType::MachineGun => (0.017, 1.1, 1),
-                WeaponType::Rocket => (0.018, 0.9, 1),
-                WeaponType::Flamethrower => (0.010, 0.7, 1),
-            };
+            let def = weapons::weapon_def(wt);
+            let (size, vel_scale, count) = (def.shell_size, def.shell_eject_speed, projectile_count as usize);
             let cam_right = self.camera.forward().cross(Vec3::Y).normalize_or_zero();
             let cam_up = cam_right.cross(self.camera.forward()).normalize_or_zero();
             for _ in 0..count {
@@ -4352,6 +5352,7 @@ impl GameState {
 
         let tracer_speed = 180.0;
         let tracer_lifetime = 0.25;
+        let def = weapons::weapon_def(self.player.current_weapon().weapon_type);
 
         for _ in 0..projectile_count {
             let spread_rad = effective_spread.to_radians();
@@ -4360,6 +5361,24 @@ impl GameState {
             let spread_rotation = Quat::from_euler(glam::EulerRot::XYZ, spread_x, spread_y, 0.0);
             let spread_direction = spread_rotation * direction;
 
+            // Rockets fly as real projectiles and explode on impact (see detonate_projectile)
+            if def.projectile_speed > 0.0 {
+                self.weapon_system.spawn_projectile(
+                    &mut self.world,
+                    origin + direction * 0.5,
+                    spread_direction.normalize() * def.projectile_speed,
+                    Projectile {
+                        damage,
+                        damage_type: DamageType::Explosion,
+                        explosion_radius: (def.explosion_radius > 0.0).then_some(def.explosion_radius),
+                        knockback: def.knockback,
+                        owner: None,
+                    },
+                    range / def.projectile_speed,
+                );
+                continue;
+            }
+
             // Spawn visible tracer
             self.tracer_projectiles.push(TracerProjectile {
                 position: origin + direction * 0.3,
@@ -4368,7 +5387,13 @@ impl GameState {
             });
 
             let dir = spread_direction.normalize();
-            let physics_hit = self.physics.raycast(origin, dir, range);
+            // Bullets only test terrain, props and enemies (not debris, casings or triggers)
+            let physics_hit = self.physics.raycast_filtered(
+                origin,
+                dir,
+                range,
+                CollisionGroup::query(&[CollisionGroup::Environment, CollisionGroup::Enemy]),
+            );
             let max_dist = physics_hit.as_ref().map(|h| h.distance).unwrap_or(range);
 
             // Helldivers 2 / Starship Troopers Extermination: player can destroy corpses by shooting
@@ -4376,16 +5401,17 @@ impl GameState {
                 self.raycast_corpse(origin, dir, max_dist)
             {
                 self.effects.spawn_bullet_impact(hit_point, hit_normal, true);
-                self.effects.spawn_gore(hit_point, hit_normal, 0.5);
+                self.effects.spawn_gore(&mut self.rng, hit_point, hit_normal, 0.5);
                 self.world.despawn(corpse_entity).ok();
             } else if let Some(hit) = physics_hit {
                 self.effects.spawn_bullet_impact(hit.point, hit.normal, false);
+                self.mark_hard_surface(hit.point, hit.normal, Some(hit.collider), BULLET_HOLE_SIZE, DecalKind::BulletHole);
                 let hit_entity = self.entity_for_collider(hit.collider);
-                self.check_bug_hits(origin, dir, hit.point, damage, hit_entity);
+                self.check_bug_hits(origin, dir, hit.point, damage, def.knockback, hit_entity);
                 self.check_destructible_hits(hit.point, damage);
 
                 // Terrain destruction: remove voxel blocks where the shot hits (chunks out of terrain)
-                if self.chunk_manager.is_terrain_collider(hit.collider) {
+                if self.physics.collider_in_group(hit.collider, CollisionGroup::Terrain) {
                     const VOXEL_BLOCK_SIZE: f32 = 1.0; // match procgen voxel block size (Minecraft Steve)
                     const MIN_TERRAIN_WORLD_Y: f32 = 24.0; // match procgen baseline for water level
                     let radius = if damage > 40.0 { VOXEL_BLOCK_SIZE * 1.5 } else if damage > 20.0 { VOXEL_BLOCK_SIZE } else { VOXEL_BLOCK_SIZE * 0.6 };
@@ -4402,19 +5428,125 @@ impl GameState {
         }
     }
 
+    /// Flamethrower: burn fuel, spray fire and roast everything in the cone. Flames stop at walls
+    /// and terrain, and now and then set the ground where they land alight.
+    fn fire_flame_weapon(&mut self, dt: f32) {
+        const MAX_FIRE_ZONES: usize = 24;
+
+        if !self.player.current_weapon_mut().drain_fuel(dt) {
+            let weapon = self.player.current_weapon();
+            if weapon.current_ammo == 0 && weapon.can_reload() {
+                self.player.current_weapon_mut().start_reload();
+            }
+            return;
+        }
+        self.input.consume_fire_buffer();
+
+        let weapon = self.player.current_weapon();
+        let (range, half_angle, dps) = (weapon.range, weapon.flame_cone.to_radians(), weapon.damage * weapon.fire_rate);
+        let origin = self.camera.position();
+        let dir = self.camera.forward();
+        let blocker = self.physics.raycast_filtered(
+            origin,
+            dir,
+            range,
+            CollisionGroup::query(&[CollisionGroup::Environment]),
+        );
+        let reach = blocker.as_ref().map_or(range, |h| h.distance + 1.0);
+
+        self.effects.spawn_flame_stream(origin + dir * 0.6 - Vec3::Y * 0.12, dir, reach);
+        self.viewmodel_anim.fire_kick = self.viewmodel_anim.fire_kick.max(0.25);
+        self.viewmodel_anim.fire_flash_timer = 0.0;
+
+        for (_, (transform, health)) in self
+            .world
+            .query_mut::<(&Transform, &mut Health)>()
+            .without::<&SquadMate>()
+        {
+            if health.is_dead() {
+                continue;
+            }
+            if let Some(falloff) = cone_falloff(origin, dir, reach, half_angle, transform.position, transform.scale.x * 0.5) {
+                health.take_damage(dps * falloff * dt);
+            }
+        }
+
+        // Ignite the ground where the stream lands (a few times per second, not on top of another fire)
+        let Some(hit) = blocker else { return };
+        if !self.physics.collider_in_group(hit.collider, CollisionGroup::Terrain) || rand::random::<f32>() > dt * 4.0 {
+            return;
+        }
+        let zones: Vec<Vec3> = self.world.query::<(&Transform, &FireZone)>().iter().map(|(_, (t, _))| t.position).collect();
+        if zones.len() >= MAX_FIRE_ZONES || zones.iter().any(|p| p.distance(hit.point) < 2.0) {
+            return;
+        }
+        let radius = 2.0;
+        let t = Transform {
+            position: hit.point,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::new(radius * 2.0, 0.1, radius * 2.0),
+        };
+        let cached = CachedRenderData {
+            matrix: t.to_matrix().to_cols_array_2d(),
+            color: hazard_visual_color(HazardType::LavaFlow),
+            mesh_group: MESH_GROUP_HAZARD,
+        };
+        self.world.spawn((t, FireZone { radius, dps: dps * 0.2, remaining: 6.0 }, cached));
+    }
+
+    /// Burning ground left by flame weapons: damages bugs and the player inside, then goes out.
+    fn update_fire_zones(&mut self, dt: f32) {
+        let mut burnt_out = Vec::new();
+        let mut burning: Vec<(Vec3, f32, f32)> = Vec::new();
+        for (entity, (transform, zone)) in self.world.query_mut::<(&Transform, &mut FireZone)>() {
+            zone.remaining -= dt;
+            if zone.remaining <= 0.0 {
+                burnt_out.push(entity);
+            } else {
+                burning.push((transform.position, zone.radius, zone.dps));
+            }
+        }
+        for e in burnt_out {
+            let _ = self.world.despawn(e);
+        }
+        for &(center, radius, dps) in &burning {
+            if rand::random::<f32>() < dt * 12.0 {
+                self.effects.spawn_ground_flame(center, radius);
+            }
+            for (_, (transform, health)) in self.world.query_mut::<(&Transform, &mut Health)>() {
+                if !health.is_dead() && transform.position.distance(center) < radius + transform.scale.x * 0.5 {
+                    health.take_damage(dps * dt);
+                }
+            }
+            let to_player = self.player.position - center;
+            if to_player.length() < radius + 1.0 && self.player.is_alive && !self.debug.god_mode {
+                self.player.take_damage(dps * dt, Some(to_player.normalize_or_zero()));
+            }
+        }
+    }
+
+    /// Leave a decal where a shot or blast struck a hard surface: props, structures and hulls.
+    /// Terrain (it deforms instead) and anything alive are skipped.
+    fn mark_hard_surface(&mut self, point: Vec3, normal: Vec3, collider: Option<ColliderHandle>, size: f32, kind: DecalKind) {
+        let Some(collider) = collider else {
+            return;
+        };
+        if !self.physics.collider_in_group(collider, CollisionGroup::Environment)
+            || self.physics.collider_in_group(collider, CollisionGroup::Terrain)
+        {
+            return;
+        }
+        if self.entity_for_collider(collider).is_some_and(|e| self.world.get::<&Health>(e).is_ok()) {
+            return;
+        }
+        self.renderer.spawn_decal(point, normal, size, kind);
+    }
+
     /// Find the entity that owns the given collider (bug or destructible).
     fn entity_for_collider(&self, collider: ColliderHandle) -> Option<hecs::Entity> {
-        for (entity, physics_bug) in self.world.query::<&PhysicsBug>().iter() {
-            if physics_bug.collider_handle == Some(collider) {
-                return Some(entity);
-            }
-        }
-        for (entity, phys) in self.world.query::<&DestructiblePhysics>().iter() {
-            if phys.collider_handle == collider {
-                return Some(entity);
-            }
-        }
-        None
+        self.physics
+            .collider_entity(collider)
+            .filter(|&entity| self.world.contains(entity))
     }
 
     /// Ray-sphere test: find closest BugCorpse hit by ray within max_dist.
@@ -4465,6 +5597,7 @@ impl GameState {
         direction: Vec3,
         hit_point: Vec3,
         base_damage: f32,
+        knockback: f32,
         hit_entity: Option<hecs::Entity>,
     ) {
         // Only damage the entity actually hit by the ray (e.g. bug); if ray hit terrain, hit_entity is None.
@@ -4489,32 +5622,111 @@ impl GameState {
         }
 
         for (entity, bug_pos, _dist) in candidates {
-            let hit_height = hit_point.y - bug_pos.y;
-            let is_headshot = hit_height > 0.3;
-            let damage = if is_headshot { base_damage * 2.0 } else { base_damage };
+            // Bugs: hit region from the authored mesh layout; skinnies only tell head from body
+            let bug_hit = self.world.query_one::<(&Transform, &Bug)>(entity).ok().and_then(|mut q| {
+                q.get().map(|(transform, bug)| {
+                    let mut color = bug.bug_type.color();
+                    if let Some(v) = bug.variant {
+                        let t = v.color_tint();
+                        color[0] *= t[0];
+                        color[1] *= t[1];
+                        color[2] *= t[2];
+                    }
+                    (HitRegion::from_shot(transform, origin, direction), transform.scale.x, color, bug.bug_type)
+                })
+            });
+            // Bugs take weakpoint damage per type; a skinny's weak spot is always the head
+            let (region, region_mult, is_weakpoint) = match bug_hit {
+                Some((region, _, _, bug_type)) => {
+                    (region, bug_type.region_damage_mult(region), bug_type.weakpoint(region).is_some())
+                }
+                None if hit_point.y - bug_pos.y > 0.3 => (HitRegion::Head, HitRegion::Head.damage_mult(), true),
+                None => (HitRegion::Thorax, HitRegion::Thorax.damage_mult(), false),
+            };
+            // A sealed Queen's carapace turns gunfire aside and hides her brood sac
+            let (region_mult, is_weakpoint) = match self.world.get::<&boss::QueenBoss>(entity) {
+                Ok(queen) => queen.filter_hit(region_mult, is_weakpoint),
+                Err(_) => (region_mult, is_weakpoint),
+            };
+            let is_headshot = is_weakpoint && region == HitRegion::Head;
+            let damage = base_damage * region_mult * self.kill_streaks.damage_multiplier();
 
             // Store impact direction for ragdoll
             if let Ok(mut physics_bug) = self.world.get::<&mut PhysicsBug>(entity) {
                 physics_bug.impact_velocity = direction * damage * 0.5;
             }
 
-            if let Ok(mut health) = self.world.get::<&mut Health>(entity) {
+            let damaged = self.world.get::<&mut Health>(entity).ok().map(|mut health| {
                 health.take_damage(damage);
-                let was_kill = health.is_dead();
+                (health.is_dead(), health.max)
+            });
+            if let Some((was_kill, max_health)) = damaged {
+
+                // Dismemberment: legs and abdomen come off once they've soaked enough damage
+                if let Some((_, bug_size, bug_color, _)) = bug_hit {
+                    let severed = self
+                        .world
+                        .get::<&mut PhysicsBug>(entity)
+                        .ok()
+                        .and_then(|mut physics_bug| physics_bug.register_limb_hit(region, damage, max_health));
+                    if let Some(severance) = severed {
+                        self.destruction.spawn_severed_limb(
+                            &mut self.world,
+                            hit_point,
+                            direction,
+                            bug_color,
+                            severance.debris_scale() * bug_size,
+                        );
+                        self.effects.spawn_gore(&mut self.rng, hit_point, -direction, bug_size * 0.4);
+                    }
+                }
+
+                // Survivors flinch: knocked back a little and briefly slowed
+                if !was_kill {
+                    if let Ok(mut q) = self.world.query_one::<(&mut Transform, &mut PhysicsBug)>(entity) {
+                        if let Some((transform, physics_bug)) = q.get() {
+                            transform.position +=
+                                physics_bug.apply_hit_stagger(direction, damage, knockback, transform.scale.x);
+                        }
+                    }
+                }
 
-                // Spawn blood splatter on hit
+                // Spawn blood splatter on hit, and splash the ground behind the bug
                 self.effects.spawn_bullet_impact(hit_point, -direction, true);
+                self.effects.spawn_blood_decal(
+                    hit_point + Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero() * 0.8,
+                    (damage * 0.02).clamp(0.3, 1.0),
+                );
+
+                // Weakpoint: a crunch and a bigger burst of ichor from the struck spot
+                if is_weakpoint {
+                    let burst = bug_hit.map_or(0.5, |(_, bug_size, _, _)| bug_size) * if was_kill { 1.0 } else { 0.6 };
+                    self.effects.spawn_gore(&mut self.rng, hit_point, -direction, burst);
+                    let occlusion = sound_occlusion(&self.physics, self.camera.position(), hit_point);
+                    if let Some(audio) = &mut self.audio {
+                        if let Err(e) = audio.play_at_position_with_variation(
+                            WEAKPOINT_CRUNCH_SOUND,
+                            hit_point,
+                            0.9..=1.1,
+                            0.7..=0.85,
+                            AudioBus::Sfx,
+                            occlusion,
+                        ) {
+                            log::warn!("Weakpoint crunch playback failed: {}", e);
+                        }
+                    }
+                }
 
                 self.combat.hit_markers.push(crate::fps::HitMarker {
                     is_kill: was_kill,
-                    is_headshot,
+                    is_weakpoint,
                     lifetime: 0.3,
                 });
 
                 self.combat.damage_numbers.push(crate::fps::DamageNumber {
                     position: hit_point + Vec3::Y * 0.5,
                     damage,
-                    is_critical: is_headshot,
+                    is_critical: is_weakpoint,
                     lifetime: 1.0,
                     velocity: Vec3::new(
                         rand::random::<f32>() * 2.0 - 1.0,
@@ -4524,18 +5736,6 @@ impl GameState {
                 });
 
                 if was_kill {
-                    self.player.kills += 1;
-                    self.player.damage_dealt += damage;
-
-                    // Cinematic: kill streak + extra shake on kills
-                    self.kill_streaks.register_kill();
-                    self.screen_shake.add_trauma(0.12);
-
-                    // Headshot kills get extra screen shake
-                    if is_headshot {
-                        self.screen_shake.add_trauma(0.15);
-                    }
-
                     let victim_name = if let Ok(bug) = self.world.get::<&Bug>(entity) {
                         format!("{:?}", bug.bug_type)
                     } else if let Ok(skinny) = self.world.get::<&Skinny>(entity) {
@@ -4543,18 +5743,40 @@ impl GameState {
                     } else {
                         "Enemy".to_string()
                     };
-                    self.combat.kill_feed.push(crate::fps::KillFeedEntry {
-                        killer: self.player.callsign.clone(),
-                        victim: victim_name,
-                        weapon: self.player.current_weapon().weapon_type,
-                        was_headshot: is_headshot,
-                        lifetime: 5.0,
-                    });
+                    self.register_player_kill(victim_name, is_headshot);
+                    self.player.damage_dealt += damage;
+
+                    // Cinematic: extra shake on kills
+                    self.screen_shake.add_trauma(0.12);
+
+                    if bug_hit.is_some_and(|(_, _, _, bug_type)| matches!(bug_type, BugType::Tanker | BugType::Queen)) {
+                        self.hitstop();
+                    }
+
+                    // Weakpoint kills get extra screen shake and keep the streak alive longer
+                    if is_weakpoint {
+                        self.screen_shake.add_trauma(0.15);
+                        self.kill_streaks.extend_window(WEAKPOINT_STREAK_BONUS_SECS);
+                        self.mission.weakpoint_kills += 1;
+                    }
                 }
             }
         }
     }
 
+    /// Book a kill for the player: kill count, kill streak and kill feed.
+    fn register_player_kill(&mut self, victim: String, was_headshot: bool) {
+        self.player.kills += 1;
+        self.kill_streaks.register_kill();
+        self.combat.kill_feed.push(crate::fps::KillFeedEntry {
+            killer: self.player.callsign.clone(),
+            victim,
+            weapon: self.player.current_weapon().weapon_type,
+            was_headshot,
+            lifetime: 5.0,
+        });
+    }
+
     fn check_destructible_hits(&mut self, hit_point: Vec3, damage: f32) {
         let hit_radius = 1.2;
 
@@ -4580,6 +5802,11 @@ impl GameState {
         let mut to_spawn_debris: Vec<(Vec3, u32, f32)> = Vec::new();
         let mut chain_reactions: Vec<(Vec3, f32, f32)> = Vec::new(); // (center, radius, damage)
         for (entity, pos, debris_count, debris_size) in to_damage {
+            let plinked = self.world.get::<&Destructible>(entity).is_ok_and(|d| d.requires_explosive);
+            if plinked && !self.hole_hint_given {
+                self.hole_hint_given = true;
+                self.game_messages.info("Bullets won't close a bug hole. Use a rocket or call in a strike!");
+            }
             let destroyed = self.world.get::<&mut Destructible>(entity).map_or(false, |mut d| d.damage(damage));
             if destroyed {
                 to_spawn_debris.push((pos, debris_count, debris_size));
@@ -4593,9 +5820,15 @@ impl GameState {
         }
         for (center, radius, chain_damage) in chain_reactions {
             self.apply_chain_reaction(center, radius, chain_damage);
+            self.hitstop();
         }
 
-        // Remove all destroyed destructible entities (and their physics bodies)
+        self.remove_destroyed_destructibles();
+    }
+
+    /// Remove all destroyed destructible entities (and their physics bodies, flow-field obstacles,
+    /// and any holes shot in them).
+    fn remove_destroyed_destructibles(&mut self) {
         let to_remove: Vec<hecs::Entity> = self
             .world
             .query::<&Destructible>()
@@ -4607,19 +5840,58 @@ impl GameState {
             if let Ok(phys) = self.world.get::<&DestructiblePhysics>(e) {
                 self.physics.remove_body(phys.body_handle);
             }
+            if let Ok(transform) = self.world.get::<&Transform>(e) {
+                self.renderer.clear_decals_near(transform.position, transform.scale.max_element() * 1.5 + 0.5);
+            }
+            self.horde_ai.remove_entity_obstacle(e);
             let _ = self.world.despawn(e);
         }
     }
 
+    /// Rocket impact: direct hit on the struck bug, then blast damage, a crater and debris.
+    fn detonate_projectile(&mut self, impact: &ProjectileImpact) {
+        let hit_entity = impact.collider.and_then(|c| self.entity_for_collider(c));
+        if hit_entity.is_some() {
+            self.check_bug_hits(impact.position, -impact.normal, impact.position, impact.damage, impact.knockback, hit_entity);
+        }
+        let Some(radius) = impact.explosion_radius else {
+            self.effects.spawn_bullet_impact(impact.position, impact.normal, hit_entity.is_some());
+            self.mark_hard_surface(impact.position, impact.normal, impact.collider, BULLET_HOLE_SIZE, DecalKind::BulletHole);
+            return;
+        };
+        self.effects.spawn_tac_explosion(impact.position);
+        self.mark_hard_surface(impact.position, impact.normal, impact.collider, radius * SCORCH_SIZE_PER_RADIUS, DecalKind::Scorch);
+        self.apply_chain_reaction(impact.position, radius, impact.damage * 0.5);
+        self.snow.melt_at(impact.position, radius);
+        let on_terrain = impact
+            .collider
+            .is_some_and(|c| self.physics.collider_in_group(c, CollisionGroup::Terrain));
+        if on_terrain {
+            self.chunk_manager.deform_at(
+                impact.position,
+                radius * 0.5,
+                radius * 0.25,
+                self.renderer.device(),
+                &mut self.physics,
+            );
+        }
+        self.destruction.spawn_debris(&mut self.world, impact.position, 12, 0.3, &mut self.physics);
+        let dist = self.player.position.distance(impact.position);
+        self.screen_shake.add_directional_trauma(self.player.position - impact.position, (0.5 * (1.0 - dist / (radius * 6.0))).max(0.0));
+    }
+
+    /// Freeze the action for a beat to sell a heavy impact. Ignored while the cooldown runs.
+    fn hitstop(&mut self) {
+        if self.hitstop_cooldown > 0.0 || self.time.is_paused() {
+            return;
+        }
+        self.time.dilate(HITSTOP_SCALE, HITSTOP_HOLD, HITSTOP_RAMP);
+        self.hitstop_cooldown = HITSTOP_COOLDOWN_SECS;
+    }
+
     /// Apply chain reaction from a destroyed destructible: radius damage to destructibles, bugs, and player.
     fn apply_chain_reaction(&mut self, center: Vec3, radius: f32, damage: f32) {
-        self.destruction.apply_explosion(
-            &mut self.world,
-            &mut self.physics,
-            center,
-            radius,
-            damage,
-        );
+        self.blast_destructibles(center, radius, damage);
         let player_pos = self.player.position;
         let dist = (player_pos - center).length();
         if dist < radius && self.player.is_alive && !self.debug.god_mode {
@@ -4627,7 +5899,7 @@ impl GameState {
             let amount = damage * falloff;
             let dir = (player_pos - center).normalize_or_zero();
             self.player.take_damage(amount, Some(dir));
-            self.screen_shake.add_trauma((amount / 50.0).min(0.4));
+            self.screen_shake.add_directional_trauma(dir, (amount / 50.0).min(0.4));
         }
         for (_, (transform, health)) in self.world.query_mut::<(&Transform, &mut Health)>() {
             if health.is_dead() {
@@ -4641,6 +5913,184 @@ impl GameState {
         }
     }
 
+    /// Blast damage to destructibles, then cave in the bug holes it sealed and set off the
+    /// chain reactions of whatever it destroyed.
+    fn blast_destructibles(&mut self, center: Vec3, radius: f32, damage: f32) {
+        let outcome = self.destruction.apply_explosion(&mut self.world, &mut self.physics, center, radius, damage);
+        self.seal_bug_holes(outcome.sealed);
+        for (position, chain) in outcome.chain_reactions {
+            self.apply_chain_reaction(position, chain.radius, chain.damage);
+            self.hitstop();
+        }
+    }
+
+    /// Bug holes brought down by a blast: count the seal and start them caving in.
+    fn seal_bug_holes(&mut self, sealed: Vec<SealedHole>) {
+        for hole in sealed {
+            self.mission.holes_sealed += 1;
+            self.screen_shake.add_trauma(0.25);
+            self.game_messages.success("Bug hole sealed!");
+            self.hole_collapses.push(HoleCollapse::new(hole));
+        }
+    }
+
+    /// Sealed holes cave in: dirt fills them from the bottom up, throwing dust off the rim.
+    fn update_hole_collapses(&mut self, dt: f32) {
+        let mut collapses = std::mem::take(&mut self.hole_collapses);
+        for collapse in &mut collapses {
+            for (center, radius) in collapse.update(dt) {
+                self.chunk_manager.deform_mound_at(center, radius, 0.0, self.renderer.device(), &mut self.physics);
+                for i in 0..6 {
+                    let angle = i as f32 * std::f32::consts::TAU / 6.0 + self.rng.range(0.0..0.5);
+                    let rim = collapse.position + Vec3::new(angle.cos(), 0.2, angle.sin()) * collapse.radius;
+                    self.effects.spawn_bullet_impact(rim, Vec3::Y, false);
+                }
+            }
+        }
+        collapses.retain(|c| !c.is_done());
+        self.hole_collapses = collapses;
+    }
+
+    /// Frag grenades: press G to pull the pin, keep holding to cook, release to throw. Thrown
+    /// grenades ride their rigid bodies until the fuse runs out.
+    fn update_frag_grenades(&mut self, dt: f32) {
+        let can_throw = self.current_planet_idx.is_some() && self.phase == GamePhase::Playing && self.player.is_alive;
+        if let Some(cook) = self.grenade_cook.as_mut() {
+            *cook += dt;
+            let fuse = cooked_fuse(*cook);
+            if fuse <= 0.0 {
+                self.grenade_cook = None;
+                self.game_messages.warning("COOKED TOO LONG!");
+                self.detonate_frag(self.camera.position() + self.camera.forward() * 0.5);
+            } else if !self.player.is_alive {
+                // Dropped live where the trooper fell
+                self.grenade_cook = None;
+                self.spawn_frag(self.player.position + Vec3::Y * 0.3, Vec3::ZERO, fuse);
+            } else if !self.input.is_action_held(Action::Grenade) {
+                self.grenade_cook = None;
+                let (origin, velocity) = self.grenade_release();
+                self.spawn_frag(origin, velocity, fuse);
+                self.game_messages.info("FRAG OUT!");
+            }
+        } else if can_throw && self.input.is_grenade_pressed() {
+            if self.player.grenades > 0 {
+                self.player.grenades -= 1;
+                self.grenade_cook = Some(0.0);
+            } else {
+                self.game_messages.warning("OUT OF GRENADES");
+            }
+        }
+
+        let mut detonated = Vec::new();
+        for grenade in &mut self.frag_grenades {
+            if let Some(transform) = self.physics.get_body_transform(grenade.body) {
+                grenade.position = transform.position;
+            }
+            if grenade.tick(dt) {
+                detonated.push((grenade.body, grenade.position));
+            }
+        }
+        self.frag_grenades.retain(|g| g.fuse > 0.0);
+        for (body, position) in detonated {
+            self.physics.remove_body(body);
+            self.detonate_frag(position);
+        }
+    }
+
+    /// Weapons on the ground: squadmates who bled out drop theirs, and the trooper can swap the held weapon
+    /// for the one under the crosshair, leaving the old one in its place.
+    fn update_weapon_pickups(&mut self) {
+        if self.current_planet_idx.is_none() {
+            return;
+        }
+        let mut fallen = Vec::new();
+        for (_, (transform, mate)) in self.world.query_mut::<(&Transform, &mut SquadMate)>() {
+            if mate.status == SquadMateStatus::Dead && !mate.weapon_dropped {
+                mate.weapon_dropped = true;
+                fallen.push((transform.position, mate.kind.weapon()));
+            }
+        }
+        for (position, weapon_type) in fallen {
+            let (x, z) = (position.x + 0.6, position.z + 0.3);
+            let ground = Vec3::new(x, self.chunk_manager.walkable_height(x, z), z);
+            let yaw = self.rng.range(0.0..std::f32::consts::TAU);
+            self.spawn_weapon_pickup(ground, yaw, WeaponPickup::full(weapon_type));
+        }
+
+        let Some(InteractionTarget::WeaponPickup(entity)) = self.player.interaction_target else {
+            return;
+        };
+        let Some((position, pickup)) = self
+            .world
+            .query_one_mut::<(&Transform, &WeaponPickup)>(entity)
+            .ok()
+            .map(|(transform, pickup)| (transform.position, *pickup))
+        else {
+            return;
+        };
+        let carried = self.player.weapons.iter().any(|w| w.weapon_type == pickup.weapon_type);
+        self.interaction_prompt = Some(InteractPrompt {
+            key: INTERACT_KEY,
+            action: if carried {
+                format!("Take {:?} ammo", pickup.weapon_type)
+            } else {
+                format!("Pick up {:?}", pickup.weapon_type)
+            },
+        });
+        if !self.input.is_interact_pressed() {
+            return;
+        }
+        let _ = self.world.despawn(entity);
+        self.player.interaction_target = None;
+        match self.player.pick_up_weapon(pickup) {
+            Some(dropped) => {
+                let yaw = self.player.yaw + self.rng.range(-0.6..0.6);
+                self.spawn_weapon_pickup(position, yaw, dropped);
+                self.game_messages.info(format!("Picked up {:?}, dropped {:?}", pickup.weapon_type, dropped.weapon_type));
+            }
+            None => self.game_messages.info(format!("+{} {:?} rounds", pickup.ammo, pickup.weapon_type)),
+        }
+    }
+
+    /// Lay a weapon on its side on the ground at `position`.
+    fn spawn_weapon_pickup(&mut self, position: Vec3, yaw: f32, pickup: WeaponPickup) {
+        let transform = Transform {
+            position: position + Vec3::Y * 0.05,
+            rotation: Quat::from_rotation_y(yaw) * Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::ONE,
+        };
+        self.world.spawn((transform, pickup));
+    }
+
+    /// Where a grenade released now leaves the hand, and how fast.
+    fn grenade_release(&self) -> (Vec3, Vec3) {
+        let forward = self.camera.forward();
+        (self.camera.position() + forward * 0.6, throw_velocity(forward, self.player.velocity))
+    }
+
+    fn spawn_frag(&mut self, position: Vec3, velocity: Vec3, fuse: f32) {
+        let (body, _) = self.physics.add_grenade_body(position, velocity, GRENADE_RADIUS);
+        self.frag_grenades.push(FragGrenade { body, position, fuse });
+    }
+
+    /// Arc the grenade in hand would fly if released now (None when not cooking).
+    fn grenade_preview(&self) -> Option<Vec<Vec3>> {
+        let cook = self.grenade_cook?;
+        let (origin, velocity) = self.grenade_release();
+        Some(trajectory_preview(origin, velocity, self.physics.gravity.y, cooked_fuse(cook), |x, z| {
+            self.chunk_manager.walkable_height(x, z)
+        }))
+    }
+
+    fn detonate_frag(&mut self, position: Vec3) {
+        self.effects.spawn_tac_explosion(position);
+        self.apply_chain_reaction(position, FRAG_RADIUS, FRAG_DAMAGE);
+        self.snow.melt_at(position, FRAG_RADIUS * 0.5);
+        self.destruction.spawn_debris(&mut self.world, position, 6, 0.2, &mut self.physics);
+        let dist = self.player.position.distance(position);
+        self.screen_shake.add_directional_trauma(self.player.position - position, (0.4 * (1.0 - dist / (FRAG_RADIUS * 5.0))).max(0.0));
+    }
+
     /// Cycle to the next planet in the current star system (R key).
     fn regenerate_planet(&mut self) {
         let num_planets = self.current_system.bodies.len();
@@ -4677,9 +6127,17 @@ impl GameState {
             let _ = self.world.despawn(entity);
         }
         self.effects = EffectsManager::new();
-        self.rain_drops.clear();
-        self.snow_particles.clear();
+        self.renderer.clear_decals();
+        self.particles.clear();
         self.tracer_projectiles.clear();
+        self.skinny_bolts.clear();
+        self.acid_globs.clear();
+        self.hole_collapses.clear();
+        self.hole_hint_given = false;
+        for grenade in self.frag_grenades.drain(..) {
+            self.physics.remove_body(grenade.body);
+        }
+        self.grenade_cook = None;
         self.last_player_track_pos = None;
         self.ground_track_bug_timer = 0.0;
         self.squad_track_last.clear();
@@ -4698,32 +6156,16 @@ impl GameState {
         )
     }
 
+    /// Play one player footstep (no-op without audio or samples for the surface).
+    fn play_footstep(&mut self, surface: FootSurface, gait: Gait) {
+        if let Some(audio) = &mut self.audio {
+            self.footsteps.play(audio, surface, gait);
+        }
+    }
+
     /// Sample snow accumulation at world (x, z). Returns 0 if outside the 128m tile or no snow.
     fn sample_snow_depth(&self, x: f32, z: f32) -> f32 {
-        let (ox, oz) = self.snow_accumulation_origin;
-        let world_size = 2.0 * DEFORM_HALF_SIZE;
-        let texels_per_unit = (DEFORM_TEXTURE_SIZE as f32) / world_size;
-        let i_f = (x - ox + DEFORM_HALF_SIZE) * texels_per_unit;
-        let j_f = (z - oz + DEFORM_HALF_SIZE) * texels_per_unit;
-        if i_f < -0.5 || i_f > DEFORM_TEXTURE_SIZE as f32 - 0.5
-            || j_f < -0.5 || j_f > DEFORM_TEXTURE_SIZE as f32 - 0.5
-        {
-            return 0.0;
-        }
-        let i0 = (i_f - 0.5).floor() as i32;
-        let j0 = (j_f - 0.5).floor() as i32;
-        let i1 = (i0 + 1).min(DEFORM_TEXTURE_SIZE as i32 - 1);
-        let j1 = (j0 + 1).min(DEFORM_TEXTURE_SIZE as i32 - 1);
-        let i0 = i0.max(0);
-        let j0 = j0.max(0);
-        let fx = (i_f - 0.5 - i0 as f32).clamp(0.0, 1.0);
-        let fy = (j_f - 0.5 - j0 as f32).clamp(0.0, 1.0);
-        let idx = |i: i32, j: i32| i as usize + j as usize * (DEFORM_TEXTURE_SIZE as usize);
-        let s00 = self.snow_accumulation_buffer[idx(i0, j0)];
-        let s10 = self.snow_accumulation_buffer[idx(i1, j0)];
-        let s01 = self.snow_accumulation_buffer[idx(i0, j1)];
-        let s11 = self.snow_accumulation_buffer[idx(i1, j1)];
-        s00 * (1.0 - fx) * (1.0 - fy) + s10 * fx * (1.0 - fy) + s01 * (1.0 - fx) * fy + s11 * fx * fy
+        self.snow.sample_depth(x, z)
     }
 
     /// Emit ground tracks (footprints) for player, squad, and bugs when moving on snow/sand.
@@ -4844,6 +6286,9 @@ impl GameState {
             );
 
             self.current_planet_idx = None;
+            if let Some(audio) = &mut self.audio {
+                audio.clear_reverb_zones();
+            }
             self.defense_base = None;
             self.settlement_center = None;
             self.earth_waypoints = None;
@@ -4854,6 +6299,7 @@ impl GameState {
             for h in self.earth_building_colliders.drain(..) {
                 self.physics.remove_collider(h);
             }
+            self.horde_ai.clear_static_obstacles();
             self.dialogue_state = DialogueState::Closed;
 
             // Clear terrain chunks (we're in space now)
@@ -4865,8 +6311,8 @@ impl GameState {
                 let _ = self.world.despawn(entity);
             }
             self.effects = EffectsManager::new();
-            self.rain_drops.clear();
-            self.snow_particles.clear();
+            self.renderer.clear_decals();
+            self.particles.clear();
             self.artillery_shells.clear();
             self.artillery_muzzle_flashes.clear();
             self.artillery_trail_particles.clear();
@@ -4878,6 +6324,7 @@ impl GameState {
                 self.physics.remove_body(s.body_handle);
             }
             self.artillery_barrage = None;
+            self.artillery_impacts.clear();
             self.extraction_squadmates_aboard.clear();
             self.last_player_track_pos = None;
             self.ground_track_bug_timer = 0.0;
@@ -4952,28 +6399,29 @@ impl GameState {
         // Flag grid resolution
         let flag_cols = 16;
         let flag_rows = 12;
-        let flag_w = 3.0;
-        let flag_h = 2.0;
+        let flag_size = glam::Vec2::new(3.0, 2.0);
 
         // UCF flag: port wall (-X side), mounted high, hanging toward center (+X)
-        let ucf_flag = ClothFlag::new(
+        let ucf_flag = Cloth::new(
             Vec3::new(-9.4, 3.8, 8.0),  // top-left pin (near wall, high up)
             Vec3::new(0.0, 0.0, -1.0),  // pole runs along -Z (flag extends left-to-right on wall)
             Vec3::new(1.0, 0.0, 0.0),   // hangs toward center (+X, away from port wall)
-            flag_w, flag_h,
+            flag_size,
             flag_cols, flag_rows,
             ucf_flag_colors(flag_cols, flag_rows),
-        );
+        )
+        .interior();
 
         // MI flag: starboard wall (+X side), mounted high, hanging toward center (-X)
-        let mi_flag = ClothFlag::new(
+        let mi_flag = Cloth::new(
             Vec3::new(9.4, 3.8, 8.0),   // top-left pin
             Vec3::new(0.0, 0.0, -1.0),  // pole runs along -Z
             Vec3::new(-1.0, 0.0, 0.0),  // hangs toward center (-X, away from starboard wall)
-            flag_w, flag_h,
+            flag_size,
             flag_cols, flag_rows,
             mi_flag_colors(flag_cols, flag_rows),
-        );
+        )
+        .interior();
 
         // Set up ship state (preserve next_mission_type so player choice persists)
         self.ship_state = Some(ShipState {
@@ -5000,10 +6448,26 @@ impl GameState {
         self.player.position = self.camera.transform.position;
         self.player.is_alive = true;
         self.player_velocity = Vec3::ZERO;
+        self.set_reverb_zones(false);
 
         self.phase = GamePhase::InShip;
     }
 
+    /// Reverb for where the player is: the Roger Young's steel rooms (same bounds as the
+    /// interior clamp in `update_ship`), or open ground on a planet.
+    fn set_reverb_zones(&mut self, on_planet: bool) {
+        let Some(audio) = &mut self.audio else { return };
+        audio.clear_reverb_zones();
+        if on_planet {
+            audio.add_reverb_zone(ReverbZone::new(Vec3::splat(-1.0e6), Vec3::splat(1.0e6), ReverbPreset::OpenField));
+        } else {
+            // CIC main room, the corridor aft, and the drop bay at the end of it
+            audio.add_reverb_zone(ReverbZone::new(Vec3::new(-9.0, 0.0, -14.0), Vec3::new(9.0, 6.0, 14.0), ReverbPreset::SteelCorridor));
+            audio.add_reverb_zone(ReverbZone::new(Vec3::new(-2.5, 0.0, -25.0), Vec3::new(2.5, 4.0, -14.0), ReverbPreset::SteelCorridor));
+            audio.add_reverb_zone(ReverbZone::new(Vec3::new(-3.5, 0.0, -30.5), Vec3::new(3.5, 8.0, -25.0), ReverbPreset::Hangar));
+        }
+    }
+
     /// Switch the war table to a different star system (stays in ship; new procgen planets/biomes).
     fn switch_war_table_system(&mut self, system_idx: usize) {
         self.current_system_idx = system_idx;
@@ -5011,7 +6475,7 @@ impl GameState {
         let seed = self.current_system.seed;
         self.orbital_time = ((seed % 100000) as f64 * 0.123).rem_euclid(628.0);
         let num_planets = self.current_system.bodies.len();
-        self.war_state = GalacticWarState::new(num_planets);
+        self.war_state = GalacticWarState::new(num_planets, &mut GameRng::new(self.current_system.seed));
         self.war_state.selected_planet = 0;
         if let Some(ref mut ship) = self.ship_state {
             ship.target_planet_idx = 0;
@@ -5021,6 +6485,64 @@ impl GameState {
         }
     }
 
+    /// Rearm timer of a stratagem.
+    fn cooldown(&self, stratagem: Stratagem) -> Cooldown {
+        self.stratagem_cooldowns.get(&stratagem).copied().unwrap_or_default()
+    }
+
+    fn cooldown_mut(&mut self, stratagem: Stratagem) -> &mut Cooldown {
+        self.stratagem_cooldowns.entry(stratagem).or_default()
+    }
+
+    /// Stratagem rearm multiplier: difficulty preset, supply-line reinforcement and any
+    /// Major Order clearance for this stratagem.
+    fn stratagem_cooldown_mult(&self, stratagem: Stratagem) -> f32 {
+        let clearance = if self.granted_rewards.contains(&OrderReward::StratagemClearance(stratagem)) {
+            STRATAGEM_CLEARANCE_MULT
+        } else {
+            1.0
+        };
+        self.difficulty.stratagem_cooldown_mult() * self.supply.stratagem_cooldown_mult * clearance
+    }
+
+    /// Heavy weapons on offer at the war table: the Morita MG plus every requisitioned weapon.
+    fn heavy_weapon_choices(&self) -> Vec<WeaponType> {
+        let mut choices = vec![WeaponType::MachineGun];
+        choices.extend(self.granted_rewards.iter().filter_map(|reward| match reward {
+            OrderReward::WeaponRequisition(weapon) if weapons::weapon_def(*weapon).requisition => Some(*weapon),
+            _ => None,
+        }));
+        choices
+    }
+
+    /// Apply Major Order rewards completed this frame: record, announce and save them.
+    fn grant_rewards(&mut self, rewards: Vec<OrderReward>) {
+        if rewards.is_empty() {
+            return;
+        }
+        for reward in rewards {
+            self.game_messages.success("MAJOR ORDER COMPLETE!".to_string());
+            self.game_messages.success(reward.announcement());
+            if !self.granted_rewards.contains(&reward) {
+                self.granted_rewards.push(reward);
+            }
+        }
+        save::save(self.universe_seed, self.current_system_idx, &self.war_state, self.difficulty, &self.granted_rewards, self.heavy_weapon);
+    }
+
+    /// Whether a stratagem could be called right now apart from its cooldown
+    /// (fleet has room, no barrage or dropship already in progress).
+    fn stratagem_available(&self, stratagem: Stratagem) -> bool {
+        match stratagem {
+            Stratagem::OrbitalStrike => {
+                self.tac_fighters.len() + 4 <= MAX_TAC_FIGHTERS && self.tac_fighter_available
+            }
+            Stratagem::Artillery => self.artillery_barrage.is_none(),
+            Stratagem::Extraction => self.extraction.is_none() && self.player.is_alive,
+            Stratagem::SupplyDrop | Stratagem::Reinforce | Stratagem::Smoke => true,
+        }
+    }
+
     /// Complete a successful extraction — player boards the retrieval boat and
     /// returns to the Federation Destroyer in orbit.
     fn complete_extraction(&mut self) {
@@ -5030,6 +6552,8 @@ impl GameState {
         let kills = self.mission.bugs_killed;
         let time = self.mission.time_survived_str();
         let peak = self.mission.peak_bugs_alive;
+        let weakpoint_kills = self.mission.weakpoint_kills;
+        let holes_sealed = self.mission.holes_sealed;
         let threat = self.spawner.threat_level.name();
 
         // Record kills and extraction in the galactic war state
@@ -5038,15 +6562,15 @@ impl GameState {
         if let Some(status) = self.war_state.planets.get_mut(planet_idx) {
             status.active_operation = false;
         }
-        save_galactic_war(self.universe_seed, self.current_system_idx, &self.war_state);
+        save::save(self.universe_seed, self.current_system_idx, &self.war_state, self.difficulty, &self.granted_rewards, self.heavy_weapon);
 
         if self.planet.name == "Earth" {
             self.game_messages.success("Dropship returning to Roger Young. Good visit, trooper.".to_string());
             self.game_messages.info("Remember what we're fighting for. The Federation thanks you.".to_string());
         } else {
             self.game_messages.success(format!(
-                "EXTRACTION COMPLETE | Kills: {} | Survived: {} | Peak bugs: {} | Threat: {}",
-                kills, time, peak, threat,
+                "EXTRACTION COMPLETE | Kills: {} ({} weakpoint) | Holes sealed: {} | Survived: {} | Peak bugs: {} | Threat: {}",
+                kills, weakpoint_kills, holes_sealed, time, peak, threat,
             ));
             self.game_messages.info("\"I'm from Buenos Aires, and I say kill 'em all!\"".to_string());
         }
@@ -5055,7 +6579,7 @@ impl GameState {
         self.leave_planet();
 
         // Reset horde systems
-        self.spawner = BugSpawner::new(self.planet.bug_spawn_rate(), self.planet.danger_level);
+        self.spawner = BugSpawner::new(self.planet.bug_spawn_rate(), self.planet.danger_level, self.difficulty, self.rng.fork(self.planet.seed));
         let biome_table = get_biome_feature_table(self.planet.primary_biome);
         self.spawner.set_biome_variant(biome_table.bug_variant, biome_table.variant_chance);
         self.mission = MissionState::new_horde();
@@ -5072,9 +6596,13 @@ impl GameState {
             if let Some(body_h) = dropship.hull_body.take() {
                 self.physics.remove_body(body_h);
             }
+            if let Some(trigger) = dropship.lz_trigger.take() {
+                self.physics.remove_collider(trigger);
+                self.player_triggers.remove(&trigger);
+            }
         }
         self.extraction = None;
-        self.extraction_cooldown = 0.0;
+        self.cooldown_mut(Stratagem::Extraction).reset();
         self.extraction_collider = None;
         self.lz_smoke = None;
         self.supply_drop_smoke.clear();
@@ -5300,7 +6828,7 @@ impl GameState {
                 scale: Vec3::new(scale, scale * 0.4, scale),
             };
             let cached = CachedRenderData { matrix: t.to_matrix().to_cols_array_2d(), color: [0.18, 0.14, 0.10, 1.0], mesh_group: MESH_GROUP_BUG_HOLE };
-            self.world.spawn((t, Destructible::new(200.0 + scale * 50.0, 6, 0.4), BugHole::new(spawn_interval, max_bugs), cached));
+            self.world.spawn((t, Destructible::new(200.0 + scale * 50.0, 6, 0.4).explosive_only(), BugHole::new(spawn_interval, max_bugs), cached));
         }
 
         // ---- Hive structures (only on HiveWorlds, never on Earth) ----
@@ -5322,7 +6850,7 @@ impl GameState {
                 let cached = CachedRenderData { matrix: t.to_matrix().to_cols_array_2d(), color: [0.12, 0.08, 0.06, 1.0], mesh_group: MESH_GROUP_HIVE_CAVE_ENTRANCE };
                 self.world.spawn((
                     t,
-                    Destructible::new(550.0 + scale * 40.0, 22, 0.52),
+                    Destructible::new(550.0 + scale * 40.0, 22, 0.52).explosive_only(),
                     BugHole::new(spawn_interval, 16),
                     ChainReaction { radius: 6.5, damage: 58.0, effect: ChainEffect::Collapse },
                     HiveTunnelEntrance,
@@ -5428,7 +6956,8 @@ impl GameState {
             let collider = self.physics.add_static_env_box_collider(body, half);
             let phys = DestructiblePhysics { body_handle: body, collider_handle: collider };
             let cached = CachedRenderData { matrix: t.to_matrix().to_cols_array_2d(), color: rock_color, mesh_group: MESH_GROUP_ROCK };
-            self.world.spawn((t, Destructible::new(40.0 + scale * 60.0, 6, 0.25), Rock, cached, phys));
+            let entity = self.world.spawn((t, Destructible::new(40.0 + scale * 60.0, 6, 0.25), Rock, cached, phys));
+            self.physics.set_collider_entity(collider, entity);
         }
 
         // ---- Biome-specific decorations (trees, crystals, etc.) ----
@@ -5608,7 +7137,16 @@ impl GameState {
             let body = self.physics.add_static_body_with_rotation(t.position, t.rotation);
             let collider = self.physics.add_static_env_box_collider(body, t.scale * 0.5);
             let phys = DestructiblePhysics { body_handle: body, collider_handle: collider };
-            self.world.spawn((t, Destructible::new(500.0, 12, 0.4), CrashedShip, cached, phys));
+            let entity = self.world.spawn((t, Destructible::new(500.0, 12, 0.4), CrashedShip, cached, phys));
+            self.physics.set_collider_entity(collider, entity);
+
+            // A salvageable weapon thrown clear of the wreck
+            let salvage_angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            let sx = x + salvage_angle.cos() * (scale * 2.0 + 1.5);
+            let sz = z + salvage_angle.sin() * (scale * 2.0 + 1.5);
+            let weapon_type = WeaponType::ALL[rng.gen_range(0..WeaponType::ALL.len())];
+            let salvage = Vec3::new(sx, self.chunk_manager.sample_height(sx, sz), sz);
+            self.spawn_weapon_pickup(salvage, salvage_angle, WeaponPickup::full(weapon_type));
         }
 
         // ---- Bone piles / skeleton heaps (biome-dependent) ----
@@ -5856,13 +7394,14 @@ impl GameState {
                 let body = self.physics.add_static_body_with_rotation(t.position, t.rotation);
                 let collider = self.physics.add_static_env_box_collider(body, t.scale * 0.5);
                 let phys = DestructiblePhysics { body_handle: body, collider_handle: collider };
-                self.world.spawn((
+                let entity = self.world.spawn((
                     t,
                     Destructible::new(health, 5, 0.25),
                     BiomeLandmark { landmark_type: *landmark_type },
                     cached,
                     phys,
                 ));
+                self.physics.set_collider_entity(collider, entity);
             }
         }
 
@@ -5916,7 +7455,7 @@ impl GameState {
                 let body = self.physics.add_static_body_with_rotation(t.position, t.rotation);
                 let collider = self.physics.add_static_env_box_collider(body, t.scale * 0.5);
                 let phys = DestructiblePhysics { body_handle: body, collider_handle: collider };
-                self.world.spawn((
+                let entity = self.world.spawn((
                     t,
                     Destructible::new(health, 8, 0.3),
                     BiomeDestructible { landmark_type: *landmark_type },
@@ -5924,6 +7463,7 @@ impl GameState {
                     cached,
                     phys,
                 ));
+                self.physics.set_collider_entity(collider, entity);
             }
         }
     }
@@ -5968,9 +7508,154 @@ impl GameState {
             }
         }
 
+        // Cover inside the walls: a pair of barricades behind each wall, sandbags further in,
+        // crate stacks in the corners
+        let inset = half_extent - 4.0;
+        for (dir, yaw) in [
+            (Vec3::Z, 0.0),
+            (Vec3::NEG_Z, std::f32::consts::PI),
+            (Vec3::X, -std::f32::consts::FRAC_PI_2),
+            (Vec3::NEG_X, std::f32::consts::FRAC_PI_2),
+        ] {
+            let along = Quat::from_rotation_y(yaw) * Vec3::X;
+            for offset in [-6.0, 6.0] {
+                self.spawn_cover(Cover::Barricade, dir * inset + along * offset, yaw);
+            }
+            self.spawn_cover(Cover::Sandbags, dir * (inset - 4.0), yaw);
+            // Turning each wall direction a quarter turn lands on a different corner per wall
+            let corner = (dir + dir.cross(Vec3::Y)) * (inset - 2.0);
+            self.spawn_cover(Cover::Crate, corner, yaw);
+        }
+
         self.defense_base = Some((Vec3::new(0.0, base_y, 0.0), half_extent));
     }
 
+    /// Spawn a piece of destructible cover standing on the terrain at `at` (XZ).
+    fn spawn_cover(&mut self, cover: Cover, at: Vec3, yaw: f32) {
+        let scale = cover.scale();
+        let y = self.chunk_manager.sample_height(at.x, at.z);
+        let t = Transform {
+            position: Vec3::new(at.x, y + scale.y * 0.5, at.z),
+            rotation: Quat::from_rotation_y(yaw),
+            scale,
+        };
+        let mesh_group = if cover == Cover::Barricade { MESH_GROUP_CUBE } else { MESH_GROUP_BEVELED_CUBE };
+        let cached = CachedRenderData { matrix: t.to_matrix().to_cols_array_2d(), color: cover.color(), mesh_group };
+        let body = self.physics.add_static_body_with_rotation(t.position, t.rotation);
+        let collider = self.physics.add_static_env_box_collider(body, t.scale * 0.5);
+        let phys = DestructiblePhysics { body_handle: body, collider_handle: collider };
+        let entity = self.world.spawn((t, cover.destructible(), cover, cached, phys));
+        self.physics.set_collider_entity(collider, entity);
+    }
+
+    /// Bugs pressed up against base cover chew through it.
+    fn update_cover(&mut self, dt: f32) {
+        if self.defense_base.is_none() {
+            return;
+        }
+        let bugs: Vec<Vec3> = self
+            .world
+            .query::<(&Transform, &Bug, &Health, &PhysicsBug)>()
+            .iter()
+            .filter(|(_, (_, _, health, physics_bug))| !health.is_dead() && physics_bug.is_surfaced())
+            .map(|(_, (transform, ..))| transform.position)
+            .collect();
+        let mut destroyed = Vec::new();
+        for (_, (transform, destructible)) in self.world.query_mut::<(&Transform, &mut Destructible)>().with::<&Cover>() {
+            if destructible.health <= 0.0 {
+                continue;
+            }
+            let reach = transform.scale.max_element() * 0.5 + COVER_CHEW_REACH;
+            let chewing = bugs
+                .iter()
+                .filter(|b| b.distance_squared(transform.position) < reach * reach)
+                .count()
+                .min(COVER_CHEW_MAX_BUGS);
+            if chewing > 0 && destructible.damage(chewing as f32 * COVER_CHEW_DPS * dt) {
+                destroyed.push((transform.position, destructible.debris_count, destructible.debris_size));
+            }
+        }
+        if destroyed.is_empty() {
+            return;
+        }
+        for (pos, debris_count, debris_size) in destroyed {
+            self.destruction.spawn_debris(&mut self.world, pos, debris_count, debris_size, &mut self.physics);
+        }
+        self.remove_destroyed_destructibles();
+        self.game_messages.warning("Cover's down! The bugs chewed through it!");
+    }
+
+    /// Spawn what the mission's objectives point at, around the landing site: hives to destroy,
+    /// stranded citizens to escort back to the LZ, or the point to capture and hold.
+    fn spawn_mission_objectives(&mut self, landing: Vec3) {
+        let mut rng = rand::thread_rng();
+        match self.mission.mission_type {
+            fps::MissionType::HiveDestruction => {
+                let first_angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                let mut targets = Vec::new();
+                for i in 0..objective::HIVE_TARGET_COUNT {
+                    let angle = first_angle + i as f32 * std::f32::consts::TAU / objective::HIVE_TARGET_COUNT as f32;
+                    let dist = rng.gen_range(objective::HIVE_TARGET_DISTANCE);
+                    let x = landing.x + angle.cos() * dist;
+                    let z = landing.z + angle.sin() * dist;
+                    let y = self.chunk_manager.sample_height(x, z);
+                    let scale = 3.5;
+                    let t = Transform {
+                        position: Vec3::new(x, y + scale * 0.3, z),
+                        rotation: Quat::from_rotation_y(rng.gen::<f32>() * std::f32::consts::TAU),
+                        scale: Vec3::new(scale, scale * 1.6, scale),
+                    };
+                    let cached = CachedRenderData { matrix: t.to_matrix().to_cols_array_2d(), color: [0.42, 0.16, 0.12, 1.0], mesh_group: MESH_GROUP_HIVE_MOUND };
+                    targets.push(self.world.spawn((
+                        t,
+                        Destructible::new(600.0, 30, 0.5).explosive_only(),
+                        BugHole::new(4.0, 10),
+                        ChainReaction { radius: 7.0, damage: 60.0, effect: ChainEffect::Explosion },
+                        HiveStructure,
+                        cached,
+                    )));
+                }
+                self.mission.objectives.push(objective::Objective::destroy_hives(targets));
+                // The Queen comes up once her hives are burning
+                self.mission.objectives.push(objective::Objective::slay_queen());
+                self.game_messages.warning(format!("{} hives marked. Blow them shut, trooper!", objective::HIVE_TARGET_COUNT));
+            }
+            fps::MissionType::Rescue => {
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                let site = landing + Vec3::new(angle.cos(), 0.0, angle.sin()) * objective::RESCUE_SITE_DISTANCE;
+                let names = ["Carmen", "Ace", "Sugar", "Kitten", "Breckinridge", "Watkins"];
+                let mut citizens = Vec::new();
+                for i in 0..objective::RESCUE_CITIZEN_COUNT {
+                    let x = site.x + (rng.gen::<f32>() - 0.5) * 6.0;
+                    let z = site.z + (rng.gen::<f32>() - 0.5) * 6.0;
+                    let y = self.chunk_manager.sample_height(x, z) + 0.5;
+                    citizens.push(self.world.spawn((
+                        Transform { position: Vec3::new(x, y, z), rotation: Quat::IDENTITY, scale: Vec3::splat(1.0) },
+                        Velocity::default(),
+                        Citizen::new(names[i % names.len()].to_string(), i % 5, &mut rng, 0, 0),
+                        objective::Rescuee { health: objective::RESCUE_CITIZEN_HEALTH, following: false },
+                    )));
+                }
+                self.mission.objectives.push(objective::Objective::rescue(citizens, landing, objective::RESCUE_REQUIRED));
+                self.game_messages.warning(format!(
+                    "Stranded colonists {:.0}m out. Bring {} of them back to the LZ alive!",
+                    objective::RESCUE_SITE_DISTANCE,
+                    objective::RESCUE_REQUIRED,
+                ));
+            }
+            fps::MissionType::Defense => {
+                let (center, radius) = match self.defense_base {
+                    Some((center, half_extent)) => (center, half_extent),
+                    None => (landing, objective::DEFENSE_POINT_RADIUS),
+                };
+                self.mission.objectives.push(objective::Objective::capture_point(center, radius));
+                self.mission.objectives.push(objective::Objective::hold_point(center, radius, objective::DEFENSE_HOLD_SECS));
+                self.game_messages.warning("Capture the point, then hold it. Don't let the bugs overrun it!".to_string());
+            }
+            _ => {}
+        }
+    }
+
     /// Arrive at a new star system after warp.
     fn arrive_at_system(&mut self, system_idx: usize) {
         self.current_system_idx = system_idx;
@@ -5979,7 +7664,7 @@ impl GameState {
         let seed = self.current_system.seed;
         self.orbital_time = ((seed % 100000) as f64 * 0.123).rem_euclid(628.0); // ~0..100 orbits worth
         // Initialize war state for the new system
-        self.war_state = GalacticWarState::new(self.current_system.bodies.len());
+        self.war_state = GalacticWarState::new(self.current_system.bodies.len(), &mut GameRng::new(self.current_system.seed));
 
         self.game_messages.success(format!("Arrived at {} !", self.current_system.name));
         self.game_messages.info(format!(
@@ -6008,9 +7693,17 @@ impl GameState {
             let _ = self.world.despawn(entity);
         }
         self.effects = EffectsManager::new();
-        self.rain_drops.clear();
-        self.snow_particles.clear();
+        self.renderer.clear_decals();
+        self.particles.clear();
         self.tracer_projectiles.clear();
+        self.skinny_bolts.clear();
+        self.acid_globs.clear();
+        self.hole_collapses.clear();
+        self.hole_hint_given = false;
+        for grenade in self.frag_grenades.drain(..) {
+            self.physics.remove_body(grenade.body);
+        }
+        self.grenade_cook = None;
         self.last_player_track_pos = None;
         self.ground_track_bug_timer = 0.0;
         self.squad_track_last.clear();
@@ -6105,7 +7798,7 @@ impl GameState {
                     atmo_rgb[0],
                     atmo_rgb[1],
                     atmo_rgb[2],
-                    if body.ring_system { 1.0 } else { 0.0 },
+                    if body.ring.is_some() { 1.0 } else { 0.0 },
                 ];
                 instances.push(CelestialBodyInstance {
                     position: pos.into(),
@@ -6310,7 +8003,7 @@ impl GameState {
                     atmo_rgb[0],
                     atmo_rgb[1],
                     atmo_rgb[2],
-                    if body.ring_system { 1.0 } else { 0.0 },
+                    if body.ring.is_some() { 1.0 } else { 0.0 },
                 ];
 
                 instances.push(CelestialBodyInstance {
@@ -6458,6 +8151,46 @@ impl GameState {
             }
         }
 
+        // Ring and belt debris last, so the renderer's instance cap trims debris before bodies
+        for (i, body) in self.current_system.bodies.iter().enumerate() {
+            let Some(ring) = &body.ring else { continue };
+            if self.current_planet_idx == Some(i) {
+                continue;
+            }
+            let body_pos = body.orbital_position(self.orbital_time);
+            let rel = body_pos - cam_dvec;
+            let center = Vec3::new(rel.x as f32, rel.y as f32, rel.z as f32);
+            if center.length() > 200000.0 {
+                continue;
+            }
+            let bts = (-body_pos).normalize();
+            for p in ring.particles() {
+                instances.push(CelestialBodyInstance {
+                    position: (center + p.position).into(),
+                    radius: p.radius,
+                    color: [ring.color.x, ring.color.y, ring.color.z, 0.3],
+                    star_direction: [bts.x as f32, bts.y as f32, bts.z as f32, 0.0],
+                    atmosphere_color: [0.0, 0.0, 0.0, 0.0],
+                });
+            }
+        }
+        if let Some(belt) = &self.current_system.asteroid_belt {
+            for p in belt.particles(self.orbital_time) {
+                let rel_f = p.position - cam_pos;
+                if rel_f.length_squared() > 200000.0 * 200000.0 {
+                    continue;
+                }
+                let to_star = (-p.position).normalize_or_zero();
+                instances.push(CelestialBodyInstance {
+                    position: rel_f.into(),
+                    radius: p.radius,
+                    color: [0.42, 0.38, 0.34, 0.3],
+                    star_direction: [to_star.x, to_star.y, to_star.z, 0.0],
+                    atmosphere_color: [0.0, 0.0, 0.0, 0.0],
+                });
+            }
+        }
+
         instances
     }
 
@@ -6535,101 +8268,76 @@ impl GameState {
         }
     }
 
-    fn sky_weather_params(&self) -> (Vec3, f32, f32, f32) {
+    fn sky_weather_params(&self) -> (Vec3, f32, f32) {
         let (sun_dir, _) = self.compute_sun_direction_and_time_of_day(self.current_planet_idx);
-        (sun_dir, self.weather.cloud_density, self.weather.dust, self.weather.fog_density)
-    }
-
-    fn update_rain(&mut self, dt: f32) {
-        let (spawn_rate, fall_speed) = self.weather.rain_params();
-
-        if spawn_rate > 0 && self.player.is_alive {
-            let cam = self.camera.position();
-            for _ in 0..(spawn_rate as f32 * dt) as usize {
-                let x = cam.x + (rand::random::<f32>() - 0.5) * 40.0;
-                let z = cam.z + (rand::random::<f32>() - 0.5) * 40.0;
-                let y = cam.y + rand::random::<f32>() * 20.0;
-                self.rain_drops.push(RainDrop {
-                    position: Vec3::new(x, y, z),
-                    velocity: Vec3::new(0.5, -fall_speed, 0.2),
-                    life: 2.0,
-                });
-            }
-        }
-
-        for r in &mut self.rain_drops {
-            r.position += r.velocity * dt;
-            r.life -= dt;
-        }
-        // Just use lifetime to cull rain (avoids 800 sample_height calls per frame)
-        self.rain_drops.retain(|r| r.life > 0.0);
-        if self.rain_drops.len() > 400 {
-            self.rain_drops.drain(0..(self.rain_drops.len() - 400));
-        }
+        (sun_dir, self.weather.cloud_density, self.weather.dust)
     }
 
-    fn update_snow(&mut self, dt: f32) {
-        let (spawn_rate, fall_speed) = self.weather.snow_params();
-        if spawn_rate > 0 && self.player.is_alive {
-            let cam = self.camera.position();
-            for _ in 0..spawn_rate {
-                let x = cam.x + (rand::random::<f32>() - 0.5) * 35.0;
-                let z = cam.z + (rand::random::<f32>() - 0.5) * 35.0;
-                let y = cam.y + rand::random::<f32>() * 18.0;
-                let size = 0.04 + rand::random::<f32>() * 0.05;
-                self.snow_particles.push(SnowParticle {
-                    position: Vec3::new(x, y, z),
-                    velocity: Vec3::new(
-                        (rand::random::<f32>() - 0.5) * 1.5,
-                        -fall_speed,
-                        (rand::random::<f32>() - 0.5) * 1.5,
-                    ),
-                    life: 4.0,
-                    size,
-                });
-            }
-        }
-        for s in &mut self.snow_particles {
-            s.position += s.velocity * dt;
-            s.life -= dt;
-        }
-        self.snow_particles.retain(|s| s.life > 0.0);
-        if self.snow_particles.len() > 350 {
-            self.snow_particles.drain(0..(self.snow_particles.len() - 350));
-        }
+    /// Set rain, snow and dust emission from weather and location, then step all particles once.
+    fn update_particles(&mut self, dt: f32) {
+        let alive = self.player.is_alive;
+        let (rain_rate, rain_speed) = self.weather.rain_params();
+        let rain_rate = if alive { rain_rate as f32 } else { 0.0 };
+        self.particles.set_emission(ParticleKind::Rain, rain_rate, rain_speed, usize::MAX);
+        // snow_params counts flakes per 60 Hz frame
+        let (snow_rate, snow_speed) = self.weather.snow_params();
+        let snow_rate = if alive { snow_rate as f32 * 60.0 } else { 0.0 };
+        self.particles.set_emission(ParticleKind::Snow, snow_rate, snow_speed, usize::MAX);
+        // Ambient dust only on a planet surface; more floating particles when cloudy/rain/snow
+        let dust_mult = 1.0 + self.weather.dust * 2.5;
+        let dust_rate = if self.current_planet_idx.is_some() && alive { 20.0 * dust_mult } else { 0.0 };
+        self.particles.set_emission(ParticleKind::Dust, dust_rate, 0.0, (150.0 * dust_mult.min(2.5)) as usize);
+        self.particles.update(dt, self.camera.position());
     }
 
+    /// Class ability (Q); the cooldown was already started by `FPSPlayer::use_ability`.
     fn execute_ability(&mut self) {
         match self.player.ability {
-            fps::ClassAbility::JetpackBurst => {
-                let forward = self.camera.forward();
-                self.player.velocity = forward * 15.0 + Vec3::Y * 12.0;
-                self.player.is_grounded = false;
-            }
-            fps::ClassAbility::ScanPulse => {
-                let bug_count = self.world.query::<&Bug>().iter().count();
-                let skinny_count = self.world.query::<&Skinny>().iter().count();
-                self.game_messages.info(format!("SCAN: {} bugs, {} Skinnies detected in area!", bug_count, skinny_count));
-            }
-            fps::ClassAbility::DeployBarricade => {
+            ClassAbility::Dash => {
+                let movement = self.input.get_movement_input();
+                let (forward, right) = (self.camera.forward(), self.camera.right());
+                let dir = forward * movement.y + right * movement.x;
+                self.abilities.start_dash(if movement == glam::Vec2::ZERO { forward } else { dir });
+            }
+            ClassAbility::ScanPulse => {
+                let origin = self.player.position;
+                let mut found: Vec<(hecs::Entity, f32)> = self
+                    .world
+                    .query::<(&Transform, &Health, &Bug)>()
+                    .iter()
+                    .filter(|(_, (_, health, _))| !health.is_dead())
+                    .map(|(entity, (t, _, _))| (entity, t.position.distance(origin)))
+                    .filter(|(_, d)| *d <= ability::SCAN_RADIUS)
+                    .collect();
+                found.sort_by(|a, b| a.1.total_cmp(&b.1));
+                let count = found.len();
+                self.abilities.mark(found.into_iter().map(|(entity, _)| entity));
+                self.game_messages.info(format!("SCAN: {} bugs within {:.0}m", count, ability::SCAN_RADIUS));
+            }
+            ClassAbility::DeployBarricade => {
+                let pos = self.player.position;
+                let feet = Vec3::new(pos.x, self.chunk_manager.walkable_height(pos.x, pos.z), pos.z);
+                self.abilities.deploy_barricade(feet, self.camera.forward());
                 self.game_messages.info("Barricade deployed!");
             }
-            fps::ClassAbility::AmmoStation => {
+            ClassAbility::AmmoStation => {
                 for weapon in &mut self.player.weapons {
-                    weapon.reserve_ammo += weapon.magazine_size * 2;
+                    weapon.reserve_ammo += weapon.magazine_size * ability::AMMO_STATION_MAGAZINES;
                 }
                 self.game_messages.info("Ammo resupplied!");
             }
-            fps::ClassAbility::ShieldDome => {
-                self.player.add_armor(25.0);
-                self.game_messages.info("Shield dome activated! +25 armor");
+            ClassAbility::ShieldDome => {
+                let pos = self.player.position;
+                let feet = Vec3::new(pos.x, self.chunk_manager.walkable_height(pos.x, pos.z), pos.z);
+                self.abilities.deploy_dome(feet);
+                self.game_messages.info("Shield dome deployed!");
             }
         }
     }
 
     fn cleanup_dead_bugs(&mut self) {
         // Helldivers 2 / Starship Troopers Extermination: corpses stay until player destroys them
-        const MAX_CORPSES: usize = 800;
+        // (or the corpse budget fades the oldest/farthest out)
         const SETTLE_WINDOW: f32 = 2.0; // seconds of gravity settling after spawn
 
         // Convert fully dead bugs into corpse entities (lightweight, no physics)
@@ -6646,6 +8354,7 @@ impl GameState {
                     BugType::Spitter => 2,
                     BugType::Tanker => 3,
                     BugType::Hopper => 4,
+                    BugType::Queen => 5,
                 };
                 let mut color = bug.bug_type.color();
                 if let Some(v) = bug.variant {
@@ -6758,25 +8467,13 @@ impl GameState {
             grid.entry((cx, cz)).or_default().push(idx);
         }
 
-        // ── Cap corpse count: remove oldest first ──
-        let total_corpses = corpse_positions.len();
-        if total_corpses > MAX_CORPSES {
-            // Collect (entity, decay_timer) so we can remove the most-decayed first
-            let mut by_decay: Vec<(hecs::Entity, f32)> = Vec::new();
-            for (entity, (_, corpse)) in self.world.query::<(&Transform, &BugCorpse)>().iter() {
-                by_decay.push((entity, corpse.decay_timer));
-            }
-            by_decay.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-            let excess = total_corpses - MAX_CORPSES;
-            for i in 0..excess.min(by_decay.len()) {
-                self.world.despawn(by_decay[i].0).ok();
-            }
-        }
+        // ── Corpse budget: fade out the farthest/oldest, keep fresh piles near the player ──
+        self.destruction.enforce_corpse_budget(&mut self.world, self.player.position);
 
         // ── Single pass: decay timer + gravity settle (only unsettled corpses) ──
         let dt = self.time.delta_seconds();
         let mut decayed: Vec<hecs::Entity> = Vec::new();
-        // Rebuild positions after cap removal for settling
+        // Rebuild positions for settling
         let mut settle_data: Vec<(hecs::Entity, Vec3, Vec3)> = Vec::new();
         for (entity, (transform, corpse)) in self.world.query::<(&Transform, &BugCorpse)>().iter() {
             if !corpse.settled {
@@ -6874,6 +8571,9 @@ impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.state.is_none() {
             let config = config::GameConfig::load();
+            weapons::load_weapon_defs();
+            fps::load_class_loadouts();
+            state::load_streak_milestones();
             let window_attrs = Window::default_attributes()
                 .with_title("OpenSST")
                 .with_inner_size(winit::dpi::LogicalSize::new(config.window_width, config.window_height));
@@ -6889,7 +8589,18 @@ impl ApplicationHandler for App {
 
             let state = pollster::block_on(GameState::new(window.clone()));
             match state {
-                Ok(s) => {
+                Ok(mut s) => {
+                    s.input.set_sensitivity(glam::Vec2::splat(config.sensitivity));
+                    s.input.set_invert_y(config.invert_y);
+                    s.camera.set_base_fov(config.fov);
+                    s.chunk_manager.set_view_distance(config.view_distance);
+                    s.chunk_manager.set_chunk_budget(config.max_loaded_chunks);
+                    s.destruction.set_corpse_budget(config.max_corpses);
+                    s.combat.set_max_damage_numbers(config.max_damage_numbers);
+                    s.stratagem_input.set_code_entry(config.stratagem_codes);
+                    if config.flight_six_dof {
+                        s.flight_mode = FlightMode::SixDof;
+                    }
                     self.state = Some(s);
                     window.request_redraw();
                 }
@@ -6955,3 +8666,114 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_budget_clamps_view_distance_and_evicts_the_farthest() {
+        // 49 chunks hold a 7x7 square: radius 3, however far the view distance setting goes
+        assert_eq!(ChunkManager::budgeted_view_distance(1000.0, MAX_VIEW_DISTANCE, 49), 3);
+        assert_eq!(ChunkManager::budgeted_view_distance(1000.0, 2, 49), 2);
+        // Near the ground the view shrinks further, but never below one ring
+        assert_eq!(ChunkManager::budgeted_view_distance(0.0, 4, 49), 2);
+        assert_eq!(ChunkManager::budgeted_view_distance(0.0, 2, MIN_CHUNK_BUDGET), 1);
+
+        // A 9x9 square around (10, 10) with a budget of 49: 32 go, none closer than any that stay
+        let loaded: Vec<(i32, i32)> = (6..=14).flat_map(|x| (6..=14).map(move |z| (x, z))).collect();
+        let evicted = ChunkManager::over_budget(loaded.iter().copied(), (10, 10), 49);
+        assert_eq!(evicted.len(), loaded.len() - 49);
+        let dist2 = |&(x, z): &(i32, i32)| (x - 10).pow(2) + (z - 10).pow(2);
+        let closest_evicted = evicted.iter().map(dist2).min().unwrap();
+        let farthest_kept = loaded.iter().filter(|k| !evicted.contains(k)).map(dist2).max().unwrap();
+        assert!(farthest_kept <= closest_evicted);
+        assert!(evicted.contains(&(6, 6)) && !evicted.contains(&(10, 10)));
+        assert!(ChunkManager::over_budget(loaded.iter().copied(), (10, 10), 81).is_empty());
+    }
+
+    #[test]
+    fn supply_floods_from_held_planets_along_uncontested_lines() {
+        let mut war = GalacticWarState::new(5, &mut GameRng::new(9));
+        for status in &mut war.planets {
+            status.liberation = 0.0;
+            status.liberated = false;
+        }
+        // Chain 0-1-2-3 (2-3 contested), 4 only reachable through the contested 3-4 line
+        war.supply_lines = vec![
+            SupplyLine { from: 0, to: 1, contested: false },
+            SupplyLine { from: 1, to: 2, contested: false },
+            SupplyLine { from: 2, to: 3, contested: true },
+            SupplyLine { from: 3, to: 4, contested: true },
+        ];
+        war.update_supply();
+        assert!(war.planets.iter().all(|p| p.supply == SupplyStatus::CutOff));
+
+        war.planets[0].liberation = SUPPLY_HOLD_LIBERATION;
+        war.update_supply();
+        let supply: Vec<SupplyStatus> = war.planets.iter().map(|p| p.supply).collect();
+        use SupplyStatus::*;
+        assert_eq!(supply, vec![Reinforced, Linked, Linked, CutOff, CutOff]);
+        assert!(war.supply_modifiers(3).spawn_rate_mult > war.supply_modifiers(1).spawn_rate_mult);
+        assert!(war.supply_modifiers(0).stratagem_cooldown_mult < war.supply_modifiers(1).stratagem_cooldown_mult);
+
+        // Holding both ends of a line secures it
+        war.planets[3].liberated = true;
+        war.planets[2].liberation = 0.9;
+        war.update_supply();
+        assert!(!war.supply_lines[2].contested);
+        assert_eq!(war.planets[1].supply, Reinforced);
+        assert_eq!(war.planets[4].supply, CutOff);
+    }
+
+    #[test]
+    fn a_fresh_system_starts_with_a_held_staging_world() {
+        for seed in 0..20 {
+            let war = GalacticWarState::new(6, &mut GameRng::new(seed));
+            assert!(war.planets[0].is_held());
+            assert_eq!(war.planets[0].supply, SupplyStatus::Reinforced);
+            assert!(war.planets.iter().filter(|p| p.supply == SupplyStatus::CutOff).count() < war.planets.len());
+            // Every planet on an open line from the staging world gets supplied
+            for line in war.supply_lines.iter().filter(|l| !l.contested && (l.from == 0 || l.to == 0)) {
+                let other = if line.from == 0 { line.to } else { line.from };
+                assert_ne!(war.planets[other].supply, SupplyStatus::CutOff);
+            }
+        }
+    }
+
+    #[test]
+    fn defense_urgency_dies_down_faster_where_supply_is_cut() {
+        let mut war = GalacticWarState::new(5, &mut GameRng::new(2));
+        war.supply_lines = vec![
+            SupplyLine { from: 0, to: 1, contested: false },
+            SupplyLine { from: 1, to: 2, contested: true },
+        ];
+        for status in &mut war.planets {
+            status.defense_urgency = 0.4;
+        }
+        war.update(10.0);
+        assert_eq!((war.planets[1].supply, war.planets[2].supply), (SupplyStatus::Linked, SupplyStatus::CutOff));
+        let linked_lost = 0.4 - war.planets[1].defense_urgency;
+        let cut_lost = 0.4 - war.planets[2].defense_urgency;
+        assert!(linked_lost > 0.0);
+        assert!((cut_lost - 2.0 * linked_lost).abs() < 1e-4);
+    }
+
+    #[test]
+    fn completing_a_major_order_grants_its_reward_once() {
+        let mut war = GalacticWarState::new(6, &mut GameRng::new(4));
+        assert!(war.update(0.0).is_empty());
+
+        let target = war.major_orders[0].target_planets[0];
+        war.planets[target].liberation = 1.0;
+        assert_eq!(war.update(0.0), vec![OrderReward::WeaponRequisition(WeaponType::Flamethrower)]);
+        assert!(war.update(0.0).is_empty());
+
+        // The supply order tracks secured lines and pays out when every line is held at both ends
+        for status in &mut war.planets {
+            status.liberated = true;
+        }
+        assert_eq!(war.update(0.0), vec![OrderReward::StratagemClearance(Stratagem::OrbitalStrike)]);
+        assert!(war.major_orders.iter().all(|order| order.completed));
+    }
+}
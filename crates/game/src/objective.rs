@@ -0,0 +1,382 @@
+//! Mission objectives: what a mission actually asks of the trooper.
+//!
+//! A mission holds a list of objectives that run in order; each one starts once the one before it
+//! is complete, and the mission is complete when the last one is. Objectives that point at things
+//! in the world (hives to destroy, citizens to escort, a point to hold) are spawned at landing and
+//! checked against the world every frame.
+
+use engine_core::{Health, Transform};
+use glam::{Quat, Vec3};
+use hecs::{Entity, World};
+
+use crate::bug::Bug;
+use crate::hud::WaypointIcon;
+
+/// Hive Destruction: how many hives are spawned, and how far from the landing site (m).
+pub const HIVE_TARGET_COUNT: usize = 3;
+pub const HIVE_TARGET_DISTANCE: std::ops::Range<f32> = 70.0..120.0;
+/// Rescue: citizens waiting at the crash site, how many must reach the LZ, and how far away they are (m).
+pub const RESCUE_CITIZEN_COUNT: usize = 4;
+pub const RESCUE_REQUIRED: u32 = 3;
+pub const RESCUE_SITE_DISTANCE: f32 = 90.0;
+/// A citizen starts following once the player is this close (m).
+const RESCUE_JOIN_RANGE: f32 = 8.0;
+/// Followers keep this far behind the player (m) and move at this speed (m/s).
+const RESCUE_FOLLOW_GAP: f32 = 3.0;
+const RESCUE_FOLLOW_SPEED: f32 = 5.0;
+/// A follower inside this radius of the LZ is safe (m).
+const RESCUE_LZ_RADIUS: f32 = 10.0;
+pub const RESCUE_CITIZEN_HEALTH: f32 = 60.0;
+/// Damage per second each bug within `BUG_REACH` deals to a citizen.
+const CITIZEN_DAMAGE_PER_BUG: f32 = 12.0;
+const BUG_REACH: f32 = 2.5;
+/// Defense: point radius when there's no base to defend (m), and how long to hold it (s).
+pub const DEFENSE_POINT_RADIUS: f32 = 12.0;
+pub const DEFENSE_HOLD_SECS: f32 = 240.0;
+/// Defense: seconds standing on the point with no bugs on it to capture it.
+const CAPTURE_SECS: f32 = 8.0;
+/// Integrity lost per second per bug on the point, and the most bugs that count.
+const INTEGRITY_DRAIN_PER_BUG: f32 = 1.5;
+const INTEGRITY_DRAIN_MAX_BUGS: usize = 8;
+
+/// A citizen on a Rescue mission. Waits until the player comes for them, then follows.
+#[derive(Debug, Clone, Copy)]
+pub struct Rescuee {
+    pub health: f32,
+    pub following: bool,
+}
+
+/// Per-frame facts objectives are checked against.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectiveContext {
+    pub dt: f32,
+    pub player_pos: Vec3,
+    pub bugs_killed: u32,
+}
+
+/// One step of a mission.
+#[derive(Debug, Clone)]
+pub enum Objective {
+    /// Kill `target` bugs (counted over the whole deployment).
+    KillBugs { target: u32, killed: u32 },
+    /// Stay alive on-planet for `secs`.
+    Survive { secs: f32, elapsed: f32 },
+    /// Destroy these hive structures.
+    DestroyHives { targets: Vec<Entity>, destroyed: u32 },
+    /// Kill the Hive Queen. She's spawned by the game when this becomes the active objective;
+    /// `health` is her remaining fraction (0–1).
+    SlayQueen { queen: Option<Entity>, health: f32 },
+    /// Get `required` of these citizens to the LZ alive.
+    Rescue { citizens: Vec<Entity>, lz: Vec3, required: u32, rescued: u32, lost: u32 },
+    /// Stand on the point with no bugs on it until it's captured.
+    CapturePoint { center: Vec3, radius: f32, progress: f32 },
+    /// Keep the point for `secs`; bugs on it wear its integrity (0–100) down.
+    HoldPoint { center: Vec3, radius: f32, integrity: f32, held: f32, secs: f32 },
+}
+
+impl Objective {
+    pub fn kill_bugs(target: u32) -> Self {
+        Objective::KillBugs { target, killed: 0 }
+    }
+
+    pub fn survive(secs: f32) -> Self {
+        Objective::Survive { secs, elapsed: 0.0 }
+    }
+
+    pub fn destroy_hives(targets: Vec<Entity>) -> Self {
+        Objective::DestroyHives { targets, destroyed: 0 }
+    }
+
+    pub fn slay_queen() -> Self {
+        Objective::SlayQueen { queen: None, health: 1.0 }
+    }
+
+    pub fn rescue(citizens: Vec<Entity>, lz: Vec3, required: u32) -> Self {
+        Objective::Rescue { citizens, lz, required, rescued: 0, lost: 0 }
+    }
+
+    pub fn capture_point(center: Vec3, radius: f32) -> Self {
+        Objective::CapturePoint { center, radius, progress: 0.0 }
+    }
+
+    pub fn hold_point(center: Vec3, radius: f32, secs: f32) -> Self {
+        Objective::HoldPoint { center, radius, integrity: 100.0, held: 0.0, secs }
+    }
+
+    /// Advance this objective for one frame. Only the active objective is updated.
+    pub fn update(&mut self, world: &mut World, ctx: &ObjectiveContext, sample_terrain_y: impl Fn(f32, f32) -> f32) {
+        match self {
+            Objective::KillBugs { killed, .. } => *killed = ctx.bugs_killed,
+            Objective::Survive { elapsed, .. } => *elapsed += ctx.dt,
+            Objective::DestroyHives { targets, destroyed } => {
+                let before = targets.len();
+                targets.retain(|&e| hive_standing(world, e));
+                *destroyed += (before - targets.len()) as u32;
+            }
+            Objective::SlayQueen { queen: Some(queen), health } => {
+                *health = world
+                    .get::<&Health>(*queen)
+                    .map_or(0.0, |h| if h.is_dead() { 0.0 } else { h.current / h.max });
+            }
+            Objective::SlayQueen { queen: None, .. } => {}
+            Objective::Rescue { citizens, lz, rescued, lost, .. } => {
+                let bugs = live_bug_positions(world);
+                let mut done = Vec::new();
+                for &entity in citizens.iter() {
+                    let Ok((transform, rescuee)) = world.query_one_mut::<(&mut Transform, &mut Rescuee)>(entity) else {
+                        *lost += 1;
+                        done.push(entity);
+                        continue;
+                    };
+                    let pos = transform.position;
+                    let attackers = bugs.iter().filter(|b| b.distance_squared(pos) < BUG_REACH * BUG_REACH).count();
+                    rescuee.health -= attackers as f32 * CITIZEN_DAMAGE_PER_BUG * ctx.dt;
+                    if rescuee.health <= 0.0 {
+                        *lost += 1;
+                        done.push(entity);
+                        continue;
+                    }
+                    if !rescuee.following && flat_distance(pos, ctx.player_pos) < RESCUE_JOIN_RANGE {
+                        rescuee.following = true;
+                    }
+                    if rescuee.following {
+                        follow(transform, ctx.player_pos, ctx.dt, &sample_terrain_y);
+                    }
+                    // Colonists who fled to the LZ on their own count too
+                    if flat_distance(transform.position, *lz) < RESCUE_LZ_RADIUS {
+                        *rescued += 1;
+                        done.push(entity);
+                    }
+                }
+                citizens.retain(|e| !done.contains(e));
+                for entity in done {
+                    world.despawn(entity).ok();
+                }
+            }
+            Objective::CapturePoint { center, radius, progress } => {
+                let contested = bugs_on_point(world, *center, *radius) > 0;
+                if flat_distance(ctx.player_pos, *center) < *radius && !contested {
+                    *progress += ctx.dt;
+                }
+            }
+            Objective::HoldPoint { center, radius, integrity, held, .. } => {
+                let bugs = bugs_on_point(world, *center, *radius).min(INTEGRITY_DRAIN_MAX_BUGS);
+                *integrity = (*integrity - bugs as f32 * INTEGRITY_DRAIN_PER_BUG * ctx.dt).max(0.0);
+                *held += ctx.dt;
+            }
+        }
+    }
+
+    /// 0–1 toward completion.
+    pub fn progress(&self) -> f32 {
+        let ratio = |done: f32, total: f32| if total > 0.0 { (done / total).min(1.0) } else { 1.0 };
+        match self {
+            Objective::KillBugs { target, killed } => ratio(*killed as f32, *target as f32),
+            Objective::Survive { secs, elapsed } => ratio(*elapsed, *secs),
+            Objective::DestroyHives { targets, destroyed } => {
+                ratio(*destroyed as f32, (*destroyed as usize + targets.len()) as f32)
+            }
+            Objective::SlayQueen { health, .. } => 1.0 - health,
+            Objective::Rescue { required, rescued, .. } => ratio(*rescued as f32, *required as f32),
+            Objective::CapturePoint { progress, .. } => ratio(*progress, CAPTURE_SECS),
+            Objective::HoldPoint { held, secs, .. } => ratio(*held, *secs),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.is_failed() && self.progress() >= 1.0
+    }
+
+    /// The objective can no longer be completed (citizens lost, point overrun).
+    pub fn is_failed(&self) -> bool {
+        match self {
+            Objective::Rescue { citizens, required, rescued, .. } => rescued + (citizens.len() as u32) < *required,
+            Objective::HoldPoint { integrity, .. } => *integrity <= 0.0,
+            _ => false,
+        }
+    }
+
+    /// Short objective line for the HUD.
+    pub fn text(&self) -> String {
+        match self {
+            Objective::KillBugs { target, killed } => format!("Kill bugs {}/{}", killed.min(target), target),
+            Objective::Survive { secs, elapsed } => format!("Survive {}", mm_ss((secs - elapsed).max(0.0))),
+            Objective::DestroyHives { targets, destroyed } => {
+                format!("Destroy hives {}/{}", destroyed, *destroyed as usize + targets.len())
+            }
+            Objective::SlayQueen { health, .. } => format!("Slay the Hive Queen {:.0}%", health * 100.0),
+            Objective::Rescue { required, rescued, lost, .. } => {
+                format!("Escort citizens to LZ {}/{} (lost {})", rescued, required, lost)
+            }
+            Objective::CapturePoint { .. } => format!("Capture the point {:.0}%", self.progress() * 100.0),
+            Objective::HoldPoint { integrity, held, secs, .. } => {
+                format!("Hold the point {} | Integrity {:.0}%", mm_ss((secs - held).max(0.0)), integrity)
+            }
+        }
+    }
+
+    /// Where this objective should show on the compass and screen.
+    pub fn waypoints(&self, world: &World) -> Vec<(Vec3, WaypointIcon)> {
+        match self {
+            Objective::DestroyHives { targets, .. } => targets
+                .iter()
+                .filter_map(|&e| world.get::<&Transform>(e).ok().map(|t| (t.position, WaypointIcon::Hive)))
+                .collect(),
+            Objective::SlayQueen { queen, .. } => queen
+                .and_then(|e| world.get::<&Transform>(e).ok().map(|t| vec![(t.position, WaypointIcon::Queen)]))
+                .unwrap_or_default(),
+            Objective::Rescue { citizens, lz, .. } => {
+                let mut points = vec![(*lz, WaypointIcon::Lz)];
+                for &e in citizens {
+                    let Ok(mut query) = world.query_one::<(&Transform, &Rescuee)>(e) else {
+                        continue;
+                    };
+                    if let Some((transform, rescuee)) = query.get() {
+                        if !rescuee.following {
+                            points.push((transform.position, WaypointIcon::Citizen));
+                        }
+                    }
+                }
+                points
+            }
+            Objective::CapturePoint { center, .. } | Objective::HoldPoint { center, .. } => {
+                vec![(*center, WaypointIcon::Objective)]
+            }
+            Objective::KillBugs { .. } | Objective::Survive { .. } => Vec::new(),
+        }
+    }
+}
+
+/// A hive target still counts while its entity exists and has health left.
+fn hive_standing(world: &World, entity: Entity) -> bool {
+    world
+        .get::<&crate::destruction::Destructible>(entity)
+        .is_ok_and(|d| d.health > 0.0)
+}
+
+fn live_bug_positions(world: &World) -> Vec<Vec3> {
+    world
+        .query::<(&Transform, &Health, &Bug)>()
+        .iter()
+        .filter(|(_, (_, health, _))| !health.is_dead())
+        .map(|(_, (transform, _, _))| transform.position)
+        .collect()
+}
+
+fn bugs_on_point(world: &World, center: Vec3, radius: f32) -> usize {
+    live_bug_positions(world)
+        .into_iter()
+        .filter(|p| flat_distance(*p, center) < radius)
+        .count()
+}
+
+fn flat_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec3::new(a.x - b.x, 0.0, a.z - b.z).length()
+}
+
+/// Walk a follower toward the player, stopping a short gap behind them.
+fn follow(transform: &mut Transform, target: Vec3, dt: f32, sample_terrain_y: impl Fn(f32, f32) -> f32) {
+    let to = Vec3::new(target.x - transform.position.x, 0.0, target.z - transform.position.z);
+    let dist = to.length();
+    if dist <= RESCUE_FOLLOW_GAP {
+        return;
+    }
+    let step = (RESCUE_FOLLOW_SPEED * dt).min(dist - RESCUE_FOLLOW_GAP);
+    transform.position += to / dist * step;
+    // Citizen transforms sit 0.5 m above the ground.
+    transform.position.y = sample_terrain_y(transform.position.x, transform.position.z) + 0.5;
+    transform.rotation = Quat::from_rotation_y(f32::atan2(-to.x, -to.z));
+}
+
+fn mm_ss(secs: f32) -> String {
+    format!("{:02}:{:02}", (secs / 60.0) as u32, (secs % 60.0) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(dt: f32, player_pos: Vec3) -> ObjectiveContext {
+        ObjectiveContext { dt, player_pos, bugs_killed: 0 }
+    }
+
+    #[test]
+    fn rescued_citizens_follow_to_the_lz_and_too_many_losses_fail() {
+        let mut world = World::new();
+        let spawn = |world: &mut World, x: f32| {
+            world.spawn((
+                Transform { position: Vec3::new(x, 0.5, 0.0), ..Default::default() },
+                Rescuee { health: RESCUE_CITIZEN_HEALTH, following: false },
+            ))
+        };
+        let a = spawn(&mut world, 100.0);
+        let b = spawn(&mut world, 102.0);
+        let mut rescue = Objective::rescue(vec![a, b], Vec3::ZERO, 1);
+
+        // Nobody follows until the player reaches them
+        rescue.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        assert!(!world.get::<&Rescuee>(a).unwrap().following);
+
+        // Player picks them up, then walks back to the LZ with them in tow
+        rescue.update(&mut world, &ctx(0.1, Vec3::new(101.0, 0.0, 0.0)), |_, _| 0.0);
+        for _ in 0..400 {
+            rescue.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        }
+        assert!(rescue.is_complete());
+        assert!(!world.contains(a));
+
+        let c = spawn(&mut world, 50.0);
+        let mut rescue = Objective::rescue(vec![c], Vec3::ZERO, 1);
+        world.despawn(c).unwrap();
+        rescue.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        assert!(rescue.is_failed());
+    }
+
+    #[test]
+    fn queen_objective_tracks_her_health_until_she_falls() {
+        let mut world = World::new();
+        let mut slay = Objective::slay_queen();
+        slay.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        assert_eq!(slay.progress(), 0.0);
+
+        let queen = world.spawn((Transform::default(), Health::new(1000.0)));
+        if let Objective::SlayQueen { queen: slot, .. } = &mut slay {
+            *slot = Some(queen);
+        }
+        world.get::<&mut Health>(queen).unwrap().take_damage(400.0);
+        slay.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        assert!((slay.progress() - 0.4).abs() < 1e-5);
+        assert_eq!(slay.waypoints(&world).len(), 1);
+
+        world.despawn(queen).unwrap();
+        slay.update(&mut world, &ctx(0.1, Vec3::ZERO), |_, _| 0.0);
+        assert!(slay.is_complete());
+    }
+
+    #[test]
+    fn point_captures_only_uncontested_and_bugs_wear_down_its_integrity() {
+        let mut world = World::new();
+        let center = Vec3::ZERO;
+        let mut capture = Objective::capture_point(center, 10.0);
+        capture.update(&mut world, &ctx(CAPTURE_SECS * 0.5, Vec3::new(30.0, 0.0, 0.0)), |_, _| 0.0);
+        assert_eq!(capture.progress(), 0.0);
+
+        let bug = world.spawn((
+            Transform { position: Vec3::new(2.0, 0.0, 0.0), ..Default::default() },
+            Health::new(100.0),
+            Bug::new(crate::bug::BugType::Warrior),
+        ));
+        capture.update(&mut world, &ctx(CAPTURE_SECS, center), |_, _| 0.0);
+        assert_eq!(capture.progress(), 0.0);
+
+        let mut hold = Objective::hold_point(center, 10.0, 60.0);
+        for _ in 0..10 {
+            hold.update(&mut world, &ctx(1.0, center), |_, _| 0.0);
+        }
+        assert!(matches!(hold, Objective::HoldPoint { integrity, .. } if integrity < 100.0));
+
+        world.despawn(bug).unwrap();
+        capture.update(&mut world, &ctx(CAPTURE_SECS, center), |_, _| 0.0);
+        assert!(capture.is_complete());
+    }
+}
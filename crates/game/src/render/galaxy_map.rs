@@ -0,0 +1,66 @@
+//! Galaxy map rendering: the starfield from the map camera, then the map overlay.
+//!
+//! Drawn instead of the whole scene while the map is open, so nothing of the ship,
+//! fleet or planet is rendered behind it.
+
+use glam::{Mat4, Quat, Vec3};
+use renderer::{AtmosphereParams, InstanceData};
+
+use super::{overlay, GLOW_EMISSIVE};
+use crate::galaxy_map::{star_color, STAR_ANGULAR_SIZE};
+use crate::GameState;
+
+/// Uncharted stars glow at this fraction of charted ones, so known space stands out.
+const UNCHARTED_GLOW: f32 = 0.35;
+/// Size multiplier for the current and selected systems.
+const HIGHLIGHT_SCALE: f32 = 1.8;
+
+/// Render the galaxy map frame: space sky, one emissive sphere per shown system, bloom, overlay.
+pub fn render(
+    state: &mut GameState,
+    encoder: &mut wgpu::CommandEncoder,
+    scene_view: &wgpu::TextureView,
+    output_view: &wgpu::TextureView,
+) {
+    let map_camera = state.galaxy_map.camera(state.camera.aspect);
+    state.renderer.update_camera(&map_camera, 0.0);
+    state.renderer.update_sky(
+        0.75,
+        [0.0, -1.0, 0.0],
+        0.0, 0.0, 0.0, 0.0, 0.0,  // atmo_height=0 = space
+        [0.02, 0.025, 0.04],
+        &AtmosphereParams::default(),
+        false,
+    );
+    state.renderer.render_sky(encoder, scene_view, Some([0.0, 0.0, 0.0, 1.0]));
+
+    // Stars keep a constant apparent size at any zoom, like points
+    let eye = map_camera.position();
+    let current = state.current_system_idx;
+    let stars: Vec<InstanceData> = state.universe.systems.iter().enumerate()
+        .filter(|(i, _)| state.galaxy_map.shows(&state.universe, *i, current))
+        .map(|(i, entry)| {
+            let pos = entry.position.as_vec3();
+            let highlighted = i == current || i == state.galaxy_map_selected;
+            let radius = eye.distance(pos) * STAR_ANGULAR_SIZE * if highlighted { HIGHLIGHT_SCALE } else { 1.0 };
+            let [r, g, b, _] = star_color(entry.star_type);
+            let glow = if entry.visited || highlighted { GLOW_EMISSIVE } else { GLOW_EMISSIVE * UNCHARTED_GLOW };
+            let model = Mat4::from_scale_rotation_translation(Vec3::splat(radius), Quat::IDENTITY, pos);
+            InstanceData::new(model.to_cols_array_2d(), [r, g, b, 1.0]).with_emissive(glow)
+        })
+        .collect();
+    state.renderer.render_instanced_load(encoder, scene_view, &state.environment_meshes.prop_sphere, &stars);
+
+    let (sw, sh) = state.renderer.dimensions();
+    let tb = overlay::build(state, sw as f32, sh as f32);
+    let bloom_view = state.renderer.run_bloom_passes(encoder, scene_view);
+    state.renderer.update_cinematic_uniform(state.time.elapsed_seconds());
+    state.renderer.run_cinematic_pass(
+        encoder,
+        scene_view,
+        &bloom_view,
+        state.renderer.depth_texture_view(),
+        output_view,
+    );
+    state.renderer.render_overlay(encoder, output_view, &tb.vertices, &tb.indices);
+}
@@ -1,23 +1,27 @@
 //! Rendering: all render passes (sky, terrain, bugs, ship interior, HUD, etc.).
 
+mod galaxy_map;
 mod overlay;
 mod planet;
 mod ship;
 
 use anyhow::Result;
-use engine_core::{Health, Lifetime, Transform};
+use engine_core::{Health, Lifetime, PreviousTransform, Transform, Velocity};
 use glam::{Quat, Vec3};
 use procgen::BiomeType;
-use renderer::{InstanceData, DEFORM_HALF_SIZE, DEFORM_TEXTURE_SIZE};
+use renderer::{AtmosphereParams, FogParams, InstanceData, ParticleInstance, TransparentQueue, DEFAULT_ROUGHNESS, DEFORM_HALF_SIZE, DEFORM_TEXTURE_SIZE, sort_particles_back_to_front};
 use std::collections::HashMap;
 use wgpu;
 
-use crate::biome_atmosphere::AtmoParticleKind;
+use crate::ability::ShieldShape;
+use crate::artillery::ARTILLERY_KILL_RADIUS;
+use crate::biome_atmosphere::{atmosphere_params, AtmoParticleKind};
+use crate::boss::{QueenBoss, QueenPhase};
 use crate::bug::{Bug, BugType};
 use crate::bug_entity::{GoreType, PhysicsBug, TrackKind};
 use crate::skinny::Skinny;
 use crate::destruction::{
-    BugCorpse, BugGoreChunk, CachedRenderData, Debris, Destructible,
+    BugCorpse, BugGoreChunk, CachedRenderData, Debris, Destructible, EnvironmentalHazard, HazardType, CORPSE_FADE_TIME,
     MESH_GROUP_ROCK, MESH_GROUP_BUG_HOLE, MESH_GROUP_EGG_CLUSTER, MESH_GROUP_PROP_SPHERE,
     MESH_GROUP_CUBE, MESH_GROUP_LANDMARK, MESH_GROUP_HAZARD, MESH_GROUP_HIVE_MOUND,
     MESH_GROUP_BEVELED_CUBE, MESH_GROUP_HIVE_CAVE_ENTRANCE, ENV_MESH_GROUP_COUNT,
@@ -25,21 +29,87 @@ use crate::destruction::{
 use crate::extraction::{ExtractionDropship, ExtractionPhase, roger_young_parts};
 use crate::fleet::{surface_corvette_positions, SURFACE_CORVETTE_PARAMS};
 use crate::fps;
-use crate::citizen::Citizen;
-use crate::squad::{SquadMate, SquadMateKind};
-use crate::weapons::WeaponType;
+use crate::citizen::{Citizen, CitizenState};
+use crate::effects::ParticleKind;
+use crate::squad::{SquadMate, SquadMateKind, SquadMateStatus};
+use crate::viewmodel::{gun_parts, GunPart};
+use crate::weapons::{Projectile, WeaponPickup};
 use crate::{
-    interior_npc_parts, roger_young_interior_npcs, roger_young_interior_parts,
-    state::WeatherState,
+    interior_npc_parts, roger_young_interior_npcs, roger_young_interior_parts, InteriorNPCKind,
     DropPhase, GamePhase, GameState,
 };
 
+/// Emissive strength for glow geometry (`mesh_type` 2 parts, engine glows, alert lights)
+/// drawn with `flash_mesh`. Pushes them past the bloom threshold.
+const GLOW_EMISSIVE: f32 = 1.5;
+
+/// Surface (roughness, metallic) of ship hull and deck steel.
+const HULL_STEEL: (f32, f32) = (0.38, 0.9);
+/// Brass fittings (flag poles and caps).
+const BRASS: (f32, f32) = (0.3, 1.0);
+/// Marauder powered suits: worn bare metal.
+const MARAUDER_SUIT: (f32, f32) = (0.32, 0.85);
+/// Mobile Infantry armor: painted plate with a little sheen.
+const TROOPER_ARMOR: (f32, f32) = (0.5, 0.25);
+
+/// Armor surface of an interior NPC, or None for cloth uniforms.
+fn npc_armor(kind: InteriorNPCKind) -> Option<(f32, f32)> {
+    match kind {
+        InteriorNPCKind::Marauder => Some(MARAUDER_SUIT),
+        InteriorNPCKind::MobileInfantry | InteriorNPCKind::JohnnyRico => Some(TROOPER_ARMOR),
+        InteriorNPCKind::Fleet | InteriorNPCKind::FleetOfficer => None,
+    }
+}
+
+/// Muzzle flash light: warm, a few meters of reach, bright enough to flicker across terrain at night.
+const MUZZLE_LIGHT_COLOR: [f32; 3] = [1.0, 0.75, 0.4];
+const MUZZLE_LIGHT_RADIUS: f32 = 9.0;
+const MUZZLE_LIGHT_INTENSITY: f32 = 6.0;
+/// Lava pools glow steadily with a slow flicker.
+const LAVA_LIGHT_COLOR: [f32; 3] = [1.0, 0.35, 0.08];
+const LAVA_LIGHT_INTENSITY: f32 = 2.5;
+
+/// Register this frame's dynamic point lights (uploaded by `begin_frame`): muzzle flashes,
+/// explosion flashes and lava hazards.
+fn register_point_lights(state: &mut GameState) {
+    for flash in &state.effects.muzzle_flashes {
+        if flash.intensity > 0.01 {
+            state.renderer.add_point_light(flash.position, MUZZLE_LIGHT_COLOR, MUZZLE_LIGHT_RADIUS, MUZZLE_LIGHT_INTENSITY * flash.intensity);
+        }
+    }
+    for light in &state.effects.flash_lights {
+        state.renderer.add_point_light(light.position, light.color, light.radius, light.current_intensity());
+    }
+    let t = state.time.elapsed_seconds();
+    for (_, (transform, hazard)) in state.world.query::<(&Transform, &EnvironmentalHazard)>().iter() {
+        if hazard.hazard_type != HazardType::LavaFlow {
+            continue;
+        }
+        let seed = transform.position.x * 0.37 + transform.position.z * 0.61;
+        let flicker = 0.85 + 0.15 * (t * 3.1 + seed).sin() * (t * 7.3 + seed * 2.0).sin();
+        state.renderer.add_point_light(
+            transform.position + Vec3::Y * 0.8,
+            LAVA_LIGHT_COLOR,
+            hazard.radius * 2.5,
+            LAVA_LIGHT_INTENSITY * flicker,
+        );
+    }
+}
+
 /// Run all render passes. Called from `GameState::render()`.
 pub fn run(state: &mut GameState) -> Result<()> {
+        register_point_lights(state);
         let (output, mut encoder) = state.renderer.begin_frame()?;
         let scene_view = state.renderer.scene_view();
         let output_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        // ========== GALAXY MAP (own camera; replaces the scene while open) ==========
+        if state.galaxy_map_visible() {
+            galaxy_map::render(state, &mut encoder, &scene_view, &output_view);
+            state.renderer.end_frame(output, encoder);
+            return Ok(());
+        }
+
         // ========== MINIMAL MAIN MENU (skip all 3D: no celestial, fleet, Roger Young) ==========
         if state.phase == GamePhase::MainMenu {
             state.renderer.update_camera(&state.camera, 0.0);
@@ -49,7 +119,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 [0.0, -1.0, 0.0],         // sun down
                 0.0, 0.0, 0.0, 0.0, 0.0,  // no clouds/dust/planet, atmo_height=0 = space
                 [0.02, 0.025, 0.04],      // surface (unused in menu)
-                [0.02, 0.025, 0.04],      // atmosphere (dark space)
+                &AtmosphereParams::default(), // unused: atmo_height=0 draws space
                 false,                    // menu: no physical sun/moon
             );
             state.renderer.render_sky(
@@ -86,13 +156,29 @@ pub fn run(state: &mut GameState) -> Result<()> {
         const GORE_RENDER_DIST_SQ: f32 = 80.0 * 80.0;      // Max gore splatter distance
         const TRACK_RENDER_DIST_SQ: f32 = 100.0 * 100.0;    // Max ground track (footprint) distance
         const EFFECT_RENDER_DIST_SQ: f32 = 120.0 * 120.0;  // Max impact/tracer/flash distance
-        let mut bug_instances_by_type: HashMap<BugType, Vec<InstanceData>> = HashMap::new();
-        for bug_type in [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper] {
+        // Bug instances carry their squared camera distance for LOD selection
+        let mut bug_instances_by_type: HashMap<BugType, Vec<(f32, InstanceData)>> = HashMap::new();
+        for bug_type in [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper, BugType::Queen] {
             bug_instances_by_type.insert(bug_type, Vec::new());
         }
-        for (_, (transform, bug, health, physics_bug)) in
-            state.world.query::<(&Transform, &Bug, &Health, &PhysicsBug)>().iter()
+        // Glowing weakpoints (acid sacs) on live bugs
+        let mut weakpoint_glow: Vec<InstanceData> = Vec::new();
+        let weakpoint_pulse = 0.8 + 0.2 * (state.time.elapsed_seconds() * 4.0).sin();
+        // Physics-driven bugs are drawn between their last two fixed steps
+        let physics_alpha = state.time.interpolation_alpha();
+        for (_, (transform, bug, health, physics_bug, previous, queen)) in state
+            .world
+            .query::<(&Transform, &Bug, &Health, &PhysicsBug, Option<&PreviousTransform>, Option<&QueenBoss>)>()
+            .iter()
         {
+            let interpolated;
+            let transform = match previous {
+                Some(prev) => {
+                    interpolated = prev.interpolate(transform, physics_alpha);
+                    &interpolated
+                }
+                None => transform,
+            };
             if state.current_planet_idx.is_none() {
                 continue; // No bugs when not on planet (ship, menu, approach)
             }
@@ -119,6 +205,10 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 if health_factor < 0.3 {
                     color[0] += 0.3;
                 }
+                // Enraged Queen throbs red
+                if queen.is_some_and(|q| q.phase == QueenPhase::Enraged) {
+                    color[0] += 0.25 * weakpoint_pulse;
+                }
             }
 
             let (_death_offset, death_rotation, death_scale) = physics_bug.get_death_animation();
@@ -136,7 +226,17 @@ pub fn run(state: &mut GameState) -> Result<()> {
             };
 
             if let Some(instances) = bug_instances_by_type.get_mut(&bug.bug_type) {
-                instances.push(InstanceData::new(final_transform.to_cols_array_2d(), color));
+                instances.push((dist_sq, InstanceData::new(final_transform.to_cols_array_2d(), color)));
+            }
+            // A sealed Queen hides her weakpoint under the carapace
+            if !physics_bug.is_ragdoll && !queen.is_some_and(QueenBoss::is_armored) {
+                for weakpoint in bug.bug_type.weakpoints().iter().filter(|w| w.glows) {
+                    let Some(center) = weakpoint.region.mesh_center() else { continue };
+                    let m = final_transform
+                        * glam::Mat4::from_scale_rotation_translation(Vec3::splat(0.16), Quat::IDENTITY, center + Vec3::Y * 0.08);
+                    let glow = [0.7 * weakpoint_pulse, 1.0 * weakpoint_pulse, 0.2, 1.0];
+                    weakpoint_glow.push(InstanceData::new(m.to_cols_array_2d(), glow).with_emissive(GLOW_EMISSIVE));
+                }
             }
         }
 
@@ -280,6 +380,50 @@ pub fn run(state: &mut GameState) -> Result<()> {
             );
             tracer_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
         }
+        // Skinny bolts: same mesh, sickly green so enemy fire reads apart from the trooper's
+        for bolt in &state.skinny_bolts {
+            let dist_sq = bolt.position.distance_squared(cam_pos);
+            if !(1.0..=EFFECT_RENDER_DIST_SQ).contains(&dist_sq) {
+                continue;
+            }
+            let dir = bolt.velocity.normalize_or_zero();
+            let up = if dir.y.abs() < 0.99 { Vec3::Y } else { Vec3::Z };
+            let right = dir.cross(up).normalize_or_zero();
+            let rot3 = glam::Mat3::from_cols(right, right.cross(dir).normalize_or_zero(), dir);
+            let matrix = glam::Mat4::from_scale_rotation_translation(
+                Vec3::new(0.06, 0.06, 0.5),
+                glam::Quat::from_mat3(&rot3),
+                bolt.position,
+            );
+            tracer_instances.push(InstanceData::new(matrix.to_cols_array_2d(), [0.6, 1.8, 0.5, 1.0]));
+        }
+        // Acid globs: squat, bright green blobs
+        for glob in &state.acid_globs {
+            let dist_sq = glob.position.distance_squared(cam_pos);
+            if !(1.0..=EFFECT_RENDER_DIST_SQ).contains(&dist_sq) {
+                continue;
+            }
+            let rot = glam::Quat::from_rotation_arc(Vec3::Z, glob.velocity.normalize_or_zero());
+            let matrix = glam::Mat4::from_scale_rotation_translation(Vec3::new(0.25, 0.25, 0.4), rot, glob.position);
+            tracer_instances.push(InstanceData::new(matrix.to_cols_array_2d(), [0.7, 1.6, 0.2, 1.0]));
+        }
+        // Rockets in flight: same mesh, larger and glowing orange
+        for (_, (transform, velocity, _)) in state.world.query::<(&Transform, &Velocity, &Projectile)>().iter() {
+            let dist_sq = transform.position.distance_squared(cam_pos);
+            if !(1.0..=EFFECT_RENDER_DIST_SQ).contains(&dist_sq) {
+                continue;
+            }
+            let dir = velocity.linear.normalize_or_zero();
+            let up = if dir.y.abs() < 0.99 { Vec3::Y } else { Vec3::Z };
+            let right = dir.cross(up).normalize_or_zero();
+            let rot3 = glam::Mat3::from_cols(right, right.cross(dir).normalize_or_zero(), dir);
+            let matrix = glam::Mat4::from_scale_rotation_translation(
+                Vec3::new(0.12, 0.12, 0.6),
+                glam::Quat::from_mat3(&rot3),
+                transform.position,
+            );
+            tracer_instances.push(InstanceData::new(matrix.to_cols_array_2d(), [2.0, 0.8, 0.2, 1.0]));
+        }
 
         // Muzzle flash instances (star-shaped flash mesh)
         let mut flash_instances: Vec<InstanceData> = Vec::new();
@@ -305,8 +449,11 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // ========== RENDER PASSES ==========
 
+        // Water and other blended surfaces, sorted and drawn after all opaque geometry (before the viewmodel)
+        let mut transparent = TransparentQueue::new();
+
         // Sun direction from time of day (match sky shader)
-        let (sun_dir, cloud_density, dust, fog_density) = state.sky_weather_params();
+        let (sun_dir, cloud_density, dust) = state.sky_weather_params();
         let biome = state.planet.get_biome_config();
         // Biome atmosphere tints the tertiary/quaternary terrain colors for fog blending
         let fog_t = &state.biome_atmosphere.config.fog_tint;
@@ -337,17 +484,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
         };
         let atmo_height = planet_radius * 0.15;
 
-        // Altitude-scaled fog: push fog farther at high altitude so terrain stays visible
-        let altitude = cam_pos.y.max(0.0);
-        let alt_fog_mult = 1.0 + (altitude / 200.0).min(8.0);
-        // Biome atmosphere modifies fog density for cinematic immersion
+        // Biome atmosphere thickens dust in the sky for cinematic immersion
         let biome_fog_mult = state.biome_atmosphere.config.fog_density_mult;
-        let fog_params = [
-            fog_density * biome_fog_mult,
-            0.05,
-            (50.0 / biome_fog_mult.max(0.5)) * alt_fog_mult, // closer fog start for thick biomes
-            (400.0 / (biome_fog_mult * 0.5 + 0.5)) * alt_fog_mult, // shorter visibility for thick biomes
-        ];
 
         // Shadow pass: sun shadow map (only when on planet surface, before any scene passes that sample it)
         if state.current_planet_idx.is_some()
@@ -355,10 +493,10 @@ pub fn run(state: &mut GameState) -> Result<()> {
         {
             state.renderer.update_shadow_light(
                 [sun_dir.x, sun_dir.y, sun_dir.z],
-                [cam_pos.x, cam_pos.y, cam_pos.z],
+                &state.camera,
                 planet_radius,
             );
-            state.renderer.with_shadow_pass(&mut encoder, |r, pass| {
+            state.renderer.with_shadow_pass(&mut encoder, |r, pass, _cascade| {
                 state.chunk_manager.render_visible_shadow(r, pass, &state.camera);
             });
         }
@@ -374,7 +512,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
         let in_space_view = state.phase == GamePhase::MainMenu || extraction_orbit || approach_in_space || in_ship_interior;
         // Always update camera so sky + celestial use current view (critical when on planet for physical sun/moon)
         state.renderer.update_camera(
-            &state.camera,
+            &state.screen_shake.shaken(&state.camera),
             if in_space_view { 0.0 } else { state.planet_radius_for_curvature() },
         );
         // When in orbit: atmo_height=0 (no sky atmosphere), but pass cloud_density so the
@@ -391,13 +529,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
         };
         let biome_dust = dust + (biome_fog_mult - 1.0).max(0.0) * 0.08;
         // Sky reflects weather: tint atmosphere so Clear=normal, Cloudy/Rain/Storm/Snow = moody grey/blue
-        let tint = state.weather.atmosphere_tint();
-        let atmo = state.planet.atmosphere_color_rgb();
-        let atmosphere_color: [f32; 3] = [
-            atmo[0] * tint[0],
-            atmo[1] * tint[1],
-            atmo[2] * tint[2],
-        ];
+        let atmosphere = atmosphere_params(&state.planet, state.current_system.star.star_type)
+            .tinted(state.weather.atmosphere_tint());
+        state.renderer.set_star_seed(state.planet.seed);
         state.renderer.update_sky(
             state.time_of_day,
             [sun_dir.x, sun_dir.y, sun_dir.z],
@@ -407,9 +541,19 @@ pub fn run(state: &mut GameState) -> Result<()> {
             planet_radius,
             sky_atmo_height,
             planet_surface_color,
-            atmosphere_color,
+            &atmosphere,
             !in_space_view && state.current_planet_idx.is_some(), // physical sun/moon when on planet surface
         );
+        // Atmospheric fog: biome haze + weather, tinted by the sky horizon just computed (none in space)
+        let fog = if in_space_view || state.current_planet_idx.is_none() {
+            FogParams { density: 0.0, ..FogParams::default() }
+        } else if state.camera_underwater() {
+            FogParams::underwater()
+        } else {
+            let ground = state.chunk_manager.walkable_height(cam_pos.x, cam_pos.z);
+            state.biome_atmosphere.fog_params(&state.weather, sun_dir.y, ground)
+        };
+        state.renderer.set_fog(fog);
         state.renderer.render_sky(
             &mut encoder,
             &scene_view,
@@ -424,6 +568,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
         let celestial_instances = state.build_celestial_instances();
         state.renderer.render_celestial(&mut encoder, &scene_view, &celestial_instances);
 
+        // Passes 0b0-0c (fleets, ship interior) are instanced draws sharing one render pass
+        let mut pass = state.renderer.begin_instanced_pass(&mut encoder, &scene_view);
+
         // Pass 0b0: Federation fleet from planet surface — Corvette Transport (Rodger Young type)
         // Ref: https://starshiptroopers.fandom.com/wiki/Rodger_Young_Type — long hull, dorsal bridge, twin engine pods
         if (!in_space_view || extraction_orbit) && state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
@@ -485,7 +632,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         glow_pos,
                     );
-                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_corvette));
+                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_corvette).with_emissive(GLOW_EMISSIVE));
                 }
             }
             // Destroyers (Corvette Transport class, larger): long hull + command tower + twin engine pods
@@ -547,7 +694,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         glow_pos,
                     );
-                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_destroyer));
+                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_destroyer).with_emissive(GLOW_EMISSIVE));
                 }
             }
             // Earth orbit: extra Corvette Transports and dropships (bustling spaceport)
@@ -603,7 +750,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             Quat::IDENTITY,
                             pod_pos - facing * 0.8,
                         );
-                        fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_earth));
+                        fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_earth).with_emissive(GLOW_EMISSIVE));
                     }
                 }
                 for (i, &(phase_off, rad_off, y_off)) in [
@@ -657,7 +804,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             Quat::IDENTITY,
                             pod_pos - facing * 1.5,
                         );
-                        fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_destroyer));
+                        fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_destroyer).with_emissive(GLOW_EMISSIVE));
                     }
                 }
                 // Dropships (compact hull + engine — troop carriers)
@@ -693,14 +840,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         stern,
                     );
-                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), [0.22, 0.42, 0.72, 0.6]));
+                    fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), [0.22, 0.42, 0.72, 0.6]).with_emissive(GLOW_EMISSIVE));
                 }
             }
             if !fleet_hull.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.cube, &fleet_hull);
+                state.renderer.draw_instanced(&mut pass, &state.environment_meshes.cube, &fleet_hull);
             }
             if !fleet_glow.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &fleet_glow);
+                state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &fleet_glow);
             }
             // Pass 0b0a: Artillery muzzle flashes (ships firing — bright emissive, visible when looking up)
             if !state.artillery_muzzle_flashes.is_empty() {
@@ -719,7 +866,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     muzzle_instances.push(InstanceData::new(m.to_cols_array_2d(), color));
                 }
                 if !muzzle_instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &muzzle_instances);
+                    state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &muzzle_instances);
                 }
             }
         }
@@ -761,7 +908,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     Quat::IDENTITY,
                     stern,
                 );
-                fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_c));
+                fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_c).with_emissive(GLOW_EMISSIVE));
             }
             let hull_d = [0.12, 0.14, 0.19, 1.0];
             let engine_d = [0.28, 0.55, 0.92, 0.7];
@@ -786,13 +933,13 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     Quat::IDENTITY,
                     stern,
                 );
-                fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_d));
+                fleet_glow.push(InstanceData::new(g.to_cols_array_2d(), engine_d).with_emissive(GLOW_EMISSIVE));
             }
             if !fleet_hull.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.cube, &fleet_hull);
+                state.renderer.draw_instanced(&mut pass, &state.environment_meshes.cube, &fleet_hull);
             }
             if !fleet_glow.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &fleet_glow);
+                state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &fleet_glow);
             }
 
             // Pass 0b1b: Roger Young in main menu (Starship Troopers 2005 orbit background)
@@ -818,7 +965,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     match part.mesh_type {
                         0 => rock_instances.push(inst),
                         1 => sphere_instances.push(inst),
-                        _ => glow_instances.push(inst),
+                        _ => glow_instances.push(inst.with_emissive(GLOW_EMISSIVE)),
                     }
                 }
 
@@ -830,13 +977,13 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 }
 
                 if !rock_instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.rock, &rock_instances);
+                    state.renderer.draw_instanced(&mut pass, state.environment_meshes.rock.full(), &rock_instances);
                 }
                 if !sphere_instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &sphere_instances);
+                    state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &sphere_instances);
                 }
                 if !glow_instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &glow_instances);
+                    state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &glow_instances);
                 }
             }
         }
@@ -901,14 +1048,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         glow_pos,
                     );
-                    destroyer_glow.push(InstanceData::new(glow_mat.to_cols_array_2d(), engine_d));
+                    destroyer_glow.push(InstanceData::new(glow_mat.to_cols_array_2d(), engine_d).with_emissive(GLOW_EMISSIVE));
                 }
             }
             if !destroyer_hull.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.cube, &destroyer_hull);
+                state.renderer.draw_instanced(&mut pass, &state.environment_meshes.cube, &destroyer_hull);
             }
             if !destroyer_glow.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &destroyer_glow);
+                state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &destroyer_glow);
             }
 
             // Pass 0b3: Federation corvettes (Corvette Transport type) — hull + bridge + twin engine pods
@@ -967,14 +1114,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         glow_pos,
                     );
-                    corvette_glow.push(InstanceData::new(glow_mat.to_cols_array_2d(), corvette_engine_c));
+                    corvette_glow.push(InstanceData::new(glow_mat.to_cols_array_2d(), corvette_engine_c).with_emissive(GLOW_EMISSIVE));
                 }
             }
             if !corvette_hull.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.cube, &corvette_hull);
+                state.renderer.draw_instanced(&mut pass, &state.environment_meshes.cube, &corvette_hull);
             }
             if !corvette_glow.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &corvette_glow);
+                state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &corvette_glow);
             }
         }
 
@@ -985,6 +1132,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
             let war_table_active = state.ship_state.as_ref().map_or(false, |s| s.war_table_active);
 
             let mut rock_instances: Vec<InstanceData> = Vec::new();
+            // Plated metal: hull steel and armor, drawn with the plate normal map
+            let mut plated_instances: Vec<InstanceData> = Vec::new();
             let mut sphere_instances: Vec<InstanceData> = Vec::new();
             let mut glow_instances: Vec<InstanceData> = Vec::new();
 
@@ -1003,9 +1152,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 let inst = InstanceData::new(matrix.to_cols_array_2d(), color);
 
                 match part.mesh_type {
-                    0 => rock_instances.push(inst),
-                    1 => sphere_instances.push(inst),
-                    2 => glow_instances.push(inst),
+                    0 => plated_instances.push(inst.with_material(HULL_STEEL.0, HULL_STEEL.1)),
+                    1 => sphere_instances.push(inst.with_material(HULL_STEEL.0, HULL_STEEL.1)),
+                    2 => glow_instances.push(inst.with_emissive(GLOW_EMISSIVE)),
                     _ => {}
                 }
             }
@@ -1019,7 +1168,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Vec3::splat(0.4 + alert_pulse * 0.2), Quat::IDENTITY,
                         Vec3::new(x_sign * 9.5, 3.8, z_pos),
                     );
-                    glow_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
+                    glow_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color).with_emissive(GLOW_EMISSIVE));
                 }
             }
 
@@ -1054,7 +1203,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             Quat::from_rotation_y(timer * 2.0),
                             Vec3::new(px, py, pz),
                         );
-                        glow_instances.push(InstanceData::new(ring_matrix.to_cols_array_2d(), [0.3, 0.6, 1.0, ring_pulse]));
+                        glow_instances.push(InstanceData::new(ring_matrix.to_cols_array_2d(), [0.3, 0.6, 1.0, ring_pulse]).with_emissive(GLOW_EMISSIVE));
                     }
                 }
             }
@@ -1070,7 +1219,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     Quat::IDENTITY,
                     Vec3::new(-9.4, 3.85, ucf_pole_z - 1.5),
                 ).to_cols_array_2d(), pole_color,
-            ));
+            ).with_material(BRASS.0, BRASS.1));
             // Pole cap (ornamental sphere)
             sphere_instances.push(InstanceData::new(
                 glam::Mat4::from_scale_rotation_translation(
@@ -1078,7 +1227,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     Quat::IDENTITY,
                     Vec3::new(-9.4, 3.85, ucf_pole_z + 0.1),
                 ).to_cols_array_2d(), pole_cap,
-            ));
+            ).with_material(BRASS.0, BRASS.1));
             // MI flag pole (starboard wall)
             rock_instances.push(InstanceData::new(
                 glam::Mat4::from_scale_rotation_translation(
@@ -1086,14 +1235,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     Quat::IDENTITY,
                     Vec3::new(9.4, 3.85, ucf_pole_z - 1.5),
                 ).to_cols_array_2d(), pole_color,
-            ));
+            ).with_material(BRASS.0, BRASS.1));
             sphere_instances.push(InstanceData::new(
                 glam::Mat4::from_scale_rotation_translation(
                     Vec3::splat(0.12),
                     Quat::IDENTITY,
                     Vec3::new(9.4, 3.85, ucf_pole_z + 0.1),
                 ).to_cols_array_2d(), pole_cap,
-            ));
+            ).with_material(BRASS.0, BRASS.1));
 
             // ── Cloth flags (physics-simulated) ──
             if let Some(ref ship) = state.ship_state {
@@ -1109,6 +1258,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
             for npc in &npcs {
                 let rot = Quat::from_rotation_y(npc.facing_yaw_rad);
                 let [tr, tg, tb] = npc.color_tint;
+                let armor = npc_armor(npc.kind);
                 for part in interior_npc_parts(npc.kind) {
                     let world_pos = npc.position + rot * part.local_offset;
                     let matrix = glam::Mat4::from_scale_rotation_translation(
@@ -1123,26 +1273,29 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         part.color[3],
                     ];
                     let inst = InstanceData::new(matrix.to_cols_array_2d(), color);
-                    match part.mesh_type {
-                        0 => rock_instances.push(inst),
-                        1 => sphere_instances.push(inst),
-                        2 => glow_instances.push(inst),
+                    match (part.mesh_type, armor) {
+                        (0, Some((roughness, metallic))) => plated_instances.push(inst.with_material(roughness, metallic)),
+                        (0, None) => rock_instances.push(inst),
+                        (1, Some((roughness, metallic))) => sphere_instances.push(inst.with_material(roughness, metallic)),
+                        (1, None) => sphere_instances.push(inst),
+                        (2, _) => glow_instances.push(inst.with_emissive(GLOW_EMISSIVE)),
                         _ => {}
                     }
                 }
             }
 
-            // Render ship interior
-            if !rock_instances.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.rock, &rock_instances);
-            }
-            if !sphere_instances.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &sphere_instances);
-            }
-            if !glow_instances.is_empty() {
-                state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &glow_instances);
-            }
+            // Render ship interior: one instanced draw per mesh
+            state.renderer.draw_instanced(&mut pass, state.environment_meshes.rock.full(), &rock_instances);
+            state.renderer.draw_instanced_with_material(
+                &mut pass,
+                state.environment_meshes.rock.full(),
+                &plated_instances,
+                &state.plate_material,
+            );
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &sphere_instances);
+            state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &glow_instances);
         }
+        drop(pass);
 
         // Pass 1: Terrain (only when on planet surface — never in ship or menu)
         if state.current_planet_idx.is_some()
@@ -1155,34 +1308,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 BiomeType::Desert | BiomeType::Frozen | BiomeType::Wasteland | BiomeType::Badlands
                     | BiomeType::Tundra | BiomeType::SaltFlat
             );
-            let snow_weather = state.weather.current == WeatherState::Snow;
-            // Voxel terrain: disable deform/snow texture pass to avoid full-screen artifact (deform is for heightfield footprints).
+            // Voxel terrain: disable deform texture pass to avoid full-screen artifact (deform is for heightfield footprints).
             let deform_enabled = false; // was: tracks_biome || snow_weather;
-            const SNOW_ACCUM_RATE: f32 = 0.08;   // m/s when snowing (knee-deep ~0.45 m in a few seconds)
-            const SNOW_MELT_RATE: f32 = 0.04;   // m/s when clear/rain
-            const SNOW_MAX_DEPTH: f32 = 0.45;   // knee-deep
             let (deform_origin_x, deform_origin_z) = if deform_enabled {
                 let ox = cam_pos.x;
                 let oz = cam_pos.z;
                 let world_size = 2.0 * DEFORM_HALF_SIZE;
                 let texels_per_unit = (DEFORM_TEXTURE_SIZE as f32) / world_size;
 
-                // Snow accumulation: same 128m tile as deform; reset buffer when camera moves
-                let prev = state.snow_accumulation_origin;
-                if (ox - prev.0).abs() > 2.0 || (oz - prev.1).abs() > 2.0 {
-                    state.snow_accumulation_buffer.fill(0.0);
-                    state.snow_accumulation_origin = (ox, oz);
-                }
-                let dt = state.smoothed_dt;
-                for idx in 0..state.snow_accumulation_buffer.len() {
-                    let s = &mut state.snow_accumulation_buffer[idx];
-                    if snow_weather {
-                        *s = (*s + SNOW_ACCUM_RATE * dt).min(SNOW_MAX_DEPTH);
-                    } else {
-                        *s = (*s - SNOW_MELT_RATE * dt).max(0.0);
-                    }
-                }
-
                 // Stamp deformation heightfield from ground tracks (Helldivers 2 / Dune style)
                 state.deformation_buffer.fill(0.0);
                 for track in &state.effects.ground_tracks {
@@ -1216,26 +1349,35 @@ pub fn run(state: &mut GameState) -> Result<()> {
                                 let idx = i as usize + j as usize * (DEFORM_TEXTURE_SIZE as usize);
                                 let new_val = state.deformation_buffer[idx] + depth * falloff;
                                 state.deformation_buffer[idx] = new_val.min(0.18);
-                                // Stamp snow away in footprint so track is a depression in snow
-                                let snow_remove = depth * falloff * 1.5; // remove slightly more snow than deform depth
-                                state.snow_accumulation_buffer[idx] =
-                                    (state.snow_accumulation_buffer[idx] - snow_remove).max(0.0);
                             }
                         }
                     }
                 }
                 state.renderer.upload_terrain_deformation(&state.deformation_buffer);
-                state.renderer.upload_terrain_snow(&state.snow_accumulation_buffer);
                 (ox, oz)
             } else {
                 (0.0, 0.0)
             };
-            let snow_enabled = deform_enabled;
+            // Snow cover is simulated in update (SnowField); it shares the deform tile origin.
+            let snow_enabled = state.snow.is_active();
+            let (deform_origin_x, deform_origin_z) = if snow_enabled {
+                state.renderer.upload_terrain_snow(state.snow.depth_buffer());
+                state.snow.origin()
+            } else {
+                (deform_origin_x, deform_origin_z)
+            };
+
+            // Blood decals: restamp the stain map around the camera (same 128m tile as deform)
+            if state.effects.blood_decals.is_empty() {
+                state.renderer.upload_terrain_blood(None, 0.0, 0.0);
+            } else {
+                state.effects.stamp_blood_decals(&mut state.blood_decal_buffer, cam_pos.x, cam_pos.z);
+                state.renderer.upload_terrain_blood(Some(&state.blood_decal_buffer), cam_pos.x, cam_pos.z);
+            }
             let terrain_detail_scale = if state.planet.name == "Earth" { 2.7 } else { 2.0 };
             state.renderer.update_terrain(
                 state.time.elapsed_seconds(),
                 [sun_dir.x, sun_dir.y, sun_dir.z, terrain_sun_intensity],
-                fog_params,
                 biome_colors,
                 planet_radius,
                 state.chunk_manager.chunk_size,
@@ -1250,14 +1392,19 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 &mut encoder,
                 &scene_view,
                 &state.camera,
+                planet_radius,
+                &mut transparent,
             );
         }
 
+        // Passes 0c-5i (everything opaque on the planet) are instanced draws sharing one render pass
+        let mut pass = state.renderer.begin_instanced_pass(&mut encoder, &scene_view);
+
         // Pass 0c: Earth roads and walking paths (city layout — only when on Earth)
         if let Some(ref roads_mesh) = state.earth_roads_mesh {
             let asphalt = [0.20, 0.20, 0.22, 1.0];
             let road_instance = InstanceData::new(glam::Mat4::IDENTITY.to_cols_array_2d(), asphalt);
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, roads_mesh, &[road_instance]);
+            state.renderer.draw_instanced(&mut pass, roads_mesh, &[road_instance]);
         }
 
         // Pass 1a0: Squad drop pods descending from orbit — pointed nose, body, heatshield base
@@ -1303,16 +1450,16 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         glow_pos,
                     );
-                    pod_glow.push(InstanceData::new(gm.to_cols_array_2d(), [0.9, 0.55, 0.2, 0.85]));
+                    pod_glow.push(InstanceData::new(gm.to_cols_array_2d(), [0.9, 0.55, 0.2, 0.85]).with_emissive(GLOW_EMISSIVE));
                 }
                 if !pod_rock.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.rock, &pod_rock);
+                    state.renderer.draw_instanced(&mut pass, state.environment_meshes.rock.full(), &pod_rock);
                 }
                 if !pod_sphere.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &pod_sphere);
+                    state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &pod_sphere);
                 }
                 if !pod_glow.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.flash_mesh, &pod_glow);
+                    state.renderer.draw_instanced(&mut pass, &state.flash_mesh, &pod_glow);
                 }
             }
         }
@@ -1380,9 +1527,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     }
 
                     if !hull_instances.is_empty() {
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
-                            &state.environment_meshes.rock,
+                        state.renderer.draw_instanced(
+                            &mut pass,
+                            state.environment_meshes.rock.full(),
                             &hull_instances,
                         );
                     }
@@ -1420,8 +1567,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     sphere_pos,
                 );
                 let inst = vec![InstanceData::new(m.to_cols_array_2d(), planet_color)];
-                state.renderer.render_instanced_load(
-                    &mut encoder, &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.prop_sphere,
                     &inst,
                 );
@@ -1438,8 +1585,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let atmo = state.planet.atmosphere_color_rgb();
                     let halo_color = [atmo[0] * glow, atmo[1] * glow, atmo[2] * glow, glow * 0.5];
                     let halo_inst = vec![InstanceData::new(halo_m.to_cols_array_2d(), halo_color)];
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.flash_mesh,
                         &halo_inst,
                     );
@@ -1456,9 +1603,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 pod_bottom + Vec3::Y * 0.6,
             );
             let pod_rock = vec![InstanceData::new(body_m.to_cols_array_2d(), pod_hull_color)];
-            state.renderer.render_instanced_load(
-                &mut encoder, &scene_view,
-                &state.environment_meshes.rock,
+            state.renderer.draw_instanced(
+                &mut pass,
+                state.environment_meshes.rock.full(),
                 &pod_rock,
             );
             let shield_m = glam::Mat4::from_scale_rotation_translation(
@@ -1467,9 +1614,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 pod_bottom - Vec3::Y * 0.4,
             );
             let shield_inst = vec![InstanceData::new(shield_m.to_cols_array_2d(), heatshield_color)];
-            state.renderer.render_instanced_load(
-                &mut encoder, &scene_view,
-                &state.environment_meshes.rock,
+            state.renderer.draw_instanced(
+                &mut pass,
+                state.environment_meshes.rock.full(),
                 &shield_inst,
             );
             let nose_m = glam::Mat4::from_scale_rotation_translation(
@@ -1478,8 +1625,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 pod_cam + Vec3::Y * 0.8,
             );
             let nose_inst = vec![InstanceData::new(nose_m.to_cols_array_2d(), pod_hull_color)];
-            state.renderer.render_instanced_load(
-                &mut encoder, &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.environment_meshes.prop_sphere,
                 &nose_inst,
             );
@@ -1496,8 +1643,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 );
                 let heat_color = [3.0 * glow, 1.5 * glow, 0.3 * glow, glow * 0.8];
                 let inst = vec![InstanceData::new(m.to_cols_array_2d(), heat_color)];
-                state.renderer.render_instanced_load(
-                    &mut encoder, &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.flash_mesh,
                     &inst,
                 );
@@ -1520,8 +1667,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     );
                     let trail_color = [2.0 * trail_alpha, 0.8 * trail_alpha, 0.15, trail_alpha * 0.6];
                     let trail_inst = vec![InstanceData::new(tm.to_cols_array_2d(), trail_color)];
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.flash_mesh,
                         &trail_inst,
                     );
@@ -1539,8 +1686,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 );
                 let retro_color = [0.5 * pulse, 0.8 * pulse, 3.0 * pulse, 0.7];
                 let inst = vec![InstanceData::new(m.to_cols_array_2d(), retro_color)];
-                state.renderer.render_instanced_load(
-                    &mut encoder, &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.flash_mesh,
                     &inst,
                 );
@@ -1549,6 +1696,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // Pass 1b-1l: All static environment entities via cached render data (only on planet).
         let mut env_instances: [Vec<InstanceData>; ENV_MESH_GROUP_COUNT] = Default::default();
+        // Rocks and rock landmarks share the 3 rock meshes (variant by position hash) and use LOD
+        let mut rock_by_variant: [Vec<(f32, InstanceData)>; 3] = Default::default();
         if state.current_planet_idx.is_some() {
         for (entity, (cached,)) in state.world.query::<(&CachedRenderData,)>().iter() {
             if let Ok(d) = state.world.get::<&Destructible>(entity) {
@@ -1562,77 +1711,58 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 continue;
             }
             let group = cached.mesh_group as usize;
-            if group < ENV_MESH_GROUP_COUNT {
-                env_instances[group].push(InstanceData::new(cached.matrix, cached.color));
+            let instance = InstanceData::new(cached.matrix, cached.color);
+            if group == MESH_GROUP_ROCK as usize || group == MESH_GROUP_LANDMARK as usize {
+                let h = ((cached.matrix[3][0].to_bits().wrapping_add(cached.matrix[3][2].to_bits())) % 3) as usize;
+                rock_by_variant[h].push((dist_sq, instance));
+            } else if group < ENV_MESH_GROUP_COUNT {
+                env_instances[group].push(instance);
             }
         }
-        // Mesh group 0: rock (3 variants by position hash)
+        // Mesh groups 0 + 6: rocks and landmarks (rock variants)
         {
             let rocks = [
                 &state.environment_meshes.rock,
                 &state.environment_meshes.rock_chunk,
                 &state.environment_meshes.rock_boulder,
             ];
-            let mut rock_by_variant: [Vec<InstanceData>; 3] = Default::default();
-            for inst in &env_instances[MESH_GROUP_ROCK as usize] {
-                let h = ((inst.model[3][0].to_bits().wrapping_add(inst.model[3][2].to_bits())) % 3) as usize;
-                rock_by_variant[h].push(*inst);
-            }
             for (variant, instances) in rock_by_variant.iter().enumerate() {
                 if !instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, rocks[variant], instances);
+                    state.renderer.draw_instanced_lod(&mut pass, rocks[variant], instances);
                 }
             }
         }
         // Mesh group 1: bug_hole (holes, hazard pools, burn craters)
         if !env_instances[MESH_GROUP_BUG_HOLE as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.bug_hole, &env_instances[MESH_GROUP_BUG_HOLE as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.bug_hole, &env_instances[MESH_GROUP_BUG_HOLE as usize]);
         }
         // Mesh group 2: hive_mound (hive structures, spore towers)
         if !env_instances[MESH_GROUP_HIVE_MOUND as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.hive_mound, &env_instances[MESH_GROUP_HIVE_MOUND as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.hive_mound, &env_instances[MESH_GROUP_HIVE_MOUND as usize]);
         }
         // Mesh group 3: egg_cluster (egg clusters, bone piles)
         if !env_instances[MESH_GROUP_EGG_CLUSTER as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.egg_cluster, &env_instances[MESH_GROUP_EGG_CLUSTER as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.egg_cluster, &env_instances[MESH_GROUP_EGG_CLUSTER as usize]);
         }
         // Mesh group 4: prop_sphere (environment props)
         if !env_instances[MESH_GROUP_PROP_SPHERE as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &env_instances[MESH_GROUP_PROP_SPHERE as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &env_instances[MESH_GROUP_PROP_SPHERE as usize]);
         }
         // Mesh group 5: cube (abandoned outposts)
         if !env_instances[MESH_GROUP_CUBE as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.cube, &env_instances[MESH_GROUP_CUBE as usize]);
-        }
-        // Mesh group 6: landmark (biome-unique landmarks; rock variants)
-        {
-            let rocks = [
-                &state.environment_meshes.rock,
-                &state.environment_meshes.rock_chunk,
-                &state.environment_meshes.rock_boulder,
-            ];
-            let mut landmark_by_variant: [Vec<InstanceData>; 3] = Default::default();
-            for inst in &env_instances[MESH_GROUP_LANDMARK as usize] {
-                let h = ((inst.model[3][0].to_bits().wrapping_add(inst.model[3][2].to_bits())) % 3) as usize;
-                landmark_by_variant[h].push(*inst);
-            }
-            for (variant, instances) in landmark_by_variant.iter().enumerate() {
-                if !instances.is_empty() {
-                    state.renderer.render_instanced_load(&mut encoder, &scene_view, rocks[variant], instances);
-                }
-            }
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.cube, &env_instances[MESH_GROUP_CUBE as usize]);
         }
         // Mesh group 7: hazard (environmental hazards; use prop_sphere for disc/zone look)
         if !env_instances[MESH_GROUP_HAZARD as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &env_instances[MESH_GROUP_HAZARD as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &env_instances[MESH_GROUP_HAZARD as usize]);
         }
         // Mesh group 8: beveled cube (UCF Earth buildings — industrial chamfered look)
         if !env_instances[MESH_GROUP_BEVELED_CUBE as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.beveled_cube, &env_instances[MESH_GROUP_BEVELED_CUBE as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.beveled_cube, &env_instances[MESH_GROUP_BEVELED_CUBE as usize]);
         }
         // Mesh group 9: hive cave / tunnel entrance (HiveWorld surface holes)
         if !env_instances[MESH_GROUP_HIVE_CAVE_ENTRANCE as usize].is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.hive_cave_entrance, &env_instances[MESH_GROUP_HIVE_CAVE_ENTRANCE as usize]);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.hive_cave_entrance, &env_instances[MESH_GROUP_HIVE_CAVE_ENTRANCE as usize]);
         }
         }
 
@@ -1644,14 +1774,20 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 if lifetime.remaining <= 0.0 { continue; }
                 let dist_sq = transform.position.distance_squared(cam_pos);
                 if dist_sq < VIEWMODEL_CULL_SQ || dist_sq > ENTITY_RENDER_DIST_SQ { continue; }
-                let t = transform.to_matrix();
+                // Settled chunks shrink into the ground before they despawn
+                let fade = Debris::fade(lifetime);
+                let sink = transform.scale.x * 0.5 * (1.0 - fade);
+                let t = glam::Mat4::from_scale_rotation_translation(
+                    transform.scale * fade,
+                    transform.rotation,
+                    transform.position - Vec3::Y * sink,
+                );
                 debris_instances.push(InstanceData::new(t.to_cols_array_2d(), debris_color));
             }
             if !debris_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
-                    &state.environment_meshes.rock,
+                state.renderer.draw_instanced(
+                    &mut pass,
+                    state.environment_meshes.rock.full(),
                     &debris_instances,
                 );
             }
@@ -1671,9 +1807,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 gore_chunk_instances.push(InstanceData::new(t.to_cols_array_2d(), color));
             }
             if !gore_chunk_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.prop_sphere,
                     &gore_chunk_instances,
                 );
@@ -1683,10 +1818,16 @@ pub fn run(state: &mut GameState) -> Result<()> {
         // Pass 1m: Bug corpses (decaying dead bugs)
         {
             let mut corpse_instances_by_type: HashMap<u8, Vec<InstanceData>> = HashMap::new();
-            for (_, (transform, corpse)) in state.world.query::<(&Transform, &BugCorpse)>().iter() {
+            for (_, (transform, corpse, fade)) in state.world.query::<(&Transform, &BugCorpse, Option<&Lifetime>)>().iter() {
                 let dist_sq = transform.position.distance_squared(cam_pos);
                 if dist_sq > VIEWMODEL_CULL_SQ && dist_sq < ENTITY_RENDER_DIST_SQ {
-                    let (color, scale_mult, sink, _) = corpse.decay_state();
+                    let (mut color, scale_mult, mut sink, _) = corpse.decay_state();
+                    // Evicted by the corpse budget: fade and sink out before despawn
+                    if let Some(fade) = fade {
+                        let t = 1.0 - (fade.remaining / CORPSE_FADE_TIME).clamp(0.0, 1.0);
+                        color[3] *= 1.0 - t;
+                        sink += t * corpse.original_scale.y;
+                    }
                     let mut pos = transform.position;
                     pos.y -= sink; // sink into ground
                     let mat = glam::Mat4::from_scale_rotation_translation(
@@ -1707,32 +1848,34 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     2 => BugType::Spitter,
                     3 => BugType::Tanker,
                     4 => BugType::Hopper,
+                    5 => BugType::Queen,
                     _ => BugType::Warrior,
                 };
-                let mesh = state.bug_meshes.get(bug_type);
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                let mesh = state.bug_meshes.get(bug_type).full();
+                state.renderer.draw_instanced(
+                    &mut pass,
                     mesh,
                     instances,
                 );
             }
         }
 
-        // Pass 2: Bugs (each type with its correct mesh)
-        for bug_type in [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper] {
+        // Pass 2: Bugs (each type with its correct mesh, LOD by distance)
+        for bug_type in [BugType::Warrior, BugType::Charger, BugType::Spitter, BugType::Tanker, BugType::Hopper, BugType::Queen] {
             let instances = &bug_instances_by_type[&bug_type];
             if instances.is_empty() {
                 continue;
             }
             let mesh = state.bug_meshes.get(bug_type);
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced_lod(
+                &mut pass,
                 mesh,
                 instances,
             );
         }
+        if !weakpoint_glow.is_empty() {
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &weakpoint_glow);
+        }
 
         // Pass 2a: Skinnies (Heinlein humanoid enemies — tall thin shape, grey-green)
         let mut skinny_instances: Vec<InstanceData> = Vec::new();
@@ -1767,14 +1910,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
             skinny_instances.push(InstanceData::new(final_transform.to_cols_array_2d(), color));
         }
         if !skinny_instances.is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.skinny_mesh, &skinny_instances);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.skinny_mesh, &skinny_instances);
         }
 
         // Pass 2b: Squad mates (deployed with player — simple head + torso)
         let mut squad_rock: Vec<InstanceData> = Vec::new();
         let mut squad_sphere: Vec<InstanceData> = Vec::new();
-        for (_, (transform, squad, health)) in state.world.query::<(&Transform, &SquadMate, &Health)>().iter() {
-            if health.current <= 0.0 {
+        for (_, (transform, squad)) in state.world.query::<(&Transform, &SquadMate)>().iter() {
+            if squad.status == SquadMateStatus::Dead {
                 continue;
             }
             let dist_sq = transform.position.distance_squared(cam_pos);
@@ -1787,26 +1930,37 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 SquadMateKind::Marauder => ([0.18, 0.17, 0.16, 1.0], [0.15, 0.14, 0.13, 1.0]),
                 SquadMateKind::Tech => ([0.35, 0.42, 0.48, 1.0], [0.12, 0.20, 0.28, 1.0]), // Tech blue-gray
             };
-            let head_pos = transform.position + transform.rotation * Vec3::new(0.0, 1.5, 0.0);
-            let torso_pos = transform.position + transform.rotation * Vec3::new(0.0, 0.9, 0.0);
+            let (roughness, metallic) = match squad.kind {
+                SquadMateKind::Marauder => MARAUDER_SUIT,
+                SquadMateKind::MobileInfantry => TROOPER_ARMOR,
+                SquadMateKind::Fleet | SquadMateKind::Tech => (DEFAULT_ROUGHNESS, 0.0),
+            };
+            // Downed troopers lie on their back, laid out along their facing
+            let (base, rotation) = if squad.is_downed() {
+                (transform.position - Vec3::Y * 0.35, transform.rotation * glam::Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2))
+            } else {
+                (transform.position, transform.rotation)
+            };
+            let head_pos = base + rotation * Vec3::new(0.0, 1.5, 0.0);
+            let torso_pos = base + rotation * Vec3::new(0.0, 0.9, 0.0);
             let head_m = glam::Mat4::from_scale_rotation_translation(
                 Vec3::splat(0.22),
-                transform.rotation,
+                rotation,
                 head_pos,
             );
             let torso_m = glam::Mat4::from_scale_rotation_translation(
                 Vec3::new(0.28, 0.4, 0.14),
-                transform.rotation,
+                rotation,
                 torso_pos,
             );
-            squad_sphere.push(InstanceData::new(head_m.to_cols_array_2d(), head_color));
-            squad_rock.push(InstanceData::new(torso_m.to_cols_array_2d(), torso_color));
+            squad_sphere.push(InstanceData::new(head_m.to_cols_array_2d(), head_color).with_material(roughness, metallic));
+            squad_rock.push(InstanceData::new(torso_m.to_cols_array_2d(), torso_color).with_material(roughness, metallic));
         }
         if !squad_rock.is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.rock, &squad_rock);
+            state.renderer.draw_instanced(&mut pass, state.environment_meshes.rock.full(), &squad_rock);
         }
         if !squad_sphere.is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &squad_sphere);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &squad_sphere);
         }
 
         // Pass 2c: Earth settlement citizens (Starship Troopers aesthetic — civilian head + torso)
@@ -1820,8 +1974,14 @@ pub fn run(state: &mut GameState) -> Result<()> {
             // Civilian colors: tan, gray, brown (Federation colony)
             let head_color = [0.52, 0.42, 0.35, 1.0];
             let torso_color = [0.38, 0.35, 0.32, 1.0];
-            let head_pos = transform.position + transform.rotation * Vec3::new(0.0, 1.4, 0.0);
-            let torso_pos = transform.position + transform.rotation * Vec3::new(0.0, 0.85, 0.0);
+            // Cowering: crouched low, head tucked forward
+            let (head_offset, torso_offset) = if citizen.state == CitizenState::Cower {
+                (Vec3::new(0.0, 0.75, -0.2), Vec3::new(0.0, 0.45, -0.08))
+            } else {
+                (Vec3::new(0.0, 1.4, 0.0), Vec3::new(0.0, 0.85, 0.0))
+            };
+            let head_pos = transform.position + transform.rotation * head_offset;
+            let torso_pos = transform.position + transform.rotation * torso_offset;
             let head_m = glam::Mat4::from_scale_rotation_translation(
                 Vec3::splat(0.2),
                 transform.rotation,
@@ -1836,17 +1996,16 @@ pub fn run(state: &mut GameState) -> Result<()> {
             citizen_rock.push(InstanceData::new(torso_m.to_cols_array_2d(), torso_color));
         }
         if !citizen_rock.is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.rock, &citizen_rock);
+            state.renderer.draw_instanced(&mut pass, state.environment_meshes.rock.full(), &citizen_rock);
         }
         if !citizen_sphere.is_empty() {
-            state.renderer.render_instanced_load(&mut encoder, &scene_view, &state.environment_meshes.prop_sphere, &citizen_sphere);
+            state.renderer.draw_instanced(&mut pass, &state.environment_meshes.prop_sphere, &citizen_sphere);
         }
 
         // Pass 3: Gore splatters
         if !gore_instances.is_empty() {
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.gore_mesh,
                 &gore_instances,
             );
@@ -1854,9 +2013,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // Pass 3b: Ground tracks (footprints in snow/sand)
         if !track_instances.is_empty() {
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.gore_mesh,
                 &track_instances,
             );
@@ -1864,9 +2022,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // Pass 4: Bullet impacts
         if !impact_instances.is_empty() {
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.particle_mesh,
                 &impact_instances,
             );
@@ -1874,9 +2031,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // Pass 4b: Tracer projectiles (proper bullet-shaped diamond mesh)
         if !tracer_instances.is_empty() {
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.tracer_mesh,
                 &tracer_instances,
             );
@@ -1884,108 +2040,26 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
         // Pass 5: Muzzle flashes (multi-pointed star mesh)
         if !flash_instances.is_empty() {
-            state.renderer.render_instanced_load(
-                &mut encoder,
-                &scene_view,
+            state.renderer.draw_instanced(
+                &mut pass,
                 &state.flash_mesh,
                 &flash_instances,
             );
         }
 
-        // Minecraft-style 2D billboard particles: quad always faces camera (vertical billboard in XZ)
-        let billboard_matrix = |pos: Vec3, scale_x: f32, scale_z: f32| {
-            let dx = cam_pos.x - pos.x;
-            let dz = cam_pos.z - pos.z;
-            let len_sq = dx * dx + dz * dz;
-            let (dir_x, dir_z) = if len_sq > 1e-8 {
-                let inv = 1.0 / len_sq.sqrt();
-                (dx * inv, dz * inv)
-            } else {
-                (1.0, 0.0)
-            };
-            let right = Vec3::new(-dir_z, 0.0, dir_x);
-            let normal = Vec3::new(dir_x, 0.0, dir_z);
-            let up = Vec3::Y;
-            let rot = glam::Mat3::from_cols(right, normal, up);
-            glam::Mat4::from_scale_rotation_translation(
-                Vec3::new(scale_x, 1.0, scale_z),
-                Quat::from_mat3(&rot),
-                pos,
-            )
-        };
-
-        // Pass 5b: Rain (2D billboard quads — thin vertical streak)
-        if !state.rain_drops.is_empty() && state.player.is_alive {
-            let mut rain_instances: Vec<InstanceData> = Vec::new();
-            for r in &state.rain_drops {
-                let alpha = (r.life / 1.0).min(1.0);
-                if alpha < 0.2 { continue; }
-                let matrix = billboard_matrix(r.position, 0.02, 0.12);
-                rain_instances.push(InstanceData::new(
-                    matrix.to_cols_array_2d(),
-                    [0.65, 0.75, 0.95, alpha],
-                ));
-            }
-            if !rain_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
-                    &state.environment_meshes.billboard_quad,
-                    &rain_instances,
-                );
-            }
-        }
-
-        // Pass 5b2: Snow (2D billboard quads — soft flakes)
-        if !state.snow_particles.is_empty() && state.player.is_alive {
-            let mut snow_instances: Vec<InstanceData> = Vec::new();
-            for s in &state.snow_particles {
-                let alpha = (s.life / 2.0).min(1.0) * 0.9;
-                if alpha < 0.15 { continue; }
-                let matrix = billboard_matrix(s.position, s.size, s.size);
-                snow_instances.push(InstanceData::new(
-                    matrix.to_cols_array_2d(),
-                    [0.95, 0.97, 1.0, alpha],
-                ));
-            }
-            if !snow_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
-                    &state.environment_meshes.billboard_quad,
-                    &snow_instances,
-                );
-            }
-        }
-
-        // Pass 5c: Ambient dust (2D billboard quads)
-        if !state.ambient_dust.particles.is_empty() && state.phase == GamePhase::Playing {
-            let mut dust_instances: Vec<InstanceData> = Vec::new();
-            for p in &state.ambient_dust.particles {
-                let life_frac = (p.life / 4.0).min(1.0);
-                let alpha = life_frac * 0.8;
-                if alpha < 0.2 { continue; }
-                let shimmer = 1.0 + (p.life * 3.0).sin() * 0.15;
-                let size = p.size * shimmer;
-                let tint = (p.position.x * 7.3 + p.position.z * 11.1).sin() * 0.05;
-                let color = [0.82 + tint, 0.76 + tint, 0.65, alpha];
-                let matrix = billboard_matrix(p.position, size, size);
-                dust_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
-            }
-            if !dust_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
-                    &state.environment_meshes.billboard_quad,
-                    &dust_instances,
-                );
-            }
-        }
+        // Pass 5b: Rain, snow and ambient dust (pooled particles). These and the smoke/fire
+        // billboards below are all drawn in one instanced particle pass after the transparents.
+        let alive = state.player.is_alive;
+        let playing = state.phase == GamePhase::Playing;
+        let mut particles: Vec<ParticleInstance> = state.particles.collect_instances(|kind| match kind {
+            ParticleKind::Rain | ParticleKind::Snow => alive,
+            ParticleKind::Dust => playing,
+        });
 
         // Pass 5d: Biome atmosphere particles (only small opaque-friendly types)
         // NOTE: Large translucent particles (fog banks, mist, toxic gas, god rays) are
         // NOT rendered here because the pipeline uses BlendState::REPLACE (no alpha blending).
-        // They still influence the scene via fog_params and biome_colors passed to shaders.
+        // They still influence the scene via the renderer fog and biome_colors passed to shaders.
         // Only small bright particles (embers, fireflies, sparkles, sand, spores, ash, ice)
         // are rendered as tiny opaque specs.
         if !state.biome_atmosphere.particles.is_empty() && state.phase == GamePhase::Playing {
@@ -2066,18 +2140,16 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
             // Draw sphere atmosphere particles (embers, fireflies, spores, ash, ice, sand)
             if !sphere_insts.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.prop_sphere,
                     &sphere_insts,
                 );
             }
             // Draw flash/sparkle atmosphere particles
             if !flash_insts.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.flash_mesh,
                     &flash_insts,
                 );
@@ -2087,9 +2159,6 @@ pub fn run(state: &mut GameState) -> Result<()> {
         // Pass 5e: Smoke grenade clouds (dense red 2D billboard particles)
         {
             let mut smoke_instances: Vec<InstanceData> = Vec::new();
-            let cam_fwd = state.camera.forward();
-            let cam_right = cam_fwd.cross(Vec3::Y).normalize_or_zero();
-            let cam_up = cam_right.cross(cam_fwd).normalize_or_zero();
 
             // In-flight grenades (small grey sphere)
             for grenade in &state.smoke_grenades {
@@ -2103,8 +2172,21 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 let color = [0.3, 0.3, 0.3, 1.0]; // dark grey metal
                 smoke_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
+            // Thrown frags (olive drab, flashing red in the last second of the fuse)
+            for grenade in &state.frag_grenades {
+                let dist_sq = grenade.position.distance_squared(cam_pos);
+                if dist_sq > EFFECT_RENDER_DIST_SQ { continue; }
+                let matrix = glam::Mat4::from_scale_rotation_translation(
+                    Vec3::splat(crate::grenade::GRENADE_RADIUS),
+                    Quat::IDENTITY,
+                    grenade.position,
+                );
+                let blink = grenade.fuse < 1.0 && (grenade.fuse * 16.0).sin() > 0.0;
+                let color = if blink { [0.9, 0.15, 0.1, 1.0] } else { [0.28, 0.32, 0.18, 1.0] };
+                smoke_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
+            }
 
-            // Smoke cloud particles (red billboard quads)
+            // Smoke cloud particles (red billboard quads, alpha blended)
             for cloud in &state.smoke_clouds {
                 for p in &cloud.particles {
                     let dist_sq = p.position.distance_squared(cam_pos);
@@ -2127,30 +2209,19 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         alpha,
                     ];
 
-                    // Billboard: face the camera
-                    let right = cam_right * p.size;
-                    let up = cam_up * p.size;
-                    let billboard_rot = glam::Mat4::from_cols(
-                        right.extend(0.0),
-                        up.extend(0.0),
-                        (cam_fwd * p.size).extend(0.0),
-                        p.position.extend(1.0),
-                    );
-                    smoke_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
+                    particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], color));
                 }
             }
 
             if !smoke_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.billboard_mesh,
                     &smoke_instances,
                 );
             }
 
             // Pass 5e2: Tac Fighter explosion particles (flat billboard fire/smoke, same look as red smoke)
-            let mut explosion_instances: Vec<InstanceData> = Vec::new();
             for p in &state.effects.explosion_particles {
                 let dist_sq = p.position.distance_squared(cam_pos);
                 if dist_sq > EFFECT_RENDER_DIST_SQ { continue; }
@@ -2164,24 +2235,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     1 => (0.95, 0.5 + vary, 0.1),          // orange
                     _ => (0.2 + vary, 0.18, 0.15),         // dark smoke
                 };
-                let color = [r, g, b, alpha];
-                let right = cam_right * p.size;
-                let up = cam_up * p.size;
-                let billboard_rot = glam::Mat4::from_cols(
-                    right.extend(0.0),
-                    up.extend(0.0),
-                    (cam_fwd * p.size).extend(0.0),
-                    p.position.extend(1.0),
-                );
-                explosion_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
-            }
-            if !explosion_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
-                    &state.billboard_mesh,
-                    &explosion_instances,
-                );
+                particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], [r, g, b, alpha]));
             }
         }
 
@@ -2211,9 +2265,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     rotation,
                     fighter.position,
                 );
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.cube,
                     &[InstanceData::new(fuselage_matrix.to_cols_array_2d(), grey)],
                 );
@@ -2225,9 +2278,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     rotation,
                     nose_pos,
                 );
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.cube,
                     &[InstanceData::new(nose_matrix.to_cols_array_2d(), dark_grey)],
                 );
@@ -2243,9 +2295,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         rotation,
                         nacelle_pos,
                     );
-                    state.renderer.render_instanced_load(
-                        &mut encoder,
-                        &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.environment_meshes.cube,
                         &[InstanceData::new(nacelle_matrix.to_cols_array_2d(), dark_grey)],
                     );
@@ -2261,9 +2312,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         Quat::IDENTITY,
                         exhaust_pos,
                     );
-                    state.renderer.render_instanced_load(
-                        &mut encoder,
-                        &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.flash_mesh,
                         &[InstanceData::new(exhaust_matrix.to_cols_array_2d(), exhaust_color)],
                     );
@@ -2293,9 +2343,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 bomb_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
             if !bomb_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.prop_sphere, // cylindrical-ish shape
                     &bomb_instances,
                 );
@@ -2320,9 +2369,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 shell_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
             if !shell_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.flash_mesh, // multi-pointed star = bright glowing point
                     &shell_instances,
                 );
@@ -2364,15 +2412,48 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 trail_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
             }
             if !trail_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.billboard_mesh,
                     &trail_instances,
                 );
             }
         }
 
+        // Pass 5g2c: Artillery impact markers — a glowing ring hugging the ground around each
+        // predicted landing point at the kill radius; it blinks faster as the shell closes in
+        if !state.artillery_impacts.is_empty() {
+            const RING_SEGMENTS: usize = 64;
+            let t = state.time.elapsed_seconds();
+            let radius = ARTILLERY_KILL_RADIUS;
+            let mut ring_instances: Vec<InstanceData> = Vec::new();
+            for impact in &state.artillery_impacts {
+                let urgency = 1.0 - (impact.time_to_impact / 3.0).clamp(0.0, 1.0);
+                let blink = (t * (4.0 + urgency * 14.0)).sin() * 0.3 + 0.7;
+                let color = [2.4, 0.25, 0.08, 0.55 + 0.45 * blink];
+                let seg_len = std::f32::consts::TAU * radius / RING_SEGMENTS as f32 * 0.7;
+                for i in 0..RING_SEGMENTS {
+                    let a = i as f32 / RING_SEGMENTS as f32 * std::f32::consts::TAU;
+                    let x = impact.position.x + a.cos() * radius;
+                    let z = impact.position.z + a.sin() * radius;
+                    let y = state.chunk_manager.sample_height(x, z) + 0.25;
+                    let matrix = glam::Mat4::from_scale_rotation_translation(
+                        Vec3::new(0.35, 0.12, seg_len),
+                        Quat::from_rotation_y(-a),
+                        Vec3::new(x, y, z),
+                    );
+                    ring_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
+                }
+            }
+            if !ring_instances.is_empty() {
+                state.renderer.draw_instanced(
+                    &mut pass,
+                    &state.environment_meshes.cube,
+                    &ring_instances,
+                );
+            }
+        }
+
         // Pass 5g2: Grounded small-arms shell casings (persistent, weapon-specific)
         if !state.grounded_shell_casings.is_empty() {
             use crate::viewmodel::ShellCasingType;
@@ -2397,9 +2478,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 shell_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
             if !shell_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.cube,
                     &shell_instances,
                 );
@@ -2423,9 +2503,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 shell_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
             if !shell_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.cube,
                     &shell_instances,
                 );
@@ -2436,27 +2515,70 @@ pub fn run(state: &mut GameState) -> Result<()> {
         if !state.supply_crates.is_empty() {
             let mut crate_instances: Vec<InstanceData> = Vec::new();
             for c in &state.supply_crates {
-                if c.used { continue; }
                 let dist_sq = c.position.distance_squared(cam_pos);
                 if dist_sq > EFFECT_RENDER_DIST_SQ { continue; }
+                // Restock: the crate bulges and its lid pops up, then settles back
+                let anim = c.restock_anim();
+                let pop = (anim * std::f32::consts::PI).sin();
                 let matrix = glam::Mat4::from_scale_rotation_translation(
-                    Vec3::splat(0.8), // small box
+                    Vec3::splat(0.8) * (1.0 + pop * 0.12), // small box
                     Quat::IDENTITY,
-                    c.position,
+                    c.position + Vec3::Y * 0.4,
                 );
-                let color = [0.2, 0.5, 0.25, 1.0]; // green crate
+                let color = [0.2 + pop * 0.3, 0.5 + pop * 0.3, 0.25, 1.0]; // green crate
                 crate_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
+                let lid = glam::Mat4::from_scale_rotation_translation(
+                    Vec3::new(0.7, 0.12, 0.7),
+                    Quat::from_rotation_x(-pop * 0.6),
+                    c.position + Vec3::Y * (0.85 + pop * 0.35),
+                );
+                crate_instances.push(InstanceData::new(lid.to_cols_array_2d(), [0.15, 0.35, 0.18, 1.0]));
             }
             if !crate_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder,
-                    &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.prop_sphere,
                     &crate_instances,
                 );
             }
         }
 
+        // Pass 5g3: Weapons lying on the ground — the viewmodel's box parts at world scale,
+        // pulsing brighter when under the crosshair
+        {
+            const PICKUP_MODEL_SCALE: f32 = 1.8;
+            let targeted = match state.player.interaction_target {
+                Some(fps::InteractionTarget::WeaponPickup(entity)) => Some(entity),
+                _ => None,
+            };
+            let glow = 1.0 + ((state.time.elapsed_seconds() * 5.0).sin() * 0.5 + 0.5) * 0.8;
+            let mut pickup_instances: Vec<InstanceData> = Vec::new();
+            for (entity, (transform, pickup)) in state.world.query::<(&Transform, &WeaponPickup)>().iter() {
+                if transform.position.distance_squared(cam_pos) > EFFECT_RENDER_DIST_SQ { continue; }
+                let model = glam::Mat4::from_rotation_translation(transform.rotation, transform.position)
+                    * glam::Mat4::from_scale(Vec3::splat(PICKUP_MODEL_SCALE));
+                let brighten = if targeted == Some(entity) { glow } else { 1.0 };
+                let (parts, _) = gun_parts(pickup.weapon_type);
+                for part in parts {
+                    let part_mat = glam::Mat4::from_scale_rotation_translation(
+                        Vec3::from(part.scale),
+                        Quat::IDENTITY,
+                        Vec3::from(part.offset),
+                    );
+                    let c = part.color;
+                    let color = [c[0] * brighten, c[1] * brighten, c[2] * brighten, c[3]];
+                    pickup_instances.push(InstanceData::new((model * part_mat).to_cols_array_2d(), color));
+                }
+            }
+            if !pickup_instances.is_empty() {
+                state.renderer.draw_instanced(
+                    &mut pass,
+                    &state.environment_meshes.cube,
+                    &pickup_instances,
+                );
+            }
+        }
+
         // Pass 5h: DR-8 Skyhook extraction dropship (Fleet shuttle / retrieval boat)
         // Ref: https://starshiptroopers.fandom.com/wiki/DR-8_Skyhook
         if let Some(ref dropship) = state.extraction {
@@ -2488,8 +2610,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let cabin_matrix = glam::Mat4::from_scale_rotation_translation(
                         cabin_scale, rotation, dropship.position,
                     );
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.environment_meshes.cube,
                         &[InstanceData::new(cabin_matrix.to_cols_array_2d(), hull_grey)],
                     );
@@ -2499,8 +2621,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let cockpit_matrix = glam::Mat4::from_scale_rotation_translation(
                         Vec3::new(0.9, 0.6, 1.0), rotation, cockpit_pos,
                     );
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.environment_meshes.cube,
                         &[InstanceData::new(cockpit_matrix.to_cols_array_2d(), dark_grey)],
                     );
@@ -2513,8 +2635,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         let eng_matrix = glam::Mat4::from_scale_rotation_translation(
                             engine_scale, rotation, eng_pos,
                         );
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
+                        state.renderer.draw_instanced(
+                            &mut pass,
                             &state.environment_meshes.cube,
                             &[InstanceData::new(eng_matrix.to_cols_array_2d(), dark_grey)],
                         );
@@ -2525,8 +2647,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         );
                         let ei = dropship.engine_intensity;
                         let glow_color = [1.8 * ei, 1.0 * ei, 0.25 * ei, ei];
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
+                        state.renderer.draw_instanced(
+                            &mut pass,
                             &state.flash_mesh,
                             &[InstanceData::new(glow_matrix.to_cols_array_2d(), glow_color)],
                         );
@@ -2539,8 +2661,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             Vec3::splat(1.4 * dropship.ramp_open), Quat::IDENTITY, ramp_light_pos,
                         );
                         let ramp_color = [0.2, 2.5, 0.3, dropship.ramp_open];
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
+                        state.renderer.draw_instanced(
+                            &mut pass,
                             &state.flash_mesh,
                             &[InstanceData::new(ramp_matrix.to_cols_array_2d(), ramp_color)],
                         );
@@ -2574,8 +2696,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             }
                         }
                         if !gun_flash_instances.is_empty() {
-                            state.renderer.render_instanced_load(
-                                &mut encoder, &scene_view,
+                            state.renderer.draw_instanced(
+                                &mut pass,
                                 &state.flash_mesh,
                                 &gun_flash_instances,
                             );
@@ -2599,8 +2721,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                             ramp_scale, ramp_rot, ramp_center,
                         );
                         let ramp_color = [0.22, 0.22, 0.20, dropship.ramp_open];
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
+                        state.renderer.draw_instanced(
+                            &mut pass,
                             &state.environment_meshes.cube,
                             &[InstanceData::new(ramp_m.to_cols_array_2d(), ramp_color)],
                         );
@@ -2610,11 +2732,6 @@ pub fn run(state: &mut GameState) -> Result<()> {
 
             // ── LZ green smoke cloud (same particle style as red tac smoke) ──
             if let Some(ref lz_cloud) = state.lz_smoke {
-                let cam_fwd_lz = state.camera.forward();
-                let cam_right_lz = cam_fwd_lz.cross(Vec3::Y).normalize_or_zero();
-                let cam_up_lz = cam_right_lz.cross(cam_fwd_lz).normalize_or_zero();
-                let mut lz_smoke_instances: Vec<InstanceData> = Vec::new();
-
                 for p in &lz_cloud.particles {
                     let dist_sq = p.position.distance_squared(cam_pos);
                     if dist_sq > EFFECT_RENDER_DIST_SQ { continue; }
@@ -2634,33 +2751,11 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         alpha,
                     ];
 
-                    // Billboard: face the camera (identical to red smoke)
-                    let right = cam_right_lz * p.size;
-                    let up = cam_up_lz * p.size;
-                    let billboard_rot = glam::Mat4::from_cols(
-                        right.extend(0.0),
-                        up.extend(0.0),
-                        (cam_fwd_lz * p.size).extend(0.0),
-                        p.position.extend(1.0),
-                    );
-                    lz_smoke_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
-                }
-
-                if !lz_smoke_instances.is_empty() {
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
-                        &state.billboard_mesh,
-                        &lz_smoke_instances,
-                    );
+                    particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], color));
                 }
             }
 
             // ── Stratagem smoke (supply drop = green, reinforce = orange, orbital strike = red) ──
-            let cam_fwd_s = state.camera.forward();
-            let cam_right_s = cam_fwd_s.cross(Vec3::Y).normalize_or_zero();
-            let cam_up_s = cam_right_s.cross(cam_fwd_s).normalize_or_zero();
-            let mut stratagem_smoke_instances: Vec<InstanceData> = Vec::new();
-
             for cloud in &state.supply_drop_smoke {
                 for p in &cloud.particles {
                     let dist_sq = p.position.distance_squared(cam_pos);
@@ -2671,12 +2766,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let alpha = life_frac.powf(0.6);
                     if alpha < 0.2 { continue; }
                     let color = [0.05 + vary * 0.3, 0.65 + vary, 0.08, alpha];
-                    let right = cam_right_s * p.size;
-                    let up = cam_up_s * p.size;
-                    let billboard_rot = glam::Mat4::from_cols(
-                        right.extend(0.0), up.extend(0.0), (cam_fwd_s * p.size).extend(0.0), p.position.extend(1.0),
-                    );
-                    stratagem_smoke_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
+                    particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], color));
                 }
             }
             if let Some(ref cloud) = state.reinforce_smoke {
@@ -2689,12 +2779,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let alpha = life_frac.powf(0.6);
                     if alpha < 0.2 { continue; }
                     let color = [(0.85 + vary).min(1.0), (0.45 + vary * 0.3).min(1.0), 0.05, alpha];
-                    let right = cam_right_s * p.size;
-                    let up = cam_up_s * p.size;
-                    let billboard_rot = glam::Mat4::from_cols(
-                        right.extend(0.0), up.extend(0.0), (cam_fwd_s * p.size).extend(0.0), p.position.extend(1.0),
-                    );
-                    stratagem_smoke_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
+                    particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], color));
                 }
             }
             if let Some(ref cloud) = state.orbital_strike_smoke {
@@ -2707,21 +2792,9 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     let alpha = life_frac.powf(0.6);
                     if alpha < 0.2 { continue; }
                     let color = [0.75 + vary, 0.08 + vary * 0.3, 0.05, alpha];
-                    let right = cam_right_s * p.size;
-                    let up = cam_up_s * p.size;
-                    let billboard_rot = glam::Mat4::from_cols(
-                        right.extend(0.0), up.extend(0.0), (cam_fwd_s * p.size).extend(0.0), p.position.extend(1.0),
-                    );
-                    stratagem_smoke_instances.push(InstanceData::new(billboard_rot.to_cols_array_2d(), color));
+                    particles.push(ParticleInstance::new(p.position.to_array(), [p.size; 2], color));
                 }
             }
-            if !stratagem_smoke_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder, &scene_view,
-                    &state.billboard_mesh,
-                    &stratagem_smoke_instances,
-                );
-            }
         }
 
         // Pass 5h2: Roger Young Federation Destroyer (visible in real time during retrieval: orbit/atmosphere)
@@ -2765,7 +2838,7 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     match part.mesh_type {
                         0 => rock_instances.push(inst),
                         1 => sphere_instances.push(inst),
-                        _ => glow_instances.push(inst),
+                        _ => glow_instances.push(inst.with_emissive(GLOW_EMISSIVE)),
                     }
                 }
 
@@ -2778,22 +2851,22 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 }
 
                 if !rock_instances.is_empty() {
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
-                        &state.environment_meshes.rock,
+                    state.renderer.draw_instanced(
+                        &mut pass,
+                        state.environment_meshes.rock.full(),
                         &rock_instances,
                     );
                 }
                 if !sphere_instances.is_empty() {
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.environment_meshes.prop_sphere,
                         &sphere_instances,
                     );
                 }
                 if !glow_instances.is_empty() {
-                    state.renderer.render_instanced_load(
-                        &mut encoder, &scene_view,
+                    state.renderer.draw_instanced(
+                        &mut pass,
                         &state.flash_mesh,
                         &glow_instances,
                     );
@@ -2812,8 +2885,8 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         );
                         let c = [blink * 2.0, blink * 0.8, blink * 0.1, blink];
                         let inst = InstanceData::new(m.to_cols_array_2d(), c);
-                        state.renderer.render_instanced_load(
-                            &mut encoder, &scene_view,
+                        state.renderer.draw_instanced(
+                            &mut pass,
                             &state.flash_mesh,
                             &vec![inst],
                         );
@@ -2846,147 +2919,87 @@ pub fn run(state: &mut GameState) -> Result<()> {
                 casing_instances.push(InstanceData::new(matrix.to_cols_array_2d(), color));
             }
             if !casing_instances.is_empty() {
-                state.renderer.render_instanced_load(
-                    &mut encoder, &scene_view,
+                state.renderer.draw_instanced(
+                    &mut pass,
                     &state.environment_meshes.cube,
                     &casing_instances,
                 );
             }
         }
 
+        // Deployed class-ability shields: translucent blue, fading as they run out
+        for shield in &state.abilities.shields {
+            let alpha = 0.12 + 0.18 * shield.strength();
+            let color = [0.35, 0.7, 1.0, alpha];
+            match shield.shape {
+                ShieldShape::Dome { radius } => {
+                    let m = glam::Mat4::from_scale_rotation_translation(Vec3::splat(radius), Quat::IDENTITY, shield.center);
+                    let instance = InstanceData::new(m.to_cols_array_2d(), color);
+                    transparent.push_instances(&state.environment_meshes.prop_sphere, &[instance]);
+                }
+                ShieldShape::Wall { half_width, height, along } => {
+                    let rotation = Quat::from_rotation_arc(Vec3::X, along);
+                    let m = glam::Mat4::from_scale_rotation_translation(
+                        Vec3::new(half_width * 2.0, height, 0.15),
+                        rotation,
+                        shield.center + Vec3::Y * (height * 0.5),
+                    );
+                    let instance = InstanceData::new(m.to_cols_array_2d(), color);
+                    transparent.push_instances(&state.environment_meshes.cube, &[instance]);
+                }
+            }
+        }
+
+        drop(pass);
+
+        // Pass 5i2: Bullet holes and scorch marks on hard surfaces
+        state.renderer.render_decals(&mut encoder, &scene_view);
+
+        // Pass 5j: Transparent surfaces (water, shields), back-to-front
+        state.renderer.render_transparent(&mut encoder, &scene_view, &state.camera, transparent);
+
+        // Pass 5k: Billboard particles (weather, smoke, fire) in one instanced draw, farthest
+        // first so the alpha-blended puffs layer correctly
+        sort_particles_back_to_front(&mut particles, state.camera.position(), state.camera.forward());
+        state.renderer.draw_particles(&mut encoder, &scene_view, &particles);
+
         // Pass 6: Viewmodel (rifle / shotgun / MG / entrenchment shovel) - animated, multi-part composition
         // Each part is a unit cube scaled/positioned to form the weapon silhouette
         let player_in_boat = state.extraction.as_ref().map_or(false, |e: &ExtractionDropship| e.player_camera_locked());
         // Show weapon on planet (FPS) — never in noclip or in boat (rifle or shovel)
         let show_viewmodel = (!state.debug.noclip && state.current_planet_idx.is_some())
             && state.phase == GamePhase::Playing && state.player.is_alive && !player_in_boat;
-        if show_viewmodel {
-            let view_to_world = state.camera.view_matrix().inverse();
-            // Helper: viewmodel part (offset, scale, color). For guns barrel points along -Z.
-            struct GunPart {
-                offset: [f32; 3],
-                scale: [f32; 3],
-                color: [f32; 4],
-            }
+        let viewmodel_rig = state.viewmodel_rig.filter(|rig| show_viewmodel && rig.is_spawned(&state.world));
+        if let Some(rig) = viewmodel_rig {
+            // Gun and muzzle world matrices, resolved through the camera -> gun -> muzzle hierarchy
+            let gun_world = rig.global(&state.world, rig.gun);
             let mut viewmodel_instances: Vec<InstanceData> = Vec::new();
+            let part_instance = |part: &GunPart| {
+                let part_mat = glam::Mat4::from_scale_rotation_translation(
+                    Vec3::from(part.scale), Quat::IDENTITY, Vec3::from(part.offset),
+                );
+                InstanceData::new((gun_world * part_mat).to_cols_array_2d(), part.color)
+            };
 
             if state.player.is_shovel_equipped() {
                 // Entrenchment shovel: held in both hands, blade down. Same cube-instance pipeline.
-                let base_pos = Vec3::new(0.12, -0.14, -0.34);
-                let aim = 0.0;
-                let (anim_offset, anim_rot) = state.viewmodel_anim.compute_transform(aim, base_pos, base_pos);
-                let shovel_pos = base_pos + anim_offset;
-                let blade_tilt = Quat::from_rotation_x(-0.5);
-                let shovel_rot = anim_rot * blade_tilt;
-
                 let shovel_parts: &[GunPart] = &[
                     GunPart { offset: [0.0, 0.0, -0.12], scale: [0.018, 0.018, 0.26], color: [0.18, 0.15, 0.12, 1.0] }, // handle
                     GunPart { offset: [0.0, 0.0, -0.28], scale: [0.10, 0.012, 0.07], color: [0.32, 0.32, 0.35, 1.0] },   // blade
                     GunPart { offset: [0.0, 0.0, 0.04], scale: [0.028, 0.038, 0.03], color: [0.14, 0.12, 0.10, 1.0] },   // grip
                 ];
-                for part in shovel_parts {
-                    let part_offset = Vec3::new(part.offset[0], part.offset[1], part.offset[2]);
-                    let part_scale = Vec3::new(part.scale[0], part.scale[1], part.scale[2]);
-                    let rotated_offset = shovel_rot * part_offset;
-                    let view_pos = shovel_pos + rotated_offset;
-                    let view_mat = glam::Mat4::from_scale_rotation_translation(
-                        part_scale, shovel_rot, view_pos,
-                    );
-                    let world_mat = view_to_world * view_mat;
-                    viewmodel_instances.push(InstanceData::new(world_mat.to_cols_array_2d(), part.color));
-                }
+                viewmodel_instances.extend(shovel_parts.iter().map(part_instance));
             } else {
-                // Base viewmodel position in view space: right, below eye, forward (hip-fire)
-                let base_pos = Vec3::new(0.18, -0.11, -0.38);
-
-                // ADS target: gun pivot position when looking through sights.
-                let ads_target = match state.player.current_weapon().weapon_type {
-                    WeaponType::Shotgun => Vec3::new(0.0, -0.025, -0.22),
-                    WeaponType::MachineGun => Vec3::new(0.0, -0.038, -0.25),
-                    _ => Vec3::new(0.0, -0.042, -0.24),
-                };
-
-                let aim = state.player.aim_progress;
-                let (anim_offset, anim_rot) = state.viewmodel_anim.compute_transform(aim, base_pos, ads_target);
-                let gun_pos = base_pos + anim_offset;
-                let gun_rot = anim_rot;
-
-                let (parts, muzzle_offset) = if state.player.current_weapon().weapon_type == WeaponType::Shotgun {
-                // MI-22 Tactical Shotgun — pump-action, short barrel, stock
-                let shotgun_parts: &[GunPart] = &[
-                    GunPart { offset: [0.0, 0.0, 0.02], scale: [0.040, 0.038, 0.14], color: [0.20, 0.20, 0.22, 1.0] }, // receiver
-                    GunPart { offset: [0.0, 0.006, -0.20], scale: [0.022, 0.022, 0.28], color: [0.28, 0.28, 0.30, 1.0] }, // barrel
-                    GunPart { offset: [0.0, 0.0, -0.08], scale: [0.032, 0.026, 0.06], color: [0.18, 0.18, 0.20, 1.0] },   // pump / forend
-                    GunPart { offset: [0.0, -0.006, 0.16], scale: [0.028, 0.034, 0.12], color: [0.14, 0.12, 0.10, 1.0] }, // stock
-                    GunPart { offset: [0.0, -0.050, 0.0], scale: [0.020, 0.050, 0.026], color: [0.12, 0.12, 0.14, 1.0] },  // grip
-                    GunPart { offset: [0.0, -0.028, -0.02], scale: [0.016, 0.008, 0.040], color: [0.22, 0.22, 0.24, 1.0] }, // trigger guard
-                    GunPart { offset: [0.0, 0.028, -0.12], scale: [0.008, 0.012, 0.18], color: [0.24, 0.24, 0.26, 1.0] },  // vent rib / sight rail
-                    GunPart { offset: [0.0, 0.006, -0.38], scale: [0.024, 0.024, 0.022], color: [0.26, 0.26, 0.28, 1.0] }, // muzzle
-                ];
-                (shotgun_parts, Vec3::new(0.0, 0.006, -0.40))
-            } else if state.player.current_weapon().weapon_type == WeaponType::MachineGun {
-                // Morita MG — heavy machine gun, longer barrel, ammo box
-                let mg_parts: &[GunPart] = &[
-                    GunPart { offset: [0.0, 0.0, 0.05], scale: [0.045, 0.050, 0.24], color: [0.20, 0.20, 0.22, 1.0] }, // receiver
-                    GunPart { offset: [0.0, 0.010, -0.32], scale: [0.018, 0.018, 0.42], color: [0.28, 0.28, 0.30, 1.0] }, // barrel
-                    GunPart { offset: [0.0, -0.012, 0.22], scale: [0.038, 0.042, 0.14], color: [0.16, 0.16, 0.18, 1.0] }, // ammo box
-                    GunPart { offset: [0.0, -0.058, 0.0], scale: [0.022, 0.055, 0.030], color: [0.14, 0.14, 0.16, 1.0] }, // grip
-                    GunPart { offset: [0.0, -0.038, -0.025], scale: [0.018, 0.010, 0.050], color: [0.22, 0.22, 0.24, 1.0] }, // trigger guard
-                    GunPart { offset: [0.0, 0.035, -0.08], scale: [0.010, 0.014, 0.22], color: [0.24, 0.24, 0.26, 1.0] }, // sight rail
-                    GunPart { offset: [0.0, 0.010, -0.52], scale: [0.028, 0.028, 0.028], color: [0.24, 0.24, 0.26, 1.0] }, // muzzle
-                ];
-                (mg_parts, Vec3::new(0.0, 0.010, -0.54))
-            } else {
-                // M1A4 Morita Rifle — Starship Troopers bullpup assault rifle
-                let rifle_parts: &[GunPart] = &[
-                    GunPart { offset: [0.0, 0.0, 0.04], scale: [0.038, 0.042, 0.20], color: [0.22, 0.22, 0.25, 1.0] },
-                    GunPart { offset: [0.0, 0.008, -0.24], scale: [0.012, 0.012, 0.34], color: [0.30, 0.30, 0.33, 1.0] },
-                    GunPart { offset: [0.0, 0.005, -0.08], scale: [0.026, 0.028, 0.12], color: [0.20, 0.20, 0.23, 1.0] },
-                    GunPart { offset: [0.0, 0.008, -0.44], scale: [0.018, 0.018, 0.030], color: [0.14, 0.14, 0.16, 1.0] },
-                    GunPart { offset: [0.0, -0.008, 0.17], scale: [0.034, 0.038, 0.10], color: [0.18, 0.18, 0.20, 1.0] },
-                    GunPart { offset: [0.0, -0.008, 0.24], scale: [0.030, 0.036, 0.018], color: [0.10, 0.10, 0.12, 1.0] },
-                    GunPart { offset: [0.0, -0.052, 0.0], scale: [0.018, 0.048, 0.024], color: [0.14, 0.14, 0.16, 1.0] },
-                    GunPart { offset: [0.0, -0.032, -0.022], scale: [0.014, 0.008, 0.045], color: [0.22, 0.22, 0.25, 1.0] },
-                    GunPart { offset: [0.0, 0.032, -0.01], scale: [0.014, 0.014, 0.16], color: [0.20, 0.20, 0.22, 1.0] },
-                    GunPart { offset: [0.0, 0.042, -0.08], scale: [0.010, 0.010, 0.010], color: [0.25, 0.25, 0.28, 1.0] },
-                    GunPart { offset: [0.0, 0.042, 0.06], scale: [0.010, 0.010, 0.010], color: [0.25, 0.25, 0.28, 1.0] },
-                    GunPart { offset: [0.0, 0.030, -0.32], scale: [0.005, 0.020, 0.005], color: [0.25, 0.25, 0.28, 1.0] },
-                    GunPart { offset: [0.0, -0.042, 0.08], scale: [0.020, 0.048, 0.028], color: [0.16, 0.16, 0.18, 1.0] },
-                    GunPart { offset: [0.0, -0.022, -0.14], scale: [0.016, 0.016, 0.18], color: [0.26, 0.26, 0.28, 1.0] },
-                    GunPart { offset: [0.0, -0.022, -0.04], scale: [0.024, 0.018, 0.055], color: [0.18, 0.18, 0.20, 1.0] },
-                    GunPart { offset: [0.022, 0.024, 0.06], scale: [0.008, 0.008, 0.018], color: [0.30, 0.30, 0.32, 1.0] },
-                    GunPart { offset: [0.022, 0.005, 0.02], scale: [0.004, 0.018, 0.035], color: [0.28, 0.28, 0.30, 1.0] },
-                    GunPart { offset: [0.018, -0.005, -0.16], scale: [0.005, 0.005, 0.005], color: [0.30, 0.30, 0.32, 1.0] },
-                ];
-                (rifle_parts, Vec3::new(0.0, 0.008, -0.46))
-            };
-
-            for part in parts {
-                let part_offset = Vec3::new(part.offset[0], part.offset[1], part.offset[2]);
-                let part_scale = Vec3::new(part.scale[0], part.scale[1], part.scale[2]);
-
-                // Build the part's transform in view space
-                let rotated_offset = gun_rot * part_offset;
-                let view_pos = gun_pos + rotated_offset;
-
-                // Construct view-space matrix, then transform to world space
-                // via the view matrix inverse for guaranteed-correct positioning.
-                let view_mat = glam::Mat4::from_scale_rotation_translation(
-                    part_scale, gun_rot, view_pos,
-                );
-                let world_mat = view_to_world * view_mat;
-
-                viewmodel_instances.push(InstanceData::new(world_mat.to_cols_array_2d(), part.color));
-            }
+                let (parts, _) = gun_parts(state.player.current_weapon().weapon_type);
+                viewmodel_instances.extend(parts.iter().map(part_instance));
 
                 // === MUZZLE FLASH (when firing) ===
                 if state.viewmodel_anim.fire_flash_timer < 0.06 {
+                    let muzzle_world = rig.global(&state.world, rig.muzzle);
                     let flash_t = state.viewmodel_anim.fire_flash_timer / 0.06;
                     let flash_intensity = (1.0 - flash_t).max(0.0);
                     let flash_size = 0.025 + flash_intensity * 0.02;
 
-                    let muzzle_view = gun_pos + gun_rot * muzzle_offset;
-
                     let flash_color = [
                         2.0 + flash_intensity * 3.0,
                         1.5 + flash_intensity * 2.0,
@@ -2995,13 +3008,10 @@ pub fn run(state: &mut GameState) -> Result<()> {
                     ];
 
                     let rot_angle = state.time.elapsed_seconds() * 137.0;
-                    let flash_rot = gun_rot * Quat::from_rotation_z(rot_angle);
-
-                    let flash_view = glam::Mat4::from_scale_rotation_translation(
-                        Vec3::splat(flash_size), flash_rot, muzzle_view,
+                    let flash_local = glam::Mat4::from_scale_rotation_translation(
+                        Vec3::splat(flash_size), Quat::from_rotation_z(rot_angle), Vec3::ZERO,
                     );
-                    let flash_world = view_to_world * flash_view;
-                    viewmodel_instances.push(InstanceData::new(flash_world.to_cols_array_2d(), flash_color));
+                    viewmodel_instances.push(InstanceData::new((muzzle_world * flash_local).to_cols_array_2d(), flash_color));
 
                     let flash2_size = flash_size * 1.8;
                     let flash2_color = [
@@ -3010,13 +3020,10 @@ pub fn run(state: &mut GameState) -> Result<()> {
                         0.1 * flash_intensity,
                         flash_intensity * 0.8,
                     ];
-                    let flash2_rot = gun_rot * Quat::from_rotation_z(rot_angle + 1.0);
-                    let flash2_view = glam::Mat4::from_scale_rotation_translation(
-                        Vec3::splat(flash2_size), flash2_rot,
-                        muzzle_view + gun_rot * Vec3::new(0.0, 0.0, -0.01),
+                    let flash2_local = glam::Mat4::from_scale_rotation_translation(
+                        Vec3::splat(flash2_size), Quat::from_rotation_z(rot_angle + 1.0), Vec3::new(0.0, 0.0, -0.01),
                     );
-                    let flash2_world = view_to_world * flash2_view;
-                    viewmodel_instances.push(InstanceData::new(flash2_world.to_cols_array_2d(), flash2_color));
+                    viewmodel_instances.push(InstanceData::new((muzzle_world * flash2_local).to_cols_array_2d(), flash2_color));
                 }
             }
 
@@ -2,15 +2,18 @@
 
 use engine_core::{Health, Transform};
 use glam::Vec3;
-use procgen::StarType;
-use renderer::OverlayTextBuilder;
+use renderer::{OverlayTextBuilder, TextAlign, TextLayout};
 
 use crate::earth_territory;
 use crate::extraction::{self, ExtractionPhase};
+use crate::fps::InteractionTarget;
+use crate::galaxy_map;
+use crate::hud::{compass_heading, compass_offset, minimap_project, MinimapBlipKind};
 use crate::roger_young_interior_npcs;
 use crate::squad::SquadMate;
+use crate::tac_fighter::TacFighterPhase;
 use crate::state::{DEPLOY_KEY, DIALOGUE_CHOICE_KEYS, DIALOGUE_CLOSE_KEY, INTERACT_KEY};
-use crate::{DropPhase, GameMessage, GamePhase, GameState};
+use crate::{DropPhase, GameMessage, GamePhase, GameState, SupplyStatus};
 
 /// Build the screen-space overlay (debug info, HUD, game messages, war table, etc.).
 pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
@@ -20,7 +23,6 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
     let bg = [0.0, 0.0, 0.0, 0.55]; // semi-transparent dark bg
     let white = [1.0, 1.0, 1.0, 1.0];
     let gray = [0.7, 0.7, 0.7, 1.0];
-    let yellow = [1.0, 0.9, 0.3, 1.0];
     // STE-style tactical: green #00ff00, amber #ffaa00 (ART_DIRECTION)
     let tactical_green = [0.0, 1.0, 0.0, 1.0];
     let tactical_amber = [1.0, 0.67, 0.0, 1.0];
@@ -29,11 +31,9 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
     if state.phase == GamePhase::MainMenu && !state.main_menu_galaxy_open {
         let title = "OpenSST";
         let subtitle = "Starship Troopers × Helldivers 2 × Star Citizen";
-        let title_scale = 1.8;
-        let title_w = title.len() as f32 * 8.0 * title_scale;
-        tb.add_text(sw * 0.5 - title_w * 0.5, sh * 0.28, title, title_scale, [0.9, 0.88, 0.75, 1.0]);
-        let sub_w = subtitle.len() as f32 * 5.0;
-        tb.add_text(sw * 0.5 - sub_w * 0.5, sh * 0.34, subtitle, 1.0, [0.5, 0.55, 0.65, 1.0]);
+        let centered = |scale: f32| TextLayout::new(scale).align(TextAlign::Center);
+        tb.add_text_layout(sw * 0.5, sh * 0.28, title, &centered(1.8), [0.9, 0.88, 0.75, 1.0]);
+        tb.add_text_layout(sw * 0.5, sh * 0.34, subtitle, &centered(1.0), [0.5, 0.55, 0.65, 1.0]);
 
         let first_sel = state.main_menu_selected == 0;
         let universe_sel = state.main_menu_selected == 1;
@@ -54,53 +54,12 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         return tb;
     }
 
-    // ---- Main menu galaxy map (when Universe Map is open from menu) ----
-    if state.phase == GamePhase::MainMenu && state.main_menu_galaxy_open {
-        // Full universe view; Enter = travel to selected system and board Roger Young
-        {
-            tb.add_rect(sw * 0.1, sh * 0.1, sw * 0.8, sh * 0.8, [0.0, 0.0, 0.05, 0.85]);
-            let title = format!("UNIVERSE — {} systems | Select destination", state.universe.systems.len());
-            tb.add_text(sw * 0.12, sh * 0.12, &title, scale, [0.6, 0.8, 1.0, 1.0]);
-            let map_cx = sw * 0.5;
-            let map_cy = sh * 0.5;
-            let map_scale = sh * 0.3 / 1000.0;
-            let current_pos = state.universe.systems[state.current_system_idx].position;
-            for (i, entry) in state.universe.systems.iter().enumerate() {
-                let rel = entry.position - current_pos;
-                let sx = map_cx + rel.x as f32 * map_scale;
-                let sy = map_cy - rel.z as f32 * map_scale;
-                if sx < sw * 0.1 || sx > sw * 0.9 || sy < sh * 0.1 || sy > sh * 0.9 {
-                    continue;
-                }
-                let star_color = match entry.star_type {
-                    StarType::RedDwarf => [1.0, 0.3, 0.2, 0.9],
-                    StarType::YellowMain => [1.0, 0.95, 0.5, 0.9],
-                    StarType::BlueGiant => [0.5, 0.6, 1.0, 0.9],
-                    StarType::WhiteDwarf => [0.9, 0.9, 1.0, 0.8],
-                    StarType::BinaryStar => [1.0, 0.8, 0.4, 0.9],
-                };
-                let dot_size = if i == state.current_system_idx { 8.0 } else if i == state.galaxy_map_selected { 6.0 } else { 4.0 };
-                if i == state.current_system_idx {
-                    tb.add_rect(sx - dot_size, sy - dot_size, dot_size * 2.0, dot_size * 2.0, [0.0, 1.0, 0.0, 0.4]);
-                }
-                if i == state.galaxy_map_selected {
-                    tb.add_rect(sx - dot_size - 1.0, sy - dot_size - 1.0, dot_size * 2.0 + 2.0, dot_size * 2.0 + 2.0, [1.0, 1.0, 0.0, 0.5]);
-                }
-                tb.add_rect(sx - dot_size * 0.5, sy - dot_size * 0.5, dot_size, dot_size, star_color);
-                if i == state.current_system_idx || i == state.galaxy_map_selected || entry.visited {
-                    tb.add_text(sx + dot_size, sy - 4.0, &entry.name, 1.5, star_color);
-                }
-            }
-            let selected = &state.universe.systems[state.galaxy_map_selected];
-            let sel_info = format!("Selected: {} ({:?})", selected.name, selected.star_type);
-            tb.add_text_with_bg(sw * 0.12, sh * 0.82, &sel_info, scale, yellow, bg);
-            tb.add_text_with_bg(sw * 0.12, sh * 0.85, "Enter = Travel to system & board Roger Young | M / Esc = Back", scale, gray, bg);
-        }
+    // ---- Galaxy map (main menu Universe Map or in-flight M): replaces the HUD while open ----
+    if state.galaxy_map_visible() {
+        draw_galaxy_map(&mut tb, state, sw, sh);
         return tb;
     }
 
-    // (Galaxy map when in-ship is drawn later via state.galaxy_map_open)
-
     // ---- Pause menu: full-screen dark overlay ----
     if state.phase == GamePhase::Paused {
         tb.add_rect(0.0, 0.0, sw, sh, [0.08, 0.08, 0.08, 1.0]); // Dark grey background
@@ -241,65 +200,6 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         tb.add_text_with_bg(x, y, controls_text, scale, tactical_amber, bg);
     }
 
-    // ---- Galaxy map overlay (when M is pressed) ----
-    if state.galaxy_map_open {
-        tb.add_rect(sw * 0.1, sh * 0.1, sw * 0.8, sh * 0.8, [0.0, 0.0, 0.05, 0.85]);
-
-        let title = format!("GALAXY MAP - {} systems | Viewing: {}", state.universe.systems.len(), state.current_system.name);
-        tb.add_text(sw * 0.12, sh * 0.12, &title, scale, [0.6, 0.8, 1.0, 1.0]);
-
-        let map_cx = sw * 0.5;
-        let map_cy = sh * 0.5;
-        let map_scale = sh * 0.3 / 1000.0;
-
-        let current_pos = state.universe.systems[state.current_system_idx].position;
-
-        for (i, entry) in state.universe.systems.iter().enumerate() {
-            let rel = entry.position - current_pos;
-            let sx = map_cx + rel.x as f32 * map_scale;
-            let sy = map_cy - rel.z as f32 * map_scale;
-
-            if sx < sw * 0.1 || sx > sw * 0.9 || sy < sh * 0.1 || sy > sh * 0.9 {
-                continue;
-            }
-
-            let star_color = match entry.star_type {
-                StarType::RedDwarf => [1.0, 0.3, 0.2, 0.9],
-                StarType::YellowMain => [1.0, 0.95, 0.5, 0.9],
-                StarType::BlueGiant => [0.5, 0.6, 1.0, 0.9],
-                StarType::WhiteDwarf => [0.9, 0.9, 1.0, 0.8],
-                StarType::BinaryStar => [1.0, 0.8, 0.4, 0.9],
-            };
-
-            let dot_size = if i == state.current_system_idx { 8.0 } else if i == state.galaxy_map_selected { 6.0 } else { 4.0 };
-
-            if i == state.current_system_idx {
-                tb.add_rect(sx - dot_size, sy - dot_size, dot_size * 2.0, dot_size * 2.0, [0.0, 1.0, 0.0, 0.4]);
-            }
-            if i == state.galaxy_map_selected {
-                tb.add_rect(sx - dot_size - 1.0, sy - dot_size - 1.0, dot_size * 2.0 + 2.0, dot_size * 2.0 + 2.0, [1.0, 1.0, 0.0, 0.5]);
-            }
-
-            tb.add_rect(sx - dot_size * 0.5, sy - dot_size * 0.5, dot_size, dot_size, star_color);
-
-            if i == state.current_system_idx || i == state.galaxy_map_selected || entry.visited {
-                tb.add_text(sx + dot_size, sy - 4.0, &entry.name, 1.5, star_color);
-            }
-        }
-
-        let selected = &state.universe.systems[state.galaxy_map_selected];
-        let sel_info = format!(
-            "Selected: {} ({:?}) | {}",
-            selected.name,
-            selected.star_type,
-            if selected.visited { "VISITED" } else { "UNCHARTED" }
-        );
-        tb.add_text_with_bg(sw * 0.12, sh * 0.85, &sel_info, scale, yellow, bg);
-
-        let help_text = "Arrow keys/scroll = select | Enter = warp | M = close";
-        tb.add_text_with_bg(sw * 0.12, sh * 0.85 + line_h, help_text, scale, gray, bg);
-    }
-
     // ---- Debug menu overlay (F3) ----
     if state.debug.menu_open {
         let menu_w = 380.0;
@@ -353,7 +253,7 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         let mode_color = if state.debug.noclip { [1.0, 0.7, 0.3, 1.0] } else { [0.3, 1.0, 0.5, 1.0] };
         tb.add_text(menu_x, footer_y + 14.0, mode_text, 1.5, mode_color);
 
-        let ts_text = format!("Time Scale: {:.2}x", state.debug.time_scale);
+        let ts_text = format!("Time Scale: {:.2}x", state.time.time_scale());
         tb.add_text(menu_x + 150.0, footer_y + 14.0, &ts_text, 1.5, [0.6, 0.6, 0.8, 0.8]);
     }
 
@@ -389,13 +289,19 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
             tb.add_rect(cx - 8.0, cy - 1.0, 6.0, 2.0, [0.5, 0.7, 1.0, 0.5]);
             tb.add_rect(cx + 2.0, cy - 1.0, 6.0, 2.0, [0.5, 0.7, 1.0, 0.5]);
 
-            // NPC nametags
+            // NPC nametags (dimmed; the crew member under the crosshair gets the highlighted tag below)
             const NAMETAG_MAX_DIST: f32 = 12.0;
             const NAMETAG_MIN_DOT: f32 = 0.4;
             let cam_pos = state.camera.position();
             let cam_fwd = state.camera.forward();
-            let view_proj = state.camera.view_projection_matrix();
+            let target_name = match &state.player.interaction_target {
+                Some(InteractionTarget::Speaker { name, .. }) => Some(name.as_str()),
+                _ => None,
+            };
             for npc in roger_young_interior_npcs() {
+                if target_name == Some(npc.name) {
+                    continue;
+                }
                 let head_pos = npc.position + Vec3::Y * 1.6;
                 let to_npc = head_pos - cam_pos;
                 let dist = to_npc.length();
@@ -406,20 +312,7 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                 if cam_fwd.dot(dir) < NAMETAG_MIN_DOT {
                     continue;
                 }
-                let clip = view_proj * glam::Vec4::new(head_pos.x, head_pos.y, head_pos.z, 1.0);
-                if clip.w <= 0.01 {
-                    continue;
-                }
-                let ndc_z = clip.z / clip.w;
-                if ndc_z > 1.0 {
-                    continue;
-                }
-                let sx = (clip.x / clip.w + 1.0) * 0.5 * sw;
-                let sy = (1.0 - clip.y / clip.w) * 0.5 * sh;
-                let name = npc.name;
-                let scale = 1.5;
-                let tw = name.len() as f32 * 6.0 * scale * 0.5;
-                tb.add_text_with_bg(sx - tw, sy - 24.0, name, scale, [1.0, 1.0, 1.0, 0.95], [0.0, 0.0, 0.0, 0.6]);
+                draw_nametag(&mut tb, state, sw, sh, head_pos, npc.name, [1.0, 1.0, 1.0, 0.55]);
             }
 
             if state.phase == GamePhase::ApproachPlanet {
@@ -551,14 +444,26 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                     let dp = &state.planet;
                     let dws = state.war_state.planets.get(selected);
                     let dx = bx + 20.0;
-                    let mut dy = by + bh - 100.0;
+                    let mut dy = by + bh - 136.0;
                     let ds = 1.5;
                     let line_hd = 18.0;
                     tb.add_text(dx, dy, &format!("TARGET: {}", dp.name), ds, [1.0, 0.9, 0.5, 1.0]); dy += line_hd;
                     // Star Citizen / Helldivers 2: contract-style mission board
                     let mission_str = state.next_mission_type.name().to_string();
-                    let contract = format!("CONTRACT: {} — {}. Reward: Liberation progress.", mission_str, dp.name);
+                    let contract = format!(
+                        "CONTRACT: {} — {} [{}]. Reward: Liberation progress.",
+                        mission_str,
+                        dp.name,
+                        state.difficulty.name().to_uppercase()
+                    );
                     tb.add_text(dx, dy, &contract, 1.1, [0.5, 0.75, 1.0, 1.0]); dy += line_hd;
+                    let class_line = format!(
+                        "Class: {} — [Q] {} | Heavy: {:?}",
+                        state.player.class.name(),
+                        state.player.ability.name(),
+                        state.heavy_weapon
+                    );
+                    tb.add_text(dx, dy, &class_line, 1.1, [0.6, 0.85, 0.7, 1.0]); dy += line_hd;
                     let is_earth = dp.name == "Earth";
                     if is_earth {
                         // Earth: no danger counter — safe zone, visit only
@@ -577,13 +482,21 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                             dws.map_or(0, |s| s.total_kills),
                             dws.map_or(0, |s| s.successful_extractions),
                         ), ds, [0.5, 0.6, 0.7, 0.9]); dy += line_hd;
+                        if let Some(status) = dws {
+                            let (effect, color) = match status.supply {
+                                SupplyStatus::CutOff => ("bugs swarm harder, counter-attacks bite deeper", [1.0, 0.45, 0.2, 1.0]),
+                                SupplyStatus::Linked => ("connected to friendly space", [0.6, 0.7, 0.8, 0.9]),
+                                SupplyStatus::Reinforced => ("stratagems rearm faster", [0.4, 0.9, 0.5, 1.0]),
+                            };
+                            tb.add_text(dx, dy, &format!("Supply: {} — {}", status.supply.label(), effect), ds, color); dy += line_hd;
+                        }
                         if dws.map_or(false, |s| s.defense_urgency > 0.1) {
                             let flash = (timer * 4.0).sin() * 0.3 + 0.7;
                             tb.add_text(dx, dy, "!! BUGS COUNTER-ATTACKING !!", ds, [1.0, 0.3 * flash, 0.1, flash]);
                         }
                     }
 
-                    let ctrl = format!("[↑/↓ or W/Q] System   [A/D] Planet   [1-5] Mission   [{}] Close   [{}] Deploy", INTERACT_KEY, DEPLOY_KEY);
+                    let ctrl = format!("[↑/↓ or W/Q] System   [A/D] Planet   [1-6] Mission   [Tab] Difficulty   [C] Class   [X] Heavy   [{}] Close   [{}] Deploy", INTERACT_KEY, DEPLOY_KEY);
                     let ctrl_w = ctrl.len() as f32 * 6.0 * 1.5;
                     tb.add_text(sw * 0.5 - ctrl_w * 0.5, by + bh - 20.0, &ctrl, 1.5, [0.5, 0.7, 1.0, 0.8]);
 
@@ -877,6 +790,18 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
             let hm_size = if hm.is_kill { 12.0 } else { 10.0 };
             tb.add_rect(cx - hm_size, cy - 1.0, hm_size * 2.0, 2.0, hm_color);
             tb.add_rect(cx - 1.0, cy - hm_size, 2.0, hm_size * 2.0, hm_color);
+            // Weakpoint: sharp gold X around the plus, stepped out of small squares
+            if hm.is_weakpoint {
+                let wp_color = [1.0, 0.8, 0.15, hm.lifetime / 0.3];
+                let reach = if hm.is_kill { 16.0 } else { 13.0 };
+                for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+                    let mut d = 6.0;
+                    while d <= reach {
+                        tb.add_rect(cx + sx * d - 1.0, cy + sy * d - 1.0, 2.0, 2.0, wp_color);
+                        d += 1.0;
+                    }
+                }
+            }
         }
 
         const SQUAD_NAMETAG_MAX_DIST: f32 = 25.0;
@@ -933,7 +858,32 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         let stamina_pct = state.player.stamina_percent();
         let sbar_y = hbar_y + hbar_h + 4.0;
         tb.add_rect(hbar_x - 1.0, sbar_y - 1.0, hbar_w + 2.0, 6.0, [0.2, 0.2, 0.2, 0.6]);
-        tb.add_rect(hbar_x, sbar_y, hbar_w * stamina_pct, 4.0, [0.3, 0.6, 1.0, 0.7]);
+        // Winded: bar turns orange until sprint unlocks again
+        let stamina_color = if state.player.stamina.winded { [1.0, 0.55, 0.2, 0.8] } else { [0.3, 0.6, 1.0, 0.7] };
+        tb.add_rect(hbar_x, sbar_y, hbar_w * stamina_pct, 4.0, stamina_color);
+
+        // Class ability (Q): name plus READY or recharge percent
+        let ability_text = if state.player.can_use_ability() {
+            format!("[Q] {}  READY", state.player.ability.name())
+        } else {
+            format!("[Q] {}  {:.0}%", state.player.ability.name(), state.player.ability_ready_percent() * 100.0)
+        };
+        let ability_color = if state.player.can_use_ability() { [0.4, 0.85, 1.0, 0.9] } else { gray };
+        tb.add_text(hbar_x, sbar_y + 10.0, &ability_text, 1.3, ability_color);
+
+        // Kill-streak buffs: what's active and how long it has left
+        let mut buff_x = hbar_x + 130.0;
+        let boost = state.kill_streaks.damage_boost_remaining();
+        if boost > 0.0 {
+            let text = format!("DMG x{:.1} {:.0}s", state.kill_streaks.damage_multiplier(), boost.ceil());
+            tb.add_text(buff_x, sbar_y + 10.0, &text, 1.3, [1.0, 0.45, 0.2, 0.95]);
+            buff_x += text.len() as f32 * 6.0 * 1.3 + 12.0;
+        }
+        let adrenaline = state.kill_streaks.adrenaline_remaining();
+        if adrenaline > 0.0 {
+            let text = format!("ADRENALINE {:.0}s", adrenaline.ceil());
+            tb.add_text(buff_x, sbar_y + 10.0, &text, 1.3, [1.0, 0.85, 0.3, 0.95]);
+        }
 
         let ammo_x = cx + 30.0;
         if state.player.is_shovel_equipped() {
@@ -976,35 +926,59 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         tb.add_text(ammo_x + 100.0, hbar_y + 36.0, &secondary_name, 1.3, slot2_color);
         tb.add_text(ammo_x + 200.0, hbar_y + 36.0, &tertiary_name, 1.3, slot3_color);
         tb.add_text(ammo_x + 300.0, hbar_y + 36.0, "[4] Shovel", 1.3, slot4_color);
+        let frag_text = format!("[G] Frag x{}", state.player.grenades);
+        let frag_color = if state.player.grenades > 0 { [0.75, 0.8, 0.55, 0.9] } else { [0.4, 0.4, 0.4, 0.6] };
+        tb.add_text(ammo_x + 400.0, hbar_y + 36.0, &frag_text, 1.3, frag_color);
 
-        let smoke_text = if state.smoke_grenade_cooldown > 0.0 {
-            format!("[G] SMOKE ({:.0}s)", state.smoke_grenade_cooldown)
-        } else {
-            "[G] SMOKE READY".to_string()
-        };
-        let smoke_color = if state.smoke_grenade_cooldown <= 0.0 {
-            [0.9, 0.3, 0.3, 1.0]
-        } else {
-            [0.5, 0.5, 0.5, 0.7]
-        };
-        tb.add_text_with_bg(ammo_x - 160.0, hbar_y + 4.0, &smoke_text, 1.3, smoke_color, bg);
+        // Stratagem tray (above the health bar): icon per stratagem, dark fill drains as it
+        // recharges, lit border when ready
+        if state.current_planet_idx.is_some() {
+            let slot = 34.0;
+            let gap = 8.0;
+            let tray_y = hbar_y - 70.0;
+            let pulse = (state.time.elapsed_seconds() * 3.0).sin() * 0.2 + 0.8;
+            let tray = state.hud.stratagem_tray(&state.stratagem_cooldowns, |s| state.stratagem_available(s));
+            for (i, entry) in tray.iter().enumerate() {
+                let sx = hbar_x + i as f32 * (slot + gap);
+                let color = entry.stratagem.color();
+                let dim = [color[0] * 0.45, color[1] * 0.45, color[2] * 0.45, 0.8];
+                tb.add_text(sx, tray_y - 12.0, entry.stratagem.hint(), 0.9, [0.7, 0.7, 0.7, 0.9]);
+                tb.add_rect(sx, tray_y, slot, slot, [0.03, 0.04, 0.06, 0.75]);
+                tb.add_text(sx + 6.0, tray_y + 8.0, entry.stratagem.icon(), 1.2, if entry.ready { color } else { dim });
+                if entry.cooldown_fraction > 0.0 {
+                    tb.add_rect(sx, tray_y, slot, slot * entry.cooldown_fraction, [0.0, 0.0, 0.0, 0.55]);
+                    tb.add_text(sx + 6.0, tray_y + 22.0, &format!("{:.0}s", entry.remaining), 0.9, [0.8, 0.8, 0.8, 1.0]);
+                }
+                if entry.ready {
+                    let edge = [color[0], color[1], color[2], pulse];
+                    tb.add_rect(sx, tray_y, slot, 2.0, edge);
+                    tb.add_rect(sx, tray_y + slot - 2.0, slot, 2.0, edge);
+                    tb.add_rect(sx, tray_y, 2.0, slot, edge);
+                    tb.add_rect(sx + slot - 2.0, tray_y, 2.0, slot, edge);
+                }
+            }
+        }
 
         let n = state.tac_fighters.len();
-        let cas_text = if n > 0 {
-            format!("CAS: {} ON STATION", n)
-        } else if state.tac_fighter_cooldown > 0.0 {
-            format!("[T] CAS ({:.0}s)", state.tac_fighter_cooldown)
-        } else {
-            "[T] TAC STRIKE READY".to_string()
-        };
-        let cas_color = if n > 0 {
-            [1.0, 0.6, 0.2, 1.0]
-        } else if state.tac_fighter_cooldown <= 0.0 {
-            [0.3, 0.9, 0.3, 1.0]
-        } else {
-            [0.5, 0.5, 0.5, 0.7]
-        };
-        tb.add_text_with_bg(ammo_x - 160.0, hbar_y + 20.0, &cas_text, 1.3, cas_color, bg);
+        if n > 0 {
+            let cas_text = format!("CAS: {} ON STATION", n);
+            tb.add_text_with_bg(ammo_x - 160.0, hbar_y + 20.0, &cas_text, 1.3, [1.0, 0.6, 0.2, 1.0], bg);
+        }
+        if let Some(fighter) = state.tac_fighters.iter().find(|f| f.is_piloted()) {
+            let speed = fighter.pilot().map_or(0.0, |p| p.speed());
+            let agl = fighter.position.y - state.chunk_manager.sample_height(fighter.position.x, fighter.position.z);
+            let pilot_text = format!(
+                "TAC FIGHTER  SPD {:.0}  ALT {:.0}m  BOMBS {}",
+                speed, agl, fighter.bombs_remaining
+            );
+            let pw = pilot_text.len() as f32 * 6.0 * 1.6;
+            tb.add_text_with_bg(sw * 0.5 - pw * 0.5, sh * 0.5 + 60.0, &pilot_text, 1.6, [1.0, 0.6, 0.2, 1.0], bg);
+            let help = "W/S throttle  MOUSE steer  LMB/SPACE bombs  T hand back";
+            let hw = help.len() as f32 * 6.0 * 1.1;
+            tb.add_text_with_bg(sw * 0.5 - hw * 0.5, sh * 0.5 + 84.0, help, 1.1, [0.8, 0.8, 0.8, 0.9], bg);
+        } else if state.tac_fighters.iter().any(|f| f.phase != TacFighterPhase::Departing && f.bombs_remaining > 0) {
+            tb.add_text_with_bg(ammo_x - 160.0, hbar_y + 40.0, "[T] TAKE THE STICK", 1.1, [1.0, 0.8, 0.4, 0.9], bg);
+        }
 
         let extract_text;
         let extract_color;
@@ -1013,8 +987,9 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                 ExtractionPhase::Called | ExtractionPhase::Inbound | ExtractionPhase::Landing => {
                     let eta = dropship.eta_to_touchdown();
                     let dist = dropship.distance_to_lz(state.player.position);
-                    extract_text = format!("[V] EXTRACT ETA:{:.0}s  LZ:{:.0}m", eta, dist);
-                    extract_color = [1.0, 0.8, 0.2, 1.0];
+                    let flash = (state.time.elapsed_seconds() * 3.0).sin() * 0.2 + 0.8;
+                    extract_text = format!("HOLD THE LZ! BOAT IN {:.0}s  LZ:{:.0}m", eta, dist);
+                    extract_color = [1.0, 0.8 * flash, 0.2, 1.0];
                 }
                 ExtractionPhase::Waiting => {
                     let remaining = dropship.time_until_dustoff();
@@ -1048,12 +1023,6 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                     extract_color = [0.3 * flash, 0.9 * flash, 1.0 * flash, 1.0];
                 }
             }
-        } else if state.extraction_cooldown > 0.0 {
-            extract_text = format!("[V] EXTRACT ({:.0}s)", state.extraction_cooldown);
-            extract_color = [0.5, 0.5, 0.5, 0.7];
-        } else if state.current_planet_idx.is_some() {
-            extract_text = "[V] CALL EXTRACTION".to_string();
-            extract_color = [0.3, 0.9, 0.3, 1.0];
         } else {
             extract_text = String::new();
             extract_color = [0.5, 0.5, 0.5, 0.5];
@@ -1069,23 +1038,11 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
                 let complete_y = obj_y + 18.0;
                 let pulse = (state.time.elapsed_seconds() * 2.0).sin() * 0.15 + 0.85;
                 tb.add_text_with_bg(ammo_x - 200.0, complete_y, "MISSION COMPLETE — Extract when ready!", 1.2, [0.2 * pulse, 1.0 * pulse, 0.3 * pulse, 1.0], bg);
+            } else if state.mission.is_failed {
+                tb.add_text_with_bg(ammo_x - 200.0, obj_y + 18.0, "OBJECTIVE FAILED — Extract and regroup", 1.2, [1.0, 0.3, 0.2, 1.0], bg);
             }
         }
 
-        let strat_y = hbar_y + 98.0;
-        let b_ready = state.tac_fighters.len() + 4 <= 8 && state.tac_fighter_available && state.tac_fighter_cooldown <= 0.0;
-        let strat_b = if b_ready { "[B] Orbital Strike" } else { "[B] Orbital Strike (cooldown)" };
-        let strat_b_color = if b_ready { [0.9, 0.6, 0.2, 0.9] } else { [0.5, 0.5, 0.5, 0.7] };
-        tb.add_text_with_bg(ammo_x - 160.0, strat_y, strat_b, 0.9, strat_b_color, bg);
-        let supply_ready = state.supply_drop_cooldown <= 0.0;
-        let strat_n: String = if supply_ready { "[N] Supply Drop".into() } else { format!("[N] Supply Drop ({:.0}s)", state.supply_drop_cooldown) };
-        let strat_n_color = if supply_ready { [0.2, 0.8, 0.4, 0.9] } else { [0.5, 0.5, 0.5, 0.7] };
-        tb.add_text_with_bg(ammo_x - 160.0, strat_y + 16.0, &strat_n, 0.9, strat_n_color, bg);
-        let reinforce_ready = state.reinforce_cooldown <= 0.0;
-        let strat_r: String = if reinforce_ready { "[R] Reinforce".into() } else { format!("[R] Reinforce ({:.0}s)", state.reinforce_cooldown) };
-        let strat_r_color = if reinforce_ready { [0.9, 0.5, 0.2, 0.9] } else { [0.5, 0.5, 0.5, 0.7] };
-        tb.add_text_with_bg(ammo_x - 160.0, strat_y + 32.0, &strat_r, 0.9, strat_r_color, bg);
-
         let mut kf_y = 60.0;
         for kf in state.combat.kill_feed.iter().rev().take(5) {
             let alpha = (kf.lifetime / 5.0).min(1.0);
@@ -1104,6 +1061,185 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
             kf_y += line_h * 0.8;
         }
 
+        // Artillery impact countdowns over each predicted landing point
+        if !state.artillery_impacts.is_empty() {
+            let view_proj = state.camera.view_projection_matrix();
+            let layout = TextLayout::new(1.6).align(TextAlign::Center);
+            for impact in &state.artillery_impacts {
+                let p = impact.position + Vec3::Y * 2.0;
+                let clip = view_proj * glam::Vec4::new(p.x, p.y, p.z, 1.0);
+                if clip.w <= 0.01 || (clip.z / clip.w) > 1.0 {
+                    continue;
+                }
+                let sx = (clip.x / clip.w + 1.0) * 0.5 * sw;
+                let sy = (1.0 - clip.y / clip.w) * 0.5 * sh;
+                let text = format!("{:.1}s", impact.time_to_impact);
+                tb.add_text_layout_with_bg(sx, sy, &text, &layout, [1.0, 0.3, 0.15, 1.0], [0.0, 0.0, 0.0, 0.45]);
+            }
+        }
+
+        // Grenade in hand: dotted throw arc, landing marker with the fuse left
+        if let (Some(points), Some(cook)) = (state.grenade_preview(), state.grenade_cook) {
+            let view_proj = state.camera.view_projection_matrix();
+            let fuse = crate::grenade::cooked_fuse(cook);
+            let arc_color = if fuse < 1.0 { [1.0, 0.3, 0.15, 0.85] } else { [1.0, 0.85, 0.3, 0.75] };
+            let project = |p: Vec3| {
+                let clip = view_proj * glam::Vec4::new(p.x, p.y, p.z, 1.0);
+                if clip.w <= 0.01 || (clip.z / clip.w) > 1.0 {
+                    return None;
+                }
+                Some(((clip.x / clip.w + 1.0) * 0.5 * sw, (1.0 - clip.y / clip.w) * 0.5 * sh))
+            };
+            for p in points.iter().skip(2).step_by(2) {
+                if let Some((sx, sy)) = project(*p) {
+                    tb.add_rect(sx - 1.5, sy - 1.5, 3.0, 3.0, arc_color);
+                }
+            }
+            if let Some((sx, sy)) = points.last().and_then(|p| project(*p)) {
+                tb.add_rect(sx - 5.0, sy - 1.0, 10.0, 2.0, arc_color);
+                tb.add_rect(sx - 1.0, sy - 5.0, 2.0, 10.0, arc_color);
+            }
+            let text = format!("FUSE {:.1}s", fuse);
+            let layout = TextLayout::new(1.8).align(TextAlign::Center);
+            tb.add_text_layout_with_bg(cx, cy + 70.0, &text, &layout, arc_color, [0.0, 0.0, 0.0, 0.45]);
+        }
+
+        // Stratagem code entry (left-center): entered arrows lit, panel flashes red on a wrong input
+        if let Some(capture) = state.stratagem_input.capture() {
+            let code = capture.stratagem.code();
+            let px = 20.0;
+            let py = sh * 0.42;
+            let arrow_scale = 3.0;
+            let arrow_w = 6.0 * arrow_scale + 8.0;
+            let pw = (code.len() as f32 * arrow_w).max(capture.stratagem.name().len() as f32 * 6.0 * 1.8) + 16.0;
+            let flash = capture.mistake_timer / crate::stratagem::MISTAKE_FLASH;
+            tb.add_rect(px, py, pw, 58.0, [0.02 + flash * 0.5, 0.03, 0.06, 0.75]);
+            tb.add_rect(px, py, 2.0, 58.0, [1.0, 0.85, 0.2, 0.9]);
+            tb.add_text(px + 8.0, py + 6.0, capture.stratagem.name(), 1.8, [1.0, 0.85, 0.2, 1.0]);
+            for (i, dir) in code.iter().enumerate() {
+                let color = if i < capture.progress {
+                    [1.0, 0.85, 0.2, 1.0]
+                } else {
+                    [0.5, 0.5, 0.55, 0.9]
+                };
+                tb.add_text(px + 8.0 + i as f32 * arrow_w, py + 26.0, dir.arrow(), arrow_scale, color);
+            }
+        }
+
+        // Compass (top-center): cardinal headings from camera yaw, waypoints ride the strip
+        {
+            let comp_w = 360.0;
+            let comp_x = cx - comp_w * 0.5;
+            let comp_y = 8.0;
+            let half_span = std::f32::consts::FRAC_PI_2;
+            let heading = compass_heading(state.camera.forward());
+            tb.add_rect(comp_x, comp_y, comp_w, 18.0, [0.0, 0.0, 0.0, 0.45]);
+            for step in 0..24 {
+                let bearing = step as f32 * std::f32::consts::TAU / 24.0;
+                let Some(off) = compass_offset(heading, bearing, half_span) else { continue };
+                let tx = cx + off * comp_w * 0.5;
+                let fade = 1.0 - off.abs() * 0.6;
+                const CARDINALS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+                if step % 3 == 0 {
+                    let label = CARDINALS[step / 3];
+                    let label_color = if step == 0 { [1.0, 0.35, 0.25, fade] } else { [0.9, 0.9, 0.9, fade] };
+                    tb.add_text_layout(tx, comp_y + 5.0, label, &TextLayout::new(1.2).align(TextAlign::Center), label_color);
+                } else {
+                    tb.add_rect(tx - 0.5, comp_y + 11.0, 1.0, 6.0, [0.8, 0.8, 0.8, fade * 0.7]);
+                }
+            }
+            tb.add_rect(cx - 1.0, comp_y + 18.0, 2.0, 5.0, white);
+
+            let markers = state.hud.project_waypoints(
+                state.camera.view_projection_matrix(),
+                state.camera.position(),
+                sw,
+                sh,
+            );
+            for m in &markers {
+                let color = m.icon.color();
+                // Pip on the compass strip (pinned to the nearer end when outside it)
+                let off = compass_offset(heading, m.bearing, half_span).unwrap_or_else(|| {
+                    let d = (m.bearing - heading).rem_euclid(std::f32::consts::TAU);
+                    if d < std::f32::consts::PI { 1.0 } else { -1.0 }
+                });
+                tb.add_rect(cx + off * comp_w * 0.5 - 3.0, comp_y + 20.0, 6.0, 6.0, color);
+
+                // World marker: label + distance, clamped to the screen edge when off-screen
+                let text = format!("{} {:.0}m", m.icon.label(), m.distance);
+                let layout = TextLayout::new(1.3).align(TextAlign::Center);
+                let alpha = if m.on_screen { 0.9 } else { 0.7 };
+                tb.add_rect(m.screen_x - 3.0, m.screen_y - 3.0, 6.0, 6.0, [color[0], color[1], color[2], alpha]);
+                tb.add_text_layout_with_bg(
+                    m.screen_x,
+                    m.screen_y + 6.0,
+                    &text,
+                    &layout,
+                    [color[0], color[1], color[2], alpha],
+                    [0.0, 0.0, 0.0, 0.4],
+                );
+            }
+        }
+
+        // Boss bar (top-center, under the compass): name, phase tag, health draining left to right
+        if let Some(boss) = state.hud.boss_bar(&state.world) {
+            let bar_w = (sw * 0.4).min(520.0);
+            let bar_x = cx - bar_w * 0.5;
+            let bar_y = 58.0;
+            let title = format!("{}  [{}]", boss.name, boss.phase);
+            tb.add_text_layout(cx, bar_y - 16.0, &title, &TextLayout::new(1.5).align(TextAlign::Center), boss.color);
+            tb.add_rect(bar_x - 2.0, bar_y - 2.0, bar_w + 4.0, 14.0, [0.0, 0.0, 0.0, 0.6]);
+            tb.add_rect(bar_x, bar_y, bar_w * boss.health_fraction, 10.0, boss.color);
+            // Rage threshold tick
+            tb.add_rect(bar_x + bar_w * crate::boss::RAGE_THRESHOLD - 1.0, bar_y - 2.0, 2.0, 14.0, white);
+        }
+
+        // Minimap (bottom-right): rotates with facing; enemies red, squad green, LZ yellow, bug holes orange
+        if state.hud.config.show_minimap {
+            let mm_r = (sh * 0.12).clamp(60.0, 110.0);
+            let mm_cx = sw - mm_r - 20.0;
+            let mm_cy = sh - mm_r - 80.0;
+            let strips = 32;
+            let strip_h = 2.0 * mm_r / strips as f32;
+            for i in 0..strips {
+                let y_mid = -1.0 + (i as f32 + 0.5) * 2.0 / strips as f32;
+                let half_w = (1.0 - y_mid * y_mid).max(0.0).sqrt() * mm_r;
+                tb.add_rect(mm_cx - half_w, mm_cy - mm_r + i as f32 * strip_h, half_w * 2.0, strip_h, [0.02, 0.06, 0.04, 0.55]);
+            }
+            let rim_color = [tactical_green[0], tactical_green[1], tactical_green[2], 0.5];
+            for i in 0..48 {
+                let a = i as f32 / 48.0 * std::f32::consts::TAU;
+                tb.add_rect(mm_cx + a.cos() * mm_r - 1.0, mm_cy + a.sin() * mm_r - 1.0, 2.0, 2.0, rim_color);
+                if i % 2 == 0 {
+                    let half = mm_r * 0.5;
+                    tb.add_rect(mm_cx + a.cos() * half - 0.5, mm_cy + a.sin() * half - 0.5, 1.0, 1.0, rim_color);
+                }
+            }
+
+            let fwd = state.camera.forward();
+            let lz = state.extraction.as_ref().map(|d| d.lz_position);
+            let pulse = (state.time.elapsed_seconds() * 4.0).sin() * 0.25 + 0.75;
+            for blip in state.hud.minimap_blips(&state.world, state.player.position, fwd, lz) {
+                let (size, rgb) = match blip.kind {
+                    MinimapBlipKind::Enemy => (3.0, [1.0, 0.2, 0.15]),
+                    MinimapBlipKind::Squad => (4.0, [0.3, 1.0, 0.4]),
+                    MinimapBlipKind::BugHole => (6.0, [1.0, 0.55, 0.1]),
+                    MinimapBlipKind::Extraction => (7.0 * pulse, [1.0, 0.9, 0.2]),
+                };
+                let bx = mm_cx + blip.x * mm_r;
+                let by = mm_cy + blip.y * mm_r;
+                tb.add_rect(bx - size * 0.5, by - size * 0.5, size, size, [rgb[0], rgb[1], rgb[2], blip.alpha]);
+            }
+
+            // Player chevron (always points up: the map turns, not the player)
+            tb.add_rect(mm_cx - 1.0, mm_cy - 5.0, 2.0, 3.0, white);
+            tb.add_rect(mm_cx - 3.0, mm_cy - 2.0, 6.0, 3.0, white);
+            tb.add_rect(mm_cx - 5.0, mm_cy + 1.0, 10.0, 3.0, white);
+            if let Some((nx, ny, _)) = minimap_project(Vec3::ZERO, fwd, Vec3::NEG_Z, 1.0, true) {
+                tb.add_text(mm_cx + nx * (mm_r + 8.0) - 3.0, mm_cy + ny * (mm_r + 8.0) - 4.0, "N", 1.0, gray);
+            }
+        }
+
         if state.player.last_damage_time < 0.5 {
             let hit_alpha = (1.0 - state.player.last_damage_time * 2.0).max(0.0) * 0.4;
             let border = 40.0;
@@ -1193,6 +1329,13 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         }
     }
 
+    // ---- Look-target nametag (ship crew or Earth citizen under the crosshair) ----
+    if !state.dialogue_state.is_open() && matches!(state.phase, GamePhase::InShip | GamePhase::Playing) {
+        if let Some(InteractionTarget::Speaker { name, position, .. }) = &state.player.interaction_target {
+            draw_nametag(&mut tb, state, sw, sh, *position + Vec3::Y * 1.6, name, [1.0, 0.85, 0.3, 1.0]);
+        }
+    }
+
     // ---- Interaction prompt (Playing: near citizen; same style as ship war table / talk) ----
     if state.phase == GamePhase::Playing
         && !state.dialogue_state.is_open()
@@ -1212,28 +1355,25 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
 
     // ---- Dialogue box (Earth settlement — Starship Troopers style) ----
     if state.dialogue_state.is_open() {
-        if let Some((line_text, choices)) = state.dialogue_state.current_line_and_choices() {
+        if let Some((line_text, choices)) = state.dialogue_state.current_line_and_choices(&state.dialogue_memory) {
             let speaker_name = match &state.dialogue_state {
                 crate::dialogue::DialogueState::Open { speaker_name, .. } => speaker_name.as_str(),
                 _ => "",
             };
             let box_w = (line_text.len() as f32 * 6.0 * 1.2).max(280.0).min(sw - 40.0);
-            let box_h = 24.0 + 28.0 + (choices.len() as f32 * 18.0);
+            let line_layout = TextLayout::new(1.1).wrap(box_w - 12.0);
+            let (_, text_h) = OverlayTextBuilder::measure_text(&line_text, &line_layout);
+            let choices_y = 24.0 + text_h + 12.0;
+            let box_h = choices_y + 8.0 + (choices.len() as f32 * 18.0);
             let box_x = sw * 0.5 - box_w * 0.5;
             let box_y = sh - box_h - 24.0;
             tb.add_rect(box_x - 4.0, box_y - 4.0, box_w + 8.0, box_h + 8.0, [0.06, 0.08, 0.12, 0.92]);
             tb.add_rect(box_x, box_y, box_w, 20.0, [0.25, 0.35, 0.45, 0.95]);
             tb.add_text(box_x + 6.0, box_y + 2.0, &format!("{}", speaker_name), 1.4, [0.9, 0.85, 0.7, 1.0]);
-            let max_chars = 70;
-            let line_trim: String = if line_text.chars().count() > max_chars {
-                line_text.chars().take(max_chars).chain("...".chars()).collect()
-            } else {
-                line_text.clone()
-            };
-            tb.add_text(box_x + 6.0, box_y + 24.0, &line_trim, 1.1, [0.85, 0.88, 0.9, 1.0]);
-            for (i, (choice_label, _)) in choices.iter().enumerate() {
+            tb.add_text_layout(box_x + 6.0, box_y + 24.0, &line_text, &line_layout, [0.85, 0.88, 0.9, 1.0]);
+            for (i, choice_label) in choices.iter().enumerate() {
                 let key = (i + 1).to_string();
-                tb.add_text(box_x + 6.0, box_y + 44.0 + i as f32 * 18.0, &format!("[{}] {}", key, choice_label), 1.0, [0.5, 0.75, 1.0, 1.0]);
+                tb.add_text(box_x + 6.0, box_y + choices_y + i as f32 * 18.0, &format!("[{}] {}", key, choice_label), 1.0, [0.5, 0.75, 1.0, 1.0]);
             }
             let dialogue_prompt = format!("{} = choose  {} = close", DIALOGUE_CHOICE_KEYS, DIALOGUE_CLOSE_KEY);
             let dpw = dialogue_prompt.len() as f32 * 6.0 * 1.0;
@@ -1241,14 +1381,18 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         }
     }
 
-    // ---- Bottom-left: game messages ----
+    // ---- Bottom-left: game messages (long bulletins wrap instead of running off screen) ----
     let visible: Vec<&GameMessage> = state.game_messages.messages.iter()
         .rev()
         .take(state.game_messages.max_visible)
         .collect();
-    let msg_count = visible.len();
-    let msg_base_y = sh - 20.0 - (msg_count as f32 * line_h);
-    for (i, msg) in visible.iter().rev().enumerate() {
+    let msg_layout = TextLayout::new(scale).wrap((sw * 0.6 - x).max(120.0));
+    // Each line advances by line_h, so a wrapped message takes one line_h per wrapped line
+    let msg_heights: Vec<f32> = visible.iter()
+        .map(|msg| OverlayTextBuilder::measure_text(&msg.text, &msg_layout).1 + (line_h - 8.0 * scale))
+        .collect();
+    let mut msg_y = sh - 20.0 - msg_heights.iter().sum::<f32>();
+    for (msg, h) in visible.iter().rev().zip(msg_heights.iter().rev()) {
         let alpha = if msg.time_remaining < 1.0 {
             msg.time_remaining
         } else {
@@ -1258,8 +1402,120 @@ pub fn build(state: &GameState, sw: f32, sh: f32) -> OverlayTextBuilder {
         color[3] *= alpha;
         let mut msg_bg = bg;
         msg_bg[3] *= alpha;
-        tb.add_text_with_bg(x, msg_base_y + i as f32 * line_h, &msg.text, scale, color, msg_bg);
+        tb.add_text_layout_with_bg(x, msg_y, &msg.text, &msg_layout, color, msg_bg);
+        msg_y += h;
     }
 
     tb
 }
+
+/// Galaxy map overlay: star labels, reticle, the filtered/sorted system list and the selection readout.
+fn draw_galaxy_map(tb: &mut OverlayTextBuilder, state: &GameState, sw: f32, sh: f32) {
+    let bg = [0.0, 0.0, 0.0, 0.55];
+    let gray = [0.7, 0.7, 0.7, 1.0];
+    let yellow = [1.0, 0.9, 0.3, 1.0];
+    let green = [0.3, 1.0, 0.4, 1.0];
+    let map = &state.galaxy_map;
+    let current = state.current_system_idx;
+    let selected = state.galaxy_map_selected;
+    let origin = state.universe.systems[current].position;
+    let view_proj = map.camera(sw / sh.max(1.0)).view_projection_matrix();
+
+    let title = format!(
+        "GALAXY MAP — {} systems | Filter: {} [F] | Sort: {} [Tab]",
+        state.universe.systems.len(),
+        map.filter.label(),
+        map.sort.label()
+    );
+    tb.add_text_with_bg(sw * 0.02, sh * 0.03, &title, 2.0, [0.6, 0.8, 1.0, 1.0], bg);
+
+    // Labels and markers on the stars themselves (charted, current and selected only, to keep it readable)
+    for (i, entry) in state.universe.systems.iter().enumerate() {
+        if !(i == current || i == selected || entry.visited) || !map.shows(&state.universe, i, current) {
+            continue;
+        }
+        let Some(p) = galaxy_map::project(view_proj, entry.position.as_vec3(), sw, sh) else { continue };
+        let marker = if i == selected { Some(yellow) } else if i == current { Some(green) } else { None };
+        if let Some(mut color) = marker {
+            color[3] = 0.5;
+            tb.add_rect(p.x - 9.0, p.y - 9.0, 18.0, 2.0, color);
+            tb.add_rect(p.x - 9.0, p.y + 7.0, 18.0, 2.0, color);
+            tb.add_rect(p.x - 9.0, p.y - 9.0, 2.0, 18.0, color);
+            tb.add_rect(p.x + 7.0, p.y - 9.0, 2.0, 18.0, color);
+        }
+        tb.add_text(p.x + 12.0, p.y - 4.0, &entry.name, 1.5, galaxy_map::star_color(entry.star_type));
+    }
+
+    // Reticle: corner brackets around the pick radius at screen center
+    let r = galaxy_map::RETICLE_RADIUS * sh;
+    let (cx, cy) = (sw * 0.5, sh * 0.5);
+    let reticle = [0.6, 0.8, 1.0, 0.6];
+    for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+        let (x, y) = (cx + dx * r, cy + dy * r);
+        tb.add_rect(x.min(x - dx * r * 0.3), y - 1.0, r * 0.3, 2.0, reticle);
+        tb.add_rect(x - 1.0, y.min(y - dy * r * 0.3), 2.0, r * 0.3, reticle);
+    }
+
+    // System list (right side), in filter/sort order
+    let list = map.listing(&state.universe, current);
+    let list_x = sw * 0.74;
+    let list_y = sh * 0.1;
+    let row_h = 16.0;
+    let rows = ((sh * 0.62 / row_h) as usize).max(1);
+    // Scroll so the selected system stays in view
+    let sel_pos = list.iter().position(|&i| i == selected).unwrap_or(0);
+    let first = sel_pos.saturating_sub(rows / 2).min(list.len().saturating_sub(rows));
+    tb.add_rect(list_x - 8.0, list_y - 8.0, sw * 0.25, rows as f32 * row_h + 28.0, [0.0, 0.0, 0.05, 0.7]);
+    tb.add_text(list_x, list_y, &format!("{} shown — N = next", list.len()), 1.2, gray);
+    for (row, &i) in list.iter().skip(first).take(rows).enumerate() {
+        let entry = &state.universe.systems[i];
+        let status = if i == current { "HERE" } else if entry.visited { "CHARTED" } else { "UNCHARTED" };
+        let line = format!("{:<14} {:>5.0} ly  {}", entry.name, entry.position.distance(origin), status);
+        let color = if i == selected { yellow } else if i == current { green } else { gray };
+        tb.add_text(list_x, list_y + 20.0 + row as f32 * row_h, &line, 1.2, color);
+    }
+
+    // Selection readout and controls
+    let entry = &state.universe.systems[selected];
+    let status = if selected == current {
+        let total_lib: f32 = state.war_state.planets.iter().map(|p| p.liberation).sum();
+        let avg_lib = if state.war_state.planets.is_empty() { 0.0 } else { total_lib / state.war_state.planets.len() as f32 };
+        format!("CURRENT SYSTEM — sector liberation {:.0}%", avg_lib * 100.0)
+    } else if entry.visited {
+        "CHARTED".to_string()
+    } else {
+        "UNCHARTED".to_string()
+    };
+    let sel_info = format!(
+        "Selected: {} ({:?}) | {:.0} ly | {}",
+        entry.name,
+        entry.star_type,
+        entry.position.distance(origin),
+        status
+    );
+    tb.add_text_with_bg(sw * 0.02, sh * 0.85, &sel_info, 2.0, yellow, bg);
+    let line_h = 20.0;
+    if state.phase == GamePhase::MainMenu {
+        tb.add_text_with_bg(sw * 0.02, sh * 0.85 + line_h, "Enter = Travel to system & board Roger Young | M / Esc = Back", 2.0, gray, bg);
+    } else if state.current_planet_idx.is_some() {
+        tb.add_text_with_bg(sw * 0.02, sh * 0.85 + line_h, "WARP DRIVE OFFLINE — return to orbit to warp | M / Esc = close", 2.0, [1.0, 0.5, 0.3, 1.0], bg);
+    } else {
+        tb.add_text_with_bg(sw * 0.02, sh * 0.85 + line_h, "Enter = Warp | M / Esc = close", 2.0, gray, bg);
+    }
+    let nav = if state.input.is_cursor_locked() { "Mouse/arrows = orbit" } else { "Drag/arrows = orbit" };
+    let nav_text = format!("{} | Scroll/+- = zoom | WASD = pan | C = recenter", nav);
+    tb.add_text_with_bg(sw * 0.02, sh * 0.85 + line_h * 2.0, &nav_text, 2.0, gray, bg);
+}
+
+/// Project a world-space head position and draw a centered name tag 24 px above it (skipped when off-screen).
+fn draw_nametag(tb: &mut OverlayTextBuilder, state: &GameState, sw: f32, sh: f32, head_pos: Vec3, name: &str, color: [f32; 4]) {
+    let clip = state.camera.view_projection_matrix() * glam::Vec4::new(head_pos.x, head_pos.y, head_pos.z, 1.0);
+    if clip.w <= 0.01 || clip.z / clip.w > 1.0 {
+        return;
+    }
+    let sx = (clip.x / clip.w + 1.0) * 0.5 * sw;
+    let sy = (1.0 - clip.y / clip.w) * 0.5 * sh;
+    let scale = 1.5;
+    let tw = name.len() as f32 * 6.0 * scale * 0.5;
+    tb.add_text_with_bg(sx - tw, sy - 24.0, name, scale, color, [0.0, 0.0, 0.0, 0.6]);
+}
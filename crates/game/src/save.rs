@@ -0,0 +1,178 @@
+//! Galactic war save file: versioned RON, migrated forward on load.
+//!
+//! The save stores only the universe seed plus war state, so a change to universe generation
+//! (see `Universe::digest`) also needs a `SAVE_VERSION` bump and a migration step.
+
+use crate::difficulty::Difficulty;
+use crate::weapons::WeaponType;
+use crate::{GalacticWarState, OrderReward};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Current save format. v0 = unversioned saves from before this field existed.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Persisted galactic war + universe (save file).
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SaveData {
+    /// Format version; missing in unversioned saves, which read as 0.
+    #[serde(default)]
+    pub version: u32,
+    pub universe_seed: u64,
+    pub current_system_idx: usize,
+    pub war_state: GalacticWarState,
+    /// Missing in saves from before difficulty presets; those load as Normal.
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// Major Order rewards earned across all systems; missing in saves from before rewards.
+    #[serde(default)]
+    pub granted_rewards: Vec<OrderReward>,
+    /// Heavy weapon picked at the war table; missing in older saves, which load with the Morita MG.
+    #[serde(default = "default_heavy_weapon")]
+    pub heavy_weapon: WeaponType,
+}
+
+fn default_heavy_weapon() -> WeaponType {
+    WeaponType::MachineGun
+}
+
+/// Why a save file couldn't be loaded.
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    /// Written by a newer build than this one.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "could not read save: {}", e),
+            SaveError::Parse(e) => write!(f, "save is corrupt or from an incompatible build: {}", e),
+            SaveError::UnsupportedVersion(v) => write!(
+                f,
+                "save version {} is newer than this build supports (max {})",
+                v, SAVE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Just the version, read before the full parse so newer saves are reported as such
+/// instead of as parse errors.
+#[derive(serde::Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    version: u32,
+}
+
+pub fn save_path() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")).join("opensst_save.ron")
+}
+
+pub fn save(
+    universe_seed: u64,
+    current_system_idx: usize,
+    war_state: &GalacticWarState,
+    difficulty: Difficulty,
+    granted_rewards: &[OrderReward],
+    heavy_weapon: WeaponType,
+) {
+    let data = SaveData {
+        version: SAVE_VERSION,
+        universe_seed,
+        current_system_idx,
+        war_state: war_state.clone(),
+        difficulty,
+        granted_rewards: granted_rewards.to_vec(),
+        heavy_weapon,
+    };
+    let path = save_path();
+    match ron::ser::to_string_pretty(&data, ron::ser::PrettyConfig::default()) {
+        Ok(s) => {
+            if let Err(e) = std::fs::write(&path, s) {
+                log::warn!("Failed to save galactic war: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize galactic war: {}", e),
+    }
+}
+
+/// Load the save if there is one. `Ok(None)` means no save file exists.
+pub fn load() -> Result<Option<SaveData>, SaveError> {
+    load_from(&save_path())
+}
+
+fn load_from(path: &Path) -> Result<Option<SaveData>, SaveError> {
+    let s = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(SaveError::Io(e)),
+    };
+    parse(&s).map(Some)
+}
+
+/// Parse and migrate save text to the current version.
+pub fn parse(s: &str) -> Result<SaveData, SaveError> {
+    let probe: VersionProbe = ron::from_str(s).map_err(SaveError::Parse)?;
+    if probe.version > SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(probe.version));
+    }
+    let data: SaveData = ron::from_str(s).map_err(SaveError::Parse)?;
+    Ok(migrate(data))
+}
+
+/// Upgrade `data` one version at a time until it is `SAVE_VERSION`.
+fn migrate(mut data: SaveData) -> SaveData {
+    if data.version == 0 {
+        // v0 -> v1: only the version field was added
+        data.version = 1;
+    }
+    data
+}
+
+/// Keep a copy of a save that failed to load, so the next autosave doesn't destroy it.
+pub fn back_up_rejected() -> Option<PathBuf> {
+    let path = save_path();
+    let backup = path.with_extension("ron.rejected");
+    std::fs::copy(&path, &backup).ok().map(|_| backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_core::GameRng;
+
+    #[test]
+    fn unversioned_saves_migrate_and_newer_ones_are_rejected() {
+        let war = GalacticWarState::new(3, &mut GameRng::new(1));
+        let current = ron::to_string(&SaveData {
+            version: SAVE_VERSION,
+            universe_seed: 77,
+            current_system_idx: 2,
+            war_state: war,
+            difficulty: Difficulty::Hard,
+            granted_rewards: vec![OrderReward::StratagemClearance(crate::Stratagem::OrbitalStrike)],
+            heavy_weapon: WeaponType::Flamethrower,
+        })
+        .unwrap();
+
+        let v0 = current.replacen(&format!("version:{},", SAVE_VERSION), "", 1);
+        assert!(!v0.contains("version"));
+        let data = parse(&v0).expect("unversioned save loads");
+        assert_eq!((data.version, data.universe_seed, data.current_system_idx), (SAVE_VERSION, 77, 2));
+        assert_eq!(data.difficulty, Difficulty::Hard);
+        assert_eq!(data.granted_rewards, vec![OrderReward::StratagemClearance(crate::Stratagem::OrbitalStrike)]);
+        assert_eq!(data.heavy_weapon, WeaponType::Flamethrower);
+
+        let before_heavy = current.replacen("heavy_weapon:Flamethrower", "", 1);
+        assert_eq!(parse(&before_heavy).expect("save without a heavy weapon loads").heavy_weapon, WeaponType::MachineGun);
+
+        let future = current.replacen(&format!("version:{}", SAVE_VERSION), "version:999", 1);
+        assert!(matches!(parse(&future), Err(SaveError::UnsupportedVersion(999))));
+        assert!(matches!(parse("not a save"), Err(SaveError::Parse(_))));
+    }
+}
@@ -1,7 +1,17 @@
 //! Skinny enemies (Heinlein Starship Troopers): humanoid aliens on some planets.
+//!
+//! Unlike bugs, Skinnies carry guns: they hold a range band around the trooper, sidestep to
+//! regain line of sight, back off when rushed, and fire led bolts (`SkinnyBolt`).
 
 use engine_core::{AIComponent, Health, Transform, Vec3};
 
+/// Seconds between a Skinny's line-of-sight raycasts (staggered per Skinny).
+pub const SIGHT_CHECK_INTERVAL: f32 = 0.3;
+/// A bolt passing within this distance (m) of the trooper's chest hits.
+pub const BOLT_HIT_RADIUS: f32 = 0.6;
+/// Bolts expire after this many seconds of flight.
+pub const BOLT_LIFETIME: f32 = 2.5;
+
 /// Skinny type — different stats and behavior (ranged vs melee).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SkinnyType {
@@ -38,6 +48,60 @@ impl SkinnyType {
         }
     }
 
+    /// Distance band (m) the Skinny tries to hold from the trooper; it advances beyond the far edge.
+    pub fn preferred_range(&self) -> (f32, f32) {
+        match self {
+            SkinnyType::Grunt => (12.0, 24.0),
+            SkinnyType::Sniper => (38.0, 60.0),
+            SkinnyType::Officer => (9.0, 18.0),
+        }
+    }
+
+    /// Closer than this (m) the Skinny breaks off and backpedals out of melee reach.
+    pub fn retreat_range(&self) -> f32 {
+        match self {
+            SkinnyType::Grunt => 7.0,
+            SkinnyType::Sniper => 22.0,
+            SkinnyType::Officer => 4.5,
+        }
+    }
+
+    /// Maximum distance (m) the Skinny will open fire from.
+    pub fn fire_range(&self) -> f32 {
+        match self {
+            SkinnyType::Grunt => 32.0,
+            SkinnyType::Sniper => 80.0,
+            SkinnyType::Officer => 26.0,
+        }
+    }
+
+    /// Seconds between shots.
+    pub fn fire_cooldown(&self) -> f32 {
+        match self {
+            SkinnyType::Grunt => 1.1,
+            SkinnyType::Sniper => 3.0,
+            SkinnyType::Officer => 0.8,
+        }
+    }
+
+    /// Bolt muzzle speed (m/s); the sniper round is fast enough to feel near-hitscan.
+    pub fn bolt_speed(&self) -> f32 {
+        match self {
+            SkinnyType::Grunt => 55.0,
+            SkinnyType::Sniper => 160.0,
+            SkinnyType::Officer => 65.0,
+        }
+    }
+
+    /// Random aim error (radians, half-angle) added on top of the led aim.
+    pub fn aim_spread(&self) -> f32 {
+        match self {
+            SkinnyType::Grunt => 0.035,
+            SkinnyType::Sniper => 0.006,
+            SkinnyType::Officer => 0.025,
+        }
+    }
+
     /// Scale for rendering (Heinlein: tall, gaunt — mesh is already elongated; scale preserves silhouette).
     pub fn scale(&self) -> Vec3 {
         match self {
@@ -66,12 +130,31 @@ impl SkinnyType {
     }
 }
 
+/// What a ranged Skinny wants to do about its distance to the trooper this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangedIntent {
+    /// Too far (or out of sight at range): close in along the flow field.
+    Approach,
+    /// Inside its band with a clear shot: stand and fire.
+    Hold,
+    /// Sidestep around cover to regain line of sight.
+    Flank,
+    /// Too close: back away from the trooper.
+    Retreat,
+}
+
 /// Skinny enemy component (like Bug but for humanoid aliens).
 #[derive(Debug, Clone)]
 pub struct Skinny {
     pub skinny_type: SkinnyType,
     pub attack_damage: f32,
     pub move_speed: f32,
+    /// Clear shot to the trooper at the last sight check.
+    pub has_line_of_sight: bool,
+    /// Counts down to the next sight check.
+    pub sight_timer: f32,
+    /// Which way (+1 right / -1 left) the Skinny sidesteps when flanking; flips when it loses sight again.
+    pub strafe_sign: f32,
 }
 
 impl Skinny {
@@ -80,10 +163,129 @@ impl Skinny {
             attack_damage: skinny_type.attack_damage(),
             move_speed: skinny_type.move_speed(),
             skinny_type,
+            has_line_of_sight: false,
+            sight_timer: 0.0,
+            strafe_sign: 1.0,
         }
     }
 
     pub fn effective_health(&self) -> f32 {
         self.skinny_type.health()
     }
+
+    /// Pick movement for a Skinny `distance` metres from the trooper.
+    pub fn ranged_intent(&self, distance: f32) -> RangedIntent {
+        let (_, far) = self.skinny_type.preferred_range();
+        if distance < self.skinny_type.retreat_range() {
+            RangedIntent::Retreat
+        } else if distance > far {
+            RangedIntent::Approach
+        } else if !self.has_line_of_sight {
+            RangedIntent::Flank
+        } else {
+            RangedIntent::Hold
+        }
+    }
+
+    /// Record a sight check; losing sight flips the flank direction so it tries the other side.
+    pub fn set_line_of_sight(&mut self, visible: bool) {
+        if self.has_line_of_sight && !visible {
+            self.strafe_sign = -self.strafe_sign;
+        }
+        self.has_line_of_sight = visible;
+    }
+}
+
+/// A Skinny round in flight. Hits the trooper (or a deployed shield) or stops on terrain and cover.
+#[derive(Debug, Clone)]
+pub struct SkinnyBolt {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub damage: f32,
+    pub lifetime: f32,
+}
+
+/// Direction to fire a `speed` m/s bolt from `muzzle` so it meets a target at `target` moving at
+/// `target_velocity`. Falls back to aiming straight at the target when no intercept exists.
+pub fn lead_direction(muzzle: Vec3, target: Vec3, target_velocity: Vec3, speed: f32) -> Vec3 {
+    let to_target = target - muzzle;
+    // |to_target + v t| = speed t  =>  (v·v - s²) t² + 2 (to_target·v) t + to_target·to_target = 0
+    let a = target_velocity.length_squared() - speed * speed;
+    let b = 2.0 * to_target.dot(target_velocity);
+    let c = to_target.length_squared();
+    let t = if a.abs() < 1e-4 {
+        if b.abs() < 1e-4 { None } else { Some(-c / b) }
+    } else {
+        let disc = b * b - 4.0 * a * c;
+        if disc < 0.0 {
+            None
+        } else {
+            let root = disc.sqrt();
+            let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+            [t1, t2].into_iter().filter(|t| *t > 0.0).reduce(f32::min)
+        }
+    };
+    match t {
+        Some(t) if t > 0.0 => (to_target + target_velocity * t).normalize_or_zero(),
+        _ => to_target.normalize_or_zero(),
+    }
+}
+
+/// Point on segment `a`–`b` closest to `p`, for swept bolt hit tests.
+pub fn closest_point_on_segment(a: Vec3, b: Vec3, p: Vec3) -> Vec3 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq < 1e-8 {
+        return a;
+    }
+    a + ab * ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn led_bolt_meets_a_strafing_target() {
+        let muzzle = Vec3::new(0.0, 1.5, 0.0);
+        let target = Vec3::new(0.0, 1.5, 30.0);
+        let velocity = Vec3::new(6.0, 0.0, 0.0);
+        let speed = SkinnyType::Grunt.bolt_speed();
+        let dir = lead_direction(muzzle, target, velocity, speed);
+        // Step the bolt and target together: they meet within hit radius
+        let (mut bolt, mut player) = (muzzle, target);
+        let mut closest = f32::MAX;
+        for _ in 0..200 {
+            bolt += dir * speed * 0.005;
+            player += velocity * 0.005;
+            closest = closest.min(bolt.distance(player));
+        }
+        assert!(closest < BOLT_HIT_RADIUS, "closest approach {closest}");
+        assert!(dir.x > 0.0, "aims ahead of the target");
+        // A target outrunning the bolt gets a straight shot rather than NaN
+        let fallback = lead_direction(muzzle, target, Vec3::new(0.0, 0.0, 500.0), speed);
+        assert!((fallback - Vec3::Z).length() < 1e-4);
+    }
+
+    #[test]
+    fn skinnies_hold_their_band_and_back_off_when_rushed() {
+        let mut grunt = Skinny::new(SkinnyType::Grunt);
+        grunt.set_line_of_sight(true);
+        assert_eq!(grunt.ranged_intent(3.0), RangedIntent::Retreat);
+        assert_eq!(grunt.ranged_intent(18.0), RangedIntent::Hold);
+        assert_eq!(grunt.ranged_intent(60.0), RangedIntent::Approach);
+
+        // Losing sight inside the band means flanking, on the other side from before
+        let sign = grunt.strafe_sign;
+        grunt.set_line_of_sight(false);
+        assert_eq!(grunt.ranged_intent(18.0), RangedIntent::Flank);
+        assert_eq!(grunt.strafe_sign, -sign);
+
+        // Snipers are content at distances a grunt would close from, and retreat sooner
+        let mut sniper = Skinny::new(SkinnyType::Sniper);
+        sniper.set_line_of_sight(true);
+        assert_eq!(sniper.ranged_intent(50.0), RangedIntent::Hold);
+        assert_eq!(sniper.ranged_intent(18.0), RangedIntent::Retreat);
+        assert!(SkinnyType::Sniper.fire_range() > SkinnyType::Sniper.preferred_range().1);
+    }
 }
@@ -0,0 +1,404 @@
+//! Snow cover around the player: weather-driven accumulation on a 128m tile that follows the
+//! camera (same layout as the terrain deformation texture). Flat open ground fills up fastest,
+//! steep slopes and sheltered spots under cliffs hold less, and heat (lava, fire, explosions)
+//! melts it. Depth feeds the terrain shader, footprints and player movement.
+
+use glam::Vec3;
+use renderer::{DEFORM_HALF_SIZE, DEFORM_TEXTURE_SIZE};
+
+use crate::bug_entity::{GroundTrack, TrackKind};
+
+/// Accumulation on fully exposed flat ground at full snowfall (m/s).
+const SNOW_ACCUM_RATE: f32 = 0.08;
+/// Melt rate when it isn't snowing (m/s).
+const SNOW_MELT_RATE: f32 = 0.04;
+/// Knee-deep.
+pub const SNOW_MAX_DEPTH: f32 = 0.45;
+/// Melt rate at the center of a heat source (m/s), falling off to 0 at its radius.
+const HEAT_MELT_RATE: f32 = 0.6;
+/// Slowest the player gets in knee-deep snow (fraction of normal speed).
+const DEEP_SNOW_SPEED: f32 = 0.45;
+/// Recenter the tile once the camera is this far from its center (m).
+const RECENTER_DISTANCE: f32 = 8.0;
+/// Distance to the neighbours sampled for slope and shelter (m).
+const EXPOSURE_PROBE: f32 = 3.0;
+/// Ground rising this much above a spot (within the probe) fully shelters it (m).
+const FULL_SHELTER_RISE: f32 = 3.0;
+/// Footprints pack snow down to this fraction of the surrounding depth, and refill over this long (s).
+const TRACK_PACKED_FRACTION: f32 = 0.25;
+const TRACK_REFILL_TIME: f32 = 40.0;
+
+const SIZE: usize = DEFORM_TEXTURE_SIZE as usize;
+
+/// World-space snow depth field centered near the camera.
+pub struct SnowField {
+    /// Loose snow depth per texel (m), row-major, SIZE x SIZE, before footprints.
+    depth: Vec<f32>,
+    /// Share of the loose depth left under footprints (1 = untouched), rebuilt from the tracks
+    /// each frame so standing tracks don't keep sinking.
+    packing: Vec<f32>,
+    /// Depth after footprints (`depth * packing`): what is rendered and walked through.
+    surface: Vec<f32>,
+    /// How readily each texel collects snow (0..1): flat open ground = 1.
+    exposure: Vec<f32>,
+    /// World (x, z) of the tile center, snapped to whole texels.
+    origin: (f32, f32),
+    /// Exposure has been computed for the current origin.
+    initialized: bool,
+    /// Deepest snow anywhere on the tile (skip rendering/sampling work when bare).
+    max_depth: f32,
+}
+
+impl SnowField {
+    pub fn new() -> Self {
+        Self {
+            depth: vec![0.0; SIZE * SIZE],
+            packing: vec![1.0; SIZE * SIZE],
+            surface: vec![0.0; SIZE * SIZE],
+            exposure: vec![1.0; SIZE * SIZE],
+            origin: (0.0, 0.0),
+            initialized: false,
+            max_depth: 0.0,
+        }
+    }
+
+    /// World (x, z) center of the tile (pass to the terrain shader with `depth_buffer`).
+    pub fn origin(&self) -> (f32, f32) {
+        self.origin
+    }
+
+    /// Depth texels for upload (DEFORM_TEXTURE_SIZE², same layout as the deform texture).
+    pub fn depth_buffer(&self) -> &[f32] {
+        &self.surface
+    }
+
+    /// Any snow on the ground?
+    pub fn is_active(&self) -> bool {
+        self.max_depth > 0.001
+    }
+
+    /// Remove all snow (e.g. leaving the planet). Exposure is recomputed on the next recenter.
+    pub fn clear(&mut self) {
+        if !self.initialized {
+            return;
+        }
+        self.depth.fill(0.0);
+        self.packing.fill(1.0);
+        self.surface.fill(0.0);
+        self.max_depth = 0.0;
+        self.initialized = false;
+    }
+
+    fn texels_per_unit() -> f32 {
+        SIZE as f32 / (2.0 * DEFORM_HALF_SIZE)
+    }
+
+    fn texel_world(&self, i: usize, j: usize) -> (f32, f32) {
+        let per_unit = Self::texels_per_unit();
+        (
+            self.origin.0 - DEFORM_HALF_SIZE + (i as f32 + 0.5) / per_unit,
+            self.origin.1 - DEFORM_HALF_SIZE + (j as f32 + 0.5) / per_unit,
+        )
+    }
+
+    /// Keep the tile around `center`, scrolling existing snow by whole texels so it doesn't reset
+    /// as the player walks. `height_at` samples terrain height for exposure of new texels.
+    pub fn recenter(&mut self, center: Vec3, height_at: impl Fn(f32, f32) -> f32) {
+        let per_unit = Self::texels_per_unit();
+        let snap = |v: f32| (v * per_unit).round() / per_unit;
+        let target = (snap(center.x), snap(center.z));
+
+        if !self.initialized {
+            self.origin = target;
+            self.depth.fill(0.0);
+            self.packing.fill(1.0);
+            self.surface.fill(0.0);
+            self.max_depth = 0.0;
+            for j in 0..SIZE {
+                for i in 0..SIZE {
+                    self.exposure[i + j * SIZE] = self.compute_exposure(i, j, &height_at);
+                }
+            }
+            self.initialized = true;
+            return;
+        }
+        if (center.x - self.origin.0).abs() < RECENTER_DISTANCE && (center.z - self.origin.1).abs() < RECENTER_DISTANCE {
+            return;
+        }
+
+        let di = ((target.0 - self.origin.0) * per_unit).round() as i64;
+        let dj = ((target.1 - self.origin.1) * per_unit).round() as i64;
+        self.origin = target;
+        let old_depth = std::mem::take(&mut self.depth);
+        let old_exposure = std::mem::take(&mut self.exposure);
+        self.depth = vec![0.0; SIZE * SIZE];
+        self.exposure = vec![1.0; SIZE * SIZE];
+        for j in 0..SIZE {
+            for i in 0..SIZE {
+                let (si, sj) = (i as i64 + di, j as i64 + dj);
+                let idx = i + j * SIZE;
+                if (0..SIZE as i64).contains(&si) && (0..SIZE as i64).contains(&sj) {
+                    let src = si as usize + sj as usize * SIZE;
+                    self.depth[idx] = old_depth[src];
+                    self.exposure[idx] = old_exposure[src];
+                } else {
+                    self.exposure[idx] = self.compute_exposure(i, j, &height_at);
+                }
+            }
+        }
+        // Footprints are pressed again from the tracks next frame
+        self.packing.fill(1.0);
+        self.refresh_surface();
+    }
+
+    /// Flat, open ground collects the most snow; slopes shed it and spots with ground rising
+    /// steeply beside them (under cliffs and overhangs) stay sheltered.
+    fn compute_exposure(&self, i: usize, j: usize, height_at: &impl Fn(f32, f32) -> f32) -> f32 {
+        let (x, z) = self.texel_world(i, j);
+        let h = height_at(x, z);
+        let neighbours = [
+            height_at(x + EXPOSURE_PROBE, z),
+            height_at(x - EXPOSURE_PROBE, z),
+            height_at(x, z + EXPOSURE_PROBE),
+            height_at(x, z - EXPOSURE_PROBE),
+        ];
+        let slope_x = (neighbours[0] - neighbours[1]).abs() / (2.0 * EXPOSURE_PROBE);
+        let slope_z = (neighbours[2] - neighbours[3]).abs() / (2.0 * EXPOSURE_PROBE);
+        let slope = slope_x.max(slope_z);
+        let flatness = 1.0 - ((slope - 0.3) / 0.7).clamp(0.0, 1.0);
+        let rise = neighbours.iter().fold(f32::NEG_INFINITY, |m, &n| m.max(n)) - h;
+        let shelter = (rise / FULL_SHELTER_RISE).clamp(0.0, 1.0);
+        flatness * (1.0 - 0.8 * shelter)
+    }
+
+    /// Advance accumulation/melt. `snowfall` is 0..1 (0 = not snowing, so snow melts);
+    /// `heat` lists (center, radius) heat sources that melt snow around them.
+    pub fn update(&mut self, dt: f32, snowfall: f32, heat: &[(Vec3, f32)]) {
+        if !self.initialized || (snowfall <= 0.0 && !self.is_active()) {
+            return;
+        }
+        let mut max_depth: f32 = 0.0;
+        for (d, exposure) in self.depth.iter_mut().zip(&self.exposure) {
+            if snowfall > 0.0 {
+                *d = (*d + SNOW_ACCUM_RATE * snowfall * exposure * dt).min(SNOW_MAX_DEPTH * exposure.max(0.2));
+            } else {
+                *d = (*d - SNOW_MELT_RATE * dt).max(0.0);
+            }
+            max_depth = max_depth.max(*d);
+        }
+        self.max_depth = max_depth;
+
+        for &(center, radius) in heat {
+            for (i, falloff) in self.texels_in(center, radius) {
+                self.depth[i] = (self.depth[i] - HEAT_MELT_RATE * falloff * dt).max(0.0);
+            }
+        }
+        self.refresh_surface();
+    }
+
+    /// Instantly clear snow in a radius (explosions, artillery craters).
+    pub fn melt_at(&mut self, center: Vec3, radius: f32) {
+        for (i, falloff) in self.texels_in(center, radius) {
+            self.depth[i] *= 1.0 - falloff;
+        }
+        self.refresh_surface();
+    }
+
+    /// Pack snow down under footprints/trails; old tracks slowly fill back in. Packing is
+    /// measured against the loose depth, so a track pressed every frame stays at
+    /// `TRACK_PACKED_FRACTION` instead of sinking to bare ground.
+    pub fn press_tracks(&mut self, tracks: &[GroundTrack]) {
+        if !self.is_active() {
+            return;
+        }
+        self.packing.fill(1.0);
+        for track in tracks {
+            let fresh = 1.0 - (track.age / TRACK_REFILL_TIME).min(1.0);
+            if fresh <= 0.0 {
+                continue;
+            }
+            let radius = match track.kind {
+                TrackKind::TrooperFoot => 0.3,
+                TrackKind::BugFoot => 0.55,
+                TrackKind::ShovelDig => 0.7,
+            };
+            for (i, falloff) in self.texels_in(track.position, radius) {
+                let packed = 1.0 - (1.0 - TRACK_PACKED_FRACTION) * falloff * fresh;
+                self.packing[i] = self.packing[i].min(packed);
+            }
+        }
+        self.refresh_surface();
+    }
+
+    fn refresh_surface(&mut self) {
+        for ((surface, depth), packing) in self.surface.iter_mut().zip(&self.depth).zip(&self.packing) {
+            *surface = depth * packing;
+        }
+    }
+
+    /// Index and falloff of every texel within `radius` of `center` (smooth falloff, 1 at center).
+    fn texels_in(&self, center: Vec3, radius: f32) -> Vec<(usize, f32)> {
+        let mut texels = Vec::new();
+        if radius <= 0.0 {
+            return texels;
+        }
+        let per_unit = Self::texels_per_unit();
+        let min_x = self.origin.0 - DEFORM_HALF_SIZE;
+        let min_z = self.origin.1 - DEFORM_HALF_SIZE;
+        let last = SIZE as i32 - 1;
+        let min_i = (((center.x - radius - min_x) * per_unit).floor() as i32).max(0);
+        let max_i = (((center.x + radius - min_x) * per_unit).ceil() as i32).min(last);
+        let min_j = (((center.z - radius - min_z) * per_unit).floor() as i32).max(0);
+        let max_j = (((center.z + radius - min_z) * per_unit).ceil() as i32).min(last);
+        for j in min_j..=max_j {
+            for i in min_i..=max_i {
+                let (wx, wz) = self.texel_world(i as usize, j as usize);
+                let dist = ((wx - center.x).powi(2) + (wz - center.z).powi(2)).sqrt();
+                if dist < radius {
+                    let t = dist / radius;
+                    let falloff = 1.0 - t * t * (3.0 - 2.0 * t);
+                    texels.push((i as usize + j as usize * SIZE, falloff));
+                }
+            }
+        }
+        texels
+    }
+
+    /// Bilinear snow depth at world (x, z). Returns 0 outside the tile or with no snow.
+    pub fn sample_depth(&self, x: f32, z: f32) -> f32 {
+        if !self.is_active() {
+            return 0.0;
+        }
+        let per_unit = Self::texels_per_unit();
+        let i_f = (x - self.origin.0 + DEFORM_HALF_SIZE) * per_unit;
+        let j_f = (z - self.origin.1 + DEFORM_HALF_SIZE) * per_unit;
+        if i_f < -0.5 || i_f > SIZE as f32 - 0.5 || j_f < -0.5 || j_f > SIZE as f32 - 0.5 {
+            return 0.0;
+        }
+        let last = SIZE as i32 - 1;
+        let i0 = (i_f - 0.5).floor() as i32;
+        let j0 = (j_f - 0.5).floor() as i32;
+        let i1 = (i0 + 1).min(last);
+        let j1 = (j0 + 1).min(last);
+        let i0 = i0.max(0);
+        let j0 = j0.max(0);
+        let fx = (i_f - 0.5 - i0 as f32).clamp(0.0, 1.0);
+        let fy = (j_f - 0.5 - j0 as f32).clamp(0.0, 1.0);
+        let idx = |i: i32, j: i32| i as usize + j as usize * SIZE;
+        let s00 = self.surface[idx(i0, j0)];
+        let s10 = self.surface[idx(i1, j0)];
+        let s01 = self.surface[idx(i0, j1)];
+        let s11 = self.surface[idx(i1, j1)];
+        s00 * (1.0 - fx) * (1.0 - fy) + s10 * fx * (1.0 - fy) + s01 * (1.0 - fx) * fy + s11 * fx * fy
+    }
+}
+
+impl Default for SnowField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Movement speed multiplier for wading through `depth` m of snow (1.0 on bare ground).
+pub fn snow_speed_scale(depth: f32) -> f32 {
+    1.0 - (1.0 - DEEP_SNOW_SPEED) * (depth / SNOW_MAX_DEPTH).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat ground at y = 0, with a 6m cliff wall rising for x > 20.
+    fn cliff_height(x: f32, _z: f32) -> f32 {
+        if x > 20.0 { 6.0 } else { 0.0 }
+    }
+
+    #[test]
+    fn open_ground_outpaces_sheltered_ground_and_heat_melts_it() {
+        let mut snow = SnowField::new();
+        snow.recenter(Vec3::ZERO, cliff_height);
+        for _ in 0..60 {
+            snow.update(0.1, 1.0, &[]);
+        }
+        let open = snow.sample_depth(0.0, 0.0);
+        let under_cliff = snow.sample_depth(19.0, 0.0);
+        assert!(open > 0.4, "open flat ground fills to near knee-deep, got {open}");
+        assert!(under_cliff < open * 0.6, "ground at the foot of the cliff is sheltered");
+
+        // Lava pool at (-10, 0): melts its surroundings while snow keeps falling elsewhere
+        for _ in 0..20 {
+            snow.update(0.1, 1.0, &[(Vec3::new(-10.0, 0.0, 0.0), 5.0)]);
+        }
+        assert!(snow.sample_depth(-10.0, 0.0) < 0.1);
+        assert!(snow.sample_depth(10.0, 0.0) > 0.4);
+
+        // Artillery strike clears a crater instantly
+        snow.melt_at(Vec3::new(10.0, 0.0, 0.0), 6.0);
+        assert!(snow.sample_depth(10.0, 0.0) < 0.01);
+    }
+
+    #[test]
+    fn recentering_keeps_existing_snow_in_world_space() {
+        let mut snow = SnowField::new();
+        snow.recenter(Vec3::ZERO, |_, _| 0.0);
+        for _ in 0..40 {
+            snow.update(0.1, 1.0, &[]);
+        }
+        snow.melt_at(Vec3::new(12.0, 0.0, 0.0), 2.0);
+        let before = (snow.sample_depth(12.0, 0.0), snow.sample_depth(14.0, 0.0));
+
+        snow.recenter(Vec3::new(20.0, 0.0, 3.0), |_, _| 0.0);
+        assert!((snow.origin().0 - 20.0).abs() < 0.5);
+        assert!((snow.sample_depth(12.0, 0.0) - before.0).abs() < 1e-4);
+        assert!((snow.sample_depth(14.0, 0.0) - before.1).abs() < 1e-4);
+        // Newly covered ground starts bare
+        assert_eq!(snow.sample_depth(20.0 + DEFORM_HALF_SIZE - 1.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn footprints_stay_packed_while_pressed_and_refill_as_they_age() {
+        let mut snow = SnowField::new();
+        snow.recenter(Vec3::ZERO, |_, _| 0.0);
+        for _ in 0..60 {
+            snow.update(0.1, 1.0, &[]);
+        }
+        let loose = snow.sample_depth(0.0, 0.0);
+        let mut track = GroundTrack {
+            position: Vec3::ZERO,
+            rotation_y: 0.0,
+            age: 0.0,
+            size: 1.0,
+            kind: TrackKind::ShovelDig,
+        };
+
+        // A fresh print pressed every frame for several seconds: packed once, not dug to the ground
+        snow.press_tracks(std::slice::from_ref(&track));
+        let first = snow.sample_depth(0.0, 0.0);
+        assert!(first < loose * 0.8);
+        for _ in 0..300 {
+            snow.update(1.0 / 60.0, 1.0, &[]);
+            snow.press_tracks(std::slice::from_ref(&track));
+        }
+        let packed = snow.sample_depth(0.0, 0.0);
+        assert!((packed - first).abs() < 1e-3, "print sank from {first} to {packed} m");
+        assert!(packed >= loose * TRACK_PACKED_FRACTION - 1e-3);
+        assert!((snow.sample_depth(2.0, 0.0) - loose).abs() < 1e-3, "snow beside the print is untouched");
+
+        // Half refilled midway through, back to loose once the track has aged out
+        track.age = TRACK_REFILL_TIME * 0.5;
+        snow.press_tracks(std::slice::from_ref(&track));
+        let refilling = snow.sample_depth(0.0, 0.0);
+        assert!(refilling > packed && refilling < loose);
+        track.age = TRACK_REFILL_TIME;
+        snow.press_tracks(std::slice::from_ref(&track));
+        assert!((snow.sample_depth(0.0, 0.0) - loose).abs() < 1e-3);
+    }
+
+    #[test]
+    fn deep_snow_slows_proportionally() {
+        assert_eq!(snow_speed_scale(0.0), 1.0);
+        assert!((snow_speed_scale(SNOW_MAX_DEPTH) - DEEP_SNOW_SPEED).abs() < 1e-6);
+        let half = snow_speed_scale(SNOW_MAX_DEPTH * 0.5);
+        assert!(half < 1.0 && half > DEEP_SNOW_SPEED);
+    }
+}
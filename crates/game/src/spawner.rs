@@ -6,11 +6,21 @@
 //! composition shifts toward more lethal variants the longer the trooper
 //! holds the line.
 
-use engine_core::Vec3;
+use engine_core::{GameRng, Health, Vec3};
 use hecs::World;
 use rand::prelude::*;
 
 use crate::bug::{BugBundle, BugType, BugVariant};
+use crate::difficulty::Difficulty;
+
+/// Seconds on a planet before the first burrow ambush.
+const FIRST_AMBUSH_DELAY: f32 = 25.0;
+/// Seconds between burrow ambushes at difficulty 0; shrinks as the horde escalates.
+const AMBUSH_INTERVAL: f32 = 40.0;
+/// Floor on the ambush interval, seconds.
+const MIN_AMBUSH_INTERVAL: f32 = 12.0;
+/// Most bugs erupting in one ambush.
+const MAX_AMBUSH_GROUP: usize = 8;
 
 /// Manages continuous, ever-escalating bug spawning.
 pub struct BugSpawner {
@@ -45,14 +55,20 @@ pub struct BugSpawner {
     pub difficulty: f32,
     /// Threat level name for HUD display.
     pub threat_level: ThreatLevel,
+    /// Temporary pressure multiplier (extraction last stand); 1.0 = normal.
+    surge: f32,
 
     /// Biome-specific bug variant (set when landing on a planet).
     pub biome_variant: Option<BugVariant>,
     /// Probability (0.0–1.0) that a spawned bug is the biome variant.
     pub variant_chance: f32,
 
-    /// Random number generator.
-    rng: StdRng,
+    // ── Burrow ambushes ─────────────────────────────────────────────────
+    /// Seconds until the next group of burrowers erupts around the trooper.
+    pub ambush_timer: f32,
+
+    /// Seeded RNG so spawn patterns replay from the universe seed.
+    rng: GameRng,
 }
 
 /// Named threat tiers shown on the HUD — pure flavour, driven by difficulty.
@@ -111,11 +127,14 @@ impl BugSpawner {
     /// Create a spawner for a planet. `base_spawn_rate` is from `planet.bug_spawn_rate()` (already scales with danger).
     /// `danger_level` is planet danger 1–10: it sets how many bugs can be alive at once and how nasty the mix is from the start.
     /// Tuned for movie/2005 game horde scale + Starship Troopers Extermination intensity.
-    pub fn new(base_spawn_rate: f32, danger_level: u32) -> Self {
+    /// `preset` scales spawn rate and starting horde size (the cap stays put for performance).
+    pub fn new(base_spawn_rate: f32, danger_level: u32, preset: Difficulty, rng: GameRng) -> Self {
         let danger = danger_level.clamp(1, 10) as f32;
         // Movie/2005 game scale: massive swarms (600–1500 base, 1300–4000 cap)
         let base_max_bugs = 500 + (danger_level as usize).min(10) * 100;   // danger 1 → 600, 10 → 1500
         let max_bugs_cap = 1000 + (danger_level as usize).min(10) * 300;   // danger 1 → 1300, 10 → 4000
+        let base_max_bugs = ((base_max_bugs as f32 * preset.spawn_rate_mult()) as usize).min(max_bugs_cap);
+        let base_spawn_rate = base_spawn_rate * preset.spawn_rate_mult();
         Self {
             base_spawn_rate,
             spawn_rate: base_spawn_rate,
@@ -130,9 +149,11 @@ impl BugSpawner {
             time_survived: 0.0,
             difficulty: 0.0,
             threat_level: ThreatLevel::Minimal,
+            surge: 1.0,
             biome_variant: None,
             variant_chance: 0.0,
-            rng: StdRng::from_entropy(),
+            ambush_timer: FIRST_AMBUSH_DELAY,
+            rng,
         }
     }
 
@@ -147,17 +168,23 @@ impl BugSpawner {
         self.time_survived += dt;
         self.difficulty = self.time_survived / 60.0; // +1 per minute
 
-        // Spawn rate: +20% per difficulty level (Extermination-style escalation)
-        self.spawn_rate = self.base_spawn_rate * (1.0 + self.difficulty * 0.20);
+        // Spawn rate: +20% per difficulty level (Extermination-style escalation), times any surge
+        self.spawn_rate = self.base_spawn_rate * (1.0 + self.difficulty * 0.20) * self.surge;
 
-        // Max bugs: base + 80 per difficulty level, capped (movie-scale horde growth)
-        self.max_bugs = (self.base_max_bugs + (self.difficulty * 80.0) as usize)
-            .min(self.max_bugs_cap);
+        // Max bugs: base + 80 per difficulty level, raised by the surge, capped (movie-scale horde growth)
+        let max_bugs = (self.base_max_bugs + (self.difficulty * 80.0) as usize) as f32 * self.surge;
+        self.max_bugs = (max_bugs as usize).min(self.max_bugs_cap);
 
         // Threat level (display only)
         self.threat_level = ThreatLevel::from_difficulty(self.difficulty);
     }
 
+    /// Multiply spawn pressure (e.g. the extraction last stand); 1.0 = normal.
+    /// Takes effect on the next `update_difficulty`.
+    pub fn set_surge(&mut self, multiplier: f32) {
+        self.surge = multiplier.max(1.0);
+    }
+
     /// Get a random bug type and optional biome variant, weighted by difficulty and variant_chance.
     /// Planet danger is added so high-danger planets get nastier mix from the start.
     pub fn random_bug_type(&mut self) -> (BugType, Option<BugVariant>) {
@@ -198,12 +225,30 @@ impl BugSpawner {
         (bug_type, variant)
     }
 
-    /// Spawn a group of bugs at a position (for bug holes).
-    pub fn spawn_group(&mut self, world: &mut World, center: Vec3, count: usize, bug_type: BugType) {
+    /// Tick the burrow-ambush clock. Returns how many bugs erupt around the trooper now (0 on
+    /// most frames). Ambushes come sooner and bigger as the horde escalates; on Burrower worlds
+    /// (desert) they come twice as often.
+    pub fn tick_ambush(&mut self, dt: f32) -> usize {
+        self.ambush_timer -= dt;
+        if self.ambush_timer > 0.0 {
+            return 0;
+        }
+        let burrower_world = self.biome_variant == Some(BugVariant::Burrower);
+        let interval = (AMBUSH_INTERVAL - self.difficulty * 3.0).max(MIN_AMBUSH_INTERVAL)
+            * if burrower_world { 0.5 } else { 1.0 };
+        self.ambush_timer = interval * self.rng.gen_range(0.75..1.25);
+        (2 + (self.planet_danger / 3.0) as usize + (self.difficulty / 4.0) as usize).min(MAX_AMBUSH_GROUP)
+    }
+
+    /// Spawn a group of bugs at a position (for bug holes). `health_mult` is the difficulty
+    /// preset's `bug_health_mult`, as on every other spawn path.
+    pub fn spawn_group(&mut self, world: &mut World, center: Vec3, count: usize, bug_type: BugType, health_mult: f32) {
         for i in 0..count {
             let angle = (i as f32 / count as f32) * std::f32::consts::TAU;
             let offset = Vec3::new(angle.cos() * 2.0, 0.0, angle.sin() * 2.0);
-            BugBundle::new(bug_type, center + offset).spawn(world);
+            let mut bundle = BugBundle::new(bug_type, center + offset);
+            bundle.health = Health::new(bundle.bug.effective_health() * health_mult);
+            bundle.spawn(world);
         }
     }
 
@@ -226,3 +271,53 @@ impl BugSpawner {
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_spawns_scale_health_with_the_preset() {
+        let mut world = World::new();
+        let mut spawner = BugSpawner::new(2.0, 1, Difficulty::Helldive, GameRng::new(3));
+        spawner.spawn_group(&mut world, Vec3::ZERO, 3, BugType::Warrior, Difficulty::Helldive.bug_health_mult());
+        let base = crate::bug::Bug::new(BugType::Warrior).effective_health();
+        let mut query = world.query::<&Health>();
+        let healths: Vec<f32> = query.iter().map(|(_, h)| h.max).collect();
+        assert_eq!(healths.len(), 3);
+        assert!(healths.iter().all(|&h| (h - base * Difficulty::Helldive.bug_health_mult()).abs() < 1e-3));
+    }
+
+    #[test]
+    fn surge_multiplies_spawn_pressure_until_cleared() {
+        let mut spawner = BugSpawner::new(2.0, 1, Difficulty::Normal, GameRng::new(7));
+        spawner.update_difficulty(0.0);
+        let (rate, max_bugs) = (spawner.spawn_rate, spawner.max_bugs);
+
+        spawner.set_surge(3.0);
+        spawner.update_difficulty(0.0);
+        assert!((spawner.spawn_rate - rate * 3.0).abs() < 1e-4);
+        assert!(spawner.max_bugs > max_bugs && spawner.max_bugs <= spawner.max_bugs_cap);
+
+        // Surges never thin the horde, and clearing one restores normal pressure
+        spawner.set_surge(0.5);
+        spawner.update_difficulty(0.0);
+        assert!((spawner.spawn_rate - rate).abs() < 1e-4);
+        assert_eq!(spawner.max_bugs, max_bugs);
+    }
+
+    #[test]
+    fn ambushes_arrive_periodically_and_grow_with_difficulty() {
+        let mut spawner = BugSpawner::new(2.0, 3, Difficulty::Normal, GameRng::new(11));
+        assert_eq!(spawner.tick_ambush(FIRST_AMBUSH_DELAY - 1.0), 0);
+        let first = spawner.tick_ambush(1.0);
+        assert!(first >= 2);
+        assert_eq!(spawner.tick_ambush(0.1), 0, "the clock resets after an ambush");
+
+        spawner.update_difficulty(60.0 * 20.0);
+        spawner.ambush_timer = 0.0;
+        let late = spawner.tick_ambush(0.1);
+        assert!(late > first && late <= MAX_AMBUSH_GROUP);
+        assert!(spawner.ambush_timer <= MIN_AMBUSH_INTERVAL * 1.25);
+    }
+}
@@ -1,7 +1,9 @@
 //! Squad mates: AI troopers that deploy with the player, kill bugs, and call air strikes.
 //!
-//! Spawned when the drop pod lands; they follow the player, engage bugs in range,
-//! and periodically request Tac Fighter CAS. Each trooper type has unique stats.
+//! Spawned when the drop pod lands; they hold formation slots around the player, duck
+//! behind nearby rocks when bugs close in, engage bugs in range, and periodically
+//! request Tac Fighter CAS. Each trooper type has unique stats. Lethal damage puts a trooper
+//! down to bleed out rather than killing them outright, so the player can still get them up.
 
 use engine_core::{Health, Transform, Velocity, Vec3};
 use glam::Quat;
@@ -9,6 +11,9 @@ use hecs::{Entity, World};
 use rand::Rng;
 
 use crate::bug::Bug;
+use crate::destruction::{EnvironmentProp, Rock};
+use crate::horde_ai::separation_forces;
+use crate::weapons::WeaponType;
 
 /// Kind of squad mate (affects visuals, behavior, and stats).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -77,6 +82,97 @@ impl SquadMateKind {
             },
         }
     }
+
+    /// Weapon the trooper carries; dropped where they fall for the player to pick up.
+    pub fn weapon(self) -> WeaponType {
+        match self {
+            SquadMateKind::Fleet | SquadMateKind::MobileInfantry => WeaponType::Rifle,
+            SquadMateKind::Marauder => WeaponType::MachineGun,
+            SquadMateKind::Tech => WeaponType::Shotgun,
+        }
+    }
+}
+
+/// Spacing between formation slots (m). Wide enough that one acid splash can't catch two troopers.
+const FORMATION_SPACING: f32 = 4.0;
+/// Separation radius between squad mates (and the player) while moving.
+const SQUAD_SEPARATION_RADIUS: f32 = 3.0;
+/// Separation push strength (m/s) blended into the move velocity.
+const SQUAD_SEPARATION_FORCE: f32 = 3.5;
+/// Enemy within this distance counts as "under fire" and sends the trooper to cover.
+const THREAT_RADIUS_SQ: f32 = 22.0 * 22.0;
+/// How long a trooper keeps cover after the last threat (s).
+const COVER_HOLD_TIME: f32 = 4.0;
+/// Cover must be within this distance of the trooper...
+const COVER_SEARCH_RADIUS_SQ: f32 = 14.0 * 14.0;
+/// ...and within this distance of the player, so the squad doesn't scatter.
+const COVER_LEASH_RADIUS_SQ: f32 = 18.0 * 18.0;
+
+/// Ground this far (m) above the player on both flanks of the wedge means a canyon or trench:
+/// fall into column. The wedge comes back once a flank drops under the lower height.
+const NARROW_WALL_HEIGHT: f32 = 2.5;
+const OPEN_WALL_HEIGHT: f32 = 1.5;
+
+/// Seconds a downed trooper holds on before bleeding out.
+pub const BLEED_OUT_SECS: f32 = 45.0;
+/// Share of max health a revived trooper gets back up with.
+pub const REVIVE_HEALTH_FRACTION: f32 = 0.4;
+
+/// Formation the squad holds around the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormationKind {
+    /// V behind the player, alternating left/right — wide fire lanes, default in the open.
+    #[default]
+    Wedge,
+    /// Single file behind the player — for canyons and tunnels.
+    Column,
+}
+
+impl FormationKind {
+    /// Slot offset in player-local space (+X right, +Z forward; the squad sits at -Z, behind).
+    pub fn slot_offset(self, slot: usize) -> Vec3 {
+        match self {
+            FormationKind::Wedge => {
+                let rank = (slot / 2 + 1) as f32;
+                let side = if slot.is_multiple_of(2) { -1.0 } else { 1.0 };
+                Vec3::new(side * rank * FORMATION_SPACING * 0.8, 0.0, -rank * FORMATION_SPACING * 0.7)
+            }
+            FormationKind::Column => Vec3::new(0.0, 0.0, -((slot + 1) as f32) * FORMATION_SPACING),
+        }
+    }
+
+    /// World position of a slot given the player's position and (horizontal) facing.
+    pub fn slot_position(self, slot: usize, anchor: Vec3, forward: Vec3) -> Vec3 {
+        let fwd = Vec3::new(forward.x, 0.0, forward.z).try_normalize().unwrap_or(Vec3::NEG_Z);
+        let right = fwd.cross(Vec3::Y);
+        let local = self.slot_offset(slot);
+        anchor + right * local.x + fwd * local.z
+    }
+
+    /// Formation for the ground around `anchor`: column when walls rise on both sides of where
+    /// the wedge's first rank would stand, wedge in the open. `self` is the current formation.
+    pub fn for_terrain(self, anchor: Vec3, forward: Vec3, sample_terrain_y: impl Fn(f32, f32) -> f32) -> Self {
+        let ground = sample_terrain_y(anchor.x, anchor.z);
+        let flank_rise = |slot| {
+            let p = FormationKind::Wedge.slot_position(slot, anchor, forward);
+            sample_terrain_y(p.x, p.z) - ground
+        };
+        let lower_flank = flank_rise(0).min(flank_rise(1));
+        match self {
+            FormationKind::Wedge if lower_flank > NARROW_WALL_HEIGHT => FormationKind::Column,
+            FormationKind::Column if lower_flank < OPEN_WALL_HEIGHT => FormationKind::Wedge,
+            current => current,
+        }
+    }
+}
+
+/// Whether a squad mate is in the fight, on the ground waiting for help, or gone for good.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SquadMateStatus {
+    Up,
+    /// Took lethal damage; bleeds out when `bleed_out` runs dry.
+    Downed { bleed_out: f32 },
+    Dead,
 }
 
 /// AI companion that deployed with the player.
@@ -88,20 +184,86 @@ pub struct SquadMate {
     pub fire_cooldown: f32,
     /// Time until can call CAS again (so they don't spam).
     pub cas_call_cooldown: f32,
-    /// Formation offset from player (XZ).
-    pub formation_offset: Vec3,
+    /// Formation slot index (see `FormationKind::slot_offset`); reassigned when the squad loses a trooper.
+    pub formation_slot: usize,
+    /// Seconds left holding cover; > 0 means the trooper steers to cover instead of its slot.
+    pub cover_timer: f32,
+    /// Health last frame (a drop means we're taking hits).
+    pub last_health: f32,
+    /// Bled out and left their weapon on the ground.
+    pub weapon_dropped: bool,
+    pub status: SquadMateStatus,
 }
 
 impl SquadMate {
-    pub fn new(name: &'static str, kind: SquadMateKind, formation_offset: Vec3) -> Self {
+    pub fn new(name: &'static str, kind: SquadMateKind, formation_slot: usize) -> Self {
         Self {
             name,
             kind,
             fire_cooldown: 0.0,
             cas_call_cooldown: 30.0 + rand::thread_rng().gen::<f32>() * 20.0, // stagger first CAS calls
-            formation_offset,
+            formation_slot,
+            cover_timer: 0.0,
+            last_health: kind.stats().health,
+            weapon_dropped: false,
+            status: SquadMateStatus::Up,
+        }
+    }
+
+    pub fn is_downed(&self) -> bool {
+        matches!(self.status, SquadMateStatus::Downed { .. })
+    }
+
+    /// Get a downed trooper back on their feet at partial health. False if they weren't down.
+    pub fn revive(&mut self, health: &mut Health) -> bool {
+        if !self.is_downed() {
+            return false;
+        }
+        health.current = health.max * REVIVE_HEALTH_FRACTION;
+        self.last_health = health.current;
+        self.cover_timer = 0.0;
+        self.status = SquadMateStatus::Up;
+        true
+    }
+}
+
+/// A change in a trooper's status this frame, for squad comms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SquadEvent {
+    Downed(&'static str),
+    BledOut(&'static str),
+}
+
+/// Put troopers who took lethal damage on the ground and run down their bleed-out timers.
+pub fn update_downed_squad(world: &mut World, dt: f32) -> Vec<SquadEvent> {
+    let mut events = Vec::new();
+    for (_, (mate, health)) in world.query_mut::<(&mut SquadMate, &Health)>() {
+        match mate.status {
+            SquadMateStatus::Up if health.is_dead() => {
+                mate.status = SquadMateStatus::Downed { bleed_out: BLEED_OUT_SECS };
+                events.push(SquadEvent::Downed(mate.name));
+            }
+            SquadMateStatus::Downed { bleed_out } if bleed_out <= dt => {
+                mate.status = SquadMateStatus::Dead;
+                events.push(SquadEvent::BledOut(mate.name));
+            }
+            SquadMateStatus::Downed { bleed_out } => {
+                mate.status = SquadMateStatus::Downed { bleed_out: bleed_out - dt };
+            }
+            SquadMateStatus::Up | SquadMateStatus::Dead => {}
         }
     }
+    events
+}
+
+/// Revive every downed squad mate (Reinforce stratagem). Returns how many got back up.
+pub fn revive_downed_squad(world: &mut World) -> usize {
+    world
+        .query_mut::<(&mut SquadMate, &mut Health)>()
+        .into_iter()
+        .map(|(_, (mate, health))| mate.revive(health))
+        .filter(|&revived| revived)
+        .count()
 }
 
 /// Despawn all squad mates (e.g. when returning to ship after extraction).
@@ -116,12 +278,12 @@ pub fn despawn_squad(world: &mut World) {
     }
 }
 
-/// Squad data for the default drop bay crew (order matches drop pod indices).
-pub const SQUAD_DROP_DATA: &[(&'static str, SquadMateKind, Vec3)] = &[
-    ("Sgt. Zim", SquadMateKind::MobileInfantry, Vec3::new(-3.0, 0.0, -2.0)),
-    ("Cpl. Higgins", SquadMateKind::MobileInfantry, Vec3::new(3.0, 0.0, -2.0)),
-    ("Marauder Acevedo", SquadMateKind::Marauder, Vec3::new(0.0, 0.0, -4.0)),
-    ("Tech Martinez", SquadMateKind::Tech, Vec3::new(-2.0, 0.0, -3.0)),
+/// Squad data for the default drop bay crew (order matches drop pod indices and initial formation slots).
+pub const SQUAD_DROP_DATA: &[(&'static str, SquadMateKind)] = &[
+    ("Sgt. Zim", SquadMateKind::MobileInfantry),
+    ("Cpl. Higgins", SquadMateKind::MobileInfantry),
+    ("Marauder Acevedo", SquadMateKind::Marauder),
+    ("Tech Martinez", SquadMateKind::Tech),
 ];
 
 /// Spawn a single squad mate at a position (used when their drop pod lands).
@@ -131,7 +293,7 @@ pub fn spawn_one_squad_mate(
     ground_y: f32,
     name: &'static str,
     kind: SquadMateKind,
-    formation_offset: Vec3,
+    formation_slot: usize,
 ) {
     let stats = kind.stats();
     let pos = Vec3::new(position.x, ground_y + 0.5, position.z);
@@ -143,7 +305,7 @@ pub fn spawn_one_squad_mate(
         },
         Velocity::default(),
         Health::new(stats.health),
-        SquadMate::new(name, kind, formation_offset),
+        SquadMate::new(name, kind, formation_slot),
     ));
 }
 
@@ -156,42 +318,183 @@ pub fn spawn_squad(world: &mut World, landing: Vec3, terrain_y: f32) {
         landing + Vec3::new(0.0, 0.0, -5.0),
         landing + Vec3::new(-1.5, 0.0, -4.0),
     ];
-    for (pos, (name, kind, formation)) in positions.iter().zip(SQUAD_DROP_DATA.iter()) {
+    for (slot, (pos, (name, kind))) in positions.iter().zip(SQUAD_DROP_DATA.iter()).enumerate() {
         let position = Vec3::new(pos.x, y, pos.z);
-        spawn_one_squad_mate(world, position, terrain_y, name, *kind, *formation);
+        spawn_one_squad_mate(world, position, terrain_y, name, *kind, slot);
+    }
+}
+
+/// Give every living squad mate a unique slot in `0..n`, keeping current slots when they're
+/// already valid. When a trooper drops (or slots collide), the slots nearest the player are
+/// refilled first by whichever survivor is closest, so nobody runs across the formation.
+pub fn assign_formation_slots(world: &mut World, formation: FormationKind, anchor: Vec3, forward: Vec3) {
+    let mates: Vec<(Entity, Vec3, usize)> = world
+        .query::<(&Transform, &SquadMate, &Health)>()
+        .iter()
+        .filter(|(_, (_, _, health))| !health.is_dead())
+        .map(|(e, (t, squad, _))| (e, t.position, squad.formation_slot))
+        .collect();
+    let n = mates.len();
+    let mut taken = vec![false; n];
+    let valid = mates.iter().all(|&(_, _, slot)| slot < n && !std::mem::replace(&mut taken[slot], true));
+    if valid {
+        return;
+    }
+
+    let mut assigned = vec![false; n];
+    for slot in 0..n {
+        let slot_pos = formation.slot_position(slot, anchor, forward);
+        let nearest = (0..n)
+            .filter(|&i| !assigned[i])
+            .min_by(|&a, &b| {
+                let da = mates[a].1.distance_squared(slot_pos);
+                let db = mates[b].1.distance_squared(slot_pos);
+                da.total_cmp(&db)
+            });
+        if let Some(i) = nearest {
+            assigned[i] = true;
+            if let Ok(mut squad) = world.get::<&mut SquadMate>(mates[i].0) {
+                squad.formation_slot = slot;
+            }
+        }
+    }
+}
+
+/// Best cover spot for a trooper: the far side of a nearby rock/prop from `threat`, skipping
+/// cover another trooper already claimed this frame.
+fn pick_cover(
+    from: Vec3,
+    anchor: Vec3,
+    threat: Vec3,
+    cover: &[(Vec3, f32)],
+    claimed: &mut [bool],
+) -> Option<Vec3> {
+    let mut best: Option<(usize, Vec3, f32)> = None;
+    for (i, &(pos, radius)) in cover.iter().enumerate() {
+        if claimed[i] {
+            continue;
+        }
+        let away = Vec3::new(pos.x - threat.x, 0.0, pos.z - threat.z).normalize_or_zero();
+        let spot = pos + away * (radius + 1.2);
+        if spot.distance_squared(anchor) > COVER_LEASH_RADIUS_SQ {
+            continue;
+        }
+        let dist_sq = spot.distance_squared(from);
+        if dist_sq > COVER_SEARCH_RADIUS_SQ {
+            continue;
+        }
+        if best.as_ref().is_none_or(|(_, _, d)| dist_sq < *d) {
+            best = Some((i, spot, dist_sq));
+        }
     }
+    best.map(|(i, spot, _)| {
+        claimed[i] = true;
+        spot
+    })
 }
 
-/// Sample terrain Y at (x, z). Caller passes a closure so we don't depend on ChunkManager type.
+/// Move squad mates to their formation slots around `anchor` (facing `anchor_forward`), or to
+/// cover behind nearby rocks when bugs close in / they take hits. Separation keeps them apart
+/// from each other and off the player.
 pub fn update_squad_movement(
     world: &mut World,
-    player_pos: Vec3,
+    anchor: Vec3,
+    anchor_forward: Vec3,
+    formation: FormationKind,
     dt: f32,
     sample_terrain_y: impl Fn(f32, f32) -> f32,
 ) {
     let follow_dist = 8.0;
-    for (_, (transform, velocity, squad)) in world.query_mut::<(&mut Transform, &mut Velocity, &SquadMate)>() {
+    assign_formation_slots(world, formation, anchor, anchor_forward);
+
+    // Pass 1: read-only — threats, cover and each trooper's destination.
+    const SCAN_RADIUS_SQ: f32 = 60.0 * 60.0;
+    let mut enemies: Vec<Vec3> = Vec::new();
+    for (_, (transform, _bug, health)) in world.query::<(&Transform, &Bug, &Health)>().iter() {
+        if !health.is_dead() && transform.position.distance_squared(anchor) < SCAN_RADIUS_SQ {
+            enemies.push(transform.position);
+        }
+    }
+    for (_, (transform, _skinny, health)) in world.query::<(&Transform, &crate::skinny::Skinny, &Health)>().iter() {
+        if !health.is_dead() && transform.position.distance_squared(anchor) < SCAN_RADIUS_SQ {
+            enemies.push(transform.position);
+        }
+    }
+
+    let mut cover: Vec<(Vec3, f32)> = Vec::new();
+    if !enemies.is_empty() {
+        let near_anchor = |p: Vec3| p.distance_squared(anchor) < COVER_LEASH_RADIUS_SQ * 1.5;
+        for (_, (transform, _rock)) in world.query::<(&Transform, &Rock)>().iter() {
+            if near_anchor(transform.position) {
+                cover.push((transform.position, transform.scale.max_element().clamp(0.5, 4.0)));
+            }
+        }
+        for (_, (transform, _prop)) in world.query::<(&Transform, &EnvironmentProp)>().iter() {
+            if near_anchor(transform.position) {
+                cover.push((transform.position, transform.scale.max_element().clamp(0.5, 4.0)));
+            }
+        }
+    }
+    let mut claimed = vec![false; cover.len()];
+
+    // (entity, position, target, cover_timer, current health)
+    let mut plans: Vec<(Entity, Vec3, Vec3, f32, f32)> = Vec::new();
+    for (entity, (transform, squad, health)) in world.query::<(&Transform, &SquadMate, &Health)>().iter() {
+        if health.is_dead() {
+            continue;
+        }
+        let pos = transform.position;
+        let nearest_threat = enemies
+            .iter()
+            .map(|e| (*e, e.distance_squared(pos)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let threatened = nearest_threat.is_some_and(|(_, d)| d < THREAT_RADIUS_SQ);
+        let took_hit = health.current < squad.last_health;
+        let cover_timer = if threatened || took_hit {
+            COVER_HOLD_TIME
+        } else {
+            (squad.cover_timer - dt).max(0.0)
+        };
+
+        let cover_spot = match nearest_threat {
+            Some((threat, _)) if cover_timer > 0.0 => pick_cover(pos, anchor, threat, &cover, &mut claimed),
+            _ => None,
+        };
+        let target = cover_spot
+            .unwrap_or_else(|| formation.slot_position(squad.formation_slot, anchor, anchor_forward));
+        plans.push((entity, pos, target, cover_timer, health.current));
+    }
+
+    // Keep troopers apart, and treat the player as an extra body so nobody stands on them.
+    let mut bodies: Vec<Vec3> = plans.iter().map(|p| p.1).collect();
+    bodies.push(anchor);
+    let separation = separation_forces(&bodies, SQUAD_SEPARATION_RADIUS, SQUAD_SEPARATION_FORCE);
+
+    // Pass 2: steer and snap to terrain.
+    for (i, (entity, _pos, target, cover_timer, current_health)) in plans.into_iter().enumerate() {
+        let Ok(mut squad) = world.get::<&mut SquadMate>(entity) else { continue };
+        squad.cover_timer = cover_timer;
+        squad.last_health = current_health;
         let move_speed = squad.kind.stats().move_speed;
-        let target = player_pos + Vec3::new(
-            squad.formation_offset.x,
-            0.0,
-            squad.formation_offset.z,
-        );
+        drop(squad);
+
+        let Ok(mut velocity) = world.get::<&mut Velocity>(entity) else { continue };
+        let Ok(mut transform) = world.get::<&mut Transform>(entity) else { continue };
         let to_target = Vec3::new(target.x - transform.position.x, 0.0, target.z - transform.position.z);
         let dist_xz = (to_target.x * to_target.x + to_target.z * to_target.z).sqrt();
-        if dist_xz < 0.1 {
+        let steer = if dist_xz < 0.1 {
+            Vec3::ZERO
+        } else if dist_xz > follow_dist {
+            to_target / dist_xz * move_speed
+        } else {
+            to_target * (0.3 / dist_xz.max(0.1)) * move_speed // slow down when close
+        };
+        velocity.linear = steer + separation[i];
+        if velocity.linear.length_squared() < 0.01 {
             velocity.linear = Vec3::ZERO;
         } else {
-            let dir = if dist_xz > follow_dist {
-                to_target / dist_xz
-            } else {
-                to_target * (0.3 / dist_xz.max(0.1)) // slow down when close
-            };
-            velocity.linear = Vec3::new(dir.x * move_speed, 0.0, dir.z * move_speed);
             transform.position += velocity.linear * dt;
-            if velocity.linear.length_squared() > 0.01 {
-                transform.rotation = Quat::from_rotation_arc(Vec3::Z, velocity.linear.normalize());
-            }
+            transform.rotation = Quat::from_rotation_arc(Vec3::Z, velocity.linear.normalize());
         }
         let ground_y = sample_terrain_y(transform.position.x, transform.position.z);
         transform.position.y = ground_y + 0.5;
@@ -287,3 +590,95 @@ pub fn update_squad_combat(
     }
     first_cas_caller
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formation_slots_are_spaced_and_behind_player() {
+        for formation in [FormationKind::Wedge, FormationKind::Column] {
+            let slots: Vec<Vec3> = (0..SQUAD_DROP_DATA.len())
+                .map(|i| formation.slot_position(i, Vec3::ZERO, Vec3::NEG_Z))
+                .collect();
+            for (i, a) in slots.iter().enumerate() {
+                assert!(a.z > 0.0, "{formation:?} slot {i} should trail a player facing -Z");
+                assert!(a.length() >= SQUAD_SEPARATION_RADIUS, "{formation:?} slot {i} crowds the player");
+                for b in &slots[i + 1..] {
+                    assert!(a.distance(*b) >= SQUAD_SEPARATION_RADIUS, "{formation:?} slots overlap");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn squad_falls_into_column_in_a_canyon_and_back_to_wedge_in_the_open() {
+        // Canyon running north-south: walls rise 6 m either side of a 6 m wide floor
+        let canyon = |x: f32, _z: f32| if x.abs() > 3.0 { 6.0 } else { 0.0 };
+        let flat = |_x: f32, _z: f32| 0.0;
+        let ditch = |x: f32, _z: f32| if x.abs() > 3.0 { 2.0 } else { 0.0 };
+
+        let wedge = FormationKind::Wedge;
+        assert_eq!(wedge.for_terrain(Vec3::ZERO, Vec3::NEG_Z, flat), FormationKind::Wedge);
+        let column = wedge.for_terrain(Vec3::ZERO, Vec3::NEG_Z, canyon);
+        assert_eq!(column, FormationKind::Column);
+        // Walking across the canyon instead of along it: both flanks are open floor
+        assert_eq!(wedge.for_terrain(Vec3::ZERO, Vec3::X, canyon), FormationKind::Wedge);
+
+        // Between the two heights the current formation holds, so it doesn't flicker
+        assert_eq!(column.for_terrain(Vec3::ZERO, Vec3::NEG_Z, ditch), FormationKind::Column);
+        assert_eq!(wedge.for_terrain(Vec3::ZERO, Vec3::NEG_Z, ditch), FormationKind::Wedge);
+        assert_eq!(column.for_terrain(Vec3::ZERO, Vec3::NEG_Z, flat), FormationKind::Wedge);
+    }
+
+    #[test]
+    fn slots_are_reassigned_when_a_trooper_dies() {
+        let mut world = World::new();
+        spawn_squad(&mut world, Vec3::ZERO, 0.0);
+        let front = world
+            .query::<&SquadMate>()
+            .iter()
+            .find(|(_, s)| s.formation_slot == 0)
+            .map(|(e, _)| e)
+            .unwrap();
+        world.get::<&mut Health>(front).unwrap().take_damage(1000.0);
+
+        assign_formation_slots(&mut world, FormationKind::Wedge, Vec3::ZERO, Vec3::NEG_Z);
+
+        let mut slots: Vec<usize> = world
+            .query::<(&SquadMate, &Health)>()
+            .iter()
+            .filter(|(_, (_, h))| !h.is_dead())
+            .map(|(_, (s, _))| s.formation_slot)
+            .collect();
+        slots.sort_unstable();
+        assert_eq!(slots, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn downed_trooper_bleeds_out_unless_revived() {
+        let mut world = World::new();
+        spawn_squad(&mut world, Vec3::ZERO, 0.0);
+        let mut mates = world.query::<&SquadMate>().iter().map(|(e, _)| e).collect::<Vec<_>>().into_iter();
+        let (saved, lost) = (mates.next().unwrap(), mates.next().unwrap());
+        for e in [saved, lost] {
+            world.get::<&mut Health>(e).unwrap().take_damage(1000.0);
+        }
+
+        let events = update_downed_squad(&mut world, 0.1);
+        assert_eq!(events.iter().filter(|ev| matches!(ev, SquadEvent::Downed(_))).count(), 2);
+        assert!(world.get::<&SquadMate>(saved).unwrap().is_downed());
+
+        {
+            let (mut mate, mut health) = (world.get::<&mut SquadMate>(saved).unwrap(), world.get::<&mut Health>(saved).unwrap());
+            assert!(mate.revive(&mut health));
+            assert_eq!(mate.status, SquadMateStatus::Up);
+            assert!(!health.is_dead() && health.current < health.max);
+        }
+
+        let events = update_downed_squad(&mut world, BLEED_OUT_SECS);
+        assert_eq!(events, vec![SquadEvent::BledOut(world.get::<&SquadMate>(lost).unwrap().name)]);
+        assert_eq!(world.get::<&SquadMate>(lost).unwrap().status, SquadMateStatus::Dead);
+        assert_eq!(revive_downed_squad(&mut world), 0, "the dead stay dead");
+    }
+}
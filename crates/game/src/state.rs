@@ -2,12 +2,19 @@
 //!
 //! Extracted from main.rs for clearer separation of state types from application logic.
 
+use engine_core::GameRng;
 use glam::{Quat, Vec3};
 use hecs::World;
 use rand::Rng;
+use renderer::Camera;
+use std::collections::VecDeque;
+use std::sync::OnceLock;
 
+use crate::flight::{FlightMode, FlightTuning, FlyingVehicleController};
+use crate::grenade::MAX_FRAG_GRENADES;
 use crate::fps;
 use crate::squad::{spawn_one_squad_mate, SQUAD_DROP_DATA};
+use crate::stratagem::Stratagem;
 
 // ── Debug & UI ─────────────────────────────────────────────────────────────
 
@@ -31,8 +38,8 @@ pub(crate) struct DebugSettings {
     pub show_physics_debug: bool,
     /// Show detailed FPS & performance stats.
     pub show_perf_stats: bool,
-    /// Time scale multiplier (0.1 = slow-mo, 1.0 = normal, 2.0 = fast).
-    pub time_scale: f32,
+    /// Requested time scale (0.25 = slow-mo, 1.0 = normal, 2.0 = fast); applied to `Time` (one-shot action).
+    pub time_scale_requested: Option<f32>,
     /// Freeze time of day cycle.
     pub freeze_time_of_day: bool,
     /// Kill all living bugs (one-shot action).
@@ -41,6 +48,8 @@ pub(crate) struct DebugSettings {
     pub teleport_origin_requested: bool,
     /// Show chunk boundaries.
     pub show_chunk_debug: bool,
+    /// Start a transition to the next weather state (one-shot action).
+    pub cycle_weather_requested: bool,
 }
 
 impl DebugSettings {
@@ -55,11 +64,12 @@ impl DebugSettings {
             infinite_ammo: false,
             show_physics_debug: false,
             show_perf_stats: true,
-            time_scale: 1.0,
+            time_scale_requested: None,
             freeze_time_of_day: false,
             kill_all_bugs_requested: false,
             teleport_origin_requested: false,
             show_chunk_debug: false,
+            cycle_weather_requested: false,
         }
     }
 
@@ -80,11 +90,12 @@ impl DebugSettings {
             ("-- Time x0.5 --", false),
             ("-- Time x1.0 --", false),
             ("-- Time x2.0 --", false),
+            ("-- Cycle Weather --", false),
         ]
     }
 
     pub fn menu_item_count(&self) -> usize {
-        15
+        16
     }
 
     pub fn toggle_selected(&mut self) {
@@ -100,10 +111,11 @@ impl DebugSettings {
             8 => self.show_chunk_debug = !self.show_chunk_debug,
             9 => self.kill_all_bugs_requested = true,
             10 => self.teleport_origin_requested = true,
-            11 => self.time_scale = 0.25,
-            12 => self.time_scale = 0.5,
-            13 => self.time_scale = 1.0,
-            14 => self.time_scale = 2.0,
+            11 => self.time_scale_requested = Some(0.25),
+            12 => self.time_scale_requested = Some(0.5),
+            13 => self.time_scale_requested = Some(1.0),
+            14 => self.time_scale_requested = Some(2.0),
+            15 => self.cycle_weather_requested = true,
             _ => {}
         }
     }
@@ -147,50 +159,208 @@ pub enum GamePhase {
     Paused,
 }
 
+/// Largest camera offset at full translational trauma (m).
+const SHAKE_MAX_OFFSET: f32 = 0.4;
+/// Largest (pitch, yaw, roll) at full rotational trauma (radians).
+const SHAKE_MAX_ANGLES: Vec3 = Vec3::new(0.05, 0.035, 0.08);
+/// Default noise frequency (Hz): fast enough to read as a jolt, slow enough not to buzz.
+const SHAKE_DEFAULT_FREQUENCY: f32 = 22.0;
+
 /// Camera screen shake for cinematic impact.
+///
+/// Trauma comes in two kinds that decay independently: translational (the view is shoved around,
+/// e.g. a shell landing nearby) and rotational (the view is twisted, e.g. weapon recoil). Each
+/// frame's motion is Perlin noise scaled by trauma², so it ramps smoothly instead of jittering.
+/// Directional trauma also biases the horizontal shove along the direction of the hit.
 pub(crate) struct ScreenShake {
+    /// Translational shake this frame (trauma²); zero when still.
     pub intensity: f32,
     pub decay_rate: f32,
+    /// Camera position offset this frame.
     pub offset: Vec3,
+    /// Camera rotation this frame, applied on top of the view.
+    pub rotation: Quat,
+    /// Translational trauma, 0..1.
     pub trauma: f32,
+    /// Rotational trauma, 0..1.
+    pub rotational_trauma: f32,
+    /// Noise frequency (Hz) of the shake motion.
+    pub frequency: f32,
+    /// Horizontal direction the recent hits pushed the view, weighted by their trauma (never longer than `trauma`).
+    bias: Vec3,
+    time: f32,
 }
 
 impl ScreenShake {
     pub fn new() -> Self {
-        Self { intensity: 0.0, decay_rate: 5.0, offset: Vec3::ZERO, trauma: 0.0 }
+        Self {
+            intensity: 0.0,
+            decay_rate: 5.0,
+            offset: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            trauma: 0.0,
+            rotational_trauma: 0.0,
+            frequency: SHAKE_DEFAULT_FREQUENCY,
+            bias: Vec3::ZERO,
+            time: 0.0,
+        }
     }
 
+    /// Omnidirectional jolt: shoves and twists the view in no particular direction.
     pub fn add_trauma(&mut self, amount: f32) {
         self.trauma = (self.trauma + amount).min(1.0);
+        self.rotational_trauma = (self.rotational_trauma + amount * 0.5).min(1.0);
+    }
+
+    /// Jolt from a hit that pushes the view along `dir` (from the source toward the player); the
+    /// shake sways mostly along that line. A vertical or zero `dir` falls back to `add_trauma`.
+    pub fn add_directional_trauma(&mut self, dir: Vec3, amount: f32) {
+        self.add_trauma(amount);
+        if let Some(flat) = Vec3::new(dir.x, 0.0, dir.z).try_normalize() {
+            self.bias = (self.bias + flat * amount).clamp_length_max(self.trauma);
+        }
+    }
+
+    /// Twist-only jolt (weapon recoil): the view kicks without moving.
+    pub fn add_rotational_trauma(&mut self, amount: f32) {
+        self.rotational_trauma = (self.rotational_trauma + amount).min(1.0);
     }
 
     pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+        let t = self.time * self.frequency;
         self.intensity = self.trauma * self.trauma;
         if self.intensity > 0.001 {
-            let max_offset = self.intensity * 0.4;
-            let t = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs_f32();
-            self.offset = Vec3::new(
-                (t * 173.7).sin() * max_offset,
-                (t * 259.3).cos() * max_offset,
-                (t * 97.1).sin() * max_offset * 0.3,
-            );
+            let noise = Vec3::new(perlin_1d(t, 0), perlin_1d(t, 1), perlin_1d(t, 2));
+            let mut horizontal = Vec3::new(noise.x, 0.0, noise.z);
+            // Directional hits: stretch the sway along the hit, squash it across
+            if let Some(dir) = self.bias.try_normalize() {
+                let strength = (self.bias.length() / self.trauma).min(1.0);
+                let along = dir * horizontal.dot(dir);
+                horizontal = along * (1.0 + strength) + (horizontal - along) * (1.0 - 0.7 * strength);
+            }
+            self.offset = (horizontal + Vec3::new(0.0, noise.y, 0.0)) * self.intensity * SHAKE_MAX_OFFSET;
         } else {
             self.offset = Vec3::ZERO;
         }
+        let rotational = self.rotational_trauma * self.rotational_trauma;
+        self.rotation = if rotational > 0.001 {
+            let angles = Vec3::new(perlin_1d(t, 3), perlin_1d(t, 4), perlin_1d(t, 5)) * SHAKE_MAX_ANGLES * rotational;
+            Quat::from_euler(glam::EulerRot::YXZ, angles.y, angles.x, angles.z)
+        } else {
+            Quat::IDENTITY
+        };
         self.trauma = (self.trauma - self.decay_rate * dt).max(0.0);
+        self.rotational_trauma = (self.rotational_trauma - self.decay_rate * dt).max(0.0);
+        self.bias = self.bias.clamp_length_max(self.trauma);
+    }
+
+    /// `camera` with this frame's shake applied (for uploading to the GPU; the real camera stays put).
+    pub fn shaken(&self, camera: &Camera) -> Camera {
+        let mut shaken = camera.clone();
+        shaken.transform.position += self.offset;
+        shaken.transform.rotation *= self.rotation;
+        shaken
+    }
+}
+
+/// 1D Perlin noise in about [-1, 1]: a random gradient at each integer, quintic blend between.
+/// `channel` picks an independent noise stream.
+fn perlin_1d(x: f32, channel: u32) -> f32 {
+    let gradient = |i: i32| {
+        let mut h = (i as u32).wrapping_mul(0x27d4_eb2d) ^ channel.wrapping_mul(0x9e37_79b9);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 13;
+        h as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    let i = x.floor();
+    let f = x - i;
+    let a = gradient(i as i32) * f;
+    let b = gradient(i as i32 + 1) * (f - 1.0);
+    let blend = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+    (a + (b - a) * blend) * 2.0
+}
+
+/// What reaching a kill-streak milestone grants.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum StreakReward {
+    /// Clear a stratagem's cooldown so it can be called in right away.
+    ReadyStratagem(Stratagem),
+    /// Bullet damage multiplied for `secs`.
+    DamageBoost { multiplier: f32, secs: f32 },
+    /// Stamina stays full for `secs`.
+    Adrenaline { secs: f32 },
+}
+
+impl StreakReward {
+    /// HUD notification text.
+    pub fn describe(&self) -> String {
+        match self {
+            StreakReward::ReadyStratagem(stratagem) => format!("{} READY", stratagem.name()),
+            StreakReward::DamageBoost { multiplier, secs } => format!("DAMAGE x{:.1} FOR {:.0}s", multiplier, secs),
+            StreakReward::Adrenaline { secs } => format!("ADRENALINE — UNLIMITED SPRINT FOR {:.0}s", secs),
+        }
     }
 }
 
-/// Kill streak tracker for cinematic announcements.
+/// A streak length and what reaching it grants, as stored in `killstreaks.ron`.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct StreakMilestone {
+    pub kills: u32,
+    pub reward: StreakReward,
+}
+
+static STREAK_MILESTONES: OnceLock<Vec<StreakMilestone>> = OnceLock::new();
+
+fn builtin_streak_milestones() -> Vec<StreakMilestone> {
+    vec![
+        StreakMilestone { kills: 5, reward: StreakReward::Adrenaline { secs: 10.0 } },
+        StreakMilestone { kills: 10, reward: StreakReward::ReadyStratagem(Stratagem::SupplyDrop) },
+        StreakMilestone { kills: 15, reward: StreakReward::DamageBoost { multiplier: 1.5, secs: 10.0 } },
+        StreakMilestone { kills: 25, reward: StreakReward::ReadyStratagem(Stratagem::OrbitalStrike) },
+    ]
+}
+
+/// Parse `killstreaks.ron` text. Unlike the weapon and class files this is a list: it replaces the
+/// built-in milestones entirely.
+fn parse_streak_milestones(s: &str) -> Result<Vec<StreakMilestone>, ron::error::SpannedError> {
+    ron::from_str(s)
+}
+
+/// Load kill-streak milestones from `killstreaks.ron` in the current directory. Call once at
+/// startup; a missing or invalid file leaves the compiled defaults in place.
+pub(crate) fn load_streak_milestones() {
+    let path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("killstreaks.ron");
+    let milestones = match std::fs::read_to_string(&path) {
+        Ok(data) => parse_streak_milestones(&data).unwrap_or_else(|e| {
+            log::warn!("Invalid kill streak data at {:?}: {}, using defaults", path, e);
+            builtin_streak_milestones()
+        }),
+        Err(_) => builtin_streak_milestones(),
+    };
+    if STREAK_MILESTONES.set(milestones).is_err() {
+        log::warn!("Kill streak data was already loaded; ignoring {:?}", path);
+    }
+}
+
+fn streak_milestones() -> &'static [StreakMilestone] {
+    STREAK_MILESTONES.get_or_init(builtin_streak_milestones)
+}
+
+/// Kill streak tracker: cinematic announcements, plus milestone rewards (queued until the game
+/// takes them with `consume_reward`) and the timed buffs they grant.
 pub(crate) struct KillStreakTracker {
     pub streak_count: u32,
     pub time_since_kill: f32,
     pub streak_timeout: f32,
     pub announcement: Option<(String, f32, [f32; 4])>,
     pub total_multikills: u32,
+    pending_rewards: VecDeque<StreakReward>,
+    /// Active damage boost: (multiplier, seconds left).
+    damage_boost: Option<(f32, f32)>,
+    /// Seconds of adrenaline (full stamina) left.
+    adrenaline: f32,
 }
 
 impl KillStreakTracker {
@@ -201,6 +371,9 @@ impl KillStreakTracker {
             streak_timeout: 3.0,
             announcement: None,
             total_multikills: 0,
+            pending_rewards: VecDeque::new(),
+            damage_boost: None,
+            adrenaline: 0.0,
         }
     }
 
@@ -208,6 +381,10 @@ impl KillStreakTracker {
         self.time_since_kill = 0.0;
         self.streak_count += 1;
 
+        for milestone in streak_milestones().iter().filter(|m| m.kills == self.streak_count) {
+            self.pending_rewards.push_back(milestone.reward);
+        }
+
         let (text, color) = match self.streak_count {
             2  => ("DOUBLE KILL!", [1.0, 0.9, 0.3, 1.0]),
             3  => ("TRIPLE KILL!", [1.0, 0.6, 0.1, 1.0]),
@@ -222,6 +399,39 @@ impl KillStreakTracker {
         self.announcement = Some((text.to_string(), 2.5, color));
     }
 
+    /// Give the current streak `secs` more before it times out.
+    pub fn extend_window(&mut self, secs: f32) {
+        self.time_since_kill -= secs;
+    }
+
+    /// Next milestone reward earned but not yet applied, oldest first.
+    pub fn consume_reward(&mut self) -> Option<StreakReward> {
+        self.pending_rewards.pop_front()
+    }
+
+    /// Start a timed buff reward (stratagem rewards are applied by the caller). Re-earning a buff
+    /// refreshes it.
+    pub fn start_buff(&mut self, reward: StreakReward) {
+        match reward {
+            StreakReward::DamageBoost { multiplier, secs } => self.damage_boost = Some((multiplier, secs)),
+            StreakReward::Adrenaline { secs } => self.adrenaline = self.adrenaline.max(secs),
+            StreakReward::ReadyStratagem(_) => {}
+        }
+    }
+
+    /// Bullet damage multiplier from an active damage boost (1 without one).
+    pub fn damage_multiplier(&self) -> f32 {
+        self.damage_boost.map_or(1.0, |(multiplier, _)| multiplier)
+    }
+
+    pub fn damage_boost_remaining(&self) -> f32 {
+        self.damage_boost.map_or(0.0, |(_, remaining)| remaining)
+    }
+
+    pub fn adrenaline_remaining(&self) -> f32 {
+        self.adrenaline
+    }
+
     pub fn update(&mut self, dt: f32) {
         self.time_since_kill += dt;
         if self.time_since_kill > self.streak_timeout {
@@ -233,6 +443,13 @@ impl KillStreakTracker {
                 self.announcement = None;
             }
         }
+        if let Some((_, remaining)) = &mut self.damage_boost {
+            *remaining -= dt;
+            if *remaining <= 0.0 {
+                self.damage_boost = None;
+            }
+        }
+        self.adrenaline = (self.adrenaline - dt).max(0.0);
     }
 }
 
@@ -248,6 +465,13 @@ pub(crate) enum WeatherState {
     Snow,
 }
 
+/// Default time (s) for a full weather change, e.g. clear sky building up to a blizzard.
+pub(crate) const DEFAULT_WEATHER_TRANSITION: f32 = 45.0;
+
+fn default_transition_duration() -> f32 {
+    DEFAULT_WEATHER_TRANSITION
+}
+
 /// Smooth weather that blends between states.
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub(crate) struct Weather {
@@ -258,6 +482,18 @@ pub(crate) struct Weather {
     pub cloud_density: f32,
     pub dust: f32,
     pub fog_density: f32,
+    /// Rainfall 0..1, eased toward the blended target (ramps in and out with the transition).
+    #[serde(default)]
+    pub rain_intensity: f32,
+    /// Snowfall 0..1, eased toward the blended target.
+    #[serde(default)]
+    pub snow_intensity: f32,
+    /// Seconds for `blend` to go from 0 to 1.
+    #[serde(default = "default_transition_duration")]
+    pub transition_duration: f32,
+    /// Direction the prevailing wind blows toward (radians about +Y, 0 = +X).
+    #[serde(default)]
+    pub wind_heading: f32,
 }
 
 impl Weather {
@@ -270,13 +506,15 @@ impl Weather {
             cloud_density: 0.2,
             dust: 0.05,
             fog_density: 0.0002,
+            rain_intensity: 0.0,
+            snow_intensity: 0.0,
+            transition_duration: DEFAULT_WEATHER_TRANSITION,
+            wind_heading: 0.0,
         }
     }
 
     /// Create weather with a random initial state (for per-planet variety).
-    pub fn random() -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+    pub fn random(rng: &mut GameRng) -> Self {
         let states = [
             WeatherState::Clear,
             WeatherState::Cloudy,
@@ -284,10 +522,11 @@ impl Weather {
             WeatherState::Storm,
             WeatherState::Snow,
         ];
-        let idx = rng.gen_range(0..states.len());
-        let state = states[idx];
+        let state = states[rng.range(0..states.len())];
         let (cloud_density, dust, fog_density) = Self::params_for(state);
-        let hold_timer = 15.0 + rng.gen::<f32>() * 50.0;
+        let (rain_intensity, snow_intensity) = Self::precipitation_for(state);
+        let hold_timer = 15.0 + rng.unit() * 50.0;
+        let wind_heading = rng.unit() * std::f32::consts::TAU;
         Self {
             current: state,
             target: state,
@@ -296,6 +535,10 @@ impl Weather {
             cloud_density,
             dust,
             fog_density,
+            rain_intensity,
+            snow_intensity,
+            transition_duration: DEFAULT_WEATHER_TRANSITION,
+            wind_heading,
         }
     }
 
@@ -309,6 +552,57 @@ impl Weather {
         }
     }
 
+    /// (rain, snow) intensity once fully settled into `state`.
+    fn precipitation_for(state: WeatherState) -> (f32, f32) {
+        match state {
+            WeatherState::Clear | WeatherState::Cloudy => (0.0, 0.0),
+            WeatherState::Rain  => (0.6, 0.0),
+            WeatherState::Storm => (1.0, 0.0),
+            WeatherState::Snow  => (0.0, 1.0),
+        }
+    }
+
+    /// (wind strength, gust strength) once fully settled into `state`.
+    fn wind_for(state: WeatherState) -> (f32, f32) {
+        match state {
+            WeatherState::Clear  => (1.0, 0.3),
+            WeatherState::Cloudy => (3.0, 0.6),
+            WeatherState::Rain   => (5.0, 0.9),
+            WeatherState::Storm  => (11.0, 1.6),
+            WeatherState::Snow   => (8.0, 1.3),
+        }
+    }
+
+    /// Prevailing wind (vector along `wind_heading`) and gust strength, blended during transitions.
+    /// Calm skies barely stir cloth; storms and blizzards blow hard and gusty.
+    pub fn wind(&self) -> (Vec3, f32) {
+        let (w0, g0) = Self::wind_for(self.current);
+        let (w1, g1) = Self::wind_for(self.target);
+        let t = self.blend;
+        let dir = Vec3::new(self.wind_heading.cos(), 0.0, self.wind_heading.sin());
+        (dir * (w0 + (w1 - w0) * t), g0 + (g1 - g0) * t)
+    }
+
+    /// Start changing toward `target` (e.g. scripted storm). Mid-transition, the change starts from
+    /// whichever state the sky is closer to; the eased values keep it from popping.
+    pub fn set_target(&mut self, target: WeatherState) {
+        if target == self.target {
+            return;
+        }
+        if target == self.current {
+            // Turning back: reverse the blend in place
+            self.current = self.target;
+            self.blend = 1.0 - self.blend;
+        } else if self.blend > 0.5 {
+            self.current = self.target;
+            self.blend = 0.0;
+        } else {
+            self.blend = 0.0;
+        }
+        self.target = target;
+        self.hold_timer = self.hold_timer.max(self.transition_duration);
+    }
+
     /// Sky color tint for current weather (blended during transition). Multiply with planet atmosphere for moody sky.
     pub fn atmosphere_tint(&self) -> [f32; 3] {
         let tint_for = |s: WeatherState| -> [f32; 3] {
@@ -332,7 +626,7 @@ impl Weather {
 
     pub fn update(&mut self, dt: f32) {
         if self.current != self.target {
-            self.blend += dt * 0.15;
+            self.blend += dt / self.transition_duration.max(0.1);
             if self.blend >= 1.0 {
                 self.blend = 0.0;
                 self.current = self.target;
@@ -341,15 +635,29 @@ impl Weather {
 
         let (c0, d0, f0) = Self::params_for(self.current);
         let (c1, d1, f1) = Self::params_for(self.target);
+        let (r0, s0) = Self::precipitation_for(self.current);
+        let (r1, s1) = Self::precipitation_for(self.target);
+        // Clouds and fog build first; precipitation only starts once the sky has mostly turned
+        // (and stops early when it clears).
         let t = self.blend;
+        let t_precip = if r1 + s1 > r0 + s0 {
+            ((t - 0.3) / 0.7).clamp(0.0, 1.0)
+        } else {
+            (t / 0.7).clamp(0.0, 1.0)
+        };
+        let t_precip = t_precip * t_precip * (3.0 - 2.0 * t_precip);
         let target_cloud = c0 + (c1 - c0) * t;
         let target_dust = d0 + (d1 - d0) * t;
         let target_fog = f0 + (f1 - f0) * t;
+        let target_rain = r0 + (r1 - r0) * t_precip;
+        let target_snow = s0 + (s1 - s0) * t_precip;
 
-        let rate = dt * 2.0;
+        let rate = (dt * 2.0).min(1.0);
         self.cloud_density += (target_cloud - self.cloud_density) * rate;
         self.dust += (target_dust - self.dust) * rate;
         self.fog_density += (target_fog - self.fog_density) * rate;
+        self.rain_intensity += (target_rain - self.rain_intensity) * rate;
+        self.snow_intensity += (target_snow - self.snow_intensity) * rate;
 
         self.hold_timer -= dt;
         if self.hold_timer <= 0.0 && self.current == self.target {
@@ -384,14 +692,9 @@ impl Weather {
         }
     }
 
-    /// Spawn rate (per frame) and fall speed for rain. Only active when current/target is Rain or Storm.
+    /// Spawn rate (per second) and fall speed for rain; follows `rain_intensity`.
     pub fn rain_params(&self) -> (u32, f32) {
-        let rain_amount = match (self.current, self.target) {
-            (WeatherState::Rain, _) | (_, WeatherState::Rain) | (WeatherState::Storm, _) | (_, WeatherState::Storm) => {
-                ((self.cloud_density - 0.5) / 0.5).clamp(0.0, 1.0)
-            }
-            _ => 0.0,
-        };
+        let rain_amount = self.rain_intensity.clamp(0.0, 1.0);
         if rain_amount < 0.01 {
             (0, 0.0)
         } else {
@@ -401,14 +704,14 @@ impl Weather {
         }
     }
 
-    /// Spawn rate (per frame) and fall speed for snow. Only active when current/target is Snow.
+    /// Snowfall intensity 0..1 (drives snow particles and ground accumulation).
+    pub fn snow_amount(&self) -> f32 {
+        self.snow_intensity.clamp(0.0, 1.0)
+    }
+
+    /// Spawn rate (per frame) and fall speed for snow; follows `snow_intensity`.
     pub fn snow_params(&self) -> (u32, f32) {
-        let snow_amount = match (self.current, self.target) {
-            (WeatherState::Snow, _) | (_, WeatherState::Snow) => {
-                ((self.cloud_density - 0.45) / 0.35).clamp(0.0, 1.0)
-            }
-            _ => 0.0,
-        };
+        let snow_amount = self.snow_amount();
         if snow_amount < 0.01 {
             (0, 0.0)
         } else {
@@ -454,8 +757,15 @@ impl WarpSequence {
 
 /// Approach craft state: flyable small craft toward planet.
 pub(crate) struct ApproachFlightState {
-    pub position: Vec3,
-    pub velocity: Vec3,
+    pub craft: FlyingVehicleController,
+}
+
+impl ApproachFlightState {
+    /// Craft at rest at `position`, nose along `forward`, with the approach preset's feel.
+    pub fn new(position: Vec3, forward: Vec3, mode: FlightMode) -> Self {
+        let tuning = FlightTuning::APPROACH_CRAFT.with_mode(mode);
+        Self { craft: FlyingVehicleController::new(position, Vec3::ZERO, forward, tuning) }
+    }
 }
 
 // ── Drop Pod ────────────────────────────────────────────────────────────────
@@ -731,14 +1041,14 @@ impl SquadDropSequence {
             let ground_y = sample_terrain(pod.position.x, pod.position.z);
             if pod.position.y <= ground_y + 2.5 {
                 pod.landed = true;
-                let (name, kind, formation) = SQUAD_DROP_DATA[pod.squad_index];
+                let (name, kind) = SQUAD_DROP_DATA[pod.squad_index];
                 spawn_one_squad_mate(
                     world,
                     pod.position,
                     ground_y,
                     name,
                     kind,
-                    formation,
+                    pod.squad_index,
                 );
             }
         }
@@ -752,12 +1062,72 @@ impl SquadDropSequence {
 
 // ── Supply Crate ───────────────────────────────────────────────────────────
 
-/// Supply drop crate — stratagem call-in (Helldivers 2 style).
+/// Restocks each supply crate holds.
+pub const SUPPLY_CRATE_CHARGES: u32 = 4;
+/// Seconds the lid stays popped after a restock; the crate can't be used again meanwhile.
+pub const SUPPLY_RESTOCK_SECS: f32 = 0.8;
+/// Seconds a supply crate stays on the field before it's cleaned up.
+pub const SUPPLY_CRATE_LIFETIME: f32 = 120.0;
+
+/// Supply drop crate — stratagem call-in (Helldivers 2 style). Troopers walk up and press
+/// interact to restock; each crate holds a few charges.
 #[derive(Debug, Clone)]
 pub struct SupplyCrate {
+    /// Ground position under the crate.
     pub position: Vec3,
     pub lifetime: f32,
-    pub used: bool,
+    /// Restocks left.
+    pub charges: u32,
+    /// Seconds left in the restock animation.
+    restock_timer: f32,
+}
+
+impl SupplyCrate {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            position,
+            lifetime: 0.0,
+            charges: SUPPLY_CRATE_CHARGES,
+            restock_timer: 0.0,
+        }
+    }
+
+    /// Has charges left and isn't mid-restock.
+    pub fn can_use(&self) -> bool {
+        self.charges > 0 && self.restock_timer <= 0.0
+    }
+
+    /// Spend one charge on `player`: tops off every weapon's reserve ammo, health and grenades,
+    /// plus a little armor. Returns false (nothing spent) if the crate can't be used right now.
+    pub fn use_charge(&mut self, player: &mut fps::FPSPlayer) -> bool {
+        if !self.can_use() {
+            return false;
+        }
+        self.charges -= 1;
+        self.restock_timer = SUPPLY_RESTOCK_SECS;
+        for weapon in &mut player.weapons {
+            weapon.reserve_ammo = weapon.reserve_ammo.max(weapon.full_reserve());
+        }
+        player.health = player.max_health;
+        player.add_armor(25.0);
+        player.grenades = MAX_FRAG_GRENADES;
+        true
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.lifetime += dt;
+        self.restock_timer = (self.restock_timer - dt).max(0.0);
+    }
+
+    /// Restock animation progress: 0 at rest, rising to 1 the moment a charge is used.
+    pub fn restock_anim(&self) -> f32 {
+        self.restock_timer / SUPPLY_RESTOCK_SECS
+    }
+
+    /// Emptied (after its last restock finishes) or left on the field too long.
+    pub fn is_spent(&self) -> bool {
+        (self.charges == 0 && self.restock_timer <= 0.0) || self.lifetime >= SUPPLY_CRATE_LIFETIME
+    }
 }
 
 // ── Game Messages ──────────────────────────────────────────────────────────
@@ -818,13 +1188,19 @@ impl GameMessages {
 
 #[cfg(test)]
 mod tests {
-    use super::DebugSettings;
+    use super::{
+        builtin_streak_milestones, parse_streak_milestones, DebugSettings, KillStreakTracker, ScreenShake, StreakReward,
+        SupplyCrate, Weather, WeatherState, DEFAULT_WEATHER_TRANSITION, SUPPLY_CRATE_CHARGES, SUPPLY_RESTOCK_SECS,
+    };
+    use crate::fps::{FPSPlayer, PlayerClass};
+    use crate::stratagem::Stratagem;
+    use glam::{Quat, Vec3};
 
     #[test]
     fn debug_settings_menu_item_count() {
         let d = DebugSettings::new();
-        assert_eq!(d.menu_item_count(), 15);
-        assert_eq!(d.menu_items().len(), 15);
+        assert_eq!(d.menu_item_count(), 16);
+        assert_eq!(d.menu_items().len(), 16);
     }
 
     #[test]
@@ -837,4 +1213,146 @@ mod tests {
         d.toggle_selected();
         assert!(!d.noclip);
     }
+
+    #[test]
+    fn streak_milestones_queue_rewards_once_and_shipped_data_matches_builtin() {
+        assert_eq!(
+            parse_streak_milestones(include_str!("../../../killstreaks.ron")).expect("killstreaks.ron parses"),
+            builtin_streak_milestones()
+        );
+
+        let mut streaks = KillStreakTracker::new();
+        for _ in 0..9 {
+            streaks.register_kill();
+        }
+        assert!(matches!(streaks.consume_reward(), Some(StreakReward::Adrenaline { .. })));
+        assert_eq!(streaks.consume_reward(), None);
+        streaks.register_kill();
+        assert_eq!(streaks.consume_reward(), Some(StreakReward::ReadyStratagem(Stratagem::SupplyDrop)));
+
+        // The streak lapses; the next kill starts over without re-earning anything
+        streaks.update(streaks.streak_timeout + 0.1);
+        streaks.register_kill();
+        assert_eq!(streaks.consume_reward(), None);
+
+        streaks.start_buff(StreakReward::DamageBoost { multiplier: 1.5, secs: 2.0 });
+        assert_eq!(streaks.damage_multiplier(), 1.5);
+        streaks.update(2.5);
+        assert_eq!(streaks.damage_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn weather_builds_up_to_snow_instead_of_snapping() {
+        let mut w = Weather::new();
+        w.hold_timer = 1000.0;
+        w.set_target(WeatherState::Snow);
+        let step = |w: &mut Weather, seconds: f32| {
+            for _ in 0..(seconds * 10.0) as usize {
+                w.update(0.1);
+            }
+        };
+
+        step(&mut w, 5.0);
+        assert_eq!(w.current, WeatherState::Clear);
+        assert!(w.snow_amount() < 0.01, "no snowfall while clouds are still gathering");
+        assert!(w.cloud_density > 0.2);
+
+        step(&mut w, DEFAULT_WEATHER_TRANSITION * 0.6);
+        let mid = w.snow_amount();
+        assert!(mid > 0.05 && mid < 0.9, "snow ramps in, got {mid}");
+
+        step(&mut w, DEFAULT_WEATHER_TRANSITION);
+        assert_eq!(w.current, WeatherState::Snow);
+        assert!(w.snow_amount() > 0.95);
+        assert!(w.snow_params().0 > 70);
+    }
+
+    #[test]
+    fn weather_set_target_back_reverses_blend() {
+        let mut w = Weather::new();
+        w.hold_timer = 1000.0;
+        w.set_target(WeatherState::Storm);
+        for _ in 0..100 {
+            w.update(0.1);
+        }
+        let blend = w.blend;
+        w.set_target(WeatherState::Clear);
+        assert_eq!((w.current, w.target), (WeatherState::Storm, WeatherState::Clear));
+        assert!((w.blend - (1.0 - blend)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn wind_picks_up_as_a_storm_rolls_in() {
+        let mut w = Weather::new();
+        w.hold_timer = 1000.0;
+        w.wind_heading = std::f32::consts::FRAC_PI_2;
+        let (calm, calm_gust) = w.wind();
+        w.set_target(WeatherState::Storm);
+        for _ in 0..(DEFAULT_WEATHER_TRANSITION * 5.0) as usize {
+            w.update(0.1);
+        }
+        let (mid, _) = w.wind();
+        for _ in 0..(DEFAULT_WEATHER_TRANSITION * 10.0) as usize {
+            w.update(0.1);
+        }
+        let (storm, storm_gust) = w.wind();
+        assert!(calm.length() < mid.length() && mid.length() < storm.length());
+        assert!(storm_gust > calm_gust);
+        assert!(storm.normalize().dot(glam::Vec3::Z) > 0.99, "blows along the heading");
+    }
+
+    #[test]
+    fn directional_trauma_sways_along_the_hit() {
+        let mut shake = ScreenShake::new();
+        let (mut along, mut across) = (0.0, 0.0);
+        for _ in 0..200 {
+            shake.add_directional_trauma(Vec3::new(1.0, 0.4, 0.0), 1.0);
+            shake.update(1.0 / 60.0);
+            along += shake.offset.x.abs();
+            across += shake.offset.z.abs();
+        }
+        assert!(along > across * 2.0, "along {along} vs across {across}");
+    }
+
+    #[test]
+    fn recoil_twists_without_moving_and_all_trauma_decays() {
+        let mut shake = ScreenShake::new();
+        shake.add_rotational_trauma(0.8);
+        shake.update(0.01);
+        assert_eq!(shake.offset, Vec3::ZERO);
+        assert!(shake.rotation.angle_between(Quat::IDENTITY) > 0.0);
+
+        shake.add_trauma(1.0);
+        for _ in 0..60 {
+            shake.update(1.0 / 60.0);
+        }
+        assert_eq!((shake.trauma, shake.rotational_trauma), (0.0, 0.0));
+        shake.update(1.0 / 60.0);
+        assert_eq!(shake.offset, Vec3::ZERO);
+        assert_eq!(shake.rotation, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn supply_crate_tops_off_a_trooper_until_its_charges_run_out() {
+        let mut player = FPSPlayer::new(PlayerClass::Hunter, "Rico".to_string(), Vec3::ZERO);
+        let mut supply = SupplyCrate::new(Vec3::ZERO);
+        player.health = 10.0;
+        player.grenades = 0;
+        player.weapons[0].reserve_ammo = 0;
+
+        assert!(supply.use_charge(&mut player));
+        assert_eq!(player.health, player.max_health);
+        assert_eq!(player.weapons[0].reserve_ammo, player.weapons[0].full_reserve());
+        assert!(player.grenades > 0);
+        assert!(!supply.use_charge(&mut player), "used again mid-restock");
+
+        for _ in 1..SUPPLY_CRATE_CHARGES {
+            supply.update(SUPPLY_RESTOCK_SECS + 0.01);
+            assert!(supply.use_charge(&mut player));
+        }
+        assert!(!supply.is_spent(), "crate vanished before its restock finished");
+        supply.update(SUPPLY_RESTOCK_SECS + 0.01);
+        assert!(!supply.use_charge(&mut player));
+        assert!(supply.is_spent());
+    }
 }
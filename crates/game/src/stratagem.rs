@@ -0,0 +1,338 @@
+//! Stratagem call-in (Helldivers 2 style). Classic mode calls a stratagem on a single key press;
+//! code-entry mode opens an input capture while the stratagem key is held, and the directional
+//! code (arrow keys or WASD) must be tapped in before it is called down.
+//! Every stratagem's cooldown lives in one `HashMap<Stratagem, Cooldown>` the HUD tray reads.
+
+use std::collections::HashMap;
+
+use input::InputState;
+use winit::keyboard::KeyCode;
+
+/// Seconds the code panel flashes red after a wrong direction.
+pub const MISTAKE_FLASH: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StratagemDir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl StratagemDir {
+    /// Glyph for the code panel.
+    pub fn arrow(self) -> &'static str {
+        match self {
+            StratagemDir::Up => "^",
+            StratagemDir::Down => "v",
+            StratagemDir::Left => "<",
+            StratagemDir::Right => ">",
+        }
+    }
+
+    /// Direction tapped this frame (arrow keys or WASD).
+    fn pressed(input: &InputState) -> Option<Self> {
+        const KEYS: [(KeyCode, KeyCode, StratagemDir); 4] = [
+            (KeyCode::ArrowUp, KeyCode::KeyW, StratagemDir::Up),
+            (KeyCode::ArrowDown, KeyCode::KeyS, StratagemDir::Down),
+            (KeyCode::ArrowLeft, KeyCode::KeyA, StratagemDir::Left),
+            (KeyCode::ArrowRight, KeyCode::KeyD, StratagemDir::Right),
+        ];
+        KEYS.iter()
+            .find(|(arrow, wasd, _)| input.is_key_pressed(*arrow) || input.is_key_pressed(*wasd))
+            .map(|&(_, _, dir)| dir)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Stratagem {
+    /// B: tac fighter fleet on your position.
+    OrbitalStrike,
+    /// N: ammo + health crate ahead of you.
+    SupplyDrop,
+    /// R: full heal, armor and ammo from orbit.
+    Reinforce,
+    /// Z: call the retrieval boat.
+    Extraction,
+    /// Orbital barrage on a red smoke designator (no key of its own).
+    Artillery,
+    /// H: red smoke grenade.
+    Smoke,
+}
+
+impl Stratagem {
+    /// Tray order.
+    pub const ALL: [Stratagem; 6] = [
+        Stratagem::OrbitalStrike,
+        Stratagem::SupplyDrop,
+        Stratagem::Reinforce,
+        Stratagem::Extraction,
+        Stratagem::Artillery,
+        Stratagem::Smoke,
+    ];
+
+    /// Call-in key; `None` for stratagems triggered some other way (smoke is thrown with H,
+    /// artillery follows the smoke).
+    pub fn key(self) -> Option<KeyCode> {
+        match self {
+            Stratagem::OrbitalStrike => Some(KeyCode::KeyB),
+            Stratagem::SupplyDrop => Some(KeyCode::KeyN),
+            Stratagem::Reinforce => Some(KeyCode::KeyR),
+            Stratagem::Extraction => Some(KeyCode::KeyZ),
+            Stratagem::Artillery | Stratagem::Smoke => None,
+        }
+    }
+
+    /// Key hint shown over the tray slot.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Stratagem::OrbitalStrike => "B",
+            Stratagem::SupplyDrop => "N",
+            Stratagem::Reinforce => "R",
+            Stratagem::Extraction => "Z",
+            Stratagem::Artillery => "SMK",
+            Stratagem::Smoke => "H",
+        }
+    }
+
+    /// Short glyph drawn inside the tray slot.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Stratagem::OrbitalStrike => "CAS",
+            Stratagem::SupplyDrop => "SUP",
+            Stratagem::Reinforce => "REI",
+            Stratagem::Extraction => "EXT",
+            Stratagem::Artillery => "ART",
+            Stratagem::Smoke => "SMK",
+        }
+    }
+
+    pub fn color(self) -> [f32; 4] {
+        match self {
+            Stratagem::OrbitalStrike => [0.9, 0.6, 0.2, 1.0],
+            Stratagem::SupplyDrop => [0.2, 0.8, 0.4, 1.0],
+            Stratagem::Reinforce => [0.9, 0.5, 0.2, 1.0],
+            Stratagem::Extraction => [0.3, 0.9, 0.3, 1.0],
+            Stratagem::Artillery => [1.0, 0.3, 0.15, 1.0],
+            Stratagem::Smoke => [0.9, 0.3, 0.3, 1.0],
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stratagem::OrbitalStrike => "ORBITAL STRIKE",
+            Stratagem::SupplyDrop => "SUPPLY DROP",
+            Stratagem::Reinforce => "REINFORCE",
+            Stratagem::Extraction => "EXTRACTION",
+            Stratagem::Artillery => "ORBITAL ARTILLERY",
+            Stratagem::Smoke => "SMOKE GRENADE",
+        }
+    }
+
+    /// Directional code tapped in while the stratagem key is held (empty without a key).
+    pub fn code(self) -> &'static [StratagemDir] {
+        use StratagemDir::*;
+        match self {
+            Stratagem::OrbitalStrike => &[Up, Right, Down, Right],
+            Stratagem::SupplyDrop => &[Down, Down, Up, Right],
+            Stratagem::Reinforce => &[Up, Down, Right, Left, Up],
+            Stratagem::Extraction => &[Down, Up, Left, Down, Up],
+            Stratagem::Artillery | Stratagem::Smoke => &[],
+        }
+    }
+
+    /// Stratagem whose call-in key was pressed this frame.
+    fn pressed(input: &InputState) -> Option<Self> {
+        Stratagem::ALL
+            .into_iter()
+            .find(|s| s.key().is_some_and(|key| input.is_key_pressed(key)))
+    }
+}
+
+/// Rearm timer for one stratagem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cooldown {
+    remaining: f32,
+    /// Length of the current cooldown (for the tray fill).
+    duration: f32,
+}
+
+impl Cooldown {
+    pub fn start(&mut self, seconds: f32) {
+        self.remaining = seconds.max(0.0);
+        self.duration = self.remaining;
+    }
+
+    /// Count down; true on the frame the stratagem becomes ready again.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        let was_cooling = self.remaining > 0.0;
+        self.remaining = (self.remaining - dt).max(0.0);
+        was_cooling && self.remaining <= 0.0
+    }
+
+    pub fn reset(&mut self) {
+        self.remaining = 0.0;
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Share of the cooldown still to go: 1 = just used, 0 = ready.
+    pub fn cooldown_fraction(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.remaining / self.duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Cooldowns at deployment: everything ready except the CAS fleet (first run after 45 s).
+pub fn initial_cooldowns() -> HashMap<Stratagem, Cooldown> {
+    let mut cooldowns: HashMap<Stratagem, Cooldown> =
+        Stratagem::ALL.into_iter().map(|s| (s, Cooldown::default())).collect();
+    cooldowns.entry(Stratagem::OrbitalStrike).or_default().start(45.0);
+    cooldowns
+}
+
+/// An open code-entry capture: which stratagem and how much of its code is entered.
+#[derive(Debug, Clone, Copy)]
+pub struct StratagemCapture {
+    pub stratagem: Stratagem,
+    /// Directions of the code entered correctly so far.
+    pub progress: usize,
+    /// Counts down after a wrong direction (the entry restarts).
+    pub mistake_timer: f32,
+}
+
+/// Tracks stratagem input each frame and reports which stratagem (if any) was called.
+#[derive(Debug, Clone, Default)]
+pub struct StratagemInput {
+    /// Require directional codes; false = classic single-key call-in.
+    code_entry: bool,
+    capture: Option<StratagemCapture>,
+}
+
+impl StratagemInput {
+    pub fn set_code_entry(&mut self, code_entry: bool) {
+        self.code_entry = code_entry;
+        self.capture = None;
+    }
+
+    /// The code being entered, if a stratagem key is held in code-entry mode.
+    pub fn capture(&self) -> Option<&StratagemCapture> {
+        self.capture.as_ref()
+    }
+
+    /// While capturing, WASD enters the code instead of moving.
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Advance one frame. Returns the stratagem called in this frame.
+    pub fn update(&mut self, input: &InputState, dt: f32) -> Option<Stratagem> {
+        if !self.code_entry {
+            return Stratagem::pressed(input);
+        }
+
+        // Releasing the stratagem key abandons the code
+        if let Some(capture) = self.capture {
+            if !capture.stratagem.key().is_some_and(|key| input.is_key_held(key)) {
+                self.capture = None;
+            }
+        }
+        if self.capture.is_none() {
+            self.capture = Stratagem::pressed(input).map(|stratagem| StratagemCapture { stratagem, progress: 0, mistake_timer: 0.0 });
+        }
+
+        let capture = self.capture.as_mut()?;
+        capture.mistake_timer = (capture.mistake_timer - dt).max(0.0);
+        let dir = StratagemDir::pressed(input)?;
+        let code = capture.stratagem.code();
+        if code[capture.progress] == dir {
+            capture.progress += 1;
+            if capture.progress == code.len() {
+                let called = capture.stratagem;
+                self.capture = None;
+                return Some(called);
+            }
+        } else {
+            capture.progress = 0;
+            capture.mistake_timer = MISTAKE_FLASH;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::ElementState;
+
+    fn stratagem_input(code_entry: bool) -> StratagemInput {
+        let mut strat = StratagemInput::default();
+        strat.set_code_entry(code_entry);
+        strat
+    }
+
+    fn tap(input: &mut InputState, strat: &mut StratagemInput, key: KeyCode) -> Option<Stratagem> {
+        input.begin_frame(0.016);
+        input.process_keyboard(key, ElementState::Pressed);
+        let called = strat.update(input, 0.016);
+        input.begin_frame(0.016);
+        input.process_keyboard(key, ElementState::Released);
+        called
+    }
+
+    #[test]
+    fn single_key_mode_calls_on_press() {
+        let mut input = InputState::new();
+        let mut strat = stratagem_input(false);
+        assert_eq!(tap(&mut input, &mut strat, KeyCode::KeyN), Some(Stratagem::SupplyDrop));
+        assert!(!strat.is_capturing());
+    }
+
+    #[test]
+    fn code_entry_requires_the_sequence_while_held() {
+        let mut input = InputState::new();
+        let mut strat = stratagem_input(true);
+
+        // Hold B: opens the capture, nothing called yet
+        input.process_keyboard(KeyCode::KeyB, ElementState::Pressed);
+        assert_eq!(strat.update(&input, 0.016), None);
+        assert!(strat.is_capturing());
+
+        // A wrong direction restarts the code
+        assert_eq!(tap(&mut input, &mut strat, KeyCode::ArrowUp), None);
+        assert_eq!(tap(&mut input, &mut strat, KeyCode::ArrowLeft), None);
+        let capture = strat.capture().unwrap();
+        assert_eq!(capture.progress, 0);
+        assert!(capture.mistake_timer > 0.0);
+
+        // Up, Right, Down, Right (mixing arrows and WASD) calls the strike
+        let keys = [KeyCode::KeyW, KeyCode::ArrowRight, KeyCode::KeyS, KeyCode::KeyD];
+        let results: Vec<_> = keys.iter().map(|k| tap(&mut input, &mut strat, *k)).collect();
+        assert_eq!(results, [None, None, None, Some(Stratagem::OrbitalStrike)]);
+        assert!(!strat.is_capturing());
+    }
+
+    #[test]
+    fn releasing_the_key_abandons_the_code() {
+        let mut input = InputState::new();
+        let mut strat = stratagem_input(true);
+        input.process_keyboard(KeyCode::KeyN, ElementState::Pressed);
+        strat.update(&input, 0.016);
+        tap(&mut input, &mut strat, KeyCode::ArrowDown);
+        assert_eq!(strat.capture().unwrap().progress, 1);
+
+        input.begin_frame(0.016);
+        input.process_keyboard(KeyCode::KeyN, ElementState::Released);
+        assert_eq!(strat.update(&input, 0.016), None);
+        assert!(!strat.is_capturing());
+    }
+}
@@ -7,6 +7,8 @@
 use glam::Vec3;
 use rand::Rng;
 
+use crate::flight::{FlightControls, FlightMode, FlightTuning, FlyingVehicleController};
+
 /// Phase of a Tac Fighter bombing run.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TacFighterPhase {
@@ -35,6 +37,8 @@ pub enum AttackPattern {
     DiveBomb,
 }
 
+/// Fighters on station at once; a CAS call brings four.
+pub const MAX_TAC_FIGHTERS: usize = 8;
 /// Minimum distance from player for bomb impact when avoiding danger close.
 const SAFE_DROP_RANGE: f32 = 55.0;
 /// Chance (0–1) that a fighter will use danger-close strikes despite the risk.
@@ -43,6 +47,11 @@ const DANGER_CLOSE_CHANCE: f32 = 0.08;
 const BOMB_FALL_TIME: f32 = 1.8;
 /// Velocity smoothing: blend factor per second (higher = snappier, lower = more inertia).
 const VELOCITY_SMOOTH: f32 = 3.0;
+/// Minimum time between bombs on a piloted run.
+const PILOT_BOMB_INTERVAL: f32 = 0.3;
+/// Piloted fighters are kept between these heights above the ground (m).
+const PILOT_MIN_ALTITUDE: f32 = 12.0;
+const PILOT_MAX_ALTITUDE: f32 = 300.0;
 
 /// A Tac Fighter performing close air support.
 pub struct TacFighter {
@@ -70,6 +79,8 @@ pub struct TacFighter {
     pub allow_danger_close: bool,
     /// Corvette index to return to when RTB (0–7). Used for Departing phase.
     pub home_corvette_index: u8,
+    /// Set while the player is flying this fighter; the AI run is suspended.
+    pilot: Option<FlyingVehicleController>,
 }
 
 impl TacFighter {
@@ -127,7 +138,65 @@ impl TacFighter {
             pattern_state: 0.0,
             allow_danger_close,
             home_corvette_index,
+            pilot: None,
+        }
+    }
+
+    pub fn is_piloted(&self) -> bool {
+        self.pilot.is_some()
+    }
+
+    /// Player takes the stick: the AI run is suspended and the fighter is flown from its current heading.
+    pub fn take_controls(&mut self, mode: FlightMode) {
+        let forward = self.velocity.try_normalize().unwrap_or(self.approach_dir);
+        let tuning = FlightTuning::TAC_FIGHTER.with_mode(mode);
+        self.pilot = Some(FlyingVehicleController::new(self.position, self.velocity, forward, tuning));
+        self.phase = TacFighterPhase::BombingRun;
+        self.phase_timer = 0.0;
+    }
+
+    /// Hand the fighter back to the AI, which flies it home.
+    pub fn release_controls(&mut self) {
+        self.pilot = None;
+        self.approach_dir = Vec3::new(self.velocity.x, 0.0, self.velocity.z).try_normalize().unwrap_or(self.approach_dir);
+        self.phase = TacFighterPhase::Departing;
+        self.phase_timer = 0.0;
+    }
+
+    /// The player-flown controller, if piloted.
+    pub fn pilot(&self) -> Option<&FlyingVehicleController> {
+        self.pilot.as_ref()
+    }
+
+    /// Fly one frame under player control, kept between the altitude limits over `ground_y`.
+    /// Returns bomb drop positions (at most one per `PILOT_BOMB_INTERVAL`).
+    pub fn update_piloted(&mut self, controls: &FlightControls, drop_bomb: bool, ground_y: f32, dt: f32) -> Vec<Vec3> {
+        let Some(pilot) = self.pilot.as_mut() else {
+            return Vec::new();
+        };
+        self.age += dt;
+        self.phase_timer += dt;
+        self.bomb_interval_timer += dt;
+        pilot.update(controls, dt);
+        let floor = ground_y + PILOT_MIN_ALTITUDE;
+        let ceiling = ground_y + PILOT_MAX_ALTITUDE;
+        if pilot.position.y < floor {
+            pilot.position.y = floor;
+            pilot.velocity.y = pilot.velocity.y.max(0.0);
+        } else if pilot.position.y > ceiling {
+            pilot.position.y = ceiling;
+            pilot.velocity.y = pilot.velocity.y.min(0.0);
+        }
+        self.position = pilot.position;
+        self.velocity = pilot.velocity;
+
+        let mut bomb_drops = Vec::new();
+        if drop_bomb && self.bombs_remaining > 0 && self.bomb_interval_timer > PILOT_BOMB_INTERVAL {
+            bomb_drops.push(self.position);
+            self.bombs_remaining -= 1;
+            self.bomb_interval_timer = 0.0;
         }
+        bomb_drops
     }
 
     /// Estimate where a bomb dropped now would impact (XZ plane).
@@ -2,36 +2,53 @@
 //!
 //! Extracted from main.rs to keep the game loop modular and maintainable.
 
+use audio::AudioBus;
 use glam::{DVec3, Vec3};
 use hecs::Entity;
 use procgen::PlanetSize;
+use rapier3d::prelude::SharedShape;
+use input::Action;
 use winit::keyboard::KeyCode;
 
 use crate::bug::Bug;
-use crate::fps::FPSPlayer;
-use crate::bug_entity::{PhysicsBug, update_bug_physics};
-use crate::destruction::{BugCorpse, BugGoreChunk, Debris};
+use crate::fps::{FPSPlayer, InteractionTarget, MissionType};
+use crate::hud::WaypointIcon;
+use crate::objective::Objective;
+use crate::bug_entity::{PhysicsBug, sync_ragdoll_transforms, update_bug_physics};
+use crate::destruction::{BugCorpse, BugGoreChunk, BurnCrater, Debris, EnvironmentalHazard, HazardType};
 use crate::effects::TracerProjectile;
-use crate::extraction::{ExtractionDropship, ExtractionMessage, ExtractionPhase};
+use crate::extraction::{ExtractionDropship, ExtractionMessage, ExtractionPhase, BOARDING_RADIUS};
 use crate::viewmodel::GroundedShellCasing;
+use crate::weapons::{self, FireZone, Projectile};
 use crate::horde_ai::apply_separation;
 use crate::skinny::Skinny;
+use crate::grenade::MAX_FRAG_GRENADES;
 use crate::smoke::{SmokeCloud, SmokeGrenade};
-use crate::citizen::{update_citizens, Citizen};
+use crate::stratagem::Stratagem;
+use crate::citizen::{update_citizen_panic, update_citizens};
 use crate::dialogue::DialogueState;
-use crate::squad::{despawn_squad, update_squad_combat, update_squad_movement, SquadMate};
+use crate::squad::{
+    despawn_squad, revive_downed_squad, update_downed_squad, update_squad_combat, update_squad_movement, SquadEvent,
+    SquadMate, SquadMateStatus,
+};
 use crate::fleet::{self, surface_corvette_positions};
-use crate::artillery::{ArtilleryBarrage, ArtilleryMuzzleFlash, ArtilleryShell, ArtilleryTrailParticle, GroundedArtilleryShell, SHELL_FIRE_DELAY, SHELLS_PER_BARRAGE};
+use crate::artillery::{predicted_impacts, ArtilleryBarrage, ArtilleryMuzzleFlash, ArtilleryShell, ArtilleryTrailParticle, GroundedArtilleryShell, ARTILLERY_GRAVITY, ARTILLERY_KILL_RADIUS, SHELL_FIRE_DELAY};
 use crate::tac_fighter::{TacBomb, TacFighter, TacFighterPhase};
-use engine_core::{Health, Lifetime, Transform};
+use engine_core::{store_previous_transforms, update_global_transforms, Health, Lifetime, Transform, Velocity};
 
-use crate::state::{InteractPrompt, WeatherState, INTERACT_KEY};
-use crate::{GamePhase, GameState, SupplyCrate};
+use crate::state::{InteractPrompt, StreakReward, WeatherState, INTERACT_KEY};
+use crate::{sound_occlusion, GamePhase, GameState, SupplyCrate, CITIZEN_SCREAM_SOUND};
+
+/// Chase camera offset behind a piloted Tac Fighter (craft-local: up, back).
+const PILOT_CHASE_OFFSET: Vec3 = Vec3::new(0.0, 4.0, 16.0);
+/// How fast (1/s) the view eases toward a new FOV (ADS zoom, speed kicks, back to base).
+pub(crate) const FOV_RATE: f32 = 12.0;
+/// Degrees added to the base FOV when sprinting, and at full drop pod descent speed.
+const SPRINT_FOV_KICK: f32 = 6.0;
+pub(crate) const DROP_FOV_KICK: f32 = 14.0;
 
 /// Run one frame of gameplay update. Called from `GameState::update_gameplay()`.
 pub fn gameplay(state: &mut GameState, dt: f32) {
-    const MAX_TAC_FIGHTERS: usize = 8;
-
     // Handle warp / quantum travel sequence — player stands at the front with pilot and captain, looking out the viewscreen
     if let Some(ref mut warp) = state.warp_sequence {
         warp.timer += dt;
@@ -54,6 +71,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
     // Environmental hazards: damage/slow when in radius (only on planet, when alive)
     if state.current_planet_idx.is_some() && state.player.is_alive {
         state.update_environmental_hazards(dt);
+        state.update_fire_zones(dt);
     }
 
     // Player movement (FPS walking or noclip based on debug settings)
@@ -61,7 +79,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
 
     // Advance orbital time (planets orbit) and universe time (day/night rotation)
     state.orbital_time += dt as f64 * 0.1;
-    let time_scale = state.debug.time_scale;
+    let time_scale = state.time.time_scale();
     state.universe_time_sec += dt as f64 * time_scale as f64;
 
     // Update universe position based on camera
@@ -126,79 +144,60 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         }
     }
 
-    // Dialogue: handle open state (number keys = choices, Escape = close)
-    if state.dialogue_state.is_open() {
-        if state.input.is_key_pressed(KeyCode::Escape) {
-            state.dialogue_state = DialogueState::Closed;
-        } else {
-            let idx = if state.input.is_key_pressed(KeyCode::Digit1) { 0 }
-                else if state.input.is_key_pressed(KeyCode::Digit2) { 1 }
-                else if state.input.is_key_pressed(KeyCode::Digit3) { 2 }
-                else if state.input.is_key_pressed(KeyCode::Digit4) { 3 }
-                else { 4 };
-            if idx < 4 {
-                if let Some((_, choices)) = state.dialogue_state.current_line_and_choices() {
-                    if idx < choices.len() {
-                        state.dialogue_state.select_choice(idx);
-                    }
-                }
-            }
+    // Citizens caught near bugs (Rescue colonists; Earth stays bug-free) run for the LZ or cower
+    if state.current_planet_idx.is_some() {
+        let bugs: Vec<Vec3> = state
+            .world
+            .query::<(&Transform, &Health, &Bug)>()
+            .iter()
+            .filter(|(_, (_, health, _))| !health.is_dead())
+            .map(|(_, (transform, _, _))| transform.position)
+            .collect();
+        let mut refuges: Vec<Vec3> = state.defense_base.iter().map(|&(center, _)| center).collect();
+        refuges.extend(state.extraction.as_ref().map(|dropship| dropship.lz_position));
+        if let Some(Objective::Rescue { lz, .. }) = state.mission.active_objective() {
+            refuges.push(*lz);
         }
-    } else if state.phase == GamePhase::Playing
-        && state.current_planet_idx.is_some()
-        && state.settlement_center.is_some()
-    {
-        // Find nearest citizen within 3m: set interaction prompt every frame, open dialogue on E
-        const TALK_RANGE_SQ: f32 = 3.0 * 3.0;
-        let mut nearest: Option<(Entity, f32, String, usize)> = None;
-        for (entity, (transform, citizen)) in state.world.query::<(&Transform, &Citizen)>().iter() {
-            let dist_sq = transform.position.distance_squared(cam_pos);
-            if dist_sq < TALK_RANGE_SQ {
-                if nearest.as_ref().map(|(_, d, _, _)| *d > dist_sq).unwrap_or(true) {
-                    nearest = Some((entity, dist_sq, citizen.display_name.clone(), citizen.dialogue_id));
+        let screams = update_citizen_panic(&mut state.world, &bugs, &refuges, dt, |x, z| {
+            state.chunk_manager.sample_height(x, z)
+        });
+        let listener = state.camera.position();
+        if let Some(audio) = &mut state.audio {
+            for position in screams {
+                let mouth = position + Vec3::Y * 1.4;
+                if let Err(e) = audio.play_at_position_with_variation(
+                    CITIZEN_SCREAM_SOUND,
+                    mouth,
+                    0.9..=1.15,
+                    0.8..=1.0,
+                    AudioBus::Sfx,
+                    sound_occlusion(&state.physics, listener, mouth),
+                ) {
+                    log::warn!("Citizen scream playback failed: {}", e);
                 }
             }
         }
-        if let Some((entity, _, name, dialogue_id)) = nearest {
+    }
+
+    // A citizen conversation ends if the speaker is gone (despawned with the settlement)
+    if let DialogueState::Open { speaker_entity: Some(speaker), .. } = state.dialogue_state {
+        if !state.world.contains(speaker) {
+            state.dialogue_state = DialogueState::Closed;
+        }
+    }
+
+    // Talk to the citizen under the crosshair (resolved in GameState::resolve_interaction_target;
+    // choices and Escape in open dialogue are handled once per frame in GameState::update)
+    if !state.dialogue_state.is_open() && state.phase == GamePhase::Playing {
+        if let Some(InteractionTarget::Speaker { name, dialogue_id, entity, .. }) = &state.player.interaction_target {
             state.interaction_prompt = Some(InteractPrompt {
                 key: INTERACT_KEY,
                 action: format!("Talk to {}", name),
             });
-            if state.input.is_key_pressed(KeyCode::KeyE) {
-                state.dialogue_state = DialogueState::Open {
-                    speaker_entity: Some(entity),
-                    speaker_name: name,
-                    dialogue_id,
-                    node_index: 0,
-                    showing_choices: true,
-                };
-            }
-        }
-    } else if state.phase == GamePhase::InShip
-        && !state.dialogue_state.is_open()
-        && state.input.is_key_pressed(KeyCode::KeyE)
-    {
-        // Find nearest Roger Young crew NPC within 3m and open dialogue
-        const TALK_RANGE_SQ: f32 = 3.0 * 3.0;
-        let cam_pos = state.camera.position();
-        let mut nearest: Option<(f32, String, usize)> = None;
-        for npc in crate::roger_young_interior_npcs() {
-            let dist_sq = npc.position.distance_squared(cam_pos);
-            if dist_sq < TALK_RANGE_SQ {
-                if nearest.as_ref().map(|(d, _, _)| *d > dist_sq).unwrap_or(true) {
-                    nearest = Some((dist_sq, npc.name.to_string(), npc.dialogue_id));
-                }
+            if state.input.is_interact_pressed() {
+                state.dialogue_state = DialogueState::open(*entity, name.clone(), *dialogue_id, &mut state.dialogue_memory);
             }
         }
-        if let Some((_, name, dialogue_id)) = nearest {
-            state.dialogue_state = DialogueState::Open {
-                speaker_entity: None,
-                speaker_name: name,
-                dialogue_id,
-                node_index: 0,
-                showing_choices: true,
-            };
-        }
     }
 
     // Update flow field target to player position (for AI)
@@ -211,9 +210,14 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         // Bug holes spawn bugs near themselves
         state.update_bug_holes(dt);
     }
+    state.update_burrowing_bugs(dt);
+    state.update_hive_queen(dt);
 
-    // Update bugs (AI + movement)
-    state.horde_ai.update(&mut state.world, dt);
+    // Update bugs (AI + movement), then land their charges, leaps, slams and acid
+    let strikes = state.horde_ai.update(&mut state.world, dt);
+    state.update_bug_strikes(strikes, dt);
+    state.update_cover(dt);
+    state.update_hole_collapses(dt);
 
     // Apply separation force so bugs don't stack on each other
     // Runs on frame_count % 4 == 0 (staggered with other expensive systems)
@@ -236,7 +240,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         }
     }
 
-    // Squad mates: follow player and stick to terrain/water surface
+    // Squad mates: hold formation on the player (cover when bugs close in) and stick to terrain/water surface
     // When extraction is waiting: squadmates run toward the LZ (NO TROOPER LEFT BEHIND!)
     let squad_target = if let Some(ref dropship) = state.extraction {
         if dropship.phase == ExtractionPhase::Waiting {
@@ -247,19 +251,48 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
     } else {
         state.player.position
     };
+    // Single file through canyons and trenches, wedge in the open
+    state.squad_formation = state.squad_formation.for_terrain(squad_target, state.camera.forward(), |x, z| {
+        state.chunk_manager.walkable_height(x, z)
+    });
     update_squad_movement(
         &mut state.world,
         squad_target,
+        state.camera.forward(),
+        state.squad_formation,
         dt,
         |x, z| state.chunk_manager.walkable_height(x, z),
     );
 
+    // Downed squad mates bleed out unless the player gets to them: look at one and press E
+    if state.current_planet_idx.is_some() {
+        for event in update_downed_squad(&mut state.world, dt) {
+            match event {
+                SquadEvent::Downed(name) => state.game_messages.warning(format!("{name} is DOWN! Get to them!")),
+                SquadEvent::BledOut(name) => state.game_messages.warning(format!("{name} bled out.")),
+            }
+        }
+        if let Some(InteractionTarget::Revivable(entity)) = state.player.interaction_target {
+            if let Ok((mate, health)) = state.world.query_one_mut::<(&mut SquadMate, &mut Health)>(entity) {
+                if let SquadMateStatus::Downed { bleed_out } = mate.status {
+                    state.interaction_prompt = Some(InteractPrompt {
+                        key: INTERACT_KEY,
+                        action: format!("Revive {} ({bleed_out:.0}s)", mate.name),
+                    });
+                    if state.input.is_interact_pressed() && mate.revive(health) {
+                        state.game_messages.success(format!("{} is back on their feet!", mate.name));
+                    }
+                }
+            }
+        }
+    }
+
     // Snap living bugs to terrain/water surface and sync kinematic physics bodies (only on planet)
     // Only snap bugs within 160m of the player – distant bugs are culled anyway
     if state.current_planet_idx.is_some() {
         let player_snap_pos = state.player.position;
-        for (_, (transform, health, physics_bug)) in
-            state.world.query_mut::<(&mut Transform, &Health, &PhysicsBug)>()
+        for (_, (transform, health, physics_bug, bug)) in
+            state.world.query_mut::<(&mut Transform, &Health, &PhysicsBug, Option<&Bug>)>()
         {
             if !health.is_dead() && !physics_bug.is_ragdoll {
                 let dist_sq = (transform.position.x - player_snap_pos.x).powi(2)
@@ -271,9 +304,13 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                     transform.position.x,
                     transform.position.z,
                 );
-                // Place bug center above terrain/water; extra clearance prevents slope clipping
-                let half_height = transform.scale.y * 0.6 + 0.15;
-                transform.position.y = surface_y + half_height;
+                // Place bug center above terrain/water; extra clearance prevents slope clipping.
+                // Bugs missing legs drag their belly along the ground.
+                let stance = if physics_bug.is_crawling() { 0.3 } else { 0.6 };
+                let half_height = transform.scale.y * stance + 0.15;
+                // Leaping Hoppers ride their arc above the stance; burrowers wait below it
+                let lift = bug.map_or(0.0, Bug::lift) - physics_bug.emerge_sink(transform.scale.y);
+                transform.position.y = surface_y + half_height + lift;
                 // Keep kinematic body in sync so collisions work
                 if let Some(handle) = physics_bug.body_handle {
                     state.physics.set_kinematic_position(handle, transform.position);
@@ -283,10 +320,11 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
     }
 
     // Bug physics and ragdolls (Euphoria-style)
-    update_bug_physics(&mut state.world, &mut state.physics, dt);
+    update_bug_physics(&mut state.world, &mut state.physics, &mut state.rng, dt);
 
     // Process dying bugs - spawn gore
     state.process_dying_bugs();
+    state.process_wounded_bugs(dt);
 
     // ---- Ground tracks (footprints in snow/sand — Dune / Helldivers 2 style) ----
     let tracks_biome = GameState::biome_has_snow_or_sand(state.planet.primary_biome);
@@ -298,17 +336,39 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         state.emit_ground_tracks(dt);
     }
 
+    // ---- Snow cover: accumulates with snowfall (most on flat open ground), melts near heat ----
+    let snowfall = state.weather.snow_amount();
+    if state.current_planet_idx.is_some() && (snowfall > 0.0 || state.snow.is_active()) {
+        let chunk_manager = &state.chunk_manager;
+        state.snow.recenter(cam_pos, |x, z| chunk_manager.walkable_height(x, z));
+        let mut heat: Vec<(Vec3, f32)> = state
+            .world
+            .query::<(&Transform, &EnvironmentalHazard)>()
+            .iter()
+            .filter(|(_, (_, h))| {
+                matches!(h.hazard_type, HazardType::LavaFlow | HazardType::GeyserEruption | HazardType::EmberStorm)
+            })
+            .map(|(_, (t, h))| (t.position, h.radius * 1.5))
+            .collect();
+        heat.extend(state.world.query::<(&Transform, &FireZone)>().iter().map(|(_, (t, z))| (t.position, z.radius)));
+        heat.extend(state.world.query::<(&Transform, &BurnCrater)>().iter().map(|(_, (t, _))| (t.position, t.scale.x * 2.0)));
+        state.snow.update(dt, snowfall, &heat);
+        state.snow.press_tracks(&state.effects.ground_tracks);
+    } else if state.current_planet_idx.is_none() {
+        state.snow.clear();
+    }
+
     // ---- Weapon fire, reload, aiming, and combat ----
-    if state.current_planet_idx.is_some() && state.player.is_alive {
+    if state.current_planet_idx.is_some() && state.player.is_alive && !state.is_piloting() {
         // Weapon firing (left mouse button)
         state.handle_weapon_fire();
+        // Melee strike (V), usable mid-reload
+        state.handle_melee(dt);
 
         // Manual reload (R key, only when weapon equipped — not shovel)
         if state.input.is_reload_pressed() && !state.debug.noclip && !state.player.is_shovel_equipped() {
-            let weapon = state.player.current_weapon();
-            if !weapon.is_reloading && weapon.current_ammo < weapon.magazine_size && weapon.reserve_ammo > 0 {
+            if state.player.current_weapon().can_reload() {
                 state.player.current_weapon_mut().start_reload();
-                state.viewmodel_anim.trigger_switch(); // reload uses same drop/raise animation
                 state.game_messages.info("Reloading...");
             }
         }
@@ -344,9 +404,15 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         // ADS (aim down sights) - right mouse button
         state.player.is_aiming = state.input.is_aiming();
 
-        // Update bug combat (bugs attacking player)
+        // Class ability (Q)
+        if state.input.is_ability_pressed() && !state.debug.noclip && state.player.use_ability() {
+            state.execute_ability();
+        }
+
+        // Update bug combat (bugs attacking player, Skinnies shooting; deployed shields soak hits)
         let hp_before = state.player.health;
-        state.bug_combat.update(&state.world, &mut state.player, dt);
+        state.bug_combat.update(&state.world, &mut state.player, &state.abilities, dt);
+        state.update_skinny_fire(dt);
         // Cinematic: screen shake when taking damage
         if state.player.health < hp_before {
             let damage_taken = hp_before - state.player.health;
@@ -354,21 +420,21 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         }
     }
 
-    // Update player state (weapon cooldowns, reload timers, stamina, ADS)
+    // Update player state (weapon cooldowns, reload timers, stamina, ADS) and ability effects
     state.player.update(dt);
+    state.abilities.update(dt);
 
-    // ADS FOV zoom (smooth transition between 70 normal and 45 ADS)
+    // FOV: zoom to the current weapon's ADS FOV while aiming, widen a little while sprinting
+    let base_fov = state.camera.base_fov_degrees;
+    let h_speed = Vec3::new(state.player_velocity.x, 0.0, state.player_velocity.z).length();
     let target_fov = if state.player.is_aiming && state.player.is_alive && !state.debug.noclip {
-        45.0
+        weapons::weapon_def(state.player.current_weapon().weapon_type).ads_fov(base_fov)
+    } else if state.player.is_sprinting && state.player_grounded && h_speed > 1.0 {
+        base_fov + SPRINT_FOV_KICK
     } else {
-        70.0
+        base_fov
     };
-    let fov_speed = 10.0;
-    if (state.camera.fov_degrees - target_fov).abs() > 0.1 {
-        state.camera.fov_degrees += (target_fov - state.camera.fov_degrees) * fov_speed * dt;
-    } else {
-        state.camera.fov_degrees = target_fov;
-    }
+    state.camera.set_fov(target_fov, FOV_RATE, dt);
 
     // Update combat system (damage numbers, hit markers, kill feed)
     state.combat.update(dt);
@@ -380,30 +446,40 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
     // Screen shake decay
     state.screen_shake.update(dt);
 
-    // Camera recoil decay (smooth return)
-    if state.camera_recoil.abs() > 0.001 {
-        state.camera_recoil *= (1.0 - 12.0 * dt).max(0.0); // fast decay
-    } else {
-        state.camera_recoil = 0.0;
+    // Recoil recovery: pull the view back toward the original aim between bursts
+    let (rec_pitch, rec_yaw) = state.recoil.recover(weapons::weapon_def(state.player.current_weapon().weapon_type), dt);
+    if rec_pitch != 0.0 || rec_yaw != 0.0 {
+        state.camera.set_yaw_pitch(state.camera.yaw() - rec_yaw, state.camera.pitch() + rec_pitch);
     }
 
-    // Kill streak tracking
+    // Kill streak tracking; milestone rewards ready a stratagem or start a timed buff
     state.kill_streaks.update(dt);
+    while let Some(reward) = state.kill_streaks.consume_reward() {
+        match reward {
+            StreakReward::ReadyStratagem(stratagem) => state.cooldown_mut(stratagem).reset(),
+            buff => state.kill_streaks.start_buff(buff),
+        }
+        state.game_messages.success(format!("KILL STREAK REWARD: {}", reward.describe()));
+    }
+    if state.kill_streaks.adrenaline_remaining() > 0.0 {
+        state.player.stamina = state.player.stamina.full();
+    }
 
-    // Ambient dust particles (only on planet surface)
     if state.current_planet_idx.is_some() && state.player.is_alive {
-        let dust_mult = 1.0 + state.weather.dust * 2.5; // more floating particles when cloudy/rain/snow
-        state.ambient_dust.update(dt, state.camera.position(), dust_mult);
         // Biome-specific volumetric atmosphere (fog banks, embers, spores, etc.)
         state.biome_atmosphere.update(dt, state.camera.position(), state.time.elapsed_seconds());
     }
 
 
+    // ---- Frag grenades: cook, throw, bounce, detonate ----
+    state.update_frag_grenades(dt);
+    state.update_weapon_pickups();
+
     // ---- Smoke grenades ----
-    state.smoke_grenade_cooldown = (state.smoke_grenade_cooldown - dt).max(0.0);
-    // G key throws smoke grenade
-    if state.input.is_key_pressed(KeyCode::KeyG) && state.phase == GamePhase::Playing
-        && state.player.is_alive && state.smoke_grenade_cooldown <= 0.0
+    state.cooldown_mut(Stratagem::Smoke).tick(dt);
+    // Smoke grenade (H by default) throws red smoke; the frag grenade is its own action
+    if state.input.is_action_pressed(Action::SmokeGrenade) && state.phase == GamePhase::Playing
+        && state.player.is_alive && state.cooldown(Stratagem::Smoke).is_ready()
     {
         let throw_pos = state.camera.position() + state.camera.forward() * 1.0;
         let throw_vel = state.camera.forward() * 25.0 + Vec3::Y * 12.0;
@@ -413,7 +489,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             age: 0.0,
             detonated: false,
         });
-        state.smoke_grenade_cooldown = 5.0; // 5 second cooldown
+        state.cooldown_mut(Stratagem::Smoke).start(5.0); // 5 second cooldown
         state.game_messages.info("SMOKE OUT!");
     }
 
@@ -459,15 +535,10 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         state.game_messages.info("RED SMOKE DEPLOYED - MARKING POSITION");
         // Red smoke = artillery designator: start staggered barrage (6 shells, one after another)
         if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing
-            && state.artillery_cooldown <= 0.0
+            && state.cooldown(Stratagem::Artillery).is_ready()
             && state.artillery_barrage.is_none()
         {
-            state.artillery_barrage = Some(ArtilleryBarrage {
-                target: *pos,
-                shells_remaining: SHELLS_PER_BARRAGE,
-                fire_timer: 0.0, // fire first shell immediately
-                fire_index: 0,
-            });
+            state.artillery_barrage = Some(ArtilleryBarrage::new(*pos));
             state.game_messages.warning("ORBITAL ARTILLERY INBOUND — DANGER CLOSE!".to_string());
             state.game_messages.info("FLEET COM: Roger, red smoke acquired. Barrage firing.");
             state.game_messages.info("Look up to see the ships fire!".to_string());
@@ -508,14 +579,20 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         }
     }
 
+    // ---- Stratagem call-ins (single key, or held key + directional code) ----
+    let stratagem_called = if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
+        state.stratagem_input.update(&state.input, dt)
+    } else {
+        None
+    };
+
     // ---- Tac Fighter fleet — multiple CAS runs (Starship Troopers style) ----
     if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
-        state.tac_fighter_cooldown -= dt;
+        state.cooldown_mut(Stratagem::OrbitalStrike).tick(dt);
 
         // Squad mates can request CAS when fleet has room and cooldown is up
-        let tac_ready = state.tac_fighters.len() + 4 <= MAX_TAC_FIGHTERS
-            && state.tac_fighter_available
-            && state.tac_fighter_cooldown <= 0.0;
+        let tac_ready = state.stratagem_available(Stratagem::OrbitalStrike)
+            && state.cooldown(Stratagem::OrbitalStrike).is_ready();
         if let Some(caller) = update_squad_combat(&mut state.world, dt, tac_ready) {
             let cam_pos = state.camera.transform.position;
             let corvettes = surface_corvette_positions(
@@ -531,13 +608,14 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 let spawn = Some((corvettes[idx], idx as u8));
                 state.tac_fighters.push(TacFighter::new_with_angle(state.player.position, angle, spawn));
             }
-            state.tac_fighter_cooldown = 25.0 + rand::random::<f32>() * 20.0;
+            let rearm = (25.0 + rand::random::<f32>() * 20.0) * state.stratagem_cooldown_mult(Stratagem::OrbitalStrike);
+            state.cooldown_mut(Stratagem::OrbitalStrike).start(rearm);
             state.game_messages.warning("TAC FIGHTER FLEET INBOUND - DANGER CLOSE!".to_string());
             state.game_messages.info(format!("{}: Roger, four birds on station! Ordnance away.", caller));
         }
 
         // Stratagem B = Orbital Strike (tac fighter fleet on your position — Helldivers 2 style)
-        if state.input.is_key_pressed(KeyCode::KeyB) && tac_ready {
+        if stratagem_called == Some(Stratagem::OrbitalStrike) && tac_ready {
             let cam_pos = state.camera.transform.position;
             let corvettes = surface_corvette_positions(
                 cam_pos,
@@ -552,51 +630,54 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 let spawn = Some((corvettes[idx], idx as u8));
                 state.tac_fighters.push(TacFighter::new_with_angle(state.player.position, angle, spawn));
             }
-            state.tac_fighter_cooldown = 25.0 + rand::random::<f32>() * 20.0;
+            let rearm = (25.0 + rand::random::<f32>() * 20.0) * state.stratagem_cooldown_mult(Stratagem::OrbitalStrike);
+            state.cooldown_mut(Stratagem::OrbitalStrike).start(rearm);
             state.orbital_strike_smoke = Some(SmokeCloud::new(state.player.position));
             state.game_messages.warning("ORBITAL STRIKE FLEET INBOUND — DANGER CLOSE!".to_string());
             state.game_messages.info("FLEET COM: Roger, four birds inbound. Good hunting.".to_string());
         }
 
         // Stratagem N = Supply Drop (ammo + health crate at position ahead of you)
-        state.supply_drop_cooldown -= dt;
-        if state.input.is_key_pressed(KeyCode::KeyN) && state.supply_drop_cooldown <= 0.0 {
+        state.cooldown_mut(Stratagem::SupplyDrop).tick(dt);
+        if stratagem_called == Some(Stratagem::SupplyDrop) && state.cooldown(Stratagem::SupplyDrop).is_ready() {
             let fwd = Vec3::new(state.camera.forward().x, 0.0, state.camera.forward().z).normalize_or_zero();
-            let drop_pos = state.player.position + fwd * 15.0;
-            state.supply_crates.push(SupplyCrate {
-                position: drop_pos,
-                lifetime: 0.0,
-                used: false,
-            });
+            let mut drop_pos = state.player.position + fwd * 15.0;
+            drop_pos.y = state.chunk_manager.walkable_height(drop_pos.x, drop_pos.z);
+            state.supply_crates.push(SupplyCrate::new(drop_pos));
             state.supply_drop_smoke.push(SmokeCloud::new(drop_pos));
-            state.supply_drop_cooldown = 60.0;
+            let rearm = 60.0 * state.stratagem_cooldown_mult(Stratagem::SupplyDrop);
+            state.cooldown_mut(Stratagem::SupplyDrop).start(rearm);
             state.game_messages.warning("SUPPLY DROP INBOUND!".to_string());
             state.game_messages.info("FLEET COM: Supply crate deploying to your position.".to_string());
         }
 
-        // Update supply crates: lifetime, pickup (refill ammo + health)
+        // Supply crates: look at one and press E to restock (limited charges per crate)
         for supply_crate in &mut state.supply_crates {
-            supply_crate.lifetime += dt;
-            if !supply_crate.used {
-                let dist = state.player.position.distance(supply_crate.position);
-                if dist < 3.0 {
-                    supply_crate.used = true;
-                    state.player.health = (state.player.health + 50.0).min(state.player.max_health);
-                    state.player.armor = (state.player.armor + 25.0).min(state.player.max_armor);
-                    for w in &mut state.player.weapons {
-                        w.current_ammo = w.magazine_size;
-                        w.reserve_ammo = (w.reserve_ammo + 100).min(999);
-                        w.is_reloading = false;
+            supply_crate.update(dt);
+        }
+        if let Some(InteractionTarget::SupplyCrate(index)) = state.player.interaction_target {
+            if let Some(supply_crate) = state.supply_crates.get_mut(index) {
+                state.interaction_prompt = Some(InteractPrompt {
+                    key: INTERACT_KEY,
+                    action: format!("Restock ({} left)", supply_crate.charges),
+                });
+                if state.input.is_interact_pressed() && supply_crate.use_charge(&mut state.player) {
+                    let lid = supply_crate.position + Vec3::Y * 0.8;
+                    state.effects.spawn_bullet_impact(lid, Vec3::Y, false);
+                    let left = supply_crate.charges;
+                    if left > 0 {
+                        state.game_messages.success(format!("Restocked — ammo, grenades and health topped off ({left} left in crate)"));
+                    } else {
+                        state.game_messages.success("Restocked — that was the last of the crate".to_string());
                     }
-                    state.game_messages.success("Supply crate — ammo and health restored!".to_string());
                 }
             }
         }
-        state.supply_crates.retain(|sc| sc.lifetime < 30.0);
+        state.supply_crates.retain(|sc| !sc.is_spent());
 
         // Stratagem R = Reinforce (full heal + armor + ammo from orbit — one life, no respawn, but reinforcements)
-        state.reinforce_cooldown -= dt;
-        if state.input.is_key_pressed(KeyCode::KeyR) && state.reinforce_cooldown <= 0.0 {
+        state.cooldown_mut(Stratagem::Reinforce).tick(dt);
+        if stratagem_called == Some(Stratagem::Reinforce) && state.cooldown(Stratagem::Reinforce).is_ready() {
             state.player.health = state.player.max_health;
             state.player.armor = state.player.max_armor;
             for w in &mut state.player.weapons {
@@ -604,15 +685,43 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 w.reserve_ammo = (w.reserve_ammo + 150).min(999);
                 w.is_reloading = false;
             }
-            state.reinforce_cooldown = 90.0;
+            state.player.grenades = MAX_FRAG_GRENADES;
+            let revived = revive_downed_squad(&mut state.world);
+            let rearm = 90.0 * state.stratagem_cooldown_mult(Stratagem::Reinforce);
+            state.cooldown_mut(Stratagem::Reinforce).start(rearm);
             state.reinforce_smoke = Some(SmokeCloud::new(state.player.position));
             state.game_messages.warning("REINFORCEMENTS INBOUND!".to_string());
             state.game_messages.success("Orbital supply run — health, armor, and ammo restored.".to_string());
+            if revived > 0 {
+                state.game_messages.success(format!("Medics on the drop — {revived} downed trooper(s) back in the fight."));
+            }
         }
 
+        // T = take the stick of an on-station Tac Fighter for a strafing run (T again hands it back)
+        if state.input.is_key_pressed(KeyCode::KeyT) || (state.is_piloting() && !state.player.is_alive) {
+            if let Some(fighter) = state.tac_fighters.iter_mut().find(|f| f.is_piloted()) {
+                fighter.release_controls();
+                state.camera.set_yaw_pitch(state.player.yaw, state.player.pitch);
+                state.game_messages.info("FLEET COM: Control returned. Bird is RTB.");
+            } else if state.player.is_alive {
+                let mode = state.flight_mode;
+                if let Some(fighter) = state
+                    .tac_fighters
+                    .iter_mut()
+                    .find(|f| f.phase != TacFighterPhase::Departing && f.bombs_remaining > 0)
+                {
+                    fighter.take_controls(mode);
+                    state.game_messages.warning("YOU HAVE THE STICK! LMB/Space = bombs, T = hand back.".to_string());
+                }
+            }
+        }
+        let pilot_controls = state.flight_controls(dt);
+        let pilot_drop = state.input.is_fire_held() || state.input.is_key_held(KeyCode::Space);
+
         // Update all tac fighters in the fleet
         let mut buzz_msg = false;
         let mut bombs_msg = false;
+        let mut pilot_out_of_bombs = false;
         let player_pos = state.player.position;
         let corvettes = surface_corvette_positions(
             state.camera.transform.position,
@@ -620,6 +729,20 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             state.time.elapsed_seconds(),
         );
         for fighter in &mut state.tac_fighters {
+            if fighter.is_piloted() {
+                let ground_y = state.chunk_manager.sample_height(fighter.position.x, fighter.position.z);
+                for drop_pos in fighter.update_piloted(&pilot_controls, pilot_drop, ground_y, dt) {
+                    state.tac_bombs.push(TacBomb::new(drop_pos, fighter.velocity));
+                }
+                if let Some(pilot) = fighter.pilot() {
+                    pilot.sync_camera(&mut state.camera, PILOT_CHASE_OFFSET);
+                }
+                if fighter.bombs_remaining == 0 {
+                    fighter.release_controls();
+                    pilot_out_of_bombs = true;
+                }
+                continue;
+            }
             let bomb_drops = fighter.update(dt, player_pos, Some(&corvettes));
             for drop_pos in bomb_drops {
                 state.tac_bombs.push(TacBomb::new(drop_pos, fighter.velocity));
@@ -638,6 +761,10 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         if bombs_msg {
             state.game_messages.warning("BOMBS AWAY! TAKE COVER!".to_string());
         }
+        if pilot_out_of_bombs {
+            state.camera.set_yaw_pitch(state.player.yaw, state.player.pitch);
+            state.game_messages.info("FLEET COM: Winchester — ordnance expended. Bird is RTB.");
+        }
 
         // Remove completed fighters
         let before = state.tac_fighters.len();
@@ -675,6 +802,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         for impact_pos in &detonated_bombs {
             // Explosion effect: fire/smoke billboards (flat look like red smoke)
             state.effects.spawn_tac_explosion(*impact_pos);
+            state.snow.melt_at(*impact_pos, 20.0);
             // Destruction debris: flying terrain chunks from crater
             state.destruction.spawn_debris(
                 &mut state.world,
@@ -708,7 +836,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             // MASSIVE screen shake
             let dist_to_player = (*impact_pos - state.player.position).length();
             let shake = (1.0 - (dist_to_player / 100.0).min(1.0)) * 0.8 + 0.2;
-            state.screen_shake.add_trauma(shake);
+            state.screen_shake.add_directional_trauma(state.player.position - *impact_pos, shake);
 
             // Kill bugs in blast radius
             let kill_radius = 18.0;
@@ -740,10 +868,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             }
 
             // Destroy any destructibles in range
-            state.destruction.apply_explosion(
-                &mut state.world, &mut state.physics,
-                *impact_pos, 15.0, 500.0,
-            );
+            state.blast_destructibles(*impact_pos, 15.0, 500.0);
 
             // Destroy corpses in blast radius (Helldivers 2 style)
             let corpse_kill_radius_sq = kill_radius_sq;
@@ -766,14 +891,31 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
 
     // ---- Orbital artillery (red smoke designator) ----
     if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
-        let was_rearming = state.artillery_cooldown > 0.0;
-        state.artillery_cooldown -= dt;
-        if was_rearming && state.artillery_cooldown <= 0.0 {
+        if state.cooldown_mut(Stratagem::Artillery).tick(dt) {
             state.game_messages.info("FLEET COM: Artillery batteries ready. Red smoke to designate.");
         }
 
         // Barrage: fire shells one after another with delay
         if let Some(ref mut barrage) = state.artillery_barrage {
+            // Follow the designator: the smoke settles into craters the barrage digs under it,
+            // and shells not yet fired re-aim at where it now sits
+            let designator = state.smoke_clouds.iter_mut()
+                .filter(|c| !c.is_done())
+                .min_by(|a, b| {
+                    a.origin.distance_squared(barrage.target)
+                        .total_cmp(&b.origin.distance_squared(barrage.target))
+                })
+                .filter(|c| {
+                    let d = c.origin - barrage.target;
+                    d.x * d.x + d.z * d.z < 4.0 * 4.0
+                });
+            if let Some(cloud) = designator {
+                cloud.origin.y = state.chunk_manager.walkable_height(cloud.origin.x, cloud.origin.z) + 0.2;
+                if cloud.origin.distance_squared(barrage.target) > 0.25 * 0.25 {
+                    barrage.retarget(cloud.origin);
+                }
+            }
+
             barrage.fire_timer -= dt;
             if barrage.fire_timer <= 0.0 && barrage.shells_remaining > 0 {
                 let cam_pos = state.camera.transform.position;
@@ -787,11 +929,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                     state.orbital_time,
                     state.time.elapsed_seconds(),
                 );
-                let target = barrage.target + Vec3::new(
-                    (rand::random::<f32>() - 0.5) * 25.0,
-                    0.0,
-                    (rand::random::<f32>() - 0.5) * 25.0,
-                );
+                let (target, flight_time) = barrage.shell_aim(barrage.fire_index);
                 let i = barrage.fire_index;
                 // Fire from ventral guns — flash between ship and ground, visible when looking up
                 let (from_pos, facing) = if i % 2 == 0 && !corvettes.is_empty() {
@@ -812,7 +950,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 } else {
                     (barrage.target + Vec3::Y * 250.0, Vec3::Y * -1.0) // fallback
                 };
-                state.artillery_shells.push(ArtilleryShell::new(from_pos, target));
+                state.artillery_shells.push(ArtilleryShell::new(from_pos, target, flight_time));
                 state.artillery_muzzle_flashes.push(ArtilleryMuzzleFlash::new(from_pos, facing));
                 barrage.fire_timer = SHELL_FIRE_DELAY;
                 barrage.shells_remaining -= 1;
@@ -820,7 +958,8 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             }
             if barrage.shells_remaining == 0 {
                 state.artillery_barrage = None;
-                state.artillery_cooldown = 40.0 + rand::random::<f32>() * 25.0; // rearm time
+                let rearm = (40.0 + rand::random::<f32>() * 25.0) * state.stratagem_cooldown_mult(Stratagem::Artillery);
+                state.cooldown_mut(Stratagem::Artillery).start(rearm);
                 state.game_messages.info("FLEET COM: Artillery batteries rearming. Stand by.");
             }
         }
@@ -836,7 +975,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         for shell in &mut state.artillery_shells {
             let prev_pos = shell.position;
             shell.age += dt;
-            shell.velocity += Vec3::new(0.0, -90.0, 0.0) * dt; // orbital guns = high velocity
+            shell.velocity += Vec3::new(0.0, -ARTILLERY_GRAVITY, 0.0) * dt; // orbital guns = high velocity
             shell.position += shell.velocity * dt;
 
             // Spawn trail particles (smoke/fire streak behind shell)
@@ -883,6 +1022,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             );
             state.grounded_artillery_shells.push(GroundedArtilleryShell::new(shell_pos));
             state.effects.spawn_tac_explosion(*impact_pos);
+            state.snow.melt_at(*impact_pos, 30.0);
             state.destruction.spawn_debris(
                 &mut state.world,
                 *impact_pos,
@@ -909,8 +1049,8 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             }
             let dist_to_player = (*impact_pos - state.player.position).length();
             let shake = (1.0 - (dist_to_player / 120.0).min(1.0)) * 0.8 + 0.2;
-            state.screen_shake.add_trauma(shake);
-            let kill_radius_sq = 28.0 * 28.0;
+            state.screen_shake.add_directional_trauma(state.player.position - *impact_pos, shake);
+            let kill_radius_sq = ARTILLERY_KILL_RADIUS * ARTILLERY_KILL_RADIUS;
             for (entity, (transform, _)) in state.world.query::<(&Transform, &Bug)>().iter() {
                 if transform.position.distance_squared(*impact_pos) < kill_radius_sq {
                     if let Ok(mut health) = state.world.get::<&mut Health>(entity) {
@@ -929,16 +1069,20 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 let offset = Vec3::new(angle.cos() * 12.0, 3.0 + (i as f32) * 0.5, angle.sin() * 12.0);
                 state.effects.spawn_muzzle_flash(*impact_pos + offset, Vec3::Y);
             }
-            state.destruction.apply_explosion(
-                &mut state.world, &mut state.physics,
-                *impact_pos, 24.0, 600.0,
-            );
+            state.blast_destructibles(*impact_pos, 24.0, 600.0);
         }
+
+        // Landing points for the ground rings and countdowns, predicted once per frame
+        state.artillery_impacts = predicted_impacts(
+            &state.artillery_shells,
+            state.artillery_barrage.as_ref(),
+            |x, z| state.chunk_manager.sample_height(x, z),
+        );
     }
 
     // ---- Extraction dropship ----
     if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
-        state.extraction_cooldown = (state.extraction_cooldown - dt).max(0.0);
+        state.cooldown_mut(Stratagem::Extraction).tick(dt);
 
         // Update LZ green smoke (keep it alive while on the surface)
         if let Some(ref mut smoke) = state.lz_smoke {
@@ -965,11 +1109,10 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             }
         }
 
-        // V key calls for extraction
-        if state.input.is_key_pressed(KeyCode::KeyV)
-            && state.extraction.is_none()
-            && state.extraction_cooldown <= 0.0
-            && state.player.is_alive
+        // Z (stratagem) calls for extraction
+        if stratagem_called == Some(Stratagem::Extraction)
+            && state.stratagem_available(Stratagem::Extraction)
+            && state.cooldown(Stratagem::Extraction).is_ready()
         {
             let lz_forward = state.camera.forward();
             let lz_xz = Vec3::new(lz_forward.x, 0.0, lz_forward.z).normalize_or_zero();
@@ -1002,6 +1145,7 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
             state.game_messages.warning("FLEET COM: Copy that, DR-8 Skyhook launching from corvette. ETA 30 seconds.".to_string());
             state.game_messages.info("\"Come on you apes, get to the LZ!\"".to_string());
             state.game_messages.info("Get to the [LZ] marker and hold position!".to_string());
+            state.game_messages.warning("BUG ACTIVITY SURGING — HOLD THE LZ UNTIL THE BOAT IS DOWN!".to_string());
         }
 
         // Update extraction dropship
@@ -1018,29 +1162,53 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 dropship.boarding_start_pos = Some(state.player.position);
             }
 
-            let msgs = dropship.update(dt, state.player.position);
+            // Falling during the last stand forfeits the squad pickup
+            if !state.player.is_alive
+                && !dropship.trooper_fell
+                && !matches!(dropship.phase, ExtractionPhase::Departing | ExtractionPhase::Ascent)
+            {
+                dropship.trooper_fell = true;
+                state.game_messages.warning("TROOPER DOWN AT THE LZ — squad pickup lost!".to_string());
+            }
+
+            // Boat on the pad: board on the interact key from inside the LZ, unless the key is
+            // already going to a crate, weapon or trooper under the crosshair
+            let can_board = dropship.lz_trigger.is_some_and(|h| state.player_triggers.contains(&h))
+                && state.player.interaction_target.is_none();
+            if can_board && dropship.phase == ExtractionPhase::Waiting {
+                state.interaction_prompt = Some(InteractPrompt {
+                    key: INTERACT_KEY,
+                    action: "Board the retrieval boat".to_string(),
+                });
+            }
+            let board = can_board && state.input.is_interact_pressed();
+            let msgs = dropship.update(dt, board);
 
-            // NO TROOPER LEFT BEHIND: pick up squadmates when boat departs with player aboard
+            // NO TROOPER LEFT BEHIND: pick up squadmates when boat departs with player aboard,
+            // unless the trooper fell holding the LZ
             if dropship.phase == ExtractionPhase::Departing
                 && dropship.player_aboard
                 && dropship.phase_timer < dt * 1.5
             {
                 const SQUAD_BOARDING_RADIUS_SQ: f32 = 25.0 * 25.0;
                 state.extraction_squadmates_aboard.clear();
-                for (entity, (transform, _squad, health)) in
-                    state.world.query::<(&Transform, &SquadMate, &Health)>().iter()
-                {
-                    if health.is_dead() {
-                        continue;
+                if dropship.trooper_fell {
+                    state.game_messages.warning("Squad left behind — you went down at the LZ.".to_string());
+                } else {
+                    // The downed are carried aboard too; only those who bled out stay behind
+                    for (entity, (transform, squad)) in state.world.query::<(&Transform, &SquadMate)>().iter() {
+                        if squad.status == SquadMateStatus::Dead {
+                            continue;
+                        }
+                        let dist_sq = transform.position.distance_squared(dropship.lz_position);
+                        if dist_sq < SQUAD_BOARDING_RADIUS_SQ {
+                            state.extraction_squadmates_aboard.push(entity);
+                        }
                     }
-                    let dist_sq = transform.position.distance_squared(dropship.lz_position);
-                    if dist_sq < SQUAD_BOARDING_RADIUS_SQ {
-                        state.extraction_squadmates_aboard.push(entity);
+                    if !state.extraction_squadmates_aboard.is_empty() {
+                        state.game_messages.success("Squad aboard! NO TROOPER LEFT BEHIND!".to_string());
                     }
                 }
-                if !state.extraction_squadmates_aboard.is_empty() {
-                    state.game_messages.success("Squad aboard! NO TROOPER LEFT BEHIND!".to_string());
-                }
             }
 
             // Move squadmates with the boat during flight to Roger Young
@@ -1097,6 +1265,20 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 state.physics.set_kinematic_position(body_h, dropship.position);
             }
 
+            // LZ boarding trigger: a tall cylinder so only horizontal distance matters
+            let needs_trigger = dropship.needs_lz_trigger();
+            if needs_trigger && dropship.lz_trigger.is_none() {
+                dropship.lz_trigger = Some(state.physics.add_sensor(
+                    dropship.lz_position,
+                    SharedShape::cylinder(50.0, BOARDING_RADIUS),
+                ));
+            } else if !needs_trigger {
+                if let Some(trigger) = dropship.lz_trigger.take() {
+                    state.physics.remove_collider(trigger);
+                    state.player_triggers.remove(&trigger);
+                }
+            }
+
             // ── Door gunner targeting ──
             if dropship.gunners_active() {
                 let left_gun = dropship.gunner_left_pos();
@@ -1263,18 +1445,62 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
                 state.complete_extraction();
             } else {
                 // Failed — boat left without us, 90 second cooldown
-                state.extraction_cooldown = 90.0;
+                state.cooldown_mut(Stratagem::Extraction).start(90.0);
                 state.game_messages.warning("FLEET COM: Next retrieval window in 90 seconds. Stay alive!".to_string());
             }
         }
     }
 
+    // ---- HUD waypoints (compass + on-screen objective markers) ----
+    state.hud.clear_waypoints();
+    if state.current_planet_idx.is_some() && state.phase == GamePhase::Playing {
+        if let Some(ref dropship) = state.extraction {
+            state.hud.add_waypoint(dropship.lz_position, WaypointIcon::Lz);
+            if matches!(
+                dropship.phase,
+                ExtractionPhase::Called | ExtractionPhase::Inbound | ExtractionPhase::Landing
+            ) {
+                state.hud.add_waypoint(dropship.position, WaypointIcon::Dropship);
+            }
+        }
+        // Defense marks the base through its capture/hold objective below
+        if let Some((base_center, _)) = state.defense_base.filter(|_| state.mission.mission_type != MissionType::Defense) {
+            state.hud.add_waypoint(base_center, WaypointIcon::Objective);
+        }
+        for mark in &state.abilities.scan_marks {
+            let Ok(mut query) = state.world.query_one::<(&Transform, &Health)>(mark.entity) else {
+                continue;
+            };
+            if let Some((transform, _)) = query.get().filter(|(_, h)| !h.is_dead()) {
+                state.hud.add_waypoint(transform.position, WaypointIcon::Scanned);
+            }
+        }
+        for (_, (transform, squad)) in state.world.query::<(&Transform, &SquadMate)>().iter() {
+            if squad.is_downed() {
+                state.hud.add_waypoint(transform.position, WaypointIcon::Downed);
+            }
+        }
+        if let Some(objective) = state.mission.active_objective() {
+            for (position, icon) in objective.waypoints(&state.world) {
+                state.hud.add_waypoint(position, icon);
+            }
+        }
+    }
+
     // ---- Viewmodel animation ----
     {
         let is_firing = state.viewmodel_anim.fire_flash_timer < 0.05;
         let is_sprinting = state.player.is_sprinting && state.player_grounded;
         let h_speed = Vec3::new(state.player_velocity.x, 0.0, state.player_velocity.z).length();
         let is_moving = h_speed > 1.0 && state.player_grounded;
+        let weapon = state.player.current_weapon();
+        let reload = if state.player.is_shovel_equipped() {
+            None
+        } else {
+            weapon.reload_progress().map(|p| (p, weapon.reload_style))
+        };
+        state.viewmodel_anim.set_reload(reload);
+        state.viewmodel_anim.set_stance(state.player.is_crouching, state.player.is_prone);
         state.viewmodel_anim.update(dt, is_firing, is_sprinting, is_moving, h_speed);
     }
 
@@ -1337,10 +1563,8 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         }
     }
 
-    // Rain drops
-    state.update_rain(dt);
-    // Snow particles (when weather is Snow)
-    state.update_snow(dt);
+    // Rain, snow and ambient dust (one pooled update)
+    state.update_particles(dt);
 
     // Destructible debris physics (with water buoyancy)
     let surface_fn = |x: f32, z: f32| {
@@ -1372,6 +1596,17 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         state.world.despawn(e).ok();
     }
 
+    // Corpses evicted by the corpse budget finish fading out
+    let mut corpses_to_despawn: Vec<Entity> = Vec::new();
+    for (entity, (_, lifetime)) in state.world.query_mut::<(&BugCorpse, &mut Lifetime)>() {
+        if lifetime.update(dt) {
+            corpses_to_despawn.push(entity);
+        }
+    }
+    for e in corpses_to_despawn {
+        state.world.despawn(e).ok();
+    }
+
     // Update visible tracer projectiles
     for t in &mut state.tracer_projectiles {
         t.position += t.velocity * dt;
@@ -1379,24 +1614,58 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
     }
     state.tracer_projectiles.retain(|t| t.lifetime > 0.0);
 
+    // Rockets in flight: smoke trail, then detonate whatever hit this frame
+    const ROCKET_TRAIL_MAX: usize = 280;
+    for (_, (transform, velocity, _)) in state.world.query::<(&Transform, &Velocity, &Projectile)>().iter() {
+        if state.artillery_trail_particles.len() < ROCKET_TRAIL_MAX {
+            state.artillery_trail_particles.push(ArtilleryTrailParticle::new(transform.position, velocity.linear));
+        }
+    }
+    let impacts = state.weapon_system.update_projectiles(&mut state.world, &state.physics, dt);
+    for impact in &impacts {
+        state.detonate_projectile(impact);
+    }
+
     // Physics step (capped at 3 per frame to prevent death spiral on lag spikes)
+    state.physics.set_sensor_position(state.player_sensor, state.player.position - Vec3::Y * 0.9);
+    // Snapshot before every step so the one that survives the frame is from before the last
+    // step: the render alpha then spans exactly one step.
     let mut physics_steps = 0;
     while state.time.should_fixed_update() && physics_steps < 3 {
+        store_previous_transforms(&mut state.world);
         state.physics.step();
+        sync_ragdoll_transforms(&mut state.world, &state.physics);
         physics_steps += 1;
     }
+    for event in state.physics.drain_intersection_events() {
+        if let Some(trigger) = event.other(state.player_sensor) {
+            if event.started {
+                state.player_triggers.insert(trigger);
+            } else {
+                state.player_triggers.remove(&trigger);
+            }
+        }
+    }
 
     // Clean up dead bugs (staggered: frame_count % 4 == 1)
     if state.time.frame_count() % 4 == 1 {
         state.cleanup_dead_bugs();
     }
 
-    // Update horde state (difficulty escalation + mission tracking)
+    // Update horde state (difficulty escalation + mission tracking); extraction surges the horde
+    state.spawner.set_surge(state.extraction.as_ref().map_or(1.0, ExtractionDropship::surge_multiplier));
     state.spawner.update_difficulty(dt);
     let bugs_alive = state.count_living_bugs();
     state.mission.bugs_remaining = bugs_alive as u32;
     state.mission.bugs_killed = state.player.kills;
-    state.mission.update(dt, state.player.is_alive);
+    let was_failed = state.mission.is_failed;
+    let was_complete = state.mission.objective_complete;
+    state.mission.update(&mut state.world, dt, state.player.position, |x, z| state.chunk_manager.sample_height(x, z));
+    if state.mission.is_failed && !was_failed {
+        state.game_messages.warning("OBJECTIVE FAILED — extract and regroup, trooper.".to_string());
+    } else if state.mission.objective_complete && !was_complete {
+        state.game_messages.success("OBJECTIVE COMPLETE — call extraction!".to_string());
+    }
 
     // Player respawn (on terrain at origin)
     if !state.player.is_alive && state.player.respawn_timer <= 0.0 {
@@ -1405,23 +1674,13 @@ pub fn gameplay(state: &mut GameState, dt: f32) {
         state.game_messages.info("Player respawned!");
     }
 
-    // Apply cinematic camera effects before uploading to GPU
-    // Screen shake: offset the camera position
-    if state.screen_shake.intensity > 0.001 {
-        state.camera.transform.position += state.screen_shake.offset;
-    }
-    // Camera recoil: pitch the view up slightly
-    if state.camera_recoil > 0.001 {
-        state.camera.process_mouse(0.0, -state.camera_recoil * 60.0); // pitch up
-    }
-
-    // Update renderer camera
-    state.renderer.update_camera(&state.camera, state.planet_radius_for_curvature());
+    // Resolve parented entities (the viewmodel rig) into world space for rendering
+    state.pose_viewmodel_rig();
+    update_global_transforms(&mut state.world);
 
-    // Remove the shake offset so it doesn't accumulate on the real position
-    if state.screen_shake.intensity > 0.001 {
-        state.camera.transform.position -= state.screen_shake.offset;
-    }
+    // Update renderer camera, shaken (the real camera stays put so the shake never accumulates)
+    let shaken = state.screen_shake.shaken(&state.camera);
+    state.renderer.update_camera(&shaken, state.planet_radius_for_curvature());
 
     // Update on-screen messages
     state.game_messages.update(dt);
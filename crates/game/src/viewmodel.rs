@@ -1,8 +1,79 @@
 //! First-person weapon viewmodel animation and shell casing physics.
 
-use glam::{Quat, Vec3};
+use engine_core::{world_matrix, GlobalTransform, Parent, Transform};
+use glam::{Mat4, Quat, Vec3};
+use hecs::{Entity, World};
 use physics::{ColliderHandle, RigidBodyHandle};
 
+use crate::weapons::{ReloadStyle, WeaponType};
+
+/// Breathing sway at full strength: position (m) and rotation (rad) amplitudes, breaths per second.
+const BREATH_OFFSET: f32 = 0.0018;
+const BREATH_ROTATION: f32 = 0.005;
+const BREATH_RATE: f32 = 0.25;
+
+/// One box of a weapon model: offset and size in the gun's frame (barrel along -Z), and color.
+pub struct GunPart {
+    pub offset: [f32; 3],
+    pub scale: [f32; 3],
+    pub color: [f32; 4],
+}
+
+/// MI-22 Tactical Shotgun — pump-action, short barrel, stock
+const SHOTGUN_PARTS: &[GunPart] = &[
+    GunPart { offset: [0.0, 0.0, 0.02], scale: [0.040, 0.038, 0.14], color: [0.20, 0.20, 0.22, 1.0] }, // receiver
+    GunPart { offset: [0.0, 0.006, -0.20], scale: [0.022, 0.022, 0.28], color: [0.28, 0.28, 0.30, 1.0] }, // barrel
+    GunPart { offset: [0.0, 0.0, -0.08], scale: [0.032, 0.026, 0.06], color: [0.18, 0.18, 0.20, 1.0] },   // pump / forend
+    GunPart { offset: [0.0, -0.006, 0.16], scale: [0.028, 0.034, 0.12], color: [0.14, 0.12, 0.10, 1.0] }, // stock
+    GunPart { offset: [0.0, -0.050, 0.0], scale: [0.020, 0.050, 0.026], color: [0.12, 0.12, 0.14, 1.0] },  // grip
+    GunPart { offset: [0.0, -0.028, -0.02], scale: [0.016, 0.008, 0.040], color: [0.22, 0.22, 0.24, 1.0] }, // trigger guard
+    GunPart { offset: [0.0, 0.028, -0.12], scale: [0.008, 0.012, 0.18], color: [0.24, 0.24, 0.26, 1.0] },  // vent rib / sight rail
+    GunPart { offset: [0.0, 0.006, -0.38], scale: [0.024, 0.024, 0.022], color: [0.26, 0.26, 0.28, 1.0] }, // muzzle
+];
+
+/// Morita MG — heavy machine gun, longer barrel, ammo box
+const MG_PARTS: &[GunPart] = &[
+    GunPart { offset: [0.0, 0.0, 0.05], scale: [0.045, 0.050, 0.24], color: [0.20, 0.20, 0.22, 1.0] }, // receiver
+    GunPart { offset: [0.0, 0.010, -0.32], scale: [0.018, 0.018, 0.42], color: [0.28, 0.28, 0.30, 1.0] }, // barrel
+    GunPart { offset: [0.0, -0.012, 0.22], scale: [0.038, 0.042, 0.14], color: [0.16, 0.16, 0.18, 1.0] }, // ammo box
+    GunPart { offset: [0.0, -0.058, 0.0], scale: [0.022, 0.055, 0.030], color: [0.14, 0.14, 0.16, 1.0] }, // grip
+    GunPart { offset: [0.0, -0.038, -0.025], scale: [0.018, 0.010, 0.050], color: [0.22, 0.22, 0.24, 1.0] }, // trigger guard
+    GunPart { offset: [0.0, 0.035, -0.08], scale: [0.010, 0.014, 0.22], color: [0.24, 0.24, 0.26, 1.0] }, // sight rail
+    GunPart { offset: [0.0, 0.010, -0.52], scale: [0.028, 0.028, 0.028], color: [0.24, 0.24, 0.26, 1.0] }, // muzzle
+];
+
+/// M1A4 Morita Rifle — Starship Troopers bullpup assault rifle
+const RIFLE_PARTS: &[GunPart] = &[
+    GunPart { offset: [0.0, 0.0, 0.04], scale: [0.038, 0.042, 0.20], color: [0.22, 0.22, 0.25, 1.0] },
+    GunPart { offset: [0.0, 0.008, -0.24], scale: [0.012, 0.012, 0.34], color: [0.30, 0.30, 0.33, 1.0] },
+    GunPart { offset: [0.0, 0.005, -0.08], scale: [0.026, 0.028, 0.12], color: [0.20, 0.20, 0.23, 1.0] },
+    GunPart { offset: [0.0, 0.008, -0.44], scale: [0.018, 0.018, 0.030], color: [0.14, 0.14, 0.16, 1.0] },
+    GunPart { offset: [0.0, -0.008, 0.17], scale: [0.034, 0.038, 0.10], color: [0.18, 0.18, 0.20, 1.0] },
+    GunPart { offset: [0.0, -0.008, 0.24], scale: [0.030, 0.036, 0.018], color: [0.10, 0.10, 0.12, 1.0] },
+    GunPart { offset: [0.0, -0.052, 0.0], scale: [0.018, 0.048, 0.024], color: [0.14, 0.14, 0.16, 1.0] },
+    GunPart { offset: [0.0, -0.032, -0.022], scale: [0.014, 0.008, 0.045], color: [0.22, 0.22, 0.25, 1.0] },
+    GunPart { offset: [0.0, 0.032, -0.01], scale: [0.014, 0.014, 0.16], color: [0.20, 0.20, 0.22, 1.0] },
+    GunPart { offset: [0.0, 0.042, -0.08], scale: [0.010, 0.010, 0.010], color: [0.25, 0.25, 0.28, 1.0] },
+    GunPart { offset: [0.0, 0.042, 0.06], scale: [0.010, 0.010, 0.010], color: [0.25, 0.25, 0.28, 1.0] },
+    GunPart { offset: [0.0, 0.030, -0.32], scale: [0.005, 0.020, 0.005], color: [0.25, 0.25, 0.28, 1.0] },
+    GunPart { offset: [0.0, -0.042, 0.08], scale: [0.020, 0.048, 0.028], color: [0.16, 0.16, 0.18, 1.0] },
+    GunPart { offset: [0.0, -0.022, -0.14], scale: [0.016, 0.016, 0.18], color: [0.26, 0.26, 0.28, 1.0] },
+    GunPart { offset: [0.0, -0.022, -0.04], scale: [0.024, 0.018, 0.055], color: [0.18, 0.18, 0.20, 1.0] },
+    GunPart { offset: [0.022, 0.024, 0.06], scale: [0.008, 0.008, 0.018], color: [0.30, 0.30, 0.32, 1.0] },
+    GunPart { offset: [0.022, 0.005, 0.02], scale: [0.004, 0.018, 0.035], color: [0.28, 0.28, 0.30, 1.0] },
+    GunPart { offset: [0.018, -0.005, -0.16], scale: [0.005, 0.005, 0.005], color: [0.30, 0.30, 0.32, 1.0] },
+];
+
+/// Box parts of a weapon's model and its muzzle point, in the gun's frame. Drawn as unit cubes,
+/// both for the first-person viewmodel and for weapons lying in the world.
+pub fn gun_parts(weapon_type: WeaponType) -> (&'static [GunPart], Vec3) {
+    match weapon_type {
+        WeaponType::Shotgun => (SHOTGUN_PARTS, Vec3::new(0.0, 0.006, -0.40)),
+        WeaponType::MachineGun => (MG_PARTS, Vec3::new(0.0, 0.010, -0.54)),
+        _ => (RIFLE_PARTS, Vec3::new(0.0, 0.008, -0.46)),
+    }
+}
+
 /// Shell casing type — matches weapon for persistent, weapon-appropriate shells.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShellCasingType {
@@ -28,6 +99,20 @@ pub struct ViewmodelAnimState {
     pub bob_time: f32,
     /// Weapon switch animation (1.0 = down, decays to 0 = ready).
     pub switch_anim: f32,
+    /// Melee swing (1.0 on the strike, decays to 0): the gun is driven forward and across.
+    pub melee_swing: f32,
+    /// Blend into the reload pose (0 = not reloading, 1 = fully in the reload clip).
+    pub reload_weight: f32,
+    /// Progress through the current reload cycle (0-1): one magazine swap or one loaded round.
+    pub reload_cycle: f32,
+    /// Current reload loads rounds one at a time (pump/bolt) instead of swapping a magazine.
+    pub reload_per_round: bool,
+    /// Set each frame by `set_reload`; drives `reload_weight`.
+    reloading: bool,
+    /// Breathing sway strength: 1 standing, lower when crouched or prone. Eases toward the
+    /// target set by `set_stance`.
+    pub steadiness: f32,
+    steadiness_target: f32,
 }
 
 impl ViewmodelAnimState {
@@ -39,6 +124,13 @@ impl ViewmodelAnimState {
             sprint_lean: 0.0,
             bob_time: 0.0,
             switch_anim: 0.5, // start with a quick raise animation
+            melee_swing: 0.0,
+            reload_weight: 0.0,
+            reload_cycle: 0.0,
+            reload_per_round: false,
+            reloading: false,
+            steadiness: 1.0,
+            steadiness_target: 1.0,
         }
     }
 
@@ -74,6 +166,43 @@ impl ViewmodelAnimState {
         // Switch animation decay
         self.switch_anim *= (1.0 - 6.0 * dt).max(0.0);
         if self.switch_anim < 0.001 { self.switch_anim = 0.0; }
+
+        // Melee swing decay
+        self.melee_swing *= (1.0 - 7.0 * dt).max(0.0);
+        if self.melee_swing < 0.001 { self.melee_swing = 0.0; }
+
+        // Reload pose eases in and back out (also when a pump reload is cut short by firing)
+        let reload_target = if self.reloading { 1.0 } else { 0.0 };
+        self.reload_weight += (reload_target - self.reload_weight) * (10.0 * dt).min(1.0);
+        if self.reload_weight < 0.001 { self.reload_weight = 0.0; }
+
+        // Settling into a stance steadies the breathing over about half a second
+        self.steadiness += (self.steadiness_target - self.steadiness) * (4.0 * dt).min(1.0);
+    }
+
+    /// Feed the player's stance: crouching halves the breathing sway, prone nearly stills it.
+    pub fn set_stance(&mut self, crouching: bool, prone: bool) {
+        self.steadiness_target = if prone {
+            0.25
+        } else if crouching {
+            0.55
+        } else {
+            1.0
+        };
+    }
+
+    /// Feed the current weapon's reload state: cycle progress and style, or None when not reloading.
+    pub fn set_reload(&mut self, reload: Option<(f32, ReloadStyle)>) {
+        self.reloading = reload.is_some();
+        if let Some((progress, style)) = reload {
+            self.reload_cycle = progress;
+            self.reload_per_round = style == ReloadStyle::PerRound;
+        }
+    }
+
+    /// Trigger the melee swing.
+    pub fn trigger_melee(&mut self) {
+        self.melee_swing = 1.0;
     }
 
     /// Trigger weapon switch animation (weapon drops and raises).
@@ -93,6 +222,14 @@ impl ViewmodelAnimState {
         let sway_x = (self.sway_time * 0.7).sin() * 0.0025 * sway_scale;
         let sway_y = (self.sway_time * 1.1).cos() * 0.0018 * sway_scale;
 
+        // Breathing: a slow figure-eight drift that shows most through the sights
+        let breath = self.sway_time * BREATH_RATE * std::f32::consts::TAU;
+        let breath_scale = self.steadiness * (0.3 + aim_progress * 0.7);
+        let breath_x = breath.sin() * BREATH_OFFSET * breath_scale;
+        let breath_y = (breath * 2.0).sin() * BREATH_OFFSET * 0.6 * breath_scale;
+        let breath_yaw = breath.sin() * BREATH_ROTATION * breath_scale;
+        let breath_pitch = (breath * 2.0).sin() * BREATH_ROTATION * 0.6 * breath_scale;
+
         // Fire recoil
         let kick = self.fire_kick;
         let kick_back = kick * 0.025;       // push gun backward (+Z in view space)
@@ -121,23 +258,118 @@ impl ViewmodelAnimState {
         let switch_y = -switch_drop * 0.15;
         let switch_rot_x = switch_drop * 0.3;
 
+        // Melee: a butt-stroke that thrusts forward and sweeps toward center, peaking mid-swing
+        let swing = if self.melee_swing > 0.0 {
+            ((1.0 - self.melee_swing) * std::f32::consts::PI).sin()
+        } else {
+            0.0
+        };
+        let (melee_x, melee_y, melee_z) = (-swing * 0.09, swing * 0.02, -swing * 0.14);
+        let (melee_yaw, melee_roll) = (swing * 0.7, -swing * 0.5);
+
+        // Reload: cant the gun and lower it; a magazine swap seats with an upward jolt late in
+        // the cycle, a per-round reload nudges forward as each round is pushed in
+        let w = self.reload_weight;
+        let (reload_roll, reload_pitch, reload_y, reload_z) = if self.reload_per_round {
+            let push = (self.reload_cycle * std::f32::consts::PI).sin();
+            (w * 0.35, w * 0.12, -w * 0.04, -w * push * 0.012)
+        } else {
+            let seat = ((self.reload_cycle - 0.6) / 0.1).clamp(0.0, 1.0) * ((0.85 - self.reload_cycle) / 0.15).clamp(0.0, 1.0);
+            (w * 0.5, w * 0.2, -w * 0.07 + w * seat * 0.02, 0.0)
+        };
+
         // Compose offset (ADS delta brings gun to sight-aligned position)
         let offset = Vec3::new(
-            sway_x + bob_x + ads_delta.x,
-            sway_y + kick_up - sprint_lower_y + bob_y + ads_delta.y + switch_y,
-            kick_back + sprint_forward_z + ads_delta.z,
+            sway_x + bob_x + breath_x + ads_delta.x + melee_x,
+            sway_y + breath_y + kick_up - sprint_lower_y + bob_y + ads_delta.y + switch_y + reload_y + melee_y,
+            kick_back + sprint_forward_z + ads_delta.z + reload_z + melee_z,
         );
 
         // Compose rotation
         let rotation = Quat::from_euler(
             glam::EulerRot::XYZ,
-            kick_rot_x + switch_rot_x + ads_tilt_x,
-            0.0,
-            kick_rot_z + sprint_tilt_z,
+            kick_rot_x + switch_rot_x + ads_tilt_x + reload_pitch + breath_pitch,
+            breath_yaw + melee_yaw,
+            kick_rot_z + sprint_tilt_z + reload_roll + melee_roll,
         );
 
         (offset, rotation)
     }
+
+    /// View-space pose of the held item: gun pivot position, rotation, and muzzle point in the
+    /// gun's frame. `weapon` is None for the entrenchment shovel (no muzzle).
+    pub fn held_pose(&self, weapon: Option<WeaponType>, aim_progress: f32) -> (Vec3, Quat, Vec3) {
+        let Some(weapon_type) = weapon else {
+            // Shovel: held in both hands, blade down
+            let base_pos = Vec3::new(0.12, -0.14, -0.34);
+            let (anim_offset, anim_rot) = self.compute_transform(0.0, base_pos, base_pos);
+            return (base_pos + anim_offset, anim_rot * Quat::from_rotation_x(-0.5), Vec3::ZERO);
+        };
+        // Base viewmodel position in view space: right, below eye, forward (hip-fire)
+        let base_pos = Vec3::new(0.18, -0.11, -0.38);
+        // ADS target: gun pivot position when looking through sights.
+        let ads_target = match weapon_type {
+            WeaponType::Shotgun => Vec3::new(0.0, -0.025, -0.22),
+            WeaponType::MachineGun => Vec3::new(0.0, -0.038, -0.25),
+            _ => Vec3::new(0.0, -0.042, -0.24),
+        };
+        let (anim_offset, anim_rot) = self.compute_transform(aim_progress, base_pos, ads_target);
+        let (_, muzzle_offset) = gun_parts(weapon_type);
+        (base_pos + anim_offset, anim_rot, muzzle_offset)
+    }
+}
+
+/// The first-person weapon as a transform hierarchy: the gun hangs off an anchor that follows
+/// the camera and the muzzle hangs off the gun, so the renderer and muzzle effects read
+/// resolved `GlobalTransform`s instead of composing view-space offsets by hand.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewmodelRig {
+    pub anchor: Entity,
+    pub gun: Entity,
+    pub muzzle: Entity,
+}
+
+impl ViewmodelRig {
+    pub fn spawn(world: &mut World) -> Self {
+        let anchor = world.spawn((Transform::default(),));
+        let gun = world.spawn((Transform::default(), Parent(anchor)));
+        let muzzle = world.spawn((Transform::default(), Parent(gun)));
+        Self { anchor, gun, muzzle }
+    }
+
+    /// Whether every part still exists (the world is emptied between missions).
+    pub fn is_spawned(&self, world: &World) -> bool {
+        world.contains(self.anchor) && world.contains(self.gun) && world.contains(self.muzzle)
+    }
+
+    /// Move the anchor onto the camera: `view_to_world` is the inverse view matrix.
+    pub fn attach_to_camera(&self, world: &mut World, view_to_world: Mat4) {
+        let (_, rotation, position) = view_to_world.to_scale_rotation_translation();
+        if let Ok(mut transform) = world.get::<&mut Transform>(self.anchor) {
+            *transform = Transform::from_position_rotation(position, rotation);
+        }
+    }
+
+    /// Set the gun's view-space pose and the muzzle point in the gun's frame.
+    pub fn pose(&self, world: &mut World, gun_pos: Vec3, gun_rot: Quat, muzzle_offset: Vec3) {
+        if let Ok(mut transform) = world.get::<&mut Transform>(self.gun) {
+            *transform = Transform::from_position_rotation(gun_pos, gun_rot);
+        }
+        if let Ok(mut transform) = world.get::<&mut Transform>(self.muzzle) {
+            *transform = Transform::from_position(muzzle_offset);
+        }
+    }
+
+    /// World matrix of a rig part as of the last `update_global_transforms`.
+    pub fn global(&self, world: &World, part: Entity) -> Mat4 {
+        world.get::<&GlobalTransform>(part).map_or(Mat4::IDENTITY, |g| g.0)
+    }
+
+    /// World-space muzzle position right now, resolved through the hierarchy so a shot fired
+    /// mid-frame doesn't use last frame's camera.
+    pub fn muzzle_position(&self, world: &World) -> Vec3 {
+        world_matrix(world, self.muzzle).w_axis.truncate()
+    }
 }
 
 /// An ejected shell casing (rigid body — flies then rests on ground, can roll when kicked).
@@ -185,3 +417,23 @@ impl GroundedShellCasing {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_core::update_global_transforms;
+
+    #[test]
+    fn muzzle_follows_the_camera_through_the_rig() {
+        let mut world = World::new();
+        let rig = ViewmodelRig::spawn(&mut world);
+        let camera = Mat4::from_rotation_translation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2), Vec3::new(5.0, 2.0, 0.0));
+        rig.attach_to_camera(&mut world, camera);
+        rig.pose(&mut world, Vec3::new(0.2, -0.1, -0.4), Quat::IDENTITY, Vec3::new(0.0, 0.0, -0.5));
+        update_global_transforms(&mut world);
+
+        let expected = camera.transform_point3(Vec3::new(0.2, -0.1, -0.9));
+        assert!(rig.global(&world, rig.muzzle).w_axis.truncate().abs_diff_eq(expected, 1e-5));
+        assert!(rig.muzzle_position(&world).abs_diff_eq(expected, 1e-5));
+    }
+}
@@ -1,11 +1,20 @@
 //! Weapon system with multiple weapon types.
+//!
+//! Per-weapon stats come from `weapons.ron` (loaded once at startup); any weapon missing from
+//! the file, or the whole file if it is absent or invalid, falls back to the compiled defaults.
+//! Weapons with a `projectile_speed` fire real projectiles that fly under gravity and explode
+//! on impact; weapons with a `flame_cone` spray a continuous damage cone; everything else is
+//! hitscan.
 
 use engine_core::{Damage, DamageType, Health, Lifetime, Transform, Velocity, Vec3};
 use hecs::World;
-use physics::{PhysicsWorld, RaycastHit};
+use physics::{ColliderHandle, CollisionGroup, PhysicsWorld, RaycastHit};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Weapon types available to the player.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WeaponType {
     /// Standard assault rifle - high fire rate, medium damage.
     Rifle,
@@ -21,6 +30,276 @@ pub enum WeaponType {
     MachineGun,
 }
 
+impl WeaponType {
+    pub const ALL: [WeaponType; 6] = [
+        WeaponType::Rifle,
+        WeaponType::Shotgun,
+        WeaponType::Sniper,
+        WeaponType::Rocket,
+        WeaponType::Flamethrower,
+        WeaponType::MachineGun,
+    ];
+}
+
+/// Tunable stats for one weapon type, as stored in `weapons.ron`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponDef {
+    pub damage: f32,
+    /// Shots per second.
+    pub fire_rate: f32,
+    /// Seconds per reload cycle: the whole magazine, or one round for `PerRound` weapons.
+    pub reload_time: f32,
+    #[serde(default)]
+    pub reload_style: ReloadStyle,
+    /// Keeps a round chambered through a reload (mag + 1 when topping off).
+    #[serde(default)]
+    pub chambers_round: bool,
+    pub magazine_size: u32,
+    /// Spare ammo carried at spawn.
+    pub reserve_ammo: u32,
+    pub range: f32,
+    /// In degrees.
+    pub spread: f32,
+    /// Pellets per shot (shotgun).
+    pub projectile_count: u32,
+    /// Ejected casing size; the casing count follows `projectile_count`.
+    pub shell_size: f32,
+    /// Ejection speed multiplier for casings.
+    pub shell_eject_speed: f32,
+    /// Per-shot view kick as (pitch up, yaw right) in radians, indexed by consecutive shots;
+    /// shots past the end repeat the last entry. Left out (empty), the compiled pattern is kept.
+    #[serde(default)]
+    pub recoil_pattern: Vec<(f32, f32)>,
+    /// Seconds without firing before the pattern restarts and the view starts recovering.
+    #[serde(default)]
+    pub recoil_reset: f32,
+    /// Rate (1/s) at which the view returns toward the original aim point between bursts.
+    #[serde(default)]
+    pub recoil_recovery: f32,
+    /// How hard each round/pellet staggers a living bug (scales damage); 0 = no flinch.
+    #[serde(default)]
+    pub knockback: f32,
+    /// Muzzle speed (m/s) of a physical projectile; 0 = hitscan.
+    #[serde(default)]
+    pub projectile_speed: f32,
+    /// Blast radius when a projectile detonates; 0 = no splash.
+    #[serde(default)]
+    pub explosion_radius: f32,
+    /// Half-angle (degrees) of a continuous flame cone; 0 = not a flame weapon. Flame weapons
+    /// drain `fire_rate` rounds per second and deal `damage` per round to everything in the cone.
+    #[serde(default)]
+    pub flame_cone: f32,
+    /// Magnification when aiming down sights; 1 = no zoom.
+    #[serde(default = "no_zoom")]
+    pub ads_zoom: f32,
+    /// Locked until a Major Order reward requisitions it, then selectable as the heavy weapon.
+    #[serde(default)]
+    pub requisition: bool,
+}
+
+fn no_zoom() -> f32 {
+    1.0
+}
+
+impl WeaponDef {
+    /// Compiled-in stats, used when `weapons.ron` doesn't cover a weapon.
+    pub fn builtin(weapon_type: WeaponType) -> Self {
+        let (damage, fire_rate, reload_time, magazine_size, reserve, range, spread, projectiles, shell_size, shell_speed) =
+            match weapon_type {
+                WeaponType::Rifle => (25.0, 10.0, 2.0, 30, 180, 100.0, 2.0, 1, 0.015, 1.0),
+                WeaponType::Shotgun => (15.0, 1.5, 0.45, 8, 48, 30.0, 8.0, 8, 0.022, 1.2),
+                WeaponType::Sniper => (150.0, 0.8, 0.6, 5, 30, 500.0, 0.5, 1, 0.020, 1.3),
+                WeaponType::Rocket => (200.0, 0.5, 3.5, 1, 12, 200.0, 0.0, 1, 0.018, 0.9),
+                WeaponType::Flamethrower => (5.0, 30.0, 0.0, 100, 300, 15.0, 10.0, 1, 0.010, 0.7),
+                WeaponType::MachineGun => (18.0, 18.0, 4.0, 200, 600, 120.0, 3.0, 1, 0.017, 1.1), // Morita MG: shreds hordes
+            };
+        let (recoil_pattern, recoil_reset, recoil_recovery) = match weapon_type {
+            // Tight: small climb with a slight wobble
+            WeaponType::Rifle => (
+                vec![(0.006, 0.0), (0.006, 0.001), (0.005, -0.001), (0.005, 0.001), (0.004, -0.001)],
+                0.25,
+                8.0,
+            ),
+            WeaponType::Shotgun => (vec![(0.03, 0.004)], 0.4, 6.0),
+            WeaponType::Sniper => (vec![(0.05, 0.0)], 0.5, 4.0),
+            WeaponType::Rocket => (vec![(0.04, 0.0)], 0.5, 4.0),
+            WeaponType::Flamethrower => (vec![(0.001, 0.0)], 0.15, 10.0),
+            // Climbs hard and drifts right, then swings back left on long bursts
+            WeaponType::MachineGun => (
+                vec![
+                    (0.004, 0.0),
+                    (0.005, 0.001),
+                    (0.006, 0.002),
+                    (0.007, 0.003),
+                    (0.008, 0.004),
+                    (0.008, 0.005),
+                    (0.007, 0.004),
+                    (0.006, 0.002),
+                    (0.006, 0.0),
+                    (0.006, -0.002),
+                    (0.006, -0.003),
+                ],
+                0.3,
+                5.0,
+            ),
+        };
+        // Pump shotgun and bolt sniper load round by round; open-bolt MG, rocket and flamer don't chamber
+        let reload_style = match weapon_type {
+            WeaponType::Shotgun | WeaponType::Sniper => ReloadStyle::PerRound,
+            _ => ReloadStyle::Magazine,
+        };
+        let chambers_round = matches!(weapon_type, WeaponType::Rifle | WeaponType::Shotgun | WeaponType::Sniper);
+        let (projectile_speed, explosion_radius) = match weapon_type {
+            WeaponType::Rocket => (60.0, 6.0),
+            _ => (0.0, 0.0),
+        };
+        let flame_cone = if weapon_type == WeaponType::Flamethrower { 12.0 } else { 0.0 };
+        // Iron sights barely magnify; the sniper's scope does
+        let ads_zoom = match weapon_type {
+            WeaponType::Rifle => 1.5,
+            WeaponType::Shotgun | WeaponType::Flamethrower => 1.15,
+            WeaponType::Sniper => 4.0,
+            WeaponType::Rocket | WeaponType::MachineGun => 1.3,
+        };
+        // Heavy single shots stop a charge outright; automatics chip away at it
+        let knockback = match weapon_type {
+            WeaponType::Rifle => 0.8,
+            WeaponType::Shotgun => 0.9,
+            WeaponType::Sniper | WeaponType::Rocket => 3.0,
+            WeaponType::Flamethrower => 0.0,
+            WeaponType::MachineGun => 0.6,
+        };
+        Self {
+            damage,
+            fire_rate,
+            reload_time,
+            reload_style,
+            chambers_round,
+            magazine_size,
+            reserve_ammo: reserve,
+            range,
+            spread,
+            projectile_count: projectiles,
+            shell_size,
+            shell_eject_speed: shell_speed,
+            recoil_pattern,
+            recoil_reset,
+            recoil_recovery,
+            knockback,
+            projectile_speed,
+            explosion_radius,
+            flame_cone,
+            ads_zoom,
+            requisition: weapon_type == WeaponType::Flamethrower,
+        }
+    }
+
+    /// Field of view when fully aimed in, for a hip-fire FOV of `hip_fov` (both in degrees).
+    pub fn ads_fov(&self, hip_fov: f32) -> f32 {
+        let half = (hip_fov.to_radians() * 0.5).tan() / self.ads_zoom.max(1.0);
+        (2.0 * half.atan()).to_degrees()
+    }
+}
+
+/// Recoil pattern progress and the view kick not yet recovered.
+#[derive(Debug, Clone, Default)]
+pub struct Recoil {
+    /// Consecutive shots in the current burst (index into the pattern).
+    shot: usize,
+    /// Seconds since the last shot.
+    since_shot: f32,
+    /// Accumulated kick (pitch, yaw) still to be returned.
+    pitch: f32,
+    yaw: f32,
+}
+
+impl Recoil {
+    /// Advance the pattern for one shot. Returns the (pitch up, yaw right) kick to add to the view.
+    pub fn kick(&mut self, def: &WeaponDef, scale: f32) -> (f32, f32) {
+        let last = def.recoil_pattern.len().saturating_sub(1);
+        let (pitch, yaw) = def.recoil_pattern.get(self.shot.min(last)).copied().unwrap_or_default();
+        self.shot += 1;
+        self.since_shot = 0.0;
+        self.pitch += pitch * scale;
+        self.yaw += yaw * scale;
+        (pitch * scale, yaw * scale)
+    }
+
+    /// Once the trigger has been released for `recoil_reset`, restart the pattern and pull the
+    /// view back toward where the burst started. Returns the (pitch up, yaw right) change to apply.
+    pub fn recover(&mut self, def: &WeaponDef, dt: f32) -> (f32, f32) {
+        self.since_shot += dt;
+        if self.since_shot < def.recoil_reset {
+            return (0.0, 0.0);
+        }
+        self.shot = 0;
+        let k = (def.recoil_recovery * dt).min(1.0);
+        let (dp, dy) = (-self.pitch * k, -self.yaw * k);
+        self.pitch += dp;
+        self.yaw += dy;
+        (dp, dy)
+    }
+}
+
+static WEAPON_DEFS: OnceLock<HashMap<WeaponType, WeaponDef>> = OnceLock::new();
+
+fn builtin_defs() -> HashMap<WeaponType, WeaponDef> {
+    WeaponType::ALL.iter().map(|&t| (t, WeaponDef::builtin(t))).collect()
+}
+
+/// Parse `weapons.ron` text; weapons the file leaves out keep their compiled defaults.
+fn parse_weapon_defs(s: &str) -> Result<HashMap<WeaponType, WeaponDef>, ron::error::SpannedError> {
+    let mut overrides: HashMap<WeaponType, WeaponDef> = ron::from_str(s)?;
+    // Files from before recoil patterns leave these out: keep the compiled recoil
+    for (&weapon_type, def) in &mut overrides {
+        let builtin = WeaponDef::builtin(weapon_type);
+        if def.recoil_pattern.is_empty() {
+            def.recoil_pattern = builtin.recoil_pattern;
+        }
+        if def.recoil_reset <= 0.0 {
+            def.recoil_reset = builtin.recoil_reset;
+        }
+        if def.recoil_recovery <= 0.0 {
+            def.recoil_recovery = builtin.recoil_recovery;
+        }
+    }
+    let mut defs = builtin_defs();
+    defs.extend(overrides);
+    Ok(defs)
+}
+
+/// Load weapon stats from `weapons.ron` in the current directory. Call once at startup, before
+/// any `Weapon` is created; a missing or invalid file leaves the compiled defaults in place.
+pub fn load_weapon_defs() {
+    let path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("weapons.ron");
+    let defs = match std::fs::read_to_string(&path) {
+        Ok(data) => parse_weapon_defs(&data).unwrap_or_else(|e| {
+            log::warn!("Invalid weapon data at {:?}: {}, using defaults", path, e);
+            builtin_defs()
+        }),
+        Err(_) => builtin_defs(),
+    };
+    if WEAPON_DEFS.set(defs).is_err() {
+        log::warn!("Weapon data was already loaded; ignoring {:?}", path);
+    }
+}
+
+/// Stats for `weapon_type`, from `weapons.ron` if it was loaded.
+pub fn weapon_def(weapon_type: WeaponType) -> &'static WeaponDef {
+    &WEAPON_DEFS.get_or_init(builtin_defs)[&weapon_type]
+}
+
+/// How a weapon's reload cycle loads ammo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReloadStyle {
+    /// Swap the whole magazine in one `reload_time` cycle.
+    #[default]
+    Magazine,
+    /// Pump/bolt weapons: load one round per `reload_time`; firing with rounds loaded cancels
+    /// the rest of the reload.
+    PerRound,
+}
+
 /// Weapon instance with current state.
 #[derive(Debug, Clone)]
 pub struct Weapon {
@@ -34,6 +313,15 @@ pub struct Weapon {
     pub range: f32,
     pub spread: f32, // In degrees
     pub projectile_count: u32, // For shotgun
+    pub reload_style: ReloadStyle,
+    /// Closed-bolt weapons keep a round chambered, so a tactical reload tops off to mag + 1.
+    pub chambers_round: bool,
+    /// Half-angle (degrees) of a continuous flame cone; 0 = not a flame weapon.
+    pub flame_cone: f32,
+    /// Fractional rounds burned by `drain_fuel` but not yet taken from the magazine.
+    fuel_drained: f32,
+    /// Reload speed multiplier from the class passive (1.0 = normal).
+    pub reload_speed: f32,
     
     // State
     pub fire_cooldown: f32,
@@ -43,27 +331,23 @@ pub struct Weapon {
 
 impl Weapon {
     pub fn new(weapon_type: WeaponType) -> Self {
-        let (damage, fire_rate, reload_time, magazine_size, reserve, range, spread, projectiles) =
-            match weapon_type {
-                WeaponType::Rifle => (25.0, 10.0, 2.0, 30, 180, 100.0, 2.0, 1),
-                WeaponType::Shotgun => (15.0, 1.5, 2.5, 8, 48, 30.0, 8.0, 8),
-                WeaponType::Sniper => (150.0, 0.8, 3.0, 5, 30, 500.0, 0.5, 1),
-                WeaponType::Rocket => (200.0, 0.5, 3.5, 1, 12, 200.0, 0.0, 1),
-                WeaponType::Flamethrower => (5.0, 30.0, 0.0, 100, 300, 15.0, 10.0, 1),
-                WeaponType::MachineGun => (18.0, 18.0, 4.0, 200, 600, 120.0, 3.0, 1), // Morita MG: shreds hordes
-            };
-
+        let def = weapon_def(weapon_type);
         Self {
             weapon_type,
-            damage,
-            fire_rate,
-            reload_time,
-            magazine_size,
-            current_ammo: magazine_size,
-            reserve_ammo: reserve,
-            range,
-            spread,
-            projectile_count: projectiles,
+            damage: def.damage,
+            fire_rate: def.fire_rate,
+            reload_time: def.reload_time,
+            magazine_size: def.magazine_size,
+            current_ammo: def.magazine_size,
+            reserve_ammo: def.reserve_ammo,
+            range: def.range,
+            spread: def.spread,
+            projectile_count: def.projectile_count,
+            reload_style: def.reload_style,
+            chambers_round: def.chambers_round,
+            flame_cone: def.flame_cone,
+            fuel_drained: 0.0,
+            reload_speed: 1.0,
             fire_cooldown: 0.0,
             reload_timer: 0.0,
             is_reloading: false,
@@ -79,16 +363,21 @@ impl Weapon {
 
         // Handle reloading
         if self.is_reloading {
-            self.reload_timer -= dt;
-            if self.reload_timer <= 0.0 {
-                self.finish_reload();
+            self.reload_timer -= dt * self.reload_speed;
+            while self.is_reloading && self.reload_timer <= 0.0 {
+                match self.reload_style {
+                    ReloadStyle::Magazine => self.finish_reload(),
+                    ReloadStyle::PerRound => self.load_round(),
+                }
             }
         }
     }
 
-    /// Check if weapon can fire.
+    /// Check if weapon can fire. Pump/bolt weapons may fire out of a partial reload.
     pub fn can_fire(&self) -> bool {
-        self.fire_cooldown <= 0.0 && self.current_ammo > 0 && !self.is_reloading
+        self.fire_cooldown <= 0.0
+            && self.current_ammo > 0
+            && (!self.is_reloading || self.reload_style == ReloadStyle::PerRound)
     }
 
     /// Fire the weapon, consuming ammo.
@@ -97,14 +386,49 @@ impl Weapon {
             return false;
         }
 
+        // Rounds already loaded stay in the tube
+        self.is_reloading = false;
+        self.reload_timer = 0.0;
         self.current_ammo -= 1;
         self.fire_cooldown = 1.0 / self.fire_rate;
         true
     }
 
+    /// Flame weapons: burn `fire_rate` rounds per second while the trigger is held.
+    /// Returns false when there's nothing to burn (empty or reloading).
+    pub fn drain_fuel(&mut self, dt: f32) -> bool {
+        if self.is_reloading || self.current_ammo == 0 {
+            return false;
+        }
+        self.fuel_drained += self.fire_rate * dt;
+        let whole = self.fuel_drained.floor();
+        self.fuel_drained -= whole;
+        self.current_ammo = self.current_ammo.saturating_sub(whole as u32);
+        true
+    }
+
+    /// Rounds the weapon holds when full: one extra in the chamber if it wasn't run dry.
+    pub fn capacity(&self) -> u32 {
+        if self.chambers_round && self.current_ammo > 0 {
+            self.magazine_size + 1
+        } else {
+            self.magazine_size
+        }
+    }
+
+    /// Reserve the weapon is issued with; resupply tops back up to this.
+    pub fn full_reserve(&self) -> u32 {
+        weapon_def(self.weapon_type).reserve_ammo
+    }
+
+    /// Whether a reload would add any rounds.
+    pub fn can_reload(&self) -> bool {
+        !self.is_reloading && self.reserve_ammo > 0 && self.current_ammo < self.capacity()
+    }
+
     /// Start reloading.
     pub fn start_reload(&mut self) {
-        if self.is_reloading || self.reserve_ammo == 0 || self.current_ammo == self.magazine_size {
+        if !self.can_reload() {
             return;
         }
 
@@ -112,9 +436,21 @@ impl Weapon {
         self.reload_timer = self.reload_time;
     }
 
+    /// Progress through the current reload cycle (0-1), or None when not reloading.
+    pub fn reload_progress(&self) -> Option<f32> {
+        if !self.is_reloading {
+            return None;
+        }
+        Some(if self.reload_time > 0.0 {
+            (1.0 - self.reload_timer / self.reload_time).clamp(0.0, 1.0)
+        } else {
+            1.0
+        })
+    }
+
     /// Finish reloading.
     fn finish_reload(&mut self) {
-        let needed = self.magazine_size - self.current_ammo;
+        let needed = self.capacity() - self.current_ammo;
         let available = needed.min(self.reserve_ammo);
 
         self.current_ammo += available;
@@ -123,6 +459,20 @@ impl Weapon {
         self.reload_timer = 0.0;
     }
 
+    /// Load one round of a per-round reload and start the next cycle if there's room.
+    fn load_round(&mut self) {
+        if self.reserve_ammo > 0 && self.current_ammo < self.capacity() {
+            self.current_ammo += 1;
+            self.reserve_ammo -= 1;
+        }
+        if self.reserve_ammo > 0 && self.current_ammo < self.capacity() {
+            self.reload_timer += self.reload_time;
+        } else {
+            self.is_reloading = false;
+            self.reload_timer = 0.0;
+        }
+    }
+
     /// Check if reloading.
     pub fn is_reloading(&self) -> bool {
         self.is_reloading
@@ -210,6 +560,68 @@ impl WeaponSystem {
         physics.raycast(origin, spread_direction.normalize(), range)
     }
 
+    /// Launch a physical projectile; it flies until it hits something or `lifetime` runs out.
+    pub fn spawn_projectile(
+        &mut self,
+        world: &mut World,
+        origin: Vec3,
+        velocity: Vec3,
+        projectile: Projectile,
+        lifetime: f32,
+    ) {
+        let entity = world.spawn((
+            Transform::from_position(origin),
+            Velocity::new(velocity),
+            projectile,
+            Lifetime::new(lifetime),
+        ));
+        self.projectiles.push(entity);
+    }
+
+    /// Move projectiles under gravity. Each step is a swept raycast against terrain, props and
+    /// enemies, so fast rockets can't tunnel through thin geometry. Returns the projectiles that
+    /// hit something or ran out of flight time (they are despawned); the caller applies damage.
+    pub fn update_projectiles(&mut self, world: &mut World, physics: &PhysicsWorld, dt: f32) -> Vec<ProjectileImpact> {
+        let mut impacts = Vec::new();
+        let mut finished = Vec::new();
+        let filter = CollisionGroup::query(&[CollisionGroup::Environment, CollisionGroup::Enemy]);
+        for (entity, (transform, velocity, projectile, lifetime)) in
+            world.query_mut::<(&mut Transform, &mut Velocity, &Projectile, &mut Lifetime)>()
+        {
+            velocity.linear.y -= PROJECTILE_GRAVITY * dt;
+            let step = velocity.linear * dt;
+            let dist = step.length();
+            let hit = if dist > 0.0 {
+                physics.raycast_filtered(transform.position, step / dist, dist, filter)
+            } else {
+                None
+            };
+            let impact = match hit {
+                Some(hit) => Some((hit.point, hit.normal, Some(hit.collider))),
+                None => {
+                    transform.position += step;
+                    lifetime.update(dt).then_some((transform.position, Vec3::Y, None))
+                }
+            };
+            if let Some((position, normal, collider)) = impact {
+                impacts.push(ProjectileImpact {
+                    position,
+                    normal,
+                    collider,
+                    damage: projectile.damage,
+                    explosion_radius: projectile.explosion_radius,
+                    knockback: projectile.knockback,
+                });
+                finished.push(entity);
+            }
+        }
+        for entity in finished {
+            world.despawn(entity).ok();
+        }
+        self.projectiles.retain(|&e| world.contains(e));
+        impacts
+    }
+
     /// Start reload on a weapon.
     pub fn reload(&self, weapon: &mut Weapon) {
         weapon.start_reload();
@@ -289,11 +701,221 @@ impl Default for WeaponSystem {
     }
 }
 
+/// Downward acceleration on projectiles in flight (m/s^2).
+pub const PROJECTILE_GRAVITY: f32 = 9.81;
+
 /// Projectile component for non-hitscan weapons.
 #[derive(Debug, Clone)]
 pub struct Projectile {
     pub damage: f32,
     pub damage_type: DamageType,
     pub explosion_radius: Option<f32>,
+    /// Stagger on a direct hit (see `WeaponDef::knockback`).
+    pub knockback: f32,
     pub owner: Option<hecs::Entity>,
 }
+
+/// How strongly a target sphere at `point` is caught by a cone from `origin` along unit `dir`:
+/// 1.0 at the nozzle fading to 0.5 at `range`, or None if it lies outside the cone.
+pub fn cone_falloff(origin: Vec3, dir: Vec3, range: f32, half_angle: f32, point: Vec3, radius: f32) -> Option<f32> {
+    let to = point - origin;
+    let along = to.dot(dir);
+    if along < -radius || along > range + radius {
+        return None;
+    }
+    let off_axis = (to - dir * along).length();
+    if off_axis > along.max(0.0) * half_angle.tan() + radius {
+        return None;
+    }
+    Some(1.0 - 0.5 * (along / range).clamp(0.0, 1.0))
+}
+
+/// Patch of ground set alight by a flame weapon; burns anything standing in it until it goes out.
+#[derive(Debug, Clone)]
+pub struct FireZone {
+    pub radius: f32,
+    /// Damage per second to everything inside.
+    pub dps: f32,
+    /// Seconds until it burns out.
+    pub remaining: f32,
+}
+
+/// A weapon lying in the world: dropped by a fallen trooper, salvaged from a wreck, or left
+/// behind by a swap. Look at it and interact to take it into the held slot.
+#[derive(Debug, Clone, Copy)]
+pub struct WeaponPickup {
+    pub weapon_type: WeaponType,
+    /// Rounds that come with it: a magazine's worth loaded, the rest to reserve.
+    pub ammo: u32,
+}
+
+impl WeaponPickup {
+    /// Fresh weapon with its issued magazine and reserve.
+    pub fn full(weapon_type: WeaponType) -> Self {
+        let def = weapon_def(weapon_type);
+        Self { weapon_type, ammo: def.magazine_size + def.reserve_ammo }
+    }
+
+    /// What's left of a weapon being put down.
+    pub fn from_weapon(weapon: &Weapon) -> Self {
+        Self { weapon_type: weapon.weapon_type, ammo: weapon.current_ammo + weapon.reserve_ammo }
+    }
+
+    /// The weapon as picked up, with `reload_speed` from the trooper's class.
+    pub fn into_weapon(self, reload_speed: f32) -> Weapon {
+        let mut weapon = Weapon::new(self.weapon_type);
+        weapon.current_ammo = self.ammo.min(weapon.magazine_size);
+        weapon.reserve_ammo = self.ammo - weapon.current_ammo;
+        weapon.reload_speed = reload_speed;
+        weapon
+    }
+}
+
+/// Where a projectile detonated; `collider` is what it hit (None if it expired in flight).
+#[derive(Debug, Clone)]
+pub struct ProjectileImpact {
+    pub position: Vec3,
+    pub normal: Vec3,
+    pub collider: Option<ColliderHandle>,
+    pub damage: f32,
+    pub explosion_radius: Option<f32>,
+    pub knockback: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_weapon_data_matches_builtin_and_partial_files_fall_back() {
+        let shipped = parse_weapon_defs(include_str!("../../../weapons.ron")).expect("weapons.ron parses");
+        for t in WeaponType::ALL {
+            let (a, b) = (&shipped[&t], WeaponDef::builtin(t));
+            assert_eq!((a.damage, a.magazine_size, a.projectile_count), (b.damage, b.magazine_size, b.projectile_count));
+            assert_eq!(a.knockback, b.knockback);
+            assert_eq!(a.ads_zoom, b.ads_zoom);
+            assert_eq!(a.requisition, b.requisition);
+        }
+        let fov = |t| shipped[&t].ads_fov(70.0);
+        assert!(fov(WeaponType::Sniper) < fov(WeaponType::Rifle) && fov(WeaponType::Rifle) < 70.0);
+
+        let partial = parse_weapon_defs(
+            "{ Sniper: (damage: 99.0, fire_rate: 1.0, reload_time: 1.0, magazine_size: 3, reserve_ammo: 9, \
+             range: 10.0, spread: 0.0, projectile_count: 1, reload_style: PerRound, shell_size: 0.02, shell_eject_speed: 1.0, \
+             recoil_pattern: [(0.05, 0.0)], recoil_reset: 0.5, recoil_recovery: 4.0) }",
+        )
+        .unwrap();
+        assert_eq!(partial[&WeaponType::Sniper].damage, 99.0);
+        assert_eq!(partial[&WeaponType::Sniper].ads_fov(70.0), 70.0);
+        assert_eq!(partial[&WeaponType::Rifle].damage, WeaponDef::builtin(WeaponType::Rifle).damage);
+
+        // Written before recoil patterns: still loads, with the compiled recoil
+        let old = parse_weapon_defs(
+            "{ Rifle: (damage: 30.0, fire_rate: 10.0, reload_time: 2.0, magazine_size: 30, reserve_ammo: 180, \
+             range: 100.0, spread: 2.0, projectile_count: 1, shell_size: 0.015, shell_eject_speed: 1.0) }",
+        )
+        .expect("pre-recoil weapons.ron parses");
+        let (rifle, builtin) = (&old[&WeaponType::Rifle], WeaponDef::builtin(WeaponType::Rifle));
+        assert_eq!(rifle.damage, 30.0);
+        assert_eq!(rifle.recoil_pattern, builtin.recoil_pattern);
+        assert_eq!((rifle.recoil_reset, rifle.recoil_recovery), (builtin.recoil_reset, builtin.recoil_recovery));
+    }
+
+    #[test]
+    fn machine_gun_climbs_and_drifts_more_than_rifle_and_recovers() {
+        let burst = |t: WeaponType| {
+            let (def, mut recoil) = (WeaponDef::builtin(t), Recoil::default());
+            let (mut pitch, mut yaw) = (0.0, 0.0f32);
+            for _ in 0..12 {
+                let (p, y) = recoil.kick(&def, 1.0);
+                pitch += p;
+                yaw += y;
+            }
+            for _ in 0..120 {
+                let (p, y) = recoil.recover(&def, 1.0 / 60.0);
+                pitch += p;
+                yaw += y;
+            }
+            (recoil.pitch, recoil.yaw.abs(), pitch, yaw.abs())
+        };
+        let (rifle, mg) = (burst(WeaponType::Rifle), burst(WeaponType::MachineGun));
+        assert!(mg.0 > rifle.0 && mg.1 > rifle.1);
+        // Two seconds after the burst the view is nearly back on the original aim
+        assert!(mg.2 < 0.01 && mg.3 < 0.01 && rifle.2 < 0.01);
+    }
+
+    #[test]
+    fn reload_keeps_chambered_round_and_pump_reload_can_be_cancelled() {
+        let mut rifle = Weapon::new(WeaponType::Rifle);
+        rifle.current_ammo = 12;
+        rifle.start_reload();
+        rifle.update(rifle.reload_time + 0.01);
+        assert_eq!((rifle.current_ammo, rifle.reserve_ammo), (31, 180 - 19));
+
+        // Run dry: nothing chambered, so only a full magazine
+        let mut dry = Weapon::new(WeaponType::Rifle);
+        dry.current_ammo = 0;
+        dry.start_reload();
+        dry.update(dry.reload_time + 0.01);
+        assert_eq!(dry.current_ammo, 30);
+
+        let mut shotgun = Weapon::new(WeaponType::Shotgun);
+        shotgun.current_ammo = 2;
+        shotgun.start_reload();
+        shotgun.update(shotgun.reload_time * 2.5);
+        assert_eq!(shotgun.current_ammo, 4);
+        assert!(shotgun.is_reloading && shotgun.can_fire());
+        assert!(shotgun.fire());
+        assert!(!shotgun.is_reloading);
+        assert_eq!((shotgun.current_ammo, shotgun.reserve_ammo), (3, 46));
+    }
+
+    #[test]
+    fn flame_cone_catches_targets_in_front_and_drains_fuel_over_time() {
+        let (origin, dir, half) = (Vec3::ZERO, Vec3::NEG_Z, 12f32.to_radians());
+        let near = cone_falloff(origin, dir, 15.0, half, Vec3::new(0.5, 0.0, -3.0), 0.5).unwrap();
+        let far = cone_falloff(origin, dir, 15.0, half, Vec3::new(0.0, 0.0, -14.0), 0.5).unwrap();
+        assert!(near > far && far >= 0.5);
+        assert!(cone_falloff(origin, dir, 15.0, half, Vec3::new(0.0, 0.0, 3.0), 0.5).is_none());
+        assert!(cone_falloff(origin, dir, 15.0, half, Vec3::new(6.0, 0.0, -5.0), 0.5).is_none());
+        assert!(cone_falloff(origin, dir, 15.0, half, Vec3::new(0.0, 0.0, -20.0), 0.5).is_none());
+
+        let mut flamer = Weapon::new(WeaponType::Flamethrower);
+        for _ in 0..60 {
+            assert!(flamer.drain_fuel(1.0 / 60.0));
+        }
+        // 30 rounds per second, however the frames are sliced
+        assert!((69..=70).contains(&flamer.current_ammo));
+        flamer.current_ammo = 0;
+        assert!(!flamer.drain_fuel(0.1));
+    }
+
+    #[test]
+    fn rocket_arcs_down_and_detonates_on_the_ground() {
+        let mut physics = PhysicsWorld::new();
+        let ground = physics.add_ground_plane();
+        physics.update_query_pipeline();
+        let (mut world, mut system) = (World::new(), WeaponSystem::new());
+        let projectile = Projectile {
+            damage: 200.0,
+            damage_type: DamageType::Explosion,
+            explosion_radius: Some(6.0),
+            knockback: 3.0,
+            owner: None,
+        };
+        system.spawn_projectile(&mut world, Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, 0.0, -60.0), projectile, 10.0);
+
+        let mut impact = None;
+        for _ in 0..120 {
+            if let Some(i) = system.update_projectiles(&mut world, &physics, 1.0 / 60.0).pop() {
+                impact = Some(i);
+                break;
+            }
+        }
+        let impact = impact.expect("rocket should hit the ground");
+        assert_eq!(impact.collider, Some(ground));
+        // Dropped 2 m under gravity after ~0.64 s, so it travelled ~38 m downrange
+        assert!(impact.position.y.abs() < 0.01 && (30.0..45.0).contains(&-impact.position.z));
+        assert!(world.is_empty() && system.projectiles.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! Semantic actions and their (rebindable) key / mouse / gamepad bindings.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{GamepadButton, KeyCode, MouseButton};
+
+/// A semantic game action that can be bound to one or more physical inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Sprint,
+    Crouch,
+    Fire,
+    Aim,
+    Reload,
+    Interact,
+    Melee,
+    Grenade,
+    SmokeGrenade,
+    Ability,
+}
+
+impl Action {
+    /// Every action, in display order.
+    pub const ALL: [Action; 15] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Sprint,
+        Action::Crouch,
+        Action::Fire,
+        Action::Aim,
+        Action::Reload,
+        Action::Interact,
+        Action::Melee,
+        Action::Grenade,
+        Action::SmokeGrenade,
+        Action::Ability,
+    ];
+
+    /// Default bindings (keyboard/mouse plus Xbox-style gamepad).
+    pub fn default_bindings(self) -> Vec<Binding> {
+        use Binding::{Gamepad, Key, Mouse};
+        match self {
+            Action::MoveForward => vec![Key(KeyCode::KeyW)],
+            Action::MoveBack => vec![Key(KeyCode::KeyS)],
+            Action::MoveLeft => vec![Key(KeyCode::KeyA)],
+            Action::MoveRight => vec![Key(KeyCode::KeyD)],
+            Action::Jump => vec![Key(KeyCode::Space), Gamepad(GamepadButton::South)],
+            Action::Sprint => vec![
+                Key(KeyCode::ShiftLeft),
+                Key(KeyCode::ShiftRight),
+                Gamepad(GamepadButton::LeftThumb),
+            ],
+            Action::Crouch => vec![
+                Key(KeyCode::ControlLeft),
+                Key(KeyCode::ControlRight),
+                Gamepad(GamepadButton::East),
+            ],
+            Action::Fire => vec![Mouse(MouseButton::Left), Gamepad(GamepadButton::RightTrigger2)],
+            Action::Aim => vec![Mouse(MouseButton::Right), Gamepad(GamepadButton::LeftTrigger2)],
+            Action::Reload => vec![Key(KeyCode::KeyR), Gamepad(GamepadButton::West)],
+            Action::Interact => vec![Key(KeyCode::KeyE), Gamepad(GamepadButton::North)],
+            Action::Melee => vec![Key(KeyCode::KeyV), Gamepad(GamepadButton::RightThumb)],
+            Action::Grenade => vec![Key(KeyCode::KeyG), Gamepad(GamepadButton::RightTrigger)],
+            Action::SmokeGrenade => vec![Key(KeyCode::KeyH)],
+            Action::Ability => vec![Key(KeyCode::KeyQ), Gamepad(GamepadButton::LeftTrigger)],
+        }
+    }
+}
+
+/// A single physical input that can trigger an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_))
+    }
+}
+
+/// Maps each `Action` to the inputs that trigger it. Serializable to RON for custom layouts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionMap {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| (action, action.default_bindings()))
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All inputs bound to an action.
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], |b| b.as_slice())
+    }
+
+    /// Add an extra binding to an action, keeping existing ones.
+    pub fn bind(&mut self, action: Action, binding: Binding) {
+        let list = self.bindings.entry(action).or_default();
+        if !list.contains(&binding) {
+            list.push(binding);
+        }
+    }
+
+    /// Replace an action's bindings on the same device family (keyboard/mouse or gamepad)
+    /// with `binding`. Rebinding a key leaves the gamepad binding alone and vice versa.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let list = self.bindings.entry(action).or_default();
+        list.retain(|b| b.is_gamepad() != binding.is_gamepad());
+        list.push(binding);
+    }
+
+    /// Remove a single binding from an action.
+    pub fn unbind(&mut self, action: Action, binding: Binding) {
+        if let Some(list) = self.bindings.get_mut(&action) {
+            list.retain(|b| *b != binding);
+        }
+    }
+
+    /// Restore one action to its default bindings.
+    pub fn reset(&mut self, action: Action) {
+        self.bindings.insert(action, action.default_bindings());
+    }
+
+    /// Serialize the map to a pretty-printed RON string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Parse a map from RON. Actions missing from the file keep their default bindings.
+    pub fn from_ron(data: &str) -> Result<Self, ron::error::SpannedError> {
+        let mut map: Self = ron::from_str(data)?;
+        for action in Action::ALL {
+            map.bindings
+                .entry(action)
+                .or_insert_with(|| action.default_bindings());
+        }
+        Ok(map)
+    }
+
+    /// Load a map from a RON file. If the file is missing or invalid, returns default bindings.
+    pub fn load(path: &Path) -> Self {
+        if let Ok(data) = std::fs::read_to_string(path) {
+            match Self::from_ron(&data) {
+                Ok(map) => return map,
+                Err(e) => log::warn!("Invalid key bindings at {:?}: {}, using defaults", path, e),
+            }
+        }
+        Self::default()
+    }
+
+    /// Save the map to a RON file. Logs on error.
+    pub fn save(&self, path: &Path) {
+        match self.to_ron() {
+            Ok(s) => {
+                if let Err(e) = std::fs::write(path, s) {
+                    log::warn!("Could not write key bindings to {:?}: {}", path, e);
+                }
+            }
+            Err(e) => log::warn!("Could not serialize key bindings: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ron_roundtrip_preserves_custom_bindings() {
+        let mut map = ActionMap::default();
+        map.rebind(Action::MoveForward, Binding::Key(KeyCode::KeyI));
+        let ron = map.to_ron().unwrap();
+        assert_eq!(ActionMap::from_ron(&ron).unwrap(), map);
+    }
+
+    #[test]
+    fn rebind_keeps_other_device_family() {
+        let mut map = ActionMap::default();
+        map.rebind(Action::Jump, Binding::Key(KeyCode::KeyF));
+        assert_eq!(
+            map.bindings(Action::Jump),
+            &[Binding::Gamepad(GamepadButton::South), Binding::Key(KeyCode::KeyF)]
+        );
+    }
+
+    #[test]
+    fn missing_actions_fall_back_to_defaults() {
+        let map = ActionMap::from_ron("(bindings: { Jump: [Key(KeyF)] })").unwrap();
+        assert_eq!(map.bindings(Action::Jump), &[Binding::Key(KeyCode::KeyF)]);
+        assert_eq!(map.bindings(Action::Reload), Action::Reload.default_bindings().as_slice());
+    }
+}
@@ -0,0 +1,76 @@
+//! Gamepad polling via gilrs. Feeds controller events into `InputState`.
+
+use gilrs::{EventType, Gilrs};
+
+use crate::{ElementState, InputState};
+
+/// Owns the gilrs context and pumps controller events into `InputState` once per frame.
+/// If no gamepad backend is available the manager is inert and the game stays keyboard/mouse only.
+pub struct GamepadManager {
+    gilrs: Option<Gilrs>,
+}
+
+impl GamepadManager {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(g) => Some(g),
+            // Backend not supported on this platform: gilrs still hands back a dummy context.
+            Err(gilrs::Error::NotImplemented(g)) => {
+                log::warn!("Gamepad backend not implemented on this platform; gamepads disabled");
+                Some(g)
+            }
+            Err(e) => {
+                log::warn!("Failed to initialise gamepad support: {}", e);
+                None
+            }
+        };
+        if let Some(ref g) = gilrs {
+            for (_, pad) in g.gamepads() {
+                log::info!("Gamepad detected: {}", pad.name());
+            }
+        }
+        Self { gilrs }
+    }
+
+    /// Drain pending controller events into `input`. Call once per frame before reading input.
+    pub fn poll(&mut self, input: &mut InputState) {
+        let Some(gilrs) = self.gilrs.as_mut() else {
+            return;
+        };
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    input.process_gamepad_button(button, ElementState::Pressed);
+                }
+                EventType::ButtonReleased(button, _) => {
+                    input.process_gamepad_button(button, ElementState::Released);
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    input.process_gamepad_axis(axis, value);
+                }
+                EventType::Connected => {
+                    log::info!("Gamepad connected: {}", gilrs.gamepad(event.id).name());
+                }
+                EventType::Disconnected => {
+                    log::info!("Gamepad disconnected");
+                    // Buttons held on the removed pad will never see a release event.
+                    input.clear_gamepad();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether at least one gamepad is currently connected.
+    pub fn is_connected(&self) -> bool {
+        self.gilrs
+            .as_ref()
+            .is_some_and(|g| g.gamepads().next().is_some())
+    }
+}
+
+impl Default for GamepadManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,22 @@
 //! Input handling for keyboard, mouse, and gamepad.
 
+mod action_map;
+mod gamepad;
+
+pub use action_map::{Action, ActionMap, Binding};
+pub use gamepad::GamepadManager;
+
 use glam::Vec2;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Radial deadzone applied to analog sticks (fraction of full deflection).
+const STICK_DEADZONE: f32 = 0.15;
+
+/// Default input buffer window in seconds (presses this recent still count for jump/fire).
+const DEFAULT_BUFFER_WINDOW: f32 = 0.15;
 
 /// Manages input state for the current frame.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct InputState {
     /// Keys currently held down.
     keys_held: HashSet<KeyCode>,
@@ -30,9 +42,68 @@ pub struct InputState {
     /// Whether the cursor is captured/locked.
     cursor_locked: bool,
 
+    /// Per-axis look sensitivity multiplier applied by `mouse_delta()` (1.0 = raw).
+    sensitivity: Vec2,
+    /// Invert vertical look (mouse and right stick).
+    invert_y: bool,
+
     /// Mouse scroll state
     scroll_up: bool,
     scroll_down: bool,
+
+    /// Gamepad buttons currently held.
+    gamepad_held: HashSet<GamepadButton>,
+    /// Gamepad buttons pressed this frame.
+    gamepad_pressed: HashSet<GamepadButton>,
+    /// Gamepad buttons released this frame.
+    gamepad_released: HashSet<GamepadButton>,
+    /// Raw left stick position (-1..1, +Y = up).
+    left_stick: Vec2,
+    /// Raw right stick position (-1..1, +Y = up).
+    right_stick: Vec2,
+    /// Right-stick look speed in mouse-delta units per second at full deflection.
+    gamepad_look_sensitivity: f32,
+
+    /// Semantic action bindings consulted by `is_action_*` and the convenience helpers.
+    action_map: ActionMap,
+
+    /// Monotonic input clock in seconds, advanced by `begin_frame`.
+    elapsed: f64,
+    /// Press/release timestamps per physical input (buffering, taps, holds, double-taps).
+    press_records: HashMap<Binding, PressRecord>,
+    /// How long a buffered jump/fire press stays valid, in seconds.
+    buffer_window: f32,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            keys_held: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            keys_released: HashSet::new(),
+            mouse_held: HashSet::new(),
+            mouse_pressed: HashSet::new(),
+            mouse_released: HashSet::new(),
+            mouse_position: Vec2::ZERO,
+            mouse_delta: Vec2::ZERO,
+            accumulated_delta: Vec2::ZERO,
+            cursor_locked: false,
+            sensitivity: Vec2::ONE,
+            invert_y: false,
+            scroll_up: false,
+            scroll_down: false,
+            gamepad_held: HashSet::new(),
+            gamepad_pressed: HashSet::new(),
+            gamepad_released: HashSet::new(),
+            left_stick: Vec2::ZERO,
+            right_stick: Vec2::ZERO,
+            gamepad_look_sensitivity: 1200.0,
+            action_map: ActionMap::default(),
+            elapsed: 0.0,
+            press_records: HashMap::new(),
+            buffer_window: DEFAULT_BUFFER_WINDOW,
+        }
+    }
 }
 
 impl InputState {
@@ -40,12 +111,16 @@ impl InputState {
         Self::default()
     }
 
-    /// Clear per-frame state. Call at the start of each frame.
-    pub fn begin_frame(&mut self) {
+    /// Clear per-frame state and advance the input clock by `dt` (unscaled seconds).
+    /// Call once per frame after input has been consumed.
+    pub fn begin_frame(&mut self, dt: f32) {
+        self.elapsed += dt as f64;
         self.keys_pressed.clear();
         self.keys_released.clear();
         self.mouse_pressed.clear();
         self.mouse_released.clear();
+        self.gamepad_pressed.clear();
+        self.gamepad_released.clear();
         self.mouse_delta = self.accumulated_delta;
         self.accumulated_delta = Vec2::ZERO;
         self.scroll_up = false;
@@ -58,12 +133,14 @@ impl InputState {
             ElementState::Pressed => {
                 if !self.keys_held.contains(&key) {
                     self.keys_pressed.insert(key);
+                    self.record_press(Binding::Key(key));
                 }
                 self.keys_held.insert(key);
             }
             ElementState::Released => {
                 self.keys_held.remove(&key);
                 self.keys_released.insert(key);
+                self.record_release(Binding::Key(key));
             }
         }
     }
@@ -74,22 +151,75 @@ impl InputState {
             ElementState::Pressed => {
                 if !self.mouse_held.contains(&button) {
                     self.mouse_pressed.insert(button);
+                    self.record_press(Binding::Mouse(button));
                 }
                 self.mouse_held.insert(button);
             }
             ElementState::Released => {
                 self.mouse_held.remove(&button);
                 self.mouse_released.insert(button);
+                self.record_release(Binding::Mouse(button));
             }
         }
     }
 
+    fn record_press(&mut self, binding: Binding) {
+        let now = self.elapsed;
+        let record = self.press_records.entry(binding).or_default();
+        record.prev_press = record.last_press;
+        record.last_press = Some(now);
+        record.consumed = false;
+    }
+
+    fn record_release(&mut self, binding: Binding) {
+        let now = self.elapsed;
+        self.press_records.entry(binding).or_default().last_release = Some(now);
+    }
+
     /// Process mouse movement.
     pub fn process_mouse_motion(&mut self, delta: (f64, f64)) {
         self.accumulated_delta.x += delta.0 as f32;
         self.accumulated_delta.y += delta.1 as f32;
     }
 
+    /// Process a gamepad button event.
+    pub fn process_gamepad_button(&mut self, button: GamepadButton, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                if !self.gamepad_held.contains(&button) {
+                    self.gamepad_pressed.insert(button);
+                    self.record_press(Binding::Gamepad(button));
+                }
+                self.gamepad_held.insert(button);
+            }
+            ElementState::Released => {
+                self.gamepad_held.remove(&button);
+                self.gamepad_released.insert(button);
+                self.record_release(Binding::Gamepad(button));
+            }
+        }
+    }
+
+    /// Process a gamepad axis change. Values are in -1..1.
+    pub fn process_gamepad_axis(&mut self, axis: GamepadAxis, value: f32) {
+        match axis {
+            GamepadAxis::LeftStickX => self.left_stick.x = value,
+            GamepadAxis::LeftStickY => self.left_stick.y = value,
+            GamepadAxis::RightStickX => self.right_stick.x = value,
+            GamepadAxis::RightStickY => self.right_stick.y = value,
+            _ => {}
+        }
+    }
+
+    /// Drop all held gamepad buttons and centre the sticks (e.g. on controller disconnect).
+    pub fn clear_gamepad(&mut self) {
+        for button in self.gamepad_held.drain() {
+            self.gamepad_released.insert(button);
+        }
+        self.left_stick = Vec2::ZERO;
+        self.right_stick = Vec2::ZERO;
+    }
+
     /// Process cursor position update.
     pub fn process_cursor_position(&mut self, position: (f64, f64)) {
         self.mouse_position = Vec2::new(position.0 as f32, position.1 as f32);
@@ -132,11 +262,264 @@ impl InputState {
         self.mouse_position
     }
 
-    /// Get the mouse movement delta for this frame.
+    /// Get the mouse movement delta for this frame, scaled by sensitivity and with
+    /// invert-Y applied. Use this for look controls.
     pub fn mouse_delta(&self) -> Vec2 {
+        self.apply_look_settings(self.mouse_delta)
+    }
+
+    /// Get the unscaled mouse movement delta for this frame (device units).
+    pub fn raw_mouse_delta(&self) -> Vec2 {
         self.mouse_delta
     }
 
+    /// Set per-axis look sensitivity (1.0 = raw device delta).
+    pub fn set_sensitivity(&mut self, sensitivity: Vec2) {
+        self.sensitivity = sensitivity.max(Vec2::ZERO);
+    }
+
+    /// Get per-axis look sensitivity.
+    pub fn sensitivity(&self) -> Vec2 {
+        self.sensitivity
+    }
+
+    /// Set whether vertical look is inverted.
+    pub fn set_invert_y(&mut self, invert_y: bool) {
+        self.invert_y = invert_y;
+    }
+
+    /// Check if vertical look is inverted.
+    pub fn invert_y(&self) -> bool {
+        self.invert_y
+    }
+
+    fn apply_look_settings(&self, delta: Vec2) -> Vec2 {
+        let mut scaled = delta * self.sensitivity;
+        if self.invert_y {
+            scaled.y = -scaled.y;
+        }
+        scaled
+    }
+
+    /// Check if a gamepad button is held.
+    pub fn is_gamepad_button_held(&self, button: GamepadButton) -> bool {
+        self.gamepad_held.contains(&button)
+    }
+
+    /// Check if a gamepad button was pressed this frame.
+    pub fn is_gamepad_button_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepad_pressed.contains(&button)
+    }
+
+    /// Check if a gamepad button was released this frame.
+    pub fn is_gamepad_button_released(&self, button: GamepadButton) -> bool {
+        self.gamepad_released.contains(&button)
+    }
+
+    /// Left stick with deadzone applied (+Y = forward).
+    pub fn gamepad_left_stick(&self) -> Vec2 {
+        apply_deadzone(self.left_stick)
+    }
+
+    /// Right stick with deadzone applied (+Y = up).
+    pub fn gamepad_right_stick(&self) -> Vec2 {
+        apply_deadzone(self.right_stick)
+    }
+
+    /// Set right-stick look speed (mouse-delta units per second at full deflection).
+    pub fn set_gamepad_look_sensitivity(&mut self, sensitivity: f32) {
+        self.gamepad_look_sensitivity = sensitivity.max(0.0);
+    }
+
+    /// Get right-stick look speed.
+    pub fn gamepad_look_sensitivity(&self) -> f32 {
+        self.gamepad_look_sensitivity
+    }
+
+    /// Look delta for this frame: mouse delta plus right stick, in mouse-delta units, with
+    /// sensitivity and invert-Y applied to both. The stick is rate-based so it needs `dt`; its
+    /// Y is flipped to match mouse (down = +Y).
+    pub fn look_delta(&self, dt: f32) -> Vec2 {
+        let stick = self.gamepad_right_stick();
+        let stick_delta = Vec2::new(stick.x, -stick.y) * self.gamepad_look_sensitivity * dt;
+        self.apply_look_settings(self.mouse_delta + stick_delta)
+    }
+
+    /// Check if any binding of an action is currently held.
+    pub fn is_action_held(&self, action: Action) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.is_binding_held(*b))
+    }
+
+    /// Check if any binding of an action was pressed this frame.
+    pub fn is_action_pressed(&self, action: Action) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.is_binding_pressed(*b))
+    }
+
+    /// Check if any binding of an action was released this frame.
+    pub fn is_action_released(&self, action: Action) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.is_binding_released(*b))
+    }
+
+    fn is_binding_held(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_held(key),
+            Binding::Mouse(button) => self.is_mouse_held(button),
+            Binding::Gamepad(button) => self.is_gamepad_button_held(button),
+        }
+    }
+
+    fn is_binding_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_pressed(key),
+            Binding::Mouse(button) => self.is_mouse_pressed(button),
+            Binding::Gamepad(button) => self.is_gamepad_button_pressed(button),
+        }
+    }
+
+    fn is_binding_released(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_released(key),
+            Binding::Mouse(button) => self.is_mouse_released(button),
+            Binding::Gamepad(button) => self.is_gamepad_button_released(button),
+        }
+    }
+
+    /// Check if a key was pressed within the last `seconds` of input time.
+    pub fn was_key_pressed_within(&self, key: KeyCode, seconds: f32) -> bool {
+        self.binding_pressed_within(Binding::Key(key), seconds)
+    }
+
+    /// Check if a mouse button was pressed within the last `seconds` of input time.
+    pub fn was_mouse_pressed_within(&self, button: MouseButton, seconds: f32) -> bool {
+        self.binding_pressed_within(Binding::Mouse(button), seconds)
+    }
+
+    /// Check if any binding of an action was pressed within the last `seconds` of input time.
+    pub fn was_action_pressed_within(&self, action: Action, seconds: f32) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.binding_pressed_within(*b, seconds))
+    }
+
+    /// Forget buffered presses for an action so one press can't trigger it twice.
+    pub fn consume_action_buffer(&mut self, action: Action) {
+        for b in self.action_map.bindings(action) {
+            if let Some(record) = self.press_records.get_mut(b) {
+                record.consumed = true;
+            }
+        }
+    }
+
+    /// Check if a key was pressed this frame with its previous press less than `window` seconds ago.
+    pub fn is_key_double_tapped(&self, key: KeyCode, window: f32) -> bool {
+        self.binding_double_tapped(Binding::Key(key), window)
+    }
+
+    /// Check if a key was released this frame after being held for at most `max_hold` seconds.
+    pub fn is_key_tapped(&self, key: KeyCode, max_hold: f32) -> bool {
+        self.binding_tapped(Binding::Key(key), max_hold)
+    }
+
+    /// Check if a key is held and has been for at least `seconds`.
+    pub fn is_key_held_longer_than(&self, key: KeyCode, seconds: f32) -> bool {
+        self.binding_held_longer_than(Binding::Key(key), seconds)
+    }
+
+    /// Action-level `is_key_double_tapped`: true if any binding was double-tapped.
+    pub fn is_action_double_tapped(&self, action: Action, window: f32) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.binding_double_tapped(*b, window))
+    }
+
+    /// Action-level `is_key_tapped`: true if any binding was released after a short press.
+    pub fn is_action_tapped(&self, action: Action, max_hold: f32) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.binding_tapped(*b, max_hold))
+    }
+
+    /// Action-level `is_key_held_longer_than`: true if any binding has been held for `seconds`.
+    pub fn is_action_held_longer_than(&self, action: Action, seconds: f32) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|b| self.binding_held_longer_than(*b, seconds))
+    }
+
+    fn binding_pressed_within(&self, binding: Binding, seconds: f32) -> bool {
+        self.press_records.get(&binding).is_some_and(|r| {
+            !r.consumed && r.last_press.is_some_and(|t| self.elapsed - t <= seconds as f64)
+        })
+    }
+
+    fn binding_double_tapped(&self, binding: Binding, window: f32) -> bool {
+        self.is_binding_pressed(binding)
+            && self.press_records.get(&binding).is_some_and(|r| match (r.prev_press, r.last_press) {
+                (Some(prev), Some(last)) => last - prev <= window as f64,
+                _ => false,
+            })
+    }
+
+    fn binding_tapped(&self, binding: Binding, max_hold: f32) -> bool {
+        self.is_binding_released(binding)
+            && self.press_records.get(&binding).is_some_and(|r| match (r.last_press, r.last_release) {
+                (Some(press), Some(release)) => release - press <= max_hold as f64,
+                _ => false,
+            })
+    }
+
+    fn binding_held_longer_than(&self, binding: Binding, seconds: f32) -> bool {
+        self.is_binding_held(binding)
+            && self
+                .press_records
+                .get(&binding)
+                .and_then(|r| r.last_press)
+                .is_some_and(|t| self.elapsed - t >= seconds as f64)
+    }
+
+    /// Set the jump/fire input buffer window in seconds.
+    pub fn set_buffer_window(&mut self, seconds: f32) {
+        self.buffer_window = seconds.max(0.0);
+    }
+
+    /// Get the jump/fire input buffer window in seconds.
+    pub fn buffer_window(&self) -> f32 {
+        self.buffer_window
+    }
+
+    /// Current action bindings.
+    pub fn action_map(&self) -> &ActionMap {
+        &self.action_map
+    }
+
+    /// Mutable action bindings (e.g. for a rebinding menu).
+    pub fn action_map_mut(&mut self) -> &mut ActionMap {
+        &mut self.action_map
+    }
+
+    /// Replace all action bindings (e.g. after loading a saved layout).
+    pub fn set_action_map(&mut self, action_map: ActionMap) {
+        self.action_map = action_map;
+    }
+
+    /// Rebind an action to a new input on the same device family.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        self.action_map.rebind(action, binding);
+    }
+
     /// Check if the cursor is locked.
     pub fn is_cursor_locked(&self) -> bool {
         self.cursor_locked
@@ -147,20 +530,22 @@ impl InputState {
         self.cursor_locked = locked;
     }
 
-    /// Get movement input as a normalized vector (WASD).
+    /// Get movement input (move actions, WASD by default, blended with the left stick),
+    /// clamped to unit length. Digital input is always full magnitude; the stick keeps its
+    /// analog magnitude.
     pub fn get_movement_input(&self) -> Vec2 {
         let mut movement = Vec2::ZERO;
 
-        if self.is_key_held(KeyCode::KeyW) {
+        if self.is_action_held(Action::MoveForward) {
             movement.y += 1.0;
         }
-        if self.is_key_held(KeyCode::KeyS) {
+        if self.is_action_held(Action::MoveBack) {
             movement.y -= 1.0;
         }
-        if self.is_key_held(KeyCode::KeyA) {
+        if self.is_action_held(Action::MoveLeft) {
             movement.x -= 1.0;
         }
-        if self.is_key_held(KeyCode::KeyD) {
+        if self.is_action_held(Action::MoveRight) {
             movement.x += 1.0;
         }
 
@@ -168,52 +553,73 @@ impl InputState {
             movement = movement.normalize();
         }
 
-        movement
+        (movement + self.gamepad_left_stick()).clamp_length_max(1.0)
     }
 
-    /// Check if sprint is held (Shift).
+    /// Check if sprint is held (Shift by default).
     pub fn is_sprinting(&self) -> bool {
-        self.is_key_held(KeyCode::ShiftLeft) || self.is_key_held(KeyCode::ShiftRight)
+        self.is_action_held(Action::Sprint)
     }
 
-    /// Check if jump was pressed (Space).
+    /// Check if jump was pressed (Space by default).
     pub fn is_jump_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::Space)
+        self.is_action_pressed(Action::Jump)
     }
 
-    /// Check if fire is held (Left mouse button).
+    /// Check if jump was pressed within the buffer window. Call `consume_jump_buffer` once
+    /// the jump actually happens.
+    pub fn is_jump_buffered(&self) -> bool {
+        self.was_action_pressed_within(Action::Jump, self.buffer_window)
+    }
+
+    /// Consume a buffered jump press.
+    pub fn consume_jump_buffer(&mut self) {
+        self.consume_action_buffer(Action::Jump);
+    }
+
+    /// Check if fire was pressed within the buffer window (e.g. a click during fire cooldown).
+    pub fn is_fire_buffered(&self) -> bool {
+        self.was_action_pressed_within(Action::Fire, self.buffer_window)
+    }
+
+    /// Consume a buffered fire press.
+    pub fn consume_fire_buffer(&mut self) {
+        self.consume_action_buffer(Action::Fire);
+    }
+
+    /// Check if fire is held (Left mouse button by default).
     pub fn is_fire_held(&self) -> bool {
-        self.is_mouse_held(MouseButton::Left)
+        self.is_action_held(Action::Fire)
     }
 
-    /// Check if fire was pressed this frame (Left mouse button — one-shot per click).
+    /// Check if fire was pressed this frame (Left mouse button by default — one-shot per click).
     pub fn is_fire_pressed(&self) -> bool {
-        self.is_mouse_pressed(MouseButton::Left)
+        self.is_action_pressed(Action::Fire)
     }
 
-    /// Check if aim is held (Right mouse button).
+    /// Check if aim is held (Right mouse button by default).
     pub fn is_aim_held(&self) -> bool {
-        self.is_mouse_held(MouseButton::Right)
+        self.is_action_held(Action::Aim)
     }
 
-    /// Check if reload was pressed (R).
+    /// Check if reload was pressed (R by default).
     pub fn is_reload_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::KeyR)
+        self.is_action_pressed(Action::Reload)
     }
 
-    /// Check if aiming (right mouse).
+    /// Check if aiming (Right mouse button by default).
     pub fn is_aiming(&self) -> bool {
-        self.is_mouse_held(MouseButton::Right)
+        self.is_aim_held()
     }
 
-    /// Check if crouching (Ctrl).
+    /// Check if crouching (Ctrl by default).
     pub fn is_crouching(&self) -> bool {
-        self.is_key_held(KeyCode::ControlLeft) || self.is_key_held(KeyCode::ControlRight)
+        self.is_action_held(Action::Crouch)
     }
 
-    /// Check if ability key was pressed (Q).
+    /// Check if ability key was pressed (Q by default).
     pub fn is_ability_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::KeyQ)
+        self.is_action_pressed(Action::Ability)
     }
 
     /// Check if a specific key was just pressed this frame.
@@ -241,22 +647,121 @@ impl InputState {
         self.scroll_down
     }
 
-    /// Check if interact was pressed (E).
+    /// Check if interact was pressed (E by default).
     pub fn is_interact_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::KeyE)
+        self.is_action_pressed(Action::Interact)
     }
 
-    /// Check if melee was pressed (V).
+    /// Check if melee was pressed (V by default).
     pub fn is_melee_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::KeyV)
+        self.is_action_pressed(Action::Melee)
     }
 
-    /// Check if grenade was pressed (G).
+    /// Check if grenade was pressed (G by default).
     pub fn is_grenade_pressed(&self) -> bool {
-        self.is_key_pressed(KeyCode::KeyG)
+        self.is_action_pressed(Action::Grenade)
     }
 }
 
+/// Press/release timestamps for one physical input, on the `InputState` clock.
+#[derive(Debug, Clone, Copy, Default)]
+struct PressRecord {
+    /// Most recent press.
+    last_press: Option<f64>,
+    /// The press before `last_press` (double-tap detection).
+    prev_press: Option<f64>,
+    /// Most recent release.
+    last_release: Option<f64>,
+    /// Set once a buffered press has been acted on, so it can't trigger twice.
+    consumed: bool,
+}
+
+/// Rescale a stick so the deadzone maps to zero and full deflection stays at 1.
+fn apply_deadzone(stick: Vec2) -> Vec2 {
+    let len = stick.length();
+    if len <= STICK_DEADZONE {
+        return Vec2::ZERO;
+    }
+    let scaled = ((len - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+    stick / len * scaled
+}
+
 // Re-export for convenience
+pub use gilrs::{Axis as GamepadAxis, Button as GamepadButton};
 pub use winit::event::{ElementState, MouseButton};
 pub use winit::keyboard::KeyCode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump_press_is_buffered_until_window_expires() {
+        let mut input = InputState::new();
+        input.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        input.begin_frame(0.05);
+        input.process_keyboard(KeyCode::Space, ElementState::Released);
+        input.begin_frame(0.05);
+        assert!(!input.is_jump_pressed());
+        assert!(input.is_jump_buffered());
+        input.begin_frame(0.1);
+        assert!(!input.is_jump_buffered());
+    }
+
+    #[test]
+    fn consumed_jump_buffer_does_not_retrigger() {
+        let mut input = InputState::new();
+        input.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        assert!(input.is_jump_buffered());
+        input.consume_jump_buffer();
+        assert!(!input.is_jump_buffered());
+    }
+
+    #[test]
+    fn mouse_delta_applies_sensitivity_and_invert_y() {
+        let mut input = InputState::new();
+        input.set_cursor_locked(true);
+        input.set_sensitivity(Vec2::new(2.0, 0.5));
+        input.set_invert_y(true);
+        input.process_mouse_motion((3.0, 4.0));
+        input.process_mouse_motion((1.0, 0.0));
+        input.begin_frame(0.016);
+        assert_eq!(input.raw_mouse_delta(), Vec2::new(4.0, 4.0));
+        assert_eq!(input.mouse_delta(), Vec2::new(8.0, -2.0));
+    }
+
+    fn tap(input: &mut InputState, key: KeyCode, hold: f32) {
+        input.process_keyboard(key, ElementState::Pressed);
+        input.begin_frame(hold);
+        input.process_keyboard(key, ElementState::Released);
+    }
+
+    #[test]
+    fn tap_and_hold_are_distinguished() {
+        let mut input = InputState::new();
+        tap(&mut input, KeyCode::ControlLeft, 0.1);
+        assert!(input.is_key_tapped(KeyCode::ControlLeft, 0.3));
+        input.begin_frame(0.016);
+
+        input.process_keyboard(KeyCode::ControlLeft, ElementState::Pressed);
+        input.begin_frame(0.5);
+        assert!(input.is_key_held_longer_than(KeyCode::ControlLeft, 0.3));
+        input.process_keyboard(KeyCode::ControlLeft, ElementState::Released);
+        assert!(!input.is_key_tapped(KeyCode::ControlLeft, 0.3));
+    }
+
+    #[test]
+    fn double_tap_requires_presses_within_window() {
+        let mut input = InputState::new();
+        tap(&mut input, KeyCode::KeyW, 0.05);
+        input.begin_frame(0.1);
+        input.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        assert!(input.is_key_double_tapped(KeyCode::KeyW, 0.25));
+
+        input.begin_frame(0.05);
+        tap(&mut input, KeyCode::KeyW, 0.05);
+        input.begin_frame(0.5);
+        input.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        assert!(!input.is_key_double_tapped(KeyCode::KeyW, 0.25));
+    }
+}
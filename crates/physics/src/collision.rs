@@ -1,5 +1,8 @@
 //! Collision groups and filtering.
 
+use crate::PhysicsWorld;
+use engine_core::Vec3;
+use rapier3d::control::{CharacterAutostep, CharacterLength};
 use rapier3d::prelude::*;
 
 /// Collision groups for different entity types.
@@ -20,6 +23,10 @@ pub enum CollisionGroup {
     Debris = 1 << 5,
     /// Triggers and sensors
     Trigger = 1 << 6,
+    /// Terrain chunks (also members of Environment)
+    Terrain = 1 << 7,
+    /// Destructible props: rocks, landmarks, wrecks (also members of Environment)
+    Destructible = 1 << 8,
 }
 
 impl CollisionGroup {
@@ -35,15 +42,51 @@ impl CollisionGroup {
         (membership, filter)
     }
 
+    /// Create a collision group for terrain. Also an Environment member so existing filters still hit it.
+    pub fn terrain() -> (Group, Group) {
+        let membership = Group::from_bits_retain(Self::Environment as u32 | Self::Terrain as u32);
+        (membership, Group::ALL)
+    }
+
+    /// Create a collision group for destructible props. Also an Environment member.
+    pub fn destructible() -> (Group, Group) {
+        let membership = Group::from_bits_retain(Self::Environment as u32 | Self::Destructible as u32);
+        (membership, Group::ALL)
+    }
+
+    /// Query groups for a ray or shape cast that only hits colliders in `targets`.
+    pub fn query(targets: &[CollisionGroup]) -> InteractionGroups {
+        let filter = targets.iter().fold(0u32, |bits, g| bits | *g as u32);
+        InteractionGroups::new(Group::ALL, Group::from_bits_retain(filter))
+    }
+
     /// Create a collision group for player.
     pub fn player() -> (Group, Group) {
         let membership = Group::from_bits_retain(Self::Player as u32);
         let filter = Group::from_bits_retain(
-            Self::Environment as u32 | Self::Enemy as u32 | Self::EnemyProjectile as u32,
+            Self::Environment as u32
+                | Self::Enemy as u32
+                | Self::EnemyProjectile as u32
+                | Self::Trigger as u32,
         );
         (membership, filter)
     }
 
+    /// Create a collision group for the player's presence sensor. Only trigger volumes are
+    /// reported, not the terrain, props and bugs the player body collides with.
+    pub fn player_presence() -> (Group, Group) {
+        let membership = Group::from_bits_retain(Self::Player as u32);
+        let filter = Group::from_bits_retain(Self::Trigger as u32);
+        (membership, filter)
+    }
+
+    /// Create a collision group for trigger volumes. Only the player sets them off.
+    pub fn trigger() -> (Group, Group) {
+        let membership = Group::from_bits_retain(Self::Trigger as u32);
+        let filter = Group::from_bits_retain(Self::Player as u32);
+        (membership, filter)
+    }
+
     /// Create a collision group for enemies.
     pub fn enemy() -> (Group, Group) {
         let membership = Group::from_bits_retain(Self::Enemy as u32);
@@ -93,3 +136,93 @@ impl PhysicsBody {
         }
     }
 }
+
+/// Result of one `KinematicCharacterController::move_shape` call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterMovement {
+    /// Translation to apply after collisions, slope limits and step-ups.
+    pub translation: Vec3,
+    /// Standing on a walkable surface after the move.
+    pub grounded: bool,
+    /// Sliding down ground steeper than `max_slope_angle`.
+    pub sliding_down_slope: bool,
+}
+
+/// Collide-and-slide movement for kinematic characters (player, squadmates, citizens).
+/// Wraps Rapier's character controller with the settings the game tunes per character.
+#[derive(Debug, Clone, Copy)]
+pub struct KinematicCharacterController {
+    /// Steepest ground (radians) the character can walk up; steeper slopes block and slide.
+    pub max_slope_angle: f32,
+    /// Tallest ledge the character steps up onto automatically. 0 disables stepping.
+    pub step_height: f32,
+    /// How far the character is pulled down to stay on the ground over bumps. 0 disables.
+    pub snap_distance: f32,
+    /// Gap kept between the character and obstacles.
+    pub skin: f32,
+    /// Colliders that block the character (see `CollisionGroup::query`).
+    pub groups: InteractionGroups,
+}
+
+impl Default for KinematicCharacterController {
+    fn default() -> Self {
+        Self {
+            max_slope_angle: 50f32.to_radians(),
+            step_height: 0.4,
+            snap_distance: 0.2,
+            skin: 0.02,
+            groups: CollisionGroup::query(&[CollisionGroup::Environment]),
+        }
+    }
+}
+
+impl KinematicCharacterController {
+    /// Controller with the given slope limit (radians) and step height (m); other settings default.
+    pub fn new(max_slope_angle: f32, step_height: f32) -> Self {
+        Self {
+            max_slope_angle,
+            step_height,
+            ..Default::default()
+        }
+    }
+
+    /// Move `shape`, centred at `position`, by `desired` and return the translation that
+    /// is actually possible this frame.
+    pub fn move_shape(
+        &self,
+        physics: &PhysicsWorld,
+        shape: &dyn Shape,
+        position: Vec3,
+        desired: Vec3,
+        dt: f32,
+    ) -> CharacterMovement {
+        let controller = rapier3d::control::KinematicCharacterController {
+            offset: CharacterLength::Absolute(self.skin),
+            autostep: (self.step_height > 0.0).then_some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(self.step_height),
+                min_width: CharacterLength::Absolute(0.2),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: self.max_slope_angle,
+            min_slope_slide_angle: self.max_slope_angle,
+            snap_to_ground: (self.snap_distance > 0.0).then_some(CharacterLength::Absolute(self.snap_distance)),
+            ..Default::default()
+        };
+        let movement = controller.move_shape(
+            dt,
+            &physics.rigid_body_set,
+            &physics.collider_set,
+            &physics.query_pipeline,
+            shape,
+            &Isometry::translation(position.x, position.y, position.z),
+            vector![desired.x, desired.y, desired.z],
+            QueryFilter::default().exclude_sensors().groups(self.groups),
+            |_| {},
+        );
+        CharacterMovement {
+            translation: Vec3::new(movement.translation.x, movement.translation.y, movement.translation.z),
+            grounded: movement.grounded,
+            sliding_down_slope: movement.is_sliding_down_slope,
+        }
+    }
+}
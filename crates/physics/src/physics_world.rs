@@ -1,9 +1,10 @@
 //! Physics world management with Rapier3D.
 
 use crate::collision::CollisionGroup;
-use engine_core::{Transform, Vec3};
+use engine_core::{Entity, Transform, Vec3};
 use rapier3d::na::{Isometry3, Quaternion, UnitQuaternion, Vector3};
 use rapier3d::prelude::*;
+use std::sync::Mutex;
 
 /// Environment collision groups so static geometry (terrain, roads, buildings) collides with player/enemies.
 fn env_collision_groups() -> InteractionGroups {
@@ -11,12 +12,95 @@ fn env_collision_groups() -> InteractionGroups {
     InteractionGroups::new(membership, filter)
 }
 
+/// Terrain collision groups: environment plus a Terrain tag so queries can single out chunks.
+fn terrain_collision_groups() -> InteractionGroups {
+    let (membership, filter) = CollisionGroup::terrain();
+    InteractionGroups::new(membership, filter)
+}
+
+/// Destructible prop collision groups: environment plus a Destructible tag.
+fn destructible_collision_groups() -> InteractionGroups {
+    let (membership, filter) = CollisionGroup::destructible();
+    InteractionGroups::new(membership, filter)
+}
+
+/// Enemy collision groups (bugs, Skinnies).
+fn enemy_collision_groups() -> InteractionGroups {
+    let (membership, filter) = CollisionGroup::enemy();
+    InteractionGroups::new(membership, filter)
+}
+
 /// Debris collision groups (shell casings, small props) — collide with terrain and other debris.
 fn debris_collision_groups() -> InteractionGroups {
     let (membership, filter) = CollisionGroup::debris();
     InteractionGroups::new(membership, filter)
 }
 
+/// Trigger volume collision groups.
+fn trigger_collision_groups() -> InteractionGroups {
+    let (membership, filter) = CollisionGroup::trigger();
+    InteractionGroups::new(membership, filter)
+}
+
+/// Two colliders, at least one of them a sensor, that started or stopped overlapping during a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntersectionEvent {
+    pub collider1: ColliderHandle,
+    pub collider2: ColliderHandle,
+    /// True when the overlap began, false when it ended.
+    pub started: bool,
+}
+
+impl IntersectionEvent {
+    /// The other collider of the pair, if `handle` is one of the two.
+    pub fn other(&self, handle: ColliderHandle) -> Option<ColliderHandle> {
+        if self.collider1 == handle {
+            Some(self.collider2)
+        } else if self.collider2 == handle {
+            Some(self.collider1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collects sensor intersection events raised while the pipeline steps.
+#[derive(Default)]
+struct IntersectionEventBuffer {
+    events: Mutex<Vec<IntersectionEvent>>,
+}
+
+impl EventHandler for IntersectionEventBuffer {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: CollisionEvent,
+        _contact_pair: Option<&ContactPair>,
+    ) {
+        if !event.sensor() {
+            return;
+        }
+        if let Ok(mut events) = self.events.lock() {
+            events.push(IntersectionEvent {
+                collider1: event.collider1(),
+                collider2: event.collider2(),
+                started: event.started(),
+            });
+        }
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: Real,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &ContactPair,
+        _total_force_magnitude: Real,
+    ) {
+    }
+}
+
 /// Main physics world containing all simulation state.
 pub struct PhysicsWorld {
     pub rigid_body_set: RigidBodySet,
@@ -31,6 +115,7 @@ pub struct PhysicsWorld {
     pub multibody_joint_set: MultibodyJointSet,
     pub ccd_solver: CCDSolver,
     pub query_pipeline: QueryPipeline,
+    intersection_events: IntersectionEventBuffer,
 }
 
 impl Default for PhysicsWorld {
@@ -55,6 +140,7 @@ impl PhysicsWorld {
             multibody_joint_set: MultibodyJointSet::new(),
             ccd_solver: CCDSolver::new(),
             query_pipeline: QueryPipeline::new(),
+            intersection_events: IntersectionEventBuffer::default(),
         }
     }
 
@@ -73,15 +159,55 @@ impl PhysicsWorld {
             &mut self.ccd_solver,
             Some(&mut self.query_pipeline),
             &(),
-            &(),
+            &self.intersection_events,
         );
     }
 
+    /// Sensor overlaps that started or stopped since the last call, in step order.
+    pub fn drain_intersection_events(&mut self) -> Vec<IntersectionEvent> {
+        match self.intersection_events.events.get_mut() {
+            Ok(events) => std::mem::take(events),
+            Err(_) => Vec::new(),
+        }
+    }
+
     /// Update query pipeline for raycasting.
     pub fn update_query_pipeline(&mut self) {
         self.query_pipeline.update(&self.collider_set);
     }
 
+    /// Add a trigger volume at `position`. It blocks nothing and reports the player entering
+    /// and leaving it through `drain_intersection_events`.
+    pub fn add_sensor(&mut self, position: Vec3, shape: SharedShape) -> ColliderHandle {
+        self.add_sensor_with_groups(position, shape, trigger_collision_groups())
+    }
+
+    /// Add a sensor with explicit collision groups, e.g. the player's own presence volume
+    /// that trigger volumes detect.
+    pub fn add_sensor_with_groups(
+        &mut self,
+        position: Vec3,
+        shape: SharedShape,
+        groups: InteractionGroups,
+    ) -> ColliderHandle {
+        let collider = ColliderBuilder::new(shape)
+            .translation(vector![position.x, position.y, position.z])
+            .sensor(true)
+            .collision_groups(groups)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            // Sensors have no body, so allow fixed-fixed pairs or they would never overlap
+            .active_collision_types(ActiveCollisionTypes::all())
+            .build();
+        self.collider_set.insert(collider)
+    }
+
+    /// Move a sensor added with `add_sensor`. Overlap changes are reported on the next step.
+    pub fn set_sensor_position(&mut self, handle: ColliderHandle, position: Vec3) {
+        if let Some(collider) = self.collider_set.get_mut(handle) {
+            collider.set_translation(vector![position.x, position.y, position.z]);
+        }
+    }
+
     /// Add a dynamic rigid body and return its handle.
     pub fn add_dynamic_body(&mut self, position: Vec3) -> RigidBodyHandle {
         let rigid_body = RigidBodyBuilder::dynamic()
@@ -136,7 +262,7 @@ impl PhysicsWorld {
         self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set)
     }
 
-    /// Add a box collider to a rigid body with environment collision groups (rocks, destructibles).
+    /// Add a box collider to a rigid body with destructible collision groups (rocks, landmarks, wrecks).
     pub fn add_static_env_box_collider(
         &mut self,
         body_handle: RigidBodyHandle,
@@ -147,7 +273,7 @@ impl PhysicsWorld {
             half_extents.y as Real,
             half_extents.z as Real,
         )
-        .collision_groups(env_collision_groups())
+        .collision_groups(destructible_collision_groups())
         .build();
         self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set)
     }
@@ -173,6 +299,19 @@ impl PhysicsWorld {
         self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set)
     }
 
+    /// Add a capsule collider with enemy collision groups (bugs, Skinnies).
+    pub fn add_enemy_capsule_collider(
+        &mut self,
+        body_handle: RigidBodyHandle,
+        half_height: f32,
+        radius: f32,
+    ) -> ColliderHandle {
+        let collider = ColliderBuilder::capsule_y(half_height, radius)
+            .collision_groups(enemy_collision_groups())
+            .build();
+        self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set)
+    }
+
     /// Add a small sphere collider with debris collision group (shell casings, etc.).
     pub fn add_debris_sphere_collider(
         &mut self,
@@ -227,6 +366,33 @@ impl PhysicsWorld {
         (body_handle, collider_handle)
     }
 
+    /// Add a dynamic ball for a thrown grenade: bounces off terrain, props and bugs, rolls to a stop.
+    /// CCD keeps a fast throw from tunnelling through thin walls. Returns (body_handle, collider_handle).
+    pub fn add_grenade_body(
+        &mut self,
+        position: Vec3,
+        lin_vel: Vec3,
+        radius: f32,
+    ) -> (RigidBodyHandle, ColliderHandle) {
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .translation(vector![position.x, position.y, position.z])
+            .linvel(vector![lin_vel.x, lin_vel.y, lin_vel.z])
+            .angular_damping(1.5)
+            .ccd_enabled(true)
+            .build();
+        let body_handle = self.rigid_body_set.insert(rigid_body);
+        let (membership, filter) = CollisionGroup::player_projectile();
+        let collider = ColliderBuilder::ball(radius)
+            .collision_groups(InteractionGroups::new(membership, filter))
+            .restitution(0.45)
+            .friction(0.7)
+            .density(2.5)
+            .build();
+        let collider_handle =
+            self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
+        (body_handle, collider_handle)
+    }
+
     /// Add a ground plane collider (flat Y=0 half-space).
     pub fn add_ground_plane(&mut self) -> ColliderHandle {
         let collider = ColliderBuilder::halfspace(Vector::y_axis())
@@ -286,7 +452,7 @@ impl PhysicsWorld {
         let scale = vector![size_x, 1.0, size_z];
 
         let collider = ColliderBuilder::heightfield(heights_matrix, scale)
-            .collision_groups(env_collision_groups())
+            .collision_groups(terrain_collision_groups())
             .build();
         self.collider_set.insert(collider)
     }
@@ -328,11 +494,33 @@ impl PhysicsWorld {
 
         let collider = ColliderBuilder::heightfield(heights_matrix, scale)
             .translation(vector![offset_x, 0.0, offset_z])
-            .collision_groups(env_collision_groups())
+            .collision_groups(terrain_collision_groups())
             .build();
         self.collider_set.insert(collider)
     }
 
+    /// Record which ECS entity owns a collider, so hits can be resolved without scanning the world.
+    pub fn set_collider_entity(&mut self, handle: ColliderHandle, entity: Entity) {
+        if let Some(collider) = self.collider_set.get_mut(handle) {
+            collider.user_data = entity.to_bits().get() as u128;
+        }
+    }
+
+    /// Entity set with `set_collider_entity`, if any. The entity may since have been despawned.
+    pub fn collider_entity(&self, handle: ColliderHandle) -> Option<Entity> {
+        let bits = self.collider_set.get(handle)?.user_data;
+        Entity::from_bits(bits as u64)
+    }
+
+    /// Whether a collider is a member of `group` (e.g. `CollisionGroup::Terrain`).
+    pub fn collider_in_group(&self, handle: ColliderHandle, group: CollisionGroup) -> bool {
+        self.collider_set.get(handle).is_some_and(|c| {
+            c.collision_groups()
+                .memberships
+                .intersects(Group::from_bits_retain(group as u32))
+        })
+    }
+
     /// Get the transform of a rigid body.
     pub fn get_body_transform(&self, handle: RigidBodyHandle) -> Option<Transform> {
         self.rigid_body_set.get(handle).map(|body| {
@@ -363,6 +551,34 @@ impl PhysicsWorld {
         }
     }
 
+    /// Switch a body (e.g. a bug's kinematic capsule) to dynamic so gravity and impulses move it.
+    /// Mass properties are refreshed from its colliders so an impulse applied straight away uses the right mass.
+    /// Returns false if the handle is gone.
+    pub fn convert_to_dynamic(&mut self, handle: RigidBodyHandle) -> bool {
+        let Some(body) = self.rigid_body_set.get_mut(handle) else {
+            return false;
+        };
+        body.set_body_type(RigidBodyType::Dynamic, true);
+        body.recompute_mass_properties_from_colliders(&self.collider_set);
+        true
+    }
+
+    /// Give a dynamic body a velocity change (impulse scaled by its mass), so light and heavy
+    /// bodies are launched at the same speed.
+    pub fn apply_velocity_impulse(&mut self, handle: RigidBodyHandle, delta_velocity: Vec3) {
+        if let Some(body) = self.rigid_body_set.get_mut(handle) {
+            let impulse = delta_velocity * body.mass();
+            body.apply_impulse(vector![impulse.x, impulse.y, impulse.z], true);
+        }
+    }
+
+    /// Apply an angular impulse (tumble) to a dynamic body.
+    pub fn apply_torque_impulse(&mut self, handle: RigidBodyHandle, torque_impulse: Vec3) {
+        if let Some(body) = self.rigid_body_set.get_mut(handle) {
+            body.apply_torque_impulse(vector![torque_impulse.x, torque_impulse.y, torque_impulse.z], true);
+        }
+    }
+
     /// Apply an impulse to a dynamic body.
     pub fn apply_impulse(&mut self, handle: RigidBodyHandle, impulse: Vec3) {
         if let Some(body) = self.rigid_body_set.get_mut(handle) {
@@ -382,3 +598,179 @@ impl PhysicsWorld {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KinematicCharacterController;
+
+    #[test]
+    fn player_presence_enters_and_leaves_trigger() {
+        let mut physics = PhysicsWorld::new();
+        let trigger = physics.add_sensor(Vec3::ZERO, SharedShape::ball(2.0));
+        // Ground the sensor overlaps the whole time: must never be reported
+        physics.add_static_cuboid(Vec3::new(0.0, -0.5, 0.0), 0.0, Vec3::new(20.0, 0.6, 20.0));
+        let (membership, filter) = CollisionGroup::player_presence();
+        let player = physics.add_sensor_with_groups(
+            Vec3::new(10.0, 0.0, 0.0),
+            SharedShape::ball(0.5),
+            InteractionGroups::new(membership, filter),
+        );
+        physics.step();
+        assert!(physics.drain_intersection_events().is_empty());
+
+        physics.set_sensor_position(player, Vec3::new(1.0, 0.0, 0.0));
+        physics.step();
+        let events = physics.drain_intersection_events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].started);
+        assert_eq!(events[0].other(player), Some(trigger));
+
+        physics.set_sensor_position(player, Vec3::new(10.0, 0.0, 0.0));
+        physics.step();
+        let events = physics.drain_intersection_events();
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].started);
+    }
+
+    #[test]
+    fn converted_kinematic_body_is_launched_by_impulse() {
+        let mut physics = PhysicsWorld::new();
+        let body = physics.add_kinematic_body(Vec3::new(0.0, 5.0, 0.0));
+        physics.add_enemy_capsule_collider(body, 0.5, 0.4);
+        physics.step();
+
+        assert!(physics.convert_to_dynamic(body));
+        physics.apply_velocity_impulse(body, Vec3::new(6.0, 0.0, 0.0));
+        let launched = physics.get_body_linvel(body).unwrap();
+        assert!((launched.x - 6.0).abs() < 0.01, "impulse lost on conversion: {launched:?}");
+
+        for _ in 0..10 {
+            physics.step();
+        }
+        let pos = physics.get_body_transform(body).unwrap().position;
+        assert!(pos.x > 0.5, "body did not fly back: {pos:?}");
+        assert!(pos.y < 5.0, "body ignores gravity: {pos:?}");
+    }
+
+    /// Wall whose near face is the plane x = 4.5, from z = -5 to 5.
+    fn wall(physics: &mut PhysicsWorld) {
+        physics.add_static_cuboid(Vec3::new(5.0, 1.0, 0.0), 0.0, Vec3::new(0.5, 2.0, 5.0));
+        physics.update_query_pipeline();
+    }
+
+    #[test]
+    fn shape_cast_and_slide_stop_at_a_blocking_wall() {
+        let mut physics = PhysicsWorld::new();
+        wall(&mut physics);
+        let ball = SharedShape::ball(0.5);
+        let groups = CollisionGroup::query(&[CollisionGroup::Environment]);
+
+        let hit = physics.cast_shape(&*ball, Vec3::new(0.0, 1.0, 0.0), Vec3::X * 10.0, 1.0, groups).expect("wall hit");
+        assert!((hit.toi - 0.4).abs() < 1e-3, "ball should touch the wall 4 m out: {}", hit.toi);
+        assert!(hit.normal.dot(Vec3::NEG_X) > 0.99, "normal faces the ball: {:?}", hit.normal);
+        assert!(physics.cast_shape(&*ball, Vec3::new(0.0, 1.0, 0.0), Vec3::NEG_X * 10.0, 1.0, groups).is_none());
+
+        let moved = physics.move_and_slide(&*ball, Vec3::new(0.0, 1.0, 0.0), Vec3::X * 10.0, groups);
+        assert_eq!(moved.hits.len(), 1);
+        assert!(moved.translation.x < 4.0 && moved.translation.x > 3.9, "stopped short of the wall: {:?}", moved.translation);
+        assert!(moved.translation.y.abs() < 1e-3 && moved.translation.z.abs() < 1e-3);
+    }
+
+    #[test]
+    fn move_and_slide_runs_along_a_wall_hit_at_an_angle() {
+        let mut physics = PhysicsWorld::new();
+        wall(&mut physics);
+        let ball = SharedShape::ball(0.5);
+        let groups = CollisionGroup::query(&[CollisionGroup::Environment]);
+
+        let moved = physics.move_and_slide(&*ball, Vec3::new(0.0, 1.0, 0.0), Vec3::new(10.0, 0.0, 4.0), groups);
+        assert!(!moved.hits.is_empty());
+        assert!(moved.translation.x < 4.0, "went into the wall: {:?}", moved.translation);
+        // The blocked part is dropped; the motion along the wall is kept in full
+        assert!((moved.translation.z - 4.0).abs() < 0.05, "lost the slide: {:?}", moved.translation);
+    }
+
+    /// Walk a 1.8 m capsule standing at the origin along +X for two seconds, falling like the
+    /// player does while airborne; returns where it ends up (center of the capsule).
+    fn walk(physics: &PhysicsWorld, controller: &KinematicCharacterController) -> Vec3 {
+        let body = SharedShape::capsule_y(0.6, 0.3);
+        let dt = 1.0 / 60.0;
+        let mut center = Vec3::new(0.0, 0.92, 0.0);
+        let mut velocity = Vec3::new(3.0, 0.0, 0.0);
+        for _ in 0..120 {
+            let movement = controller.move_shape(physics, &*body, center, velocity * dt, dt);
+            center += movement.translation;
+            velocity.y = if movement.grounded { -1.0 } else { velocity.y - 25.0 * dt };
+        }
+        center
+    }
+
+    fn ground(physics: &mut PhysicsWorld) {
+        physics.add_static_cuboid(Vec3::new(0.0, -0.5, 0.0), 0.0, Vec3::new(20.0, 0.5, 20.0));
+    }
+
+    #[test]
+    fn character_steps_up_low_ledges_but_not_tall_ones() {
+        let controller = KinematicCharacterController::new(50f32.to_radians(), 0.4);
+        for (ledge, climbs) in [(0.3, true), (0.8, false)] {
+            let mut physics = PhysicsWorld::new();
+            ground(&mut physics);
+            // Ledge from x = 2 onward
+            physics.add_static_cuboid(Vec3::new(6.0, ledge * 0.5, 0.0), 0.0, Vec3::new(4.0, ledge * 0.5, 4.0));
+            physics.update_query_pipeline();
+
+            let end = walk(&physics, &controller);
+            if climbs {
+                assert!(end.x > 3.0 && end.y > 0.92 + ledge - 0.05, "{ledge} m ledge not climbed: {end:?}");
+            } else {
+                assert!(end.x < 2.0 && end.y < 1.0, "walked up a {ledge} m wall: {end:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn character_walks_up_gentle_slopes_and_is_stopped_by_steep_ones() {
+        let controller = KinematicCharacterController::new(45f32.to_radians(), 0.0);
+        for (degrees, climbs) in [(25f32, true), (65f32, false)] {
+            let mut physics = PhysicsWorld::new();
+            ground(&mut physics);
+            // Ramp rising along +X, its foot at x = 1.5
+            let angle = degrees.to_radians();
+            let (half_len, half_thick) = (10.0, 0.5);
+            let center = Vec3::new(1.5, 0.0, 0.0)
+                + Vec3::new(angle.cos(), angle.sin(), 0.0) * half_len
+                + Vec3::new(-angle.sin(), angle.cos(), 0.0) * -half_thick;
+            let ramp = ColliderBuilder::cuboid(half_len, half_thick, 4.0)
+                .translation(vector![center.x, center.y, center.z])
+                .rotation(vector![0.0, 0.0, angle])
+                .collision_groups(env_collision_groups())
+                .build();
+            physics.collider_set.insert(ramp);
+            physics.update_query_pipeline();
+
+            let end = walk(&physics, &controller);
+            if climbs {
+                assert!(end.y > 1.5, "{degrees}° slope not climbed: {end:?}");
+            } else {
+                assert!(end.x < 2.0, "walked up a {degrees}° slope: {end:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn grenade_bounces_off_the_ground() {
+        let mut physics = PhysicsWorld::new();
+        physics.add_static_cuboid(Vec3::new(0.0, -0.5, 0.0), 0.0, Vec3::new(10.0, 0.5, 10.0));
+        let (body, _) = physics.add_grenade_body(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, -8.0, 0.0), 0.1);
+
+        let mut bounced = false;
+        for _ in 0..120 {
+            physics.step();
+            bounced |= physics.get_body_linvel(body).unwrap().y > 1.0;
+        }
+        assert!(bounced, "grenade never bounced");
+        let pos = physics.get_body_transform(body).unwrap().position;
+        assert!(pos.y > 0.0, "grenade fell through the ground: {pos:?}");
+    }
+}
@@ -1,7 +1,8 @@
-//! Raycasting for weapon hit detection and queries.
+//! Raycasting and shape casting for weapon hit detection and movement queries.
 
 use crate::PhysicsWorld;
 use engine_core::Vec3;
+use rapier3d::parry::query::ShapeCastOptions;
 use rapier3d::prelude::*;
 
 /// Result of a raycast query.
@@ -17,15 +18,54 @@ pub struct RaycastHit {
     pub normal: Vec3,
 }
 
+/// Result of a shape cast.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeCastResult {
+    /// The collider that was hit.
+    pub collider: ColliderHandle,
+    /// Fraction of `velocity` travelled before contact (distance = toi * |velocity|).
+    pub toi: f32,
+    /// World position of the contact on the hit collider.
+    pub point: Vec3,
+    /// Outward surface normal of the hit collider at the contact.
+    pub normal: Vec3,
+}
+
+/// Outcome of `PhysicsWorld::move_and_slide`.
+#[derive(Debug, Clone, Default)]
+pub struct SlideResult {
+    /// Translation actually applied after sliding along contacts.
+    pub translation: Vec3,
+    /// Every contact hit along the way, in order.
+    pub hits: Vec<ShapeCastResult>,
+}
+
+/// Gap kept between a sliding shape and whatever it hits, so the next cast doesn't start touching.
+const SLIDE_SKIN: f32 = 0.02;
+/// Contacts resolved per `move_and_slide` call before the remaining motion is dropped.
+const MAX_SLIDE_ITERATIONS: usize = 4;
+
 impl PhysicsWorld {
     /// Cast a ray and return the first hit.
     pub fn raycast(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<RaycastHit> {
+        self.raycast_filtered(origin, direction, max_distance, InteractionGroups::all())
+    }
+
+    /// Cast a ray and return the first hit among colliders matching `groups`
+    /// (see `CollisionGroup::query`).
+    pub fn raycast_filtered(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        groups: InteractionGroups,
+    ) -> Option<RaycastHit> {
         let ray = Ray::new(
             point![origin.x, origin.y, origin.z],
             vector![direction.x, direction.y, direction.z],
         );
 
-        let filter = QueryFilter::default();
+        let filter = QueryFilter::default().exclude_sensors().groups(groups);
 
         self.query_pipeline
             .cast_ray_and_get_normal(
@@ -57,13 +97,24 @@ impl PhysicsWorld {
         origin: Vec3,
         direction: Vec3,
         max_distance: f32,
+    ) -> Vec<RaycastHit> {
+        self.raycast_all_filtered(origin, direction, max_distance, InteractionGroups::all())
+    }
+
+    /// Cast a ray and return all hits among colliders matching `groups`, nearest first.
+    pub fn raycast_all_filtered(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        groups: InteractionGroups,
     ) -> Vec<RaycastHit> {
         let ray = Ray::new(
             point![origin.x, origin.y, origin.z],
             vector![direction.x, direction.y, direction.z],
         );
 
-        let filter = QueryFilter::default();
+        let filter = QueryFilter::default().exclude_sensors().groups(groups);
         let mut hits = Vec::new();
 
         self.query_pipeline.intersections_with_ray(
@@ -94,6 +145,88 @@ impl PhysicsWorld {
         hits
     }
 
+    /// Sweep `shape` from `from` along `velocity` and return the first collider matching
+    /// `groups` that it touches within `max_toi` (in units of `velocity`). A shape that starts
+    /// overlapping something but is moving out of it is not reported.
+    pub fn cast_shape(
+        &self,
+        shape: &dyn Shape,
+        from: Vec3,
+        velocity: Vec3,
+        max_toi: f32,
+        groups: InteractionGroups,
+    ) -> Option<ShapeCastResult> {
+        let shape_pos = Isometry::translation(from.x, from.y, from.z);
+        let shape_vel = vector![velocity.x, velocity.y, velocity.z];
+        let options = ShapeCastOptions {
+            max_time_of_impact: max_toi,
+            target_distance: 0.0,
+            stop_at_penetration: false,
+            compute_impact_geometry_on_penetration: true,
+        };
+        let filter = QueryFilter::default().exclude_sensors().groups(groups);
+
+        self.query_pipeline
+            .cast_shape(
+                &self.rigid_body_set,
+                &self.collider_set,
+                &shape_pos,
+                &shape_vel,
+                shape,
+                options,
+                filter,
+            )
+            .map(|(collider, hit)| ShapeCastResult {
+                collider,
+                toi: hit.time_of_impact,
+                point: Vec3::new(hit.witness1.x, hit.witness1.y, hit.witness1.z),
+                normal: Vec3::new(hit.normal1.x, hit.normal1.y, hit.normal1.z),
+            })
+    }
+
+    /// Move `shape` from `from` by `translation`, sliding along anything matching `groups`
+    /// instead of stopping dead. Resolves up to a few contacts per call (walls, corners
+    /// between two colliders), keeping a small skin gap from each.
+    pub fn move_and_slide(
+        &self,
+        shape: &dyn Shape,
+        from: Vec3,
+        translation: Vec3,
+        groups: InteractionGroups,
+    ) -> SlideResult {
+        let mut result = SlideResult::default();
+        let mut remaining = translation;
+        let mut normals: Vec<Vec3> = Vec::new();
+
+        for _ in 0..MAX_SLIDE_ITERATIONS {
+            let length = remaining.length();
+            if length < 1e-5 {
+                break;
+            }
+            let Some(hit) = self.cast_shape(shape, from + result.translation, remaining, 1.0, groups) else {
+                result.translation += remaining;
+                break;
+            };
+
+            // Advance up to the contact, leaving the skin gap
+            let travel = (hit.toi * length - SLIDE_SKIN).max(0.0);
+            result.translation += remaining * (travel / length);
+            remaining *= 1.0 - travel / length;
+
+            // Drop the part of the motion that pushes into the surface
+            remaining -= hit.normal * remaining.dot(hit.normal).min(0.0);
+            // Wedged between two surfaces: slide along the crease they form
+            if let Some(&previous) = normals.iter().find(|n| remaining.dot(**n) < 0.0) {
+                let crease = previous.cross(hit.normal).normalize_or_zero();
+                remaining = crease * remaining.dot(crease);
+            }
+            normals.push(hit.normal);
+            result.hits.push(hit);
+        }
+
+        result
+    }
+
     /// Check if there's a clear line of sight between two points.
     pub fn line_of_sight(&self, from: Vec3, to: Vec3) -> bool {
         let direction = to - from;
@@ -110,7 +243,7 @@ impl PhysicsWorld {
     pub fn overlap_sphere(&self, center: Vec3, radius: f32) -> Vec<ColliderHandle> {
         let shape = Ball::new(radius);
         let shape_pos = Isometry::translation(center.x, center.y, center.z);
-        let filter = QueryFilter::default();
+        let filter = QueryFilter::default().exclude_sensors();
 
         let mut results = Vec::new();
         self.query_pipeline.intersections_with_shape(
@@ -320,6 +320,21 @@ pub const ALL_BIOMES: [BiomeType; 18] = [
     BiomeType::Ruins,
 ];
 
+/// Default width (world units) of the color transition between neighbouring biomes.
+pub const DEFAULT_BIOME_BLEND_WIDTH: f64 = 32.0;
+/// Height scale blends over a wider band than color so borders don't turn into cliffs.
+const HEIGHT_BLEND_SPREAD: f64 = 4.0;
+
+/// The two biomes nearest a position and how much the second has blended in.
+#[derive(Debug, Clone, Copy)]
+pub struct BiomeBlend {
+    /// Dominant biome (used for water, props and gameplay rules).
+    pub primary: BiomeType,
+    pub secondary: BiomeType,
+    /// Share of `secondary`, 0..0.5 (0.5 exactly on the border).
+    pub weight: f32,
+}
+
 /// Noise-based biome sampler for a planet.
 /// Uses large-scale noise to assign biome regions across the surface.
 pub struct PlanetBiomes {
@@ -329,6 +344,8 @@ pub struct PlanetBiomes {
     _blend_noise: Simplex,
     /// Scale: lower = larger biome regions.
     pub region_scale: f64,
+    /// Width in world units of the transition between biomes (0 = hard border).
+    pub blend_width: f64,
 }
 
 impl PlanetBiomes {
@@ -342,6 +359,7 @@ impl PlanetBiomes {
             biome_noise,
             _blend_noise: blend_noise,
             region_scale: 0.003, // Slightly larger regions so all 12 biomes are visible
+            blend_width: DEFAULT_BIOME_BLEND_WIDTH,
         }
     }
 
@@ -369,36 +387,80 @@ impl PlanetBiomes {
             biome_noise,
             _blend_noise: blend_noise,
             region_scale: 0.004 + rng.gen::<f64>() * 0.003, // 0.004..0.007
+            blend_width: DEFAULT_BIOME_BLEND_WIDTH,
+        }
+    }
+
+    /// Biome noise mapped to [0, n-1]; biome `i` owns the band around `i`.
+    fn band_at(&self, x: f64, z: f64) -> f64 {
+        let n = self.biomes.len();
+        let val = self.biome_noise.get([x * self.region_scale, z * self.region_scale]);
+        // Map noise [-1, 1] to [0, n)
+        ((val * 0.5 + 0.5) * n as f64).clamp(0.0, (n - 1) as f64)
+    }
+
+    /// Indices of the two neighbouring biome bands at (x, z) and the share of the second,
+    /// ramping over `width` world units centered on their border. The border distance comes
+    /// from the local noise gradient, so the ramp is the same width wherever the border lies.
+    /// Where a band is thinner than `width` the ramp shrinks to fit it, so it has finished by
+    /// the time the next pair of bands takes over.
+    fn band_blend(&self, x: f64, z: f64, width: f64) -> (usize, usize, f32) {
+        let n = self.biomes.len();
+        let mapped = self.band_at(x, z);
+        let idx_a = mapped.floor() as usize;
+        let idx_b = (idx_a + 1).min(n - 1);
+        let frac = mapped - idx_a as f64;
+        if idx_a == idx_b {
+            return (idx_a, idx_b, 0.0);
+        }
+
+        const EPS: f64 = 1.0;
+        let gx = (self.band_at(x + EPS, z) - self.band_at(x - EPS, z)) / (2.0 * EPS);
+        let gz = (self.band_at(x, z + EPS) - self.band_at(x, z - EPS)) / (2.0 * EPS);
+        let gradient = (gx * gx + gz * gz).sqrt().max(1e-6);
+        // Signed world distance past the a|b border (positive on b's side)
+        let distance = (frac - 0.5) / gradient;
+        let width = width.min(1.0 / gradient);
+        let blend = if width <= 0.0 {
+            if distance >= 0.0 { 1.0 } else { 0.0 }
+        } else {
+            let t = (distance / width + 0.5).clamp(0.0, 1.0);
+            t * t * (3.0 - 2.0 * t)
+        };
+        (idx_a, idx_b, blend as f32)
+    }
+
+    /// Dominant and neighbouring biome at (x, z), for dithering block choices across borders.
+    pub fn blend_at(&self, x: f64, z: f64) -> BiomeBlend {
+        if self.biomes.is_empty() {
+            return BiomeBlend {
+                primary: BiomeType::Desert,
+                secondary: BiomeType::Desert,
+                weight: 0.0,
+            };
+        }
+        let (a, b, blend) = self.band_blend(x, z, self.blend_width);
+        let (primary, secondary, weight) = if blend < 0.5 { (a, b, blend) } else { (b, a, 1.0 - blend) };
+        BiomeBlend {
+            primary: self.biomes[primary],
+            secondary: self.biomes[secondary],
+            weight,
         }
     }
 
     /// Sample the biome at a world (x, z) position.
     /// Returns the biome config and a blend color (RGBA) for the vertex.
     pub fn sample_at(&self, x: f64, z: f64) -> (BiomeConfig, [f32; 4]) {
-        let n = self.biomes.len();
-        if n == 0 {
+        if self.biomes.is_empty() {
             let cfg = BiomeConfig::from_type(BiomeType::Desert);
             let c = cfg.base_color;
             return (cfg, [c.x, c.y, c.z, 1.0]);
         }
 
-        // Primary biome selection noise (large-scale regions)
-        let val = self.biome_noise.get([x * self.region_scale, z * self.region_scale]);
-        // Map noise [-1, 1] to [0, n)
-        let mapped = ((val * 0.5 + 0.5) * n as f64).clamp(0.0, (n - 1) as f64);
-
-        let idx_a = mapped.floor() as usize;
-        let idx_b = (idx_a + 1).min(n - 1);
-        let frac = (mapped - idx_a as f64) as f32;
-
+        let (idx_a, idx_b, blend) = self.band_blend(x, z, self.blend_width);
         let cfg_a = BiomeConfig::from_type(self.biomes[idx_a]);
         let cfg_b = BiomeConfig::from_type(self.biomes[idx_b]);
 
-        // Sharpen the blend: most of the terrain is clearly one biome, with
-        // smooth transitions at boundaries.
-        let t = (frac * 2.0 - 1.0).clamp(-1.0, 1.0); // remap to [-1, 1]
-        let blend = (t * t * t * 0.5 + 0.5).clamp(0.0, 1.0); // cubic ease
-
         // Blend colors
         let color = cfg_a.base_color * (1.0 - blend) + cfg_b.base_color * blend;
 
@@ -410,19 +472,13 @@ impl PlanetBiomes {
 
     /// Sample just the height scale at a position (for terrain height variation by biome).
     pub fn height_scale_at(&self, x: f64, z: f64) -> f32 {
-        let n = self.biomes.len();
-        if n == 0 {
+        if self.biomes.is_empty() {
             return 1.0;
         }
-        let val = self.biome_noise.get([x * self.region_scale, z * self.region_scale]);
-        let mapped = ((val * 0.5 + 0.5) * n as f64).clamp(0.0, (n - 1) as f64);
-        let idx_a = mapped.floor() as usize;
-        let idx_b = (idx_a + 1).min(n - 1);
-        let frac = mapped - idx_a as f64;
-
+        let (idx_a, idx_b, blend) = self.band_blend(x, z, self.blend_width * HEIGHT_BLEND_SPREAD);
         let a = BiomeConfig::from_type(self.biomes[idx_a]).height_scale;
         let b = BiomeConfig::from_type(self.biomes[idx_b]).height_scale;
-        a + (b - a) * frac as f32
+        a + (b - a) * blend
     }
 }
 
@@ -470,3 +526,44 @@ impl Default for BiomeBlender {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Color changes gradually across a biome border instead of in a single step.
+    #[test]
+    fn blend_is_gradual_and_width_zero_is_hard() {
+        let mut biomes = PlanetBiomes::from_seed(42);
+        biomes.biomes = vec![BiomeType::Desert, BiomeType::Frozen];
+        let weights: Vec<f32> = (0..4000).map(|i| biomes.blend_at(i as f64 * 0.5, 0.0).weight).collect();
+        assert!(weights.iter().any(|&w| w > 0.05 && w < 0.45), "expected partial blend near a border");
+        assert!(weights.windows(2).all(|w| (w[1] - w[0]).abs() < 0.2));
+
+        biomes.blend_width = 0.0;
+        assert!((0..4000).all(|i| biomes.blend_at(i as f64 * 0.5, 0.0).weight == 0.0));
+    }
+
+    /// With several thin bands and a wide blend, height scale and colour still change smoothly:
+    /// no jump where one pair of bands hands over to the next.
+    #[test]
+    fn wide_blend_across_many_bands_stays_continuous() {
+        let mut biomes = PlanetBiomes::from_seed(7);
+        biomes.biomes = vec![BiomeType::Desert, BiomeType::Mountain, BiomeType::Frozen, BiomeType::Swamp, BiomeType::Volcanic];
+        for width in [DEFAULT_BIOME_BLEND_WIDTH, 400.0] {
+            biomes.blend_width = width;
+            // Fine steps: a thin band ramps steeply but smoothly, a jump shows up at any step
+            let samples: Vec<(f32, [f32; 4])> = (0..150_000)
+                .map(|i| {
+                    let x = i as f64 * 0.02;
+                    (biomes.height_scale_at(x, 0.0), biomes.sample_at(x, 0.0).1)
+                })
+                .collect();
+            for pair in samples.windows(2) {
+                let ((h0, c0), (h1, c1)) = (pair[0], pair[1]);
+                assert!((h1 - h0).abs() < 0.05, "height scale jumped {h0} -> {h1} (width {width})");
+                assert!((0..3).all(|k| (c1[k] - c0[k]).abs() < 0.05), "colour jumped {c0:?} -> {c1:?} (width {width})");
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Flow field pathfinding for horde AI.
 //! 
 //! Flow fields allow thousands of units to pathfind efficiently by computing
-//! a single direction field that all units sample.
+//! a single direction field that all units sample. Obstacles are kept in world
+//! space and re-stamped into the cost grid whenever it recenters on the goal.
 
 use glam::{IVec2, Vec2, Vec3};
 use std::collections::VecDeque;
@@ -17,14 +18,30 @@ pub struct FlowField {
     pub cell_size: f32,
     /// Origin of the grid in world space.
     pub origin: Vec2,
-    /// Cost field (0 = walkable, 255 = blocked).
+    /// Cost field (0 = walkable, 255 = blocked): `base_costs` with obstacles stamped on top.
     costs: Vec<u8>,
+    /// Costs set with `set_blocked` / `set_cost`, kept apart so restamping obstacles after a
+    /// recenter or removal doesn't wipe them.
+    base_costs: Vec<u8>,
     /// Integration field (distance to goal).
     integration: Vec<u16>,
     /// Flow directions (normalized).
     directions: Vec<Vec2>,
     /// Goal position in grid coordinates.
     goal: Option<IVec2>,
+    /// World-space obstacle footprints, stamped into `costs` as blocked cells.
+    obstacles: Vec<FlowObstacle>,
+}
+
+/// Axis-aligned XZ footprint of something units can't walk through (rock, wreck, building).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowObstacle {
+    /// Caller-chosen id used to remove the obstacle later.
+    pub id: u64,
+    /// Minimum world XZ corner.
+    pub min: Vec2,
+    /// Maximum world XZ corner.
+    pub max: Vec2,
 }
 
 const BLOCKED: u8 = 255;
@@ -40,41 +57,176 @@ impl FlowField {
             cell_size,
             origin,
             costs: vec![1; size],
+            base_costs: vec![1; size],
             integration: vec![MAX_INTEGRATION; size],
             directions: vec![Vec2::ZERO; size],
             goal: None,
+            obstacles: Vec::new(),
         }
     }
 
     /// Set a cell as blocked (obstacle).
     pub fn set_blocked(&mut self, x: usize, y: usize) {
-        if x < self.width && y < self.height {
-            self.costs[y * self.width + x] = BLOCKED;
-        }
+        self.set_base_cost(x, y, BLOCKED);
     }
 
     /// Set a cell's movement cost (1-254, higher = slower).
     pub fn set_cost(&mut self, x: usize, y: usize, cost: u8) {
-        if x < self.width && y < self.height {
-            self.costs[y * self.width + x] = cost.min(254).max(1);
+        self.set_base_cost(x, y, cost.min(254).max(1));
+    }
+
+    /// Write a base cost; a registered obstacle over the cell keeps it blocked.
+    fn set_base_cost(&mut self, x: usize, y: usize, cost: u8) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let idx = y * self.width + x;
+        self.base_costs[idx] = cost;
+        let covered = self.obstacles.iter().any(|o| {
+            self.obstacle_cells(o).is_some_and(|(x0, y0, x1, y1)| (x0..=x1).contains(&x) && (y0..=y1).contains(&y))
+        });
+        if !covered {
+            self.costs[idx] = cost;
         }
     }
 
     /// Clear all blocked cells.
     pub fn clear(&mut self) {
         self.costs.fill(1);
+        self.base_costs.fill(1);
         self.integration.fill(MAX_INTEGRATION);
         self.directions.fill(Vec2::ZERO);
         self.goal = None;
+        self.obstacles.clear();
+    }
+
+    /// Register a world-space obstacle footprint. Replaces any obstacle with the same id.
+    /// Its cells stay blocked as the grid recenters; call `recompute` to reroute immediately.
+    pub fn add_obstacle(&mut self, id: u64, min: Vec2, max: Vec2) {
+        self.remove_obstacle(id);
+        let obstacle = FlowObstacle { id, min, max };
+        self.stamp_obstacle(&obstacle);
+        self.obstacles.push(obstacle);
+    }
+
+    /// Remove an obstacle and unblock its cells (cells another obstacle still covers stay blocked).
+    /// Returns false if no obstacle has this id. Call `recompute` to reroute immediately.
+    pub fn remove_obstacle(&mut self, id: u64) -> bool {
+        let Some(i) = self.obstacles.iter().position(|o| o.id == id) else {
+            return false;
+        };
+        let removed = self.obstacles.swap_remove(i);
+        if let Some((x0, y0, x1, y1)) = self.obstacle_cells(&removed) {
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    let idx = y * self.width + x;
+                    self.costs[idx] = self.base_costs[idx];
+                }
+            }
+            let overlapping: Vec<FlowObstacle> = self
+                .obstacles
+                .iter()
+                .filter(|o| o.min.cmple(removed.max).all() && o.max.cmpge(removed.min).all())
+                .copied()
+                .collect();
+            for o in &overlapping {
+                self.stamp_obstacle(o);
+            }
+        }
+        true
+    }
+
+    /// Whether an obstacle with this id is registered.
+    pub fn has_obstacle(&self, id: u64) -> bool {
+        self.obstacles.iter().any(|o| o.id == id)
+    }
+
+    /// All registered obstacles.
+    pub fn obstacles(&self) -> &[FlowObstacle] {
+        &self.obstacles
+    }
+
+    /// Recalculate the field toward the current goal (e.g. after obstacles changed).
+    pub fn recompute(&mut self) {
+        if let Some(goal) = self.goal {
+            self.calculate_integration(goal.x as usize, goal.y as usize);
+            self.calculate_flow();
+        }
+    }
+
+    /// Grid cell range (x0, y0, x1, y1 inclusive) an obstacle blocks: every cell whose center
+    /// lies inside the footprint, or just the cell under its center if it is smaller than a cell.
+    fn obstacle_cells(&self, obstacle: &FlowObstacle) -> Option<(usize, usize, usize, usize)> {
+        let lo = (obstacle.min - self.origin) / self.cell_size - Vec2::splat(0.5);
+        let hi = (obstacle.max - self.origin) / self.cell_size - Vec2::splat(0.5);
+        let (mut x0, mut y0) = (lo.x.ceil() as i32, lo.y.ceil() as i32);
+        let (mut x1, mut y1) = (hi.x.floor() as i32, hi.y.floor() as i32);
+        if x0 > x1 || y0 > y1 {
+            let c = (obstacle.min + obstacle.max) * 0.5;
+            let center = self.world_to_grid(Vec3::new(c.x, 0.0, c.y));
+            (x0, y0, x1, y1) = (center.x, center.y, center.x, center.y);
+        }
+        let x0 = x0.max(0);
+        let y0 = y0.max(0);
+        let x1 = x1.min(self.width as i32 - 1);
+        let y1 = y1.min(self.height as i32 - 1);
+        if x0 > x1 || y0 > y1 {
+            return None;
+        }
+        Some((x0 as usize, y0 as usize, x1 as usize, y1 as usize))
+    }
+
+    fn stamp_obstacle(&mut self, obstacle: &FlowObstacle) {
+        if let Some((x0, y0, x1, y1)) = self.obstacle_cells(obstacle) {
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    self.costs[y * self.width + x] = BLOCKED;
+                }
+            }
+        }
+    }
+
+    /// Rebuild the cost grid after the origin moved by `shift` cells: base costs move with the
+    /// world (cells scrolled in start at cost 1), then obstacles are stamped back on top.
+    fn restamp_obstacles(&mut self, shift: IVec2) {
+        let (w, h) = (self.width as i32, self.height as i32);
+        let mut shifted = vec![1; self.base_costs.len()];
+        for y in 0..h {
+            for x in 0..w {
+                let (sx, sy) = (x + shift.x, y + shift.y);
+                if sx >= 0 && sy >= 0 && sx < w && sy < h {
+                    shifted[(y * w + x) as usize] = self.base_costs[(sy * w + sx) as usize];
+                }
+            }
+        }
+        self.base_costs = shifted;
+        self.costs.copy_from_slice(&self.base_costs);
+        let obstacles = std::mem::take(&mut self.obstacles);
+        for o in &obstacles {
+            self.stamp_obstacle(o);
+        }
+        self.obstacles = obstacles;
+    }
+
+    /// True if (x, y) is inside the grid and blocked.
+    fn is_blocked(&self, x: i32, y: i32) -> bool {
+        !(x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32)
+            && self.costs[y as usize * self.width + x as usize] == BLOCKED
     }
 
     /// Set the goal and recalculate the field.
     /// Centers the grid on the goal so the flow field always covers the area around the target.
     pub fn set_goal(&mut self, world_pos: Vec3) {
-        // Center the grid on the goal so bugs pathfind correctly wherever the player is
+        // Center the grid on the goal so bugs pathfind correctly wherever the player is.
+        // Snap to whole cells so obstacles land on the same cells after every recenter.
         let half_w = (self.width as f32 * self.cell_size) * 0.5;
         let half_h = (self.height as f32 * self.cell_size) * 0.5;
-        self.origin = Vec2::new(world_pos.x - half_w, world_pos.z - half_h);
+        let origin = (Vec2::new(world_pos.x - half_w, world_pos.z - half_h) / self.cell_size).floor() * self.cell_size;
+        if origin != self.origin {
+            let shift = ((origin - self.origin) / self.cell_size).round().as_ivec2();
+            self.origin = origin;
+            self.restamp_obstacles(shift);
+        }
 
         let grid_pos = self.world_to_grid(world_pos);
         self.set_goal_grid(grid_pos.x, grid_pos.y);
@@ -94,11 +246,9 @@ impl FlowField {
     fn calculate_integration(&mut self, goal_x: usize, goal_y: usize) {
         self.integration.fill(MAX_INTEGRATION);
 
+        // Seed the goal even when it sits in a blocked cell (player hugging a wall),
+        // so the surrounding cells still flow toward it.
         let goal_idx = goal_y * self.width + goal_x;
-        if self.costs[goal_idx] == BLOCKED {
-            return;
-        }
-
         self.integration[goal_idx] = 0;
 
         let mut open = VecDeque::new();
@@ -135,6 +285,10 @@ impl FlowField {
                 if self.costs[neighbor_idx] == BLOCKED {
                     continue;
                 }
+                // No diagonal corner-cutting past a blocked cell
+                if dx != 0 && dy != 0 && (self.is_blocked(nx as i32, y as i32) || self.is_blocked(x as i32, ny as i32)) {
+                    continue;
+                }
 
                 let new_cost = current_cost
                     .saturating_add(base_cost)
@@ -182,6 +336,10 @@ impl FlowField {
                         continue;
                     }
 
+                    if dx != 0 && dy != 0 && (self.is_blocked(nx, y as i32) || self.is_blocked(x as i32, ny)) {
+                        continue;
+                    }
+
                     let neighbor_idx = ny as usize * self.width + nx as usize;
                     let neighbor_cost = self.integration[neighbor_idx];
 
@@ -345,4 +503,60 @@ mod tests {
         f.set_blocked(4, 4);
         assert!(!f.is_walkable(4, 4));
     }
+
+    #[test]
+    fn flow_field_routes_around_obstacle() {
+        // Goal at the left edge; a wall across the middle with a gap at the top row.
+        let mut f = FlowField::new(10, 10, 1.0, Vec2::ZERO);
+        f.add_obstacle(1, Vec2::new(4.2, 1.2), Vec2::new(4.8, 9.8));
+        f.set_goal_grid(0, 5);
+        assert!(!f.is_walkable(4, 5));
+        // Directly behind the wall the flow must not point into it.
+        let behind = f.sample(Vec3::new(5.5, 0.0, 5.5));
+        assert!(behind.x >= 0.0 || behind.y < 0.0, "flow walks into the wall: {behind:?}");
+        assert_ne!(behind, Vec2::ZERO, "cell behind the wall should still reach the goal via the gap");
+    }
+
+    #[test]
+    fn flow_field_obstacle_survives_recenter() {
+        let mut f = FlowField::new(20, 20, 2.0, Vec2::ZERO);
+        f.add_obstacle(7, Vec2::new(9.0, 9.0), Vec2::new(13.0, 13.0));
+        f.set_goal(Vec3::new(6.0, 0.0, 4.0));
+        let cell = f.world_to_grid(Vec3::new(11.0, 0.0, 11.0));
+        assert!(!f.is_walkable(cell.x, cell.y));
+    }
+
+    #[test]
+    fn flow_field_base_costs_survive_obstacle_changes_and_recenter() {
+        let mut f = FlowField::new(20, 20, 2.0, Vec2::ZERO);
+        f.set_blocked(3, 3);
+        f.add_obstacle(1, Vec2::new(13.0, 13.0), Vec2::new(15.0, 15.0));
+        f.set_cost(6, 6, 50);
+        assert!(f.remove_obstacle(1));
+        assert_eq!(f.costs[6 * 20 + 6], 50, "removing an obstacle restores the base cost under it");
+
+        // Recenter: the blocked cell stays with its world position
+        let blocked_world = f.grid_to_world(IVec2::new(3, 3));
+        f.set_goal(Vec3::new(24.0, 0.0, 22.0));
+        let cell = f.world_to_grid(blocked_world);
+        assert_eq!(cell, IVec2::new(1, 2));
+        assert!(!f.is_walkable(cell.x, cell.y));
+        assert_eq!(f.costs.iter().filter(|&&c| c == BLOCKED).count(), 1);
+    }
+
+    #[test]
+    fn flow_field_remove_obstacle_unblocks() {
+        let mut f = FlowField::new(10, 10, 1.0, Vec2::ZERO);
+        f.add_obstacle(1, Vec2::new(3.0, 3.0), Vec2::new(6.0, 6.0));
+        f.add_obstacle(2, Vec2::new(5.2, 5.2), Vec2::new(5.8, 5.8));
+        f.set_goal_grid(0, 0);
+        assert!(f.remove_obstacle(1));
+        assert!(!f.remove_obstacle(1));
+        f.recompute();
+        assert!(f.is_walkable(4, 4));
+        // Cell still covered by obstacle 2 stays blocked.
+        assert!(!f.is_walkable(5, 5));
+        assert!(f.has_obstacle(2));
+        assert_ne!(f.sample(Vec3::new(4.5, 0.0, 4.5)), Vec2::ZERO);
+    }
 }
@@ -4,6 +4,7 @@ pub mod biome;
 pub mod bug_mesh;
 pub mod flow_field;
 pub mod planet;
+pub mod river;
 pub mod star_system;
 pub mod terrain;
 pub mod textures;
@@ -14,6 +15,7 @@ pub use biome::*;
 pub use bug_mesh::*;
 pub use flow_field::*;
 pub use planet::*;
+pub use river::*;
 pub use star_system::*;
 pub use terrain::*;
 pub use textures::*;
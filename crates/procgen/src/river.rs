@@ -0,0 +1,277 @@
+//! Rivers and lakes carved into voxel terrain.
+//!
+//! Sources are scattered on a coarse world grid and each river is traced downhill over the
+//! terrain height field until it reaches sea level or gets stuck in a basin, which becomes a
+//! lake. Every chunk re-traces the rivers that can reach it, so channels line up across chunk
+//! borders regardless of generation order.
+
+/// River and lake parameters (world units). Set `TerrainConfig::rivers` to `None` to disable.
+#[derive(Debug, Clone)]
+pub struct RiverConfig {
+    /// Size of the source grid cells; each cell holds at most one source.
+    pub source_spacing: f32,
+    /// Chance (0-1) that a grid cell spawns a source.
+    pub source_chance: f32,
+    /// Sources must sit at least this far above sea level.
+    pub min_source_height: f32,
+    /// Maximum path length before a river gives up.
+    pub max_length: f32,
+    /// Distance between traced path points.
+    pub step: f32,
+    /// Channel half-width at the source; widens towards `max_width` downstream.
+    pub width: f32,
+    pub max_width: f32,
+    /// Channel bed depth below the original ground.
+    pub depth: f32,
+    /// Radius of the lake formed where a river ends in a basin.
+    pub lake_radius: f32,
+    /// Lake depth at the center, below the lake surface.
+    pub lake_depth: f32,
+}
+
+impl Default for RiverConfig {
+    fn default() -> Self {
+        Self {
+            source_spacing: 128.0,
+            source_chance: 0.35,
+            min_source_height: 10.0,
+            max_length: 256.0,
+            step: 4.0,
+            width: 1.5,
+            max_width: 4.0,
+            depth: 2.5,
+            lake_radius: 10.0,
+            lake_depth: 4.0,
+        }
+    }
+}
+
+impl RiverConfig {
+    /// How far outside a chunk a source can be and still carve into it.
+    pub fn reach(&self) -> f32 {
+        self.max_length + self.max_width.max(self.lake_radius)
+    }
+}
+
+/// One sample along a river: position, original ground height and channel half-width.
+#[derive(Debug, Clone, Copy)]
+pub struct RiverPoint {
+    pub x: f32,
+    pub z: f32,
+    pub ground_y: f32,
+    pub half_width: f32,
+}
+
+/// A basin lake at the end of a river; `level` is the water surface.
+#[derive(Debug, Clone, Copy)]
+pub struct Lake {
+    pub x: f32,
+    pub z: f32,
+    pub radius: f32,
+    pub level: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RiverPath {
+    pub points: Vec<RiverPoint>,
+    pub lake: Option<Lake>,
+}
+
+/// Deterministic 64-bit hash of a source grid cell (splitmix64 finalizer).
+fn cell_hash(seed: u64, cx: i32, cz: i32) -> u64 {
+    let mut h = seed
+        ^ (cx as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (cz as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// Candidate river sources inside the world rectangle `min..max`, jittered within their grid cells.
+pub fn river_sources(cfg: &RiverConfig, seed: u64, min: (f32, f32), max: (f32, f32)) -> Vec<(f32, f32)> {
+    let spacing = cfg.source_spacing.max(1.0);
+    let mut sources = Vec::new();
+    for cz in (min.1 / spacing).floor() as i32..=(max.1 / spacing).floor() as i32 {
+        for cx in (min.0 / spacing).floor() as i32..=(max.0 / spacing).floor() as i32 {
+            let h = cell_hash(seed, cx, cz);
+            let unit = |shift: u32| ((h >> shift) & 0xffff) as f32 / 65536.0;
+            if unit(0) >= cfg.source_chance {
+                continue;
+            }
+            sources.push(((cx as f32 + unit(16)) * spacing, (cz as f32 + unit(32)) * spacing));
+        }
+    }
+    sources
+}
+
+/// Follow the steepest descent from `source` over `ground(x, z)`. Stops at `sea_level`, at
+/// `max_length`, or in a basin (no lower neighbour), which becomes a lake.
+pub fn trace_river(
+    cfg: &RiverConfig,
+    source: (f32, f32),
+    sea_level: f32,
+    ground: impl Fn(f32, f32) -> f32,
+) -> RiverPath {
+    const DIRS: [(f32, f32); 8] = [
+        (1.0, 0.0),
+        (-1.0, 0.0),
+        (0.0, 1.0),
+        (0.0, -1.0),
+        (0.707, 0.707),
+        (0.707, -0.707),
+        (-0.707, 0.707),
+        (-0.707, -0.707),
+    ];
+    let step = cfg.step.max(0.5);
+    let max_steps = (cfg.max_length / step).ceil().max(1.0) as usize;
+    let (mut x, mut z) = source;
+    let mut y = ground(x, z);
+    let mut points = Vec::new();
+    let mut lake = None;
+
+    for i in 0..max_steps {
+        let t = i as f32 / max_steps as f32;
+        points.push(RiverPoint {
+            x,
+            z,
+            ground_y: y,
+            half_width: cfg.width + (cfg.max_width - cfg.width) * t,
+        });
+        if y <= sea_level {
+            break;
+        }
+        let (nx, nz, ny) = DIRS
+            .iter()
+            .map(|&(dx, dz)| {
+                let (px, pz) = (x + dx * step, z + dz * step);
+                (px, pz, ground(px, pz))
+            })
+            .fold((x, z, f32::INFINITY), |best, c| if c.2 < best.2 { c } else { best });
+        if ny >= y {
+            lake = Some(Lake {
+                x,
+                z,
+                radius: cfg.lake_radius,
+                level: y,
+            });
+            break;
+        }
+        (x, z, y) = (nx, nz, ny);
+    }
+    RiverPath { points, lake }
+}
+
+/// Carved bed and water heights for a grid of chunk columns.
+pub struct ChannelMap {
+    min_x: f32,
+    min_z: f32,
+    cell: f32,
+    nx: usize,
+    nz: usize,
+    bed: Vec<f32>,
+    water: Vec<f32>,
+}
+
+impl ChannelMap {
+    /// Empty map; column (ix, iz) is centered at `min + (i + 0.5) * cell`.
+    pub fn new(min_x: f32, min_z: f32, cell: f32, nx: usize, nz: usize) -> Self {
+        Self {
+            min_x,
+            min_z,
+            cell,
+            nx,
+            nz,
+            bed: vec![f32::INFINITY; nx * nz],
+            water: vec![f32::NEG_INFINITY; nx * nz],
+        }
+    }
+
+    /// Lowered ground top for a column, if a channel or lake passes through it.
+    pub fn bed(&self, ix: usize, iz: usize) -> Option<f32> {
+        let b = self.bed[ix + self.nx * iz];
+        b.is_finite().then_some(b)
+    }
+
+    /// Water surface for a column, if a channel or lake passes through it.
+    pub fn water(&self, ix: usize, iz: usize) -> Option<f32> {
+        let w = self.water[ix + self.nx * iz];
+        w.is_finite().then_some(w)
+    }
+
+    /// Stamp a round dip at (x, z): bed `depth` below `surface` at the center, shallower at the rim.
+    fn stamp(&mut self, x: f32, z: f32, radius: f32, surface: f32, depth: f32) {
+        let ix0 = ((x - radius - self.min_x) / self.cell).floor().max(0.0) as usize;
+        let iz0 = ((z - radius - self.min_z) / self.cell).floor().max(0.0) as usize;
+        let ix1 = ((x + radius - self.min_x) / self.cell).ceil().min(self.nx as f32);
+        let iz1 = ((z + radius - self.min_z) / self.cell).ceil().min(self.nz as f32);
+        if ix1 <= 0.0 || iz1 <= 0.0 {
+            return;
+        }
+        for iz in iz0..iz1 as usize {
+            for ix in ix0..ix1 as usize {
+                let cx = self.min_x + (ix as f32 + 0.5) * self.cell;
+                let cz = self.min_z + (iz as f32 + 0.5) * self.cell;
+                let d2 = ((cx - x).powi(2) + (cz - z).powi(2)) / (radius * radius);
+                if d2 > 1.0 {
+                    continue;
+                }
+                let i = ix + self.nx * iz;
+                self.bed[i] = self.bed[i].min(surface + 1.0 - (depth + 1.0) * (1.0 - d2 * 0.6));
+                self.water[i] = self.water[i].max(surface);
+            }
+        }
+    }
+
+    /// Carve a traced river (and its lake) into the map.
+    pub fn add_path(&mut self, cfg: &RiverConfig, path: &RiverPath) {
+        for p in &path.points {
+            // Water sits a block below the banks
+            self.stamp(p.x, p.z, p.half_width, p.ground_y - 1.0, cfg.depth - 1.0);
+        }
+        if let Some(lake) = path.lake {
+            self.stamp(lake.x, lake.z, lake.radius, lake.level, cfg.lake_depth);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bed.iter().all(|b| !b.is_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_descends_to_sea_or_lake() {
+        let cfg = RiverConfig::default();
+        // Plane sloping down towards +x reaches the sea without a lake
+        let slope = trace_river(&cfg, (0.0, 0.0), 10.0, |x, _| 50.0 - x * 0.2);
+        assert!(slope.lake.is_none());
+        assert!(slope.points.windows(2).all(|w| w[1].ground_y < w[0].ground_y));
+        assert!(slope.points.last().unwrap().ground_y <= 10.0);
+
+        // Bowl centered at (40, 0) traps the river
+        let bowl = trace_river(&cfg, (0.0, 0.0), 0.0, |x, z| 20.0 + ((x - 40.0).powi(2) + z * z) * 0.01);
+        let lake = bowl.lake.expect("basin should form a lake");
+        assert!((lake.x - 40.0).abs() <= cfg.step && lake.z.abs() <= cfg.step);
+    }
+
+    #[test]
+    fn sources_and_carve_are_deterministic() {
+        let cfg = RiverConfig {
+            source_chance: 1.0,
+            ..Default::default()
+        };
+        let a = river_sources(&cfg, 5, (-300.0, -300.0), (300.0, 300.0));
+        assert_eq!(a, river_sources(&cfg, 5, (-300.0, -300.0), (300.0, 300.0)));
+        assert_eq!(a.len(), 36);
+
+        let path = trace_river(&cfg, (8.0, 8.0), 0.0, |x, _| 40.0 - x * 0.1);
+        let mut map = ChannelMap::new(0.0, 0.0, 1.0, 16, 16);
+        map.add_path(&cfg, &path);
+        let bed = map.bed(8, 8).expect("source column is carved");
+        assert!(bed < 40.0 - 1.0 && map.water(8, 8).unwrap() > bed);
+        assert!(map.bed(8, 0).is_none());
+    }
+}
@@ -125,6 +125,110 @@ impl Star {
     }
 }
 
+/// Seed offset for ring and belt generation, kept off the main system RNG so adding
+/// debris doesn't reshuffle existing planets.
+const DEBRIS_SEED_SALT: u64 = 0x5249_4e47_4245_4c54;
+
+/// One piece of ring or belt debris: position (relative to the ring's planet, or to the
+/// star for belts) and radius, both in game units.
+#[derive(Debug, Clone, Copy)]
+pub struct DebrisParticle {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+/// Planetary ring system, drawn as instanced debris around the planet.
+#[derive(Debug, Clone)]
+pub struct Ring {
+    /// Inner and outer radius from the planet center, in game units.
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Tilt of the ring plane away from the orbital plane (radians, about X).
+    pub tilt: f32,
+    pub particle_count: u32,
+    /// Tint for the debris.
+    pub color: Vec3,
+    pub seed: u64,
+}
+
+impl Ring {
+    /// Ring for a planet of `planet_radius`, deterministic from the planet seed.
+    pub fn generate(planet_seed: u64, planet_radius: f32) -> Self {
+        let mut rng = StdRng::seed_from_u64(planet_seed ^ DEBRIS_SEED_SALT);
+        let inner = 1.3 + rng.gen::<f32>() * 0.3;
+        let outer = inner + 0.6 + rng.gen::<f32>() * 0.9;
+        let shade = 0.55 + rng.gen::<f32>() * 0.3;
+        Self {
+            inner_radius: planet_radius * inner,
+            outer_radius: planet_radius * outer,
+            tilt: (rng.gen::<f32>() - 0.5) * 0.6,
+            particle_count: rng.gen_range(180..=360),
+            color: Vec3::new(shade, shade * 0.92, shade * 0.8),
+            seed: rng.gen(),
+        }
+    }
+
+    /// Debris positions relative to the planet center; identical on every call.
+    pub fn particles(&self) -> Vec<DebrisParticle> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let (sin_t, cos_t) = self.tilt.sin_cos();
+        let width = self.outer_radius - self.inner_radius;
+        (0..self.particle_count)
+            .map(|_| {
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                let r = self.inner_radius + width * rng.gen::<f32>();
+                let y = (rng.gen::<f32>() - 0.5) * width * 0.02;
+                let (x, z) = (angle.cos() * r, angle.sin() * r);
+                DebrisParticle {
+                    position: Vec3::new(x, y * cos_t - z * sin_t, y * sin_t + z * cos_t),
+                    radius: width * (0.004 + rng.gen::<f32>() * 0.008),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Asteroid belt circling the star between two planet orbits.
+#[derive(Debug, Clone)]
+pub struct AsteroidBelt {
+    /// Inner and outer radius from the star, in game units.
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Vertical spread of the belt (total height).
+    pub thickness: f32,
+    /// Tilt of the belt plane (radians, about X).
+    pub tilt: f32,
+    pub particle_count: u32,
+    /// Mean motion (radians per second) at the belt's middle radius.
+    pub orbital_speed: f32,
+    pub seed: u64,
+}
+
+impl AsteroidBelt {
+    /// Debris positions relative to the star at `time`. The whole belt turns at the
+    /// mid-radius orbital speed.
+    pub fn particles(&self, time: f64) -> Vec<DebrisParticle> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let spin = (time * self.orbital_speed as f64).rem_euclid(std::f64::consts::TAU) as f32;
+        let (sin_t, cos_t) = self.tilt.sin_cos();
+        let width = self.outer_radius - self.inner_radius;
+        (0..self.particle_count)
+            .map(|_| {
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU + spin;
+                // Denser toward the middle of the belt
+                let t = (rng.gen::<f32>() + rng.gen::<f32>()) * 0.5;
+                let r = self.inner_radius + width * t;
+                let y = (rng.gen::<f32>() - 0.5) * self.thickness;
+                let (x, z) = (angle.cos() * r, angle.sin() * r);
+                DebrisParticle {
+                    position: Vec3::new(x, y * cos_t - z * sin_t, y * sin_t + z * cos_t),
+                    radius: 15.0 + rng.gen::<f32>().powi(3) * 90.0,
+                }
+            })
+            .collect()
+    }
+}
+
 /// An orbital body (planet or moon) in a star system.
 #[derive(Debug, Clone)]
 pub struct OrbitalBody {
@@ -148,8 +252,8 @@ pub struct OrbitalBody {
     pub axial_tilt: f32,
     /// Child moons.
     pub moons: Vec<OrbitalBody>,
-    /// Whether this body has a ring system (visual only).
-    pub ring_system: bool,
+    /// Ring system (visual only).
+    pub ring: Option<Ring>,
 }
 
 impl OrbitalBody {
@@ -225,6 +329,8 @@ pub struct StarSystem {
     pub star: Star,
     pub bodies: Vec<OrbitalBody>,
     pub galaxy_position: DVec3,
+    /// Asteroid belt in the widest gap between planet orbits, if any.
+    pub asteroid_belt: Option<AsteroidBelt>,
 }
 
 impl StarSystem {
@@ -300,12 +406,18 @@ impl StarSystem {
                     orbital_longitude: rng.gen::<f32>() * std::f32::consts::TAU,
                     axial_tilt: rng.gen::<f32>() * 0.3,
                     moons: Vec::new(),
-                    ring_system: false,
+                    ring: None,
                 });
             }
 
-            let ring_system = matches!(planet.size, PlanetSize::Large | PlanetSize::Massive)
-                && rng.gen_bool(0.15);
+            // Gas-giant-class worlds are the ones that get rings
+            let ring_chance = match planet.size {
+                PlanetSize::Massive => 0.45,
+                PlanetSize::Large => 0.15,
+                _ => 0.0,
+            };
+            let ring = (ring_chance > 0.0 && rng.gen_bool(ring_chance))
+                .then(|| Ring::generate(planet_seed, planet.visual_radius()));
 
             bodies.push(OrbitalBody {
                 planet,
@@ -318,10 +430,12 @@ impl StarSystem {
                 orbital_longitude,
                 axial_tilt,
                 moons,
-                ring_system,
+                ring,
             });
         }
 
+        let asteroid_belt = Self::generate_belt(seed, &bodies, star.mass);
+
         // System name from star
         let system_name = format!("{} System", star.name);
 
@@ -338,9 +452,38 @@ impl StarSystem {
             star,
             bodies,
             galaxy_position,
+            asteroid_belt,
         }
     }
 
+    /// Place a belt in the widest gap between neighbouring orbits (60% of systems).
+    fn generate_belt(seed: u64, bodies: &[OrbitalBody], star_mass: f32) -> Option<AsteroidBelt> {
+        let mut rng = StdRng::seed_from_u64(seed ^ DEBRIS_SEED_SALT);
+        if bodies.len() < 2 || !rng.gen_bool(0.6) {
+            return None;
+        }
+        let mut orbits: Vec<f32> = bodies.iter().map(|b| b.orbital_radius).collect();
+        orbits.sort_by(|a, b| a.total_cmp(b));
+        let (inner_orbit, outer_orbit) = orbits
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+            .max_by(|a, b| (a.1 - a.0).total_cmp(&(b.1 - b.0)))?;
+        let gap = outer_orbit - inner_orbit;
+        let middle = inner_orbit + gap * (0.4 + rng.gen::<f32>() * 0.2);
+        let half_width = gap * (0.08 + rng.gen::<f32>() * 0.08);
+        let mu = GRAVITATIONAL_PARAM * star_mass as f64;
+        let a = middle as f64;
+        Some(AsteroidBelt {
+            inner_radius: middle - half_width,
+            outer_radius: middle + half_width,
+            thickness: half_width * 0.15,
+            tilt: rng.gen::<f32>() * 0.2,
+            particle_count: rng.gen_range(600..=1200),
+            orbital_speed: (mu / (a * a * a)).sqrt() as f32,
+            seed: rng.gen(),
+        })
+    }
+
     /// Get all orbital body positions at a given time. Returns (planet_idx, position).
     pub fn body_positions(&self, time: f64) -> Vec<(usize, DVec3)> {
         self.bodies
@@ -363,3 +506,30 @@ impl StarSystem {
             .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rings and belts come out identical for the same seed, so they are stable across loads.
+    #[test]
+    fn debris_is_deterministic() {
+        let ring = Ring::generate(99, 500.0);
+        let (a, b) = (ring.particles(), Ring::generate(99, 500.0).particles());
+        assert_eq!(a.len(), ring.particle_count as usize);
+        for (pa, pb) in a.iter().zip(&b) {
+            assert_eq!(pa.position, pb.position);
+            let r = pa.position.length();
+            assert!(r >= ring.inner_radius * 0.99 && r <= ring.outer_radius * 1.01);
+        }
+
+        let system = (0..64)
+            .map(StarSystem::generate)
+            .find(|s| s.asteroid_belt.is_some())
+            .expect("some system should have a belt");
+        let belt = system.asteroid_belt.as_ref().unwrap();
+        let again = StarSystem::generate(system.seed);
+        assert_eq!(belt.particles(10.0)[5].position, again.asteroid_belt.unwrap().particles(10.0)[5].position);
+        assert!(system.bodies.iter().all(|b| b.orbital_radius < belt.inner_radius || b.orbital_radius > belt.outer_radius));
+    }
+}
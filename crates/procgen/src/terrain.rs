@@ -10,6 +10,7 @@ use glam::Vec3;
 use noise::{NoiseFn, Perlin, Simplex};
 
 use crate::biome::PlanetBiomes;
+use crate::river::RiverConfig;
 
 /// Derive a deterministic u32 noise seed from a world seed and an offset.
 /// Same (seed, offset) always gives the same result so terrain is reproducible.
@@ -63,6 +64,11 @@ pub struct TerrainConfig {
     /// Voxel size for Castle Miner Z–style blocky terrain. Heights are quantized to this grid.
     /// e.g. 1.0 = 1m blocks. None = smooth terrain (no quantization).
     pub voxel_size: Option<f32>,
+    /// Rivers traced downhill from high ground, ending in the sea or in basin lakes.
+    /// Only carved when `water_level` is set.
+    pub rivers: Option<RiverConfig>,
+    /// Carve 3D-noise caves and cliff overhangs into voxel terrain.
+    pub generate_caves: bool,
 }
 
 impl Default for TerrainConfig {
@@ -81,6 +87,8 @@ impl Default for TerrainConfig {
             water_level: Some(0.35), // Minecraft-style: sea level so valleys are below, peaks above
             water_coverage: 0.45,
             voxel_size: Some(1.0),   // Castle Miner Z style: 1m blocky terrain
+            rivers: Some(RiverConfig::default()),
+            generate_caves: true,
         }
     }
 }
@@ -497,9 +505,14 @@ impl TerrainData {
     /// Sample normalized terrain height at world (x, z) for voxel generation.
     /// Returns value in [-0.25, 1.25] (same as fractal_noise; sea level ~0.35).
     pub fn sample_height_for_voxel(config: &TerrainConfig, x: f64, z: f64) -> f64 {
+        Self::voxel_height_sampler(config)(x, z)
+    }
+
+    /// Same as `sample_height_for_voxel`, but builds the noise once for many samples.
+    pub fn voxel_height_sampler(config: &TerrainConfig) -> impl Fn(f64, f64) -> f64 + '_ {
         let perlin = Perlin::new(deterministic_noise_seed(config.seed, 0));
         let simplex = Simplex::new(deterministic_noise_seed(config.seed, 1));
-        Self::fractal_noise(&perlin, &simplex, x, z, config)
+        move |x, z| Self::fractal_noise(&perlin, &simplex, x, z, config)
     }
 
     /// Check if a world position is within this chunk's bounds.
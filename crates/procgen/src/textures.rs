@@ -263,6 +263,130 @@ impl TextureGenerator {
         }
     }
 
+    /// Generate tileable detail layers for the voxel terrain, one per `TerrainMaterial` in
+    /// `TerrainMaterial::ALL` order. Layers are near-greyscale and average mid-grey: the terrain
+    /// shader multiplies them into the block color, so the block color stays the tint.
+    pub fn generate_terrain_materials(&mut self, size: u32) -> Vec<TextureData> {
+        TerrainMaterial::ALL
+            .iter()
+            .map(|&material| {
+                let mut values = Vec::with_capacity((size * size) as usize);
+                for y in 0..size {
+                    for x in 0..size {
+                        let u = x as f64 / size as f64;
+                        let v = y as f64 / size as f64;
+                        values.push(self.material_value(material, u, v));
+                    }
+                }
+                let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+                let (contrast, warmth) = material.detail_contrast();
+                let mut texture = TextureData::new(size, size);
+                for (pixel, value) in texture.pixels.iter_mut().zip(values) {
+                    let d = ((value - mean) * contrast) as f32;
+                    *pixel = Pixel::from_rgb(0.5 + d * (1.0 + warmth), 0.5 + d, 0.5 + d * (1.0 - warmth));
+                }
+                texture
+            })
+            .collect()
+    }
+
+    /// Raw (un-normalized) detail value of `material` at tile coordinates (u, v) in 0..1.
+    fn material_value(&self, material: TerrainMaterial, u: f64, v: f64) -> f64 {
+        match material {
+            // Ridged cracks over blotchy stone
+            TerrainMaterial::Rock => {
+                let blotch = self.tiled_fbm(u, v, 3.0, 4);
+                let ridge = 1.0 - (self.tiled_fbm(u, v, 6.0, 3) - 0.5).abs() * 2.0;
+                blotch - self.smooth_step(0.85, 0.97, ridge) * 0.35
+            }
+            // Clods with scattered pebbles
+            TerrainMaterial::Dirt => {
+                let clods = self.tiled_fbm(u, v, 5.0, 4);
+                let pebbles = self.smooth_step(0.68, 0.74, self.tiled_fbm(u, v, 24.0, 2));
+                clods + pebbles * 0.25
+            }
+            // Wind ripples bent by noise, plus fine grain
+            TerrainMaterial::Sand => {
+                let warp = self.tiled_fbm(u, v, 2.0, 2);
+                let ripple = (std::f64::consts::TAU * (v * 10.0 + warp * 1.5)).sin() * 0.5 + 0.5;
+                ripple * 0.6 + self.tiled_fbm(u, v, 40.0, 2) * 0.4
+            }
+            // Soft drifts with a little sparkle
+            TerrainMaterial::Snow => {
+                let drifts = self.tiled_fbm(u, v, 3.0, 3);
+                let sparkle = self.smooth_step(0.75, 0.8, self.tiled_fbm(u, v, 48.0, 1));
+                drifts + sparkle * 0.3
+            }
+            // Blade streaks (stretched noise) over patchy turf
+            TerrainMaterial::Grass => {
+                let patches = self.tiled_fbm(u, v, 4.0, 3);
+                let blades = self.tiled_fbm_aniso(u, v, 48.0, 8.0, 2);
+                patches * 0.5 + blades * 0.5
+            }
+        }
+    }
+
+    /// Generate a tileable tangent-space normal map of armor / hull plating: `panels` x `panels`
+    /// plates with recessed seams, rivets at the corners and fine brushed streaks.
+    pub fn generate_plate_normals(&mut self, size: u32, panels: u32) -> TextureData {
+        let panels = panels.max(1) as f64;
+        let mut heights = Vec::with_capacity((size * size) as usize);
+        for y in 0..size {
+            for x in 0..size {
+                let u = x as f64 / size as f64;
+                let v = y as f64 / size as f64;
+                // Position inside the plate, 0..1
+                let (pu, pv) = ((u * panels).fract(), (v * panels).fract());
+                let edge = pu.min(1.0 - pu).min(pv).min(1.0 - pv);
+                let seam = 1.0 - self.smooth_step(0.0, 0.035, edge);
+                let corner = (pu.min(1.0 - pu) - 0.08).hypot(pv.min(1.0 - pv) - 0.08);
+                let rivet = 1.0 - self.smooth_step(0.02, 0.035, corner);
+                let brushed = self.tiled_fbm_aniso(u, v, 96.0, 4.0, 2);
+                let dents = self.tiled_fbm(u, v, 6.0, 3);
+                heights.push(-seam * 0.6 + rivet * 0.4 + brushed * 0.05 + dents * 0.08);
+            }
+        }
+
+        let mut texture = TextureData::new(size, size);
+        let at = |x: i64, y: i64| {
+            let n = size as i64;
+            heights[(y.rem_euclid(n) * n + x.rem_euclid(n)) as usize]
+        };
+        let strength = size as f64 / 64.0;
+        for y in 0..size as i64 {
+            for x in 0..size as i64 {
+                let dx = (at(x + 1, y) - at(x - 1, y)) * strength;
+                let dy = (at(x, y + 1) - at(x, y - 1)) * strength;
+                let n = Vec3::new(-dx as f32, -dy as f32, 1.0).normalize() * 0.5 + Vec3::splat(0.5);
+                texture.set_pixel(x as u32, y as u32, Pixel::from_rgb(n.x, n.y, n.z));
+            }
+        }
+        texture
+    }
+
+    /// Fractal noise that wraps at u, v = 0 and 1: each axis walks a circle in 4D noise space.
+    fn tiled_fbm(&self, u: f64, v: f64, frequency: f64, octaves: u32) -> f64 {
+        self.tiled_fbm_aniso(u, v, frequency, frequency, octaves)
+    }
+
+    /// `tiled_fbm` with separate frequencies along u and v (streaks when they differ).
+    fn tiled_fbm_aniso(&self, u: f64, v: f64, freq_u: f64, freq_v: f64, octaves: u32) -> f64 {
+        let (su, cu) = (u * std::f64::consts::TAU).sin_cos();
+        let (sv, cv) = (v * std::f64::consts::TAU).sin_cos();
+        let mut value = 0.0;
+        let mut amplitude = 0.5;
+        let mut scale = 1.0 / std::f64::consts::TAU;
+        for octave in 0..octaves {
+            let (ru, rv) = (freq_u * scale, freq_v * scale);
+            let offset = octave as f64 * 17.3;
+            let n = self.perlin.get([cu * ru + offset, su * ru, cv * rv - offset, sv * rv]);
+            value += amplitude * (n * 0.5 + 0.5);
+            amplitude *= 0.5;
+            scale *= 2.0;
+        }
+        value
+    }
+
     /// Generate ichor/gore texture
     pub fn generate_gore(&mut self, width: u32, height: u32) -> TextureData {
         let mut texture = TextureData::new(width, height);
@@ -466,6 +590,43 @@ impl CarapaceConfig {
     }
 }
 
+/// Surface materials of the voxel terrain; each is one layer of the terrain material texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerrainMaterial {
+    Rock = 0,
+    Dirt = 1,
+    Sand = 2,
+    Snow = 3,
+    Grass = 4,
+}
+
+impl TerrainMaterial {
+    /// Every material, in texture layer order.
+    pub const ALL: [TerrainMaterial; 5] = [
+        TerrainMaterial::Rock,
+        TerrainMaterial::Dirt,
+        TerrainMaterial::Sand,
+        TerrainMaterial::Snow,
+        TerrainMaterial::Grass,
+    ];
+
+    /// Texture layer index (also what the voxel mesh writes into the vertex uv.x).
+    pub fn layer(self) -> u32 {
+        self as u32
+    }
+
+    /// (contrast, warmth) applied when normalizing the layer around mid-grey.
+    fn detail_contrast(self) -> (f64, f32) {
+        match self {
+            TerrainMaterial::Rock => (1.6, 0.0),
+            TerrainMaterial::Dirt => (1.5, 0.08),
+            TerrainMaterial::Sand => (0.9, 0.04),
+            TerrainMaterial::Snow => (0.6, -0.04),
+            TerrainMaterial::Grass => (1.4, 0.0),
+        }
+    }
+}
+
 /// Configuration for terrain texture generation
 #[derive(Debug, Clone)]
 pub struct TerrainTextureConfig {
@@ -481,3 +642,45 @@ impl Default for TerrainTextureConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean(texture: &TextureData) -> f32 {
+        texture.pixels.iter().map(|p| p.g as f32).sum::<f32>() / texture.pixels.len() as f32
+    }
+
+    #[test]
+    fn terrain_materials_average_mid_grey_and_tile_seamlessly() {
+        let size = 32;
+        let layers = TextureGenerator::new(7).generate_terrain_materials(size);
+        assert_eq!(layers.len(), TerrainMaterial::ALL.len());
+        for layer in &layers {
+            assert!((mean(layer) - 127.5).abs() < 6.0, "layer mean {}", mean(layer));
+            // Wrapping from the last column to the first is no bigger a step than within the tile
+            let step = |a: u32, b: u32| {
+                (0..size)
+                    .map(|y| (layer.get_pixel(a, y).g as f32 - layer.get_pixel(b, y).g as f32).abs())
+                    .sum::<f32>()
+                    / size as f32
+            };
+            let inner = (1..size).map(|x| step(x - 1, x)).sum::<f32>() / (size - 1) as f32;
+            assert!(step(size - 1, 0) <= inner * 2.5 + 2.0);
+        }
+        assert_ne!(layers[TerrainMaterial::Rock as usize].to_bytes(), layers[TerrainMaterial::Sand as usize].to_bytes());
+    }
+
+    #[test]
+    fn plate_normals_point_out_of_the_surface_and_dip_at_seams() {
+        let texture = TextureGenerator::new(3).generate_plate_normals(64, 2);
+        // Mostly facing straight out (blue), tilted only around seams and rivets
+        assert!(texture.pixels.iter().all(|p| p.b >= 128));
+        let flat = texture.pixels.iter().filter(|p| p.r.abs_diff(128) < 12 && p.g.abs_diff(128) < 12).count();
+        assert!(flat > texture.pixels.len() / 2);
+        // Either side of the seam between plates (x = 32) tilts in opposite directions
+        let left = texture.get_pixel(30, 16).r as i32 - 128;
+        let right = texture.get_pixel(33, 16).r as i32 - 128;
+        assert!(left * right < 0, "left {} right {}", left, right);
+    }
+}
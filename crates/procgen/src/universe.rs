@@ -3,6 +3,11 @@
 //! Simulates a disc galaxy: spiral arm structure, radial density falloff,
 //! and thin disc (height spread). Systems are placed in 3D for a realistic
 //! star map and warp distances.
+//!
+//! **Save compatibility:** saves store only the universe seed, so generation must stay
+//! byte-for-byte stable. `Universe::digest` fingerprints the generated content and a test
+//! pins it for a fixed seed; if a change to generation moves the digest, bump the save
+//! version and update the pinned value.
 
 use crate::planet::Planet;
 use crate::star_system::{StarSystem, StarType, Star};
@@ -104,6 +109,33 @@ impl Universe {
         }
     }
 
+    /// Stable fingerprint of everything a save depends on: system names, galaxy positions,
+    /// star types, and each system's planets (seed, name, classification, biomes).
+    /// Uses FNV-1a rather than `std` hashing, whose output may change between Rust releases.
+    pub fn digest(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash.write(&self.seed.to_le_bytes());
+        for entry in &self.systems {
+            hash.write(entry.name.as_bytes());
+            // Quantized so float formatting noise can't change the digest
+            for c in entry.position.to_array() {
+                hash.write(&((c * 1000.0).round() as i64).to_le_bytes());
+            }
+            hash.write(format!("{:?}", entry.star_type).as_bytes());
+            let system = StarSystem::generate(entry.seed);
+            hash.write(&(system.bodies.len() as u64).to_le_bytes());
+            for body in &system.bodies {
+                let planet = &body.planet;
+                hash.write(&planet.seed.to_le_bytes());
+                hash.write(planet.name.as_bytes());
+                hash.write(format!("{:?}", planet.classification).as_bytes());
+                hash.write(format!("{:?}", planet.primary_biome).as_bytes());
+                hash.write(format!("{:?}", planet.secondary_biome).as_bytes());
+            }
+        }
+        hash.finish()
+    }
+
     /// Find the system entry nearest to a galaxy-space position.
     pub fn nearest_system(&self, pos: DVec3) -> Option<(usize, f64)> {
         self.systems
@@ -133,6 +165,28 @@ impl Universe {
     }
 }
 
+/// 64-bit FNV-1a, for digests that must not change between builds.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        // Field separator so ("ab", "c") and ("a", "bc") differ
+        self.0 = (self.0 ^ 0xff).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Generate a system name (Heinlein / Federation / Helldivers 2 style).
 fn generate_system_name(seed: u64) -> String {
     let mut rng = StdRng::seed_from_u64(seed.wrapping_add(42));
@@ -211,4 +265,13 @@ mod tests {
         let names2: Vec<_> = u2.systems.iter().map(|s| s.name.as_str()).collect();
         assert_ne!(names1, names2);
     }
+
+    /// Pinned fingerprint of a fixed seed. If this fails, generation changed and existing
+    /// saves would load a different galaxy: bump the save version and update the value.
+    #[test]
+    fn universe_digest_is_pinned() {
+        let universe = Universe::generate(20240601, 24);
+        assert_eq!(universe.digest(), Universe::generate(20240601, 24).digest());
+        assert_eq!(universe.digest(), 11952152973720534395);
+    }
 }
@@ -2,12 +2,21 @@
 //!
 //! Chunks are 3D grids of blocks. Generation uses the same noise as heightfield terrain
 //! to get height at (x,z), then fills columns with Stone, Dirt, Grass/Sand/Snow.
-//! Minecraft-style caves are carved underground on every planet (denser on HiveWorld/Fungal).
+//! With `TerrainConfig::generate_caves`, 3D noise carves Minecraft-style caves underground
+//! (denser on HiveWorld/Fungal) and overhangs into exposed cliff faces.
 //! Mesh is built from culled cube faces; physics uses a heightfield derived from voxel tops.
+//! Blocks changed after generation are recorded in `VoxelChunk::edits`, so a deformed chunk can
+//! be regenerated from seed and have its edits replayed.
 
 use crate::biome::{BiomeType, PlanetBiomes};
+use crate::river::{river_sources, trace_river, ChannelMap};
 use crate::terrain::{TerrainConfig, TerrainData, TerrainVertex};
+use crate::textures::TerrainMaterial;
 use noise::{NoiseFn, Perlin};
+use std::collections::HashMap;
+
+/// Blocks changed since generation, keyed by cell index (`VoxelChunk::index`).
+pub type BlockEdits = HashMap<usize, BlockId>;
 
 /// Block type for voxel terrain (Minecraft/Ace of Spades style).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +43,17 @@ impl BlockId {
         !matches!(self, BlockId::Air)
     }
 
+    /// Detail texture the terrain shader tiles over this block (its vertex color is the tint).
+    pub fn material(self) -> TerrainMaterial {
+        match self {
+            BlockId::Air | BlockId::Stone | BlockId::Bedrock => TerrainMaterial::Rock,
+            BlockId::Dirt => TerrainMaterial::Dirt,
+            BlockId::Grass => TerrainMaterial::Grass,
+            BlockId::Sand | BlockId::Water => TerrainMaterial::Sand,
+            BlockId::Snow => TerrainMaterial::Snow,
+        }
+    }
+
     /// Vertex color for terrain shader (RGBA).
     pub fn color(self) -> [f32; 4] {
         match self {
@@ -56,6 +76,10 @@ const DIRT_LAYERS: usize = 3;
 const MIN_TERRAIN_WORLD_Y: f32 = 24.0; // 24 blocks at 1m (Minecraft Steve scale)
 /// When filling water after deform: only fill air if solid ground is within this many blocks below (avoids water-over-cave deadfall pits).
 const WATER_FILL_BUFFER: usize = 6;
+/// A column must stand this many blocks above a neighbour before its face gets overhangs.
+const OVERHANG_MIN_DROP: usize = 4;
+/// Solid blocks kept at the top of an overhanging face.
+const OVERHANG_LIP: usize = 2;
 
 /// Deterministic noise seed from world seed (same formula as terrain/biome for reproducibility).
 #[inline]
@@ -66,6 +90,93 @@ fn cave_noise_seed(seed: u64, offset: u64) -> u32 {
         >> 32) as u32
 }
 
+/// Deterministic value in [0, 1) for the block column containing world (x, z).
+#[inline]
+fn column_hash(seed: u64, x: f32, z: f32) -> f32 {
+    let h = seed
+        ^ (x.floor() as i64 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (z.floor() as i64 as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    let h = (h ^ (h >> 31)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    ((h >> 40) & 0xffff) as f32 / 65536.0
+}
+
+/// A side of a chunk, shared with the neighbouring chunk in that direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkEdge {
+    MinX,
+    MaxX,
+    MinZ,
+    MaxZ,
+}
+
+impl ChunkEdge {
+    pub const ALL: [ChunkEdge; 4] = [ChunkEdge::MinX, ChunkEdge::MaxX, ChunkEdge::MinZ, ChunkEdge::MaxZ];
+
+    /// The same boundary as seen from the neighbouring chunk.
+    pub fn opposite(self) -> Self {
+        match self {
+            ChunkEdge::MinX => ChunkEdge::MaxX,
+            ChunkEdge::MaxX => ChunkEdge::MinX,
+            ChunkEdge::MinZ => ChunkEdge::MaxZ,
+            ChunkEdge::MaxZ => ChunkEdge::MinZ,
+        }
+    }
+
+    /// Chunk-grid offset (dx, dz) of the neighbour across this edge.
+    pub fn neighbor_offset(self) -> (i32, i32) {
+        match self {
+            ChunkEdge::MinX => (-1, 0),
+            ChunkEdge::MaxX => (1, 0),
+            ChunkEdge::MinZ => (0, -1),
+            ChunkEdge::MaxZ => (0, 1),
+        }
+    }
+}
+
+/// A corner column of a chunk, shared with the three chunks that meet there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkCorner {
+    MinXMinZ,
+    MaxXMinZ,
+    MinXMaxZ,
+    MaxXMaxZ,
+}
+
+impl ChunkCorner {
+    pub const ALL: [ChunkCorner; 4] =
+        [ChunkCorner::MinXMinZ, ChunkCorner::MaxXMinZ, ChunkCorner::MinXMaxZ, ChunkCorner::MaxXMaxZ];
+
+    /// The same corner as seen from the diagonal neighbour.
+    pub fn opposite(self) -> Self {
+        match self {
+            ChunkCorner::MinXMinZ => ChunkCorner::MaxXMaxZ,
+            ChunkCorner::MaxXMinZ => ChunkCorner::MinXMaxZ,
+            ChunkCorner::MinXMaxZ => ChunkCorner::MaxXMinZ,
+            ChunkCorner::MaxXMaxZ => ChunkCorner::MinXMinZ,
+        }
+    }
+
+    /// Chunk-grid offset (dx, dz) of the diagonal neighbour across this corner.
+    pub fn neighbor_offset(self) -> (i32, i32) {
+        match self {
+            ChunkCorner::MinXMinZ => (-1, -1),
+            ChunkCorner::MaxXMinZ => (1, -1),
+            ChunkCorner::MinXMaxZ => (-1, 1),
+            ChunkCorner::MaxXMaxZ => (1, 1),
+        }
+    }
+
+    /// The X edge this corner lies on, and whether it is that edge's last column.
+    fn x_edge(self) -> (ChunkEdge, bool) {
+        match self {
+            ChunkCorner::MinXMinZ => (ChunkEdge::MinX, false),
+            ChunkCorner::MaxXMinZ => (ChunkEdge::MaxX, false),
+            ChunkCorner::MinXMaxZ => (ChunkEdge::MinX, true),
+            ChunkCorner::MaxXMaxZ => (ChunkEdge::MaxX, true),
+        }
+    }
+}
+
 /// One chunk of voxel terrain. Block-aligned grid.
 /// Chunk spans [offset_x - size_x/2, offset_x + size_x/2] in X, same for Z, Y from 0 to size_y.
 #[derive(Debug, Clone)]
@@ -77,6 +188,8 @@ pub struct VoxelChunk {
     pub offset_x: f32,
     pub offset_z: f32,
     pub data: Vec<BlockId>,
+    /// Every block `set` has changed since generation (or `apply_edits`).
+    pub edits: BlockEdits,
 }
 
 impl VoxelChunk {
@@ -95,7 +208,20 @@ impl VoxelChunk {
     pub fn set(&mut self, ix: usize, iy: usize, iz: usize, block: BlockId) {
         if ix < self.nx && iy < self.ny && iz < self.nz {
             let i = self.index(ix, iy, iz);
-            self.data[i] = block;
+            if self.data[i] != block {
+                self.data[i] = block;
+                self.edits.insert(i, block);
+            }
+        }
+    }
+
+    /// Replay edits recorded on an earlier copy of this chunk (same config and seed).
+    pub fn apply_edits(&mut self, edits: &BlockEdits) {
+        for (&i, &block) in edits {
+            if let Some(cell) = self.data.get_mut(i) {
+                *cell = block;
+                self.edits.insert(i, block);
+            }
         }
     }
 
@@ -115,6 +241,20 @@ impl VoxelChunk {
         (iy as f32) * self.block_size
     }
 
+    /// World-space axis-aligned bounds (min, max) of the whole block grid.
+    pub fn bounds(&self) -> (glam::Vec3, glam::Vec3) {
+        let half_x = self.nx as f32 * self.block_size * 0.5;
+        let half_z = self.nz as f32 * self.block_size * 0.5;
+        (
+            glam::Vec3::new(self.offset_x - half_x, 0.0, self.offset_z - half_z),
+            glam::Vec3::new(
+                self.offset_x + half_x,
+                self.ny as f32 * self.block_size,
+                self.offset_z + half_z,
+            ),
+        )
+    }
+
     /// Generate voxel chunk from terrain config (same noise as heightfield terrain).
     pub fn generate(
         config: &TerrainConfig,
@@ -127,32 +267,80 @@ impl VoxelChunk {
         let ny = ((config.height_scale * 2.0) / block_size).ceil().max(64.0) as usize;
         let len = nx * ny * nz;
         let mut data = vec![BlockId::Air; len];
-        let mut top_block_y_col: Vec<usize> = vec![0; nx * nz];
+        // Column tops carry a one-column border from the neighbouring chunks (`px` x `pz`), so
+        // carving that looks at neighbours agrees on both sides of a chunk seam.
+        let (px, pz) = (nx + 2, nz + 2);
+        let mut top_block_y_col: Vec<usize> = vec![0; px * pz];
+        let mut channel_water_col: Vec<Option<usize>> = vec![None; px * pz];
 
         let has_water = config.water_level.is_some();
         let _water_level_norm = 0.35;
         // Baseline + variation: hills, mountains, plains (Minecraft-style). Sea level sits above baseline.
         let sea_level_world = config.water_level.map(|w| MIN_TERRAIN_WORLD_Y + w * config.height_scale);
 
+        let height = TerrainData::voxel_height_sampler(config);
+        let surface_y = |wx: f32, wz: f32| {
+            let norm = height(wx as f64, wz as f64);
+            let height_mult = planet_biomes
+                .map(|pb| pb.height_scale_at(wx as f64, wz as f64))
+                .unwrap_or(1.0);
+            // Additive baseline + amplified variation: plains, hills, mountains (Minecraft-style).
+            let variation = (norm as f32 * config.height_scale * height_mult).max(0.0) * 1.25;
+            variation + MIN_TERRAIN_WORLD_Y
+        };
+
+        // Rivers and lakes: re-trace every river that can reach this chunk so borders agree.
+        let min_x = config.offset_x - config.size * 0.5;
+        let min_z = config.offset_z - config.size * 0.5;
+        let mut channels = ChannelMap::new(min_x - block_size, min_z - block_size, block_size, px, pz);
+        if let (Some(rivers), Some(sea)) = (&config.rivers, sea_level_world) {
+            let reach = rivers.reach() + block_size;
+            let max_x = min_x + config.size;
+            let max_z = min_z + config.size;
+            let sources = river_sources(rivers, config.seed, (min_x - reach, min_z - reach), (max_x + reach, max_z + reach));
+            for source in sources {
+                if surface_y(source.0, source.1) < sea + rivers.min_source_height {
+                    continue;
+                }
+                let path = trace_river(rivers, source, sea, surface_y);
+                channels.add_path(rivers, &path);
+            }
+        }
+
+        for cz in 0..pz {
+            for cx in 0..px {
+                let wx = min_x + (cx as f32 - 0.5) * block_size;
+                let wz = min_z + (cz as f32 - 0.5) * block_size;
+                let world_y = surface_y(wx, wz);
+                let mut top_block_y = ((world_y / block_size).floor() as usize).min(ny.saturating_sub(1));
+                // Channel water never stands above the original ground
+                if let (Some(bed), Some(water)) = (channels.bed(cx, cz), channels.water(cx, cz)) {
+                    let bed_top = (bed.max(block_size * BEDROCK_LAYERS as f32) / block_size).floor() as usize;
+                    let water_top = ((water / block_size).floor() as usize).min(top_block_y);
+                    top_block_y = top_block_y.min(bed_top);
+                    channel_water_col[cx + px * cz] = Some(water_top);
+                }
+                top_block_y_col[cx + px * cz] = top_block_y;
+            }
+        }
+
         for iz in 0..nz {
             for ix in 0..nx {
-                let wx = config.offset_x - config.size * 0.5 + (ix as f32 + 0.5) * block_size;
-                let wz = config.offset_z - config.size * 0.5 + (iz as f32 + 0.5) * block_size;
-                let norm = TerrainData::sample_height_for_voxel(config, wx as f64, wz as f64);
-                let height_mult = planet_biomes
-                    .map(|pb| pb.height_scale_at(wx as f64, wz as f64))
-                    .unwrap_or(1.0);
-                // Additive baseline + amplified variation: plains, hills, mountains (Minecraft-style).
-                let variation = (norm as f32 * config.height_scale * height_mult).max(0.0) * 1.25;
-                let world_y = variation + MIN_TERRAIN_WORLD_Y;
-                let top_block_y = (world_y / block_size).floor() as usize;
-                let top_block_y = top_block_y.min(ny.saturating_sub(1));
-                top_block_y_col[ix + nx * iz] = top_block_y;
+                let wx = min_x + (ix as f32 + 0.5) * block_size;
+                let wz = min_z + (iz as f32 + 0.5) * block_size;
+                let top_block_y = top_block_y_col[(ix + 1) + px * (iz + 1)];
+                let channel_water_top = channel_water_col[(ix + 1) + px * (iz + 1)];
 
                 // Minecraft-style surface block from biome
                 let surface_block = if let Some(pb) = planet_biomes {
-                    let (biome_cfg, _) = pb.sample_at(wx as f64, wz as f64);
-                    match biome_cfg.biome_type {
+                    // Dither between neighbouring biomes so borders fade instead of switching in a line
+                    let blend = pb.blend_at(wx as f64, wz as f64);
+                    let biome = if column_hash(config.seed, wx, wz) < blend.weight {
+                        blend.secondary
+                    } else {
+                        blend.primary
+                    };
+                    match biome {
                         BiomeType::Frozen | BiomeType::Tundra => BlockId::Snow,
                         BiomeType::Desert | BiomeType::Wasteland | BiomeType::SaltFlat => BlockId::Sand,
                         _ => BlockId::Grass,
@@ -160,6 +348,8 @@ impl VoxelChunk {
                 } else {
                     BlockId::Grass
                 };
+                // Sandy river and lake beds
+                let surface_block = if channel_water_top.is_some() { BlockId::Sand } else { surface_block };
 
                 // Layers: bedrock (bottom) -> stone -> dirt -> surface (top)
                 let stone_start = top_block_y.saturating_sub(DIRT_LAYERS);
@@ -175,6 +365,9 @@ impl VoxelChunk {
                                 }
                             }
                         }
+                        if channel_water_top.is_some_and(|top| iy <= top) {
+                            data[idx] = BlockId::Water;
+                        }
                         continue;
                     }
                     if iy < BEDROCK_LAYERS {
@@ -190,6 +383,34 @@ impl VoxelChunk {
             }
         }
 
+        if config.generate_caves {
+            Self::carve_caves(&mut data, &top_block_y_col, (nx, ny, nz), block_size, config, planet_biomes, sea_level_world);
+        }
+
+        VoxelChunk {
+            nx,
+            ny,
+            nz,
+            block_size,
+            offset_x: config.offset_x,
+            offset_z: config.offset_z,
+            data,
+            edits: BlockEdits::new(),
+        }
+    }
+
+    /// Carve caves and cliff overhangs out of freshly filled columns. `top_block_y_col` holds
+    /// each column's surface block before carving, including the one-column border from the
+    /// neighbouring chunks (`(nx + 2) * (nz + 2)` entries).
+    fn carve_caves(
+        data: &mut [BlockId],
+        top_block_y_col: &[usize],
+        (nx, ny, nz): (usize, usize, usize),
+        block_size: f32,
+        config: &TerrainConfig,
+        planet_biomes: Option<&PlanetBiomes>,
+        sea_level_world: Option<f32>,
+    ) {
         // Minecraft-style caves: smaller tunnels, entrances only (never carve near surface), varied sizes.
         let (cave_scale, base_threshold) = if let Some(pb) = planet_biomes {
             let (biome_cfg, _) = pb.sample_at(config.offset_x as f64, config.offset_z as f64);
@@ -201,12 +422,15 @@ impl VoxelChunk {
             (0.032, 0.06)
         };
         const CAVE_SURFACE_BUFFER: usize = 12; // solid crust below surface/water so no deadfall pits; caves start deeper
+        let px = nx + 2;
+        // Indexed by bordered column: chunk column (ix, iz) is (ix + 1, iz + 1)
+        let top_at = |cx: usize, cz: usize| top_block_y_col[cx + px * cz];
         let cave_noise = Perlin::new(cave_noise_seed(config.seed, 10));
         let size_noise = Perlin::new(cave_noise_seed(config.seed, 11)); // varies tunnel size by area
         for iz in 0..nz {
             for iy in BEDROCK_LAYERS..ny {
                 for ix in 0..nx {
-                    let top_y = top_block_y_col[ix + nx * iz];
+                    let top_y = top_at(ix + 1, iz + 1);
                     if iy >= top_y {
                         continue;
                     }
@@ -240,14 +464,37 @@ impl VoxelChunk {
             }
         }
 
-        VoxelChunk {
-            nx,
-            ny,
-            nz,
-            block_size,
-            offset_x: config.offset_x,
-            offset_z: config.offset_z,
-            data,
+        // Overhangs: eat into cliff faces (columns towering over a neighbour) below a solid lip,
+        // so steep slopes get ledges and alcoves instead of sheer block walls.
+        let sea_top = sea_level_world.map_or(0, |sw| (sw / block_size).floor() as usize);
+        let overhang_noise = Perlin::new(cave_noise_seed(config.seed, 12));
+        for iz in 0..nz {
+            for ix in 0..nx {
+                let (cx, cz) = (ix + 1, iz + 1);
+                let top_y = top_at(cx, cz);
+                // Edge columns compare against the border, i.e. the adjacent chunk's columns
+                let lowest_neighbour = top_at(cx - 1, cz)
+                    .min(top_at(cx + 1, cz))
+                    .min(top_at(cx, cz - 1))
+                    .min(top_at(cx, cz + 1));
+                if lowest_neighbour + OVERHANG_MIN_DROP > top_y {
+                    continue;
+                }
+                let floor = lowest_neighbour.max(sea_top) + 1;
+                for iy in floor..top_y.saturating_sub(OVERHANG_LIP) {
+                    let idx = ix + nx * (iy + ny * iz);
+                    if !data[idx].is_solid() || data[idx] == BlockId::Bedrock {
+                        continue;
+                    }
+                    let wx = config.offset_x - config.size * 0.5 + (ix as f32 + 0.5) * block_size;
+                    let wy = (iy as f32 + 0.5) * block_size;
+                    let wz = config.offset_z - config.size * 0.5 + (iz as f32 + 0.5) * block_size;
+                    let n = overhang_noise.get([wx as f64 * 0.08, wy as f64 * 0.12, wz as f64 * 0.08]);
+                    if n > 0.2 {
+                        data[idx] = BlockId::Air;
+                    }
+                }
+            }
         }
     }
 
@@ -291,6 +538,8 @@ impl VoxelChunk {
                     let cy = self.world_y(iy) + half;
                     let cz = self.world_z(iz);
                     let color = b.color();
+                    // uv.x carries the material layer; the shader projects textures triplanar
+                    let material = b.material().layer() as f32;
 
                     let px = cx - half;
                     let py = cy - half;
@@ -306,7 +555,7 @@ impl VoxelChunk {
                             v.push(TerrainVertex {
                                 position: p,
                                 normal,
-                                uv: [0.0, 0.0],
+                                uv: [material, 0.0],
                                 color,
                             });
                         }
@@ -527,6 +776,103 @@ impl VoxelChunk {
         modified
     }
 
+    /// World Y of the top of the highest solid block in a column (0 if empty or out of bounds).
+    pub fn column_height(&self, ix: usize, iz: usize) -> f32 {
+        if ix >= self.nx || iz >= self.nz {
+            return 0.0;
+        }
+        for iy in (0..self.ny).rev() {
+            if self.get(ix, iy, iz).is_solid() {
+                return self.world_y(iy) + self.block_size;
+            }
+        }
+        0.0
+    }
+
+    /// Grid cell of the `i`-th column along an edge (ordered by increasing Z for X edges, X for Z edges).
+    fn edge_column(&self, edge: ChunkEdge, i: usize) -> (usize, usize) {
+        match edge {
+            ChunkEdge::MinX => (0, i),
+            ChunkEdge::MaxX => (self.nx - 1, i),
+            ChunkEdge::MinZ => (i, 0),
+            ChunkEdge::MaxZ => (i, self.nz - 1),
+        }
+    }
+
+    /// Column heights along an edge, in the same order on both sides of a shared boundary.
+    pub fn edge_heights(&self, edge: ChunkEdge) -> Vec<f32> {
+        let len = match edge {
+            ChunkEdge::MinX | ChunkEdge::MaxX => self.nz,
+            ChunkEdge::MinZ | ChunkEdge::MaxZ => self.nx,
+        };
+        (0..len)
+            .map(|i| {
+                let (ix, iz) = self.edge_column(edge, i);
+                self.column_height(ix, iz)
+            })
+            .collect()
+    }
+
+    /// Set the `i`-th edge column's surface height. Returns true if the column changed.
+    pub fn set_edge_height(&mut self, edge: ChunkEdge, i: usize, world_y_top: f32) -> bool {
+        let (ix, iz) = self.edge_column(edge, i);
+        if self.column_height(ix, iz) == world_y_top {
+            return false;
+        }
+        self.set_column_height(ix, iz, world_y_top)
+    }
+
+    /// Edge columns whose height differs from `previous` (an earlier `edge_heights` snapshot),
+    /// as `(index, new height)`: what a deformation changed along a shared boundary.
+    pub fn changed_edge_heights(&self, edge: ChunkEdge, previous: &[f32]) -> Vec<(usize, f32)> {
+        self.edge_heights(edge)
+            .into_iter()
+            .zip(previous)
+            .enumerate()
+            .filter(|(_, (new, old))| new != *old)
+            .map(|(i, (new, _))| (i, new))
+            .collect()
+    }
+
+    /// Copy the neighbour's `changed_edge_heights` across `edge` (the neighbour's side of the
+    /// boundary) into this chunk's matching columns, so both sides agree. Returns true if any
+    /// column changed.
+    pub fn sync_edge_from(&mut self, edge: ChunkEdge, changed: &[(usize, f32)]) -> bool {
+        let mut any = false;
+        for &(i, height) in changed {
+            any |= self.set_edge_height(edge.opposite(), i, height);
+        }
+        any
+    }
+
+    /// Grid cell of a corner column.
+    fn corner_column(&self, corner: ChunkCorner) -> (usize, usize) {
+        let (edge, last) = corner.x_edge();
+        self.edge_column(edge, if last { self.nz - 1 } else { 0 })
+    }
+
+    /// The corner column's new height if it differs from `previous` (an earlier `edge_heights`
+    /// snapshot of all four edges, in `ChunkEdge::ALL` order).
+    pub fn changed_corner_height(&self, corner: ChunkCorner, previous: &[Vec<f32>; 4]) -> Option<f32> {
+        let (edge, last) = corner.x_edge();
+        let snapshot = &previous[ChunkEdge::ALL.iter().position(|&e| e == edge)?];
+        let old = if last { snapshot.last() } else { snapshot.first() }?;
+        let (ix, iz) = self.corner_column(corner);
+        let new = self.column_height(ix, iz);
+        (new != *old).then_some(new)
+    }
+
+    /// Copy the diagonal neighbour's changed `corner` height into this chunk's matching corner.
+    /// Edge syncing only reaches the chunks sharing a side; this reaches the fourth chunk at a
+    /// corner. Returns true if the column changed.
+    pub fn sync_corner_from(&mut self, corner: ChunkCorner, height: f32) -> bool {
+        let (ix, iz) = self.corner_column(corner.opposite());
+        if self.column_height(ix, iz) == height {
+            return false;
+        }
+        self.set_column_height(ix, iz, height)
+    }
+
     /// Set a column's surface to the given world Y (top of top block). Fills below with Stone/Dirt, clears above.
     pub fn set_column_height(&mut self, ix: usize, iz: usize, world_y_top: f32) -> bool {
         if ix >= self.nx || iz >= self.nz {
@@ -585,3 +931,127 @@ impl VoxelChunk {
         modified
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk_at(cx: i32) -> VoxelChunk {
+        chunk_at_xz(cx, 0)
+    }
+
+    fn chunk_at_xz(cx: i32, cz: i32) -> VoxelChunk {
+        let config = TerrainConfig {
+            size: 16.0,
+            offset_x: cx as f32 * 16.0,
+            offset_z: cz as f32 * 16.0,
+            seed: 7,
+            ..Default::default()
+        };
+        VoxelChunk::generate(&config, None)
+    }
+
+    #[test]
+    fn dig_along_boundary_then_sync_edges_agree() {
+        let mut a = chunk_at(0);
+        let mut b = chunk_at(1);
+        let before = a.edge_heights(ChunkEdge::MaxX);
+
+        // Dig a trench along the shared boundary, only in chunk A
+        let border_x = 8.0;
+        for iz in 0..a.nz {
+            let z = a.world_z(iz);
+            let y = a.column_height(a.nx - 1, iz);
+            a.deform_sphere(border_x - 0.5, y - 0.5, z, 2.5);
+        }
+        let after = a.edge_heights(ChunkEdge::MaxX);
+        assert_ne!(before, after);
+
+        // Copy modified boundary columns across
+        let changed = a.changed_edge_heights(ChunkEdge::MaxX, &before);
+        assert!(b.sync_edge_from(ChunkEdge::MaxX, &changed));
+        assert!(!b.sync_edge_from(ChunkEdge::MaxX, &changed));
+        assert_eq!(a.edge_heights(ChunkEdge::MaxX), b.edge_heights(ChunkEdge::MinX));
+    }
+
+    #[test]
+    fn dig_at_a_corner_then_sync_reaches_the_diagonal_chunk() {
+        let mut a = chunk_at_xz(0, 0);
+        let mut d = chunk_at_xz(1, 1);
+        let before = ChunkEdge::ALL.map(|edge| a.edge_heights(edge));
+        let corner = ChunkCorner::MaxXMaxZ;
+        assert!(a.changed_corner_height(corner, &before).is_none());
+
+        let (ix, iz) = (a.nx - 1, a.nz - 1);
+        let y = a.column_height(ix, iz);
+        a.deform_sphere(a.world_x(ix), y - 0.5, a.world_z(iz), 2.0);
+        let height = a.changed_corner_height(corner, &before).expect("corner dug");
+
+        assert!(d.sync_corner_from(corner, height));
+        assert!(!d.sync_corner_from(corner, height));
+        assert_eq!(d.column_height(0, 0), a.column_height(ix, iz));
+    }
+
+    #[test]
+    fn edits_replayed_on_a_regenerated_chunk_restore_the_deformation() {
+        let mut dug = chunk_at(0);
+        let y = dug.column_height(4, 4);
+        dug.deform_sphere(dug.world_x(4), y - 0.5, dug.world_z(4), 2.0);
+        dug.fill_sphere(dug.world_x(10), y + 0.5, dug.world_z(10), 1.0, BlockId::Dirt);
+        assert!(!dug.edits.is_empty());
+
+        let mut regenerated = chunk_at(0);
+        assert!(regenerated.edits.is_empty());
+        regenerated.apply_edits(&dug.edits);
+        assert_eq!(regenerated.data, dug.data);
+        assert_eq!(regenerated.edits, dug.edits);
+    }
+
+    #[test]
+    fn caves_only_carved_when_enabled() {
+        let air_below_surface = |generate_caves: bool| {
+            let config = TerrainConfig {
+                size: 32.0,
+                seed: 3,
+                generate_caves,
+                ..Default::default()
+            };
+            let chunk = VoxelChunk::generate(&config, None);
+            let mut count = 0;
+            for iz in 0..chunk.nz {
+                for ix in 0..chunk.nx {
+                    let top = (0..chunk.ny).rev().find(|&iy| chunk.get(ix, iy, iz).is_solid()).unwrap_or(0);
+                    count += (0..top).filter(|&iy| chunk.get(ix, iy, iz) == BlockId::Air).count();
+                }
+            }
+            count
+        };
+        assert_eq!(air_below_surface(false), 0);
+        assert!(air_below_surface(true) > 0);
+    }
+
+    #[test]
+    fn carving_matches_across_chunk_seams() {
+        // Two 16m chunks must carve exactly what one 32m chunk over the same ground does
+        let config = |size: f32, offset_x: f32| TerrainConfig {
+            size,
+            offset_x,
+            seed: 3,
+            height_scale: 60.0,
+            rivers: None,
+            ..Default::default()
+        };
+        let whole = VoxelChunk::generate(&config(32.0, 0.0), None);
+        for (offset_x, x0) in [(-8.0, 0), (8.0, 16)] {
+            let half = VoxelChunk::generate(&config(16.0, offset_x), None);
+            assert_eq!(half.ny, whole.ny);
+            for iz in 0..half.nz {
+                for iy in 0..half.ny {
+                    for ix in 0..half.nx {
+                        assert_eq!(half.get(ix, iy, iz), whole.get(x0 + ix, iy, iz + 8), "column ({ix}, {iz}) at y {iy}");
+                    }
+                }
+            }
+        }
+    }
+}
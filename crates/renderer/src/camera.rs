@@ -2,15 +2,24 @@
 
 use bytemuck::{Pod, Zeroable};
 use engine_core::Transform;
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec3, Vec4};
+use std::cell::Cell;
+
+/// Range accepted for the user's base field of view (degrees).
+pub const MIN_BASE_FOV: f32 = 60.0;
+pub const MAX_BASE_FOV: f32 = 110.0;
 
 /// FPS camera with configurable FOV and clipping planes.
 #[derive(Debug, Clone)]
 pub struct Camera {
     /// Camera transform (position and rotation).
     pub transform: Transform,
-    /// Field of view in degrees.
+    /// Field of view in degrees (the base FOV plus any zoom or speed kick this frame).
     pub fov_degrees: f32,
+    /// The user's field of view setting; dynamic FOV changes return to this.
+    pub base_fov_degrees: f32,
+    /// Field of view for the viewmodel pass, fixed so zooming the world doesn't magnify the gun.
+    pub viewmodel_fov_degrees: f32,
     /// Near clipping plane.
     pub near: f32,
     /// Far clipping plane.
@@ -23,6 +32,8 @@ pub struct Camera {
     pitch: f32,
     /// Current yaw (left/right rotation) in radians.
     yaw: f32,
+    /// Last projection built and the (fov, aspect, near, far) it was built from.
+    projection_cache: Cell<([f32; 4], Mat4)>,
 }
 
 impl Default for Camera {
@@ -30,12 +41,15 @@ impl Default for Camera {
         Self {
             transform: Transform::default(),
             fov_degrees: 70.0,
+            base_fov_degrees: 70.0,
+            viewmodel_fov_degrees: 70.0,
             near: 0.1,
             far: 1000.0,
             aspect: 16.0 / 9.0,
             sensitivity: 0.002,
             pitch: 0.0,
             yaw: 0.0,
+            projection_cache: Cell::new(([f32::NAN; 4], Mat4::IDENTITY)),
         }
     }
 }
@@ -107,6 +121,21 @@ impl Camera {
         }
     }
 
+    /// Set the user's base field of view (clamped to `MIN_BASE_FOV..=MAX_BASE_FOV`) and jump to it.
+    pub fn set_base_fov(&mut self, degrees: f32) {
+        self.base_fov_degrees = degrees.clamp(MIN_BASE_FOV, MAX_BASE_FOV);
+        self.fov_degrees = self.base_fov_degrees;
+    }
+
+    /// Ease the field of view toward `target_degrees` at `rate` (1/s); snaps once within 0.05°.
+    pub fn set_fov(&mut self, target_degrees: f32, rate: f32, dt: f32) {
+        let t = 1.0 - (-rate * dt).exp();
+        self.fov_degrees += (target_degrees - self.fov_degrees) * t;
+        if (self.fov_degrees - target_degrees).abs() < 0.05 {
+            self.fov_degrees = target_degrees;
+        }
+    }
+
     /// Get the view matrix.
     pub fn view_matrix(&self) -> Mat4 {
         let eye = self.transform.position;
@@ -115,9 +144,16 @@ impl Camera {
         Mat4::look_at_rh(eye, target, up)
     }
 
-    /// Get the projection matrix.
+    /// Get the projection matrix. Rebuilt only when the FOV, aspect or clip planes change.
     pub fn projection_matrix(&self) -> Mat4 {
-        Mat4::perspective_rh(self.fov_degrees.to_radians(), self.aspect, self.near, self.far)
+        let key = [self.fov_degrees, self.aspect, self.near, self.far];
+        let (cached_key, cached) = self.projection_cache.get();
+        if cached_key == key {
+            return cached;
+        }
+        let projection = Mat4::perspective_rh(self.fov_degrees.to_radians(), self.aspect, self.near, self.far);
+        self.projection_cache.set((key, projection));
+        projection
     }
 
     /// Get the combined view-projection matrix.
@@ -131,9 +167,19 @@ impl Camera {
         Mat4::from_quat(self.transform.rotation).inverse()
     }
 
+    /// Projection for the viewmodel pass (its own FOV).
+    pub fn projection_matrix_viewmodel(&self) -> Mat4 {
+        Mat4::perspective_rh(self.viewmodel_fov_degrees.to_radians(), self.aspect, self.near, self.far)
+    }
+
     /// View-projection for viewmodel pass (camera at origin).
     pub fn view_projection_matrix_viewmodel(&self) -> Mat4 {
-        self.projection_matrix() * self.view_matrix_viewmodel()
+        self.projection_matrix_viewmodel() * self.view_matrix_viewmodel()
+    }
+
+    /// World-space view frustum for culling.
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_view_proj(self.view_projection_matrix())
     }
 
     /// Get camera position.
@@ -171,6 +217,43 @@ impl Camera {
     }
 }
 
+/// View frustum as six inward-facing planes (xyz = unit normal, w = distance).
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extract planes from a view-projection matrix (wgpu clip space: depth 0..1).
+    pub fn from_view_proj(view_proj: Mat4) -> Self {
+        let r0 = view_proj.row(0);
+        let r1 = view_proj.row(1);
+        let r2 = view_proj.row(2);
+        let r3 = view_proj.row(3);
+        let planes = [
+            r3 + r0, // left
+            r3 - r0, // right
+            r3 + r1, // bottom
+            r3 - r1, // top
+            r2,      // near
+            r3 - r2, // far
+        ]
+        .map(|p| p / p.truncate().length().max(f32::EPSILON));
+        Self { planes }
+    }
+
+    /// True if the axis-aligned box overlaps the frustum. Conservative: boxes near a corner
+    /// may pass even when just outside, but a visible box is never rejected.
+    pub fn intersects_aabb(&self, min: Vec3, max: Vec3) -> bool {
+        self.planes.iter().all(|p| {
+            let normal = p.truncate();
+            // Corner furthest along the plane normal
+            let corner = Vec3::select(normal.cmpge(Vec3::ZERO), max, min);
+            normal.dot(corner) + p.w >= 0.0
+        })
+    }
+}
+
 /// Camera uniform data for GPU.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -178,7 +261,7 @@ pub struct CameraUniform {
     pub view_proj: [[f32; 4]; 4],
     pub view: [[f32; 4]; 4],
     pub proj: [[f32; 4]; 4],
-    pub position: [f32; 4], // w unused, padding
+    pub position: [f32; 4], // w = 1 world-space pass, 0 viewmodel (view-space) pass
     /// Planet radius for curvature (d^2/2R). 0 = no curvature.
     pub planet_radius: f32,
     /// Padding to match WGSL std140 layout (vec3 alignment + struct size multiple of 16).
@@ -209,9 +292,10 @@ impl CameraUniform {
     /// Set uniform for viewmodel pass: view/proj with camera at origin so viewmodel is in view space.
     pub fn update_viewmodel(&mut self, camera: &Camera) {
         self.view = camera.view_matrix_viewmodel().to_cols_array_2d();
-        self.proj = camera.projection_matrix().to_cols_array_2d();
+        self.proj = camera.projection_matrix_viewmodel().to_cols_array_2d();
         self.view_proj = camera.view_projection_matrix_viewmodel().to_cols_array_2d();
-        self.position = [0.0, 0.0, 0.0, 1.0];
+        // w = 0 marks the view-space pass; world-space point lights don't apply to it
+        self.position = [0.0, 0.0, 0.0, 0.0];
         self.planet_radius = 0.0; // No curvature for viewmodel (in view space)
     }
 }
@@ -221,3 +305,47 @@ impl Default for CameraUniform {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frustum_culls_boxes_behind_and_keeps_boxes_ahead() {
+        let camera = Camera::new(Vec3::ZERO);
+        let frustum = camera.frustum();
+        // Default camera looks down -Z
+        assert!(frustum.intersects_aabb(Vec3::new(-1.0, -1.0, -11.0), Vec3::new(1.0, 1.0, -9.0)));
+        assert!(!frustum.intersects_aabb(Vec3::new(-1.0, -1.0, 9.0), Vec3::new(1.0, 1.0, 11.0)));
+        assert!(!frustum.intersects_aabb(Vec3::new(-1.0, -1.0, -2000.0), Vec3::new(1.0, 1.0, -1500.0)));
+    }
+
+    #[test]
+    fn set_fov_eases_toward_the_target_independent_of_frame_rate() {
+        let mut coarse = Camera::default();
+        let mut fine = Camera::default();
+        coarse.set_fov(20.0, 10.0, 0.1);
+        for _ in 0..10 {
+            fine.set_fov(20.0, 10.0, 0.01);
+        }
+        assert!(coarse.fov_degrees < 70.0 && coarse.fov_degrees > 20.0);
+        assert!((coarse.fov_degrees - fine.fov_degrees).abs() < 1e-3);
+
+        for _ in 0..100 {
+            coarse.set_fov(20.0, 10.0, 0.1);
+        }
+        assert_eq!(coarse.fov_degrees, 20.0);
+    }
+
+    #[test]
+    fn projection_follows_fov_changes_and_base_fov_is_clamped() {
+        let mut camera = Camera::default();
+        let wide = camera.projection_matrix();
+        assert_eq!(camera.projection_matrix(), wide);
+        camera.fov_degrees = 40.0;
+        assert_ne!(camera.projection_matrix(), wide);
+
+        camera.set_base_fov(200.0);
+        assert_eq!((camera.base_fov_degrees, camera.fov_degrees), (MAX_BASE_FOV, MAX_BASE_FOV));
+    }
+}
@@ -0,0 +1,412 @@
+//! Verlet cloth: a grid of particles held together by distance constraints.
+//!
+//! Used for the ship's flags; pin sets, tearing and simple colliders make it reusable for
+//! tattered hive banners, tarps over supply crates, and capes. Rendered as one flat quad per cell.
+
+use glam::{Mat3, Mat4, Quat, Vec2, Vec3};
+
+/// Ventilation draft (along the hang direction) and gust strength for interior cloth.
+const INTERIOR_DRAFT: f32 = 3.0;
+const INTERIOR_GUST: f32 = 1.0;
+/// Wind force cap so a storm can't push the Verlet step past stability.
+const MAX_WIND: f32 = 20.0;
+/// Largest simulation step; longer frames are clamped to keep Verlet stable.
+const MAX_DT: f32 = 0.033;
+/// Constraint relaxation passes per step (more = stiffer cloth).
+const SOLVER_ITERATIONS: usize = 5;
+
+/// Which particles are fixed in place.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClothPins {
+    /// Whole top row (flag on a pole, banner on a rail).
+    TopRow,
+    /// Only the two top corners (tarp or banner hung from two points).
+    TopCorners,
+    /// Whole first column (flag on a vertical mast).
+    LeftColumn,
+    /// Explicit (row, col) particles.
+    Custom(Vec<(usize, usize)>),
+}
+
+/// Simple shape the cloth cannot pass through.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClothCollider {
+    /// Horizontal ground plane at this height.
+    Ground { height: f32 },
+    Sphere { center: Vec3, radius: f32 },
+}
+
+/// Distance constraint between two particles; torn links stop pulling and stop rendering.
+#[derive(Debug, Clone, Copy)]
+struct Link {
+    a: usize,
+    b: usize,
+    rest: f32,
+    intact: bool,
+}
+
+/// Cloth-simulated grid using Verlet integration with distance constraints.
+#[derive(Debug, Clone)]
+pub struct Cloth {
+    /// Particle positions (row-major: cols across, rows down).
+    positions: Vec<Vec3>,
+    /// Previous positions (for Verlet integration).
+    prev_positions: Vec<Vec3>,
+    pinned: Vec<bool>,
+    cols: usize,
+    rows: usize,
+    /// Horizontal links first (`rows * (cols - 1)`), then vertical (`(rows - 1) * cols`).
+    links: Vec<Link>,
+    /// Direction the cloth hangs away from its mount (unit vector).
+    hang_dir: Vec3,
+    /// Direction along the pole (unit vector, "right" for the cloth).
+    pole_dir: Vec3,
+    /// Color per cell, row-major ((cols - 1) * (rows - 1)).
+    colors: Vec<[f32; 4]>,
+    /// Accumulated time for wind variation.
+    wind_time: f32,
+    /// Indoors: weather wind is ignored and only a ventilation draft stirs the cloth.
+    interior: bool,
+    /// Links tear when stretched beyond `rest * tear_ratio`.
+    tear_ratio: Option<f32>,
+    colliders: Vec<ClothCollider>,
+}
+
+impl Cloth {
+    /// Create cloth hanging straight down from `origin`, with the top row pinned.
+    /// `pole_dir`: unit vector along the pole (direction of increasing column).
+    /// `hang_dir`: unit vector the cloth is blown toward (away from the wall, perpendicular to pole).
+    /// `size`: physical width and height in meters. `cols`, `rows`: grid resolution (at least 2).
+    /// `colors`: [r,g,b,a] per cell ((cols - 1) * (rows - 1)); missing cells render grey.
+    pub fn new(
+        origin: Vec3,
+        pole_dir: Vec3,
+        hang_dir: Vec3,
+        size: Vec2,
+        cols: usize,
+        rows: usize,
+        colors: Vec<[f32; 4]>,
+    ) -> Self {
+        let cols = cols.max(2);
+        let rows = rows.max(2);
+        let rest_x = size.x / (cols - 1) as f32;
+        let rest_y = size.y / (rows - 1) as f32;
+
+        let mut positions = Vec::with_capacity(cols * rows);
+        for r in 0..rows {
+            for c in 0..cols {
+                positions.push(origin + pole_dir * (c as f32 * rest_x) - Vec3::Y * (r as f32 * rest_y));
+            }
+        }
+
+        let mut links = Vec::with_capacity(rows * (cols - 1) + (rows - 1) * cols);
+        for r in 0..rows {
+            for c in 0..(cols - 1) {
+                let a = r * cols + c;
+                links.push(Link { a, b: a + 1, rest: rest_x, intact: true });
+            }
+        }
+        for r in 0..(rows - 1) {
+            for c in 0..cols {
+                let a = r * cols + c;
+                links.push(Link { a, b: a + cols, rest: rest_y, intact: true });
+            }
+        }
+
+        let cloth = Self {
+            prev_positions: positions.clone(),
+            positions,
+            pinned: vec![false; cols * rows],
+            cols,
+            rows,
+            links,
+            hang_dir,
+            pole_dir,
+            colors,
+            wind_time: 0.0,
+            interior: false,
+            tear_ratio: None,
+            colliders: Vec::new(),
+        };
+        cloth.with_pins(ClothPins::TopRow)
+    }
+
+    /// Replace the pin set.
+    pub fn with_pins(mut self, pins: ClothPins) -> Self {
+        let (cols, rows) = (self.cols, self.rows);
+        self.pinned.iter_mut().for_each(|p| *p = false);
+        let mut pin = |r: usize, c: usize| {
+            if r < rows && c < cols {
+                self.pinned[r * cols + c] = true;
+            }
+        };
+        match pins {
+            ClothPins::TopRow => (0..cols).for_each(|c| pin(0, c)),
+            ClothPins::TopCorners => {
+                pin(0, 0);
+                pin(0, cols - 1);
+            }
+            ClothPins::LeftColumn => (0..rows).for_each(|r| pin(r, 0)),
+            ClothPins::Custom(list) => list.into_iter().for_each(|(r, c)| pin(r, c)),
+        }
+        self
+    }
+
+    /// Mark the cloth as indoors (ship interior): it ignores weather wind.
+    pub fn interior(mut self) -> Self {
+        self.interior = true;
+        self
+    }
+
+    /// Let links tear when stretched past `stretch_ratio` times their rest length (e.g. 1.6).
+    pub fn with_tearing(mut self, stretch_ratio: f32) -> Self {
+        self.tear_ratio = Some(stretch_ratio.max(1.0));
+        self
+    }
+
+    pub fn with_collider(mut self, collider: ClothCollider) -> Self {
+        self.colliders.push(collider);
+        self
+    }
+
+    /// Move every pinned particle by `delta` (cape on a moving trooper, tarp on a dropped crate).
+    pub fn translate_pins(&mut self, delta: Vec3) {
+        for (i, pinned) in self.pinned.iter().enumerate() {
+            if *pinned {
+                self.positions[i] += delta;
+                self.prev_positions[i] += delta;
+            }
+        }
+    }
+
+    /// Particle positions, row-major.
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// Number of links that have torn.
+    pub fn torn_links(&self) -> usize {
+        self.links.iter().filter(|l| !l.intact).count()
+    }
+
+    /// Step the cloth simulation. `wind_vector` is the prevailing wind (force per unit mass) and
+    /// `gust_strength` scales gusts and turbulence (0 = steady); interior cloth uses its draft instead.
+    pub fn update(&mut self, dt: f32, wind_vector: Vec3, gust_strength: f32) {
+        let dt = dt.min(MAX_DT);
+        self.wind_time += dt;
+
+        let gravity = Vec3::new(0.0, -4.0, 0.0);
+        let damping = 0.98;
+
+        let (wind, gust_strength) = if self.interior {
+            (self.hang_dir * INTERIOR_DRAFT, INTERIOR_GUST)
+        } else {
+            (wind_vector.clamp_length_max(MAX_WIND), gust_strength.max(0.0))
+        };
+        // Flutter scales with wind speed: limp in calm air, whipping in a storm
+        let flutter = wind.length() / INTERIOR_DRAFT * gust_strength;
+
+        // Wind: oscillating gusts along the wind with turbulence
+        let wind_base = 1.0 + (self.wind_time * 1.2).sin() * (2.0 / 3.0) * gust_strength;
+        let wind_gust = (self.wind_time * 3.7).sin() * (self.wind_time * 0.8).cos() * 0.5 * gust_strength;
+        let wind_force = wind * (wind_base + wind_gust);
+        let wind_dir = wind.normalize_or_zero();
+        // Cross-wind turbulence
+        let cross = self.pole_dir * (self.wind_time * 2.3).sin() * 0.8 * flutter;
+
+        // Verlet integration
+        for i in 0..self.positions.len() {
+            if self.pinned[i] {
+                continue;
+            }
+
+            let pos = self.positions[i];
+            let prev = self.prev_positions[i];
+            let vel = (pos - prev) * damping;
+
+            // Per-particle wind variation based on grid position
+            let r = i / self.cols;
+            let c = i % self.cols;
+            let wave = ((c as f32 * 0.5 + self.wind_time * 4.0).sin() * 0.3
+                + (r as f32 * 0.7 + self.wind_time * 2.5).cos() * 0.2)
+                * flutter
+                * wind_dir;
+
+            let accel = gravity + wind_force + cross + wave;
+            self.prev_positions[i] = pos;
+            self.positions[i] = pos + vel + accel * dt * dt;
+        }
+
+        self.solve_constraints();
+        self.collide();
+    }
+
+    /// Relax all intact links (several passes for stiffness), tearing overstretched ones.
+    fn solve_constraints(&mut self) {
+        for _ in 0..SOLVER_ITERATIONS {
+            for k in 0..self.links.len() {
+                let Link { a, b, rest, intact } = self.links[k];
+                if !intact {
+                    continue;
+                }
+                let delta = self.positions[b] - self.positions[a];
+                let dist = delta.length();
+                if dist < 0.0001 {
+                    continue;
+                }
+                if self.tear_ratio.is_some_and(|ratio| dist > rest * ratio) {
+                    self.links[k].intact = false;
+                    continue;
+                }
+                let (pin_a, pin_b) = (self.pinned[a], self.pinned[b]);
+                let correction = delta * (1.0 - rest / dist);
+                match (pin_a, pin_b) {
+                    (true, true) => {}
+                    (true, false) => self.positions[b] -= correction,
+                    (false, true) => self.positions[a] += correction,
+                    (false, false) => {
+                        self.positions[a] += correction * 0.5;
+                        self.positions[b] -= correction * 0.5;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Push free particles out of colliders.
+    fn collide(&mut self) {
+        for collider in &self.colliders {
+            for (p, pinned) in self.positions.iter_mut().zip(&self.pinned) {
+                if *pinned {
+                    continue;
+                }
+                match *collider {
+                    ClothCollider::Ground { height } => p.y = p.y.max(height),
+                    ClothCollider::Sphere { center, radius } => {
+                        let offset = *p - center;
+                        let dist = offset.length();
+                        if dist < radius {
+                            *p = center + offset.try_normalize().unwrap_or(Vec3::Y) * radius;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn link_intact(&self, index: usize) -> bool {
+        self.links[index].intact
+    }
+
+    /// Generate renderable quad instances, one per cell whose four edges are intact.
+    /// Returns a list of (matrix, color) for each cloth cell.
+    pub fn render_instances(&self) -> Vec<(Mat4, [f32; 4])> {
+        let mut instances = Vec::new();
+        let horizontal = self.rows * (self.cols - 1);
+
+        for r in 0..(self.rows - 1) {
+            for c in 0..(self.cols - 1) {
+                let top = r * (self.cols - 1) + c;
+                let bottom = top + (self.cols - 1);
+                let left = horizontal + r * self.cols + c;
+                if ![top, bottom, left, left + 1].iter().all(|&k| self.link_intact(k)) {
+                    continue;
+                }
+
+                let tl = self.positions[r * self.cols + c];
+                let tr = self.positions[r * self.cols + c + 1];
+                let bl = self.positions[(r + 1) * self.cols + c];
+                let br = self.positions[(r + 1) * self.cols + c + 1];
+
+                // Quad center
+                let center = (tl + tr + bl + br) * 0.25;
+                // Approximate quad normal
+                let edge_h = (tr - tl + br - bl) * 0.5;
+                let edge_v = (bl - tl + br - tr) * 0.5;
+                if edge_h.cross(edge_v).length() < 0.0001 {
+                    continue;
+                }
+
+                // Scale from edge lengths
+                let sx = ((tr - tl).length() + (br - bl).length()) * 0.5;
+                let sy = ((bl - tl).length() + (br - tr).length()) * 0.5;
+
+                // Build rotation from edges
+                let right = edge_h.normalize_or_zero();
+                let up_approx = edge_v.normalize_or_zero();
+                let fwd = right.cross(up_approx).normalize_or_zero();
+                let corrected_up = fwd.cross(right).normalize_or_zero();
+                let rotation = Quat::from_mat3(&Mat3::from_cols(right, corrected_up, fwd));
+
+                let matrix = Mat4::from_scale_rotation_translation(
+                    Vec3::new(sx, 0.01, sy), // flat quad: X-width, Z-height, Y-thin
+                    rotation,
+                    center,
+                );
+
+                let color = self.colors.get(r * (self.cols - 1) + c).copied().unwrap_or([0.5, 0.5, 0.5, 1.0]);
+                instances.push((matrix, color));
+            }
+        }
+
+        instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(cols: usize, rows: usize) -> Cloth {
+        Cloth::new(Vec3::new(0.0, 5.0, 0.0), Vec3::X, Vec3::Z, Vec2::splat(2.0), cols, rows, Vec::new())
+    }
+
+    fn max_stretch(cloth: &Cloth) -> f32 {
+        cloth
+            .links
+            .iter()
+            .filter(|l| l.intact)
+            .map(|l| (cloth.positions[l.b].distance(cloth.positions[l.a]) / l.rest - 1.0).abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn pinned_grid_settles_near_rest_lengths() {
+        let mut cloth = grid(9, 9);
+        let pins: Vec<Vec3> = cloth.positions[..9].to_vec();
+        for _ in 0..600 {
+            cloth.update(1.0 / 60.0, Vec3::Z * 2.0, 0.5);
+        }
+        assert_eq!(&cloth.positions[..9], pins.as_slice(), "top row stays pinned");
+        assert!(max_stretch(&cloth) < 0.05, "links within 5% of rest, got {}", max_stretch(&cloth));
+        assert!(cloth.positions.iter().all(|p| p.is_finite()));
+        assert_eq!(cloth.render_instances().len(), 8 * 8);
+    }
+
+    #[test]
+    fn corner_pins_sag_and_tearing_drops_cells() {
+        let mut cloth = grid(5, 5).with_pins(ClothPins::TopCorners).with_tearing(1.05);
+        let before = cloth.positions[2].y;
+        // Yank both pinned corners away fast: the rest of the cloth lags, so links near the pins overstretch and tear.
+        for _ in 0..10 {
+            cloth.translate_pins(Vec3::new(0.0, 0.0, 0.5));
+            cloth.update(1.0 / 60.0, Vec3::ZERO, 0.0);
+        }
+        assert!(cloth.positions[2].y < before, "unpinned top-middle sags");
+        assert!(cloth.torn_links() > 0);
+        assert!(cloth.render_instances().len() < 4 * 4);
+    }
+
+    #[test]
+    fn colliders_keep_particles_outside() {
+        let sphere = Vec3::new(1.0, 3.5, 0.0);
+        let mut cloth = grid(6, 6)
+            .with_collider(ClothCollider::Ground { height: 4.0 })
+            .with_collider(ClothCollider::Sphere { center: sphere, radius: 0.6 });
+        for _ in 0..300 {
+            cloth.update(1.0 / 60.0, Vec3::ZERO, 0.0);
+        }
+        assert!(cloth.positions().iter().all(|p| p.y >= 4.0 - 1e-4));
+        assert!(cloth.positions().iter().all(|p| p.distance(sphere) >= 0.6 - 1e-4));
+    }
+}
@@ -0,0 +1,178 @@
+//! Projected decals: bullet holes and scorch marks left on hard surfaces.
+//!
+//! Each decal is a small quad laid flat against the surface it marks, nudged off it along the
+//! normal so it never z-fights. The buffer holds a fixed number; spawning past it evicts the
+//! oldest, so sustained fire keeps the freshest marks.
+
+use crate::InstanceData;
+use glam::{Mat4, Quat, Vec3};
+use std::collections::VecDeque;
+
+/// Decals kept at once before the oldest are evicted.
+pub const MAX_DECALS: usize = 256;
+/// Distance the quad floats off the surface (m).
+const SURFACE_OFFSET: f32 = 0.015;
+/// Edge length of the generated decal texture.
+pub const DECAL_TEXTURE_SIZE: u32 = 64;
+
+/// What left the mark; sets its tint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecalKind {
+    /// Small puncture with a chipped rim (bullets).
+    BulletHole,
+    /// Soot blast mark (explosions against structures).
+    Scorch,
+}
+
+impl DecalKind {
+    fn color(self) -> [f32; 4] {
+        match self {
+            DecalKind::BulletHole => [0.08, 0.07, 0.06, 0.95],
+            DecalKind::Scorch => [0.05, 0.045, 0.04, 0.8],
+        }
+    }
+}
+
+/// One placed decal.
+#[derive(Debug, Clone, Copy)]
+pub struct Decal {
+    pub position: Vec3,
+    pub normal: Vec3,
+    pub size: f32,
+    pub kind: DecalKind,
+}
+
+impl Decal {
+    /// Instance for the unit decal quad (XZ plane, facing +Y): turned to face along the normal
+    /// and spun by a position hash so neighbouring holes don't look stamped.
+    fn instance(&self) -> InstanceData {
+        let spin = (self.position.x * 12.9898 + self.position.y * 78.233 + self.position.z * 37.719).sin() * 43758.547;
+        let rotation = Quat::from_rotation_arc(Vec3::Y, self.normal) * Quat::from_rotation_y(spin.fract() * std::f32::consts::TAU);
+        let model = Mat4::from_scale_rotation_translation(
+            Vec3::new(self.size, 1.0, self.size),
+            rotation,
+            self.position + self.normal * SURFACE_OFFSET,
+        );
+        InstanceData::new(model.to_cols_array_2d(), self.kind.color()).with_material(0.9, 0.0)
+    }
+}
+
+/// Placed decals, oldest first.
+#[derive(Debug)]
+pub struct DecalBuffer {
+    decals: VecDeque<Decal>,
+    capacity: usize,
+}
+
+impl Default for DecalBuffer {
+    fn default() -> Self {
+        Self::with_capacity(MAX_DECALS)
+    }
+}
+
+impl DecalBuffer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { decals: VecDeque::with_capacity(capacity), capacity: capacity.max(1) }
+    }
+
+    /// Place a decal, evicting the oldest when full. Degenerate normals are ignored.
+    pub fn spawn(&mut self, position: Vec3, normal: Vec3, size: f32, kind: DecalKind) {
+        let Some(normal) = normal.try_normalize() else {
+            return;
+        };
+        if self.decals.len() >= self.capacity {
+            self.decals.pop_front();
+        }
+        self.decals.push_back(Decal { position, normal, size, kind });
+    }
+
+    /// Drop decals within `radius` of `center` (the surface under them is gone).
+    pub fn clear_near(&mut self, center: Vec3, radius: f32) {
+        self.decals.retain(|d| d.position.distance_squared(center) > radius * radius);
+    }
+
+    pub fn clear(&mut self) {
+        self.decals.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.decals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decals.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Decal> {
+        self.decals.iter()
+    }
+
+    pub fn instances(&self) -> Vec<InstanceData> {
+        self.decals.iter().map(Decal::instance).collect()
+    }
+}
+
+/// RGBA8 bullet-hole mask: opaque dark core, chipped rim with radial cracks, clear outside.
+/// Color is white; the instance tint darkens it.
+pub fn decal_texture_pixels(size: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+    let half = size as f32 * 0.5;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = (x as f32 + 0.5 - half) / half;
+            let dy = (y as f32 + 0.5 - half) / half;
+            let r = (dx * dx + dy * dy).sqrt();
+            let angle = dy.atan2(dx);
+            // Ragged edge and a few cracks running outward
+            let ragged = 0.42 + (angle * 7.0).sin() * 0.04 + (angle * 13.0).cos() * 0.03;
+            let crack = (angle * 5.0 + 1.3).sin().abs().powf(40.0) * (1.0 - r).max(0.0);
+            let core = 1.0 - smoothstep(0.18, 0.24, r);
+            let rim = (1.0 - smoothstep(ragged - 0.06, ragged, r)) * 0.6;
+            let alpha = core.max(rim).max(crack * 0.8).clamp(0.0, 1.0);
+            // Core darkest, rim a shade lighter (chipped material)
+            let shade = 1.0 - core * 0.5;
+            let c = (shade * 255.0) as u8;
+            pixels.extend_from_slice(&[c, c, c, (alpha * 255.0) as u8]);
+        }
+    }
+    pixels
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_decals_are_evicted_past_capacity() {
+        let mut decals = DecalBuffer::with_capacity(3);
+        for i in 0..5 {
+            decals.spawn(Vec3::new(i as f32, 0.0, 0.0), Vec3::X, 0.1, DecalKind::BulletHole);
+        }
+        assert_eq!(decals.len(), 3);
+        let xs: Vec<f32> = decals.iter().map(|d| d.position.x).collect();
+        assert_eq!(xs, [2.0, 3.0, 4.0]);
+
+        decals.spawn(Vec3::ZERO, Vec3::ZERO, 0.1, DecalKind::BulletHole);
+        assert_eq!(decals.len(), 3);
+        decals.clear_near(Vec3::new(3.0, 0.0, 0.0), 1.5);
+        assert!(decals.is_empty());
+    }
+
+    #[test]
+    fn decal_quad_lies_flat_on_the_surface_just_off_it() {
+        let mut decals = DecalBuffer::default();
+        decals.spawn(Vec3::new(1.0, 2.0, 3.0), Vec3::NEG_Z, 0.2, DecalKind::BulletHole);
+        let model = Mat4::from_cols_array_2d(&decals.instances()[0].model);
+        // Quad's +Y (its facing) maps onto the surface normal
+        assert!(model.transform_vector3(Vec3::Y).normalize().abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        let center = model.transform_point3(Vec3::ZERO);
+        assert!(center.abs_diff_eq(Vec3::new(1.0, 2.0, 3.0 - SURFACE_OFFSET), 1e-5));
+        // Scaled to `size` across the surface
+        assert!((model.transform_vector3(Vec3::X).length() - 0.2).abs() < 1e-5);
+    }
+}
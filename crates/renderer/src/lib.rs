@@ -1,15 +1,27 @@
 //! Rendering system using wgpu for OpenSST.
 
 pub mod camera;
+pub mod cloth;
+pub mod decal;
+pub mod light;
+pub mod lod;
 pub mod mesh;
 pub mod pipeline;
 pub mod renderer;
+pub mod shadow;
 pub mod texture;
+pub mod transparent;
 pub mod vertex;
 
 pub use camera::*;
+pub use cloth::*;
+pub use decal::*;
+pub use light::*;
+pub use lod::*;
 pub use mesh::*;
 pub use pipeline::*;
 pub use renderer::*;
+pub use shadow::*;
 pub use texture::*;
+pub use transparent::*;
 pub use vertex::*;
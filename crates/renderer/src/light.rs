@@ -0,0 +1,125 @@
+//! Short-lived dynamic point lights (muzzle flashes, explosions, lava).
+//!
+//! Lights are registered every frame they shine and uploaded once per frame. Only the
+//! `MAX_POINT_LIGHTS` that matter most to the camera (bright and close) reach the shaders;
+//! anything whose reach ends beyond `LIGHT_CULL_DISTANCE` is dropped outright.
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+/// Lights the shaders loop over (must match MAX_POINT_LIGHTS in main.wgsl and terrain.wgsl).
+pub const MAX_POINT_LIGHTS: usize = 16;
+/// Lights whose sphere of influence lies entirely farther than this from the camera are culled (m).
+pub const LIGHT_CULL_DISTANCE: f32 = 150.0;
+
+/// One point light for the current frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    pub position: Vec3,
+    /// Linear RGB.
+    pub color: [f32; 3],
+    /// Distance where the light falls to zero (m).
+    pub radius: f32,
+    pub intensity: f32,
+}
+
+/// GPU point light (std140: two vec4s).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct PointLightGpu {
+    /// xyz = world position, w = radius
+    pub position_radius: [f32; 4],
+    /// rgb = color, w = intensity
+    pub color_intensity: [f32; 4],
+}
+
+/// Point light uniform (must match PointLightUniform in main.wgsl and terrain.wgsl).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct PointLightUniform {
+    pub lights: [PointLightGpu; MAX_POINT_LIGHTS],
+    /// x = active light count
+    pub count: [u32; 4],
+}
+
+impl Default for PointLightUniform {
+    fn default() -> Self {
+        Self::zeroed()
+    }
+}
+
+/// Lights registered for the frame being built.
+#[derive(Debug, Default)]
+pub struct PointLights {
+    pending: Vec<PointLight>,
+}
+
+impl PointLights {
+    /// Register a light for this frame. Lights with no reach or no brightness are ignored.
+    pub fn add(&mut self, light: PointLight) {
+        if light.radius > 0.0 && light.intensity > 0.0 {
+            self.pending.push(light);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Build this frame's uniform as seen from `camera_pos` and start an empty list for the next frame.
+    pub fn take_uniform(&mut self, camera_pos: Vec3) -> PointLightUniform {
+        let mut lights: Vec<(f32, PointLight)> = self
+            .pending
+            .drain(..)
+            .filter_map(|light| {
+                let gap = light.position.distance(camera_pos) - light.radius;
+                (gap < LIGHT_CULL_DISTANCE).then(|| (light.intensity * light.radius / (1.0 + gap.max(0.0)), light))
+            })
+            .collect();
+        lights.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut uniform = PointLightUniform::default();
+        for (slot, (_, light)) in uniform.lights.iter_mut().zip(lights.iter()) {
+            *slot = PointLightGpu {
+                position_radius: [light.position.x, light.position.y, light.position.z, light.radius],
+                color_intensity: [light.color[0], light.color[1], light.color[2], light.intensity],
+            };
+        }
+        uniform.count[0] = lights.len().min(MAX_POINT_LIGHTS) as u32;
+        uniform
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn light_at(x: f32, intensity: f32) -> PointLight {
+        PointLight { position: Vec3::new(x, 0.0, 0.0), color: [1.0, 0.8, 0.5], radius: 8.0, intensity }
+    }
+
+    #[test]
+    fn far_lights_are_culled_and_the_rest_capped_nearest_first() {
+        let mut lights = PointLights::default();
+        lights.add(light_at(LIGHT_CULL_DISTANCE + 20.0, 10.0));
+        for i in 0..MAX_POINT_LIGHTS + 4 {
+            lights.add(light_at(i as f32 * 5.0, 2.0));
+        }
+        lights.add(light_at(0.0, 0.0));
+
+        let uniform = lights.take_uniform(Vec3::ZERO);
+        assert_eq!(uniform.count[0] as usize, MAX_POINT_LIGHTS);
+        let xs: Vec<f32> = uniform.lights.iter().map(|l| l.position_radius[0]).collect();
+        assert_eq!(xs[0], 0.0);
+        assert!(xs.windows(2).all(|w| w[0] <= w[1]));
+        assert!(xs.iter().all(|&x| x < LIGHT_CULL_DISTANCE));
+
+        // Each frame starts empty
+        assert!(lights.is_empty());
+        assert_eq!(lights.take_uniform(Vec3::ZERO).count[0], 0);
+    }
+}
@@ -0,0 +1,177 @@
+//! Distance-based level of detail for instanced meshes.
+//!
+//! Coarser levels are generated by vertex clustering: vertices are snapped to a grid,
+//! merged per cell, and triangles that collapse are dropped.
+
+use crate::{mesh::Mesh, vertex::Vertex};
+use glam::Vec3;
+use std::collections::HashMap;
+
+/// One reduced level: the clustering cell size (mesh units) and the camera distance
+/// beyond which it replaces the previous level.
+#[derive(Debug, Clone, Copy)]
+pub struct LodLevel {
+    pub cell_size: f32,
+    pub distance: f32,
+}
+
+/// A mesh plus coarser variants, chosen per instance by camera distance.
+pub struct LodMesh {
+    /// Level 0 is full detail; later levels are coarser.
+    levels: Vec<Mesh>,
+    /// Switch distance for each level after the first, ascending.
+    distances: Vec<f32>,
+}
+
+impl LodMesh {
+    /// Build the full mesh and one clustered level per `LodLevel`. Levels that would
+    /// collapse to nothing are skipped.
+    pub fn from_data(device: &wgpu::Device, vertices: &[Vertex], indices: &[u32], lods: &[LodLevel]) -> Self {
+        let mut levels = vec![Mesh::from_data(device, vertices, indices)];
+        let mut distances = Vec::new();
+        for lod in lods {
+            let (v, i) = simplify_clustered(vertices, indices, lod.cell_size);
+            if i.is_empty() {
+                continue;
+            }
+            levels.push(Mesh::from_data(device, &v, &i));
+            distances.push(lod.distance);
+        }
+        Self { levels, distances }
+    }
+
+    /// Full-detail mesh, for draws that don't go through LOD selection.
+    pub fn full(&self) -> &Mesh {
+        &self.levels[0]
+    }
+
+    pub fn level(&self, index: usize) -> &Mesh {
+        &self.levels[index.min(self.levels.len() - 1)]
+    }
+
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Level to draw at squared camera distance `dist_sq` (the value already computed for culling).
+    pub fn level_for_distance_sq(&self, dist_sq: f32) -> usize {
+        lod_index(&self.distances, dist_sq)
+    }
+}
+
+/// Number of switch distances at or below `sqrt(dist_sq)`.
+fn lod_index(distances: &[f32], dist_sq: f32) -> usize {
+    distances.iter().take_while(|&&d| d * d <= dist_sq).count()
+}
+
+/// Reduce a mesh by merging all vertices that fall in the same `cell_size` grid cell.
+/// Position, normal and color are averaged per cell; degenerate triangles are removed.
+pub fn simplify_clustered(vertices: &[Vertex], indices: &[u32], cell_size: f32) -> (Vec<Vertex>, Vec<u32>) {
+    if cell_size <= 0.0 {
+        return (vertices.to_vec(), indices.to_vec());
+    }
+
+    struct Cluster {
+        position: Vec3,
+        normal: Vec3,
+        color: [f32; 4],
+        tex_coords: [f32; 2],
+        count: f32,
+    }
+
+    let mut cell_of: HashMap<(i32, i32, i32), u32> = HashMap::new();
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let remap: Vec<u32> = vertices
+        .iter()
+        .map(|v| {
+            let p = Vec3::from_array(v.position);
+            let key = (
+                (p.x / cell_size).floor() as i32,
+                (p.y / cell_size).floor() as i32,
+                (p.z / cell_size).floor() as i32,
+            );
+            let index = *cell_of.entry(key).or_insert_with(|| {
+                clusters.push(Cluster {
+                    position: Vec3::ZERO,
+                    normal: Vec3::ZERO,
+                    color: [0.0; 4],
+                    tex_coords: v.tex_coords,
+                    count: 0.0,
+                });
+                clusters.len() as u32 - 1
+            });
+            let c = &mut clusters[index as usize];
+            c.position += p;
+            c.normal += Vec3::from_array(v.normal);
+            for (sum, channel) in c.color.iter_mut().zip(v.color) {
+                *sum += channel;
+            }
+            c.count += 1.0;
+            index
+        })
+        .collect();
+
+    let out_vertices = clusters
+        .iter()
+        .map(|c| {
+            Vertex::with_color(
+                (c.position / c.count).to_array(),
+                c.normal.normalize_or(Vec3::Y).to_array(),
+                c.tex_coords,
+                c.color.map(|channel| channel / c.count),
+            )
+        })
+        .collect();
+
+    let mut out_indices = Vec::with_capacity(indices.len());
+    for tri in indices.chunks_exact(3) {
+        let (a, b, c) = (remap[tri[0] as usize], remap[tri[1] as usize], remap[tri[2] as usize]);
+        if a != b && b != c && a != c {
+            out_indices.extend([a, b, c]);
+        }
+    }
+    (out_vertices, out_indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(n: usize) -> (Vec<Vertex>, Vec<u32>) {
+        let mut vertices = Vec::new();
+        for z in 0..=n {
+            for x in 0..=n {
+                let p = [x as f32 / n as f32, 0.0, z as f32 / n as f32];
+                vertices.push(Vertex::new(p, [0.0, 1.0, 0.0], [p[0], p[2]]));
+            }
+        }
+        let mut indices = Vec::new();
+        let row = (n + 1) as u32;
+        for z in 0..n as u32 {
+            for x in 0..n as u32 {
+                let i = z * row + x;
+                indices.extend([i, i + row, i + 1, i + 1, i + row, i + row + 1]);
+            }
+        }
+        (vertices, indices)
+    }
+
+    #[test]
+    fn clustering_reduces_triangles() {
+        let (vertices, indices) = grid(16);
+        let (v, i) = simplify_clustered(&vertices, &indices, 0.25);
+        assert!(v.len() < vertices.len());
+        assert!(!i.is_empty() && i.len() < indices.len());
+        assert!(i.iter().all(|&idx| (idx as usize) < v.len()));
+        let (_, none) = simplify_clustered(&vertices, &indices, 10.0);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn level_follows_distance() {
+        let distances = [40.0, 100.0];
+        assert_eq!(lod_index(&distances, 10.0 * 10.0), 0);
+        assert_eq!(lod_index(&distances, 40.0 * 40.0), 1);
+        assert_eq!(lod_index(&distances, 150.0 * 150.0), 2);
+    }
+}
@@ -36,9 +36,12 @@ impl Mesh {
         }
     }
 
-    /// Alias for `new` - create a mesh from vertex and index data.
+    /// Create a mesh from authored vertex and index data, generating tangents for normal mapping.
+    /// Use `new` for meshes whose shader has no normal maps (terrain, water).
     pub fn from_data(device: &wgpu::Device, vertices: &[Vertex], indices: &[u32]) -> Self {
-        Self::new(device, vertices, indices)
+        let mut vertices = vertices.to_vec();
+        generate_tangents(&mut vertices, indices);
+        Self::new(device, &vertices, indices)
     }
 
     /// Create a unit cube centered at origin.
@@ -88,7 +91,7 @@ impl Mesh {
             20, 23, 22, 20, 22, 21, // Left -X
         ];
 
-        Self::new(device, &vertices, &indices)
+        Self::from_data(device, &vertices, &indices)
     }
 
     /// Create a billboard quad (XY plane, facing +Z). Use with a camera-facing rotation
@@ -117,7 +120,7 @@ impl Mesh {
 
         let indices: [u32; 6] = [0, 1, 2, 2, 3, 0];
 
-        Self::new(device, &vertices, &indices)
+        Self::from_data(device, &vertices, &indices)
     }
 
     /// Unit cube for composable viewmodel rendering (each instance = one rifle part).
@@ -247,7 +250,43 @@ impl Mesh {
             }
         }
 
-        Self::new(device, &vertices, &indices)
+        Self::from_data(device, &vertices, &indices)
+    }
+}
+
+/// Fill each vertex's `tangent` from its triangles' UV layout (per-triangle tangents
+/// accumulated, then Gram-Schmidt against the normal). Vertices whose UVs don't vary get an
+/// arbitrary tangent perpendicular to the normal, so normal maps still have a frame.
+pub fn generate_tangents(vertices: &mut [Vertex], indices: &[u32]) {
+    let mut tangents = vec![Vec3::ZERO; vertices.len()];
+    let mut bitangents = vec![Vec3::ZERO; vertices.len()];
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if a >= vertices.len() || b >= vertices.len() || c >= vertices.len() {
+            continue;
+        }
+        let p = |i: usize| Vec3::from(vertices[i].position);
+        let uv = |i: usize| glam::Vec2::from(vertices[i].tex_coords);
+        let (e1, e2) = (p(b) - p(a), p(c) - p(a));
+        let (d1, d2) = (uv(b) - uv(a), uv(c) - uv(a));
+        let det = d1.x * d2.y - d2.x * d1.y;
+        if det.abs() < 1e-12 {
+            continue;
+        }
+        let r = 1.0 / det;
+        let t = (e1 * d2.y - e2 * d1.y) * r;
+        let bt = (e2 * d1.x - e1 * d2.x) * r;
+        for i in [a, b, c] {
+            tangents[i] += t;
+            bitangents[i] += bt;
+        }
+    }
+    for (i, vertex) in vertices.iter_mut().enumerate() {
+        let n = Vec3::from(vertex.normal).normalize_or(Vec3::Y);
+        let t = tangents[i] - n * n.dot(tangents[i]);
+        let t = if t.length_squared() > 1e-12 { t.normalize() } else { n.any_orthonormal_vector() };
+        let w = if n.cross(t).dot(bitangents[i]) < 0.0 { -1.0 } else { 1.0 };
+        vertex.tangent = [t.x, t.y, t.z, w];
     }
 }
 
@@ -267,3 +306,31 @@ impl MeshData {
         Mesh::new(device, &self.vertices, &self.indices)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tangents_follow_u_and_stay_perpendicular_to_the_normal() {
+        // Upward quad: u runs along +x, v along +z
+        let mut vertices = [
+            Vertex::new([0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0]),
+            Vertex::new([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0]),
+            Vertex::new([1.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 1.0]),
+            Vertex::new([0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [0.0, 1.0]),
+        ];
+        generate_tangents(&mut vertices, &[0, 2, 1, 0, 3, 2]);
+        for v in &vertices {
+            assert_eq!(v.tangent, [1.0, 0.0, 0.0, -1.0]);
+        }
+
+        // Constant UVs still give a usable frame
+        let mut flat = [Vertex::new([0.0; 3], [0.0, 0.0, 1.0], [0.0; 2]); 3];
+        flat[1].position = [1.0, 0.0, 0.0];
+        flat[2].position = [0.0, 1.0, 0.0];
+        generate_tangents(&mut flat, &[0, 1, 2]);
+        let t = Vec3::new(flat[0].tangent[0], flat[0].tangent[1], flat[0].tangent[2]);
+        assert!((t.length() - 1.0).abs() < 1e-5 && t.z.abs() < 1e-5);
+    }
+}
@@ -2,8 +2,12 @@
 
 use crate::{
     camera::{Camera, CameraUniform},
+    decal::{decal_texture_pixels, DecalBuffer, DecalKind, DECAL_TEXTURE_SIZE},
+    light::{PointLight, PointLightUniform, PointLights},
+    lod::LodMesh,
     mesh::Mesh,
     pipeline::{
+        create_bloom_upsample_pipeline,
         create_blur_bind_group_layout,
         create_blur_pipeline,
         create_bright_bind_group_layout,
@@ -15,6 +19,7 @@ use crate::{
         create_main_shadow_pipeline,
         create_overlay_bind_group_layout,
         create_overlay_pipeline,
+        create_particle_pipeline,
         create_render_pipeline,
         create_shadow_bind_group_layout,
         create_shadow_pass_bind_group_layout,
@@ -25,10 +30,14 @@ use crate::{
         create_terrain_shadow_pipeline,
         create_water_pipeline,
         create_texture_bind_group_layout,
+        create_transparent_pipeline,
         create_viewmodel_pipeline,
+        HDR_FORMAT,
     },
-    texture::Texture,
-    vertex::{CelestialBodyInstance, InstanceData, OverlayVertex},
+    shadow::{cascade_splits, cascade_view_proj, MAX_SHADOW_CASCADES},
+    texture::{Material, Texture},
+    transparent::{TransparentDraw, TransparentQueue},
+    vertex::{CelestialBodyInstance, InstanceData, OverlayVertex, ParticleInstance},
 };
 use anyhow::Result;
 use bytemuck::{Pod, Zeroable};
@@ -36,10 +45,20 @@ use std::sync::Arc;
 use wgpu::util::DeviceExt;
 use winit::window::Window;
 
+/// Resolution of each shadow cascade.
+const SHADOW_MAP_SIZE: u32 = 2048;
+
+/// Bloom downsample chain depth: 1/4, 1/8 and 1/16 of the screen.
+const BLOOM_LEVELS: usize = 3;
+
 /// Size of the terrain deformation heightfield (world space follows player).
 pub const DEFORM_TEXTURE_SIZE: u32 = 256;
 /// Half-extent of deformation region in world units (total 128m x 128m).
 pub const DEFORM_HALF_SIZE: f32 = 64.0;
+/// Edge length of each terrain material layer (RGBA8, tileable).
+pub const TERRAIN_MATERIAL_SIZE: u32 = 256;
+/// Terrain material layers, in shader order: rock, dirt, sand, snow, grass.
+pub const TERRAIN_MATERIAL_LAYERS: u32 = 5;
 
 /// Terrain shader uniform (must match terrain.wgsl TerrainUniform).
 #[repr(C)]
@@ -48,11 +67,12 @@ pub struct TerrainUniform {
     pub biome_colors: [[f32; 4]; 4],
     pub biome_params: [f32; 4],
     pub sun_direction: [f32; 4],
-    pub fog_params: [f32; 4],
     /// x = deform_origin_x, y = deform_origin_z, z = deform_half_size, w = deform_enabled (0 or 1)
     pub deform_params: [f32; 4],
     /// x = snow_enabled (0 or 1), yzw unused
     pub snow_params: [f32; 4],
+    /// x = blood_origin_x, y = blood_origin_z, z = half_size, w = blood_enabled (0 or 1)
+    pub blood_params: [f32; 4],
 }
 
 impl Default for TerrainUniform {
@@ -66,9 +86,9 @@ impl Default for TerrainUniform {
             ],
             biome_params: [4.0, 2.0, 0.0, 0.0], // blend_sharpness, detail_scale, time, unused
             sun_direction: [0.5, 1.0, 0.3, 0.0],
-            fog_params: [0.0003, 0.05, 50.0, 400.0], // density, height_falloff, start, end
             deform_params: [0.0, 0.0, DEFORM_HALF_SIZE, 0.0], // origin_x, origin_z, half_size, enabled
             snow_params: [0.0, 0.0, 0.0, 0.0], // x = snow_enabled
+            blood_params: [0.0, 0.0, DEFORM_HALF_SIZE, 0.0], // origin_x, origin_z, half_size, enabled
         }
     }
 }
@@ -85,6 +105,8 @@ pub struct SkyUniform {
     pub params: [f32; 4],            // x = time, y = cloud_density, z = dust, w = planet_type
     /// x = 1.0 to skip procedural sun/moon (use physical celestial spheres instead)
     pub skip_procedural_sun_moon: [f32; 4],
+    /// Night-sky star field: x, y = per-planet orientation (radians), z = sky rotation from time of day, w unused
+    pub stars: [f32; 4],
 }
 
 impl Default for SkyUniform {
@@ -97,20 +119,136 @@ impl Default for SkyUniform {
             ground_color: [0.4, 0.35, 0.3, 0.3],
             params: [0.0, 0.3, 0.1, 0.0],
             skip_procedural_sun_moon: [0.0, 0.0, 0.0, 0.0],
+            stars: [0.0, 0.0, 0.0, 0.0],
         }
     }
 }
 
-/// Shadow map uniform (must match shadow.wgsl ShadowUniform). Used for depth pass and for sampling.
+/// Shadow depth pass uniform for one cascade (must match shadow.wgsl ShadowPassUniform).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct ShadowUniform {
+pub struct ShadowPassUniform {
     pub light_view_proj: [[f32; 4]; 4],
     pub camera_pos: [f32; 3],
     pub planet_radius: f32,
     pub _pad: [f32; 4],
 }
 
+/// Shadow sampling uniform (must match ShadowUniform in main.wgsl and terrain.wgsl).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct ShadowUniform {
+    pub cascade_view_proj: [[[f32; 4]; 4]; MAX_SHADOW_CASCADES],
+    /// Far view-space depth of each cascade.
+    pub cascade_splits: [f32; MAX_SHADOW_CASCADES],
+    pub camera_pos: [f32; 3],
+    pub planet_radius: f32,
+    pub camera_forward: [f32; 3],
+    pub cascade_count: u32,
+}
+
+/// Atmospheric fog for lit geometry (meshes, terrain, water). Set per biome and weather via `Renderer::set_fog`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogParams {
+    /// Haze color of the atmosphere (biome tint); blended toward the sky horizon by `sky_blend`.
+    pub color: [f32; 3],
+    /// Extinction per meter at `base_height` (0 = no fog).
+    pub density: f32,
+    /// How quickly fog thins with altitude above `base_height` (per meter).
+    pub height_falloff: f32,
+    /// World height where the fog is densest (ground level).
+    pub base_height: f32,
+    /// 0 = use `color` as is, 1 = use the current sky horizon color (time of day, from `update_sky`).
+    pub sky_blend: f32,
+}
+
+impl Default for FogParams {
+    fn default() -> Self {
+        Self {
+            color: [0.38, 0.34, 0.32],
+            density: 0.004,
+            height_falloff: 0.01,
+            base_height: 0.0,
+            sky_blend: 0.5,
+        }
+    }
+}
+
+impl FogParams {
+    /// Murky water seen from below the surface: thick, even blue-green fog with no height falloff.
+    pub fn underwater() -> Self {
+        Self {
+            color: [0.05, 0.2, 0.22],
+            density: 0.09,
+            height_falloff: 0.0,
+            base_height: 0.0,
+            sky_blend: 0.0,
+        }
+    }
+}
+
+/// Sky palette of a planet's atmosphere under its star (see `Renderer::update_sky`). Time of day
+/// blends between these; night stays dark everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtmosphereParams {
+    /// Sky straight up at midday.
+    pub zenith: [f32; 3],
+    /// Horizon band at midday.
+    pub horizon: [f32; 3],
+    /// Horizon at sunrise and sunset, when sunlight crosses the most air.
+    pub sunset: [f32; 3],
+    /// Tint of direct sunlight (star color after the air has filtered it).
+    pub sun_color: [f32; 3],
+}
+
+impl Default for AtmosphereParams {
+    /// Earth-like air under a yellow star.
+    fn default() -> Self {
+        Self {
+            zenith: [0.15, 0.35, 0.85],
+            horizon: [0.50, 0.60, 0.90],
+            sunset: [0.98, 0.30, 0.08],
+            sun_color: [1.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl AtmosphereParams {
+    /// Sky colors multiplied by `tint` (weather: overcast greys, storm blues). Sunlight is left alone.
+    pub fn tinted(&self, tint: [f32; 3]) -> Self {
+        let mul = |c: [f32; 3]| [c[0] * tint[0], c[1] * tint[1], c[2] * tint[2]];
+        Self { zenith: mul(self.zenith), horizon: mul(self.horizon), sunset: mul(self.sunset), ..*self }
+    }
+}
+
+/// Fog uniform (must match FogUniform in main.wgsl, terrain.wgsl and water.wgsl).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct FogUniform {
+    /// rgb = final fog color, w = density
+    pub color_density: [f32; 4],
+    /// x = height_falloff, y = base_height, z = distance where geometry fully fades out (camera far), w unused
+    pub params: [f32; 4],
+}
+
+impl FogUniform {
+    fn new(fog: &FogParams, sky_horizon: [f32; 3], far: f32) -> Self {
+        let k = fog.sky_blend.clamp(0.0, 1.0);
+        let color = [
+            fog.color[0] + (sky_horizon[0] - fog.color[0]) * k,
+            fog.color[1] + (sky_horizon[1] - fog.color[1]) * k,
+            fog.color[2] + (sky_horizon[2] - fog.color[2]) * k,
+        ];
+        Self {
+            color_density: [color[0], color[1], color[2], fog.density.max(0.0)],
+            params: [fog.height_falloff.max(0.0), fog.base_height, far, 0.0],
+        }
+    }
+}
+
+/// Screen tint color of the underwater look (see `Renderer::set_underwater`).
+const UNDERWATER_TINT: [f32; 3] = [0.18, 0.52, 0.5];
+
 /// Main renderer state.
 pub struct Renderer {
     pub surface: wgpu::Surface<'static>,
@@ -122,6 +260,7 @@ pub struct Renderer {
 
     // Pipelines
     render_pipeline: wgpu::RenderPipeline,
+    transparent_pipeline: wgpu::RenderPipeline,
     terrain_pipeline: wgpu::RenderPipeline,
     water_pipeline: wgpu::RenderPipeline,
     sky_pipeline: wgpu::RenderPipeline,
@@ -136,29 +275,59 @@ pub struct Renderer {
     deform_sampler: wgpu::Sampler,
     /// Snow accumulation heightfield (weather-driven). R32Float, 256x256.
     snow_texture: wgpu::Texture,
+    /// Blood stain intensity on the ground (0..1). R32Float, 256x256.
+    blood_texture: wgpu::Texture,
+    /// Terrain material layers (D2 array, mipmapped). Neutral grey until `upload_terrain_materials`.
+    terrain_material_texture: wgpu::Texture,
+    /// Last uploaded blood decal region (copied into the terrain uniform each frame).
+    blood_params: [f32; 4],
     sky_bind_group: wgpu::BindGroup,
     sky_buffer: wgpu::Buffer,
+    /// Seeds the night-sky star field orientation (set per planet so its sky stays the same).
+    star_seed: u64,
     camera_bind_group: wgpu::BindGroup,
     camera_buffer: wgpu::Buffer,
     camera_uniform: CameraUniform,
 
     texture_bind_group_layout: wgpu::BindGroupLayout,
     default_texture_bind_group: wgpu::BindGroup,
-
-    // Shadow mapping (directional sun shadow)
+    /// White albedo shared by materials that only bring a normal map.
+    default_texture: Texture,
+
+    // Shadow mapping (directional sun, cascaded)
+    /// Active shadow cascades, 1..=MAX_SHADOW_CASCADES.
+    pub shadow_cascade_count: usize,
+    /// Cascade split spacing: 0.0 = uniform, 1.0 = logarithmic (sharper near the camera).
+    pub shadow_split_lambda: f32,
+    /// View distance covered by the last cascade.
+    pub shadow_distance: f32,
     shadow_map_texture: wgpu::Texture,
-    shadow_map_view: wgpu::TextureView,
+    /// One layer per cascade, rendered into individually.
+    shadow_cascade_views: Vec<wgpu::TextureView>,
     shadow_sampler: wgpu::Sampler,
     shadow_buffer: wgpu::Buffer,
-    shadow_pass_bind_group: wgpu::BindGroup,
+    shadow_pass_buffers: Vec<wgpu::Buffer>,
+    shadow_pass_bind_groups: Vec<wgpu::BindGroup>,
     shadow_bind_group: wgpu::BindGroup,
     terrain_shadow_pipeline: wgpu::RenderPipeline,
+
+    // Atmospheric fog (binding 3 of the shadow sampling group, shared by main/terrain/water)
+    fog_buffer: wgpu::Buffer,
+    fog: FogParams,
+    // Dynamic point lights (binding 4 of the shadow sampling group), rebuilt every frame
+    point_light_buffer: wgpu::Buffer,
+    point_lights: PointLights,
+    /// Horizon color from the last `update_sky` (fog blends toward it).
+    sky_horizon_color: [f32; 3],
+    /// Camera far plane from the last `update_camera`; fog reaches full opacity there.
+    camera_far: f32,
     main_shadow_pipeline: wgpu::RenderPipeline,
 
     // Depth buffer
     depth_texture: Texture,
 
     // Instance buffer for batched rendering
+    /// Per-frame instance data for all instanced draws; grows as needed and is reused across frames.
     instance_buffer: wgpu::Buffer,
     max_instances: u32,
     /// Tracks current write offset into instance_buffer per frame.
@@ -169,12 +338,27 @@ pub struct Renderer {
     /// Viewmodel mesh (rifle) owned by renderer so this pass can never draw a bug mesh by mistake.
     viewmodel_mesh: Mesh,
 
+    /// Bullet holes and scorch marks on hard surfaces (drawn by `render_decals`).
+    decals: DecalBuffer,
+    /// Unit quad (XZ, facing +Y) every decal is an instance of.
+    decal_mesh: Mesh,
+    /// Decal mask texture bound as albedo.
+    decal_bind_group: wgpu::BindGroup,
+
     // Celestial body rendering
     celestial_pipeline: wgpu::RenderPipeline,
     celestial_sphere_mesh: Mesh,
     celestial_instance_buffer: wgpu::Buffer,
     celestial_max_instances: u32,
 
+    // Billboard particles (one instanced draw, camera facing built in the vertex shader)
+    particle_pipeline: wgpu::RenderPipeline,
+    particle_quad: Mesh,
+    /// Grows as needed; each `draw_particles` call in a frame writes its own region.
+    particle_instance_buffer: wgpu::Buffer,
+    max_particles: u32,
+    frame_particle_offset: u32,
+
     // Text overlay
     overlay_pipeline: wgpu::RenderPipeline,
     overlay_bind_group: wgpu::BindGroup,
@@ -186,16 +370,21 @@ pub struct Renderer {
     cinematic_uniform_buffer: wgpu::Buffer,
     cinematic_sampler: wgpu::Sampler,
 
-    // Bloom: bright pass + blur
-    bloom_texture_a: wgpu::Texture,
-    bloom_texture_b: wgpu::Texture,
+    // Bloom: bright pass + blur/downsample chain + additive upsample
+    /// Ping-pong pair per level, each half the size of the previous.
+    bloom_levels: Vec<[wgpu::Texture; 2]>,
+    bloom_intensity: f32,
+    /// 0–1 blend toward the underwater look (tint in the cinematic pass).
+    underwater: f32,
     bright_pipeline: wgpu::RenderPipeline,
     blur_pipeline: wgpu::RenderPipeline,
+    bloom_upsample_pipeline: wgpu::RenderPipeline,
     bright_bind_group_layout: wgpu::BindGroupLayout,
     blur_bind_group_layout: wgpu::BindGroupLayout,
     bright_uniform_buffer: wgpu::Buffer,
     blur_uniform_h: wgpu::Buffer,
     blur_uniform_v: wgpu::Buffer,
+    bloom_upsample_uniform: wgpu::Buffer,
 
     // Depth sampler for SSAO (non-compare, for sampling depth values)
     depth_sampler_linear: wgpu::Sampler,
@@ -293,8 +482,9 @@ impl Renderer {
             }],
         });
 
-        // Create default white texture
+        // Create default white texture and flat normal map
         let default_texture = Texture::white_pixel(&device, &queue);
+        let flat_normal = Texture::flat_normal_pixel(&device, &queue);
         let default_texture_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Default Texture Bind Group"),
             layout: &texture_bind_group_layout,
@@ -307,27 +497,50 @@ impl Renderer {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(&default_texture.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&flat_normal.view),
+                },
             ],
         });
 
-        // Shadow mapping: directional sun shadow map (2048x2048 depth)
-        const SHADOW_MAP_SIZE: u32 = 2048;
+        // Shadow mapping: cascaded sun shadow map (2048x2048 depth per cascade, one array layer each)
         let shadow_pass_layout = create_shadow_pass_bind_group_layout(&device);
         let shadow_sample_layout = create_shadow_bind_group_layout(&device);
         let shadow_uniform = ShadowUniform {
-            light_view_proj: glam::Mat4::IDENTITY.to_cols_array_2d(),
+            cascade_view_proj: [glam::Mat4::IDENTITY.to_cols_array_2d(); MAX_SHADOW_CASCADES],
+            cascade_splits: [0.0; MAX_SHADOW_CASCADES],
             camera_pos: [0.0, 0.0, 0.0],
             planet_radius: 0.0,
-            _pad: [0.0; 4],
+            camera_forward: [0.0, 0.0, -1.0],
+            cascade_count: 0,
         };
         let shadow_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Shadow Uniform"),
             contents: bytemuck::cast_slice(&[shadow_uniform]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
+        let shadow_pass_buffers: Vec<wgpu::Buffer> = (0..MAX_SHADOW_CASCADES)
+            .map(|_| {
+                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Shadow Pass Uniform"),
+                    contents: bytemuck::cast_slice(&[ShadowPassUniform {
+                        light_view_proj: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                        camera_pos: [0.0, 0.0, 0.0],
+                        planet_radius: 0.0,
+                        _pad: [0.0; 4],
+                    }]),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                })
+            })
+            .collect();
         let shadow_map_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Shadow Map"),
-            size: wgpu::Extent3d { width: SHADOW_MAP_SIZE, height: SHADOW_MAP_SIZE, depth_or_array_layers: 1 },
+            size: wgpu::Extent3d {
+                width: SHADOW_MAP_SIZE,
+                height: SHADOW_MAP_SIZE,
+                depth_or_array_layers: MAX_SHADOW_CASCADES as u32,
+            },
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
@@ -335,7 +548,22 @@ impl Renderer {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
-        let shadow_map_view = shadow_map_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let shadow_map_view = shadow_map_texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Shadow Map Array View"),
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        let shadow_cascade_views: Vec<wgpu::TextureView> = (0..MAX_SHADOW_CASCADES as u32)
+            .map(|layer| {
+                shadow_map_texture.create_view(&wgpu::TextureViewDescriptor {
+                    label: Some("Shadow Cascade View"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                })
+            })
+            .collect();
         let shadow_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("Shadow Sampler"),
             address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -346,10 +574,28 @@ impl Renderer {
             compare: Some(wgpu::CompareFunction::LessEqual),
             ..Default::default()
         });
-        let shadow_pass_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Shadow Pass Bind Group"),
-            layout: &shadow_pass_layout,
-            entries: &[wgpu::BindGroupEntry { binding: 0, resource: shadow_buffer.as_entire_binding() }],
+        let shadow_pass_bind_groups: Vec<wgpu::BindGroup> = shadow_pass_buffers
+            .iter()
+            .map(|buffer| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Shadow Pass Bind Group"),
+                    layout: &shadow_pass_layout,
+                    entries: &[wgpu::BindGroupEntry { binding: 0, resource: buffer.as_entire_binding() }],
+                })
+            })
+            .collect();
+        let fog = FogParams::default();
+        let sky_horizon_color = [0.5, 0.6, 0.9];
+        let camera_far = 1000.0;
+        let fog_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Fog Uniform"),
+            contents: bytemuck::cast_slice(&[FogUniform::new(&fog, sky_horizon_color, camera_far)]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let point_light_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Point Light Uniform"),
+            contents: bytemuck::cast_slice(&[PointLightUniform::default()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
         let shadow_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Shadow Bind Group"),
@@ -358,6 +604,8 @@ impl Renderer {
                 wgpu::BindGroupEntry { binding: 0, resource: shadow_buffer.as_entire_binding() },
                 wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&shadow_map_view) },
                 wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(&shadow_sampler) },
+                wgpu::BindGroupEntry { binding: 3, resource: fog_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 4, resource: point_light_buffer.as_entire_binding() },
             ],
         });
         let terrain_shadow_pipeline = create_terrain_shadow_pipeline(&device, &shadow_pass_layout);
@@ -366,7 +614,14 @@ impl Renderer {
         // Create render pipeline
         let render_pipeline = create_render_pipeline(
             &device,
-            &config,
+            HDR_FORMAT,
+            &camera_bind_group_layout,
+            &texture_bind_group_layout,
+            &shadow_sample_layout,
+        );
+        let transparent_pipeline = create_transparent_pipeline(
+            &device,
+            HDR_FORMAT,
             &camera_bind_group_layout,
             &texture_bind_group_layout,
             &shadow_sample_layout,
@@ -432,6 +687,61 @@ impl Renderer {
             bytemuck::cast_slice(&deform_pixels),
         );
         let snow_view = snow_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // Blood decal stain map (same size/format; kill zones stained by gore)
+        let blood_texture = device.create_texture_with_data(
+            &queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Terrain Blood Decals"),
+                size: wgpu::Extent3d {
+                    width: deform_size,
+                    height: deform_size,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R32Float,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            bytemuck::cast_slice(&deform_pixels),
+        );
+        let blood_view = blood_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // Terrain material layers: neutral grey (no detail) until the game uploads generated ones
+        let material_mips = TERRAIN_MATERIAL_SIZE.ilog2() + 1;
+        let terrain_material_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Terrain Materials"),
+            size: wgpu::Extent3d {
+                width: TERRAIN_MATERIAL_SIZE,
+                height: TERRAIN_MATERIAL_SIZE,
+                depth_or_array_layers: TERRAIN_MATERIAL_LAYERS,
+            },
+            mip_level_count: material_mips,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let neutral = vec![128u8; (TERRAIN_MATERIAL_SIZE * TERRAIN_MATERIAL_SIZE * 4) as usize];
+        for layer in 0..TERRAIN_MATERIAL_LAYERS {
+            write_material_layer(&queue, &terrain_material_texture, layer, &neutral);
+        }
+        let terrain_material_view = terrain_material_texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        let terrain_material_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Terrain Material Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            anisotropy_clamp: 4,
+            ..Default::default()
+        });
         let terrain_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Terrain Bind Group"),
             layout: &terrain_bind_group_layout,
@@ -460,10 +770,22 @@ impl Renderer {
                     binding: 5,
                     resource: wgpu::BindingResource::Sampler(&deform_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&blood_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: wgpu::BindingResource::TextureView(&terrain_material_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: wgpu::BindingResource::Sampler(&terrain_material_sampler),
+                },
             ],
         });
-        let terrain_pipeline = create_terrain_pipeline(&device, &config, &terrain_bind_group_layout, &shadow_sample_layout);
-        let water_pipeline = create_water_pipeline(&device, &config, &terrain_bind_group_layout);
+        let terrain_pipeline = create_terrain_pipeline(&device, HDR_FORMAT, &terrain_bind_group_layout, &shadow_sample_layout);
+        let water_pipeline = create_water_pipeline(&device, HDR_FORMAT, &terrain_bind_group_layout, &shadow_sample_layout);
 
         let sky_bind_group_layout = create_sky_bind_group_layout(&device);
         let sky_uniform = SkyUniform::default();
@@ -486,15 +808,15 @@ impl Renderer {
                 },
             ],
         });
-        let sky_pipeline = create_sky_pipeline(&device, &config, &sky_bind_group_layout);
+        let sky_pipeline = create_sky_pipeline(&device, HDR_FORMAT, &sky_bind_group_layout);
 
         let viewmodel_pipeline =
-            create_viewmodel_pipeline(&device, &config, &camera_bind_group_layout, &texture_bind_group_layout, &shadow_sample_layout);
+            create_viewmodel_pipeline(&device, HDR_FORMAT, &camera_bind_group_layout, &texture_bind_group_layout, &shadow_sample_layout);
 
         // Create depth texture
         let depth_texture = Texture::create_depth_texture(&device, config.width, config.height, "Depth Texture");
 
-        // Create instance buffer (support up to 65536 instances — bugs + env + debris + corpses)
+        // Create instance buffer (starts at 65536 instances — bugs + env + debris + corpses; grows on demand)
         let max_instances = 65536u32;
         let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Instance Buffer"),
@@ -505,10 +827,38 @@ impl Renderer {
 
         let viewmodel_mesh = Mesh::rifle_viewmodel(&device);
 
+        let decal_mesh = Mesh::plane(&device, 1.0);
+        let decal_texture = Texture::from_rgba(
+            &device,
+            &queue,
+            DECAL_TEXTURE_SIZE,
+            DECAL_TEXTURE_SIZE,
+            &decal_texture_pixels(DECAL_TEXTURE_SIZE),
+            "Decal Mask",
+        );
+        let decal_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Decal Bind Group"),
+            layout: &texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&decal_texture.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&decal_texture.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&flat_normal.view),
+                },
+            ],
+        });
+
         // --- Celestial body rendering ---
-        let celestial_pipeline = create_celestial_pipeline(&device, &config, &camera_bind_group_layout);
+        let celestial_pipeline = create_celestial_pipeline(&device, HDR_FORMAT, &camera_bind_group_layout);
         let celestial_sphere_mesh = Mesh::sphere(&device, 1.0, 24, 16);
-        let celestial_max_instances = 256u32;
+        let celestial_max_instances = 2048u32; // room for ring and asteroid belt debris
         let celestial_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Celestial Instance Buffer"),
             size: (std::mem::size_of::<CelestialBodyInstance>() * celestial_max_instances as usize) as u64,
@@ -516,6 +866,22 @@ impl Renderer {
             mapped_at_creation: false,
         });
 
+        // --- Billboard particles ---
+        let particle_pipeline = create_particle_pipeline(
+            &device,
+            HDR_FORMAT,
+            &camera_bind_group_layout,
+            &shadow_sample_layout,
+        );
+        let particle_quad = Mesh::billboard_quad(&device, 1.0);
+        let max_particles = 4096u32;
+        let particle_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Instance Buffer"),
+            size: (std::mem::size_of::<ParticleInstance>() * max_particles as usize) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         // --- Overlay (text) pipeline ---
         let overlay_bind_group_layout = create_overlay_bind_group_layout(&device);
         let overlay_pipeline = create_overlay_pipeline(&device, &config, &overlay_bind_group_layout);
@@ -564,7 +930,7 @@ impl Renderer {
             ],
         });
 
-        // Scene texture for cinematic pass (render 3D to this, then post-process to swap chain)
+        // HDR scene texture for cinematic pass (render 3D to this, then post-process to swap chain)
         let scene_color_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Scene Color"),
             size: wgpu::Extent3d {
@@ -575,7 +941,7 @@ impl Renderer {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: config.format,
+            format: HDR_FORMAT,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
@@ -583,8 +949,8 @@ impl Renderer {
         // Cinematic post-process (MIRO-style stylized + Starship Troopers military palette)
         let cinematic_bind_group_layout = create_cinematic_bind_group_layout(&device);
         let cinematic_pipeline = create_cinematic_pipeline(&device, &config, &cinematic_bind_group_layout);
-        // Uniform: time, dither, vignette, bloom_strength, lift+ssao_scale, inv_gamma+ssao_radius, gain+ssao_bias
-        let cinematic_uniform: [f32; 16] = [
+        // Uniform: time, dither, vignette, bloom_strength, lift+ssao_scale, inv_gamma+ssao_radius, gain+ssao_bias, tint
+        let cinematic_uniform: [f32; 20] = [
             0.0,   // time
             0.03,  // dither_strength (lighter for cleaner stylized look)
             0.38,  // vignette_strength (softer for atmospheric MIRO feel)
@@ -592,6 +958,7 @@ impl Renderer {
             0.06, 0.03, 0.01, 0.4,    // lift (warmer orange/amber SST shadows), ssao_scale
             0.92, 0.92, 0.92, 0.018,  // inv_gamma (slightly flatter for stylized), ssao_radius
             1.12, 1.08, 1.05, 0.002,  // gain (punchier highlights), ssao_bias
+            0.0, 0.0, 0.0, 0.0,       // tint color, amount (off)
         ];
         let cinematic_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Cinematic Uniform"),
@@ -618,34 +985,14 @@ impl Renderer {
             ..Default::default()
         });
 
-        // Bloom textures (1/4 resolution for performance)
-        let bloom_w = (config.width / 4).max(1);
-        let bloom_h = (config.height / 4).max(1);
-        let bloom_texture_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Bloom A"),
-            size: wgpu::Extent3d { width: bloom_w, height: bloom_h, depth_or_array_layers: 1 },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: config.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let bloom_texture_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Bloom B"),
-            size: wgpu::Extent3d { width: bloom_w, height: bloom_h, depth_or_array_layers: 1 },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: config.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
+        // Bloom chain (1/4 resolution and below for performance)
+        let bloom_levels = create_bloom_levels(&device, config.width, config.height);
 
-        // Bright pass: threshold 0.7
+        // Bright pass: threshold 1.0. Lit surfaces stay at or below 1.0, so only emissive
+        // instances (and anything else pushed into HDR range) feed the bloom.
         let bright_bind_group_layout = create_bright_bind_group_layout(&device);
-        let bright_pipeline = create_bright_pipeline(&device, &config, &bright_bind_group_layout);
-        let bright_uniform: [f32; 4] = [0.7, 0.0, 0.0, 0.0]; // threshold
+        let bright_pipeline = create_bright_pipeline(&device, HDR_FORMAT, &bright_bind_group_layout);
+        let bright_uniform: [f32; 4] = [1.0, 0.0, 0.0, 0.0]; // threshold
         let bright_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Bright Uniform"),
             contents: bytemuck::cast_slice(&bright_uniform),
@@ -654,7 +1001,7 @@ impl Renderer {
 
         // Blur: separate direction uniforms for H and V (avoid overwrite between passes)
         let blur_bind_group_layout = create_blur_bind_group_layout(&device);
-        let blur_pipeline = create_blur_pipeline(&device, &config, &blur_bind_group_layout);
+        let blur_pipeline = create_blur_pipeline(&device, HDR_FORMAT, &blur_bind_group_layout);
         let blur_h: [f32; 4] = [1.0, 0.0, 0.0, 0.0];
         let blur_v: [f32; 4] = [0.0, 1.0, 0.0, 0.0];
         let blur_uniform_h = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -668,6 +1015,15 @@ impl Renderer {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
+        // Upsample: each smaller level is added onto the next larger one at this weight
+        let bloom_upsample_pipeline = create_bloom_upsample_pipeline(&device, &blur_bind_group_layout);
+        let bloom_upsample: [f32; 4] = [0.8, 0.0, 0.0, 0.0];
+        let bloom_upsample_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bloom Upsample Uniform"),
+            contents: bytemuck::cast_slice(&bloom_upsample),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         Ok(Self {
             surface,
             device,
@@ -676,6 +1032,7 @@ impl Renderer {
             size,
             window,
             render_pipeline,
+            transparent_pipeline,
             terrain_pipeline,
             water_pipeline,
             sky_pipeline,
@@ -687,29 +1044,52 @@ impl Renderer {
             deform_texture,
             deform_sampler,
             snow_texture,
+            blood_texture,
+            terrain_material_texture,
+            blood_params: TerrainUniform::default().blood_params,
             sky_bind_group,
             sky_buffer,
+            star_seed: 0,
             camera_buffer,
             camera_uniform,
             texture_bind_group_layout,
             default_texture_bind_group,
+            default_texture,
+            shadow_cascade_count: 3,
+            shadow_split_lambda: 0.75,
+            shadow_distance: 300.0,
             shadow_map_texture,
-            shadow_map_view,
+            shadow_cascade_views,
             shadow_sampler,
             shadow_buffer,
-            shadow_pass_bind_group,
+            shadow_pass_buffers,
+            shadow_pass_bind_groups,
             shadow_bind_group,
             terrain_shadow_pipeline,
             main_shadow_pipeline,
+            fog_buffer,
+            fog,
+            point_light_buffer,
+            point_lights: PointLights::default(),
+            sky_horizon_color,
+            camera_far,
             depth_texture,
             instance_buffer,
             max_instances,
             frame_instance_offset: 0,
             viewmodel_mesh,
+            decals: DecalBuffer::default(),
+            decal_mesh,
+            decal_bind_group,
             celestial_pipeline,
             celestial_sphere_mesh,
             celestial_instance_buffer,
             celestial_max_instances,
+            particle_pipeline,
+            particle_quad,
+            particle_instance_buffer,
+            max_particles,
+            frame_particle_offset: 0,
             overlay_pipeline,
             overlay_bind_group,
             scene_color_texture,
@@ -717,71 +1097,85 @@ impl Renderer {
             cinematic_bind_group_layout,
             cinematic_uniform_buffer,
             cinematic_sampler,
-            bloom_texture_a,
-            bloom_texture_b,
+            bloom_levels,
+            bloom_intensity: 0.42,
+            underwater: 0.0,
             bright_pipeline,
             blur_pipeline,
+            bloom_upsample_pipeline,
             bright_bind_group_layout,
             blur_bind_group_layout,
             bright_uniform_buffer,
             blur_uniform_h,
             blur_uniform_v,
+            bloom_upsample_uniform,
             depth_sampler_linear,
         })
     }
 
-    /// Update shadow light view-proj and camera/planet for curvature. Call before shadow pass and before main scene.
+    /// Update the shadow cascades for the sun direction and camera (planet radius for curvature).
+    /// Call before the shadow pass and before the main scene.
     pub fn update_shadow_light(
         &mut self,
         sun_dir: [f32; 3],
-        camera_pos: [f32; 3],
+        camera: &Camera,
         planet_radius: f32,
     ) {
         let sun = glam::Vec3::from_array(sun_dir);
-        let cam = glam::Vec3::from_array(camera_pos);
-        let dist = 120.0;
-        let light_eye = cam + sun * dist;
-        let light_target = cam;
-        // Stable up: avoid flip when sun is near vertical (reduces shadow map jitter when sun/moon behind terrain).
-        let up = if sun.y > 0.98 {
-            glam::Vec3::NEG_Z
-        } else if sun.y < -0.98 {
-            glam::Vec3::Z
-        } else {
-            glam::Vec3::Y
-        };
-        let view = glam::Mat4::look_at_rh(light_eye, light_target, up);
-        let half = 70.0f32;
-        let proj = glam::Mat4::orthographic_rh(-half, half, -half, half, 10.0, 280.0);
-        let light_view_proj = proj * view;
+        let count = self.shadow_cascade_count.clamp(1, MAX_SHADOW_CASCADES);
+        let far = self.shadow_distance.min(camera.far);
+        let splits = cascade_splits(camera.near, far, count, self.shadow_split_lambda);
+        let camera_pos = camera.position().to_array();
+
+        let mut cascade_view_projs = [glam::Mat4::IDENTITY.to_cols_array_2d(); MAX_SHADOW_CASCADES];
+        let mut slice_near = camera.near;
+        for (i, &slice_far) in splits.iter().enumerate().take(count) {
+            let light_view_proj = cascade_view_proj(camera, sun, slice_near, slice_far, SHADOW_MAP_SIZE);
+            cascade_view_projs[i] = light_view_proj.to_cols_array_2d();
+            let pass = ShadowPassUniform {
+                light_view_proj: cascade_view_projs[i],
+                camera_pos,
+                planet_radius,
+                _pad: [0.0; 4],
+            };
+            self.queue.write_buffer(&self.shadow_pass_buffers[i], 0, bytemuck::cast_slice(&[pass]));
+            slice_near = slice_far;
+        }
+
         let u = ShadowUniform {
-            light_view_proj: light_view_proj.to_cols_array_2d(),
+            cascade_view_proj: cascade_view_projs,
+            cascade_splits: splits,
             camera_pos,
             planet_radius,
-            _pad: [0.0; 4],
+            camera_forward: camera.forward().to_array(),
+            cascade_count: count as u32,
         };
         self.queue.write_buffer(&self.shadow_buffer, 0, bytemuck::cast_slice(&[u]));
     }
 
-    /// Run shadow pass: clear shadow map, set bind group, then run the closure to draw terrain and instanced geometry.
+    /// Run the shadow pass: for each active cascade, clear its layer, set its bind group and run
+    /// the closure (with the cascade index) to draw terrain and instanced geometry.
     pub fn with_shadow_pass(
         &self,
         encoder: &mut wgpu::CommandEncoder,
-        f: impl FnOnce(&Self, &mut wgpu::RenderPass),
+        mut f: impl FnMut(&Self, &mut wgpu::RenderPass, usize),
     ) {
-        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Shadow Pass"),
-            color_attachments: &[],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                view: &self.shadow_map_view,
-                depth_ops: Some(wgpu::Operations { load: wgpu::LoadOp::Clear(1.0), store: wgpu::StoreOp::Store }),
-                stencil_ops: None,
-            }),
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-        pass.set_bind_group(0, &self.shadow_pass_bind_group, &[]);
-        f(self, &mut pass);
+        let count = self.shadow_cascade_count.clamp(1, MAX_SHADOW_CASCADES);
+        for cascade in 0..count {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Shadow Pass"),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.shadow_cascade_views[cascade],
+                    depth_ops: Some(wgpu::Operations { load: wgpu::LoadOp::Clear(1.0), store: wgpu::StoreOp::Store }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_bind_group(0, &self.shadow_pass_bind_groups[cascade], &[]);
+            f(self, &mut pass, cascade);
+        }
     }
 
     /// Draw one terrain chunk into the shadow map. Call after begin_shadow_pass; use terrain_shadow_pipeline.
@@ -839,32 +1233,11 @@ impl Renderer {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: self.config.format,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-            let bloom_w = (self.config.width / 4).max(1);
-            let bloom_h = (self.config.height / 4).max(1);
-            self.bloom_texture_a = self.device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Bloom A"),
-                size: wgpu::Extent3d { width: bloom_w, height: bloom_h, depth_or_array_layers: 1 },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.config.format,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-            self.bloom_texture_b = self.device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Bloom B"),
-                size: wgpu::Extent3d { width: bloom_w, height: bloom_h, depth_or_array_layers: 1 },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.config.format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             });
+            self.bloom_levels = create_bloom_levels(&self.device, self.config.width, self.config.height);
         }
     }
 
@@ -874,16 +1247,30 @@ impl Renderer {
             .create_view(&wgpu::TextureViewDescriptor::default())
     }
 
+    /// Strength of the bloom added back over the scene (default 0.42, 0 disables).
+    /// Takes effect at the next `update_cinematic_uniform`.
+    pub fn set_bloom_intensity(&mut self, intensity: f32) {
+        self.bloom_intensity = intensity.max(0.0);
+    }
+
+    /// Tint the final image blue-green while the camera is below water (0 = off, 1 = fully
+    /// submerged). Pair with `FogParams::underwater` for the short sight range.
+    /// Takes effect at the next `update_cinematic_uniform`.
+    pub fn set_underwater(&mut self, amount: f32) {
+        self.underwater = amount.clamp(0.0, 1.0);
+    }
+
     /// Update cinematic uniform (call once per frame before run_cinematic_pass).
     pub fn update_cinematic_uniform(&mut self, time: f32) {
-        let cinematic_uniform: [f32; 16] = [
+        let cinematic_uniform: [f32; 20] = [
             time,
             0.03,  // dither_strength
             0.38,  // vignette_strength
-            0.42,  // bloom_strength
+            self.bloom_intensity,
             0.0, 0.0, 0.0, 0.4,       // lift (neutral — no orange/amber piss filter), ssao_scale
             0.92, 0.92, 0.92, 0.018,  // inv_gamma, ssao_radius
             1.12, 1.08, 1.05, 0.002,  // gain, ssao_bias
+            UNDERWATER_TINT[0], UNDERWATER_TINT[1], UNDERWATER_TINT[2], self.underwater, // tint, amount
         ];
         self.queue.write_buffer(
             &self.cinematic_uniform_buffer,
@@ -892,16 +1279,25 @@ impl Renderer {
         );
     }
 
-    /// Run bloom passes: bright extract -> blur H -> blur V. Returns bloom texture view.
+    /// Run bloom passes: bright extract, then blur H/V down the level chain (each H pass
+    /// also halves the resolution), then add the levels back up. Returns the 1/4-res bloom view.
     pub fn run_bloom_passes(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         scene_view: &wgpu::TextureView,
     ) -> wgpu::TextureView {
-        let bloom_a_view = self.bloom_texture_a.create_view(&wgpu::TextureViewDescriptor::default());
-        let bloom_b_view = self.bloom_texture_b.create_view(&wgpu::TextureViewDescriptor::default());
+        let views: Vec<[wgpu::TextureView; 2]> = self
+            .bloom_levels
+            .iter()
+            .map(|[a, b]| {
+                [
+                    a.create_view(&wgpu::TextureViewDescriptor::default()),
+                    b.create_view(&wgpu::TextureViewDescriptor::default()),
+                ]
+            })
+            .collect();
 
-        // Bright pass: scene -> bloom_a
+        // Bright pass: scene -> level 0 A
         let bright_bind = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Bright Bind Group"),
             layout: &self.bright_bind_group_layout,
@@ -911,83 +1307,75 @@ impl Renderer {
                 wgpu::BindGroupEntry { binding: 2, resource: self.bright_uniform_buffer.as_entire_binding() },
             ],
         });
-        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Bright Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &bloom_a_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-        pass.set_pipeline(&self.bright_pipeline);
-        pass.set_bind_group(0, &bright_bind, &[]);
-        pass.draw(0..3, 0..1);
-        drop(pass);
+        self.fullscreen_pass(encoder, "Bright Pass", &views[0][0], &self.bright_pipeline, &bright_bind, true);
+
+        // Downsample + blur: previous level A -> this level B (H), B -> A (V)
+        for level in 0..views.len() {
+            let source = if level == 0 { &views[0][0] } else { &views[level - 1][0] };
+            let blur_bind_h = self.blur_bind_group(source, &self.blur_uniform_h);
+            self.fullscreen_pass(encoder, "Blur H Pass", &views[level][1], &self.blur_pipeline, &blur_bind_h, true);
+            let blur_bind_v = self.blur_bind_group(&views[level][1], &self.blur_uniform_v);
+            self.fullscreen_pass(encoder, "Blur V Pass", &views[level][0], &self.blur_pipeline, &blur_bind_v, true);
+        }
 
-        // Blur horizontal: bloom_a -> bloom_b
-        let blur_bind_h = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blur Bind H"),
-            layout: &self.blur_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&bloom_a_view) },
-                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.cinematic_sampler) },
-                wgpu::BindGroupEntry { binding: 2, resource: self.blur_uniform_h.as_entire_binding() },
-            ],
-        });
-        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Blur H Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &bloom_b_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-        pass.set_pipeline(&self.blur_pipeline);
-        pass.set_bind_group(0, &blur_bind_h, &[]);
-        pass.draw(0..3, 0..1);
-        drop(pass);
+        // Upsample: add each smaller level onto the next larger one, smallest first
+        for level in (1..views.len()).rev() {
+            let upsample_bind = self.blur_bind_group(&views[level][0], &self.bloom_upsample_uniform);
+            self.fullscreen_pass(
+                encoder,
+                "Bloom Upsample Pass",
+                &views[level - 1][0],
+                &self.bloom_upsample_pipeline,
+                &upsample_bind,
+                false,
+            );
+        }
+
+        views.into_iter().next().map(|[a, _]| a).expect("bloom chain has at least one level")
+    }
 
-        // Blur vertical: bloom_b -> bloom_a
-        let blur_bind_v = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blur Bind V"),
+    /// Bind group for the blur layout: input texture, shared sampler, uniform.
+    fn blur_bind_group(&self, input: &wgpu::TextureView, uniform: &wgpu::Buffer) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Blur Bind Group"),
             layout: &self.blur_bind_group_layout,
             entries: &[
-                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&bloom_b_view) },
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(input) },
                 wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.cinematic_sampler) },
-                wgpu::BindGroupEntry { binding: 2, resource: self.blur_uniform_v.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: uniform.as_entire_binding() },
             ],
-        });
+        })
+    }
+
+    /// Draw a fullscreen triangle into `target`, clearing it first or blending onto it.
+    fn fullscreen_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        label: &str,
+        target: &wgpu::TextureView,
+        pipeline: &wgpu::RenderPipeline,
+        bind_group: &wgpu::BindGroup,
+        clear: bool,
+    ) {
+        let load = if clear {
+            wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 })
+        } else {
+            wgpu::LoadOp::Load
+        };
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Blur V Pass"),
+            label: Some(label),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &bloom_a_view,
+                view: target,
                 resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
-                    store: wgpu::StoreOp::Store,
-                },
+                ops: wgpu::Operations { load, store: wgpu::StoreOp::Store },
             })],
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
         });
-        pass.set_pipeline(&self.blur_pipeline);
-        pass.set_bind_group(0, &blur_bind_v, &[]);
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
         pass.draw(0..3, 0..1);
-
-        bloom_a_view
     }
 
     /// Run cinematic post-process: scene + bloom + SSAO + color grading -> output.
@@ -1057,6 +1445,7 @@ impl Renderer {
     /// Update camera uniform. `planet_radius` > 0 enables curvature for instanced objects to match terrain.
     pub fn update_camera(&mut self, camera: &Camera, planet_radius: f32) {
         self.camera_uniform.update(camera, planet_radius);
+        self.camera_far = camera.far;
         self.queue.write_buffer(
             &self.camera_buffer,
             0,
@@ -1074,9 +1463,19 @@ impl Renderer {
         );
     }
 
+    /// Light the scene around `position` this frame (muzzle flash, explosion, lava). Register it
+    /// again each frame it should keep shining; `begin_frame` uploads the ones nearest the camera.
+    pub fn add_point_light(&mut self, position: glam::Vec3, color: [f32; 3], radius: f32, intensity: f32) {
+        self.point_lights.add(PointLight { position, color, radius, intensity });
+    }
+
     /// Begin a new frame, returns the command encoder and output view.
     pub fn begin_frame(&mut self) -> Result<(wgpu::SurfaceTexture, wgpu::CommandEncoder)> {
         self.frame_instance_offset = 0; // Reset per-frame instance offset
+        self.frame_particle_offset = 0;
+        let [x, y, z, _] = self.camera_uniform.position;
+        let lights = self.point_lights.take_uniform(glam::Vec3::new(x, y, z));
+        self.queue.write_buffer(&self.point_light_buffer, 0, bytemuck::cast_slice(&[lights]));
         let output = self.surface.get_current_texture()?;
         let encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
@@ -1084,6 +1483,139 @@ impl Renderer {
         Ok((output, encoder))
     }
 
+    /// Copy `instances` into a fresh region of the frame's instance buffer and return the index
+    /// of the first one. Grows the buffer when a frame needs more room; draws already recorded
+    /// keep the old buffer alive until they have run.
+    fn upload_instances(&mut self, instances: &[InstanceData]) -> u32 {
+        let offset = self.frame_instance_offset;
+        let needed = offset + instances.len() as u32;
+        if needed > self.max_instances {
+            let capacity = needed.next_power_of_two();
+            self.instance_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Instance Buffer"),
+                size: (std::mem::size_of::<InstanceData>() * capacity as usize) as u64,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.max_instances = capacity;
+        }
+        let byte_offset = (offset as usize * std::mem::size_of::<InstanceData>()) as u64;
+        self.queue.write_buffer(&self.instance_buffer, byte_offset, bytemuck::cast_slice(instances));
+        self.frame_instance_offset = needed;
+        offset
+    }
+
+    /// Begin a scene pass for batched instanced draws, loading existing frame content.
+    /// Issue any number of `draw_instanced` calls on it; each is one instanced draw.
+    pub fn begin_instanced_pass<'e>(
+        &self,
+        encoder: &'e mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+    ) -> wgpu::RenderPass<'e> {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Instanced Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.default_texture_bind_group, &[]);
+        render_pass.set_bind_group(2, &self.shadow_bind_group, &[]);
+        render_pass
+    }
+
+    /// Draw every instance of `mesh` with a single instanced draw call inside a pass from
+    /// `begin_instanced_pass`.
+    pub fn draw_instanced(
+        &mut self,
+        pass: &mut wgpu::RenderPass,
+        mesh: &Mesh,
+        instances: &[InstanceData],
+    ) {
+        if instances.is_empty() {
+            return;
+        }
+        let offset = self.upload_instances(instances);
+        pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(0..mesh.num_indices, 0, offset..(offset + instances.len() as u32));
+    }
+
+    /// Draw instances of a LOD mesh inside a pass from `begin_instanced_pass`, each at the level
+    /// for its squared camera distance. One instanced draw per level in use.
+    pub fn draw_instanced_lod(
+        &mut self,
+        pass: &mut wgpu::RenderPass,
+        lod: &LodMesh,
+        instances: &[(f32, InstanceData)],
+    ) {
+        let mut by_level: Vec<Vec<InstanceData>> = vec![Vec::new(); lod.level_count()];
+        for (dist_sq, instance) in instances {
+            by_level[lod.level_for_distance_sq(*dist_sq)].push(*instance);
+        }
+        for (level, level_instances) in by_level.iter().enumerate() {
+            self.draw_instanced(pass, lod.level(level), level_instances);
+        }
+    }
+
+    /// Create a material for the main pipeline from a tiling `size`² RGBA8 tangent-space
+    /// normal map (white albedo; color comes from the instances).
+    pub fn create_material(&self, label: &str, size: u32, normal_rgba: &[u8]) -> Material {
+        let normal_map = Texture::normal_map(&self.device, &self.queue, size, size, normal_rgba, label);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Material Bind Group"),
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&self.default_texture.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&normal_map.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&normal_map.view),
+                },
+            ],
+        });
+        Material { bind_group, _normal_map: normal_map }
+    }
+
+    /// `draw_instanced` with `material`'s textures bound instead of the defaults.
+    pub fn draw_instanced_with_material(
+        &mut self,
+        pass: &mut wgpu::RenderPass,
+        mesh: &Mesh,
+        instances: &[InstanceData],
+        material: &Material,
+    ) {
+        if instances.is_empty() {
+            return;
+        }
+        pass.set_bind_group(1, &material.bind_group, &[]);
+        self.draw_instanced(pass, mesh, instances);
+        pass.set_bind_group(1, &self.default_texture_bind_group, &[]);
+    }
+
     /// Render meshes with instancing.
     pub fn render_instanced(
         &mut self,
@@ -1096,19 +1628,8 @@ impl Renderer {
             return;
         }
 
-        // Allocate a unique region in the instance buffer for this draw call
-        let offset = self.frame_instance_offset;
-        let remaining = self.max_instances.saturating_sub(offset) as usize;
-        let instance_count = instances.len().min(remaining);
-        if instance_count == 0 { return; }
-
-        let byte_offset = (offset as usize * std::mem::size_of::<InstanceData>()) as u64;
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            byte_offset,
-            bytemuck::cast_slice(&instances[..instance_count]),
-        );
-        self.frame_instance_offset = offset + instance_count as u32;
+        let offset = self.upload_instances(instances);
+        let instance_count = instances.len();
 
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Render Pass"),
@@ -1159,19 +1680,8 @@ impl Renderer {
             return;
         }
 
-        // Allocate a unique region in the instance buffer for this draw call
-        let offset = self.frame_instance_offset;
-        let remaining = self.max_instances.saturating_sub(offset) as usize;
-        let instance_count = instances.len().min(remaining);
-        if instance_count == 0 { return; }
-
-        let byte_offset = (offset as usize * std::mem::size_of::<InstanceData>()) as u64;
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            byte_offset,
-            bytemuck::cast_slice(&instances[..instance_count]),
-        );
-        self.frame_instance_offset = offset + instance_count as u32;
+        let offset = self.upload_instances(instances);
+        let instance_count = instances.len();
 
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Render Pass (Load)"),
@@ -1205,6 +1715,63 @@ impl Renderer {
         render_pass.draw_indexed(0..mesh.num_indices, 0, offset..(offset + instance_count as u32));
     }
 
+
+    /// Leave a decal of `size` (m across) at `position` on a surface facing `normal`.
+    /// Past `MAX_DECALS` the oldest one disappears.
+    pub fn spawn_decal(&mut self, position: glam::Vec3, normal: glam::Vec3, size: f32, kind: DecalKind) {
+        self.decals.spawn(position, normal, size, kind);
+    }
+
+    /// Remove decals within `radius` of `center` (e.g. the structure they were on was destroyed).
+    pub fn clear_decals_near(&mut self, center: glam::Vec3, radius: f32) {
+        self.decals.clear_near(center, radius);
+    }
+
+    /// Remove every decal (leaving a planet or mission).
+    pub fn clear_decals(&mut self) {
+        self.decals.clear();
+    }
+
+    /// Draw all decals over the opaque scene (alpha blended, depth tested, no depth write).
+    pub fn render_decals(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        if self.decals.is_empty() {
+            return;
+        }
+        let instances = self.decals.instances();
+        let offset = self.upload_instances(&instances);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Decal Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.transparent_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.decal_bind_group, &[]);
+        render_pass.set_bind_group(2, &self.shadow_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.decal_mesh.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_index_buffer(self.decal_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.decal_mesh.num_indices, 0, offset..(offset + instances.len() as u32));
+    }
+
     /// Render viewmodel (gun) with no depth test so it always draws on top. Uses the renderer's own rifle mesh so this pass can never draw a bug mesh.
     pub fn render_viewmodel(
         &mut self,
@@ -1216,19 +1783,8 @@ impl Renderer {
             return;
         }
 
-        // Allocate a unique region in the instance buffer for this draw call
-        let offset = self.frame_instance_offset;
-        let remaining = self.max_instances.saturating_sub(offset) as usize;
-        let instance_count = instances.len().min(remaining);
-        if instance_count == 0 { return; }
-
-        let byte_offset = (offset as usize * std::mem::size_of::<InstanceData>()) as u64;
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            byte_offset,
-            bytemuck::cast_slice(&instances[..instance_count]),
-        );
-        self.frame_instance_offset = offset + instance_count as u32;
+        let offset = self.upload_instances(instances);
+        let instance_count = instances.len();
 
         let mesh = &self.viewmodel_mesh;
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -1263,14 +1819,13 @@ impl Renderer {
         render_pass.draw_indexed(0..mesh.num_indices, 0, offset..(offset + instance_count as u32));
     }
 
-    /// Update terrain uniform (sun, fog, biome colors, time, planet radius, chunk_size, deformation, snow).
+    /// Update terrain uniform (sun, biome colors, time, planet radius, chunk_size, deformation, snow). Fog is set via `set_fog`.
     /// Call before render_terrain. Deform origin is world (x,z) center of the deformation texture.
     /// `detail_scale`: higher = finer procedural detail (e.g. 2.7 for Earth).
     pub fn update_terrain(
         &mut self,
         time: f32,
         sun_direction: [f32; 4],
-        fog_params: [f32; 4],
         biome_colors: [[f32; 4]; 4],
         planet_radius: f32,
         chunk_size: f32,
@@ -1286,14 +1841,14 @@ impl Renderer {
         uniform.biome_params[2] = time;           // z = time
         uniform.biome_params[3] = planet_radius;  // w = planet radius for curvature
         uniform.sun_direction = sun_direction;
-        uniform.fog_params = fog_params;
         uniform.biome_colors = biome_colors;
         uniform.deform_params[0] = deform_origin_x;
         uniform.deform_params[1] = deform_origin_z;
         uniform.deform_params[2] = DEFORM_HALF_SIZE;
         uniform.deform_params[3] = if deform_enabled { 1.0 } else { 0.0 };
         uniform.snow_params[0] = if snow_enabled { 1.0 } else { 0.0 };
-        uniform.snow_params[1] = 1.0; // voxel flat color: use vertex (block/biome) color as albedo
+        uniform.snow_params[1] = 1.0; // voxel mode: vertex (block/biome) color tints the block material textures
+        uniform.blood_params = self.blood_params;
         self.queue
             .write_buffer(&self.terrain_buffer, 0, bytemuck::cast_slice(&[uniform]));
     }
@@ -1346,6 +1901,47 @@ impl Renderer {
         );
     }
 
+    /// Upload the blood decal stain map (256x256 f32s, 0..1) centered on world (x, z) `origin`.
+    /// `None` disables the overlay. Takes effect on the next `update_terrain`.
+    pub fn upload_terrain_blood(&mut self, data: Option<&[f32]>, origin_x: f32, origin_z: f32) {
+        let Some(data) = data else {
+            self.blood_params[3] = 0.0;
+            return;
+        };
+        debug_assert_eq!(data.len(), (DEFORM_TEXTURE_SIZE * DEFORM_TEXTURE_SIZE) as usize);
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.blood_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(data),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(DEFORM_TEXTURE_SIZE * 4),
+                rows_per_image: Some(DEFORM_TEXTURE_SIZE),
+            },
+            wgpu::Extent3d {
+                width: DEFORM_TEXTURE_SIZE,
+                height: DEFORM_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.blood_params = [origin_x, origin_z, DEFORM_HALF_SIZE, 1.0];
+    }
+
+    /// Upload terrain material layers (rock, dirt, sand, snow, grass): each is
+    /// `TERRAIN_MATERIAL_SIZE`² tileable RGBA8. Mips are built here. Layers should average
+    /// mid-grey; the shader multiplies them into the block color as detail.
+    pub fn upload_terrain_materials(&mut self, layers: &[Vec<u8>]) {
+        debug_assert_eq!(layers.len(), TERRAIN_MATERIAL_LAYERS as usize);
+        for (layer, pixels) in layers.iter().enumerate().take(TERRAIN_MATERIAL_LAYERS as usize) {
+            debug_assert_eq!(pixels.len(), (TERRAIN_MATERIAL_SIZE * TERRAIN_MATERIAL_SIZE * 4) as usize);
+            write_material_layer(&self.queue, &self.terrain_material_texture, layer as u32, pixels);
+        }
+    }
+
     /// Update sky uniform for dynamic time of day and weather. Call before render_sky.
     /// `time_of_day`: 0 = dawn, 0.25 = noon, 0.5 = dusk, 0.75 = midnight.
     /// `sun_dir`: pre-computed sun direction (from game sky_weather_params).
@@ -1354,8 +1950,9 @@ impl Renderer {
     /// `planet_radius`: conceptual planet sphere radius (for space rendering).
     /// `atmo_height`: atmosphere thickness above surface.
     /// `planet_surface_color`: average biome color (orbit, drop, surface — single source).
-    /// `atmosphere_color`: planet atmosphere tint for zenith/horizon (same in orbit, drop, surface).
+    /// `atmosphere`: the planet's sky palette (star + biome scattering), already tinted for weather.
     /// `skip_procedural_sun_moon`: if true, sky shader does not draw sun/moon discs (use physical celestial spheres).
+    /// Stars fade in on the surface at night, oriented by `set_star_seed` and turning with `time_of_day`.
     pub fn update_sky(
         &mut self,
         time_of_day: f32,
@@ -1366,7 +1963,7 @@ impl Renderer {
         planet_radius: f32,
         atmo_height: f32,
         planet_surface_color: [f32; 3],
-        atmosphere_color: [f32; 3],
+        atmosphere: &AtmosphereParams,
         skip_procedural_sun_moon: bool,
     ) {
         let t = time_of_day;
@@ -1387,17 +1984,18 @@ impl Renderer {
         }
 
         // Keyframe colors: [zenith, horizon, ground]
-        // Helldivers 2 / SST Extermination style: saturated, cinematic, dramatic
-        let dawn_zenith   = [0.12, 0.08, 0.35]; // deep blue-purple
-        let dawn_horizon  = [0.95, 0.50, 0.20]; // intense orange-pink
+        // Helldivers 2 / SST Extermination style: saturated, cinematic, dramatic. Day and twilight
+        // come from the planet's atmosphere (star + biome); twilight zeniths keep a dark violet base.
+        let dawn_zenith   = lerp3([0.12, 0.08, 0.35], atmosphere.zenith, 0.3); // deep blue-purple
+        let dawn_horizon  = lerp3(atmosphere.sunset, atmosphere.horizon, 0.25); // paler than dusk
         let dawn_ground   = [0.30, 0.22, 0.12];
 
-        let noon_zenith   = [0.15, 0.35, 0.85]; // rich blue sky
-        let noon_horizon  = [0.50, 0.60, 0.90]; // bright horizon band
+        let noon_zenith   = atmosphere.zenith;
+        let noon_horizon  = atmosphere.horizon;
         let noon_ground   = [0.45, 0.42, 0.35];
 
-        let dusk_zenith   = [0.12, 0.06, 0.22]; // deep purple
-        let dusk_horizon  = [0.98, 0.30, 0.08]; // intense red-orange
+        let dusk_zenith   = lerp3([0.12, 0.06, 0.22], atmosphere.zenith, 0.2); // deep purple
+        let dusk_horizon  = atmosphere.sunset;
         let dusk_ground   = [0.25, 0.12, 0.05];
 
         // Night: properly dark so night feels like night
@@ -1443,9 +2041,9 @@ impl Renderer {
 
         // Sun color: warm at low elevation, white at high elevation
         let sun_warmth = (1.0 - sun_dir[1].max(0.0)).powf(0.5);
-        let sun_r = 1.0;
-        let sun_g = 0.85 + (1.0 - sun_warmth) * 0.13;
-        let sun_b = 0.6 + (1.0 - sun_warmth) * 0.35;
+        let sun_r = atmosphere.sun_color[0];
+        let sun_g = (0.85 + (1.0 - sun_warmth) * 0.13) * atmosphere.sun_color[1];
+        let sun_b = (0.6 + (1.0 - sun_warmth) * 0.35) * atmosphere.sun_color[2];
 
         // Ground color: blend time-of-day keyframe with actual planet biome surface color
         let ground_final = [
@@ -1454,33 +2052,47 @@ impl Renderer {
             ground[2] * 0.3 + planet_surface_color[2] * 0.7,
         ];
 
-        // Zenith and horizon tinted by planet atmosphere so drop/surface match orbit
+        // Keep some of the daytime palette at every hour so the atmosphere reads from orbit and drop
         let atmo_k = 0.35;
-        let zenith_tinted = [
-            zenith[0] * (1.0 - atmo_k) + atmosphere_color[0] * atmo_k,
-            zenith[1] * (1.0 - atmo_k) + atmosphere_color[1] * atmo_k,
-            zenith[2] * (1.0 - atmo_k) + atmosphere_color[2] * atmo_k,
-        ];
-        let horizon_tinted = [
-            horizon[0] * (1.0 - atmo_k) + atmosphere_color[0] * atmo_k,
-            horizon[1] * (1.0 - atmo_k) + atmosphere_color[1] * atmo_k,
-            horizon[2] * (1.0 - atmo_k) + atmosphere_color[2] * atmo_k,
-        ];
+        let zenith = lerp3(zenith, atmosphere.zenith, atmo_k);
+        let horizon = lerp3(horizon, atmosphere.horizon, atmo_k);
 
         let mut u = SkyUniform::default();
         u.sun_direction = [sun_dir[0], sun_dir[1], sun_dir[2], sun_intensity];
         u.sun_color = [sun_r, sun_g, sun_b, 0.02]; // w = disk size
-        u.sky_color_zenith = [zenith_tinted[0], zenith_tinted[1], zenith_tinted[2], planet_radius];  // w = planet radius
-        u.sky_color_horizon = [horizon_tinted[0], horizon_tinted[1], horizon_tinted[2], atmo_height]; // w = atmo height
+        u.sky_color_zenith = [zenith[0], zenith[1], zenith[2], planet_radius];  // w = planet radius
+        u.sky_color_horizon = [horizon[0], horizon[1], horizon[2], atmo_height]; // w = atmo height
         u.ground_color = [ground_final[0], ground_final[1], ground_final[2], dust_amount];
         u.params[0] = time_of_day * 100.0;
         u.params[1] = cloud_density;
         u.params[2] = dust_amount;
         u.params[3] = planet_type;
         u.skip_procedural_sun_moon = [if skip_procedural_sun_moon { 1.0 } else { 0.0 }, 0.0, 0.0, 0.0];
+        let (star_yaw, star_tilt) = star_field_orientation(self.star_seed);
+        u.stars = [star_yaw, star_tilt, time_of_day * std::f32::consts::TAU, 0.0];
 
         self.queue
             .write_buffer(&self.sky_buffer, 0, bytemuck::cast_slice(&[u]));
+
+        self.sky_horizon_color = horizon;
+        self.write_fog_uniform();
+    }
+
+    /// Seed for the night-sky star field (e.g. the planet seed): each planet gets its own stable sky.
+    pub fn set_star_seed(&mut self, seed: u64) {
+        self.star_seed = seed;
+    }
+
+    /// Set atmospheric fog (distance + height falloff) for meshes, terrain and water. The color is
+    /// blended toward the sky horizon from `update_sky`, so call after it each frame.
+    pub fn set_fog(&mut self, params: FogParams) {
+        self.fog = params;
+        self.write_fog_uniform();
+    }
+
+    fn write_fog_uniform(&self) {
+        let u = FogUniform::new(&self.fog, self.sky_horizon_color, self.camera_far);
+        self.queue.write_buffer(&self.fog_buffer, 0, bytemuck::cast_slice(&[u]));
     }
 
     /// Render sky (fullscreen). Call first in frame after begin_frame; clears color and depth.
@@ -1573,6 +2185,66 @@ impl Renderer {
         render_pass.draw_indexed(0..mesh.num_indices, 0, 0..instance_count as u32);
     }
 
+    /// Draw every particle as a camera-facing billboard in a single instanced draw (alpha blended,
+    /// depth tested, unsorted). Loads existing frame content; call after opaque geometry.
+    pub fn draw_particles(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        particles: &[ParticleInstance],
+    ) {
+        if particles.is_empty() {
+            return;
+        }
+
+        let offset = self.frame_particle_offset;
+        let needed = offset + particles.len() as u32;
+        if needed > self.max_particles {
+            let capacity = needed.next_power_of_two();
+            self.particle_instance_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Particle Instance Buffer"),
+                size: (std::mem::size_of::<ParticleInstance>() * capacity as usize) as u64,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.max_particles = capacity;
+        }
+        let byte_offset = (offset as usize * std::mem::size_of::<ParticleInstance>()) as u64;
+        self.queue.write_buffer(&self.particle_instance_buffer, byte_offset, bytemuck::cast_slice(particles));
+        self.frame_particle_offset = needed;
+
+        let mesh = &self.particle_quad;
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Particle Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.particle_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.shadow_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.particle_instance_buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.num_indices, 0, offset..needed);
+    }
+
     /// Render terrain mesh with triplanar procedural shader. Use after render_sky (loads existing color/depth).
     pub fn render_terrain(
         &self,
@@ -1644,11 +2316,99 @@ impl Renderer {
 
         render_pass.set_pipeline(&self.water_pipeline);
         render_pass.set_bind_group(0, &self.terrain_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.shadow_bind_group, &[]);
         render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         render_pass.draw_indexed(0..mesh.num_indices, 0, 0..1);
     }
 
+    /// Draw queued transparent surfaces (water, blended particles) farthest-first in one pass.
+    /// Depth is tested but not written. Call after all opaque geometry.
+    pub fn render_transparent(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        camera: &Camera,
+        queue: TransparentQueue,
+    ) {
+        if queue.is_empty() {
+            return;
+        }
+
+        // Merge runs of the same mesh into one instanced draw; upload all instances at once
+        enum Batch<'a> {
+            Water(&'a Mesh),
+            Instances(&'a Mesh, std::ops::Range<u32>),
+        }
+        let mut instances: Vec<InstanceData> = Vec::with_capacity(queue.len());
+        let mut batches: Vec<Batch> = Vec::new();
+        for draw in queue.into_sorted(camera.position(), camera.forward()) {
+            match draw {
+                TransparentDraw::Water(mesh) => batches.push(Batch::Water(mesh)),
+                TransparentDraw::Instance(mesh, instance) => {
+                    let index = instances.len() as u32;
+                    instances.push(instance);
+                    if let Some(Batch::Instances(prev, range)) = batches.last_mut() {
+                        if std::ptr::eq(*prev, mesh) {
+                            range.end = index + 1;
+                            continue;
+                        }
+                    }
+                    batches.push(Batch::Instances(mesh, index..index + 1));
+                }
+            }
+        }
+        let base = if instances.is_empty() { 0 } else { self.upload_instances(&instances) };
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Transparent Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        for batch in batches {
+            match batch {
+                Batch::Water(mesh) => {
+                    if mesh.num_indices == 0 {
+                        continue;
+                    }
+                    render_pass.set_pipeline(&self.water_pipeline);
+                    render_pass.set_bind_group(0, &self.terrain_bind_group, &[]);
+                    render_pass.set_bind_group(1, &self.shadow_bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                    render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..mesh.num_indices, 0, 0..1);
+                }
+                Batch::Instances(mesh, range) => {
+                    render_pass.set_pipeline(&self.transparent_pipeline);
+                    render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                    render_pass.set_bind_group(1, &self.default_texture_bind_group, &[]);
+                    render_pass.set_bind_group(2, &self.shadow_bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                    render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+                    render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..mesh.num_indices, 0, (base + range.start)..(base + range.end));
+                }
+            }
+        }
+    }
+
     /// Render screen-space text overlay. Call as the very last pass before end_frame.
     /// Takes pre-built overlay vertices and indices from an `OverlayTextBuilder`.
     pub fn render_overlay(
@@ -1721,3 +2481,108 @@ impl Renderer {
         &self.depth_texture.view
     }
 }
+
+/// Ping-pong textures for each bloom level, starting at 1/4 of the screen and halving.
+fn create_bloom_levels(device: &wgpu::Device, width: u32, height: u32) -> Vec<[wgpu::Texture; 2]> {
+    (0..BLOOM_LEVELS)
+        .map(|level| {
+            let size = wgpu::Extent3d {
+                width: (width >> (level + 2)).max(1),
+                height: (height >> (level + 2)).max(1),
+                depth_or_array_layers: 1,
+            };
+            let texture = |label: &str| {
+                device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: HDR_FORMAT,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+            };
+            [texture("Bloom A"), texture("Bloom B")]
+        })
+        .collect()
+}
+
+/// Write one terrain material layer and its box-filtered mip chain.
+fn write_material_layer(queue: &wgpu::Queue, texture: &wgpu::Texture, layer: u32, pixels: &[u8]) {
+    let mut size = TERRAIN_MATERIAL_SIZE;
+    let mut level = pixels.to_vec();
+    for mip in 0..texture.mip_level_count() {
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: mip,
+                origin: wgpu::Origin3d { x: 0, y: 0, z: layer },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &level,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(size * 4),
+                rows_per_image: Some(size),
+            },
+            wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+        );
+        if size > 1 {
+            level = downsample_rgba(&level, size);
+            size /= 2;
+        }
+    }
+}
+
+/// Halve a square RGBA8 image by averaging each 2x2 block.
+fn downsample_rgba(pixels: &[u8], size: u32) -> Vec<u8> {
+    let size = size as usize;
+    let half = size / 2;
+    let mut out = vec![0u8; half * half * 4];
+    for y in 0..half {
+        for x in 0..half {
+            for c in 0..4 {
+                let at = |dx: usize, dy: usize| pixels[((y * 2 + dy) * size + x * 2 + dx) * 4 + c] as u32;
+                out[(y * half + x) * 4 + c] = ((at(0, 0) + at(1, 0) + at(0, 1) + at(1, 1) + 2) / 4) as u8;
+            }
+        }
+    }
+    out
+}
+
+/// Stable (yaw, tilt) in radians for a star-field seed; splitmix64 so neighbouring seeds differ.
+fn star_field_orientation(seed: u64) -> (f32, f32) {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    let yaw = (z & 0xFFFF) as f32 / 65536.0 * std::f32::consts::TAU;
+    let tilt = ((z >> 16) & 0xFFFF) as f32 / 65536.0 * std::f32::consts::PI - std::f32::consts::FRAC_PI_2;
+    (yaw, tilt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downsample_rgba, star_field_orientation};
+
+    #[test]
+    fn star_field_is_stable_per_seed_and_differs_between_planets() {
+        assert_eq!(star_field_orientation(42), star_field_orientation(42));
+        assert_ne!(star_field_orientation(42), star_field_orientation(43));
+        let (yaw, tilt) = star_field_orientation(7);
+        assert!((0.0..std::f32::consts::TAU).contains(&yaw));
+        assert!(tilt.abs() <= std::f32::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn material_mips_average_each_block_of_four() {
+        let pixels: Vec<u8> = [[0, 0, 0, 255], [100, 100, 100, 255], [200, 200, 200, 255], [100, 100, 100, 255]]
+            .concat();
+        assert_eq!(downsample_rgba(&pixels, 2), vec![100, 100, 100, 255]);
+    }
+}
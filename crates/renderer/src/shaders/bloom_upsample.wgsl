@@ -0,0 +1,49 @@
+// Bloom upsample: tent-filter a smaller bloom level and add it onto the next larger one.
+// Drawn with additive blending, so the destination keeps its own blurred contents.
+
+struct UpsampleUniform {
+    weight: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+};
+
+@group(0) @binding(0)
+var input_tex: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> params: UpsampleUniform;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vi: u32) -> VertexOutput {
+    let x = f32((vi << 1u) & 2u);
+    let y = f32(vi & 2u);
+    var out: VertexOutput;
+    out.position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, y);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(input_tex));
+
+    // 3x3 tent (1 2 1 / 2 4 2 / 1 2 1) hides the blockiness of the smaller level
+    var color = textureSample(input_tex, input_sampler, in.uv).rgb * 4.0;
+    color += textureSample(input_tex, input_sampler, in.uv + vec2<f32>(texel.x, 0.0)).rgb * 2.0;
+    color += textureSample(input_tex, input_sampler, in.uv - vec2<f32>(texel.x, 0.0)).rgb * 2.0;
+    color += textureSample(input_tex, input_sampler, in.uv + vec2<f32>(0.0, texel.y)).rgb * 2.0;
+    color += textureSample(input_tex, input_sampler, in.uv - vec2<f32>(0.0, texel.y)).rgb * 2.0;
+    color += textureSample(input_tex, input_sampler, in.uv + texel).rgb;
+    color += textureSample(input_tex, input_sampler, in.uv - texel).rgb;
+    color += textureSample(input_tex, input_sampler, in.uv + vec2<f32>(texel.x, -texel.y)).rgb;
+    color += textureSample(input_tex, input_sampler, in.uv + vec2<f32>(-texel.x, texel.y)).rgb;
+
+    return vec4<f32>(color / 16.0 * params.weight, 1.0);
+}
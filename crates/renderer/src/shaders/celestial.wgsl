@@ -174,7 +174,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         // HDR intensity
         star_color *= 2.5;
 
-        return vec4<f32>(star_color, 1.0);
+        return vec4<f32>(min(star_color, vec3<f32>(1.0)), 1.0);
 
     } else {
         // ===== PLANET / MOON =====
@@ -293,6 +293,6 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         let terminator = smoothstep(-0.08, 0.12, ndl);
         planet_color *= terminator + (1.0 - terminator) * 0.12;
 
-        return vec4<f32>(planet_color, 1.0);
+        return vec4<f32>(min(planet_color, vec3<f32>(1.0)), 1.0);
     }
 }
@@ -12,6 +12,7 @@ struct CinematicUniform {
     ssao_radius: f32,
     gain: vec3<f32>,   // highlight punch
     ssao_bias: f32,
+    tint: vec4<f32>,   // rgb = screen tint (underwater), w = amount
 };
 
 @group(0) @binding(0)
@@ -86,6 +87,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let dither = (bayer - 0.5) * cinematic.dither_strength;
     color += dither;
 
+    // --- Screen tint (underwater): keep brightness, pull the hue toward the tint color ---
+    let tint_luma = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+    let tinted = tint_luma * cinematic.tint.rgb * 2.0 + cinematic.tint.rgb * 0.12;
+    color = mix(color, tinted, cinematic.tint.w);
+
     // --- Lift / Gamma / Gain (color grading) ---
     // Lift: shadow tint (neutral = no orange/amber filter)
     color = color + cinematic.lift * (1.0 - color);
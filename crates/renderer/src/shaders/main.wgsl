@@ -17,27 +17,120 @@ var<uniform> camera: CameraUniform;
 var t_albedo: texture_2d<f32>;
 @group(1) @binding(1)
 var s_albedo: sampler;
+// Tangent-space normal map (flat by default), sampled with s_albedo
+@group(1) @binding(2)
+var t_normal: texture_2d<f32>;
+
+const MAX_SHADOW_CASCADES: u32 = 4u;
 
 struct ShadowUniform {
-    light_view_proj: mat4x4<f32>,
+    cascade_view_proj: array<mat4x4<f32>, MAX_SHADOW_CASCADES>,
+    // Far view-space depth of each cascade
+    cascade_splits: vec4<f32>,
     camera_pos: vec3<f32>,
     planet_radius: f32,
-    _pad: vec3<f32>,
+    camera_forward: vec3<f32>,
+    cascade_count: u32,
 }
 
 @group(2) @binding(0)
 var<uniform> shadow: ShadowUniform;
 
 @group(2) @binding(1)
-var shadow_tex: texture_depth_2d;
+var shadow_tex: texture_depth_2d_array;
 
 @group(2) @binding(2)
 var shadow_sampler: sampler_comparison;
 
+struct FogUniform {
+    color_density: vec4<f32>,  // rgb = fog color (biome haze blended toward sky horizon), w = density per meter
+    params: vec4<f32>,         // x = height_falloff, y = base_height, z = far (full fade), w unused
+}
+
+@group(2) @binding(3)
+var<uniform> fog: FogUniform;
+
+const MAX_POINT_LIGHTS: u32 = 16u;
+
+struct PointLight {
+    position_radius: vec4<f32>,  // xyz = world position, w = radius (light reaches zero there)
+    color_intensity: vec4<f32>,  // rgb = color, w = intensity
+}
+
+struct PointLightUniform {
+    lights: array<PointLight, MAX_POINT_LIGHTS>,
+    count: vec4<u32>,            // x = active lights
+}
+
+@group(2) @binding(4)
+var<uniform> point_lights: PointLightUniform;
+
+// Diffuse light from the frame's dynamic point lights (muzzle flashes, explosions, lava) at a
+// surface point. Light positions get the same curvature drop as the geometry they illuminate.
+fn point_light_diffuse(p: vec3<f32>, n: vec3<f32>) -> vec3<f32> {
+    var total = vec3<f32>(0.0);
+    // The viewmodel pass (camera.position.w == 0) is in view space, where world lights don't line up
+    let count = select(0u, min(point_lights.count.x, MAX_POINT_LIGHTS), camera.position.w > 0.5);
+    for (var i = 0u; i < count; i = i + 1u) {
+        let light = point_lights.lights[i];
+        var light_pos = light.position_radius.xyz;
+        if (camera.planet_radius > 0.0) {
+            let d = light_pos.xz - camera.position.xz;
+            light_pos.y -= dot(d, d) / (2.0 * camera.planet_radius);
+        }
+        let to_light = light_pos - p;
+        let dist = length(to_light);
+        let radius = light.position_radius.w;
+        if (dist >= radius) {
+            continue;
+        }
+        // Smooth window to zero at the radius, inverse-square-ish inside it
+        let x = dist / radius;
+        let window = (1.0 - x * x) * (1.0 - x * x);
+        let falloff = window / (1.0 + dist * dist * 0.25);
+        let n_dot_l = max(dot(n, to_light / max(dist, 1e-4)), 0.0);
+        total += light.color_intensity.rgb * light.color_intensity.w * n_dot_l * falloff;
+    }
+    return total;
+}
+
+// Exponential height fog integrated along the view ray (thick in valleys, thin up high), plus a
+// fade to full fog near the far plane so distant geometry never clips against the sky.
+fn fog_amount(cam: vec3<f32>, p: vec3<f32>) -> f32 {
+    let density = fog.color_density.w;
+    if (density <= 0.0) {
+        return 0.0;
+    }
+    let dist = length(p - cam);
+    let falloff = fog.params.x;
+    let cam_h = max(cam.y - fog.params.y, 0.0);
+    var optical = density * exp(-falloff * cam_h) * dist;
+    let k = clamp(falloff * (p.y - cam.y), -60.0, 60.0);
+    if (abs(k) > 0.001) {
+        optical *= (1.0 - exp(-k)) / k;
+    }
+    let far = fog.params.z;
+    let far_fade = smoothstep(far * 0.75, far * 0.98, dist);
+    return clamp(max(1.0 - exp(-optical), far_fade), 0.0, 1.0);
+}
+
+// Index of the shadow cascade covering this point's view depth, or -1 beyond the last one.
+fn shadow_cascade(world_pos: vec3<f32>) -> i32 {
+    let depth = dot(world_pos - shadow.camera_pos, shadow.camera_forward);
+    for (var i = 0u; i < shadow.cascade_count; i = i + 1u) {
+        if (depth < shadow.cascade_splits[i]) {
+            return i32(i);
+        }
+    }
+    return -1;
+}
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) uv: vec2<f32>,
+    // xyz = +u direction, w = bitangent sign; zero when the mesh has no tangents
+    @location(9) tangent: vec4<f32>,
 };
 
 struct InstanceInput {
@@ -46,6 +139,9 @@ struct InstanceInput {
     @location(5) model_matrix_2: vec4<f32>,
     @location(6) model_matrix_3: vec4<f32>,
     @location(7) color: vec4<f32>,
+    @location(8) emissive: f32,
+    // x = roughness, y = metallic
+    @location(10) material: vec2<f32>,
 };
 
 struct VertexOutput {
@@ -54,6 +150,9 @@ struct VertexOutput {
     @location(1) world_normal: vec3<f32>,
     @location(2) uv: vec2<f32>,
     @location(3) color: vec4<f32>,
+    @location(4) @interpolate(flat) emissive: f32,
+    @location(5) world_tangent: vec4<f32>,
+    @location(6) @interpolate(flat) material: vec2<f32>,
 };
 
 @vertex
@@ -89,33 +188,48 @@ fn vs_main(
     out.world_position = world_pos;
     out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
     out.world_normal = normalize(normal_matrix * vertex.normal);
+    out.world_tangent = vec4<f32>(normal_matrix * vertex.tangent.xyz, vertex.tangent.w);
+    out.material = instance.material;
     out.uv = vertex.uv;
     out.color = instance.color;
+    out.emissive = instance.emissive;
 
     return out;
 }
 
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    // Sample texture
+// Shared shading for the opaque and transparent entry points. Alpha is albedo * instance alpha.
+fn shade(in: VertexOutput) -> vec4<f32> {
+    // Sample textures (before any branch: derivatives need uniform control flow)
     let albedo = textureSample(t_albedo, s_albedo, in.uv);
+    let normal_sample = textureSample(t_normal, s_albedo, in.uv).xyz * 2.0 - 1.0;
 
     // Use camera Y to determine if we're on a planet surface or in space.
     // On a planet surface, reduce lighting at night (camera at low altitude).
     // The terrain shader has its own dynamic sun direction; this shader approximates it.
     let altitude = camera.position.y;
 
-    // Emissive objects (color alpha > 0.9 AND very bright channel) bypass normal lighting.
-    // This lets embers, fireflies, tracers, muzzle flashes glow properly.
+    // Emissive objects (per-instance emissive flag, or a very bright color channel) bypass
+    // normal lighting. This lets glow parts, embers, tracers, muzzle flashes glow properly.
     let max_channel = max(max(in.color.r, in.color.g), in.color.b);
-    let is_emissive = (max_channel > 1.5);
+    let is_emissive = in.emissive > 0.0 || max_channel > 1.5;
 
     if (is_emissive) {
-        // Emissive particles: just output the color directly, no lighting
+        // Emissive: output the color directly, no lighting. Left unclamped (HDR) so the
+        // bright pass picks it up for bloom.
         let final_alpha = albedo.a * in.color.a;
         if (final_alpha < 0.15) { discard; }
-        let emit_color = in.color.rgb * albedo.rgb;
-        return vec4<f32>(clamp(emit_color, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+        let emit_color = in.color.rgb * albedo.rgb * (1.0 + in.emissive);
+        return vec4<f32>(max(emit_color, vec3<f32>(0.0)), final_alpha);
+    }
+
+    // Shading normal: the normal map tilts it through the mesh's tangent frame
+    let geo_n = normalize(in.world_normal);
+    var n = geo_n;
+    let tangent = in.world_tangent.xyz - geo_n * dot(geo_n, in.world_tangent.xyz);
+    if (dot(tangent, tangent) > 1e-6) {
+        let t = normalize(tangent);
+        let b = cross(geo_n, t) * in.world_tangent.w;
+        n = normalize(t * normal_sample.x + b * normal_sample.y + geo_n * normal_sample.z);
     }
 
     // MIRO + Starship Troopers: cel/toon lighting (stylized, colorful)
@@ -124,43 +238,54 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let ambient = vec3<f32>(0.12, 0.12, 0.18); // Brighter ambient for stylized look
 
     // Cel shading: 3-step diffuse
-    let ndotl = max(dot(in.world_normal, light_dir), 0.0);
+    let ndotl = max(dot(n, light_dir), 0.0);
     let half_lambert = ndotl * 0.7 + 0.3;
     let toon_lambert = floor(half_lambert * 3.0 + 0.5) / 3.0;
     var diffuse = light_color * toon_lambert * 0.85;
 
-    // Shadow map: sample sun shadow
-    let light_clip = shadow.light_view_proj * vec4<f32>(in.world_position, 1.0);
+    // Shadow map: sample the sun shadow cascade covering this depth
+    let cascade = shadow_cascade(in.world_position);
+    let light_clip = shadow.cascade_view_proj[max(cascade, 0)] * vec4<f32>(in.world_position, 1.0);
     let light_ndc = light_clip.xyz / light_clip.w;
-    let shadow_uv = light_ndc.xy * 0.5 + 0.5;
-    let depth_compare = light_ndc.z * 0.5 + 0.5 + 0.002;
-    let in_bounds = all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0));
-    let shadow_factor = select(1.0, textureSampleCompare(shadow_tex, shadow_sampler, shadow_uv, depth_compare), in_bounds);
+    let shadow_uv = vec2<f32>(light_ndc.x * 0.5 + 0.5, 0.5 - light_ndc.y * 0.5);
+    let depth_compare = light_ndc.z + 0.002;
+    let in_bounds = cascade >= 0 && all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0));
+    let sampled = textureSampleCompareLevel(shadow_tex, shadow_sampler, shadow_uv, max(cascade, 0), depth_compare);
+    let shadow_factor = select(1.0, sampled, in_bounds);
     diffuse *= shadow_factor;
 
-    // View direction for specular and rim
+    let base_color = albedo.rgb * in.color.rgb;
+    let roughness = clamp(in.material.x, 0.05, 1.0);
+    let metallic = clamp(in.material.y, 0.0, 1.0);
+
+    // Blinn-Phong specular, energy-normalized, with Schlick Fresnel. Metals tint their
+    // highlights with the base color; rough surfaces get broad, dim highlights.
     let view_dir = normalize(camera.position.xyz - in.world_position);
     let half_dir = normalize(light_dir + view_dir);
-    let spec_power = 32.0; // Softer spec for stylized
-    let spec = pow(max(dot(in.world_normal, half_dir), 0.0), spec_power) * 0.06;
+    let n_dot_v = max(dot(n, view_dir), 0.0);
+    let f0 = mix(vec3<f32>(0.04), base_color, metallic);
+    let fresnel = f0 + (max(vec3<f32>(1.0 - roughness), f0) - f0) * pow(1.0 - n_dot_v, 5.0);
+    let spec_power = exp2(10.0 * (1.0 - roughness) + 1.0);
+    let spec_norm = (spec_power + 8.0) / 25.13;
+    let spec = fresnel * spec_norm * pow(max(dot(n, half_dir), 0.0), spec_power) * ndotl * light_color * shadow_factor;
+
+    // Cheap environment reflection (sky above, dark deck/ground below) so metal reads as metal
+    let refl = reflect(-view_dir, n);
+    let env = mix(vec3<f32>(0.07, 0.065, 0.07), vec3<f32>(0.34, 0.38, 0.46), smoothstep(-0.3, 0.6, refl.y));
+    let env_spec = env * fresnel * (1.0 - roughness * 0.75);
 
     // MIRO-style bold rim: colorful edge glow
-    let rim = pow(1.0 - max(dot(in.world_normal, view_dir), 0.0), 2.5);
+    let rim = pow(1.0 - n_dot_v, 2.5);
     let rim_color = vec3<f32>(0.22, 0.28, 0.42) * rim;
-    let rim_spec = pow(max(dot(in.world_normal, half_dir), 0.0), 12.0) * rim * 0.15;
 
-    // Combine lighting with color
-    let base_color = albedo.rgb * in.color.rgb;
-    let lit_color = base_color * (ambient + diffuse + rim_color) + vec3<f32>(spec + rim_spec) * base_color;
-
-    // Simple distance fog (MIRO-style: slightly more saturated)
-    let fog_color = vec3<f32>(0.38, 0.34, 0.32);
-    let fog_start = 60.0;
-    let fog_end = 400.0;
-    let dist = length(camera.position.xyz - in.world_position);
-    let fog_factor = clamp((dist - fog_start) / (fog_end - fog_start), 0.0, 0.85);
+    // Combine lighting with color (metal keeps a little diffuse for the stylized look)
+    let diffuse_color = base_color * (1.0 - metallic * 0.8);
+    let dynamic_light = point_light_diffuse(in.world_position, n);
+    let lit_color = diffuse_color * (ambient + diffuse + rim_color + dynamic_light) + spec + env_spec;
 
-    let final_color = mix(lit_color, fog_color, fog_factor);
+    // Atmospheric fog (biome + weather density, sky-tinted color)
+    let fog_factor = fog_amount(camera.position.xyz, in.world_position);
+    let final_color = mix(lit_color, fog.color_density.rgb, fog_factor);
     let final_alpha = albedo.a * in.color.a;
 
     // Discard fragments with low alpha (particles that would be transparent)
@@ -168,5 +293,17 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return vec4<f32>(final_color, 1.0);
+    // Lit surfaces stay in LDR range so only emissive instances bloom
+    return vec4<f32>(min(final_color, vec3<f32>(1.0)), final_alpha);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(shade(in).rgb, 1.0);
+}
+
+// Transparent pass (sorted back-to-front, alpha blended, no depth write)
+@fragment
+fn fs_transparent(in: VertexOutput) -> @location(0) vec4<f32> {
+    return shade(in);
 }
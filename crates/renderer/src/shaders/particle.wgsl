@@ -0,0 +1,117 @@
+// Billboard particle shader: one instanced draw for rain, snow, dust, smoke and fire.
+// Quads are turned to face the camera here instead of per particle on the CPU.
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    proj: mat4x4<f32>,
+    position: vec4<f32>,
+    planet_radius: f32,
+    _pad: vec3<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct FogUniform {
+    color_density: vec4<f32>,  // rgb = fog color, w = density per meter
+    params: vec4<f32>,         // x = height_falloff, y = base_height, z = far (full fade), w unused
+}
+
+// Shadow sampling group (shared with main/terrain/water); particles only read the fog.
+@group(1) @binding(3)
+var<uniform> fog: FogUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) tex_coords: vec2<f32>,
+};
+
+struct InstanceInput {
+    @location(3) inst_position: vec3<f32>,
+    @location(4) inst_rotation: f32,
+    @location(5) inst_size: vec2<f32>,
+    @location(6) inst_upright: f32,
+    @location(7) inst_color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+// Same height fog as main.wgsl so particles fade with the geometry around them.
+fn fog_amount(cam: vec3<f32>, p: vec3<f32>) -> f32 {
+    let density = fog.color_density.w;
+    if (density <= 0.0) {
+        return 0.0;
+    }
+    let dist = length(p - cam);
+    let falloff = fog.params.x;
+    let cam_h = max(cam.y - fog.params.y, 0.0);
+    var optical = density * exp(-falloff * cam_h) * dist;
+    let k = clamp(falloff * (p.y - cam.y), -60.0, 60.0);
+    if (abs(k) > 0.001) {
+        optical *= (1.0 - exp(-k)) / k;
+    }
+    let far = fog.params.z;
+    let far_fade = smoothstep(far * 0.75, far * 0.98, dist);
+    return clamp(max(1.0 - exp(-optical), far_fade), 0.0, 1.0);
+}
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    // Camera axes are the rows of the view matrix
+    var right = vec3<f32>(camera.view[0].x, camera.view[1].x, camera.view[2].x);
+    var up = vec3<f32>(camera.view[0].y, camera.view[1].y, camera.view[2].y);
+    if (instance.inst_upright > 0.5) {
+        // Upright billboards (rain streaks) only turn about world Y
+        let to_cam = camera.position.xz - instance.inst_position.xz;
+        let len = length(to_cam);
+        var dir = vec2<f32>(1.0, 0.0);
+        if (len > 1e-4) {
+            dir = to_cam / len;
+        }
+        right = vec3<f32>(-dir.y, 0.0, dir.x);
+        up = vec3<f32>(0.0, 1.0, 0.0);
+    }
+
+    let c = cos(instance.inst_rotation);
+    let s = sin(instance.inst_rotation);
+    let corner = vertex.position.xy * instance.inst_size;
+    let offset = vec2<f32>(corner.x * c - corner.y * s, corner.x * s + corner.y * c);
+    var world_pos = instance.inst_position + right * offset.x + up * offset.y;
+
+    // Planetary curvature: match terrain shader so particles stay with the ground
+    let planet_radius = camera.planet_radius;
+    if (planet_radius > 0.0) {
+        let dx = world_pos.x - camera.position.x;
+        let dz = world_pos.z - camera.position.z;
+        world_pos.y -= (dx * dx + dz * dz) / (2.0 * planet_radius);
+    }
+
+    var out: VertexOutput;
+    out.world_position = world_pos;
+    out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
+    out.color = instance.inst_color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (in.color.a < 0.15) {
+        discard;
+    }
+    // Bright colors (fire cores, embers) are emissive and left in HDR for bloom
+    let max_channel = max(max(in.color.r, in.color.g), in.color.b);
+    if (max_channel > 1.5) {
+        return in.color;
+    }
+    // Flat camera-facing quads: constant cel-lit tone, then the scene fog
+    let lit = in.color.rgb * vec3<f32>(0.82, 0.78, 0.78);
+    let fog_factor = fog_amount(camera.position.xyz, in.world_position);
+    let color = mix(lit, fog.color_density.rgb, fog_factor);
+    return vec4<f32>(min(color, vec3<f32>(1.0)), in.color.a);
+}
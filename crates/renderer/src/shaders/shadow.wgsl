@@ -1,7 +1,7 @@
 // Depth-only shadow pass: terrain and instanced meshes from sun's POV.
-// Outputs depth to shadow map; no color target.
+// Outputs depth to one shadow cascade layer; no color target.
 
-struct ShadowUniform {
+struct ShadowPassUniform {
     light_view_proj: mat4x4<f32>,
     camera_pos: vec3<f32>,
     planet_radius: f32,
@@ -9,7 +9,7 @@ struct ShadowUniform {
 }
 
 @group(0) @binding(0)
-var<uniform> shadow: ShadowUniform;
+var<uniform> shadow: ShadowPassUniform;
 
 struct VertexInput {
     @location(0) position: vec3<f32>,
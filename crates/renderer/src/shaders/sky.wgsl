@@ -21,6 +21,7 @@ struct SkyUniform {
     ground_color: vec4<f32>,      // rgb = planet surface color, w = haze amount
     params: vec4<f32>,            // x = time, y = cloud_density, z = dust_amount, w = planet_type
     skip_procedural_sun_moon: vec4<f32>, // x = 1.0 to skip procedural sun/moon (use physical spheres)
+    stars: vec4<f32>,             // x, y = per-planet star orientation (yaw, tilt), z = sky rotation, w unused
 };
 
 @group(0) @binding(0)
@@ -407,6 +408,21 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     return out;
 }
 
+// Rotate a view direction into the planet's star frame: the sky turns with time of day about the
+// spin axis, and each planet has its own orientation so its constellations stay put between visits.
+fn star_frame(dir: vec3<f32>) -> vec3<f32> {
+    let spin = sky.stars.z;
+    let cs = cos(spin);
+    let ss = sin(spin);
+    var d = vec3<f32>(dir.x, dir.y * cs - dir.z * ss, dir.y * ss + dir.z * cs);
+    let cy = cos(sky.stars.x);
+    let sy = sin(sky.stars.x);
+    d = vec3<f32>(d.x * cy + d.z * sy, d.y, -d.x * sy + d.z * cy);
+    let ct = cos(sky.stars.y);
+    let st = sin(sky.stars.y);
+    return vec3<f32>(d.x * ct - d.y * st, d.x * st + d.y * ct, d.z);
+}
+
 // ============================================================================
 // FRAGMENT SHADER
 // ============================================================================
@@ -440,9 +456,20 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // ===== ATMOSPHERIC SKY (surface) =====
     let up_dot = view_dir.y;
-    let zenith_blend = pow(max(up_dot, 0.0), mix(0.35, 0.6, day_factor));
+    // Horizon-to-zenith gradient: eases out of the horizon band instead of snapping to the zenith color
+    let elevation = clamp(up_dot, 0.0, 1.0);
+    let zenith_blend = smoothstep(0.0, 1.0, pow(elevation, mix(0.45, 0.7, day_factor)));
 
     var atmo_color = mix(sky.sky_color_horizon.rgb, sky.sky_color_zenith.rgb, zenith_blend);
+    // Soft glow hugging the horizon
+    atmo_color += sky.sky_color_horizon.rgb * exp(-elevation * 12.0) * 0.12;
+
+    // Night sky: the planet's star field fades in after sunset (hidden by clouds, dust and the horizon)
+    let star_vis = smoothstep(0.08, -0.12, sun_dir.y) * (1.0 - cloud_density * 0.85)
+        * (1.0 - clamp(dust_amount, 0.0, 1.0) * 0.5) * smoothstep(-0.02, 0.12, up_dot);
+    if (star_vis > 0.001) {
+        atmo_color += starfield(star_frame(view_dir), time * 1.8) * star_vis * 0.9;
+    }
 
     // Mie scattering — stronger, more cinematic sun halo
     let sun_dot = dot(view_dir, sun_dir);
@@ -744,5 +771,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let e = 0.14;
     sky_color = clamp((sky_color * (sky_color * a + b)) / (sky_color * (sky_color * c + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
 
-    return vec4<f32>(sky_color, 1.0);
+    return vec4<f32>(min(sky_color, vec3<f32>(1.0)), 1.0);
 }
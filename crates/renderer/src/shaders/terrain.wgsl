@@ -15,9 +15,9 @@ struct TerrainUniform {
     biome_colors: array<vec4<f32>, 4>,
     biome_params: vec4<f32>,           // x = blend_sharpness, y = detail_scale, z = time, w = planet_radius
     sun_direction: vec4<f32>,
-    fog_params: vec4<f32>,            // x = density, y = height_falloff, z = start, w = end
     deform_params: vec4<f32>,         // x = origin_x, y = origin_z, z = half_size, w = enabled
-    snow_params: vec4<f32>,           // x = snow_enabled, y = voxel_flat_color (1 = use vertex color only)
+    snow_params: vec4<f32>,           // x = snow_enabled, y = voxel mode (1 = vertex color tints block material textures)
+    blood_params: vec4<f32>,          // x = origin_x, y = origin_z, z = half_size, w = enabled
 };
 
 @group(0) @binding(0)
@@ -38,22 +38,119 @@ var snow_tex: texture_2d<f32>;
 @group(0) @binding(5)
 var snow_sampler: sampler;
 
+@group(0) @binding(6)
+var blood_tex: texture_2d<f32>;
+
+// Tileable detail layers for voxel blocks: 0 rock, 1 dirt, 2 sand, 3 snow, 4 grass (mid-grey mean)
+@group(0) @binding(7)
+var material_tex: texture_2d_array<f32>;
+
+@group(0) @binding(8)
+var material_sampler: sampler;
+
+const MAX_SHADOW_CASCADES: u32 = 4u;
+
 struct ShadowUniform {
-    light_view_proj: mat4x4<f32>,
+    cascade_view_proj: array<mat4x4<f32>, MAX_SHADOW_CASCADES>,
+    // Far view-space depth of each cascade
+    cascade_splits: vec4<f32>,
     camera_pos: vec3<f32>,
     planet_radius: f32,
-    _pad: vec3<f32>,
+    camera_forward: vec3<f32>,
+    cascade_count: u32,
 }
 
 @group(1) @binding(0)
 var<uniform> shadow: ShadowUniform;
 
 @group(1) @binding(1)
-var shadow_tex: texture_depth_2d;
+var shadow_tex: texture_depth_2d_array;
 
 @group(1) @binding(2)
 var shadow_sampler: sampler_comparison;
 
+struct FogUniform {
+    color_density: vec4<f32>,  // rgb = fog color (biome haze blended toward sky horizon), w = density per meter
+    params: vec4<f32>,         // x = height_falloff, y = base_height, z = far (full fade), w unused
+}
+
+@group(1) @binding(3)
+var<uniform> fog: FogUniform;
+
+const MAX_POINT_LIGHTS: u32 = 16u;
+
+struct PointLight {
+    position_radius: vec4<f32>,  // xyz = world position, w = radius (light reaches zero there)
+    color_intensity: vec4<f32>,  // rgb = color, w = intensity
+}
+
+struct PointLightUniform {
+    lights: array<PointLight, MAX_POINT_LIGHTS>,
+    count: vec4<u32>,            // x = active lights
+}
+
+@group(1) @binding(4)
+var<uniform> point_lights: PointLightUniform;
+
+// Diffuse light from the frame's dynamic point lights (muzzle flashes, explosions, lava) at a
+// surface point. Light positions get the same curvature drop as the geometry they illuminate.
+fn point_light_diffuse(p: vec3<f32>, n: vec3<f32>) -> vec3<f32> {
+    var total = vec3<f32>(0.0);
+    let count = min(point_lights.count.x, MAX_POINT_LIGHTS);
+    for (var i = 0u; i < count; i = i + 1u) {
+        let light = point_lights.lights[i];
+        var light_pos = light.position_radius.xyz;
+        if (camera.planet_radius > 0.0) {
+            let d = light_pos.xz - camera.position.xz;
+            light_pos.y -= dot(d, d) / (2.0 * camera.planet_radius);
+        }
+        let to_light = light_pos - p;
+        let dist = length(to_light);
+        let radius = light.position_radius.w;
+        if (dist >= radius) {
+            continue;
+        }
+        // Smooth window to zero at the radius, inverse-square-ish inside it
+        let x = dist / radius;
+        let window = (1.0 - x * x) * (1.0 - x * x);
+        let falloff = window / (1.0 + dist * dist * 0.25);
+        let n_dot_l = max(dot(n, to_light / max(dist, 1e-4)), 0.0);
+        total += light.color_intensity.rgb * light.color_intensity.w * n_dot_l * falloff;
+    }
+    return total;
+}
+
+// Exponential height fog integrated along the view ray (thick in valleys, thin up high), plus a
+// fade to full fog near the far plane so distant geometry never clips against the sky.
+fn fog_amount(cam: vec3<f32>, p: vec3<f32>) -> f32 {
+    let density = fog.color_density.w;
+    if (density <= 0.0) {
+        return 0.0;
+    }
+    let dist = length(p - cam);
+    let falloff = fog.params.x;
+    let cam_h = max(cam.y - fog.params.y, 0.0);
+    var optical = density * exp(-falloff * cam_h) * dist;
+    let k = clamp(falloff * (p.y - cam.y), -60.0, 60.0);
+    if (abs(k) > 0.001) {
+        optical *= (1.0 - exp(-k)) / k;
+    }
+    let far = fog.params.z;
+    let far_fade = smoothstep(far * 0.75, far * 0.98, dist);
+    return clamp(max(1.0 - exp(-optical), far_fade), 0.0, 1.0);
+}
+
+// Index of the shadow cascade covering this point's view depth, or -1 beyond the last one.
+fn shadow_cascade(world_pos: vec3<f32>) -> i32 {
+    let depth = dot(world_pos - shadow.camera_pos, shadow.camera_forward);
+    for (var i = 0u; i < shadow.cascade_count; i = i + 1u) {
+        if (depth < shadow.cascade_splits[i]) {
+            return i32(i);
+        }
+    }
+    return -1;
+}
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
@@ -794,6 +891,85 @@ fn chunk_edge_blend(world_pos: vec3<f32>, chunk_size: f32) -> f32 {
     return 1.0 - smoothstep(0.0, 0.18, edge_dist);
 }
 
+// ============================================================================
+// VOXEL MATERIALS
+// Each block face carries its material layer in uv.x. The layer is projected
+// triplanar in world space so textures stay continuous across blocks and
+// chunks, then multiplied into the block color (mid-grey = no change).
+// ============================================================================
+
+const MAT_ROCK: i32 = 0;
+const MAT_DIRT: i32 = 1;
+const MAT_SNOW: i32 = 3;
+const MAT_GRASS: i32 = 4;
+// Textures repeat every 4 m
+const MATERIAL_TILE: f32 = 0.25;
+
+fn material_triplanar(world_p: vec3<f32>, n: vec3<f32>, layer: i32) -> vec3<f32> {
+    var w = pow(abs(n), vec3<f32>(4.0));
+    w = w / (w.x + w.y + w.z);
+    let p = world_p * MATERIAL_TILE;
+    let x = textureSample(material_tex, material_sampler, p.zy, layer).rgb;
+    let y = textureSample(material_tex, material_sampler, p.xz, layer).rgb;
+    let z = textureSample(material_tex, material_sampler, p.xy, layer).rgb;
+    return x * w.x + y * w.y + z * w.z;
+}
+
+// Detail multiplier (1 = untouched) for a voxel face. Grass and snow only cover the
+// tops of blocks: steep faces fade to the dirt or rock underneath. Rock gets faint
+// height strata; all detail flattens out with distance to avoid shimmer.
+// Both layers are sampled on every path and blended with select(): the material varies
+// per fragment, and textureSample needs uniform control flow for its derivatives.
+fn voxel_material_detail(world_p: vec3<f32>, n: vec3<f32>, material: i32, view_dist: f32) -> vec3<f32> {
+    let surface = material_triplanar(world_p, n, material);
+    let under_layer = select(MAT_ROCK, MAT_DIRT, material == MAT_GRASS);
+    let under = material_triplanar(world_p, n, under_layer);
+    let side = 1.0 - smoothstep(0.45, 0.8, n.y);
+    let under_amount = select(select(0.0, side * 0.6, material == MAT_SNOW), side, material == MAT_GRASS);
+    var detail = mix(surface, under, under_amount);
+    if (material == MAT_ROCK) {
+        let strata = sin(world_p.y * 1.7 + fbm(world_p * 0.15, 2) * 4.0) * 0.04;
+        detail = detail + vec3<f32>(strata);
+    }
+    let fade = smoothstep(60.0, 220.0, view_dist);
+    return mix(detail * 2.0, vec3<f32>(1.0), fade);
+}
+
+// ============================================================================
+// BLOOD DECALS
+// Kill zones stain the ground: bilinear stain intensity from the blood map
+// (world-space, follows the camera), broken up with noise so splats look wet
+// and irregular instead of round. Only upward-facing surfaces get stained.
+// ============================================================================
+
+fn apply_blood(albedo: vec3<f32>, world_p: vec3<f32>, n: vec3<f32>) -> vec3<f32> {
+    if (terrain.blood_params.w < 0.5) {
+        return albedo;
+    }
+    let half_size = terrain.blood_params.z;
+    let u = (world_p.x - terrain.blood_params.x) / (2.0 * half_size) + 0.5;
+    let v = (world_p.z - terrain.blood_params.y) / (2.0 * half_size) + 0.5;
+    if (u <= 0.0 || u >= 1.0 || v <= 0.0 || v >= 1.0) {
+        return albedo;
+    }
+    let tex_size = 256;
+    let tex_size_f = f32(tex_size);
+    let px = clamp(i32(floor(u * tex_size_f - 0.5)), 0, tex_size - 1);
+    let py = clamp(i32(floor(v * tex_size_f - 0.5)), 0, tex_size - 1);
+    let fx = fract(u * tex_size_f - 0.5);
+    let fy = fract(v * tex_size_f - 0.5);
+    let b00 = textureLoad(blood_tex, vec2<i32>(px, py), 0).r;
+    let b10 = textureLoad(blood_tex, vec2<i32>(min(px + 1, tex_size - 1), py), 0).r;
+    let b01 = textureLoad(blood_tex, vec2<i32>(px, min(py + 1, tex_size - 1)), 0).r;
+    let b11 = textureLoad(blood_tex, vec2<i32>(min(px + 1, tex_size - 1), min(py + 1, tex_size - 1)), 0).r;
+    let stain = mix(mix(b00, b10, fx), mix(b01, b11, fx), fy);
+    let splotch = fbm(world_p * 1.7, 3);
+    let coverage = smoothstep(0.35, 0.6, stain + (splotch - 0.5) * 0.6) * smoothstep(0.5, 0.8, n.y);
+    // Bug ichor: dark green-brown, darker where it pools
+    let ichor = mix(vec3<f32>(0.16, 0.22, 0.06), vec3<f32>(0.07, 0.1, 0.03), clamp(stain, 0.0, 1.0));
+    return mix(albedo, ichor, coverage * 0.85);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let n = normalize(in.world_normal);
@@ -826,10 +1002,12 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let use_uniform = !has_vertex_color || is_earth_palette;
     let biome_tint = select(vertex_rgb, uniform_base, use_uniform);
 
-    // Voxel flat color: use block/biome vertex color as albedo (no procedural texture)
+    // Voxel blocks: block/biome vertex color tints the block's material texture
     let voxel_flat = terrain.snow_params.y > 0.5;
     if (voxel_flat) {
-        let albedo_flat = max(biome_tint, vec3<f32>(0.18, 0.18, 0.20));
+        let material = i32(round(in.uv.x));
+        let material_detail = voxel_material_detail(world_p, n, material, distance(camera.position.xyz, world_p));
+        let albedo_flat = apply_blood(max(biome_tint, vec3<f32>(0.18, 0.18, 0.20)) * material_detail, world_p, n);
         let light_dir = normalize(terrain.sun_direction.xyz);
         let sun_intensity = terrain.sun_direction.w;
         let day_factor = clamp(light_dir.y * 3.0, 0.0, 1.0);
@@ -847,14 +1025,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         let back_face_to_light = n_dot_l < 0.02;
         var shadow_factor: f32 = 1.0;
         if (!back_face_to_light) {
-            let light_clip = shadow.light_view_proj * vec4<f32>(world_p, 1.0);
+            let cascade = shadow_cascade(world_p);
+            let light_clip = shadow.cascade_view_proj[max(cascade, 0)] * vec4<f32>(world_p, 1.0);
             let light_ndc = light_clip.xyz / light_clip.w;
-            let shadow_uv = light_ndc.xy * 0.5 + 0.5;
+            let shadow_uv = vec2<f32>(light_ndc.x * 0.5 + 0.5, 0.5 - light_ndc.y * 0.5);
             let slope_bias = (1.0 - abs(dot(n, light_dir))) * 0.018;
-            let depth_compare = light_ndc.z * 0.5 + 0.5 + 0.006 + slope_bias;
-            let in_bounds = all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0))
+            let depth_compare = light_ndc.z + 0.006 + slope_bias;
+            let in_bounds = cascade >= 0 && all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0))
                 && light_ndc.z >= -0.02 && light_ndc.z <= 1.02;
-            let sampled = textureSampleCompare(shadow_tex, shadow_sampler, shadow_uv, depth_compare);
+            let sampled = textureSampleCompareLevel(shadow_tex, shadow_sampler, shadow_uv, max(cascade, 0), depth_compare);
             shadow_factor = select(1.0, sampled, in_bounds);
             if (n_dot_l < 0.08) {
                 shadow_factor = mix(0.0, shadow_factor, smoothstep(0.02, 0.08, n_dot_l));
@@ -865,19 +1044,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         color_flat *= shadow_factor;
         // Floor so shadowed voxels stay visible (no pitch-black patches)
         color_flat = max(color_flat, albedo_flat * vec3<f32>(0.14, 0.12, 0.16));
+        color_flat += albedo_flat * point_light_diffuse(world_p, n);
         // Fog
         let view_dir = normalize(camera.position.xyz - world_p);
-        let dist = length(camera.position.xyz - world_p);
-        let fog_start = terrain.fog_params.z;
-        let fog_end = terrain.fog_params.w;
-        let fog_amount = clamp((dist - fog_start) / max(fog_end - fog_start, 1.0), 0.0, 0.88);
-        let day_fog = mix(terrain.biome_colors[2].rgb * 0.7 + biome_tint * 0.15 + vec3<f32>(0.08, 0.06, 0.05), terrain.biome_colors[3].rgb * 0.5 + vec3<f32>(0.25, 0.24, 0.22), day_factor);
-        let night_fog = terrain.biome_colors[2].rgb * 0.08 + vec3<f32>(0.012, 0.014, 0.025);
-        let fog_color = mix(night_fog, day_fog, day_factor);
-        color_flat = mix(color_flat, fog_color, fog_amount);
+        color_flat = mix(color_flat, fog.color_density.rgb, fog_amount(camera.position.xyz, world_p));
         color_flat = clamp(color_flat, vec3<f32>(0.0), vec3<f32>(1.0));
         color_flat = pow(color_flat, vec3<f32>(1.0 / 2.2));
-        return vec4<f32>(color_flat, 1.0);
+        return vec4<f32>(min(color_flat, vec3<f32>(1.0)), 1.0);
     }
 
     // Classify biome from color heuristics (for procedural material selection)
@@ -956,6 +1129,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     albedo *= 1.15;
     // Prevent near-black terrain (shadow + dark material): floor albedo so lighting never goes to black
     albedo = max(albedo, vec3<f32>(0.2, 0.2, 0.22));
+    albedo = apply_blood(albedo, world_p, n);
 
     // Erosion streaks on slopes (vertical dark lines from water erosion)
     // Also reduce at chunk edges
@@ -1002,15 +1176,16 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let back_face_to_light = n_dot_l < 0.02;
     var shadow_factor: f32 = 1.0;
     if (!back_face_to_light) {
-        let light_clip = shadow.light_view_proj * vec4<f32>(world_p, 1.0);
+        let cascade = shadow_cascade(world_p);
+        let light_clip = shadow.cascade_view_proj[max(cascade, 0)] * vec4<f32>(world_p, 1.0);
         let light_ndc = light_clip.xyz / light_clip.w;
-        let shadow_uv = light_ndc.xy * 0.5 + 0.5;
+        let shadow_uv = vec2<f32>(light_ndc.x * 0.5 + 0.5, 0.5 - light_ndc.y * 0.5);
         // Slope-scaled bias: voxel/steep faces need more bias to avoid checkered shadow acne (self-shadow)
         let slope_bias = (1.0 - abs(dot(n, light_dir))) * 0.012;
-        let depth_compare = light_ndc.z * 0.5 + 0.5 + 0.005 + slope_bias;
-        let in_bounds = all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0))
+        let depth_compare = light_ndc.z + 0.005 + slope_bias;
+        let in_bounds = cascade >= 0 && all(shadow_uv >= vec2<f32>(0.0)) && all(shadow_uv <= vec2<f32>(1.0))
             && light_ndc.z >= -0.02 && light_ndc.z <= 1.02;
-        let sampled = textureSampleCompare(shadow_tex, shadow_sampler, shadow_uv, depth_compare);
+        let sampled = textureSampleCompareLevel(shadow_tex, shadow_sampler, shadow_uv, max(cascade, 0), depth_compare);
         shadow_factor = select(1.0, sampled, in_bounds);
         // Smooth transition for grazing angles so no hard flicker at terminator
         if (n_dot_l < 0.08) {
@@ -1033,7 +1208,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let spec_final = spec * fresnel * mix(0.05, 0.2, 1.0 - roughness);
 
     // Combine lighting
-    var color = albedo * (ambient_light + diffuse) + vec3<f32>(spec_final) * sun_color;
+    var color = albedo * (ambient_light + diffuse + point_light_diffuse(world_p, n)) + vec3<f32>(spec_final) * sun_color;
 
     // MIRO-style bold rim light: stylized edge glow (day + golden hour)
     let rim = pow(1.0 - max(dot(n, view_dir), 0.0), 3.0);
@@ -1041,29 +1216,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     color += rim_color * rim * (0.25 + golden_hour * 0.2);
 
     // ---- ATMOSPHERIC FOG ----
-    let dist = length(camera.position.xyz - world_p);
-    let fog_start = terrain.fog_params.z;
-    let fog_end = terrain.fog_params.w;
-    let fog_amount = clamp((dist - fog_start) / max(fog_end - fog_start, 1.0), 0.0, 0.88);
-
-    // Fog color: biome-tinted atmosphere (slot 2 = fog tint, slot 3 = ambient tint)
-    // Night fog reflects the warm amber/orange nebula glow from the SST-style space
-    let biome_fog_tint = terrain.biome_colors[2].rgb;
-    let biome_amb_tint = terrain.biome_colors[3].rgb;
-    let day_fog = mix(
-        biome_fog_tint * 0.7 + biome_tint * 0.15 + vec3<f32>(0.08, 0.06, 0.05),
-        biome_amb_tint * 0.5 + vec3<f32>(0.25, 0.24, 0.22),
-        0.4
-    );
-    // Night fog: warm amber glow from nebulae visible overhead
-    let nebula_tint = vec3<f32>(0.025, 0.015, 0.008); // warm amber from space
-    let night_fog = biome_fog_tint * 0.08 + nebula_tint + vec3<f32>(0.012, 0.014, 0.025);
-    let fog_color = mix(night_fog, day_fog, day_factor);
-
-    // Height fog: denser near ground level, scaled by biome fog density
-    let height_fog = exp(-max(world_p.y, 0.0) * 0.004) * 0.18 * day_factor;
-    let total_fog = min(fog_amount + height_fog, 0.92);
-    color = mix(color, fog_color, total_fog);
+    // Height + distance fog from the shared fog uniform (biome haze, weather, sky color)
+    color = mix(color, fog.color_density.rgb, fog_amount(camera.position.xyz, world_p));
 
     // ---- TONE MAPPING (ACES filmic) ----
     let a = 2.51;
@@ -1076,5 +1230,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Gamma correction
     color = pow(color, vec3<f32>(1.0 / 2.2));
 
-    return vec4<f32>(color, 1.0);
+    return vec4<f32>(min(color, vec3<f32>(1.0)), 1.0);
 }
@@ -1,5 +1,5 @@
 // Procedural water shader: lakes, streams, ocean
-// Uses same bind group layout as terrain (camera + terrain uniform for sun/fog)
+// Uses same bind group layouts as terrain (camera + terrain uniform for sun; shadow group for fog)
 
 struct CameraUniform {
     view_proj: mat4x4<f32>,
@@ -14,9 +14,9 @@ struct TerrainUniform {
     biome_colors: array<vec4<f32>, 4>,
     biome_params: vec4<f32>,
     sun_direction: vec4<f32>,
-    fog_params: vec4<f32>,
     deform_params: vec4<f32>,
     snow_params: vec4<f32>,
+    blood_params: vec4<f32>,
 };
 
 @group(0) @binding(0)
@@ -25,6 +25,34 @@ var<uniform> camera: CameraUniform;
 @group(0) @binding(1)
 var<uniform> terrain: TerrainUniform;
 
+struct FogUniform {
+    color_density: vec4<f32>,  // rgb = fog color (biome haze blended toward sky horizon), w = density per meter
+    params: vec4<f32>,         // x = height_falloff, y = base_height, z = far (full fade), w unused
+}
+
+@group(1) @binding(3)
+var<uniform> fog: FogUniform;
+
+// Exponential height fog integrated along the view ray (thick in valleys, thin up high), plus a
+// fade to full fog near the far plane so distant geometry never clips against the sky.
+fn fog_amount(cam: vec3<f32>, p: vec3<f32>) -> f32 {
+    let density = fog.color_density.w;
+    if (density <= 0.0) {
+        return 0.0;
+    }
+    let dist = length(p - cam);
+    let falloff = fog.params.x;
+    let cam_h = max(cam.y - fog.params.y, 0.0);
+    var optical = density * exp(-falloff * cam_h) * dist;
+    let k = clamp(falloff * (p.y - cam.y), -60.0, 60.0);
+    if (abs(k) > 0.001) {
+        optical *= (1.0 - exp(-k)) / k;
+    }
+    let far = fog.params.z;
+    let far_fade = smoothstep(far * 0.75, far * 0.98, dist);
+    return clamp(max(1.0 - exp(-optical), far_fade), 0.0, 1.0);
+}
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
@@ -134,16 +162,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     color = mix(color, vec3<f32>(0.15, 0.25, 0.4), fresnel * 0.5);
 
     // Fog
-    let dist = length(camera.position.xyz - in.world_position);
-    let fog_start = terrain.fog_params.z;
-    let fog_end = terrain.fog_params.w;
-    let fog_amount = clamp((dist - fog_start) / max(fog_end - fog_start, 1.0), 0.0, 0.85);
-    let fog_color = mix(
-        vec3<f32>(0.02, 0.03, 0.05),
-        vec3<f32>(0.15, 0.18, 0.22),
-        day_factor
-    );
-    color = mix(color, fog_color, fog_amount);
+    color = mix(color, fog.color_density.rgb, fog_amount(camera.position.xyz, in.world_position));
 
     // Tone mapping
     color = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
@@ -151,5 +170,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // Minecraft-style transparency: voxel water passes alpha in vertex color (0.4–0.7); smooth water uses 0.95
     let alpha = select(0.95, in.water_color.a, in.water_color.a >= 0.4 && in.water_color.a <= 0.85);
-    return vec4<f32>(color, alpha);
+    return vec4<f32>(min(color, vec3<f32>(1.0)), alpha);
 }
@@ -0,0 +1,94 @@
+//! Cascaded shadow map math: view-depth splits and per-cascade light matrices.
+
+use crate::Camera;
+use glam::{Mat4, Vec3, Vec4};
+
+/// Shadow map array layers; `Renderer::shadow_cascade_count` is clamped to this.
+pub const MAX_SHADOW_CASCADES: usize = 4;
+
+/// Extra depth behind each cascade (toward the sun) so off-screen casters still land in the map.
+const CASTER_MARGIN: f32 = 150.0;
+
+/// Far view-space distance of each cascade, splitting `near..far` into `count` slices.
+/// `lambda` blends uniform (0.0) and logarithmic (1.0) spacing; higher packs more
+/// resolution near the camera. Unused entries repeat `far`.
+pub fn cascade_splits(near: f32, far: f32, count: usize, lambda: f32) -> [f32; MAX_SHADOW_CASCADES] {
+    let count = count.clamp(1, MAX_SHADOW_CASCADES);
+    let near = near.max(0.01);
+    let lambda = lambda.clamp(0.0, 1.0);
+    let mut splits = [far; MAX_SHADOW_CASCADES];
+    for (i, split) in splits.iter_mut().enumerate().take(count) {
+        let p = (i + 1) as f32 / count as f32;
+        let log = near * (far / near).powf(p);
+        let uniform = near + (far - near) * p;
+        *split = lambda * log + (1.0 - lambda) * uniform;
+    }
+    splits
+}
+
+/// Orthographic light view-projection covering the camera frustum slice `near..far`.
+/// Fitted to the slice's bounding sphere and snapped to shadow texels, so the cascade
+/// doesn't shimmer as the camera turns or moves.
+pub fn cascade_view_proj(camera: &Camera, sun_dir: Vec3, near: f32, far: f32, map_size: u32) -> Mat4 {
+    let proj = Mat4::perspective_rh(camera.fov_degrees.to_radians(), camera.aspect, near, far);
+    let inv = (proj * camera.view_matrix()).inverse();
+    let mut corners = [Vec3::ZERO; 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let ndc = Vec4::new(
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -1.0 } else { 1.0 },
+            if i & 4 == 0 { 0.0 } else { 1.0 },
+            1.0,
+        );
+        let world = inv * ndc;
+        *corner = world.truncate() / world.w;
+    }
+    let center = corners.iter().copied().sum::<Vec3>() / 8.0;
+    let radius = corners
+        .iter()
+        .map(|c| c.distance(center))
+        .fold(0.0f32, f32::max)
+        .max(1.0);
+
+    let sun = sun_dir.normalize_or_zero();
+    // Stable up: avoid flip when sun is near vertical
+    let up = if sun.y > 0.98 {
+        Vec3::NEG_Z
+    } else if sun.y < -0.98 {
+        Vec3::Z
+    } else {
+        Vec3::Y
+    };
+    let distance = radius + CASTER_MARGIN;
+    let view = Mat4::look_at_rh(center + sun * distance, center, up);
+
+    // Snap the light-space origin to whole texels
+    let texel = 2.0 * radius / map_size.max(1) as f32;
+    let origin = view.transform_point3(Vec3::ZERO);
+    let snap = Vec3::new(
+        (origin.x / texel).round() * texel - origin.x,
+        (origin.y / texel).round() * texel - origin.y,
+        0.0,
+    );
+    let view = Mat4::from_translation(snap) * view;
+
+    let ortho = Mat4::orthographic_rh(-radius, radius, -radius, radius, 0.1, distance + radius);
+    ortho * view
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_increase_and_end_at_far() {
+        for lambda in [0.0, 0.5, 1.0] {
+            let splits = cascade_splits(0.1, 300.0, 4, lambda);
+            assert!(splits.windows(2).all(|w| w[0] < w[1]));
+            assert!((splits[3] - 300.0).abs() < 1e-3);
+        }
+        let uniform = cascade_splits(0.0, 100.0, 2, 0.0);
+        assert!((uniform[0] - 50.0).abs() < 0.1);
+        assert_eq!(uniform[2], 100.0);
+    }
+}
@@ -112,6 +112,93 @@ impl Texture {
         Ok(Self { _texture: texture, view, sampler })
     }
 
+    /// Create a color texture (sRGB, with alpha) from RGBA8 pixels, clamped and linearly filtered.
+    pub fn from_rgba(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        label: &str,
+    ) -> Self {
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            rgba,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self { _texture: texture, view, sampler }
+    }
+
+    /// Create a tiling tangent-space normal map from RGBA8 pixels (linear, not sRGB).
+    pub fn normal_map(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        label: &str,
+    ) -> Self {
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            rgba,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self { _texture: texture, view, sampler }
+    }
+
+    /// 1x1 flat normal map (straight up in tangent space): no change to the surface normal.
+    pub fn flat_normal_pixel(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        Self::normal_map(device, queue, 1, 1, &[128, 128, 255, 255], "Flat Normal")
+    }
+
     /// Create a simple 1x1 white texture (placeholder).
     pub fn white_pixel(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
         let size = wgpu::Extent3d {
@@ -149,3 +236,10 @@ impl Texture {
 
     pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 }
+
+/// Textures for one kind of surface in the main pipeline (bind group 1): albedo and normal map.
+/// Create with `Renderer::create_material`, draw with `Renderer::draw_instanced_with_material`.
+pub struct Material {
+    pub(crate) bind_group: wgpu::BindGroup,
+    pub(crate) _normal_map: Texture,
+}
@@ -0,0 +1,101 @@
+//! Back-to-front ordering for transparent draws (water surfaces, blended particles).
+
+use crate::{InstanceData, Mesh, ParticleInstance};
+use glam::Vec3;
+
+/// One transparent draw, positioned for depth sorting.
+pub enum TransparentDraw<'a> {
+    /// A whole water mesh (one terrain chunk), drawn with the water pipeline.
+    Water(&'a Mesh),
+    /// A single instance of a mesh, drawn with the transparent instanced pipeline.
+    Instance(&'a Mesh, InstanceData),
+}
+
+/// Transparent draws collected over a frame and submitted together by
+/// `Renderer::render_transparent`, farthest first.
+#[derive(Default)]
+pub struct TransparentQueue<'a> {
+    positions: Vec<Vec3>,
+    draws: Vec<TransparentDraw<'a>>,
+}
+
+impl<'a> TransparentQueue<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a water mesh; `center` is used as its sort position.
+    pub fn push_water(&mut self, mesh: &'a Mesh, center: Vec3) {
+        self.positions.push(center);
+        self.draws.push(TransparentDraw::Water(mesh));
+    }
+
+    /// Queue instances of `mesh`, each sorted by its own model translation.
+    pub fn push_instances(&mut self, mesh: &'a Mesh, instances: &[InstanceData]) {
+        for instance in instances {
+            self.positions.push(Vec3::from_slice(&instance.model[3][..3]));
+            self.draws.push(TransparentDraw::Instance(mesh, *instance));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.draws.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    /// Draws ordered farthest-first along the camera's forward axis.
+    pub fn into_sorted(self, camera_pos: Vec3, camera_forward: Vec3) -> Vec<TransparentDraw<'a>> {
+        let order = back_to_front(&self.positions, camera_pos, camera_forward);
+        let mut draws: Vec<Option<TransparentDraw<'a>>> = self.draws.into_iter().map(Some).collect();
+        order.into_iter().filter_map(|i| draws[i].take()).collect()
+    }
+}
+
+/// Indices of `positions` sorted by decreasing camera-space depth. Ties keep submission
+/// order, so consecutive instances of the same mesh can still be batched.
+pub fn back_to_front(positions: &[Vec3], camera_pos: Vec3, camera_forward: Vec3) -> Vec<usize> {
+    let depths: Vec<f32> = positions
+        .iter()
+        .map(|p| (*p - camera_pos).dot(camera_forward))
+        .collect();
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| depths[b].total_cmp(&depths[a]));
+    order
+}
+
+/// Reorder alpha-blended billboards farthest-first so overlapping smoke and fire composite
+/// correctly in `Renderer::draw_particles`.
+pub fn sort_particles_back_to_front(particles: &mut Vec<ParticleInstance>, camera_pos: Vec3, camera_forward: Vec3) {
+    let positions: Vec<Vec3> = particles.iter().map(|p| Vec3::from(p.position)).collect();
+    let order = back_to_front(&positions, camera_pos, camera_forward);
+    *particles = order.into_iter().map(|i| particles[i]).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn farthest_first_along_view_axis() {
+        let positions = [
+            Vec3::new(0.0, 0.0, -5.0),
+            Vec3::new(10.0, 0.0, -20.0),
+            Vec3::new(0.0, 0.0, 3.0),
+            Vec3::new(-4.0, 0.0, -5.0),
+        ];
+        let order = back_to_front(&positions, Vec3::ZERO, Vec3::NEG_Z);
+        assert_eq!(order, vec![1, 0, 3, 2]);
+    }
+
+    #[test]
+    fn particles_draw_farthest_first() {
+        let smoke = |z: f32| ParticleInstance::new([0.0, 0.0, z], [1.0, 1.0], [0.5, 0.5, 0.5, 0.6]);
+        let mut particles = vec![smoke(-2.0), smoke(-30.0), smoke(-8.0)];
+        sort_particles_back_to_front(&mut particles, Vec3::ZERO, Vec3::NEG_Z);
+        let depths: Vec<f32> = particles.iter().map(|p| p.position[2]).collect();
+        assert_eq!(depths, vec![-30.0, -8.0, -2.0]);
+    }
+}
@@ -2,7 +2,7 @@
 
 use bytemuck::{Pod, Zeroable};
 
-/// Standard vertex with position, normal, UV coordinates, and color.
+/// Standard vertex with position, normal, UV coordinates, color and tangent.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct Vertex {
@@ -10,6 +10,9 @@ pub struct Vertex {
     pub normal: [f32; 3],
     pub tex_coords: [f32; 2],
     pub color: [f32; 4],
+    /// xyz = direction of +u on the surface, w = bitangent sign. Filled by `Mesh::from_data`;
+    /// zero means no tangent frame (normal maps are ignored).
+    pub tangent: [f32; 4],
 }
 
 impl Vertex {
@@ -19,11 +22,12 @@ impl Vertex {
             normal, 
             tex_coords,
             color: [1.0, 1.0, 1.0, 1.0],
+            tangent: [0.0; 4],
         }
     }
 
     pub fn with_color(position: [f32; 3], normal: [f32; 3], tex_coords: [f32; 2], color: [f32; 4]) -> Self {
-        Self { position, normal, tex_coords, color }
+        Self { position, normal, tex_coords, color, tangent: [0.0; 4] }
     }
 
     pub fn layout() -> wgpu::VertexBufferLayout<'static> {
@@ -49,6 +53,12 @@ impl Vertex {
                     shader_location: 2,
                     format: wgpu::VertexFormat::Float32x2,
                 },
+                // Tangent (after color; 3..8 are taken by the instance attributes)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 12]>() as wgpu::BufferAddress,
+                    shader_location: 9,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
             ],
         }
     }
@@ -93,11 +103,33 @@ pub struct InstanceData {
     pub model: [[f32; 4]; 4],
     /// Color tint
     pub color: [f32; 4],
+    /// Emissive strength: 0 = lit normally, > 0 = unlit and pushed into HDR range for bloom
+    pub emissive: f32,
+    /// Surface roughness 0..1 (0 = mirror-sharp highlight, 1 = fully diffuse)
+    pub roughness: f32,
+    /// 0 = dielectric (paint, chitin, cloth), 1 = bare metal (highlights and reflections take the color)
+    pub metallic: f32,
 }
 
+/// Roughness of instances that don't set a material: soft, dull highlights.
+pub const DEFAULT_ROUGHNESS: f32 = 0.7;
+
 impl InstanceData {
     pub fn new(model: [[f32; 4]; 4], color: [f32; 4]) -> Self {
-        Self { model, color }
+        Self { model, color, emissive: 0.0, roughness: DEFAULT_ROUGHNESS, metallic: 0.0 }
+    }
+
+    /// Per-material surface constants (see `roughness` and `metallic`).
+    pub fn with_material(mut self, roughness: f32, metallic: f32) -> Self {
+        self.roughness = roughness;
+        self.metallic = metallic;
+        self
+    }
+
+    /// Mark this instance as emissive (glow meshes, lights, flashes) so it feeds bloom.
+    pub fn with_emissive(mut self, emissive: f32) -> Self {
+        self.emissive = emissive;
+        self
     }
 
     pub fn layout() -> wgpu::VertexBufferLayout<'static> {
@@ -135,6 +167,18 @@ impl InstanceData {
                     shader_location: 7,
                     format: wgpu::VertexFormat::Float32x4,
                 },
+                // Emissive strength
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 20]>() as wgpu::BufferAddress,
+                    shader_location: 8,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // Roughness, metallic
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 21]>() as wgpu::BufferAddress,
+                    shader_location: 10,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
             ],
         }
     }
@@ -145,6 +189,9 @@ impl Default for InstanceData {
         Self {
             model: glam::Mat4::IDENTITY.to_cols_array_2d(),
             color: [1.0, 1.0, 1.0, 1.0],
+            emissive: 0.0,
+            roughness: DEFAULT_ROUGHNESS,
+            metallic: 0.0,
         }
     }
 }
@@ -161,7 +208,7 @@ pub struct CelestialBodyInstance {
     pub color: [f32; 4],
     /// Direction to the star (for diffuse lighting of planets). w = has_atmosphere flag.
     pub star_direction: [f32; 4],
-    /// Atmosphere color (rgb). w = ring_system flag.
+    /// Atmosphere color (rgb). w = 1.0 if the body has a ring.
     pub atmosphere_color: [f32; 4],
 }
 
@@ -206,6 +253,78 @@ impl CelestialBodyInstance {
     }
 }
 
+/// One camera-facing billboard for `Renderer::draw_particles`. The vertex shader turns the
+/// quad toward the camera, so the CPU only fills in position, size and color.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct ParticleInstance {
+    pub position: [f32; 3],
+    /// Spin in the view plane (radians).
+    pub rotation: f32,
+    /// Quad width and height in world units.
+    pub size: [f32; 2],
+    /// 1.0 = only turn about world Y (rain streaks stay vertical), 0.0 = fully face the camera.
+    pub upright: f32,
+    /// RGBA; channels above 1.5 are emissive and feed bloom.
+    pub color: [f32; 4],
+}
+
+impl ParticleInstance {
+    pub fn new(position: [f32; 3], size: [f32; 2], color: [f32; 4]) -> Self {
+        Self { position, rotation: 0.0, size, upright: 0.0, color }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Keep the quad vertical, turning only about world Y.
+    pub fn upright(mut self) -> Self {
+        self.upright = 1.0;
+        self
+    }
+
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<ParticleInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                // position (vec3)
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                // rotation (f32)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // size (vec2)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                    shader_location: 5,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                // upright (f32)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 6]>() as wgpu::BufferAddress,
+                    shader_location: 6,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // color (vec4)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 7]>() as wgpu::BufferAddress,
+                    shader_location: 7,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+            ],
+        }
+    }
+}
+
 /// Vertex for screen-space text / UI overlay.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -244,6 +363,70 @@ impl OverlayVertex {
     }
 }
 
+/// Horizontal alignment of laid-out text relative to its anchor x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    /// Anchor is the left edge of every line.
+    #[default]
+    Left,
+    /// Anchor is the center of every line.
+    Center,
+    /// Anchor is the right edge of every line.
+    Right,
+}
+
+/// How a block of overlay text is laid out: glyph scale, optional wrap width and alignment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayout {
+    /// Glyph scale (1.0 = 6x8 screen pixels per glyph).
+    pub scale: f32,
+    /// Wrap lines at word boundaries so none is wider than this many pixels. `None` = no wrapping.
+    pub max_width: Option<f32>,
+    pub align: TextAlign,
+    /// Line advance as a multiple of the glyph height.
+    pub line_spacing: f32,
+}
+
+impl TextLayout {
+    pub fn new(scale: f32) -> Self {
+        Self { scale, ..Default::default() }
+    }
+
+    pub fn wrap(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    fn line_height(&self) -> f32 {
+        GLYPH_PX_H * self.scale * self.line_spacing
+    }
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            max_width: None,
+            align: TextAlign::Left,
+            line_spacing: 1.25,
+        }
+    }
+}
+
+/// Pixel-space bounding box of a laid-out text block.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextBounds {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
 /// Helper to build screen-space overlay text geometry.
 /// Generates OverlayVertex quads for each character + optional background rects.
 pub struct OverlayTextBuilder {
@@ -322,6 +505,124 @@ impl OverlayTextBuilder {
         }
     }
 
+    /// Split `text` into the lines it occupies under `layout`: explicit newlines are kept, and with a
+    /// wrap width lines break at spaces. Words wider than the wrap width are split mid-word.
+    pub fn wrap_lines(text: &str, layout: &TextLayout) -> Vec<String> {
+        let max_chars = layout
+            .max_width
+            .map(|w| ((w / (GLYPH_PX_W * layout.scale)).floor() as usize).max(1));
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let Some(max_chars) = max_chars else {
+                lines.push(paragraph.to_string());
+                continue;
+            };
+            let mut line = String::new();
+            let mut line_len = 0;
+            for word in paragraph.split_whitespace() {
+                let mut word: Vec<char> = word.chars().collect();
+                if line_len > 0 && line_len + 1 + word.len() <= max_chars {
+                    line.push(' ');
+                    line.extend(&word);
+                    line_len += 1 + word.len();
+                    continue;
+                }
+                if line_len > 0 {
+                    lines.push(std::mem::take(&mut line));
+                }
+                while word.len() > max_chars {
+                    let rest = word.split_off(max_chars);
+                    lines.push(word.into_iter().collect());
+                    word = rest;
+                }
+                line_len = word.len();
+                line.extend(word);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Size in pixels (width, height) that `text` would occupy under `layout`, without drawing it.
+    pub fn measure_text(text: &str, layout: &TextLayout) -> (f32, f32) {
+        let lines = Self::wrap_lines(text, layout);
+        let gw = GLYPH_PX_W * layout.scale;
+        let w = lines
+            .iter()
+            .map(|l| l.chars().count() as f32 * gw)
+            .fold(0.0, f32::max);
+        (w, Self::block_height(lines.len(), layout))
+    }
+
+    fn block_height(line_count: usize, layout: &TextLayout) -> f32 {
+        if line_count == 0 {
+            return 0.0;
+        }
+        (line_count - 1) as f32 * layout.line_height() + GLYPH_PX_H * layout.scale
+    }
+
+    /// Add a (possibly multi-line) block of text. `x` is the left edge, center or right edge of
+    /// each line depending on `layout.align`; `y` is the top of the first line.
+    /// Returns the bounding box of the drawn text.
+    pub fn add_text_layout(
+        &mut self,
+        x: f32,
+        y: f32,
+        text: &str,
+        layout: &TextLayout,
+        color: [f32; 4],
+    ) -> TextBounds {
+        let gw = GLYPH_PX_W * layout.scale;
+        let lines = Self::wrap_lines(text, layout);
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        for (i, line) in lines.iter().enumerate() {
+            let w = line.chars().count() as f32 * gw;
+            let lx = match layout.align {
+                TextAlign::Left => x,
+                TextAlign::Center => x - w * 0.5,
+                TextAlign::Right => x - w,
+            };
+            min_x = min_x.min(lx);
+            max_x = max_x.max(lx + w);
+            self.add_text(lx, y + i as f32 * layout.line_height(), line, layout.scale, color);
+        }
+        TextBounds {
+            x: min_x,
+            y,
+            w: (max_x - min_x).max(0.0),
+            h: Self::block_height(lines.len(), layout),
+        }
+    }
+
+    /// Like `add_text_layout`, with a padded background rect behind the whole block.
+    /// Returns the height of the block including padding (the Y offset for the next block).
+    pub fn add_text_layout_with_bg(
+        &mut self,
+        x: f32,
+        y: f32,
+        text: &str,
+        layout: &TextLayout,
+        text_color: [f32; 4],
+        bg_color: [f32; 4],
+    ) -> f32 {
+        let padding = 2.0 * layout.scale;
+        let (w, h) = Self::measure_text(text, layout);
+        let left = match layout.align {
+            TextAlign::Left => x,
+            TextAlign::Center => x - w * 0.5 - padding,
+            TextAlign::Right => x - w - padding * 2.0,
+        };
+        self.add_rect(left, y, w + padding * 2.0, h + padding * 2.0, bg_color);
+        let anchor = match layout.align {
+            TextAlign::Left => x + padding,
+            TextAlign::Center => x,
+            TextAlign::Right => x - padding,
+        };
+        self.add_text_layout(anchor, y + padding, text, layout, text_color);
+        h + padding * 2.0
+    }
+
     /// Add text with a dark background behind it. Returns the Y offset for the next line.
     pub fn add_text_with_bg(
         &mut self,
@@ -478,3 +779,38 @@ const FONT_5X7: [[u8; 7]; 96] = [
     [0x1F,0x1F,0x1F,0x1F,0x1F,0x1F,0x1F], // 127 DEL (solid block - useful for bg)
 ];
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_at_words_and_splits_long_words() {
+        // 10 glyphs per line at scale 1
+        let layout = TextLayout::new(1.0).wrap(60.0);
+        let lines = OverlayTextBuilder::wrap_lines("Federation bulletin: citizenship is everything", &layout);
+        assert_eq!(lines, ["Federation", "bulletin:", "citizenshi", "p is", "everything"]);
+        assert!(lines.iter().all(|l| l.chars().count() <= 10));
+
+        let lines = OverlayTextBuilder::wrap_lines("one two\nthree", &TextLayout::new(1.0));
+        assert_eq!(lines, ["one two", "three"]);
+    }
+
+    #[test]
+    fn measure_matches_drawn_bounds_for_each_alignment() {
+        let text = "Service guarantees citizenship. Would you like to know more?";
+        for align in [TextAlign::Left, TextAlign::Center, TextAlign::Right] {
+            let layout = TextLayout::new(2.0).wrap(200.0).align(align);
+            let (w, h) = OverlayTextBuilder::measure_text(text, &layout);
+            let mut tb = OverlayTextBuilder::new(800.0, 600.0);
+            let b = tb.add_text_layout(400.0, 100.0, text, &layout, [1.0; 4]);
+            assert!(w <= 200.0);
+            assert_eq!((b.w, b.h), (w, h));
+            let expected_x = match align {
+                TextAlign::Left => 400.0,
+                TextAlign::Center => 400.0 - w * 0.5,
+                TextAlign::Right => 400.0 - w,
+            };
+            assert_eq!(b.x, expected_x);
+        }
+    }
+}